@@ -0,0 +1,144 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! RPC method reporting, for the recent collations of a collator, when each of them was built,
+//! announced to the validators, fetched, seconded and finally backed.
+//!
+//! The timelines are kept by the collator side of the collator protocol, running in the relay
+//! chain node embedded in the collator.
+
+#![warn(missing_docs)]
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use futures::channel::oneshot;
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::ErrorObject,
+};
+use polkadot_node_subsystem::messages::CollatorProtocolMessage;
+use polkadot_overseer::Handle as OverseerHandle;
+use polkadot_primitives::Hash;
+use sc_rpc_api::DenyUnsafe;
+use serde::{Deserialize, Serialize};
+
+/// The timeline of a collation.
+///
+/// All the timestamps are in milliseconds since the unix epoch, as observed by the collator.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CollationTimeline {
+	/// The hash of the candidate.
+	pub candidate_hash: Hash,
+	/// The relay chain block the collation was built on.
+	pub relay_parent: Hash,
+	/// The hash of the head data produced by the candidate.
+	pub para_head: Hash,
+	/// When the collation was built.
+	pub built: u64,
+	/// When the collation was first advertised to a validator.
+	pub announced: Option<u64>,
+	/// When the collation was first fetched by a validator.
+	pub fetched: Option<u64>,
+	/// When a validator first reported having seconded the collation.
+	pub seconded: Option<u64>,
+	/// When the candidate was first seen backed.
+	pub backed: Option<u64>,
+	/// The relay chain block the candidate was backed in.
+	pub backed_in: Option<Hash>,
+}
+
+impl From<polkadot_node_primitives::CollationTimeline> for CollationTimeline {
+	fn from(timeline: polkadot_node_primitives::CollationTimeline) -> Self {
+		Self {
+			candidate_hash: timeline.candidate_hash.0,
+			relay_parent: timeline.relay_parent,
+			para_head: timeline.para_head,
+			built: unix_millis(timeline.built),
+			announced: timeline.announced.map(unix_millis),
+			fetched: timeline.fetched.map(unix_millis),
+			seconded: timeline.seconded.map(unix_millis),
+			backed: timeline.backed.map(|(time, _)| unix_millis(time)),
+			backed_in: timeline.backed.map(|(_, block_hash)| block_hash),
+		}
+	}
+}
+
+fn unix_millis(time: SystemTime) -> u64 {
+	time.duration_since(UNIX_EPOCH)
+		.map(|d| d.as_millis() as u64)
+		.unwrap_or_default()
+}
+
+/// Collation latency RPC methods.
+#[rpc(client, server)]
+pub trait CollationLatencyApi {
+	/// Returns the timelines of the recent collations, oldest first.
+	#[method(name = "collator_collationTimelines")]
+	async fn collation_timelines(&self) -> RpcResult<Vec<CollationTimeline>>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The collator protocol did not answer.
+	Unavailable,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::Unavailable => 1,
+		}
+	}
+}
+
+/// Provides the timelines of the recent collations.
+pub struct CollationLatency {
+	overseer_handle: OverseerHandle,
+	deny_unsafe: DenyUnsafe,
+}
+
+impl CollationLatency {
+	/// Create new `CollationLatency` with the given reference to the overseer of the relay chain
+	/// node.
+	pub fn new(overseer_handle: OverseerHandle, deny_unsafe: DenyUnsafe) -> Self {
+		Self { overseer_handle, deny_unsafe }
+	}
+}
+
+#[async_trait]
+impl CollationLatencyApiServer for CollationLatency {
+	async fn collation_timelines(&self) -> RpcResult<Vec<CollationTimeline>> {
+		self.deny_unsafe.check_if_safe()?;
+
+		let (tx, rx) = oneshot::channel();
+		self.overseer_handle
+			.clone()
+			.send_msg(CollatorProtocolMessage::GetCollationTimelines(tx), "CollationLatencyRpc")
+			.await;
+
+		let timelines = rx.await.map_err(|_| {
+			ErrorObject::owned(
+				Error::Unavailable.into(),
+				"The collator protocol is not running.",
+				None::<()>,
+			)
+		})?;
+
+		Ok(timelines.into_iter().map(Into::into).collect())
+	}
+}
@@ -47,7 +47,9 @@ use sp_application_crypto::AppPublic;
 use sp_consensus::BlockOrigin;
 use sp_consensus_aura::{AuraApi, Slot, SlotDuration};
 use sp_core::crypto::Pair;
-use sp_inherents::{CreateInherentDataProviders, InherentData, InherentDataProvider};
+use sp_inherents::{
+	CreateInherentDataProviders, HealthSeverity, InherentData, InherentDataProvider,
+};
 use sp_keystore::KeystorePtr;
 use sp_runtime::{
 	generic::Digest,
@@ -140,14 +142,30 @@ where
 				),
 		};
 
-		let mut other_inherent_data = self
+		let inherent_data_providers = self
 			.create_inherent_data_providers
 			.create_inherent_data_providers(parent_hash, ())
 			.map_err(|e| e as Box<dyn Error + Send + Sync + 'static>)
-			.await?
-			.create_inherent_data()
-			.await
-			.map_err(Box::new)?;
+			.await?;
+
+		let mut not_ready = Vec::new();
+		for issue in inherent_data_providers.check_health().await {
+			match issue.severity {
+				HealthSeverity::Stale => tracing::warn!(
+					target: crate::LOG_TARGET,
+					?parent_hash,
+					%issue,
+					"Inherent data is stale",
+				),
+				HealthSeverity::NotReady => not_ready.push(issue.to_string()),
+			}
+		}
+		if !not_ready.is_empty() {
+			return Err(format!("Inherent data is not ready: {}", not_ready.join(", ")).into())
+		}
+
+		let mut other_inherent_data =
+			inherent_data_providers.create_inherent_data().await.map_err(Box::new)?;
 
 		if let Some(timestamp) = timestamp.into() {
 			other_inherent_data.replace_data(sp_timestamp::INHERENT_IDENTIFIER, &timestamp);
@@ -36,6 +36,7 @@ async fn collect_relay_storage_proof(
 	relay_chain_interface: &impl RelayChainInterface,
 	para_id: ParaId,
 	relay_parent: PHash,
+	additional_keys: Vec<Vec<u8>>,
 ) -> Option<sp_state_machine::StorageProof> {
 	use relay_chain::well_known_keys as relay_well_known_keys;
 
@@ -121,6 +122,7 @@ async fn collect_relay_storage_proof(
 	relevant_keys.extend(egress_channels.into_iter().map(|recipient| {
 		relay_well_known_keys::hrmp_channels(HrmpChannelId { sender: para_id, recipient })
 	}));
+	relevant_keys.extend(additional_keys);
 
 	relay_chain_interface
 		.prove_read(relay_parent, &relevant_keys)
@@ -148,8 +150,37 @@ impl ParachainInherentDataProvider {
 		validation_data: &PersistedValidationData,
 		para_id: ParaId,
 	) -> Option<ParachainInherentData> {
-		let relay_chain_state =
-			collect_relay_storage_proof(relay_chain_interface, para_id, relay_parent).await?;
+		Self::create_at_with_additional_keys(
+			relay_parent,
+			relay_chain_interface,
+			validation_data,
+			para_id,
+			Vec::new(),
+		)
+		.await
+	}
+
+	/// Create the [`ParachainInherentData`] at the given `relay_parent`, including the given
+	/// relay chain storage keys in the relay chain state proof.
+	///
+	/// The additional keys are the keys read by the runtime from the relay chain state proof, e.g.
+	/// the keys registered in `cumulus-pallet-relay-state-query`.
+	///
+	/// Returns `None` if the creation failed.
+	pub async fn create_at_with_additional_keys(
+		relay_parent: PHash,
+		relay_chain_interface: &impl RelayChainInterface,
+		validation_data: &PersistedValidationData,
+		para_id: ParaId,
+		additional_keys: Vec<Vec<u8>>,
+	) -> Option<ParachainInherentData> {
+		let relay_chain_state = collect_relay_storage_proof(
+			relay_chain_interface,
+			para_id,
+			relay_parent,
+			additional_keys,
+		)
+		.await?;
 
 		let downward_messages = relay_chain_interface
 			.retrieve_dmq_contents(para_id, relay_parent)
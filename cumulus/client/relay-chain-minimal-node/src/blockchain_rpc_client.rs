@@ -462,6 +462,18 @@ impl RuntimeApiSubsystemClient for BlockChainRpcClient {
 			.parachain_host_candidates_pending_availability(at, para_id)
 			.await?)
 	}
+
+	async fn submit_candidate_stats(
+		&self,
+		at: Hash,
+		stmt: polkadot_primitives::vstaging::CandidateStatsStatement,
+		signature: polkadot_primitives::ValidatorSignature,
+	) -> Result<(), ApiError> {
+		Ok(self
+			.rpc_client
+			.parachain_host_submit_candidate_stats(at, stmt, signature)
+			.await?)
+	}
 }
 
 #[async_trait::async_trait]
@@ -32,9 +32,9 @@ use parity_scale_codec::{Decode, Encode};
 use cumulus_primitives_core::{
 	relay_chain::{
 		async_backing::{AsyncBackingParams, BackingState},
-		slashing, ApprovalVotingParams, BlockNumber, CandidateCommitments, CandidateEvent,
-		CandidateHash, CommittedCandidateReceipt, CoreIndex, CoreState, DisputeState,
-		ExecutorParams, GroupRotationInfo, Hash as RelayHash, Header as RelayHeader,
+		slashing, vstaging, ApprovalVotingParams, BlockNumber, CandidateCommitments,
+		CandidateEvent, CandidateHash, CommittedCandidateReceipt, CoreIndex, CoreState,
+		DisputeState, ExecutorParams, GroupRotationInfo, Hash as RelayHash, Header as RelayHeader,
 		InboundHrmpMessage, NodeFeatures, OccupiedCoreAssumption, PvfCheckStatement,
 		ScrapedOnChainVotes, SessionIndex, SessionInfo, ValidationCode, ValidationCodeHash,
 		ValidatorId, ValidatorIndex, ValidatorSignature,
@@ -669,6 +669,21 @@ impl RelayChainRpcClient {
 		.await
 	}
 
+	/// Submits the statistics of a candidate into the transaction pool.
+	pub async fn parachain_host_submit_candidate_stats(
+		&self,
+		at: RelayHash,
+		stmt: vstaging::CandidateStatsStatement,
+		signature: ValidatorSignature,
+	) -> Result<(), RelayChainError> {
+		self.call_remote_runtime_function(
+			"ParachainHost_submit_candidate_stats",
+			at,
+			Some((stmt, signature)),
+		)
+		.await
+	}
+
 	pub async fn validation_code_hash(
 		&self,
 		at: RelayHash,
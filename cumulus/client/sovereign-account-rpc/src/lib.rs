@@ -0,0 +1,408 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! RPC methods for resolving the local sovereign account of a sibling parachain, the relay chain
+//! or any other XCM `Location`, and for inspecting its nonce and asset holdings.
+//!
+//! The account derivation is delegated to the runtime through
+//! [`LocationToAccountApi`], so the result always matches what the runtime's XCM configuration
+//! would use.
+
+#![warn(missing_docs)]
+
+use std::sync::Arc;
+
+use codec::{Codec, Decode, Encode};
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::{error::ErrorObject, ErrorObjectOwned},
+};
+use serde::{Deserialize, Serialize};
+use sp_api::{ApiExt, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_runtime::traits::Block as BlockT;
+use xcm::{
+	latest::{Junction::Parachain, Location},
+	VersionedLocation,
+};
+
+pub use assets_common::runtime_api::{FungiblesApi, LocationToAccountApi};
+pub use frame_system_rpc_runtime_api::AccountNonceApi;
+
+/// The consensus system whose sovereign account should be resolved.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SovereignLocation {
+	/// The relay chain this parachain is connected to.
+	Relay,
+	/// A sibling parachain, identified by its para id.
+	Sibling(u32),
+	/// Any other location, as a SCALE-encoded `VersionedLocation`.
+	Location(Bytes),
+}
+
+impl SovereignLocation {
+	fn into_versioned(self) -> Result<VersionedLocation, codec::Error> {
+		Ok(match self {
+			Self::Relay => Location::parent().into(),
+			Self::Sibling(para_id) => Location::new(1, [Parachain(para_id)]).into(),
+			Self::Location(encoded) => VersionedLocation::decode(&mut &encoded[..])?,
+		})
+	}
+}
+
+/// The state of a sovereign account.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SovereignAccountInfo<AccountId, Nonce> {
+	/// The local sovereign account.
+	pub account: AccountId,
+	/// The current nonce of the account, as reported by the runtime.
+	pub nonce: Nonce,
+	/// SCALE-encoded `VersionedAssets` held by the account.
+	///
+	/// `None` if the runtime does not expose the account holdings.
+	pub assets: Option<Bytes>,
+}
+
+/// Sovereign account RPC methods.
+#[rpc(client, server)]
+pub trait SovereignAccountApi<BlockHash, AccountId, Nonce> {
+	/// Returns the local sovereign account of the given location.
+	#[method(name = "xcm_sovereignAccount")]
+	fn sovereign_account(
+		&self,
+		location: SovereignLocation,
+		at: Option<BlockHash>,
+	) -> RpcResult<AccountId>;
+
+	/// Returns the local sovereign account of the given location together with its nonce and
+	/// asset holdings.
+	#[method(name = "xcm_sovereignAccountInfo")]
+	fn sovereign_account_info(
+		&self,
+		location: SovereignLocation,
+		at: Option<BlockHash>,
+	) -> RpcResult<SovereignAccountInfo<AccountId, Nonce>>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The location was not decodable.
+	DecodeError,
+	/// The call to runtime failed.
+	RuntimeError,
+	/// The runtime does not derive a local account for the given location.
+	Unsupported,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::DecodeError => 2,
+			Error::Unsupported => 3,
+		}
+	}
+}
+
+fn map_err(error: impl ToString, desc: &'static str) -> ErrorObjectOwned {
+	ErrorObject::owned(Error::RuntimeError.into(), desc, Some(error.to_string()))
+}
+
+/// Provides RPC methods to resolve and inspect sovereign accounts.
+pub struct SovereignAccount<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> SovereignAccount<C, B> {
+	/// Creates a new instance of the sovereign account RPC.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block> SovereignAccount<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block>,
+{
+	fn resolve<AccountId: Codec>(
+		&self,
+		location: SovereignLocation,
+		at: Block::Hash,
+	) -> RpcResult<AccountId>
+	where
+		C::Api: LocationToAccountApi<Block, AccountId>,
+	{
+		let location = location.into_versioned().map_err(|e| {
+			ErrorObject::owned(
+				Error::DecodeError.into(),
+				"Unable to decode location.",
+				Some(e.to_string()),
+			)
+		})?;
+
+		self.client
+			.runtime_api()
+			.convert_location(at, location)
+			.map_err(|e| map_err(e, "Unable to convert location."))?
+			.map_err(|e| {
+				ErrorObject::owned(
+					Error::Unsupported.into(),
+					"Location has no local sovereign account.",
+					Some(format!("{:?}", e)),
+				)
+			})
+	}
+}
+
+impl<C, Block, AccountId, Nonce> SovereignAccountApiServer<Block::Hash, AccountId, Nonce>
+	for SovereignAccount<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: LocationToAccountApi<Block, AccountId>,
+	C::Api: AccountNonceApi<Block, AccountId, Nonce>,
+	C::Api: FungiblesApi<Block, AccountId>,
+	AccountId: Codec + Clone + Serialize + Send + Sync + 'static,
+	Nonce: Codec + Serialize + Send + Sync + 'static,
+{
+	fn sovereign_account(
+		&self,
+		location: SovereignLocation,
+		at: Option<Block::Hash>,
+	) -> RpcResult<AccountId> {
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.resolve(location, at_hash)
+	}
+
+	fn sovereign_account_info(
+		&self,
+		location: SovereignLocation,
+		at: Option<Block::Hash>,
+	) -> RpcResult<SovereignAccountInfo<AccountId, Nonce>> {
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+		let account = self.resolve(location, at_hash)?;
+
+		let api = self.client.runtime_api();
+		let nonce = api
+			.account_nonce(at_hash, account.clone())
+			.map_err(|e| map_err(e, "Unable to query nonce."))?;
+
+		// `query_account_balances` only returns `VersionedAssets` since version 2.
+		let fungibles_version = api
+			.api_version::<dyn FungiblesApi<Block, AccountId>>(at_hash)
+			.map_err(|e| map_err(e, "Unable to query assets."))?;
+		let assets = match fungibles_version {
+			Some(version) if version >= 2 => api
+				.query_account_balances(at_hash, account.clone())
+				.map_err(|e| map_err(e, "Unable to query assets."))?
+				.map(|assets| Some(Bytes(assets.encode())))
+				.map_err(|e| map_err(format!("{:?}", e), "Unable to query assets."))?,
+			_ => None,
+		};
+
+		Ok(SovereignAccountInfo { account, nonce, assets })
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use assets_common::runtime_api::{FungiblesAccessError, LocationToAccountError};
+	use polkadot_parachain_primitives::primitives::Sibling;
+	use sp_api::ApiRef;
+	use sp_blockchain::{BlockStatus, Info};
+	use sp_runtime::{
+		traits::{NumberFor, Zero},
+		AccountId32,
+	};
+	use substrate_test_runtime_client::runtime::{Block, Hash};
+	use xcm::{latest::Junctions::Here, VersionedAssets};
+	use xcm_builder::{ParentIsPreset, SiblingParachainConvertsVia};
+
+	/// The conversion of the parachain runtimes for the relay chain and the sibling parachains.
+	type LocationToAccountId =
+		(ParentIsPreset<AccountId32>, SiblingParachainConvertsVia<Sibling, AccountId32>);
+
+	struct TestApi {
+		assets: Result<VersionedAssets, ()>,
+	}
+
+	impl ProvideRuntimeApi<Block> for TestApi {
+		type Api = RuntimeApi;
+
+		fn runtime_api(&self) -> ApiRef<'_, Self::Api> {
+			RuntimeApi { assets: self.assets.clone() }.into()
+		}
+	}
+
+	impl HeaderBackend<Block> for TestApi {
+		fn header(&self, _hash: Hash) -> sp_blockchain::Result<Option<<Block as BlockT>::Header>> {
+			Ok(None)
+		}
+
+		fn info(&self) -> Info<Block> {
+			Info {
+				best_hash: Default::default(),
+				best_number: Zero::zero(),
+				finalized_hash: Default::default(),
+				finalized_number: Zero::zero(),
+				genesis_hash: Default::default(),
+				number_leaves: Default::default(),
+				finalized_state: None,
+				block_gap: None,
+			}
+		}
+
+		fn status(&self, _hash: Hash) -> sp_blockchain::Result<BlockStatus> {
+			Ok(BlockStatus::Unknown)
+		}
+
+		fn number(&self, _hash: Hash) -> sp_blockchain::Result<Option<NumberFor<Block>>> {
+			Ok(None)
+		}
+
+		fn hash(&self, _number: NumberFor<Block>) -> sp_blockchain::Result<Option<Hash>> {
+			Ok(None)
+		}
+	}
+
+	struct RuntimeApi {
+		assets: Result<VersionedAssets, ()>,
+	}
+
+	sp_api::mock_impl_runtime_apis! {
+		impl LocationToAccountApi<Block, AccountId32> for RuntimeApi {
+			fn convert_location(
+				location: VersionedLocation,
+			) -> Result<AccountId32, LocationToAccountError> {
+				assets_common::runtime_api::convert_location::<LocationToAccountId, _>(location)
+			}
+		}
+
+		impl AccountNonceApi<Block, AccountId32, u32> for RuntimeApi {
+			fn account_nonce(_account: AccountId32) -> u32 {
+				7
+			}
+		}
+
+		impl FungiblesApi<Block, AccountId32> for RuntimeApi {
+			fn query_account_balances(
+				&self,
+				_account: AccountId32,
+			) -> Result<VersionedAssets, FungiblesAccessError> {
+				self.assets.clone().map_err(|_| FungiblesAccessError::AssetIdConversionFailed)
+			}
+		}
+	}
+
+	type Rpc = SovereignAccount<TestApi, Block>;
+
+	fn rpc(assets: Result<VersionedAssets, ()>) -> Rpc {
+		SovereignAccount::new(Arc::new(TestApi { assets }))
+	}
+
+	fn resolve(rpc: &Rpc, location: SovereignLocation) -> RpcResult<AccountId32> {
+		SovereignAccountApiServer::<Hash, AccountId32, u32>::sovereign_account(rpc, location, None)
+	}
+
+	fn info(
+		rpc: &Rpc,
+		location: SovereignLocation,
+	) -> RpcResult<SovereignAccountInfo<AccountId32, u32>> {
+		SovereignAccountApiServer::<Hash, AccountId32, u32>::sovereign_account_info(
+			rpc, location, None,
+		)
+	}
+
+	fn no_assets() -> Result<VersionedAssets, ()> {
+		Ok(xcm::latest::Assets::new().into())
+	}
+
+	/// The account whose encoding starts with `prefix`, padded with zeros.
+	fn account(prefix: &[u8]) -> AccountId32 {
+		let mut account = [0u8; 32];
+		account[..prefix.len()].copy_from_slice(prefix);
+		account.into()
+	}
+
+	fn sibling_account(para_id: u32) -> AccountId32 {
+		account(&[b"sibl".as_slice(), &para_id.to_le_bytes()].concat())
+	}
+
+	#[test]
+	fn resolves_relay_sovereign_account() {
+		let rpc = rpc(no_assets());
+
+		assert_eq!(resolve(&rpc, SovereignLocation::Relay).unwrap(), account(b"Parent"));
+	}
+
+	#[test]
+	fn resolves_sibling_sovereign_account() {
+		let rpc = rpc(no_assets());
+
+		assert_eq!(resolve(&rpc, SovereignLocation::Sibling(1000)).unwrap(), sibling_account(1000));
+
+		// The same as for the encoded location of the sibling.
+		let location = VersionedLocation::from(Location::new(1, [Parachain(1000)]));
+		assert_eq!(
+			resolve(&rpc, SovereignLocation::Location(location.encode().into())).unwrap(),
+			sibling_account(1000),
+		);
+	}
+
+	#[test]
+	fn rejects_location_without_sovereign_account() {
+		let rpc = rpc(no_assets());
+		let location = VersionedLocation::from(Location::new(0, Here));
+
+		let error =
+			resolve(&rpc, SovereignLocation::Location(location.encode().into())).unwrap_err();
+		assert_eq!(error.code(), i32::from(Error::Unsupported));
+
+		let error = resolve(&rpc, SovereignLocation::Location(vec![0xff].into())).unwrap_err();
+		assert_eq!(error.code(), i32::from(Error::DecodeError));
+	}
+
+	#[test]
+	fn returns_sovereign_account_info() {
+		let rpc = rpc(no_assets());
+
+		assert_eq!(
+			info(&rpc, SovereignLocation::Sibling(2000)).unwrap(),
+			SovereignAccountInfo {
+				account: sibling_account(2000),
+				nonce: 7,
+				assets: Some(Bytes(VersionedAssets::from(xcm::latest::Assets::new()).encode())),
+			},
+		);
+	}
+
+	#[test]
+	fn reports_asset_query_failures() {
+		let rpc = rpc(Err(()));
+
+		let error = info(&rpc, SovereignLocation::Relay).unwrap_err();
+		assert_eq!(error.code(), i32::from(Error::RuntimeError));
+		assert_eq!(error.message(), "Unable to query assets.");
+	}
+}
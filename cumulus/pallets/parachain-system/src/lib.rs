@@ -1481,6 +1481,27 @@ impl<T: Config> Pallet<T> {
 		})
 	}
 
+	/// Set the relay chain state proof and its storage root for using them in benchmarks or tests.
+	///
+	/// The proof is returned by [`Self::relay_state_proof`] until the next block is initialized.
+	#[cfg(any(feature = "runtime-benchmarks", feature = "std"))]
+	pub fn set_relay_state_proof_for_benchmarks_or_tests(
+		relay_parent_storage_root: relay_chain::Hash,
+		proof: sp_trie::StorageProof,
+	) {
+		let mut validation_data = ValidationData::<T>::get().unwrap_or_else(||
+			// PersistedValidationData does not impl default in non-std
+			PersistedValidationData {
+				parent_head: vec![].into(),
+				relay_parent_number: Default::default(),
+				max_pov_size: Default::default(),
+				relay_parent_storage_root: Default::default(),
+			});
+		validation_data.relay_parent_storage_root = relay_parent_storage_root;
+		ValidationData::<T>::put(validation_data);
+		RelayStateProof::<T>::put(proof);
+	}
+
 	/// Open HRMP channel for using it in benchmarks or tests.
 	///
 	/// The caller assumes that the pallet will accept regular outbound message to the sibling
@@ -1600,6 +1621,17 @@ impl<T: Config> Pallet<T> {
 	pub fn last_relay_block_number() -> RelayChainBlockNumber {
 		LastRelayChainBlockNumber::<T>::get()
 	}
+
+	/// The state proof of the relay parent of the current block.
+	///
+	/// Returns `None` if the validation data of the current block isn't set yet, e.g. within
+	/// `on_initialize`.
+	pub fn relay_state_proof() -> Option<RelayChainStateProof> {
+		let relay_parent_storage_root = ValidationData::<T>::get()?.relay_parent_storage_root;
+		let proof = RelayStateProof::<T>::get()?;
+
+		RelayChainStateProof::new(T::SelfParaId::get(), relay_parent_storage_root, proof).ok()
+	}
 }
 
 impl<T: Config> UpwardMessageSender for Pallet<T> {
@@ -359,4 +359,14 @@ impl RelayChainStateProof {
 	{
 		read_optional_entry(&self.trie_backend, key).map_err(Error::ReadOptionalEntry)
 	}
+
+	/// Read the raw value of an entry given by the key.
+	///
+	/// Returns `Err` in case the backend can't return the value under the specific key (likely due
+	/// to a malformed proof or to a key which isn't part of the proof).
+	pub fn read_raw_entry(&self, key: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+		self.trie_backend
+			.storage(key)
+			.map_err(|_| Error::ReadOptionalEntry(ReadEntryErr::Proof))
+	}
 }
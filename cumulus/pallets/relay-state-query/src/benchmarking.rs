@@ -0,0 +1,66 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmarks for the relay state query pallet.
+
+use super::*;
+use frame_benchmarking::v2::*;
+
+/// A key as long as allowed, distinct for each `index`.
+fn key<T: Config>(index: u32) -> RelayKey<T> {
+	let mut key = index.encode();
+	key.resize(T::MaxKeyLength::get().max(4) as usize, 0);
+	key.try_into().expect("`MaxKeyLength` allows the keys of the benchmarks")
+}
+
+/// Register all but one of the allowed keys.
+fn register_keys<T: Config>() {
+	let keys = (1..T::MaxKeys::get()).map(key::<T>).collect::<Vec<_>>();
+	RelayKeys::<T>::put(BoundedVec::truncate_from(keys));
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn register_key() -> Result<(), BenchmarkError> {
+		let origin =
+			T::ManagerOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		register_keys::<T>();
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, key::<T>(0));
+
+		assert!(Pallet::<T>::is_registered(&key::<T>(0)));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn remove_key() -> Result<(), BenchmarkError> {
+		let origin =
+			T::ManagerOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		register_keys::<T>();
+		RelayKeys::<T>::mutate(|keys| keys.try_push(key::<T>(0)).expect("one key is left"));
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, key::<T>(0));
+
+		assert!(!Pallet::<T>::is_registered(&key::<T>(0)));
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
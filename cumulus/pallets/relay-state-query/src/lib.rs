@@ -0,0 +1,277 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Relay State Query Pallet
+//!
+//! Reads whitelisted relay chain storage entries from the relay chain state proof of the current
+//! parachain block.
+//!
+//! ## Overview
+//!
+//! Every parachain block carries a proof of some relay chain storage entries at its relay parent,
+//! checked by `cumulus-pallet-parachain-system` when the validation data inherent is applied.
+//! This pallet keeps the list of the additional relay chain storage keys the runtime is
+//! interested in, registered by the [`Config::ManagerOrigin`], and exposes the values of these
+//! keys to the other pallets:
+//!
+//! - [`Pallet::read`] decodes the value of a registered key at the relay parent.
+//! - [`Pallet::read_raw`] returns the encoded value of a registered key at the relay parent.
+//! - [`Pallet::reader`] returns a [`RelayStateReader`] to read many keys from the same proof.
+//!
+//! The keys of the relay chain storage items are built with [`storage_value_key`] and
+//! [`storage_map_key`].
+//!
+//! The collators must include the registered keys in the relay chain state proof of the blocks
+//! they build, e.g. by fetching them with the [`runtime_api::RelayStateQueryApi`] and passing them
+//! to `ParachainInherentDataProvider::create_at_with_additional_keys`. The reads of the keys which
+//! aren't part of the proof fail with [`ReadError::NotInProof`].
+//!
+//! The values are only available once the validation data inherent is applied, i.e. not in
+//! `on_initialize`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+pub mod runtime_api;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+use codec::{Decode, Encode};
+use cumulus_pallet_parachain_system::RelayChainStateProof;
+use frame_support::{pallet_prelude::*, BoundedVec, StorageHasher};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use sp_std::{marker::PhantomData, prelude::*};
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+/// A relay chain storage key.
+pub type RelayKey<T> = BoundedVec<u8, <T as Config>::MaxKeyLength>;
+
+/// An error reading a relay chain storage entry.
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, TypeInfo, RuntimeDebug)]
+pub enum ReadError {
+	/// The key is not registered.
+	NotRegistered,
+	/// The relay chain state proof of the current block is not available, e.g. within
+	/// `on_initialize`.
+	NoProof,
+	/// The entry is not part of the relay chain state proof.
+	NotInProof,
+	/// The value can't be decoded as the requested type.
+	Decode,
+}
+
+/// The key of a storage value of a relay chain pallet.
+pub fn storage_value_key(pallet: &[u8], item: &[u8]) -> Vec<u8> {
+	frame_support::storage::storage_prefix(pallet, item).to_vec()
+}
+
+/// The key of an entry of a storage map of a relay chain pallet, hashed with `H`.
+pub fn storage_map_key<H: StorageHasher, K: Encode>(
+	pallet: &[u8],
+	item: &[u8],
+	key: &K,
+) -> Vec<u8> {
+	let mut final_key = storage_value_key(pallet, item);
+	final_key.extend_from_slice(H::hash(&key.encode()).as_ref());
+	final_key
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + cumulus_pallet_parachain_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The origin allowed to register and remove the keys.
+		type ManagerOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The maximum number of registered keys.
+		#[pallet::constant]
+		type MaxKeys: Get<u32>;
+
+		/// The maximum length of a key.
+		#[pallet::constant]
+		type MaxKeyLength: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// The relay chain storage keys the runtime reads from the relay chain state proof.
+	#[pallet::storage]
+	pub type RelayKeys<T: Config> =
+		StorageValue<_, BoundedVec<RelayKey<T>, T::MaxKeys>, ValueQuery>;
+
+	#[pallet::genesis_config]
+	#[derive(DefaultNoBound)]
+	pub struct GenesisConfig<T: Config> {
+		/// The keys registered at genesis.
+		pub keys: Vec<Vec<u8>>,
+		#[serde(skip)]
+		pub _config: PhantomData<T>,
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+		fn build(&self) {
+			let mut keys = BoundedVec::<RelayKey<T>, T::MaxKeys>::default();
+			for key in &self.keys {
+				let key = RelayKey::<T>::try_from(key.clone())
+					.expect("genesis keys are longer than T::MaxKeyLength");
+				assert!(!keys.contains(&key), "duplicate keys in genesis.");
+				keys.try_push(key).expect("genesis keys are more than T::MaxKeys");
+			}
+			RelayKeys::<T>::put(keys);
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A key was registered.
+		KeyRegistered { key: RelayKey<T> },
+		/// A key was removed.
+		KeyRemoved { key: RelayKey<T> },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The key is already registered.
+		AlreadyRegistered,
+		/// The key is not registered.
+		NotRegistered,
+		/// The maximum number of keys is reached.
+		TooManyKeys,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Register a relay chain storage key to read from the relay chain state proof.
+		///
+		/// Must be called by the [`Config::ManagerOrigin`].
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::register_key())]
+		pub fn register_key(origin: OriginFor<T>, key: RelayKey<T>) -> DispatchResult {
+			T::ManagerOrigin::ensure_origin(origin)?;
+
+			RelayKeys::<T>::try_mutate(|keys| {
+				ensure!(!keys.contains(&key), Error::<T>::AlreadyRegistered);
+				keys.try_push(key.clone()).map_err(|_| Error::<T>::TooManyKeys)
+			})?;
+			Self::deposit_event(Event::KeyRegistered { key });
+			Ok(())
+		}
+
+		/// Remove a registered relay chain storage key.
+		///
+		/// Must be called by the [`Config::ManagerOrigin`].
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::remove_key())]
+		pub fn remove_key(origin: OriginFor<T>, key: RelayKey<T>) -> DispatchResult {
+			T::ManagerOrigin::ensure_origin(origin)?;
+
+			RelayKeys::<T>::try_mutate(|keys| {
+				let index = keys.iter().position(|k| k == &key).ok_or(Error::<T>::NotRegistered)?;
+				keys.remove(index);
+				Ok::<_, Error<T>>(())
+			})?;
+			Self::deposit_event(Event::KeyRemoved { key });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The registered keys, for the `RelayStateQueryApi`.
+	pub fn keys() -> Vec<Vec<u8>> {
+		RelayKeys::<T>::get().into_iter().map(Into::into).collect()
+	}
+
+	/// Whether the given key is registered.
+	pub fn is_registered(key: &[u8]) -> bool {
+		RelayKeys::<T>::get().iter().any(|k| k.as_slice() == key)
+	}
+
+	/// A reader of the registered entries at the relay parent of the current block.
+	pub fn reader() -> Result<RelayStateReader<T>, ReadError> {
+		let proof = cumulus_pallet_parachain_system::Pallet::<T>::relay_state_proof()
+			.ok_or(ReadError::NoProof)?;
+
+		Ok(RelayStateReader { proof, keys: RelayKeys::<T>::get(), _config: PhantomData })
+	}
+
+	/// The encoded value of the given registered key at the relay parent of the current block.
+	///
+	/// Returns `Ok(None)` if the entry doesn't exist on the relay chain.
+	pub fn read_raw(key: &[u8]) -> Result<Option<Vec<u8>>, ReadError> {
+		Self::reader()?.read_raw(key)
+	}
+
+	/// The value of the given registered key at the relay parent of the current block.
+	///
+	/// Returns `Ok(None)` if the entry doesn't exist on the relay chain.
+	pub fn read<V: Decode>(key: &[u8]) -> Result<Option<V>, ReadError> {
+		Self::reader()?.read(key)
+	}
+}
+
+/// Reads the registered entries from the relay chain state proof of the current block.
+///
+/// The proof is checked once, when the reader is created by [`Pallet::reader`].
+pub struct RelayStateReader<T: Config> {
+	proof: RelayChainStateProof,
+	keys: BoundedVec<RelayKey<T>, T::MaxKeys>,
+	_config: PhantomData<T>,
+}
+
+impl<T: Config> RelayStateReader<T> {
+	/// The encoded value of the given registered key.
+	///
+	/// Returns `Ok(None)` if the entry doesn't exist on the relay chain.
+	pub fn read_raw(&self, key: &[u8]) -> Result<Option<Vec<u8>>, ReadError> {
+		ensure!(self.keys.iter().any(|k| k.as_slice() == key), ReadError::NotRegistered);
+
+		self.proof.read_raw_entry(key).map_err(|_| ReadError::NotInProof)
+	}
+
+	/// The value of the given registered key.
+	///
+	/// Returns `Ok(None)` if the entry doesn't exist on the relay chain.
+	pub fn read<V: Decode>(&self, key: &[u8]) -> Result<Option<V>, ReadError> {
+		self.read_raw(key)?
+			.map(|raw| V::decode(&mut &raw[..]).map_err(|_| ReadError::Decode))
+			.transpose()
+	}
+
+	/// The value of the given registered key, or `V::default()` if the entry doesn't exist on the
+	/// relay chain.
+	pub fn read_or_default<V: Decode + Default>(&self, key: &[u8]) -> Result<V, ReadError> {
+		self.read(key).map(Option::unwrap_or_default)
+	}
+}
@@ -0,0 +1,30 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API definition for the relay state query pallet.
+
+use crate::ReadError;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	/// The relay chain storage entries read by the runtime.
+	pub trait RelayStateQueryApi {
+		/// The relay chain storage keys the collators must include in the relay chain state proof.
+		fn relay_keys() -> Vec<Vec<u8>>;
+
+		/// The encoded value of the given registered key at the relay parent of the block.
+		fn relay_entry(key: Vec<u8>) -> Result<Option<Vec<u8>>, ReadError>;
+	}
+}
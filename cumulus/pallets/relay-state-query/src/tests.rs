@@ -0,0 +1,95 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the relay state query pallet.
+
+use crate::{mock::*, *};
+use codec::Encode;
+use cumulus_primitives_core::{relay_chain::well_known_keys, ParaId};
+use frame_support::{assert_noop, assert_ok, Twox64Concat};
+use sp_runtime::DispatchError;
+
+fn relay_key(key: &[u8]) -> RelayKey<Test> {
+	key.to_vec().try_into().unwrap()
+}
+
+#[test]
+fn register_and_remove_keys_work() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			RelayStateQuery::register_key(RuntimeOrigin::signed(1), relay_key(b"a")),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(RelayStateQuery::register_key(RuntimeOrigin::root(), relay_key(b"a")));
+		System::assert_last_event(Event::KeyRegistered { key: relay_key(b"a") }.into());
+		assert_noop!(
+			RelayStateQuery::register_key(RuntimeOrigin::root(), relay_key(b"a")),
+			Error::<Test>::AlreadyRegistered
+		);
+
+		for key in [b"b", b"c", b"d"] {
+			assert_ok!(RelayStateQuery::register_key(RuntimeOrigin::root(), relay_key(key)));
+		}
+		assert_noop!(
+			RelayStateQuery::register_key(RuntimeOrigin::root(), relay_key(b"e")),
+			Error::<Test>::TooManyKeys
+		);
+
+		assert_ok!(RelayStateQuery::remove_key(RuntimeOrigin::root(), relay_key(b"b")));
+		System::assert_last_event(Event::KeyRemoved { key: relay_key(b"b") }.into());
+		assert_noop!(
+			RelayStateQuery::remove_key(RuntimeOrigin::root(), relay_key(b"b")),
+			Error::<Test>::NotRegistered
+		);
+		assert_eq!(RelayStateQuery::keys(), vec![b"a".to_vec(), b"c".to_vec(), b"d".to_vec()]);
+	});
+}
+
+#[test]
+fn read_registered_entries() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(RelayStateQuery::register_key(RuntimeOrigin::root(), relay_key(b"value")));
+		assert_ok!(RelayStateQuery::register_key(RuntimeOrigin::root(), relay_key(b"absent")));
+		assert_eq!(RelayStateQuery::read::<u32>(b"value"), Err(ReadError::NoProof));
+
+		set_relay_entries(vec![
+			(b"value".to_vec(), 42u32.encode()),
+			(b"unregistered".to_vec(), 7u32.encode()),
+		]);
+
+		assert_eq!(RelayStateQuery::read::<u32>(b"value"), Ok(Some(42)));
+		assert_eq!(RelayStateQuery::read_raw(b"value"), Ok(Some(42u32.encode())));
+		assert_eq!(RelayStateQuery::read::<u64>(b"value"), Err(ReadError::Decode));
+		assert_eq!(RelayStateQuery::read::<u32>(b"absent"), Ok(None));
+		assert_eq!(RelayStateQuery::read::<u32>(b"unregistered"), Err(ReadError::NotRegistered));
+
+		let reader = RelayStateQuery::reader().unwrap();
+		assert_eq!(reader.read::<u32>(b"value"), Ok(Some(42)));
+		assert_eq!(reader.read_or_default::<u32>(b"absent"), Ok(0));
+	});
+}
+
+#[test]
+fn relay_storage_keys_match_well_known_keys() {
+	assert_eq!(
+		storage_value_key(b"Configuration", b"ActiveConfig"),
+		well_known_keys::ACTIVE_CONFIG
+	);
+	assert_eq!(
+		storage_map_key::<Twox64Concat, _>(b"Paras", b"Heads", &ParaId::from(100)),
+		well_known_keys::para_head(ParaId::from(100)),
+	);
+}
@@ -17,9 +17,10 @@
 //! Helpers to deal with configuring the message queue in the runtime.
 
 use cumulus_primitives_core::{AggregateMessageOrigin, ParaId};
-use frame_support::traits::{QueueFootprint, QueuePausedQuery};
+use frame_support::traits::{Get, QueueFootprint, QueuePausedQuery, QueueServiceShares};
 use pallet_message_queue::OnQueueChanged;
-use sp_std::marker::PhantomData;
+use sp_runtime::Perbill;
+use sp_std::{marker::PhantomData, prelude::*};
 
 /// Narrow the scope of the `Inner` query from `AggregateMessageOrigin` to `ParaId`.
 ///
@@ -46,6 +47,45 @@ impl<Inner: OnQueueChanged<ParaId>> OnQueueChanged<AggregateMessageOrigin>
 	}
 }
 
+/// The class of an `AggregateMessageOrigin`, with all siblings sharing one class.
+#[derive(Clone, Copy, Eq, PartialEq, sp_runtime::RuntimeDebug)]
+pub enum AggregateMessageOriginClass {
+	/// The para-chain itself.
+	Here,
+	/// The relay-chain (DMP).
+	Parent,
+	/// Any sibling para-chain (HRMP).
+	Siblings,
+}
+
+/// Reserve the `Parent`, `Siblings` and `Here` shares of the message queue service weight for
+/// the respective queues.
+///
+/// This guarantees the relay-chain messages a minimal share of the service weight, even when a
+/// sibling floods its queue.
+pub struct ServiceSharesByOrigin<Parent, Siblings, Here>(PhantomData<(Parent, Siblings, Here)>);
+impl<Parent: Get<Perbill>, Siblings: Get<Perbill>, Here: Get<Perbill>>
+	QueueServiceShares<AggregateMessageOrigin> for ServiceSharesByOrigin<Parent, Siblings, Here>
+{
+	type Class = AggregateMessageOriginClass;
+
+	fn class_of(origin: &AggregateMessageOrigin) -> Self::Class {
+		match origin {
+			AggregateMessageOrigin::Here => AggregateMessageOriginClass::Here,
+			AggregateMessageOrigin::Parent => AggregateMessageOriginClass::Parent,
+			AggregateMessageOrigin::Sibling(_) => AggregateMessageOriginClass::Siblings,
+		}
+	}
+
+	fn shares() -> Vec<(Self::Class, Perbill)> {
+		vec![
+			(AggregateMessageOriginClass::Parent, Parent::get()),
+			(AggregateMessageOriginClass::Siblings, Siblings::get()),
+			(AggregateMessageOriginClass::Here, Here::get()),
+		]
+	}
+}
+
 /// Convert a sibling `ParaId` to an `AggregateMessageOrigin`.
 pub struct ParaIdToSibling;
 impl sp_runtime::traits::Convert<ParaId, AggregateMessageOrigin> for ParaIdToSibling {
@@ -463,6 +463,8 @@ impl<T: frame_system::Config> pallet_assets::WeightInfo for WeightInfo<T> {
 	/// Proof: `ForeignAssets::Asset` (`max_values`: None, `max_size`: Some(808), added: 3283, mode: `MaxEncodedLen`)
 	/// Storage: `System::Account` (r:1 w:1)
 	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Sponsorship` (r:1 w:1)
+	/// Proof: `ForeignAssets::Sponsorship` (`max_values`: None, `max_size`: Some(650), added: 3145, mode: `MaxEncodedLen`)
 	fn touch() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `345`
@@ -470,8 +472,8 @@ impl<T: frame_system::Config> pallet_assets::WeightInfo for WeightInfo<T> {
 		// Minimum execution time: 34_984_000 picoseconds.
 		Weight::from_parts(35_512_000, 0)
 			.saturating_add(Weight::from_parts(0, 4273))
-			.saturating_add(T::DbWeight::get().reads(3))
-			.saturating_add(T::DbWeight::get().writes(3))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(4))
 	}
 	/// Storage: `ForeignAssets::Account` (r:1 w:1)
 	/// Proof: `ForeignAssets::Account` (`max_values`: None, `max_size`: Some(732), added: 3207, mode: `MaxEncodedLen`)
@@ -531,4 +533,32 @@ impl<T: frame_system::Config> pallet_assets::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(2))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
+	/// Storage: `ForeignAssets::Asset` (r:1 w:0)
+	/// Proof: `ForeignAssets::Asset` (`max_values`: None, `max_size`: Some(808), added: 3283, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Sponsorship` (r:0 w:1)
+	/// Proof: `ForeignAssets::Sponsorship` (`max_values`: None, `max_size`: Some(650), added: 3145, mode: `MaxEncodedLen`)
+	fn set_sponsorship() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `276`
+		//  Estimated: `3283`
+		// Minimum execution time: 14_512_000 picoseconds.
+		Weight::from_parts(14_512_000, 0)
+			.saturating_add(Weight::from_parts(0, 3283))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `ForeignAssets::Asset` (r:1 w:0)
+	/// Proof: `ForeignAssets::Asset` (`max_values`: None, `max_size`: Some(808), added: 3283, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Sponsorship` (r:1 w:1)
+	/// Proof: `ForeignAssets::Sponsorship` (`max_values`: None, `max_size`: Some(650), added: 3145, mode: `MaxEncodedLen`)
+	fn clear_sponsorship() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `372`
+		//  Estimated: `3283`
+		// Minimum execution time: 16_204_000 picoseconds.
+		Weight::from_parts(16_204_000, 0)
+			.saturating_add(Weight::from_parts(0, 3283))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
 }
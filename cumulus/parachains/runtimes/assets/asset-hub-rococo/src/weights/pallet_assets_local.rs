@@ -460,6 +460,8 @@ impl<T: frame_system::Config> pallet_assets::WeightInfo for WeightInfo<T> {
 	/// Proof: `Assets::Asset` (`max_values`: None, `max_size`: Some(210), added: 2685, mode: `MaxEncodedLen`)
 	/// Storage: `System::Account` (r:1 w:1)
 	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Assets::Sponsorship` (r:1 w:1)
+	/// Proof: `Assets::Sponsorship` (`max_values`: None, `max_size`: Some(52), added: 2547, mode: `MaxEncodedLen`)
 	fn touch() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `346`
@@ -467,8 +469,8 @@ impl<T: frame_system::Config> pallet_assets::WeightInfo for WeightInfo<T> {
 		// Minimum execution time: 32_578_000 picoseconds.
 		Weight::from_parts(33_675_000, 0)
 			.saturating_add(Weight::from_parts(0, 3675))
-			.saturating_add(T::DbWeight::get().reads(3))
-			.saturating_add(T::DbWeight::get().writes(3))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(4))
 	}
 	/// Storage: `Assets::Account` (r:1 w:1)
 	/// Proof: `Assets::Account` (`max_values`: None, `max_size`: Some(134), added: 2609, mode: `MaxEncodedLen`)
@@ -528,4 +530,32 @@ impl<T: frame_system::Config> pallet_assets::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(2))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
+	/// Storage: `Assets::Asset` (r:1 w:0)
+	/// Proof: `Assets::Asset` (`max_values`: None, `max_size`: Some(210), added: 2685, mode: `MaxEncodedLen`)
+	/// Storage: `Assets::Sponsorship` (r:0 w:1)
+	/// Proof: `Assets::Sponsorship` (`max_values`: None, `max_size`: Some(52), added: 2547, mode: `MaxEncodedLen`)
+	fn set_sponsorship() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `276`
+		//  Estimated: `2685`
+		// Minimum execution time: 14_512_000 picoseconds.
+		Weight::from_parts(14_512_000, 0)
+			.saturating_add(Weight::from_parts(0, 2685))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `Assets::Asset` (r:1 w:0)
+	/// Proof: `Assets::Asset` (`max_values`: None, `max_size`: Some(210), added: 2685, mode: `MaxEncodedLen`)
+	/// Storage: `Assets::Sponsorship` (r:1 w:1)
+	/// Proof: `Assets::Sponsorship` (`max_values`: None, `max_size`: Some(52), added: 2547, mode: `MaxEncodedLen`)
+	fn clear_sponsorship() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `372`
+		//  Estimated: `2685`
+		// Minimum execution time: 16_204_000 picoseconds.
+		Weight::from_parts(16_204_000, 0)
+			.saturating_add(Weight::from_parts(0, 2685))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
 }
@@ -427,4 +427,31 @@ impl<T: frame_system::Config> pallet_xcm::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(1))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
+	/// Storage: `PolkadotXcm::QueryExpiryDelay` (r:0 w:1)
+	/// Proof: `PolkadotXcm::QueryExpiryDelay` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	fn force_query_expiry() -> Weight {
+		// Placeholder derived from `force_default_xcm_version`, to be replaced by the benchmark output.
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 1_884_000 picoseconds.
+		Weight::from_parts(2_016_000, 0)
+			.saturating_add(Weight::from_parts(0, 0))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `PolkadotXcm::Queries` (r:1 w:1)
+	/// Proof: `PolkadotXcm::Queries` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `PolkadotXcm::QueryOwners` (r:1 w:1)
+	/// Proof: `PolkadotXcm::QueryOwners` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn cancel_query() -> Weight {
+		// Placeholder derived from `take_response`, to be replaced by the benchmark output.
+		// Proof Size summary in bytes:
+		//  Measured:  `7740`
+		//  Estimated: `11205`
+		// Minimum execution time: 26_064_000 picoseconds.
+		Weight::from_parts(26_497_000, 0)
+			.saturating_add(Weight::from_parts(0, 11205))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
 }
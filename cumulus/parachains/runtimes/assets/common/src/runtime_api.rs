@@ -17,6 +17,8 @@
 
 use codec::{Codec, Decode, Encode};
 use sp_runtime::RuntimeDebug;
+use xcm::{latest::Location, VersionedLocation};
+use xcm_executor::traits::ConvertLocation;
 #[cfg(feature = "std")]
 use {sp_std::vec::Vec, xcm::latest::Asset};
 
@@ -29,6 +31,15 @@ pub enum FungiblesAccessError {
 	AmountToBalanceConversionFailed,
 }
 
+/// The possible errors that can happen resolving the local account of a `Location`.
+#[derive(Eq, PartialEq, Encode, Decode, RuntimeDebug, scale_info::TypeInfo)]
+pub enum LocationToAccountError {
+	/// `VersionedLocation` could not be converted to the latest `Location`.
+	VersionedConversionFailed,
+	/// The runtime does not derive a local account for the given `Location`.
+	Unsupported,
+}
+
 sp_api::decl_runtime_apis! {
 	/// The API for querying account's balances from runtime.
 	#[api_version(2)]
@@ -44,3 +55,29 @@ sp_api::decl_runtime_apis! {
 		fn query_account_balances(account: AccountId) -> Result<xcm::VersionedAssets, FungiblesAccessError>;
 	}
 }
+
+sp_api::decl_runtime_apis! {
+	/// The API for resolving the local (sovereign) account of a `Location`.
+	pub trait LocationToAccountApi<AccountId>
+	where
+		AccountId: Codec,
+	{
+		/// Converts a `Location` into the local `AccountId` it controls, using the same
+		/// conversion as the runtime's XCM configuration.
+		fn convert_location(location: VersionedLocation) -> Result<AccountId, LocationToAccountError>;
+	}
+}
+
+/// Helper for implementing [`LocationToAccountApi`] on top of a runtime's `ConvertLocation`
+/// configuration.
+pub fn convert_location<Converter, AccountId>(
+	location: VersionedLocation,
+) -> Result<AccountId, LocationToAccountError>
+where
+	Converter: ConvertLocation<AccountId>,
+{
+	let location: Location = location
+		.try_into()
+		.map_err(|_| LocationToAccountError::VersionedConversionFailed)?;
+	Converter::convert_location(&location).ok_or(LocationToAccountError::Unsupported)
+}
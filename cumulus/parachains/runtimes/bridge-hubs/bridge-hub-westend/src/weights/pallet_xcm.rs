@@ -405,4 +405,31 @@ impl<T: frame_system::Config> pallet_xcm::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(1))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
+	/// Storage: `PolkadotXcm::QueryExpiryDelay` (r:0 w:1)
+	/// Proof: `PolkadotXcm::QueryExpiryDelay` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	fn force_query_expiry() -> Weight {
+		// Placeholder derived from `force_default_xcm_version`, to be replaced by the benchmark output.
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 1_987_000 picoseconds.
+		Weight::from_parts(2_076_000, 0)
+			.saturating_add(Weight::from_parts(0, 0))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `PolkadotXcm::Queries` (r:1 w:1)
+	/// Proof: `PolkadotXcm::Queries` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `PolkadotXcm::QueryOwners` (r:1 w:1)
+	/// Proof: `PolkadotXcm::QueryOwners` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn cancel_query() -> Weight {
+		// Placeholder derived from `take_response`, to be replaced by the benchmark output.
+		// Proof Size summary in bytes:
+		//  Measured:  `7669`
+		//  Estimated: `11134`
+		// Minimum execution time: 26_336_000 picoseconds.
+		Weight::from_parts(26_873_000, 0)
+			.saturating_add(Weight::from_parts(0, 11134))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
 }
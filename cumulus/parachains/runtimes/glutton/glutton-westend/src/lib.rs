@@ -209,6 +209,7 @@ impl pallet_message_queue::Config for Runtime {
 	type Size = u32;
 	type QueueChangeHandler = ();
 	type QueuePausedQuery = (); // No XCMP queue pallet deployed.
+	type ServiceShares = ();
 	type HeapSize = sp_core::ConstU32<{ 64 * 1024 }>;
 	type MaxStale = sp_core::ConstU32<8>;
 	type ServiceWeight = MessageQueueServiceWeight;
@@ -41,7 +41,8 @@ use frame_support::{
 	pallet_prelude::Weight,
 	parameter_types,
 	traits::{
-		AsEnsureOriginWithArg, ConstBool, ConstU32, ConstU64, ConstU8, Everything, TransformOrigin,
+		AsEnsureOriginWithArg, ConstBool, ConstU32, ConstU64, ConstU8, Everything, GetDefault,
+		TransformOrigin,
 	},
 	weights::{
 		constants::WEIGHT_REF_TIME_PER_SECOND, ConstantMultiplier, FeePolynomial,
@@ -55,7 +56,7 @@ use frame_system::{
 };
 use parachains_common::{
 	impls::{AssetsToBlockAuthor, NonZeroIssuance},
-	message_queue::{NarrowOriginToSibling, ParaIdToSibling},
+	message_queue::{NarrowOriginToSibling, ParaIdToSibling, ServiceSharesByOrigin},
 };
 use smallvec::smallvec;
 use sp_api::impl_runtime_apis;
@@ -449,6 +450,7 @@ impl pallet_assets::Config<pallet_assets::Instance1> for Runtime {
 	type Extra = ();
 	type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
 	type CallbackHandle = ();
+	type FreezeConsumer = ();
 	type AssetAccountDeposit = AssetAccountDeposit;
 	type RemoveItemsLimit = frame_support::traits::ConstU32<1000>;
 	#[cfg(feature = "runtime-benchmarks")]
@@ -484,6 +486,7 @@ impl pallet_assets::Config<ForeignAssetsInstance> for Runtime {
 	type Extra = ();
 	type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
 	type CallbackHandle = ();
+	type FreezeConsumer = ();
 	type AssetAccountDeposit = ForeignAssetsAssetAccountDeposit;
 	type RemoveItemsLimit = frame_support::traits::ConstU32<1000>;
 	#[cfg(feature = "runtime-benchmarks")]
@@ -519,6 +522,8 @@ impl parachain_info::Config for Runtime {}
 
 parameter_types! {
 	pub MessageQueueServiceWeight: Weight = Perbill::from_percent(35) * RuntimeBlockWeights::get().max_block;
+	/// The share of the message queue service weight that is reserved for the relay-chain.
+	pub const MessageQueueParentShare: Perbill = Perbill::from_percent(20);
 }
 
 impl pallet_message_queue::Config for Runtime {
@@ -533,6 +538,7 @@ impl pallet_message_queue::Config for Runtime {
 	// The XCMP queue pallet is only ever able to handle the `Sibling(ParaId)` origin:
 	type QueueChangeHandler = NarrowOriginToSibling<XcmpQueue>;
 	type QueuePausedQuery = NarrowOriginToSibling<XcmpQueue>;
+	type ServiceShares = ServiceSharesByOrigin<MessageQueueParentShare, GetDefault, GetDefault>;
 	type HeapSize = sp_core::ConstU32<{ 64 * 1024 }>;
 	type MaxStale = sp_core::ConstU32<8>;
 	type ServiceWeight = MessageQueueServiceWeight;
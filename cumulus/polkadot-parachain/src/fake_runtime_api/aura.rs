@@ -172,6 +172,22 @@ sp_api::impl_runtime_apis! {
 		}
 	}
 
+	impl assets_common::runtime_api::LocationToAccountApi<Block, AccountId> for Runtime {
+		fn convert_location(
+			_: xcm::VersionedLocation,
+		) -> Result<AccountId, assets_common::runtime_api::LocationToAccountError> {
+			unimplemented!()
+		}
+	}
+
+	impl assets_common::runtime_api::FungiblesApi<Block, AccountId> for Runtime {
+		fn query_account_balances(
+			_: AccountId,
+		) -> Result<xcm::VersionedAssets, assets_common::runtime_api::FungiblesAccessError> {
+			unimplemented!()
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(_: bool) -> (
@@ -16,6 +16,7 @@
 
 use codec::{Codec, Decode};
 use cumulus_client_cli::CollatorOptions;
+use cumulus_client_collation_latency_rpc::{CollationLatency, CollationLatencyApiServer};
 use cumulus_client_collator::service::CollatorService;
 use cumulus_client_consensus_aura::collators::lookahead::{self as aura, Params as AuraParams};
 use cumulus_client_consensus_common::{
@@ -287,14 +288,25 @@ where
 		let client = client.clone();
 		let transaction_pool = transaction_pool.clone();
 		let backend_for_rpc = backend.clone();
+		// Only collators keep track of the collation timelines.
+		let overseer_handle =
+			if validator { relay_chain_interface.overseer_handle().ok() } else { None };
 
 		Box::new(move |deny_unsafe, _| {
-			rpc_ext_builder(
+			let mut module = rpc_ext_builder(
 				deny_unsafe,
 				client.clone(),
 				backend_for_rpc.clone(),
 				transaction_pool.clone(),
-			)
+			)?;
+
+			if let Some(overseer_handle) = overseer_handle.clone() {
+				module
+					.merge(CollationLatency::new(overseer_handle, deny_unsafe).into_rpc())
+					.map_err(|e| sc_service::Error::Application(Box::new(e)))?;
+			}
+
+			Ok(module)
 		})
 	};
 
@@ -471,7 +483,9 @@ where
 	RuntimeApi::RuntimeApi: sp_transaction_pool::runtime_api::TaggedTransactionQueue<Block>
 		+ sp_block_builder::BlockBuilder<Block>
 		+ pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>
-		+ frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
+		+ frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>
+		+ cumulus_client_sovereign_account_rpc::LocationToAccountApi<Block, AccountId>
+		+ cumulus_client_sovereign_account_rpc::FungiblesApi<Block, AccountId>,
 {
 	let deps = rpc::FullDeps { client, pool, deny_unsafe };
 
@@ -737,7 +751,9 @@ where
 		+ sp_consensus_aura::AuraApi<Block, <<AuraId as AppCrypto>::Pair as Pair>::Public>
 		+ pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>
 		+ frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>
-		+ cumulus_primitives_aura::AuraUnincludedSegmentApi<Block>,
+		+ cumulus_primitives_aura::AuraUnincludedSegmentApi<Block>
+		+ cumulus_client_sovereign_account_rpc::LocationToAccountApi<Block, AccountId>
+		+ cumulus_client_sovereign_account_rpc::FungiblesApi<Block, AccountId>,
 	<<AuraId as AppCrypto>::Pair as Pair>::Signature:
 		TryFrom<Vec<u8>> + std::hash::Hash + sp_runtime::traits::Member + Codec,
 	Net: NetworkBackend<Block, Hash>,
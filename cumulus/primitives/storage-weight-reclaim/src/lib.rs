@@ -17,23 +17,11 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use codec::{Decode, Encode};
 use cumulus_primitives_core::Weight;
 use cumulus_primitives_proof_size_hostfunction::{
 	storage_proof_size::storage_proof_size, PROOF_RECORDING_DISABLED,
 };
-use frame_support::{
-	dispatch::{DispatchInfo, PostDispatchInfo},
-	weights::WeightMeter,
-};
-use frame_system::Config;
-use scale_info::TypeInfo;
-use sp_runtime::{
-	traits::{DispatchInfoOf, Dispatchable, PostDispatchInfoOf, SignedExtension},
-	transaction_validity::TransactionValidityError,
-	DispatchResult,
-};
-use sp_std::marker::PhantomData;
+use frame_support::weights::WeightMeter;
 
 const LOG_TARGET: &'static str = "runtime::storage_reclaim";
 
@@ -96,117 +84,35 @@ pub fn get_proof_size() -> Option<u64> {
 	(proof_size != PROOF_RECORDING_DISABLED).then_some(proof_size)
 }
 
+/// Storage proof size reported by the host.
+#[derive(Clone, Eq, PartialEq, Default)]
+pub struct HostStorageProofSize;
+
+impl frame_system::StorageProofSize for HostStorageProofSize {
+	fn storage_proof_size() -> Option<u64> {
+		get_proof_size()
+	}
+}
+
 /// Storage weight reclaim mechanism.
 ///
 /// This extension checks the size of the node-side storage proof
 /// before and after executing a given extrinsic. The difference between
 /// benchmarked and spent weight can be reclaimed.
-#[derive(Encode, Decode, Clone, Eq, PartialEq, Default, TypeInfo)]
-#[scale_info(skip_type_params(T))]
-pub struct StorageWeightReclaim<T: Config + Send + Sync>(PhantomData<T>);
-
-impl<T: Config + Send + Sync> StorageWeightReclaim<T> {
-	/// Create a new `StorageWeightReclaim` instance.
-	pub fn new() -> Self {
-		Self(Default::default())
-	}
-}
-
-impl<T: Config + Send + Sync> core::fmt::Debug for StorageWeightReclaim<T> {
-	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
-		let _ = write!(f, "StorageWeightReclaim");
-		Ok(())
-	}
-}
-
-impl<T: Config + Send + Sync> SignedExtension for StorageWeightReclaim<T>
-where
-	T::RuntimeCall: Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
-{
-	const IDENTIFIER: &'static str = "StorageWeightReclaim";
-
-	type AccountId = T::AccountId;
-	type Call = T::RuntimeCall;
-	type AdditionalSigned = ();
-	type Pre = Option<u64>;
-
-	fn additional_signed(
-		&self,
-	) -> Result<Self::AdditionalSigned, sp_runtime::transaction_validity::TransactionValidityError>
-	{
-		Ok(())
-	}
-
-	fn pre_dispatch(
-		self,
-		_who: &Self::AccountId,
-		_call: &Self::Call,
-		_info: &sp_runtime::traits::DispatchInfoOf<Self::Call>,
-		_len: usize,
-	) -> Result<Self::Pre, sp_runtime::transaction_validity::TransactionValidityError> {
-		Ok(get_proof_size())
-	}
-
-	fn post_dispatch(
-		pre: Option<Self::Pre>,
-		info: &DispatchInfoOf<Self::Call>,
-		post_info: &PostDispatchInfoOf<Self::Call>,
-		_len: usize,
-		_result: &DispatchResult,
-	) -> Result<(), TransactionValidityError> {
-		let Some(Some(pre_dispatch_proof_size)) = pre else {
-			return Ok(());
-		};
-
-		let Some(post_dispatch_proof_size) = get_proof_size() else {
-			log::debug!(
-				target: LOG_TARGET,
-				"Proof recording enabled during pre-dispatch, now disabled. This should not happen."
-			);
-			return Ok(())
-		};
-		let benchmarked_weight = info.weight.proof_size();
-		let consumed_weight = post_dispatch_proof_size.saturating_sub(pre_dispatch_proof_size);
-
-		// Unspent weight according to the `actual_weight` from `PostDispatchInfo`
-		// This unspent weight will be refunded by the `CheckWeight` extension, so we need to
-		// account for that.
-		let unspent = post_info.calc_unspent(info).proof_size();
-		let storage_size_diff =
-			benchmarked_weight.saturating_sub(unspent).abs_diff(consumed_weight as u64);
-
-		// This value will be reclaimed by [`frame_system::CheckWeight`], so we need to calculate
-		// that in.
-		frame_system::BlockWeight::<T>::mutate(|current| {
-			if consumed_weight > benchmarked_weight {
-				log::error!(
-					target: LOG_TARGET,
-					"Benchmarked storage weight smaller than consumed storage weight. benchmarked: {benchmarked_weight} consumed: {consumed_weight} unspent: {unspent}"
-				);
-				current.accrue(Weight::from_parts(0, storage_size_diff), info.class)
-			} else {
-				log::trace!(
-					target: LOG_TARGET,
-					"Reclaiming storage weight. benchmarked: {benchmarked_weight}, consumed: {consumed_weight} unspent: {unspent}"
-				);
-				current.reduce(Weight::from_parts(0, storage_size_diff), info.class)
-			}
-		});
-		Ok(())
-	}
-}
+///
+/// See [`frame_system::StorageWeightReclaim`].
+pub type StorageWeightReclaim<T> = frame_system::StorageWeightReclaim<T, HostStorageProofSize>;
 
 #[cfg(test)]
 mod tests {
 	use super::*;
 	use frame_support::{
 		assert_ok,
-		dispatch::DispatchClass,
+		dispatch::{DispatchClass, DispatchInfo, PostDispatchInfo},
 		weights::{Weight, WeightMeter},
 	};
-	use frame_system::{BlockWeight, CheckWeight};
-	use sp_runtime::{AccountId32, BuildStorage};
-	use sp_std::marker::PhantomData;
+	use frame_system::{BlockWeight, CheckWeight, Config};
+	use sp_runtime::{traits::SignedExtension, AccountId32, BuildStorage};
 	use sp_trie::proof_size_extension::ProofSizeExt;
 
 	type Test = cumulus_test_runtime::Runtime;
@@ -268,7 +174,7 @@ mod tests {
 			let info = DispatchInfo { weight: Weight::from_parts(0, 500), ..Default::default() };
 			let post_info = PostDispatchInfo::default();
 
-			let pre = StorageWeightReclaim::<Test>(PhantomData)
+			let pre = StorageWeightReclaim::<Test>::new()
 				.pre_dispatch(&ALICE, CALL, &info, LEN)
 				.unwrap();
 			assert_eq!(pre, Some(0));
@@ -299,7 +205,7 @@ mod tests {
 			let info = DispatchInfo { weight: Weight::from_parts(0, 500), ..Default::default() };
 			let post_info = PostDispatchInfo::default();
 
-			let pre = StorageWeightReclaim::<Test>(PhantomData)
+			let pre = StorageWeightReclaim::<Test>::new()
 				.pre_dispatch(&ALICE, CALL, &info, LEN)
 				.unwrap();
 			assert_eq!(pre, None);
@@ -327,7 +233,7 @@ mod tests {
 			let info = DispatchInfo { weight: Weight::from_parts(0, 100), ..Default::default() };
 			let post_info = PostDispatchInfo::default();
 
-			let pre = StorageWeightReclaim::<Test>(PhantomData)
+			let pre = StorageWeightReclaim::<Test>::new()
 				.pre_dispatch(&ALICE, CALL, &info, LEN)
 				.unwrap();
 			assert_eq!(pre, Some(100));
@@ -354,7 +260,7 @@ mod tests {
 			let info = DispatchInfo { weight: Weight::from_parts(0, 500), ..Default::default() };
 			let post_info = PostDispatchInfo::default();
 
-			let pre = StorageWeightReclaim::<Test>(PhantomData)
+			let pre = StorageWeightReclaim::<Test>::new()
 				.pre_dispatch(&ALICE, CALL, &info, LEN)
 				.unwrap();
 			assert_eq!(pre, Some(0));
@@ -382,7 +288,7 @@ mod tests {
 			let info = DispatchInfo { weight: Weight::from_parts(0, 500), ..Default::default() };
 			let post_info = PostDispatchInfo::default();
 
-			let pre = StorageWeightReclaim::<Test>(PhantomData)
+			let pre = StorageWeightReclaim::<Test>::new()
 				.pre_dispatch(&ALICE, CALL, &info, LEN)
 				.unwrap();
 			assert_eq!(pre, Some(300));
@@ -416,7 +322,7 @@ mod tests {
 				pays_fee: Default::default(),
 			};
 
-			let pre = StorageWeightReclaim::<Test>(PhantomData)
+			let pre = StorageWeightReclaim::<Test>::new()
 				.pre_dispatch(&ALICE, CALL, &info, LEN)
 				.unwrap();
 			assert_eq!(pre, Some(100));
@@ -451,7 +357,7 @@ mod tests {
 				pays_fee: Default::default(),
 			};
 
-			let pre = StorageWeightReclaim::<Test>(PhantomData)
+			let pre = StorageWeightReclaim::<Test>::new()
 				.pre_dispatch(&ALICE, CALL, &info, LEN)
 				.unwrap();
 			assert_eq!(pre, Some(100));
@@ -487,7 +393,7 @@ mod tests {
 				pays_fee: Default::default(),
 			};
 
-			let pre = StorageWeightReclaim::<Test>(PhantomData)
+			let pre = StorageWeightReclaim::<Test>::new()
 				.pre_dispatch(&ALICE, CALL, &info, LEN)
 				.unwrap();
 			assert_eq!(pre, Some(100));
@@ -523,7 +429,7 @@ mod tests {
 				pays_fee: Default::default(),
 			};
 
-			let pre = StorageWeightReclaim::<Test>(PhantomData)
+			let pre = StorageWeightReclaim::<Test>::new()
 				.pre_dispatch(&ALICE, CALL, &info, LEN)
 				.unwrap();
 			assert_eq!(pre, Some(100));
@@ -23,6 +23,7 @@
 
 use itertools::Itertools;
 use jaeger::{hash_to_trace_identifier, PerLeafSpan};
+use parity_scale_codec::Encode;
 use polkadot_node_jaeger as jaeger;
 use polkadot_node_primitives::{
 	approval::{
@@ -54,10 +55,10 @@ use polkadot_node_subsystem_util::{
 	TimeoutExt,
 };
 use polkadot_primitives::{
-	ApprovalVoteMultipleCandidates, ApprovalVotingParams, BlockNumber, CandidateHash,
-	CandidateIndex, CandidateReceipt, CoreIndex, DisputeStatement, ExecutorParams, GroupIndex,
-	Hash, PvfExecKind, SessionIndex, SessionInfo, ValidDisputeStatementKind, ValidatorId,
-	ValidatorIndex, ValidatorPair, ValidatorSignature,
+	vstaging::CandidateStatsStatement, ApprovalVoteMultipleCandidates, ApprovalVotingParams,
+	BlockNumber, CandidateHash, CandidateIndex, CandidateReceipt, CoreIndex, DisputeStatement,
+	ExecutorParams, GroupIndex, Hash, PvfExecKind, SessionIndex, SessionInfo,
+	ValidDisputeStatementKind, ValidatorId, ValidatorIndex, ValidatorPair, ValidatorSignature,
 };
 use sc_keystore::LocalKeystore;
 use sp_application_crypto::Pair;
@@ -78,7 +79,7 @@ use std::{
 		btree_map::Entry as BTMEntry, hash_map::Entry as HMEntry, BTreeMap, HashMap, HashSet,
 	},
 	sync::Arc,
-	time::Duration,
+	time::{Duration, Instant},
 };
 
 use schnellru::{ByLength, LruMap};
@@ -132,6 +133,9 @@ pub struct Config {
 	/// The slot duration of the consensus algorithm, in milliseconds. Should be evenly
 	/// divisible by 500.
 	pub slot_duration_millis: u64,
+	/// Whether to submit the size of the PoV and the execution time of the candidates this node
+	/// approves to the runtime, which aggregates them into the parachain statistics.
+	pub report_candidate_stats: bool,
 }
 
 // The mode of the approval voting subsystem. It should start in a `Syncing` mode when it first
@@ -156,6 +160,7 @@ pub struct ApprovalVotingSubsystem {
 	keystore: Arc<LocalKeystore>,
 	db_config: DatabaseConfig,
 	slot_duration_millis: u64,
+	report_candidate_stats: bool,
 	db: Arc<dyn Database>,
 	mode: Mode,
 	metrics: Metrics,
@@ -468,6 +473,7 @@ impl ApprovalVotingSubsystem {
 		ApprovalVotingSubsystem {
 			keystore,
 			slot_duration_millis: config.slot_duration_millis,
+			report_candidate_stats: config.report_candidate_stats,
 			db,
 			db_config: DatabaseConfig { col_approval_data: config.col_approval_data },
 			mode: Mode::Syncing(sync_oracle),
@@ -785,6 +791,7 @@ where
 struct State {
 	keystore: Arc<LocalKeystore>,
 	slot_duration_millis: u64,
+	report_candidate_stats: bool,
 	clock: Box<dyn Clock + Send + Sync>,
 	assignment_criteria: Box<dyn AssignmentCriteria + Send + Sync>,
 	spans: HashMap<Hash, jaeger::PerLeafSpan>,
@@ -938,6 +945,7 @@ where
 	let mut state = State {
 		keystore: subsystem.keystore,
 		slot_duration_millis: subsystem.slot_duration_millis,
+		report_candidate_stats: subsystem.report_candidate_stats,
 		clock: subsystem.clock,
 		assignment_criteria,
 		spans: HashMap::new(),
@@ -1211,6 +1219,22 @@ async fn handle_actions<Context>(
 						actions_iter = new_actions.into_iter();
 					},
 					None => {
+						// The key the statistics of the candidate are signed with, if reported.
+						let stats_key = if state.report_candidate_stats {
+							get_session_info(
+								session_info_provider,
+								ctx.sender(),
+								relay_block_hash,
+								session,
+							)
+							.await
+							.and_then(|session_info| session_info.validators.get(validator_index))
+							.and_then(|public| {
+								state.keystore.key_pair::<ValidatorPair>(public).ok().flatten()
+							})
+						} else {
+							None
+						};
 						let ctx = &mut *ctx;
 
 						currently_checking_set
@@ -1228,6 +1252,7 @@ async fn handle_actions<Context>(
 										block_hash,
 										backing_group,
 										executor_params,
+										stats_key,
 										&launch_approval_span,
 									)
 									.await
@@ -2149,7 +2174,12 @@ fn schedule_wakeup_action(
 				last_assignment_tick.map(|l| l + APPROVAL_DELAY).filter(|t| t > &tick_now),
 				next_no_show,
 			)
-			.map(|tick| Action::ScheduleWakeup { block_hash, block_number, candidate_hash, tick })
+			.map(|tick| Action::ScheduleWakeup {
+				block_hash,
+				block_number,
+				candidate_hash,
+				tick,
+			})
 		},
 		RequiredTranches::Pending { considered, next_no_show, clock_drift, .. } => {
 			// select the minimum of `next_no_show`, or the tick of the next non-empty tranche
@@ -3084,6 +3114,7 @@ async fn launch_approval<Context>(
 	block_hash: Hash,
 	backing_group: GroupIndex,
 	executor_params: ExecutorParams,
+	stats_key: Option<ValidatorPair>,
 	span: &jaeger::Span,
 ) -> SubsystemResult<RemoteHandle<ApprovalState>> {
 	let (a_tx, a_rx) = oneshot::channel();
@@ -3222,6 +3253,8 @@ async fn launch_approval<Context>(
 			},
 		};
 
+		let pov_size = available_data.pov.encoded_size();
+		let validation_started = Instant::now();
 		let (val_tx, val_rx) = oneshot::channel();
 		sender
 			.send_message(CandidateValidationMessage::ValidateFromExhaustive {
@@ -3243,6 +3276,27 @@ async fn launch_approval<Context>(
 
 				gum::trace!(target: LOG_TARGET, ?candidate_hash, ?para_id, "Candidate Valid");
 
+				if let Some(key) = stats_key {
+					submit_candidate_stats(
+						&mut sender,
+						&key,
+						block_hash,
+						CandidateStatsStatement {
+							session_index,
+							validator_index,
+							candidate_hash,
+							pov_size: pov_size.try_into().unwrap_or(u32::MAX),
+							execution_weight: validation_started
+								.elapsed()
+								.as_nanos()
+								.saturating_mul(1_000)
+								.try_into()
+								.unwrap_or(u64::MAX),
+						},
+					)
+					.await;
+				}
+
 				let _ = metrics_guard.take();
 				return ApprovalState::approved(validator_index, candidate_hash)
 			},
@@ -3599,6 +3653,30 @@ fn sign_approval(
 	Some(key.sign(&payload[..]))
 }
 
+// Sign the statistics of an approved candidate and submit them to the runtime. The execution
+// weight is the time the validation took, so it includes the queueing in the PVF host; the
+// runtime records the median of the reports of several checkers.
+async fn submit_candidate_stats<Sender>(
+	sender: &mut Sender,
+	key: &ValidatorPair,
+	block_hash: Hash,
+	stmt: CandidateStatsStatement,
+) where
+	Sender: overseer::ApprovalVotingSenderTrait,
+{
+	let signature = key.sign(&stmt.signing_payload());
+
+	// The outcome isn't awaited: the statistics are best-effort and the runtime may not support
+	// them yet.
+	let (tx, _) = oneshot::channel();
+	sender
+		.send_message(RuntimeApiMessage::Request(
+			block_hash,
+			RuntimeApiRequest::SubmitCandidateStats(stmt, signature, tx),
+		))
+		.await;
+}
+
 /// Send `IssueLocalStatement` to dispute-coordinator.
 fn issue_local_invalid_statement<Sender>(
 	sender: &mut Sender,
@@ -183,6 +183,7 @@ where
 			ClaimQueue(relay_parent, sender) => {
 				self.requests_cache.cache_claim_queue(relay_parent, sender);
 			},
+			SubmitCandidateStats(_, _, _, ()) => {},
 		}
 	}
 
@@ -340,6 +341,10 @@ where
 			},
 			Request::ClaimQueue(sender) =>
 				query!(claim_queue(), sender).map(|sender| Request::ClaimQueue(sender)),
+			request @ Request::SubmitCandidateStats(_, _, _) => {
+				// This request is side-effecting and thus cannot be cached.
+				Some(request)
+			},
 		}
 	}
 
@@ -649,5 +654,11 @@ where
 			ver = Request::CLAIM_QUEUE_RUNTIME_REQUIREMENT,
 			sender
 		),
+		Request::SubmitCandidateStats(stmt, signature, sender) => query!(
+			SubmitCandidateStats,
+			submit_candidate_stats(stmt, signature),
+			ver = Request::SUBMIT_CANDIDATE_STATS_RUNTIME_REQUIREMENT,
+			sender
+		),
 	}
 }
@@ -20,7 +20,7 @@ use polkadot_node_primitives::{BabeAllowedSlots, BabeEpoch, BabeEpochConfigurati
 use polkadot_node_subsystem::SpawnGlue;
 use polkadot_node_subsystem_test_helpers::make_subsystem_context;
 use polkadot_primitives::{
-	async_backing, slashing, ApprovalVotingParams, AuthorityDiscoveryId, BlockNumber,
+	async_backing, slashing, vstaging, ApprovalVotingParams, AuthorityDiscoveryId, BlockNumber,
 	CandidateCommitments, CandidateEvent, CandidateHash, CommittedCandidateReceipt, CoreIndex,
 	CoreState, DisputeState, ExecutorParams, GroupRotationInfo, Id as ParaId,
 	InboundDownwardMessage, InboundHrmpMessage, NodeFeatures, OccupiedCoreAssumption,
@@ -301,6 +301,15 @@ impl RuntimeApiSubsystemClient for MockSubsystemClient {
 	) -> Result<BTreeMap<CoreIndex, VecDeque<ParaId>>, ApiError> {
 		todo!("Not required for tests")
 	}
+
+	async fn submit_candidate_stats(
+		&self,
+		_: Hash,
+		_: vstaging::CandidateStatsStatement,
+		_: ValidatorSignature,
+	) -> Result<(), ApiError> {
+		todo!("Not required for tests")
+	}
 }
 
 #[test]
@@ -49,14 +49,14 @@ use polkadot_node_subsystem_util::{
 	backing_implicit_view::View as ImplicitView,
 	reputation::{ReputationAggregator, REPUTATION_CHANGE_INTERVAL},
 	runtime::{
-		get_availability_cores, get_group_rotation_info, prospective_parachains_mode,
-		ProspectiveParachainsMode, RuntimeInfo,
+		get_availability_cores, get_candidate_events, get_group_rotation_info,
+		prospective_parachains_mode, ProspectiveParachainsMode, RuntimeInfo,
 	},
 	TimeoutExt,
 };
 use polkadot_primitives::{
-	AuthorityDiscoveryId, CandidateHash, CandidateReceipt, CollatorPair, CoreIndex, CoreState,
-	GroupIndex, Hash, HeadData, Id as ParaId, SessionIndex,
+	AuthorityDiscoveryId, CandidateEvent, CandidateHash, CandidateReceipt, CollatorPair, CoreIndex,
+	CoreState, GroupIndex, Hash, HeadData, Id as ParaId, SessionIndex,
 };
 
 use super::LOG_TARGET;
@@ -69,12 +69,14 @@ mod collation;
 mod metrics;
 #[cfg(test)]
 mod tests;
+mod timeline;
 mod validators_buffer;
 
 use collation::{
 	ActiveCollationFetches, Collation, CollationSendResult, CollationStatus,
 	VersionedCollationRequest, WaitingCollationFetches,
 };
+use timeline::Timelines;
 use validators_buffer::{
 	ResetInterestTimeout, ValidatorGroupsBuffer, RESET_INTEREST_TIMEOUT, VALIDATORS_BUFFER_CAPACITY,
 };
@@ -318,6 +320,9 @@ struct State {
 
 	/// Aggregated reputation change
 	reputation: ReputationAggregator,
+
+	/// Timelines of the recent collations.
+	timelines: Timelines,
 }
 
 impl State {
@@ -347,6 +352,7 @@ impl State {
 			active_collation_fetches: Default::default(),
 			advertisement_timeouts: Default::default(),
 			reputation,
+			timelines: Default::default(),
 		}
 	}
 }
@@ -514,6 +520,10 @@ async fn distribute_collation<Context>(
 		state.collation_result_senders.insert(candidate_hash, result_sender);
 	}
 
+	state
+		.timelines
+		.on_built(candidate_hash, candidate_relay_parent, receipt.descriptor.para_head);
+
 	let parent_head_data = if elastic_scaling {
 		ParentHeadData::WithData { hash: parent_head_data_hash, head_data: parent_head_data }
 	} else {
@@ -558,6 +568,7 @@ async fn distribute_collation<Context>(
 			peer_data.version,
 			&state.peer_ids,
 			&mut state.advertisement_timeouts,
+			&mut state.timelines,
 			&state.metrics,
 		)
 		.await;
@@ -729,6 +740,7 @@ async fn advertise_collation<Context>(
 	protocol_version: CollationVersion,
 	peer_ids: &HashMap<PeerId, HashSet<AuthorityDiscoveryId>>,
 	advertisement_timeouts: &mut FuturesUnordered<ResetInterestTimeout>,
+	timelines: &mut Timelines,
 	metrics: &Metrics,
 ) {
 	for (candidate_hash, collation_and_core) in per_relay_parent.collations.iter_mut() {
@@ -807,6 +819,7 @@ async fn advertise_collation<Context>(
 		.await;
 
 		validator_group.advertised_to_peer(candidate_hash, &peer_ids, peer);
+		timelines.on_announced(candidate_hash);
 
 		advertisement_timeouts.push(ResetInterestTimeout::new(
 			*candidate_hash,
@@ -895,6 +908,9 @@ async fn process_msg<Context>(
 				);
 			}
 		},
+		GetCollationTimelines(tx) => {
+			let _ = tx.send(state.timelines.snapshot());
+		},
 		msg @ (ReportCollator(..) | Invalid(..) | Seconded(..) | Backed { .. }) => {
 			gum::warn!(
 				target: LOG_TARGET,
@@ -957,6 +973,7 @@ async fn send_collation(
 		.boxed(),
 	);
 
+	state.timelines.on_fetched(&candidate_hash);
 	state.metrics.on_collation_sent();
 }
 
@@ -1018,6 +1035,8 @@ async fn handle_incoming_peer_message<Context>(
 					.await?
 					.map_err(Error::InvalidStatementSignature)?;
 
+				state.timelines.on_seconded(&statement.payload().candidate_hash());
+
 				let removed =
 					state.collation_result_senders.remove(&statement.payload().candidate_hash());
 
@@ -1242,6 +1261,7 @@ async fn handle_peer_view_change<Context>(
 				*version,
 				&state.peer_ids,
 				&mut state.advertisement_timeouts,
+				&mut state.timelines,
 				&state.metrics,
 			)
 			.await;
@@ -1328,6 +1348,35 @@ async fn handle_network_msg<Context>(
 	Ok(())
 }
 
+/// Record the collations backed in the relay chain block `leaf` in their timelines.
+async fn track_backed_candidates<Sender>(sender: &mut Sender, timelines: &mut Timelines, leaf: Hash)
+where
+	Sender: CollatorProtocolSenderTrait,
+{
+	if !timelines.has_pending_backing() {
+		return
+	}
+
+	let events = match get_candidate_events(sender, leaf).await {
+		Ok(events) => events,
+		Err(err) => {
+			gum::debug!(
+				target: LOG_TARGET,
+				?leaf,
+				?err,
+				"Failed to fetch candidate events",
+			);
+			return
+		},
+	};
+
+	for event in events {
+		if let CandidateEvent::CandidateBacked(receipt, ..) = event {
+			timelines.on_backed(&receipt.hash(), leaf);
+		}
+	}
+}
+
 /// Handles our view changes.
 async fn handle_our_view_change<Sender>(
 	sender: &mut Sender,
@@ -1490,8 +1539,9 @@ async fn run_inner<Context>(
 					)?;
 				},
 				FromOrchestra::Signal(ActiveLeaves(update)) => {
-					if update.activated.is_some() {
+					if let Some(activated) = update.activated {
 						*reconnect_timeout = futures_timer::Delay::new(RECONNECT_AFTER_LEAF_TIMEOUT).fuse();
+						track_backed_candidates(ctx.sender(), &mut state.timelines, activated.hash).await;
 					}
 				}
 				FromOrchestra::Signal(BlockFinalized(..)) => {}
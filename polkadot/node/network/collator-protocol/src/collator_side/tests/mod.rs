@@ -1023,6 +1023,71 @@ fn advertise_collation_v2_protocol() {
 	);
 }
 
+#[test]
+fn collation_timelines_are_reported() {
+	let test_state = TestState::default();
+	let local_peer_id = test_state.local_peer_id;
+	let collator_pair = test_state.collator_pair.clone();
+
+	test_harness(
+		local_peer_id,
+		collator_pair,
+		ReputationAggregator::new(|_| true),
+		|mut test_harness| async move {
+			let virtual_overseer = &mut test_harness.virtual_overseer;
+
+			setup_system(virtual_overseer, &test_state).await;
+
+			let DistributeCollation { candidate, .. } =
+				distribute_collation(virtual_overseer, &test_state, test_state.relay_parent, true)
+					.await;
+
+			let (tx, rx) = oneshot::channel();
+			overseer_send(virtual_overseer, CollatorProtocolMessage::GetCollationTimelines(tx))
+				.await;
+			let timelines = rx.await.unwrap();
+			assert_eq!(timelines.len(), 1);
+			assert_eq!(timelines[0].candidate_hash, candidate.hash());
+			assert_eq!(timelines[0].relay_parent, test_state.relay_parent);
+			assert!(timelines[0].announced.is_none());
+			assert!(timelines[0].backed.is_none());
+
+			// The candidate gets backed in the next relay chain block.
+			let leaf = Hash::repeat_byte(0xAA);
+			overseer_signal(
+				virtual_overseer,
+				OverseerSignal::ActiveLeaves(ActiveLeavesUpdate::start_work(new_leaf(leaf, 2))),
+			)
+			.await;
+
+			assert_matches!(
+				overseer_recv(virtual_overseer).await,
+				AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+					relay_parent,
+					RuntimeApiRequest::CandidateEvents(tx)
+				)) => {
+					assert_eq!(relay_parent, leaf);
+					tx.send(Ok(vec![CandidateEvent::CandidateBacked(
+						candidate.clone(),
+						HeadData(vec![1, 2, 3]),
+						CoreIndex(0),
+						GroupIndex(0),
+					)]))
+					.unwrap();
+				}
+			);
+
+			let (tx, rx) = oneshot::channel();
+			overseer_send(virtual_overseer, CollatorProtocolMessage::GetCollationTimelines(tx))
+				.await;
+			let timelines = rx.await.unwrap();
+			assert_matches!(timelines[0].backed, Some((_, block_hash)) if block_hash == leaf);
+
+			test_harness
+		},
+	);
+}
+
 #[test]
 #[allow(clippy::async_yields_async)]
 fn send_only_one_collation_per_relay_parent_at_a_time() {
@@ -0,0 +1,100 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Timelines of the recent collations, from being built to being backed.
+//!
+//! Only the first occurrence of each event is recorded, e.g. the first validator fetching the
+//! collation. Collations are evicted in FIFO order once [`TIMELINES_CAPACITY`] is reached.
+
+use std::{collections::VecDeque, time::SystemTime};
+
+use polkadot_node_primitives::CollationTimeline;
+use polkadot_primitives::{CandidateHash, Hash};
+
+/// Maximum number of collations a timeline is kept for.
+pub const TIMELINES_CAPACITY: usize = 64;
+
+/// Timelines of the most recent collations, oldest first.
+#[derive(Default)]
+pub struct Timelines(VecDeque<CollationTimeline>);
+
+impl Timelines {
+	/// Start the timeline of a collation that was just built.
+	pub fn on_built(&mut self, candidate_hash: CandidateHash, relay_parent: Hash, para_head: Hash) {
+		if self.0.iter().any(|t| t.candidate_hash == candidate_hash) {
+			return
+		}
+
+		if self.0.len() == TIMELINES_CAPACITY {
+			self.0.pop_front();
+		}
+
+		self.0.push_back(CollationTimeline {
+			candidate_hash,
+			relay_parent,
+			para_head,
+			built: SystemTime::now(),
+			announced: None,
+			fetched: None,
+			seconded: None,
+			backed: None,
+		});
+	}
+
+	/// The collation was advertised to a validator.
+	pub fn on_announced(&mut self, candidate_hash: &CandidateHash) {
+		self.update(candidate_hash, |t| {
+			t.announced.get_or_insert_with(SystemTime::now);
+		});
+	}
+
+	/// The collation was sent to a validator.
+	pub fn on_fetched(&mut self, candidate_hash: &CandidateHash) {
+		self.update(candidate_hash, |t| {
+			t.fetched.get_or_insert_with(SystemTime::now);
+		});
+	}
+
+	/// A validator reported having seconded the collation.
+	pub fn on_seconded(&mut self, candidate_hash: &CandidateHash) {
+		self.update(candidate_hash, |t| {
+			t.seconded.get_or_insert_with(SystemTime::now);
+		});
+	}
+
+	/// The candidate was backed in the relay chain block `block_hash`.
+	pub fn on_backed(&mut self, candidate_hash: &CandidateHash, block_hash: Hash) {
+		self.update(candidate_hash, |t| {
+			t.backed.get_or_insert_with(|| (SystemTime::now(), block_hash));
+		});
+	}
+
+	/// Whether any of the collations is yet to be seen backed.
+	pub fn has_pending_backing(&self) -> bool {
+		self.0.iter().any(|t| t.backed.is_none())
+	}
+
+	/// The timelines of the recent collations, oldest first.
+	pub fn snapshot(&self) -> Vec<CollationTimeline> {
+		self.0.iter().cloned().collect()
+	}
+
+	fn update(&mut self, candidate_hash: &CandidateHash, f: impl FnOnce(&mut CollationTimeline)) {
+		if let Some(timeline) = self.0.iter_mut().find(|t| &t.candidate_hash == candidate_hash) {
+			f(timeline)
+		}
+	}
+}
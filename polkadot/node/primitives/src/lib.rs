@@ -446,6 +446,32 @@ pub struct CollationSecondedSignal {
 	pub statement: SignedFullStatement,
 }
 
+/// Timeline of a collation distributed by the local collator, from its distribution to its
+/// backing.
+///
+/// The times are the ones at which the collator observed the events.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg(not(target_os = "unknown"))]
+pub struct CollationTimeline {
+	/// The hash of the candidate.
+	pub candidate_hash: CandidateHash,
+	/// The relay chain block the collation was built on.
+	pub relay_parent: Hash,
+	/// The hash of the head data produced by the candidate.
+	pub para_head: Hash,
+	/// When the collation was handed to the collator protocol, right after it was built.
+	pub built: std::time::SystemTime,
+	/// When the collation was first advertised to a validator.
+	pub announced: Option<std::time::SystemTime>,
+	/// When the collation was first sent to a validator requesting it.
+	pub fetched: Option<std::time::SystemTime>,
+	/// When a validator first reported having seconded the collation.
+	pub seconded: Option<std::time::SystemTime>,
+	/// When the candidate was first seen backed, along with the relay chain block it was backed
+	/// in.
+	pub backed: Option<(std::time::SystemTime, Hash)>,
+}
+
 /// Result of the [`CollatorFn`] invocation.
 #[cfg(not(target_os = "unknown"))]
 pub struct CollationResult {
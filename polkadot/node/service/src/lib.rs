@@ -524,6 +524,8 @@ where
 		grandpa_hard_forks,
 		telemetry.as_ref().map(|x| x.handle()),
 	)?;
+	let grandpa_block_import =
+		grandpa_block_import.with_justification_verifier(config.justification_verifier);
 	let justification_import = grandpa_block_import.clone();
 
 	let (beefy_block_import, beefy_voter_links, beefy_rpc_links) =
@@ -533,6 +535,8 @@ where
 			client.clone(),
 			config.prometheus_registry().cloned(),
 		);
+	let beefy_block_import =
+		beefy_block_import.with_justification_verifier(config.justification_verifier);
 
 	let babe_config = babe::configuration(&*client)?;
 	let (block_import, babe_link) =
@@ -914,11 +918,14 @@ pub fn new_full<
 		Vec::new()
 	};
 
-	let warp_sync = Arc::new(grandpa::warp_proof::NetworkProvider::new(
-		backend.clone(),
-		import_setup.1.shared_authority_set().clone(),
-		grandpa_hard_forks,
-	));
+	let warp_sync = Arc::new(
+		grandpa::warp_proof::NetworkProvider::new(
+			backend.clone(),
+			import_setup.1.shared_authority_set().clone(),
+			grandpa_hard_forks,
+		)
+		.with_justification_verifier(config.justification_verifier),
+	);
 
 	let ext_overseer_args = if is_parachain_node.is_running_alongside_parachain_node() {
 		None
@@ -959,6 +966,7 @@ pub fn new_full<
 		let approval_voting_config = ApprovalVotingConfig {
 			col_approval_data: parachains_db::REAL_COLUMNS.col_approval_data,
 			slot_duration_millis: slot_duration.as_millis() as u64,
+			report_candidate_stats: true,
 		};
 		let dispute_coordinator_config = DisputeCoordinatorConfig {
 			col_dispute_data: parachains_db::REAL_COLUMNS.col_dispute_coordinator_data,
@@ -998,6 +1006,19 @@ pub fn new_full<
 			metrics,
 		})?;
 
+	if role.is_authority() {
+		// The offchain workers signal the version of the node, which gates the runtime upgrades.
+		if let Some(mut offchain_db) = backend.offchain_storage() {
+			use sp_core::offchain::OffchainStorage;
+
+			offchain_db.set(
+				sp_core::offchain::STORAGE_PREFIX,
+				polkadot_primitives::vstaging::NODE_VERSION_OFFCHAIN_KEY,
+				polkadot_node_primitives::NODE_VERSION.as_bytes(),
+			);
+		}
+	}
+
 	if config.offchain_worker.enabled {
 		use futures::FutureExt;
 
@@ -1476,6 +1497,7 @@ fn revert_approval_voting(db: Arc<dyn Database>, hash: Hash) -> sp_blockchain::R
 	let config = approval_voting_subsystem::Config {
 		col_approval_data: parachains_db::REAL_COLUMNS.col_approval_data,
 		slot_duration_millis: Default::default(),
+		report_candidate_stats: false,
 	};
 
 	let approval_voting = approval_voting_subsystem::ApprovalVotingSubsystem::with_config(
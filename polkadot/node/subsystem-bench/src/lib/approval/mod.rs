@@ -93,6 +93,7 @@ pub(crate) const SLOT_DURATION_MILLIS: u64 = 6000;
 pub(crate) const TEST_CONFIG: ApprovalVotingConfig = ApprovalVotingConfig {
 	col_approval_data: DATA_COL,
 	slot_duration_millis: SLOT_DURATION_MILLIS,
+	report_candidate_stats: false,
 };
 
 const DATA_COL: u32 = 0;
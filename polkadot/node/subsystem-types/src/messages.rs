@@ -37,12 +37,12 @@ use polkadot_node_primitives::{
 		v2::{CandidateBitfield, IndirectAssignmentCertV2, IndirectSignedApprovalVoteV2},
 	},
 	AvailableData, BabeEpoch, BlockWeight, CandidateVotes, CollationGenerationConfig,
-	CollationSecondedSignal, DisputeMessage, DisputeStatus, ErasureChunk, PoV,
+	CollationSecondedSignal, CollationTimeline, DisputeMessage, DisputeStatus, ErasureChunk, PoV,
 	SignedDisputeStatement, SignedFullStatement, SignedFullStatementWithPVD, SubmitCollationParams,
 	ValidationResult,
 };
 use polkadot_primitives::{
-	async_backing, slashing, ApprovalVotingParams, AuthorityDiscoveryId, BackedCandidate,
+	async_backing, slashing, vstaging, ApprovalVotingParams, AuthorityDiscoveryId, BackedCandidate,
 	BlockNumber, CandidateEvent, CandidateHash, CandidateIndex, CandidateReceipt, CollatorId,
 	CommittedCandidateReceipt, CoreIndex, CoreState, DisputeState, ExecutorParams, GroupIndex,
 	GroupRotationInfo, Hash, HeadData, Header as BlockHeader, Id as ParaId, InboundDownwardMessage,
@@ -251,6 +251,11 @@ pub enum CollatorProtocolMessage {
 		/// Hash of the para head generated by candidate.
 		para_head: Hash,
 	},
+	/// Get the timelines of the most recent collations distributed by the local collator, oldest
+	/// first.
+	///
+	/// Only answered by the collator side of the protocol.
+	GetCollationTimelines(oneshot::Sender<Vec<CollationTimeline>>),
 }
 
 impl Default for CollatorProtocolMessage {
@@ -742,6 +747,13 @@ pub enum RuntimeApiRequest {
 	/// Get the candidates pending availability for a particular parachain
 	/// `V11`
 	CandidatesPendingAvailability(ParaId, RuntimeApiSender<Vec<CommittedCandidateReceipt>>),
+	/// Submits the statistics of an enacted candidate into the transaction pool.
+	/// `V13`
+	SubmitCandidateStats(
+		vstaging::CandidateStatsStatement,
+		ValidatorSignature,
+		RuntimeApiSender<()>,
+	),
 }
 
 impl RuntimeApiRequest {
@@ -782,6 +794,9 @@ impl RuntimeApiRequest {
 
 	/// `candidates_pending_availability`
 	pub const CANDIDATES_PENDING_AVAILABILITY_RUNTIME_REQUIREMENT: u32 = 11;
+
+	/// `SubmitCandidateStats`
+	pub const SUBMIT_CANDIDATE_STATS_RUNTIME_REQUIREMENT: u32 = 13;
 }
 
 /// A message to the Runtime API subsystem.
@@ -114,9 +114,9 @@
 //! separated from the stable primitives.
 
 use crate::{
-	async_backing, slashing, ApprovalVotingParams, AsyncBackingParams, BlockNumber,
+	async_backing, slashing, vstaging, ApprovalVotingParams, AsyncBackingParams, BlockNumber,
 	CandidateCommitments, CandidateEvent, CandidateHash, CommittedCandidateReceipt, CoreIndex,
-	CoreState, DisputeState, ExecutorParams, GroupRotationInfo, Hash, NodeFeatures,
+	CoreState, DisputeState, ExecutorParams, GroupRotationInfo, Hash, HrmpChannelId, NodeFeatures,
 	OccupiedCoreAssumption, PersistedValidationData, PvfCheckStatement, ScrapedOnChainVotes,
 	SessionIndex, SessionInfo, ValidatorId, ValidatorIndex, ValidatorSignature,
 };
@@ -293,5 +293,21 @@ sp_api::decl_runtime_apis! {
 		/// Elastic scaling support
 		#[api_version(11)]
 		fn candidates_pending_availability(para_id: ppp::Id) -> Vec<CommittedCandidateReceipt<Hash>>;
+
+		/***** Added in v12 *****/
+		/// Returns the moving averages of the traffic going through the given HRMP channel.
+		///
+		/// Returns `None` if the channel doesn't exist.
+		#[api_version(12)]
+		fn hrmp_channel_usage(channel: HrmpChannelId) -> Option<vstaging::HrmpChannelUsage>;
+
+		/***** Added in v13 *****/
+		/// Submits the statistics of an enacted candidate measured by a validator into the
+		/// transaction pool.
+		#[api_version(13)]
+		fn submit_candidate_stats(
+			stmt: vstaging::CandidateStatsStatement,
+			signature: ValidatorSignature,
+		);
 	}
 }
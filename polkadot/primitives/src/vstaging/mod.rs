@@ -23,7 +23,7 @@ use sp_std::prelude::*;
 use parity_scale_codec::{Decode, Encode};
 use primitives::RuntimeDebug;
 use scale_info::TypeInfo;
-use sp_arithmetic::Perbill;
+use sp_arithmetic::{FixedU128, Perbill};
 
 /// Scheduler configuration parameters. All coretime/ondemand parameters are here.
 #[derive(
@@ -99,3 +99,49 @@ impl<BlockNumber: Default + From<u32>> Default for SchedulerParams<BlockNumber>
 		}
 	}
 }
+
+/// Traffic going through an HRMP channel, averaged over recent blocks.
+///
+/// The values are exponential moving averages over
+/// [`HRMP_USAGE_AVERAGING_WINDOW`] blocks. Blocks in which no message was sent over the channel
+/// count as zero.
+#[derive(RuntimeDebug, Copy, Clone, Default, PartialEq, Encode, Decode, TypeInfo)]
+pub struct HrmpChannelUsage {
+	/// Average number of messages sent per block.
+	pub msg_count_per_block: FixedU128,
+	/// Average number of bytes sent per block.
+	pub total_size_per_block: FixedU128,
+}
+
+/// The number of blocks the [`HrmpChannelUsage`] moving averages are computed over.
+pub const HRMP_USAGE_AVERAGING_WINDOW: u32 = 600;
+
+/// The size of the PoV and the execution weight of an enacted candidate, as measured by a
+/// validator which checked it.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct CandidateStatsStatement {
+	/// The session the candidate was enacted in.
+	pub session_index: SessionIndex,
+	/// The index of the reporting validator in the active validator set.
+	pub validator_index: ValidatorIndex,
+	/// The hash of the enacted candidate.
+	pub candidate_hash: CandidateHash,
+	/// The size of the PoV of the candidate, in bytes.
+	pub pov_size: u32,
+	/// The time the validator spent executing the candidate, in picoseconds of reference time.
+	pub execution_weight: u64,
+}
+
+impl CandidateStatsStatement {
+	/// Yields the signing payload for this statement.
+	pub fn signing_payload(&self) -> Vec<u8> {
+		const MAGIC: [u8; 4] = *b"PSTA";
+		(MAGIC, self.clone()).encode()
+	}
+}
+
+/// The key of the version of the node in the persistent offchain storage of the validators.
+///
+/// The node stores its version as a `major.minor.patch` string under this key, for the offchain
+/// workers of the runtime to signal it.
+pub const NODE_VERSION_OFFCHAIN_KEY: &[u8] = b"parachains::node_version";
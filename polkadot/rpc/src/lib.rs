@@ -138,8 +138,20 @@ where
 	let chain_name = chain_spec.name().to_string();
 	let genesis_hash = client.hash(0).ok().flatten().expect("Genesis block exists; qed");
 	let properties = chain_spec.properties();
+	let fork_id = chain_spec.fork_id().map(ToOwned::to_owned);
+	let sync_state = SyncState::new(
+		chain_spec,
+		client.clone(),
+		shared_authority_set.clone(),
+		babe_worker_handle.clone(),
+	)?;
 
-	io.merge(ChainSpec::new(chain_name, genesis_hash, properties).into_rpc())?;
+	io.merge(
+		ChainSpec::new(chain_name, genesis_hash, properties)
+			.with_fork_id(fork_id)
+			.with_light_sync_state(Arc::new(sync_state.clone()))
+			.into_rpc(),
+	)?;
 	io.merge(StateMigration::new(client.clone(), backend.clone(), deny_unsafe).into_rpc())?;
 	io.merge(System::new(client.clone(), pool.clone(), deny_unsafe).into_rpc())?;
 	io.merge(TransactionPayment::new(client.clone()).into_rpc())?;
@@ -166,9 +178,7 @@ where
 		)
 		.into_rpc(),
 	)?;
-	io.merge(
-		SyncState::new(chain_spec, client, shared_authority_set, babe_worker_handle)?.into_rpc(),
-	)?;
+	io.merge(sync_state.into_rpc())?;
 
 	io.merge(
 		Beefy::<Block>::new(
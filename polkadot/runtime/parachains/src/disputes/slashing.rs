@@ -41,14 +41,19 @@
 //! chain, without `FullIdentification` of the offenders. Later on, a block
 //! producer can submit an unsigned transaction with `KeyOwnershipProof` of an
 //! offender and submit it to the runtime to produce an offence.
+//!
+//! Whether the offenders are also disabled is decided by the runtime's
+//! [`DisablingPolicy`]. [`ConfigurableDisabling`] follows the
+//! [`DisablingParams`] set by governance.
 
-use crate::{disputes, initializer::ValidatorSetCount, session_info::IdentificationTuple};
+use crate::{disputes, initializer::ValidatorSetCount, session_info::IdentificationTuple, shared};
 use frame_support::{
 	dispatch::Pays,
 	traits::{Defensive, Get, KeyOwnerProofSystem, ValidatorSet, ValidatorSetWithIdentification},
 	weights::Weight,
 };
 use frame_system::pallet_prelude::BlockNumberFor;
+use parity_scale_codec::{Decode, Encode};
 
 use primitives::{
 	slashing::{DisputeProof, DisputesTimeSlot, PendingSlashes, SlashingOffenceKind},
@@ -61,7 +66,7 @@ use sp_runtime::{
 		InvalidTransaction, TransactionPriority, TransactionSource, TransactionValidity,
 		TransactionValidityError, ValidTransaction,
 	},
-	KeyTypeId, Perbill,
+	KeyTypeId, Perbill, RuntimeDebug,
 };
 use sp_session::{GetSessionNumber, GetValidatorCount};
 use sp_staking::offence::{DisableStrategy, Kind, Offence, OffenceError, ReportOffence};
@@ -108,6 +113,8 @@ pub struct SlashingOffence<KeyOwnerIdentification> {
 	pub slash_fraction: Perbill,
 	/// Whether the candidate was valid or invalid.
 	pub kind: SlashingOffenceKind,
+	/// Whether the offenders should be disabled.
+	pub disable_strategy: DisableStrategy,
 }
 
 impl<Offender> Offence<Offender> for SlashingOffence<Offender>
@@ -135,12 +142,7 @@ where
 	}
 
 	fn disable_strategy(&self) -> DisableStrategy {
-		match self.kind {
-			SlashingOffenceKind::ForInvalid => DisableStrategy::Always,
-			// in the future we might change it based on number of disputes initiated:
-			// <https://github.com/paritytech/polkadot/issues/5946>
-			SlashingOffenceKind::AgainstValid => DisableStrategy::Never,
-		}
+		self.disable_strategy
 	}
 
 	fn slash_fraction(&self, _offenders: u32) -> Perbill {
@@ -155,13 +157,106 @@ impl<KeyOwnerIdentification> SlashingOffence<KeyOwnerIdentification> {
 		validator_set_count: ValidatorSetCount,
 		offenders: Vec<KeyOwnerIdentification>,
 		kind: SlashingOffenceKind,
+		disable_strategy: DisableStrategy,
 	) -> Self {
 		let time_slot = DisputesTimeSlot::new(session_index, candidate_hash);
 		let slash_fraction = match kind {
 			SlashingOffenceKind::ForInvalid => SLASH_FOR_INVALID,
 			SlashingOffenceKind::AgainstValid => SLASH_AGAINST_VALID,
 		};
-		Self { time_slot, validator_set_count, offenders, slash_fraction, kind }
+		Self { time_slot, validator_set_count, offenders, slash_fraction, kind, disable_strategy }
+	}
+}
+
+/// Decides whether validators losing a dispute get disabled.
+pub trait DisablingPolicy {
+	/// The disable strategy for an offence of the given kind committed by `offenders` out of
+	/// `validator_set_count` validators.
+	fn disable_strategy(
+		kind: SlashingOffenceKind,
+		offenders: u32,
+		validator_set_count: ValidatorSetCount,
+	) -> DisableStrategy;
+}
+
+/// Always disables validators backing invalid candidates and never those disputing valid ones.
+pub struct DisableForInvalid;
+
+impl DisablingPolicy for DisableForInvalid {
+	fn disable_strategy(
+		kind: SlashingOffenceKind,
+		_offenders: u32,
+		_validator_set_count: ValidatorSetCount,
+	) -> DisableStrategy {
+		match kind {
+			SlashingOffenceKind::ForInvalid => DisableStrategy::Always,
+			// in the future we might change it based on number of disputes initiated:
+			// <https://github.com/paritytech/polkadot/issues/5946>
+			SlashingOffenceKind::AgainstValid => DisableStrategy::Never,
+		}
+	}
+}
+
+/// Parameters of [`ConfigurableDisabling`], adjustable via governance.
+#[derive(Encode, Decode, TypeInfo, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct DisablingParams {
+	/// Disable strategy for validators backing an invalid candidate.
+	pub for_invalid: DisableStrategy,
+	/// Disable strategy for validators disputing a valid candidate.
+	pub against_valid: DisableStrategy,
+	/// Maximum share of the validator set disabled at the same time.
+	///
+	/// Offenders are not disabled if that would exceed this limit.
+	pub max_disabled: Perbill,
+}
+
+impl Default for DisablingParams {
+	fn default() -> Self {
+		Self {
+			for_invalid: DisableStrategy::Always,
+			against_valid: DisableStrategy::Never,
+			max_disabled: Perbill::one(),
+		}
+	}
+}
+
+impl DisablingParams {
+	/// The disable strategy for an offence of the given kind committed by `offenders` out of
+	/// `validator_set_count` validators, of which `disabled` are already disabled.
+	pub fn disable_strategy(
+		&self,
+		kind: SlashingOffenceKind,
+		offenders: u32,
+		disabled: u32,
+		validator_set_count: ValidatorSetCount,
+	) -> DisableStrategy {
+		if disabled.saturating_add(offenders) > self.max_disabled.mul_floor(validator_set_count) {
+			return DisableStrategy::Never
+		}
+
+		match kind {
+			SlashingOffenceKind::ForInvalid => self.for_invalid,
+			SlashingOffenceKind::AgainstValid => self.against_valid,
+		}
+	}
+}
+
+/// Disabling policy following the [`DisablingParams`] stored on chain.
+pub struct ConfigurableDisabling<T>(sp_std::marker::PhantomData<T>);
+
+impl<T: Config> DisablingPolicy for ConfigurableDisabling<T> {
+	fn disable_strategy(
+		kind: SlashingOffenceKind,
+		offenders: u32,
+		validator_set_count: ValidatorSetCount,
+	) -> DisableStrategy {
+		let disabled = shared::Pallet::<T>::disabled_validators().len() as u32;
+		ActiveDisablingParams::<T>::get().disable_strategy(
+			kind,
+			offenders,
+			disabled,
+			validator_set_count,
+		)
 	}
 }
 
@@ -241,12 +336,18 @@ where
 		let maybe = Self::maybe_identify_validators(session_index, to_punish.iter().cloned());
 		if let Some(offenders) = maybe {
 			let validator_set_count = session_info.discovery_keys.len() as ValidatorSetCount;
+			let disable_strategy = T::DisablingPolicy::disable_strategy(
+				kind,
+				offenders.len() as u32,
+				validator_set_count,
+			);
 			let offence = SlashingOffence::new(
 				session_index,
 				candidate_hash,
 				validator_set_count,
 				offenders,
 				kind,
+				disable_strategy,
 			);
 			// This is the first time we report an offence for this dispute,
 			// so it is not a duplicate.
@@ -363,6 +464,7 @@ impl<T: Config> HandleReports<T> for () {
 
 pub trait WeightInfo {
 	fn report_dispute_lost(validator_count: ValidatorSetCount) -> Weight;
+	fn set_disabling_params() -> Weight;
 }
 
 pub struct TestWeightInfo;
@@ -370,6 +472,9 @@ impl WeightInfo for TestWeightInfo {
 	fn report_dispute_lost(_validator_count: ValidatorSetCount) -> Weight {
 		Weight::zero()
 	}
+	fn set_disabling_params() -> Weight {
+		Weight::zero()
+	}
 }
 
 pub use pallet::*;
@@ -405,6 +510,10 @@ pub mod pallet {
 		/// `ValidateUnsigned` in the runtime definition.
 		type HandleReports: HandleReports<Self>;
 
+		/// Decides whether validators losing a dispute get disabled, e.g.
+		/// [`ConfigurableDisabling`] to let governance adjust it.
+		type DisablingPolicy: DisablingPolicy;
+
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 
@@ -432,6 +541,10 @@ pub mod pallet {
 	pub(super) type ValidatorSetCounts<T> =
 		StorageMap<_, Twox64Concat, SessionIndex, ValidatorSetCount>;
 
+	/// Parameters of the [`ConfigurableDisabling`] policy.
+	#[pallet::storage]
+	pub type ActiveDisablingParams<T> = StorageValue<_, DisablingParams, ValueQuery>;
+
 	#[pallet::error]
 	pub enum Error<T> {
 		/// The key ownership proof is invalid.
@@ -500,12 +613,15 @@ pub mod pallet {
 
 			<UnappliedSlashes<T>>::try_mutate_exists(&session_index, &candidate_hash, try_remove)?;
 
+			let disable_strategy =
+				T::DisablingPolicy::disable_strategy(dispute_proof.kind, 1, validator_set_count);
 			let offence = SlashingOffence::new(
 				session_index,
 				candidate_hash,
 				validator_set_count,
 				vec![offender],
 				dispute_proof.kind,
+				disable_strategy,
 			);
 
 			<T::HandleReports as HandleReports<T>>::report_offence(offence)
@@ -513,6 +629,21 @@ pub mod pallet {
 
 			Ok(Pays::No.into())
 		}
+
+		/// Set the parameters of the [`ConfigurableDisabling`] policy.
+		///
+		/// Only affects offences reported afterwards. Validators already disabled stay
+		/// disabled.
+		#[pallet::call_index(1)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_disabling_params())]
+		pub fn set_disabling_params(
+			origin: OriginFor<T>,
+			params: DisablingParams,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			ActiveDisablingParams::<T>::put(params);
+			Ok(())
+		}
 	}
 
 	#[pallet::validate_unsigned]
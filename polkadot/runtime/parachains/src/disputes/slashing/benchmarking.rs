@@ -160,4 +160,11 @@ benchmarks! {
 		let unapplied = <UnappliedSlashes<T>>::get(session_index, CANDIDATE_HASH);
 		assert!(unapplied.is_none());
 	}
+
+	set_disabling_params {
+		let params = DisablingParams { max_disabled: Perbill::from_percent(33), ..Default::default() };
+	}: _(RawOrigin::Root, params)
+	verify {
+		assert_eq!(ActiveDisablingParams::<T>::get(), params);
+	}
 }
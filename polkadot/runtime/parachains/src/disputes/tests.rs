@@ -2408,3 +2408,23 @@ fn import_ignores_single_sided() {
 		assert!(statements.is_empty());
 	})
 }
+
+#[test]
+fn disabling_params_respect_disabled_limit() {
+	use primitives::slashing::SlashingOffenceKind;
+	use sp_runtime::Perbill;
+	use sp_staking::offence::DisableStrategy;
+
+	let params =
+		slashing::DisablingParams { max_disabled: Perbill::from_percent(33), ..Default::default() };
+	let kind = SlashingOffenceKind::ForInvalid;
+
+	// 3 out of 10 validators may be disabled.
+	assert_eq!(params.disable_strategy(kind, 2, 1, 10), DisableStrategy::Always);
+	assert_eq!(params.disable_strategy(kind, 2, 2, 10), DisableStrategy::Never);
+	assert_eq!(params.disable_strategy(kind, 4, 0, 10), DisableStrategy::Never);
+	assert_eq!(
+		params.disable_strategy(SlashingOffenceKind::AgainstValid, 1, 0, 10),
+		DisableStrategy::Never
+	);
+}
@@ -23,13 +23,17 @@ use frame_system::pallet_prelude::*;
 use parity_scale_codec::{Decode, Encode};
 use polkadot_parachain_primitives::primitives::{HorizontalMessages, IsSystem};
 use primitives::{
+	vstaging::{HrmpChannelUsage, HRMP_USAGE_AVERAGING_WINDOW},
 	Balance, Hash, HrmpChannelId, Id as ParaId, InboundHrmpMessage, OutboundHrmpMessage,
 	SessionIndex,
 };
 use scale_info::TypeInfo;
 use sp_runtime::{
-	traits::{AccountIdConversion, BlakeTwo256, Hash as HashT, UniqueSaturatedInto, Zero},
-	ArithmeticError,
+	traits::{
+		AccountIdConversion, AtLeast32BitUnsigned, BlakeTwo256, Hash as HashT, One, Saturating,
+		UniqueSaturatedInto, Zero,
+	},
+	ArithmeticError, FixedU128,
 };
 use sp_std::{
 	collections::{btree_map::BTreeMap, btree_set::BTreeSet},
@@ -164,6 +168,64 @@ pub struct HrmpChannel {
 	pub recipient_deposit: Balance,
 }
 
+/// Bookkeeping for the moving averages of the traffic going through an HRMP channel.
+///
+/// Rather than updating the averages of every channel in every block, the messages sent within
+/// a block are accumulated and folded into the averages the next time the channel is used or
+/// queried. Blocks without any messages in between are accounted for at that point as well.
+#[derive(Encode, Decode, TypeInfo, Default)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub struct HrmpChannelUsageTracker<BlockNumber> {
+	/// The moving averages over the blocks before `block`.
+	pub usage: HrmpChannelUsage,
+	/// The block the pending counters below belong to.
+	pub block: BlockNumber,
+	/// The number of messages sent in `block`.
+	pub pending_msg_count: u32,
+	/// The total size in bytes of the messages sent in `block`.
+	pub pending_total_size: u32,
+}
+
+impl<BlockNumber: AtLeast32BitUnsigned + Copy> HrmpChannelUsageTracker<BlockNumber> {
+	/// Returns the moving averages over the blocks before `now`.
+	pub fn usage_at(&self, now: BlockNumber) -> HrmpChannelUsage {
+		if now <= self.block {
+			return self.usage
+		}
+
+		let alpha = FixedU128::from_rational(2, HRMP_USAGE_AVERAGING_WINDOW as u128 + 1);
+		let retain = FixedU128::one().saturating_sub(alpha);
+		// Every block without messages shrinks the averages by `retain`.
+		let idle_blocks: u32 = (now - self.block - One::one()).unique_saturated_into();
+		let decay = retain.saturating_pow(idle_blocks as usize);
+
+		let average = |previous: FixedU128, sample: u32| {
+			alpha
+				.saturating_mul(FixedU128::from_u32(sample))
+				.saturating_add(retain.saturating_mul(previous))
+				.saturating_mul(decay)
+		};
+
+		HrmpChannelUsage {
+			msg_count_per_block: average(self.usage.msg_count_per_block, self.pending_msg_count),
+			total_size_per_block: average(self.usage.total_size_per_block, self.pending_total_size),
+		}
+	}
+
+	/// Notes a message of `size` bytes sent over the channel in block `now`.
+	pub fn note_message(&mut self, now: BlockNumber, size: u32) {
+		if self.block < now {
+			self.usage = self.usage_at(now);
+			self.block = now;
+			self.pending_msg_count = 0;
+			self.pending_total_size = 0;
+		}
+
+		self.pending_msg_count.saturating_inc();
+		self.pending_total_size.saturating_accrue(size);
+	}
+}
+
 /// An error returned by [`Pallet::check_hrmp_watermark`] that indicates an acceptance criteria
 /// check didn't pass.
 pub(crate) enum HrmpWatermarkAcceptanceErr<BlockNumber> {
@@ -462,6 +524,18 @@ pub mod pallet {
 	pub type HrmpChannelDigests<T: Config> =
 		StorageMap<_, Twox64Concat, ParaId, Vec<(BlockNumberFor<T>, Vec<ParaId>)>, ValueQuery>;
 
+	/// The moving averages of the traffic going through each HRMP channel.
+	///
+	/// Invariant: cannot be non-default if the corresponding channel in `HrmpChannels` is `None`.
+	#[pallet::storage]
+	pub type HrmpChannelUsageTrackers<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		HrmpChannelId,
+		HrmpChannelUsageTracker<BlockNumberFor<T>>,
+		ValueQuery,
+	>;
+
 	/// Preopen the given HRMP channels.
 	///
 	/// The values in the tuple corresponds to
@@ -1140,6 +1214,7 @@ impl<T: Config> Pallet<T> {
 		}
 
 		HrmpChannelContents::<T>::remove(channel_id);
+		HrmpChannelUsageTrackers::<T>::remove(channel_id);
 
 		HrmpEgressChannelsIndex::<T>::mutate(&channel_id.sender, |v| {
 			if let Ok(i) = v.binary_search(&channel_id.recipient) {
@@ -1381,6 +1456,9 @@ impl<T: Config> Pallet<T> {
 			// book keeping
 			channel.msg_count += 1;
 			channel.total_size += inbound.data.len() as u32;
+			HrmpChannelUsageTrackers::<T>::mutate(&channel_id, |tracker| {
+				tracker.note_message(now, inbound.data.len() as u32)
+			});
 
 			// compute the new MQC head of the channel
 			let prev_head = channel.mqc_head.unwrap_or(Default::default());
@@ -1418,7 +1496,7 @@ impl<T: Config> Pallet<T> {
 			}
 			HrmpChannelDigests::<T>::insert(&channel_id.recipient, recipient_digest);
 
-			weight += T::DbWeight::get().reads_writes(2, 2);
+			weight += T::DbWeight::get().reads_writes(3, 3);
 		}
 
 		weight
@@ -1697,6 +1775,19 @@ impl<T: Config> Pallet<T> {
 
 		inbound_hrmp_channels_contents
 	}
+
+	/// Returns the moving averages of the traffic going through the given channel over the blocks
+	/// before the current one.
+	///
+	/// Returns `None` if the channel doesn't exist.
+	pub(crate) fn channel_usage(channel_id: &HrmpChannelId) -> Option<HrmpChannelUsage> {
+		if !HrmpChannels::<T>::contains_key(channel_id) {
+			return None
+		}
+
+		let now = frame_system::Pallet::<T>::block_number();
+		Some(HrmpChannelUsageTrackers::<T>::get(channel_id).usage_at(now))
+	}
 }
 
 impl<T: Config> Pallet<T> {
@@ -1803,6 +1894,11 @@ impl<T: Config> Pallet<T> {
 			assert!(!contents.is_empty());
 		}
 
+		// Only open channels can have usage recorded.
+		for (used_channel, _) in HrmpChannelUsageTrackers::<T>::iter() {
+			assert!(HrmpChannels::<T>::contains_key(&used_channel));
+		}
+
 		// Senders and recipients must be onboarded. Otherwise, all channels associated with them
 		// are removed.
 		assert_contains_only_onboarded(
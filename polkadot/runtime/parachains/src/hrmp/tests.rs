@@ -574,6 +574,74 @@ fn hrmp_mqc_head_fixture() {
 	});
 }
 
+#[test]
+fn channel_usage_tracks_moving_averages() {
+	let para_a = 2000.into();
+	let para_b = 2024.into();
+	let channel_id = HrmpChannelId { sender: para_a, recipient: para_b };
+
+	let mut genesis = GenesisConfigBuilder::default();
+	genesis.hrmp_channel_max_message_size = 20;
+	genesis.hrmp_channel_max_total_size = 40;
+	new_test_ext(genesis.build()).execute_with(|| {
+		register_parachain(para_a);
+		register_parachain(para_b);
+
+		run_to_block(5, Some(vec![4, 5]));
+		assert_eq!(Hrmp::channel_usage(&channel_id), None);
+		Hrmp::init_open_channel(para_a, para_b, 2, 20).unwrap();
+		Hrmp::accept_open_channel(para_b, para_a).unwrap();
+
+		// On block 6 A sends two messages to B. They are not accounted for before block 6 is
+		// over.
+		run_to_block(6, Some(vec![6]));
+		assert_eq!(Hrmp::channel_usage(&channel_id), Some(HrmpChannelUsage::default()));
+		let _ = Hrmp::queue_outbound_hrmp(
+			para_a,
+			vec![
+				OutboundHrmpMessage { recipient: para_b, data: vec![1; 10] },
+				OutboundHrmpMessage { recipient: para_b, data: vec![2; 20] },
+			]
+			.try_into()
+			.unwrap(),
+		);
+		assert_eq!(Hrmp::channel_usage(&channel_id), Some(HrmpChannelUsage::default()));
+		Hrmp::assert_storage_consistency_exhaustive();
+
+		let alpha = FixedU128::from_rational(2, HRMP_USAGE_AVERAGING_WINDOW as u128 + 1);
+		let retain = FixedU128::one() - alpha;
+
+		run_to_block(7, None);
+		assert_eq!(
+			Hrmp::channel_usage(&channel_id),
+			Some(HrmpChannelUsage {
+				msg_count_per_block: alpha.saturating_mul(FixedU128::from_u32(2)),
+				total_size_per_block: alpha.saturating_mul(FixedU128::from_u32(30)),
+			}),
+		);
+
+		// Blocks without messages decay the averages.
+		run_to_block(9, None);
+		assert_eq!(
+			Hrmp::channel_usage(&channel_id),
+			Some(HrmpChannelUsage {
+				msg_count_per_block: alpha
+					.saturating_mul(FixedU128::from_u32(2))
+					.saturating_mul(retain.saturating_pow(2)),
+				total_size_per_block: alpha
+					.saturating_mul(FixedU128::from_u32(30))
+					.saturating_mul(retain.saturating_pow(2)),
+			}),
+		);
+
+		// Closing the channel removes the usage as well.
+		Hrmp::close_hrmp_channel(&channel_id);
+		assert_eq!(Hrmp::channel_usage(&channel_id), None);
+		assert!(!HrmpChannelUsageTrackers::<Test>::contains_key(&channel_id));
+		Hrmp::assert_storage_consistency_exhaustive();
+	});
+}
+
 #[test]
 fn accept_incoming_request_and_offboard() {
 	let para_a = 2032.into();
@@ -198,6 +198,22 @@ pub trait RewardValidators {
 	fn reward_bitfields(validators: impl IntoIterator<Item = ValidatorIndex>);
 }
 
+/// A hook called when a candidate is enacted.
+pub trait OnCandidateEnacted {
+	/// Called before the commitments of the candidate are applied. Returns the weight consumed.
+	fn on_candidate_enacted(
+		para_id: ParaId,
+		candidate_hash: CandidateHash,
+		commitments: &CandidateCommitments,
+	) -> Weight;
+}
+
+impl OnCandidateEnacted for () {
+	fn on_candidate_enacted(_: ParaId, _: CandidateHash, _: &CandidateCommitments) -> Weight {
+		Weight::zero()
+	}
+}
+
 /// Helper return type for `process_candidates`.
 #[derive(Encode, Decode, PartialEq, TypeInfo)]
 #[cfg_attr(test, derive(Debug))]
@@ -289,6 +305,9 @@ pub mod pallet {
 		type DisputesHandler: disputes::DisputesHandler<BlockNumberFor<Self>>;
 		type RewardValidators: RewardValidators;
 
+		/// Notified of each enacted candidate.
+		type OnCandidateEnacted: OnCandidateEnacted;
+
 		/// The system message queue.
 		///
 		/// The message queue provides general queueing and processing functionality. Currently it
@@ -830,6 +849,11 @@ impl<T: Config> Pallet<T> {
 
 		// initial weight is config read.
 		let mut weight = T::DbWeight::get().reads_writes(1, 0);
+		weight.saturating_accrue(T::OnCandidateEnacted::on_candidate_enacted(
+			receipt.descriptor.para_id,
+			plain.hash(),
+			&commitments,
+		));
 		if let Some(new_code) = commitments.new_validation_code {
 			// Block number of candidate's inclusion.
 			let now = frame_system::Pallet::<T>::block_number();
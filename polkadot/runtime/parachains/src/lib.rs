@@ -35,12 +35,14 @@ pub mod inclusion;
 pub mod initializer;
 pub mod metrics;
 pub mod origin;
+pub mod para_stats;
 pub mod paras;
 pub mod paras_inherent;
 pub mod reward_points;
 pub mod scheduler;
 pub mod session_info;
 pub mod shared;
+pub mod upgrade_readiness;
 
 pub mod runtime_api_impl;
 
@@ -20,11 +20,11 @@ use crate::{
 	assigner_coretime, assigner_on_demand, assigner_parachains, configuration, coretime, disputes,
 	dmp, hrmp,
 	inclusion::{self, AggregateMessageOrigin, UmpQueueId},
-	initializer, origin, paras,
+	initializer, origin, para_stats, paras,
 	paras::ParaKind,
 	paras_inherent, scheduler,
 	scheduler::common::AssignmentProvider,
-	session_info, shared, ParaId,
+	session_info, shared, upgrade_readiness, ParaId,
 };
 use frame_support::pallet_prelude::*;
 use primitives::CoreIndex;
@@ -68,6 +68,8 @@ frame_support::construct_runtime!(
 		ParasShared: shared,
 		ParaInclusion: inclusion,
 		ParaInherent: paras_inherent,
+		ParaStats: para_stats,
+		UpgradeReadiness: upgrade_readiness,
 		Scheduler: scheduler,
 		MockAssigner: mock_assigner,
 		ParachainsAssigner: assigner_parachains,
@@ -364,6 +366,7 @@ impl pallet_message_queue::Config for Test {
 	type MessageProcessor = TestProcessMessage;
 	type QueueChangeHandler = ParaInclusion;
 	type QueuePausedQuery = ();
+	type ServiceShares = ();
 	type HeapSize = ConstU32<65536>;
 	type MaxStale = ConstU32<8>;
 	type ServiceWeight = MessageQueueServiceWeight;
@@ -416,10 +419,37 @@ impl crate::inclusion::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type DisputesHandler = Disputes;
 	type RewardValidators = TestRewardValidators;
+	type OnCandidateEnacted = ParaStats;
 	type MessageQueue = MessageQueue;
 }
 
+parameter_types! {
+	pub const ParaStatsRetention: SessionIndex = 3;
+	pub const ParaStatsReportsPerCandidate: u32 = 3;
+	pub const ParaStatsUnsignedPriority: TransactionPriority = TransactionPriority::max_value();
+}
+
+impl crate::para_stats::Config for Test {
+	type StatsRetention = ParaStatsRetention;
+	type ReportsPerCandidate = ParaStatsReportsPerCandidate;
+	type UnsignedPriority = ParaStatsUnsignedPriority;
+	type WeightInfo = crate::para_stats::TestWeightInfo;
+}
+
+parameter_types! {
+	pub const UpgradeReadinessThreshold: Perbill = Perbill::from_percent(66);
+	pub const UpgradeReadinessUnsignedPriority: TransactionPriority = TransactionPriority::max_value();
+}
+
+impl crate::upgrade_readiness::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type ReadinessThreshold = UpgradeReadinessThreshold;
+	type UnsignedPriority = UpgradeReadinessUnsignedPriority;
+	type WeightInfo = crate::upgrade_readiness::TestWeightInfo;
+}
+
 impl crate::paras_inherent::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = crate::paras_inherent::TestWeightInfo;
 }
 
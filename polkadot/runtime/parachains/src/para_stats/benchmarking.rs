@@ -0,0 +1,78 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Para statistics pallet benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::{Pallet, *};
+use crate::shared::Pallet as ParasShared;
+
+use frame_benchmarking::v2::*;
+use frame_system::RawOrigin;
+use primitives::ValidatorId;
+use sp_application_crypto::RuntimeAppPublic;
+
+const SESSION_INDEX: SessionIndex = 1;
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn report_candidate_stats() {
+		// Setup: the last report of the candidate, which aggregates its reports.
+		let reports = T::ReportsPerCandidate::get().max(1);
+		let validators: Vec<_> = (0..reports)
+			.map(|_| <ValidatorId as RuntimeAppPublic>::generate_pair(None))
+			.collect();
+		ParasShared::<T>::set_active_validators_ascending(validators.clone());
+		ParasShared::<T>::set_session_index(SESSION_INDEX);
+
+		let candidate_hash = CandidateHash(Default::default());
+		UnreportedCandidates::<T>::insert(SESSION_INDEX, candidate_hash, ParaId::from(1000));
+		let previous_reports = (0..reports - 1)
+			.map(|validator| CandidateReport {
+				validator_index: ValidatorIndex(validator),
+				pov_size: 5 * 1024 * 1024,
+				execution_weight: 2_000_000_000_000,
+			})
+			.collect::<Vec<_>>();
+		CandidateReports::<T>::insert(SESSION_INDEX, candidate_hash, previous_reports);
+
+		let stmt = CandidateStatsStatement {
+			session_index: SESSION_INDEX,
+			validator_index: ValidatorIndex(reports - 1),
+			candidate_hash,
+			pov_size: 5 * 1024 * 1024,
+			execution_weight: 2_000_000_000_000,
+		};
+		let signature = validators[stmt.validator_index.0 as usize]
+			.sign(&stmt.signing_payload())
+			.unwrap();
+
+		#[extrinsic_call]
+		_(RawOrigin::None, stmt, signature);
+
+		assert!(SessionStats::<T>::get(SESSION_INDEX, ParaId::from(1000)).is_some());
+	}
+
+	impl_benchmark_test_suite!(
+		Pallet,
+		crate::mock::new_test_ext(Default::default()),
+		crate::mock::Test
+	);
+}
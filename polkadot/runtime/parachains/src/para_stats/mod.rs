@@ -0,0 +1,479 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The para statistics module.
+//!
+//! Records statistics of the candidates of each para per session, giving governance data to
+//! adjust the limits of the host configuration with.
+//!
+//! The size of the validation code upgrades is taken from the candidates enacted by the
+//! inclusion module, which needs to be configured with this module as its
+//! [`OnCandidateEnacted`](crate::inclusion::OnCandidateEnacted) hook. The size of the PoV and
+//! the execution weight of the candidates are not known on-chain, they are reported by the
+//! approval checkers with signed statements submitted as unsigned transactions. The reports of
+//! an enacted candidate are collected until [`Config::ReportsPerCandidate`] validators reported
+//! it, the median of their reports is then recorded, so that a single validator can't skew the
+//! statistics.
+//!
+//! This module is not handled by the initializer but is instead instantiated in the
+//! `construct_runtime` macro. The statistics are kept for [`Config::StatsRetention`] sessions.
+
+mod benchmarking;
+
+#[cfg(test)]
+mod tests;
+
+use crate::{inclusion::OnCandidateEnacted, paras, shared};
+use frame_support::{pallet_prelude::*, traits::EstimateNextSessionRotation};
+use frame_system::pallet_prelude::*;
+use parity_scale_codec::{Decode, Encode};
+use primitives::{
+	CandidateCommitments, CandidateHash, Id as ParaId, SessionIndex, ValidatorIndex,
+	ValidatorSignature,
+};
+use scale_info::TypeInfo;
+use sp_core::RuntimeDebug;
+use sp_runtime::traits::AppVerify;
+use sp_std::prelude::*;
+
+pub use pallet::*;
+pub use primitives::vstaging::CandidateStatsStatement;
+
+const LOG_TARGET: &str = "runtime::parachains::para-stats";
+
+/// Maximum number of enacted candidates of past sessions removed per block.
+const MAX_PRUNED_CANDIDATES: u32 = 512;
+
+pub trait WeightInfo {
+	fn report_candidate_stats() -> Weight;
+}
+
+/// A weight info that is only suitable for testing.
+pub struct TestWeightInfo;
+
+impl WeightInfo for TestWeightInfo {
+	fn report_candidate_stats() -> Weight {
+		Weight::MAX
+	}
+}
+
+/// The statistics of an enacted candidate reported by a validator, waiting for the reports of
+/// the other validators.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct CandidateReport {
+	/// The index of the reporting validator in the active validator set.
+	pub validator_index: ValidatorIndex,
+	/// The size of the PoV of the candidate, in bytes.
+	pub pov_size: u32,
+	/// The time the validator spent executing the candidate, in picoseconds of reference time.
+	pub execution_weight: u64,
+}
+
+/// Statistics of the candidates of a para in a session.
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ParaSessionStats {
+	/// The number of enacted candidates.
+	pub candidates: u32,
+	/// The number of enacted candidates whose PoV size and execution weight were reported.
+	pub reported_candidates: u32,
+	/// The largest reported PoV size, in bytes.
+	pub max_pov_size: u32,
+	/// The sum of the reported PoV sizes, in bytes.
+	pub total_pov_size: u64,
+	/// The largest reported execution weight, in picoseconds of reference time.
+	pub max_execution_weight: u64,
+	/// The sum of the reported execution weights, in picoseconds of reference time.
+	pub total_execution_weight: u64,
+	/// The number of validation code upgrades enacted.
+	pub code_upgrades: u32,
+	/// The size of the largest validation code upgrade, in bytes.
+	pub max_code_size: u32,
+}
+
+impl ParaSessionStats {
+	/// The average reported PoV size, in bytes.
+	pub fn avg_pov_size(&self) -> u32 {
+		self.total_pov_size
+			.checked_div(self.reported_candidates.into())
+			.map_or(0, |avg| avg as u32)
+	}
+
+	/// The average reported execution weight, in picoseconds of reference time.
+	pub fn avg_execution_weight(&self) -> u64 {
+		self.total_execution_weight
+			.checked_div(self.reported_candidates.into())
+			.unwrap_or(0)
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use sp_runtime::transaction_validity::{
+		InvalidTransaction, TransactionPriority, TransactionSource, TransactionValidity,
+		ValidTransaction,
+	};
+
+	#[pallet::pallet]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config:
+		frame_system::Config
+		+ shared::Config
+		+ paras::Config
+		+ frame_system::offchain::SendTransactionTypes<Call<Self>>
+	{
+		/// The number of sessions the statistics are kept for.
+		#[pallet::constant]
+		type StatsRetention: Get<SessionIndex>;
+
+		/// The number of validators whose reports of a candidate are aggregated into the
+		/// statistics. Must be non-zero.
+		#[pallet::constant]
+		type ReportsPerCandidate: Get<u32>;
+
+		/// The priority of the validators' reports.
+		#[pallet::constant]
+		type UnsignedPriority: Get<TransactionPriority>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// The candidates enacted in a session which weren't reported yet.
+	#[pallet::storage]
+	pub(super) type UnreportedCandidates<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		SessionIndex,
+		Identity,
+		CandidateHash,
+		ParaId,
+		OptionQuery,
+	>;
+
+	/// The reports of the candidates enacted in a session which weren't aggregated yet.
+	#[pallet::storage]
+	pub(super) type CandidateReports<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		SessionIndex,
+		Identity,
+		CandidateHash,
+		Vec<CandidateReport>,
+		ValueQuery,
+	>;
+
+	/// The statistics of the paras per session.
+	#[pallet::storage]
+	pub type SessionStats<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		SessionIndex,
+		Twox64Concat,
+		ParaId,
+		ParaSessionStats,
+		OptionQuery,
+	>;
+
+	/// The session the statistics were last recorded in.
+	#[pallet::storage]
+	pub(super) type LastSession<T: Config> = StorageValue<_, SessionIndex, ValueQuery>;
+
+	/// Past sessions whose unreported candidates are still being removed.
+	#[pallet::storage]
+	pub(super) type PrunedSessions<T: Config> =
+		StorageValue<_, BoundedVec<SessionIndex, ConstU32<16>>, ValueQuery>;
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The statement is for a past session.
+		StatementStale,
+		/// The statement is for a future session.
+		StatementFuture,
+		/// The validator index is out of bounds of the active validator set.
+		ValidatorIndexOutOfBounds,
+		/// The signature of the statement is invalid.
+		InvalidSignature,
+		/// The candidate wasn't enacted in the session or its reports were already aggregated.
+		UnknownCandidate,
+		/// The validator already reported the candidate.
+		AlreadyReported,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_now: BlockNumberFor<T>) -> Weight {
+			let mut weight = T::DbWeight::get().reads(2);
+
+			let session_index = shared::CurrentSessionIndex::<T>::get();
+			let last_session = LastSession::<T>::get();
+			if session_index != last_session {
+				LastSession::<T>::put(session_index);
+				PrunedSessions::<T>::mutate(|sessions| {
+					if sessions.try_push(last_session).is_err() {
+						log::warn!(
+							target: LOG_TARGET,
+							"Too many sessions to prune, leaking the candidates of session {}",
+							last_session,
+						);
+					}
+				});
+
+				if let Some(expired) = session_index.checked_sub(T::StatsRetention::get()) {
+					let removed = SessionStats::<T>::clear_prefix(expired, u32::MAX, None);
+					weight.saturating_accrue(
+						T::DbWeight::get().writes(removed.backend.saturating_add(2).into()),
+					);
+				}
+			}
+
+			weight.saturating_add(Self::prune_candidates())
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Record the PoV size and the execution weight of an enacted candidate as reported by
+		/// a validator.
+		///
+		/// Once [`Config::ReportsPerCandidate`] validators reported the candidate, the median of
+		/// their reports is recorded in the statistics of the para.
+		#[pallet::call_index(0)]
+		#[pallet::weight(<T as Config>::WeightInfo::report_candidate_stats())]
+		pub fn report_candidate_stats(
+			origin: OriginFor<T>,
+			stmt: CandidateStatsStatement,
+			signature: ValidatorSignature,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+
+			let current_session = shared::CurrentSessionIndex::<T>::get();
+			if stmt.session_index < current_session {
+				return Err(Error::<T>::StatementStale.into())
+			} else if stmt.session_index > current_session {
+				return Err(Error::<T>::StatementFuture.into())
+			}
+
+			let validators = shared::ActiveValidatorKeys::<T>::get();
+			let validator_public = validators
+				.get(stmt.validator_index.0 as usize)
+				.ok_or(Error::<T>::ValidatorIndexOutOfBounds)?;
+			ensure!(
+				signature.verify(&stmt.signing_payload()[..], validator_public),
+				Error::<T>::InvalidSignature,
+			);
+
+			let para_id = UnreportedCandidates::<T>::get(stmt.session_index, stmt.candidate_hash)
+				.ok_or(Error::<T>::UnknownCandidate)?;
+			let mut reports = CandidateReports::<T>::get(stmt.session_index, stmt.candidate_hash);
+			ensure!(
+				!reports.iter().any(|report| report.validator_index == stmt.validator_index),
+				Error::<T>::AlreadyReported,
+			);
+			reports.push(CandidateReport {
+				validator_index: stmt.validator_index,
+				pov_size: stmt.pov_size,
+				execution_weight: stmt.execution_weight,
+			});
+
+			if (reports.len() as u32) < T::ReportsPerCandidate::get() {
+				CandidateReports::<T>::insert(stmt.session_index, stmt.candidate_hash, reports);
+				return Ok(())
+			}
+
+			UnreportedCandidates::<T>::remove(stmt.session_index, stmt.candidate_hash);
+			CandidateReports::<T>::remove(stmt.session_index, stmt.candidate_hash);
+			let pov_size = median(reports.iter().map(|report| report.pov_size).collect());
+			let execution_weight =
+				median(reports.iter().map(|report| report.execution_weight).collect());
+			SessionStats::<T>::mutate(stmt.session_index, para_id, |stats| {
+				let stats = stats.get_or_insert_with(Default::default);
+				stats.reported_candidates.saturating_inc();
+				stats.max_pov_size = stats.max_pov_size.max(pov_size);
+				stats.total_pov_size.saturating_accrue(pov_size.into());
+				stats.max_execution_weight = stats.max_execution_weight.max(execution_weight);
+				stats.total_execution_weight.saturating_accrue(execution_weight);
+			});
+
+			Ok(())
+		}
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			let (stmt, signature) = match call {
+				Call::report_candidate_stats { stmt, signature } => (stmt, signature),
+				_ => return InvalidTransaction::Call.into(),
+			};
+
+			let current_session = shared::CurrentSessionIndex::<T>::get();
+			if stmt.session_index < current_session {
+				return InvalidTransaction::Stale.into()
+			} else if stmt.session_index > current_session {
+				return InvalidTransaction::Future.into()
+			}
+
+			let validators = shared::ActiveValidatorKeys::<T>::get();
+			let validator_public = match validators.get(stmt.validator_index.0 as usize) {
+				Some(pk) => pk,
+				None => return InvalidTransaction::Custom(INVALID_TX_BAD_VALIDATOR_IDX).into(),
+			};
+
+			if !signature.verify(&stmt.signing_payload()[..], validator_public) {
+				return InvalidTransaction::BadProof.into()
+			}
+
+			if !UnreportedCandidates::<T>::contains_key(stmt.session_index, stmt.candidate_hash) {
+				return InvalidTransaction::Custom(INVALID_TX_UNKNOWN_CANDIDATE).into()
+			}
+
+			if CandidateReports::<T>::get(stmt.session_index, stmt.candidate_hash)
+				.iter()
+				.any(|report| report.validator_index == stmt.validator_index)
+			{
+				return InvalidTransaction::Custom(INVALID_TX_ALREADY_REPORTED).into()
+			}
+
+			ValidTransaction::with_tag_prefix("ParaStatsReport")
+				.priority(<T as Config>::UnsignedPriority::get())
+				.longevity(
+					TryInto::<u64>::try_into(
+						<T as paras::Config>::NextSessionRotation::average_session_length() /
+							2u32.into(),
+					)
+					.unwrap_or(64_u64),
+				)
+				// Only one report of a candidate per validator is recorded.
+				.and_provides((stmt.session_index, stmt.candidate_hash, stmt.validator_index))
+				.propagate(true)
+				.build()
+		}
+
+		fn pre_dispatch(_call: &Self::Call) -> Result<(), TransactionValidityError> {
+			// The `report_candidate_stats` dispatchable performs the same checks.
+			Ok(())
+		}
+	}
+}
+
+// custom transaction error codes
+const INVALID_TX_BAD_VALIDATOR_IDX: u8 = 1;
+const INVALID_TX_UNKNOWN_CANDIDATE: u8 = 2;
+const INVALID_TX_ALREADY_REPORTED: u8 = 3;
+
+/// The median of the given values, the lower one for an even number of values.
+fn median<V: Ord + Copy + Default>(mut values: Vec<V>) -> V {
+	values.sort_unstable();
+	values.get(values.len().saturating_sub(1) / 2).copied().unwrap_or_default()
+}
+
+impl<T: Config> Pallet<T> {
+	/// The statistics of the paras in the given session.
+	pub fn session_stats(session_index: SessionIndex) -> Vec<(ParaId, ParaSessionStats)> {
+		SessionStats::<T>::iter_prefix(session_index).collect()
+	}
+
+	/// The statistics of the given para in the retained sessions, oldest first.
+	pub fn para_stats(para_id: ParaId) -> Vec<(SessionIndex, ParaSessionStats)> {
+		let current_session = shared::CurrentSessionIndex::<T>::get();
+		let first_session =
+			current_session.saturating_sub(T::StatsRetention::get().saturating_sub(1));
+		(first_session..=current_session)
+			.filter_map(|session| {
+				SessionStats::<T>::get(session, para_id).map(|stats| (session, stats))
+			})
+			.collect()
+	}
+
+	/// Submits the given statistics of a candidate with corresponding signature as an unsigned
+	/// transaction into the memory pool. Ultimately, that disseminates the transaction across the
+	/// network.
+	///
+	/// This function expects an offchain context and cannot be callable from the on-chain logic.
+	///
+	/// The signature assumed to pertain to `stmt`.
+	pub(crate) fn submit_candidate_stats(
+		stmt: CandidateStatsStatement,
+		signature: ValidatorSignature,
+	) {
+		use frame_system::offchain::SubmitTransaction;
+
+		if let Err(e) = SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(
+			Call::report_candidate_stats { stmt, signature }.into(),
+		) {
+			log::error!(target: LOG_TARGET, "Error submitting candidate stats: {:?}", e);
+		}
+	}
+
+	/// Remove the unreported candidates of past sessions and their reports, up to
+	/// [`MAX_PRUNED_CANDIDATES`] of each.
+	fn prune_candidates() -> Weight {
+		let Some(&session_index) = PrunedSessions::<T>::get().first() else {
+			return T::DbWeight::get().reads(1)
+		};
+
+		let removed =
+			UnreportedCandidates::<T>::clear_prefix(session_index, MAX_PRUNED_CANDIDATES, None);
+		let removed_reports =
+			CandidateReports::<T>::clear_prefix(session_index, MAX_PRUNED_CANDIDATES, None);
+		if removed.maybe_cursor.is_none() && removed_reports.maybe_cursor.is_none() {
+			PrunedSessions::<T>::mutate(|sessions| sessions.remove(0));
+		}
+
+		T::DbWeight::get().reads_writes(
+			1,
+			removed.backend.saturating_add(removed_reports.backend).saturating_add(1).into(),
+		)
+	}
+}
+
+impl<T: Config> OnCandidateEnacted for Pallet<T> {
+	fn on_candidate_enacted(
+		para_id: ParaId,
+		candidate_hash: CandidateHash,
+		commitments: &CandidateCommitments,
+	) -> Weight {
+		let session_index = shared::CurrentSessionIndex::<T>::get();
+		UnreportedCandidates::<T>::insert(session_index, candidate_hash, para_id);
+		SessionStats::<T>::mutate(session_index, para_id, |stats| {
+			let stats = stats.get_or_insert_with(Default::default);
+			stats.candidates.saturating_inc();
+			if let Some(code) = &commitments.new_validation_code {
+				stats.code_upgrades.saturating_inc();
+				stats.max_code_size = stats.max_code_size.max(code.0.len() as u32);
+			}
+		});
+
+		T::DbWeight::get().reads_writes(2, 2)
+	}
+}
+
+sp_api::decl_runtime_apis! {
+	/// The API to query the statistics of the candidates of the paras.
+	pub trait ParaStatsApi {
+		/// The statistics of the paras in the given session.
+		fn session_stats(session_index: SessionIndex) -> Vec<(ParaId, ParaSessionStats)>;
+
+		/// The statistics of the given para in the retained sessions, oldest first.
+		fn para_stats(para_id: ParaId) -> Vec<(SessionIndex, ParaSessionStats)>;
+	}
+}
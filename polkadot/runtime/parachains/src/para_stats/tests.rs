@@ -0,0 +1,261 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+use frame_support::{assert_noop, assert_ok, traits::Hooks};
+use keyring::Sr25519Keyring;
+use primitives::{HeadData, ValidationCode};
+use sp_runtime::transaction_validity::{InvalidTransaction, TransactionSource};
+use test_helpers::validator_pubkeys;
+
+use crate::mock::{new_test_ext, ParaStats, ParasShared, RuntimeOrigin, System, Test};
+
+static VALIDATORS: &[Sr25519Keyring] =
+	&[Sr25519Keyring::Alice, Sr25519Keyring::Bob, Sr25519Keyring::Charlie];
+
+fn start_session(session_index: SessionIndex) {
+	ParasShared::set_active_validators_ascending(validator_pubkeys(VALIDATORS));
+	ParasShared::set_session_index(session_index);
+	ParaStats::on_initialize(System::block_number());
+}
+
+fn commitments(new_validation_code: Option<ValidationCode>) -> CandidateCommitments {
+	CandidateCommitments {
+		upward_messages: Default::default(),
+		horizontal_messages: Default::default(),
+		new_validation_code,
+		head_data: HeadData(vec![1, 2, 3]),
+		processed_downward_messages: 0,
+		hrmp_watermark: 0,
+	}
+}
+
+fn enact(
+	para_id: u32,
+	candidate: u8,
+	new_validation_code: Option<ValidationCode>,
+) -> CandidateHash {
+	let candidate_hash = CandidateHash([candidate; 32].into());
+	ParaStats::on_candidate_enacted(
+		para_id.into(),
+		candidate_hash,
+		&commitments(new_validation_code),
+	);
+	candidate_hash
+}
+
+fn statement(
+	validator: u32,
+	candidate_hash: CandidateHash,
+	pov_size: u32,
+	execution_weight: u64,
+) -> CandidateStatsStatement {
+	CandidateStatsStatement {
+		session_index: shared::CurrentSessionIndex::<Test>::get(),
+		validator_index: ValidatorIndex(validator),
+		candidate_hash,
+		pov_size,
+		execution_weight,
+	}
+}
+
+fn sign(stmt: &CandidateStatsStatement) -> ValidatorSignature {
+	// Out of the active validator set, sign with a key which isn't in it.
+	let keyring = VALIDATORS.get(stmt.validator_index.0 as usize).unwrap_or(&Sr25519Keyring::Dave);
+	keyring.sign(&stmt.signing_payload()).into()
+}
+
+fn report(stmt: CandidateStatsStatement) -> DispatchResult {
+	let signature = sign(&stmt);
+	ParaStats::report_candidate_stats(RuntimeOrigin::none(), stmt, signature)
+}
+
+fn report_by_all(candidate_hash: CandidateHash, pov_size: u32, execution_weight: u64) {
+	for validator in 0..VALIDATORS.len() as u32 {
+		assert_ok!(report(statement(validator, candidate_hash, pov_size, execution_weight)));
+	}
+}
+
+#[test]
+fn enacted_candidates_and_reports_are_recorded() {
+	new_test_ext(Default::default()).execute_with(|| {
+		start_session(1);
+
+		let first = enact(1000, 1, None);
+		let second = enact(1000, 2, Some(ValidationCode(vec![0; 100])));
+		enact(1000, 3, Some(ValidationCode(vec![0; 40])));
+		let other = enact(2000, 4, None);
+
+		report_by_all(first, 1_000, 10);
+		report_by_all(second, 3_000, 30);
+		report_by_all(other, 500, 5);
+
+		let stats = SessionStats::<Test>::get(1, ParaId::from(1000)).unwrap();
+		assert_eq!(
+			stats,
+			ParaSessionStats {
+				candidates: 3,
+				reported_candidates: 2,
+				max_pov_size: 3_000,
+				total_pov_size: 4_000,
+				max_execution_weight: 30,
+				total_execution_weight: 40,
+				code_upgrades: 2,
+				max_code_size: 100,
+			}
+		);
+		assert_eq!(stats.avg_pov_size(), 2_000);
+		assert_eq!(stats.avg_execution_weight(), 20);
+		assert_eq!(ParaStats::session_stats(1).len(), 2);
+	});
+}
+
+#[test]
+fn median_of_the_reports_is_recorded() {
+	new_test_ext(Default::default()).execute_with(|| {
+		start_session(1);
+		let candidate = enact(1000, 1, None);
+
+		// A single validator can't skew the statistics.
+		assert_ok!(report(statement(0, candidate, 5_000_000, 1)));
+		assert_ok!(report(statement(1, candidate, 1_000, 20)));
+		assert_eq!(
+			SessionStats::<Test>::get(1, ParaId::from(1000)).unwrap().reported_candidates,
+			0
+		);
+		assert_ok!(report(statement(2, candidate, 2_000, 10)));
+
+		let stats = SessionStats::<Test>::get(1, ParaId::from(1000)).unwrap();
+		assert_eq!(stats.reported_candidates, 1);
+		assert_eq!((stats.max_pov_size, stats.max_execution_weight), (2_000, 10));
+		assert!(!CandidateReports::<Test>::contains_key(1, candidate));
+	});
+}
+
+#[test]
+fn validators_report_candidates_once() {
+	new_test_ext(Default::default()).execute_with(|| {
+		start_session(1);
+		let candidate = enact(1000, 1, None);
+
+		assert_ok!(report(statement(0, candidate, 1_000, 10)));
+		assert_noop!(report(statement(0, candidate, 2_000, 20)), Error::<Test>::AlreadyReported);
+		let stmt = statement(0, candidate, 2_000, 20);
+		let signature = sign(&stmt);
+		assert_eq!(
+			ParaStats::validate_unsigned(
+				TransactionSource::External,
+				&Call::report_candidate_stats { stmt, signature },
+			),
+			Err(InvalidTransaction::Custom(INVALID_TX_ALREADY_REPORTED).into()),
+		);
+
+		assert_ok!(report(statement(1, candidate, 1_000, 10)));
+		assert_ok!(report(statement(2, candidate, 1_000, 10)));
+		assert_noop!(report(statement(0, candidate, 2_000, 20)), Error::<Test>::UnknownCandidate);
+		assert_noop!(
+			report(statement(1, CandidateHash([9; 32].into()), 2_000, 20)),
+			Error::<Test>::UnknownCandidate,
+		);
+	});
+}
+
+#[test]
+fn invalid_reports_are_rejected() {
+	new_test_ext(Default::default()).execute_with(|| {
+		start_session(1);
+		let candidate = enact(1000, 1, None);
+
+		let mut stmt = statement(0, candidate, 1_000, 10);
+		let signature = sign(&stmt);
+		stmt.pov_size = 1;
+		assert_noop!(
+			ParaStats::report_candidate_stats(
+				RuntimeOrigin::none(),
+				stmt.clone(),
+				signature.clone()
+			),
+			Error::<Test>::InvalidSignature,
+		);
+		assert_eq!(
+			ParaStats::validate_unsigned(
+				TransactionSource::External,
+				&Call::report_candidate_stats { stmt, signature },
+			),
+			Err(InvalidTransaction::BadProof.into()),
+		);
+
+		assert_noop!(
+			report(statement(3, candidate, 1_000, 10)),
+			Error::<Test>::ValidatorIndexOutOfBounds,
+		);
+
+		let mut stmt = statement(0, candidate, 1_000, 10);
+		stmt.session_index = 2;
+		assert_noop!(report(stmt), Error::<Test>::StatementFuture);
+
+		start_session(2);
+		assert_noop!(report(statement(0, candidate, 1_000, 10)), Error::<Test>::UnknownCandidate);
+		let mut stmt = statement(0, candidate, 1_000, 10);
+		stmt.session_index = 1;
+		assert_noop!(report(stmt), Error::<Test>::StatementStale);
+	});
+}
+
+#[test]
+fn valid_report_passes_validate_unsigned() {
+	new_test_ext(Default::default()).execute_with(|| {
+		start_session(1);
+		let candidate = enact(1000, 1, None);
+
+		let stmt = statement(0, candidate, 1_000, 10);
+		let signature = sign(&stmt);
+		assert!(ParaStats::validate_unsigned(
+			TransactionSource::External,
+			&Call::report_candidate_stats { stmt, signature },
+		)
+		.is_ok());
+	});
+}
+
+#[test]
+fn old_sessions_are_pruned() {
+	new_test_ext(Default::default()).execute_with(|| {
+		start_session(1);
+		let candidate = enact(1000, 1, None);
+		assert_ok!(report(statement(0, candidate, 1_000, 10)));
+
+		start_session(2);
+		enact(1000, 2, None);
+		assert_eq!(PrunedSessions::<Test>::get().into_inner(), Vec::<SessionIndex>::new());
+		assert!(!UnreportedCandidates::<Test>::contains_key(1, candidate));
+		assert!(!CandidateReports::<Test>::contains_key(1, candidate));
+
+		start_session(3);
+		assert_eq!(ParaStats::para_stats(1000.into()).len(), 2);
+
+		// `ParaStatsRetention` is 3 sessions.
+		start_session(4);
+		assert!(SessionStats::<Test>::get(1, ParaId::from(1000)).is_none());
+		assert_eq!(
+			ParaStats::para_stats(1000.into())
+				.into_iter()
+				.map(|(s, _)| s)
+				.collect::<Vec<_>>(),
+			vec![2],
+		);
+	});
+}
@@ -210,6 +210,17 @@ benchmarks! {
 			cores_with_backed.len()
 		);
 	}
+
+	set_skipped_components {
+		let components = BoundedVec::truncate_from(
+			(0..MAX_SKIPPED_COMPONENTS)
+				.map(|i| SkippedComponent::Disputes { first_session: i, last_session: i })
+				.collect(),
+		);
+	}: _(RawOrigin::Root, components.clone())
+	verify {
+		assert_eq!(SkippedComponents::<T>::get(), components);
+	}
 }
 
 impl_benchmark_test_suite!(
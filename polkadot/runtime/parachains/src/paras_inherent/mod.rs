@@ -99,6 +99,32 @@ impl DisputedBitfield {
 	}
 }
 
+/// Maximum number of inherent components that can be skipped at once.
+pub const MAX_SKIPPED_COMPONENTS: u32 = 16;
+
+/// A part of the inherent data that governance excluded from processing.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum SkippedComponent {
+	/// Dispute statement sets of the sessions within the inclusive range.
+	Disputes { first_session: SessionIndex, last_session: SessionIndex },
+	/// Backed candidates of the given para.
+	BackedCandidates(ParaId),
+}
+
+impl SkippedComponent {
+	fn skips_dispute(&self, session: SessionIndex) -> bool {
+		match self {
+			Self::Disputes { first_session, last_session } =>
+				(*first_session..=*last_session).contains(&session),
+			Self::BackedCandidates(_) => false,
+		}
+	}
+
+	fn skips_backed_candidate(&self, para_id: ParaId) -> bool {
+		matches!(self, Self::BackedCandidates(skipped) if *skipped == para_id)
+	}
+}
+
 /// The context in which the inherent data is checked or processed.
 #[derive(PartialEq)]
 pub enum ProcessInherentDataContext {
@@ -123,10 +149,22 @@ pub mod pallet {
 	pub trait Config:
 		inclusion::Config + scheduler::Config + initializer::Config + pallet_babe::Config
 	{
+		/// The overarching event type.
+		type RuntimeEvent: From<Event> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 	}
 
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event {
+		/// The inherent components skipped during processing were changed.
+		SkippedComponentsSet { components: Vec<SkippedComponent> },
+		/// Inherent data was dropped because its component is skipped.
+		InherentDataSkipped { disputes: u32, backed_candidates: u32 },
+	}
+
 	#[pallet::error]
 	pub enum Error<T> {
 		/// Inclusion inherent called more than once per block.
@@ -141,6 +179,8 @@ pub mod pallet {
 		CandidatesFilteredDuringExecution,
 		/// Too many candidates supplied.
 		UnscheduledCandidate,
+		/// A skipped dispute session range ends before it starts.
+		InvalidSkippedSessionRange,
 	}
 
 	/// Whether the paras inherent was included within this block.
@@ -156,6 +196,13 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type OnChainVotes<T: Config> = StorageValue<_, ScrapedOnChainVotes<T::Hash>>;
 
+	/// Components of the inherent data which are dropped instead of being processed.
+	///
+	/// Set by governance, e.g. to stop importing disputes of some sessions during an incident.
+	#[pallet::storage]
+	pub type SkippedComponents<T: Config> =
+		StorageValue<_, BoundedVec<SkippedComponent, ConstU32<MAX_SKIPPED_COMPONENTS>>, ValueQuery>;
+
 	/// Update the disputes statements set part of the on-chain votes.
 	pub(crate) fn set_scrapable_on_chain_disputes<T: Config>(
 		session: SessionIndex,
@@ -200,7 +247,8 @@ pub mod pallet {
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
 		fn on_initialize(_: BlockNumberFor<T>) -> Weight {
-			T::DbWeight::get().reads_writes(1, 1) // in `on_finalize`.
+			// `Included` in `on_finalize` and `SkippedComponents` in `enter`.
+			T::DbWeight::get().reads_writes(2, 1)
 		}
 
 		fn on_finalize(_: BlockNumberFor<T>) {
@@ -251,6 +299,33 @@ pub mod pallet {
 			Self::process_inherent_data(data, ProcessInherentDataContext::Enter)
 				.map(|(_processed, post_info)| post_info)
 		}
+
+		/// Set the components of the inherent data to drop instead of processing them.
+		///
+		/// Replaces the previously skipped components, an empty list restores regular processing.
+		#[pallet::call_index(1)]
+		#[pallet::weight((
+			<T as Config>::WeightInfo::set_skipped_components(),
+			DispatchClass::Operational,
+		))]
+		pub fn set_skipped_components(
+			origin: OriginFor<T>,
+			components: BoundedVec<SkippedComponent, ConstU32<MAX_SKIPPED_COMPONENTS>>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+
+			for component in &components {
+				if let SkippedComponent::Disputes { first_session, last_session } = component {
+					ensure!(first_session <= last_session, Error::<T>::InvalidSkippedSessionRange);
+				}
+			}
+
+			SkippedComponents::<T>::put(&components);
+			Self::deposit_event(Event::SkippedComponentsSet {
+				components: components.into_inner(),
+			});
+			Ok(())
+		}
 	}
 }
 
@@ -324,6 +399,17 @@ impl<T: Config> Pallet<T> {
 			Error::<T>::InvalidParentHeader,
 		);
 
+		let (skipped_disputes, skipped_candidates) =
+			Self::drop_skipped_components(&mut disputes, &mut backed_candidates);
+		if context == ProcessInherentDataContext::Enter &&
+			(skipped_disputes > 0 || skipped_candidates > 0)
+		{
+			Self::deposit_event(Event::InherentDataSkipped {
+				disputes: skipped_disputes,
+				backed_candidates: skipped_candidates,
+			});
+		}
+
 		let now = frame_system::Pallet::<T>::block_number();
 		let config = configuration::ActiveConfig::<T>::get();
 
@@ -641,6 +727,38 @@ impl<T: Config> Pallet<T> {
 		};
 		Ok((processed, Some(all_weight_after).into()))
 	}
+
+	/// Drop the inherent data of the components in [`SkippedComponents`].
+	///
+	/// Returns the number of dropped dispute statement sets and backed candidates.
+	fn drop_skipped_components(
+		disputes: &mut MultiDisputeStatementSet,
+		backed_candidates: &mut Vec<BackedCandidate<T::Hash>>,
+	) -> (u32, u32) {
+		let skipped = SkippedComponents::<T>::get();
+		if skipped.is_empty() {
+			return (0, 0)
+		}
+
+		let disputes_before = disputes.len();
+		disputes.retain(|set| !skipped.iter().any(|c| c.skips_dispute(set.session)));
+		let skipped_disputes = (disputes_before - disputes.len()) as u32;
+
+		let candidates_before = backed_candidates.len();
+		backed_candidates.retain(|candidate| {
+			!skipped.iter().any(|c| c.skips_backed_candidate(candidate.descriptor().para_id))
+		});
+		let skipped_candidates = (candidates_before - backed_candidates.len()) as u32;
+
+		log::debug!(
+			target: LOG_TARGET,
+			"Skipped {} dispute statement sets and {} backed candidates",
+			skipped_disputes,
+			skipped_candidates,
+		);
+
+		(skipped_disputes, skipped_candidates)
+	}
 }
 
 /// Derive a bitfield from dispute
@@ -46,7 +46,10 @@ mod enter {
 	use super::{inclusion::tests::TestCandidateBuilder, *};
 	use crate::{
 		builder::{Bench, BenchBuilder},
-		mock::{mock_assigner, new_test_ext, BlockLength, BlockWeights, RuntimeOrigin, Test},
+		mock::{
+			mock_assigner, new_test_ext, BlockLength, BlockWeights, RuntimeEvent, RuntimeOrigin,
+			Test,
+		},
 		scheduler::{
 			common::{Assignment, AssignmentProvider},
 			ParasEntry,
@@ -55,7 +58,7 @@ mod enter {
 	};
 	use assert_matches::assert_matches;
 	use core::panic;
-	use frame_support::assert_ok;
+	use frame_support::{assert_noop, assert_ok};
 	use frame_system::limits;
 	use primitives::{vstaging::SchedulerParams, AvailabilityBitfield, UncheckedSigned};
 	use sp_runtime::Perbill;
@@ -653,6 +656,111 @@ mod enter {
 		});
 	}
 
+	#[test]
+	// Ensure that disputes of skipped sessions are dropped and reported.
+	fn skipped_disputes_are_dropped() {
+		new_test_ext(MockGenesisConfig::default()).execute_with(|| {
+			let scenario = make_inherent_data(TestConfig {
+				dispute_statements: BTreeMap::new(),
+				dispute_sessions: vec![1, 2],
+				backed_and_concluding: BTreeMap::new(),
+				num_validators_per_core: 5,
+				code_upgrade: None,
+				fill_claimqueue: false,
+				elastic_paras: BTreeMap::new(),
+				unavailable_cores: vec![],
+			});
+			let expected_para_inherent_data = scenario.data.clone();
+			assert_eq!(expected_para_inherent_data.disputes.len(), 2);
+
+			let skipped: BoundedVec<_, _> =
+				vec![SkippedComponent::Disputes { first_session: 0, last_session: 1 }]
+					.try_into()
+					.unwrap();
+			assert_noop!(
+				Pallet::<Test>::set_skipped_components(RuntimeOrigin::signed(1), skipped.clone()),
+				sp_runtime::DispatchError::BadOrigin,
+			);
+			assert_noop!(
+				Pallet::<Test>::set_skipped_components(
+					RuntimeOrigin::root(),
+					vec![SkippedComponent::Disputes { first_session: 2, last_session: 1 }]
+						.try_into()
+						.unwrap(),
+				),
+				Error::<Test>::InvalidSkippedSessionRange,
+			);
+			assert_ok!(Pallet::<Test>::set_skipped_components(RuntimeOrigin::root(), skipped));
+
+			let mut inherent_data = InherentData::new();
+			inherent_data
+				.put_data(PARACHAINS_INHERENT_IDENTIFIER, &expected_para_inherent_data)
+				.unwrap();
+
+			// The dispute of session 1 is not part of the created inherent.
+			let created = Pallet::<Test>::create_inherent_inner(&inherent_data).unwrap();
+			assert_eq!(created.disputes.len(), 1);
+			assert_eq!(created.disputes[0].session, 2);
+
+			// A block author not respecting the skip list has its disputes dropped on import.
+			assert_ok!(Pallet::<Test>::enter(
+				frame_system::RawOrigin::None.into(),
+				expected_para_inherent_data,
+			));
+
+			let disputes = OnChainVotes::<Test>::get().unwrap().disputes;
+			assert_eq!(disputes.len(), 1);
+			assert_eq!(disputes[0].session, 2);
+			assert!(frame_system::Pallet::<Test>::events().iter().any(|record| record.event ==
+				RuntimeEvent::ParaInherent(Event::InherentDataSkipped {
+					disputes: 1,
+					backed_candidates: 0,
+				})));
+		});
+	}
+
+	#[test]
+	// Ensure that backed candidates of skipped paras are dropped and reported.
+	fn skipped_backed_candidates_are_dropped() {
+		new_test_ext(MockGenesisConfig::default()).execute_with(|| {
+			let mut backed_and_concluding = BTreeMap::new();
+			backed_and_concluding.insert(0, 1);
+			backed_and_concluding.insert(1, 1);
+
+			let scenario = make_inherent_data(TestConfig {
+				dispute_statements: BTreeMap::new(),
+				dispute_sessions: vec![],
+				backed_and_concluding,
+				num_validators_per_core: 1,
+				code_upgrade: None,
+				fill_claimqueue: false,
+				elastic_paras: BTreeMap::new(),
+				unavailable_cores: vec![],
+			});
+			let expected_para_inherent_data = scenario.data.clone();
+			assert_eq!(expected_para_inherent_data.backed_candidates.len(), 2);
+
+			assert_ok!(Pallet::<Test>::set_skipped_components(
+				RuntimeOrigin::root(),
+				vec![SkippedComponent::BackedCandidates(1.into())].try_into().unwrap(),
+			));
+
+			assert_ok!(Pallet::<Test>::enter(
+				frame_system::RawOrigin::None.into(),
+				expected_para_inherent_data,
+			));
+
+			let backed = OnChainVotes::<Test>::get().unwrap().backing_validators_per_candidate;
+			assert_eq!(backed.len(), 1);
+			assert_eq!(backed[0].0.descriptor().para_id, ParaId::from(0));
+			assert!(frame_system::Pallet::<Test>::events().iter().any(|record| record.event ==
+				RuntimeEvent::ParaInherent(Event::InherentDataSkipped {
+					disputes: 0,
+					backed_candidates: 1,
+				})));
+		});
+	}
+
 	#[test]
 	// Ensure that when dispute data establishes an over weight block that we adequately
 	// filter out disputes according to our prioritization rule
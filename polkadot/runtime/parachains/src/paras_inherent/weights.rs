@@ -38,6 +38,8 @@ pub trait WeightInfo {
 	fn enter_backed_candidates_variable(v: u32) -> Weight;
 	/// The weight of a single backed candidate with a code upgrade.
 	fn enter_backed_candidate_code_upgrade() -> Weight;
+	/// The weight of `set_skipped_components`.
+	fn set_skipped_components() -> Weight;
 }
 
 pub struct TestWeightInfo;
@@ -60,6 +62,9 @@ impl WeightInfo for TestWeightInfo {
 	fn enter_backed_candidate_code_upgrade() -> Weight {
 		Weight::zero()
 	}
+	fn set_skipped_components() -> Weight {
+		Weight::zero()
+	}
 }
 // To simplify benchmarks running as tests, we set all the weights to 0. `enter` will exit early
 // when if the data causes it to be over weight, but we don't want that to block a benchmark from
@@ -78,6 +83,9 @@ impl WeightInfo for TestWeightInfo {
 	fn enter_backed_candidate_code_upgrade() -> Weight {
 		Weight::zero()
 	}
+	fn set_skipped_components() -> Weight {
+		Weight::zero()
+	}
 }
 
 pub fn paras_inherent_total_weight<T: Config>(
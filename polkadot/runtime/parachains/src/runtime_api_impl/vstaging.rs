@@ -16,8 +16,11 @@
 
 //! Put implementations of functions from staging APIs here.
 
-use crate::{inclusion, initializer, scheduler};
-use primitives::{CommittedCandidateReceipt, CoreIndex, Id as ParaId};
+use crate::{hrmp, inclusion, initializer, para_stats, scheduler};
+use primitives::{
+	vstaging::{CandidateStatsStatement, HrmpChannelUsage},
+	CommittedCandidateReceipt, CoreIndex, HrmpChannelId, Id as ParaId, ValidatorSignature,
+};
 use sp_runtime::traits::One;
 use sp_std::{
 	collections::{btree_map::BTreeMap, vec_deque::VecDeque},
@@ -49,3 +52,16 @@ pub fn candidates_pending_availability<T: initializer::Config>(
 ) -> Vec<CommittedCandidateReceipt<T::Hash>> {
 	<inclusion::Pallet<T>>::candidates_pending_availability(para_id)
 }
+
+/// Returns the moving averages of the traffic going through the given HRMP channel.
+pub fn hrmp_channel_usage<T: hrmp::Config>(channel: HrmpChannelId) -> Option<HrmpChannelUsage> {
+	<hrmp::Pallet<T>>::channel_usage(&channel)
+}
+
+/// Submits the statistics of an enacted candidate measured by a validator.
+pub fn submit_candidate_stats<T: para_stats::Config>(
+	stmt: CandidateStatsStatement,
+	signature: ValidatorSignature,
+) {
+	<para_stats::Pallet<T>>::submit_candidate_stats(stmt, signature)
+}
@@ -0,0 +1,90 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Upgrade readiness pallet benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::{Pallet, *};
+use crate::shared::Pallet as ParasShared;
+
+use frame_benchmarking::v2::*;
+use frame_system::RawOrigin;
+use primitives::ValidatorId;
+use sp_application_crypto::RuntimeAppPublic;
+
+const SESSION_INDEX: SessionIndex = 1;
+
+const NODE_VERSION: NodeVersion = NodeVersion { major: 1, minor: 0, patch: 0 };
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn signal_readiness() {
+		// Setup
+		let validator = <ValidatorId as RuntimeAppPublic>::generate_pair(None);
+		ParasShared::<T>::set_active_validators_ascending(vec![validator.clone()]);
+		ParasShared::<T>::set_session_index(SESSION_INDEX);
+		RequiredNodeVersion::<T>::put(NODE_VERSION);
+
+		let stmt = ReadinessStatement {
+			session_index: SESSION_INDEX,
+			validator_index: ValidatorIndex(0),
+			node_version: NODE_VERSION,
+		};
+		let signature = validator.sign(&stmt.signing_payload()).unwrap();
+
+		#[extrinsic_call]
+		_(RawOrigin::None, stmt, signature);
+
+		assert_eq!(ReadyValidators::<T>::get(), 1);
+	}
+
+	#[benchmark]
+	fn set_required_node_version(v: Linear<1, 1_000>) {
+		// Setup
+		ParasShared::<T>::set_session_index(SESSION_INDEX);
+		for i in 0..v {
+			NodeVersions::<T>::insert(SESSION_INDEX, ValidatorIndex(i), NODE_VERSION);
+		}
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, Some(NODE_VERSION));
+
+		assert_eq!(ReadyValidators::<T>::get(), v);
+	}
+
+	#[benchmark]
+	fn discard_pending_code() {
+		// Setup
+		let code = vec![0u8; 4 * 1024 * 1024];
+		PendingCodeHash::<T>::put(T::Hashing::hash(&code));
+		PendingCode::<T>::put(code);
+
+		#[extrinsic_call]
+		_(RawOrigin::Root);
+
+		assert!(Pallet::<T>::pending_upgrade().is_none());
+	}
+
+	impl_benchmark_test_suite!(
+		Pallet,
+		crate::mock::new_test_ext(Default::default()),
+		crate::mock::Test
+	);
+}
@@ -0,0 +1,553 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The upgrade readiness module.
+//!
+//! Gates the enactment of the runtime upgrades of the relay chain on the readiness of the
+//! validators, reducing the risk of a large part of the validators running a node which is
+//! incompatible with the new runtime, e.g. lacking a host function or the support of a new PVF
+//! executor parameter, once it is enacted.
+//!
+//! The gate is enabled by setting a [`RequiredNodeVersion`]. The runtime upgrades set through
+//! `frame_system` are then kept pending until at least [`Config::ReadinessThreshold`] of the
+//! active validators signalled running a node of the required version or later. This module
+//! must be configured as the `OnSetCode` hook of `frame_system` to do so. Like heartbeats, the
+//! validators signal the version of their node once per session, with signed statements
+//! submitted as unsigned transactions by their offchain workers. The node stores its version in
+//! the persistent offchain storage under [`NODE_VERSION_OFFCHAIN_KEY`] for them to do so.
+//!
+//! Disabling the gate enacts the pending upgrade in the next block, governance may also discard
+//! it. This module is not handled by the initializer but is instead instantiated in the
+//! `construct_runtime` macro.
+
+mod benchmarking;
+
+#[cfg(test)]
+mod tests;
+
+use crate::{paras, shared};
+use frame_support::{pallet_prelude::*, traits::EstimateNextSessionRotation};
+use frame_system::pallet_prelude::*;
+use parity_scale_codec::{Codec, Decode, Encode};
+use primitives::{SessionIndex, ValidatorId, ValidatorIndex, ValidatorSignature};
+use scale_info::TypeInfo;
+use sp_core::{offchain::StorageKind, RuntimeDebug};
+use sp_runtime::{
+	offchain::storage::{StorageRetrievalError, StorageValueRef},
+	traits::{AppVerify, Hash as HashT},
+	Perbill, RuntimeAppPublic,
+};
+use sp_std::prelude::*;
+
+pub use pallet::*;
+pub use primitives::vstaging::NODE_VERSION_OFFCHAIN_KEY;
+
+const LOG_TARGET: &str = "runtime::parachains::upgrade-readiness";
+
+/// The key of the last signal of the local validator in the persistent offchain storage.
+const LAST_SIGNAL_OFFCHAIN_KEY: &[u8] = b"parachains::upgrade_readiness::last_signal";
+
+/// The number of blocks after which a signal which didn't make it on chain is submitted again.
+const RESIGNAL_PERIOD: u32 = 10;
+
+pub trait WeightInfo {
+	fn signal_readiness() -> Weight;
+	fn set_required_node_version(v: u32) -> Weight;
+	fn discard_pending_code() -> Weight;
+}
+
+/// A weight info that is only suitable for testing.
+pub struct TestWeightInfo;
+
+impl WeightInfo for TestWeightInfo {
+	fn signal_readiness() -> Weight {
+		Weight::MAX
+	}
+	fn set_required_node_version(_v: u32) -> Weight {
+		Weight::MAX
+	}
+	fn discard_pending_code() -> Weight {
+		Weight::MAX
+	}
+}
+
+/// The version of a validator node, ordered by `major`, then `minor`, then `patch`.
+#[derive(
+	Encode,
+	Decode,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	PartialOrd,
+	Ord,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+pub struct NodeVersion {
+	pub major: u32,
+	pub minor: u32,
+	pub patch: u32,
+}
+
+impl NodeVersion {
+	/// Parses a `major.minor.patch` version.
+	pub fn parse(version: &str) -> Option<Self> {
+		let mut parts = version.split('.').map(|part| part.parse::<u32>().ok());
+		let version =
+			NodeVersion { major: parts.next()??, minor: parts.next()??, patch: parts.next()?? };
+		parts.next().is_none().then_some(version)
+	}
+}
+
+/// The version of the node of a validator signalled by the validator.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct ReadinessStatement {
+	/// The session the signal is for.
+	pub session_index: SessionIndex,
+	/// The index of the signalling validator in the active validator set.
+	pub validator_index: ValidatorIndex,
+	/// The version of the node of the validator.
+	pub node_version: NodeVersion,
+}
+
+impl ReadinessStatement {
+	/// Yields the signing payload for this statement.
+	pub fn signing_payload(&self) -> Vec<u8> {
+		const MAGIC: [u8; 4] = *b"URDY";
+		(MAGIC, self.clone()).encode()
+	}
+}
+
+/// The status of a pending runtime upgrade.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct PendingUpgrade<Hash> {
+	/// The hash of the pending runtime code.
+	pub code_hash: Hash,
+	/// The node version the validators must run, `None` if the gate was disabled, in which
+	/// case the upgrade is enacted in the next block.
+	pub required_node_version: Option<NodeVersion>,
+	/// The number of active validators which signalled running the required version.
+	pub ready_validators: u32,
+	/// The number of active validators which must signal running the required version.
+	pub needed_validators: u32,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use sp_runtime::transaction_validity::{
+		InvalidTransaction, TransactionPriority, TransactionSource, TransactionValidity,
+		ValidTransaction,
+	};
+
+	#[pallet::pallet]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config:
+		frame_system::Config
+		+ shared::Config
+		+ paras::Config
+		+ frame_system::offchain::SendTransactionTypes<Call<Self>>
+	{
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The share of the active validators which must signal running the required node
+		/// version before a runtime upgrade is enacted.
+		#[pallet::constant]
+		type ReadinessThreshold: Get<Perbill>;
+
+		/// The priority of the validators' signals.
+		#[pallet::constant]
+		type UnsignedPriority: Get<TransactionPriority>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// The node version the validators must run before a runtime upgrade is enacted. If `None`,
+	/// then the runtime upgrades are enacted right away.
+	#[pallet::storage]
+	pub type RequiredNodeVersion<T: Config> = StorageValue<_, NodeVersion, OptionQuery>;
+
+	/// The runtime code waiting for the validators to be ready.
+	#[pallet::storage]
+	pub(super) type PendingCode<T: Config> = StorageValue<_, Vec<u8>, OptionQuery>;
+
+	/// The hash of [`PendingCode`].
+	#[pallet::storage]
+	pub type PendingCodeHash<T: Config> = StorageValue<_, T::Hash, OptionQuery>;
+
+	/// The node versions signalled by the active validators, per session.
+	#[pallet::storage]
+	pub(super) type NodeVersions<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		SessionIndex,
+		Twox64Concat,
+		ValidatorIndex,
+		NodeVersion,
+		OptionQuery,
+	>;
+
+	/// The number of active validators which signalled running [`RequiredNodeVersion`] or later
+	/// in the current session.
+	#[pallet::storage]
+	pub(super) type ReadyValidators<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// The session the signals were last recorded in.
+	#[pallet::storage]
+	pub(super) type LastSession<T: Config> = StorageValue<_, SessionIndex, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A runtime upgrade is pending until the validators are ready.
+		UpgradeGated { code_hash: T::Hash },
+		/// The pending runtime upgrade has been enacted.
+		PendingUpgradeEnacted { code_hash: T::Hash },
+		/// The pending runtime upgrade has been discarded.
+		PendingUpgradeDiscarded { code_hash: T::Hash },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The statement is for a past session.
+		StatementStale,
+		/// The statement is for a future session.
+		StatementFuture,
+		/// The validator index is out of bounds of the active validator set.
+		ValidatorIndexOutOfBounds,
+		/// The signature of the statement is invalid.
+		InvalidSignature,
+		/// The validator already signalled this node version in the session.
+		AlreadySignalled,
+		/// There is no pending runtime upgrade.
+		NoPendingUpgrade,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_now: BlockNumberFor<T>) -> Weight {
+			let mut weight = T::DbWeight::get().reads(3);
+
+			let session_index = shared::CurrentSessionIndex::<T>::get();
+			let last_session = LastSession::<T>::get();
+			if session_index != last_session {
+				// The signals are bounded by the number of validators, the validators signal
+				// again in the new session.
+				LastSession::<T>::put(session_index);
+				ReadyValidators::<T>::kill();
+				let removed = NodeVersions::<T>::clear_prefix(last_session, u32::MAX, None);
+				weight.saturating_accrue(
+					T::DbWeight::get().writes(removed.backend.saturating_add(2).into()),
+				);
+			}
+
+			if PendingCodeHash::<T>::exists() && Self::is_ready() {
+				Self::enact_pending_code();
+				// Like `frame_system::Pallet::set_code`.
+				weight = T::BlockWeights::get().max_block;
+			}
+
+			weight
+		}
+
+		fn offchain_worker(now: BlockNumberFor<T>) {
+			// Only the validators signal the version of their node.
+			if sp_io::offchain::is_validator() {
+				Self::offchain_signal_readiness(now);
+			}
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Record the version of the node of a validator as signalled by the validator.
+		#[pallet::call_index(0)]
+		#[pallet::weight(<T as Config>::WeightInfo::signal_readiness())]
+		pub fn signal_readiness(
+			origin: OriginFor<T>,
+			stmt: ReadinessStatement,
+			signature: ValidatorSignature,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+
+			let current_session = shared::CurrentSessionIndex::<T>::get();
+			if stmt.session_index < current_session {
+				return Err(Error::<T>::StatementStale.into())
+			} else if stmt.session_index > current_session {
+				return Err(Error::<T>::StatementFuture.into())
+			}
+
+			let validators = shared::ActiveValidatorKeys::<T>::get();
+			let validator_public = validators
+				.get(stmt.validator_index.0 as usize)
+				.ok_or(Error::<T>::ValidatorIndexOutOfBounds)?;
+			ensure!(
+				signature.verify(&stmt.signing_payload()[..], validator_public),
+				Error::<T>::InvalidSignature,
+			);
+
+			let previous = NodeVersions::<T>::get(stmt.session_index, stmt.validator_index);
+			ensure!(previous != Some(stmt.node_version), Error::<T>::AlreadySignalled);
+			NodeVersions::<T>::insert(stmt.session_index, stmt.validator_index, stmt.node_version);
+
+			let required = RequiredNodeVersion::<T>::get();
+			let is_compatible = |version: &NodeVersion| required.map_or(false, |r| *version >= r);
+			match (
+				previous.as_ref().map_or(false, is_compatible),
+				is_compatible(&stmt.node_version),
+			) {
+				(false, true) => ReadyValidators::<T>::mutate(|ready| ready.saturating_inc()),
+				(true, false) => ReadyValidators::<T>::mutate(|ready| ready.saturating_dec()),
+				_ => {},
+			}
+
+			Ok(())
+		}
+
+		/// Set the node version the validators must run before a runtime upgrade is enacted.
+		///
+		/// `None` disables the gate, the pending runtime upgrade is then enacted in the next
+		/// block.
+		#[pallet::call_index(1)]
+		#[pallet::weight((
+			<T as Config>::WeightInfo::set_required_node_version(
+				shared::ActiveValidatorKeys::<T>::decode_len().unwrap_or_default() as u32,
+			),
+			DispatchClass::Operational,
+		))]
+		pub fn set_required_node_version(
+			origin: OriginFor<T>,
+			version: Option<NodeVersion>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			RequiredNodeVersion::<T>::set(version);
+
+			let session_index = shared::CurrentSessionIndex::<T>::get();
+			let ready = version.map_or(0, |required| {
+				NodeVersions::<T>::iter_prefix_values(session_index)
+					.filter(|signalled| *signalled >= required)
+					.count() as u32
+			});
+			ReadyValidators::<T>::put(ready);
+
+			Ok(())
+		}
+
+		/// Discard the pending runtime upgrade.
+		#[pallet::call_index(2)]
+		#[pallet::weight((
+			<T as Config>::WeightInfo::discard_pending_code(),
+			DispatchClass::Operational,
+		))]
+		pub fn discard_pending_code(origin: OriginFor<T>) -> DispatchResult {
+			ensure_root(origin)?;
+			let code_hash = PendingCodeHash::<T>::take().ok_or(Error::<T>::NoPendingUpgrade)?;
+			PendingCode::<T>::kill();
+			Self::deposit_event(Event::PendingUpgradeDiscarded { code_hash });
+			Ok(())
+		}
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			let (stmt, signature) = match call {
+				Call::signal_readiness { stmt, signature } => (stmt, signature),
+				_ => return InvalidTransaction::Call.into(),
+			};
+
+			let current_session = shared::CurrentSessionIndex::<T>::get();
+			if stmt.session_index < current_session {
+				return InvalidTransaction::Stale.into()
+			} else if stmt.session_index > current_session {
+				return InvalidTransaction::Future.into()
+			}
+
+			let validators = shared::ActiveValidatorKeys::<T>::get();
+			let validator_public = match validators.get(stmt.validator_index.0 as usize) {
+				Some(pk) => pk,
+				None => return InvalidTransaction::Custom(INVALID_TX_BAD_VALIDATOR_IDX).into(),
+			};
+
+			if !signature.verify(&stmt.signing_payload()[..], validator_public) {
+				return InvalidTransaction::BadProof.into()
+			}
+
+			if NodeVersions::<T>::get(stmt.session_index, stmt.validator_index) ==
+				Some(stmt.node_version)
+			{
+				return InvalidTransaction::Custom(INVALID_TX_ALREADY_SIGNALLED).into()
+			}
+
+			ValidTransaction::with_tag_prefix("UpgradeReadinessSignal")
+				.priority(<T as Config>::UnsignedPriority::get())
+				.longevity(
+					TryInto::<u64>::try_into(
+						<T as paras::Config>::NextSessionRotation::average_session_length() /
+							2u32.into(),
+					)
+					.unwrap_or(64_u64),
+				)
+				.and_provides((stmt.session_index, stmt.validator_index, stmt.node_version))
+				.propagate(true)
+				.build()
+		}
+
+		fn pre_dispatch(_call: &Self::Call) -> Result<(), TransactionValidityError> {
+			// The `signal_readiness` dispatchable performs the same checks.
+			Ok(())
+		}
+	}
+}
+
+// custom transaction error codes
+const INVALID_TX_BAD_VALIDATOR_IDX: u8 = 1;
+const INVALID_TX_ALREADY_SIGNALLED: u8 = 2;
+
+impl<T: Config> Pallet<T> {
+	/// The status of the pending runtime upgrade, if any.
+	pub fn pending_upgrade() -> Option<PendingUpgrade<T::Hash>> {
+		let code_hash = PendingCodeHash::<T>::get()?;
+		Some(PendingUpgrade {
+			code_hash,
+			required_node_version: RequiredNodeVersion::<T>::get(),
+			ready_validators: ReadyValidators::<T>::get(),
+			needed_validators: Self::needed_validators(),
+		})
+	}
+
+	/// Signals the version of the node of the local validator, if it didn't make it on chain in
+	/// the current session yet.
+	///
+	/// This function expects an offchain context and cannot be callable from the on-chain logic.
+	fn offchain_signal_readiness(now: BlockNumberFor<T>) {
+		let Some(node_version) =
+			sp_io::offchain::local_storage_get(StorageKind::PERSISTENT, NODE_VERSION_OFFCHAIN_KEY)
+				.and_then(|version| NodeVersion::parse(sp_std::str::from_utf8(&version).ok()?))
+		else {
+			return
+		};
+
+		let session_index = shared::CurrentSessionIndex::<T>::get();
+		let local_keys = ValidatorId::all();
+		let Some((validator_index, validator)) = shared::ActiveValidatorKeys::<T>::get()
+			.into_iter()
+			.enumerate()
+			.find(|(_, validator)| local_keys.contains(validator))
+		else {
+			return
+		};
+		let validator_index = ValidatorIndex(validator_index as u32);
+		if NodeVersions::<T>::get(session_index, validator_index) == Some(node_version) {
+			return
+		}
+
+		// Like heartbeats, the signal is only submitted again if it didn't make it on chain after
+		// a while.
+		let last_signal = StorageValueRef::persistent(LAST_SIGNAL_OFFCHAIN_KEY);
+		let res = last_signal.mutate(
+			|last: Result<
+				Option<(SessionIndex, NodeVersion, BlockNumberFor<T>)>,
+				StorageRetrievalError,
+			>| match last {
+				Ok(Some((session, version, at)))
+					if session == session_index &&
+						version == node_version &&
+						now < at.saturating_add(RESIGNAL_PERIOD.into()) =>
+					Err(()),
+				_ => Ok((session_index, node_version, now)),
+			},
+		);
+		if res.is_err() {
+			return
+		}
+
+		let stmt = ReadinessStatement { session_index, validator_index, node_version };
+		let Some(signature) = validator.sign(&stmt.signing_payload()) else {
+			log::error!(target: LOG_TARGET, "Error signing readiness statement");
+			return
+		};
+
+		use frame_system::offchain::SubmitTransaction;
+
+		if let Err(e) = SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(
+			Call::signal_readiness { stmt, signature }.into(),
+		) {
+			log::error!(target: LOG_TARGET, "Error submitting readiness statement: {:?}", e);
+		}
+	}
+
+	/// The number of active validators which must signal running the required node version.
+	fn needed_validators() -> u32 {
+		let validators = shared::ActiveValidatorKeys::<T>::decode_len().unwrap_or_default();
+		T::ReadinessThreshold::get().mul_ceil(validators as u32)
+	}
+
+	/// Whether the validators are ready for a runtime upgrade.
+	fn is_ready() -> bool {
+		RequiredNodeVersion::<T>::get().is_none() ||
+			ReadyValidators::<T>::get() >= Self::needed_validators()
+	}
+
+	fn enact_pending_code() {
+		let (Some(code_hash), Some(code)) =
+			(PendingCodeHash::<T>::take(), PendingCode::<T>::take())
+		else {
+			return
+		};
+
+		log::info!(
+			target: LOG_TARGET,
+			"Validators are ready, enacting runtime upgrade {:?}",
+			code_hash,
+		);
+		frame_system::Pallet::<T>::update_code_in_storage(&code);
+		Self::deposit_event(Event::PendingUpgradeEnacted { code_hash });
+	}
+}
+
+impl<T: Config> frame_system::SetCode<T> for Pallet<T> {
+	fn set_code(code: Vec<u8>) -> DispatchResult {
+		if RequiredNodeVersion::<T>::get().is_none() {
+			frame_system::Pallet::<T>::update_code_in_storage(&code);
+			return Ok(())
+		}
+
+		// A later runtime upgrade replaces the pending one.
+		let code_hash = T::Hashing::hash(&code);
+		PendingCode::<T>::put(code);
+		PendingCodeHash::<T>::put(code_hash);
+		Self::deposit_event(Event::UpgradeGated { code_hash });
+		Ok(())
+	}
+}
+
+sp_api::decl_runtime_apis! {
+	/// The API to query the readiness of the validators for the pending runtime upgrade.
+	pub trait UpgradeReadinessApi<Hash: Codec> {
+		/// The status of the pending runtime upgrade, if any.
+		fn pending_upgrade() -> Option<PendingUpgrade<Hash>>;
+	}
+}
@@ -0,0 +1,297 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+use frame_support::{assert_noop, assert_ok, storage::unhashed, traits::Hooks};
+use frame_system::SetCode;
+use keyring::Sr25519Keyring;
+use sp_core::{
+	offchain::{
+		testing::{TestOffchainExt, TestTransactionPoolExt},
+		OffchainDbExt, OffchainWorkerExt, TransactionPoolExt,
+	},
+	storage::well_known_keys::CODE,
+};
+use sp_runtime::transaction_validity::{InvalidTransaction, TransactionSource};
+use test_helpers::validator_pubkeys;
+
+use crate::mock::{
+	new_test_ext, ParasShared, RuntimeCall, RuntimeOrigin, System, Test, UpgradeReadiness,
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+
+static VALIDATORS: &[Sr25519Keyring] =
+	&[Sr25519Keyring::Alice, Sr25519Keyring::Bob, Sr25519Keyring::Charlie];
+
+const fn version(major: u32, minor: u32, patch: u32) -> NodeVersion {
+	NodeVersion { major, minor, patch }
+}
+
+fn start_session(session_index: SessionIndex) {
+	ParasShared::set_active_validators_ascending(validator_pubkeys(VALIDATORS));
+	ParasShared::set_session_index(session_index);
+	UpgradeReadiness::on_initialize(System::block_number());
+}
+
+fn statement(validator: u32, node_version: NodeVersion) -> ReadinessStatement {
+	ReadinessStatement {
+		session_index: shared::CurrentSessionIndex::<Test>::get(),
+		validator_index: ValidatorIndex(validator),
+		node_version,
+	}
+}
+
+fn sign(stmt: &ReadinessStatement) -> ValidatorSignature {
+	// Out of the active validator set, sign with a key which isn't in it.
+	let keyring = VALIDATORS.get(stmt.validator_index.0 as usize).unwrap_or(&Sr25519Keyring::Dave);
+	keyring.sign(&stmt.signing_payload()).into()
+}
+
+fn signal(validator: u32, node_version: NodeVersion) -> DispatchResult {
+	let stmt = statement(validator, node_version);
+	let signature = sign(&stmt);
+	UpgradeReadiness::signal_readiness(RuntimeOrigin::none(), stmt, signature)
+}
+
+fn code() -> Option<Vec<u8>> {
+	unhashed::get_raw(CODE)
+}
+
+#[test]
+fn upgrades_are_enacted_right_away_without_required_version() {
+	new_test_ext(Default::default()).execute_with(|| {
+		start_session(1);
+
+		assert_ok!(UpgradeReadiness::set_code(vec![1, 2, 3]));
+		assert_eq!(code(), Some(vec![1, 2, 3]));
+		assert_eq!(UpgradeReadiness::pending_upgrade(), None);
+	});
+}
+
+#[test]
+fn upgrades_wait_for_ready_validators() {
+	new_test_ext(Default::default()).execute_with(|| {
+		System::set_block_number(1);
+		start_session(1);
+		assert_ok!(UpgradeReadiness::set_required_node_version(
+			RuntimeOrigin::root(),
+			Some(version(1, 2, 0)),
+		));
+
+		assert_ok!(UpgradeReadiness::set_code(vec![1, 2, 3]));
+		assert_eq!(code(), None);
+		let code_hash = PendingCodeHash::<Test>::get().unwrap();
+		System::assert_last_event(Event::<Test>::UpgradeGated { code_hash }.into());
+
+		assert_ok!(signal(0, version(1, 2, 0)));
+		assert_ok!(signal(1, version(1, 1, 9)));
+		UpgradeReadiness::on_initialize(System::block_number());
+		// `ReadinessThreshold` is 66% of the 3 validators.
+		assert_eq!(
+			UpgradeReadiness::pending_upgrade(),
+			Some(PendingUpgrade {
+				code_hash,
+				required_node_version: Some(version(1, 2, 0)),
+				ready_validators: 1,
+				needed_validators: 2,
+			})
+		);
+
+		assert_ok!(signal(1, version(2, 0, 0)));
+		UpgradeReadiness::on_initialize(System::block_number());
+		assert_eq!(code(), Some(vec![1, 2, 3]));
+		assert_eq!(UpgradeReadiness::pending_upgrade(), None);
+		System::assert_last_event(Event::<Test>::PendingUpgradeEnacted { code_hash }.into());
+	});
+}
+
+#[test]
+fn ready_validators_are_counted_per_session() {
+	new_test_ext(Default::default()).execute_with(|| {
+		start_session(1);
+		assert_ok!(signal(0, version(1, 0, 0)));
+		assert_ok!(signal(1, version(1, 1, 0)));
+		assert_eq!(ReadyValidators::<Test>::get(), 0);
+
+		assert_ok!(UpgradeReadiness::set_required_node_version(
+			RuntimeOrigin::root(),
+			Some(version(1, 1, 0)),
+		));
+		assert_eq!(ReadyValidators::<Test>::get(), 1);
+
+		// Downgrading a node is signalled too.
+		assert_ok!(signal(1, version(1, 0, 0)));
+		assert_eq!(ReadyValidators::<Test>::get(), 0);
+		assert_ok!(signal(1, version(1, 1, 0)));
+		assert_eq!(ReadyValidators::<Test>::get(), 1);
+
+		start_session(2);
+		assert_eq!(ReadyValidators::<Test>::get(), 0);
+		assert_eq!(NodeVersions::<Test>::iter_prefix(1).count(), 0);
+		assert_ok!(signal(1, version(1, 1, 0)));
+		assert_eq!(ReadyValidators::<Test>::get(), 1);
+	});
+}
+
+#[test]
+fn pending_upgrades_can_be_discarded_or_released() {
+	new_test_ext(Default::default()).execute_with(|| {
+		System::set_block_number(1);
+		start_session(1);
+		assert_ok!(UpgradeReadiness::set_required_node_version(
+			RuntimeOrigin::root(),
+			Some(version(1, 0, 0)),
+		));
+		assert_noop!(
+			UpgradeReadiness::discard_pending_code(RuntimeOrigin::root()),
+			Error::<Test>::NoPendingUpgrade,
+		);
+
+		assert_ok!(UpgradeReadiness::set_code(vec![1]));
+		let code_hash = PendingCodeHash::<Test>::get().unwrap();
+		assert_ok!(UpgradeReadiness::discard_pending_code(RuntimeOrigin::root()));
+		System::assert_last_event(Event::<Test>::PendingUpgradeDiscarded { code_hash }.into());
+		assert_eq!(UpgradeReadiness::pending_upgrade(), None);
+
+		// Disabling the gate enacts the pending upgrade.
+		assert_ok!(UpgradeReadiness::set_code(vec![2]));
+		assert_ok!(UpgradeReadiness::set_required_node_version(RuntimeOrigin::root(), None));
+		UpgradeReadiness::on_initialize(System::block_number());
+		assert_eq!(code(), Some(vec![2]));
+	});
+}
+
+#[test]
+fn invalid_signals_are_rejected() {
+	new_test_ext(Default::default()).execute_with(|| {
+		start_session(1);
+		assert_ok!(signal(0, version(1, 0, 0)));
+		assert_noop!(signal(0, version(1, 0, 0)), Error::<Test>::AlreadySignalled);
+		assert_noop!(signal(3, version(1, 0, 0)), Error::<Test>::ValidatorIndexOutOfBounds);
+
+		let mut stmt = statement(1, version(1, 0, 0));
+		let signature = sign(&stmt);
+		stmt.node_version = version(2, 0, 0);
+		assert_noop!(
+			UpgradeReadiness::signal_readiness(
+				RuntimeOrigin::none(),
+				stmt.clone(),
+				signature.clone()
+			),
+			Error::<Test>::InvalidSignature,
+		);
+		assert_eq!(
+			UpgradeReadiness::validate_unsigned(
+				TransactionSource::External,
+				&Call::signal_readiness { stmt, signature },
+			),
+			Err(InvalidTransaction::BadProof.into()),
+		);
+
+		let stmt = statement(0, version(1, 0, 0));
+		let signature = sign(&stmt);
+		assert_eq!(
+			UpgradeReadiness::validate_unsigned(
+				TransactionSource::External,
+				&Call::signal_readiness { stmt, signature },
+			),
+			Err(InvalidTransaction::Custom(INVALID_TX_ALREADY_SIGNALLED).into()),
+		);
+
+		let mut stmt = statement(0, version(1, 0, 0));
+		stmt.session_index = 0;
+		let signature = sign(&stmt);
+		assert_noop!(
+			UpgradeReadiness::signal_readiness(RuntimeOrigin::none(), stmt, signature),
+			Error::<Test>::StatementStale,
+		);
+	});
+}
+
+#[test]
+fn valid_signal_passes_validate_unsigned() {
+	new_test_ext(Default::default()).execute_with(|| {
+		start_session(1);
+
+		let stmt = statement(0, version(1, 0, 0));
+		let signature = sign(&stmt);
+		assert!(UpgradeReadiness::validate_unsigned(
+			TransactionSource::External,
+			&Call::signal_readiness { stmt, signature },
+		)
+		.is_ok());
+	});
+}
+
+#[test]
+fn node_versions_are_parsed() {
+	assert_eq!(NodeVersion::parse("1.10.0"), Some(version(1, 10, 0)));
+	assert_eq!(NodeVersion::parse("1.10"), None);
+	assert_eq!(NodeVersion::parse("1.10.0.1"), None);
+	assert_eq!(NodeVersion::parse("1.10.0-dev"), None);
+	assert!(version(1, 10, 0) > version(1, 9, 3));
+}
+
+#[test]
+fn offchain_workers_signal_the_node_version() {
+	let mut ext = new_test_ext(Default::default());
+	let (offchain, _offchain_state) = TestOffchainExt::new();
+	let (pool, pool_state) = TestTransactionPoolExt::new();
+	ext.register_extension(OffchainDbExt::new(offchain.clone()));
+	ext.register_extension(OffchainWorkerExt::new(offchain));
+	ext.register_extension(TransactionPoolExt::new(pool));
+
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		start_session(1);
+
+		// Nothing is signalled before the node stored its version, nor by non-validators.
+		UpgradeReadiness::offchain_worker(1);
+		sp_io::offchain::local_storage_set(
+			StorageKind::PERSISTENT,
+			NODE_VERSION_OFFCHAIN_KEY,
+			b"1.2.0",
+		);
+		UpgradeReadiness::offchain_worker(1);
+		assert!(pool_state.read().transactions.is_empty());
+
+		ValidatorId::generate_pair(Some(Sr25519Keyring::Bob.to_seed().into_bytes()));
+		UpgradeReadiness::offchain_worker(1);
+		let tx = pool_state.write().transactions.pop().unwrap();
+		let tx = UncheckedExtrinsic::decode(&mut &*tx).unwrap();
+		let (stmt, signature) = match tx.function {
+			RuntimeCall::UpgradeReadiness(Call::signal_readiness { stmt, signature }) =>
+				(stmt, signature),
+			call => panic!("unexpected call: {:?}", call),
+		};
+		assert_eq!(stmt, statement(1, version(1, 2, 0)));
+		assert_ok!(UpgradeReadiness::signal_readiness(RuntimeOrigin::none(), stmt, signature));
+
+		// The signal made it on chain.
+		UpgradeReadiness::offchain_worker(2);
+		assert!(pool_state.read().transactions.is_empty());
+
+		// A signal which didn't make it on chain is submitted again after a while.
+		start_session(2);
+		UpgradeReadiness::offchain_worker(2);
+		assert_eq!(pool_state.write().transactions.drain(..).count(), 1);
+		UpgradeReadiness::offchain_worker(3);
+		assert!(pool_state.read().transactions.is_empty());
+		UpgradeReadiness::offchain_worker(2 + RESIGNAL_PERIOD);
+		assert_eq!(pool_state.read().transactions.len(), 1);
+	});
+}
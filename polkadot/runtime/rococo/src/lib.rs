@@ -29,12 +29,12 @@ use frame_support::dynamic_params::{dynamic_pallet_params, dynamic_params};
 use pallet_nis::WithMaximumOf;
 use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
 use primitives::{
-	slashing, AccountId, AccountIndex, ApprovalVotingParams, Balance, BlockNumber, CandidateEvent,
-	CandidateHash, CommittedCandidateReceipt, CoreIndex, CoreState, DisputeState, ExecutorParams,
-	GroupRotationInfo, Hash, Id as ParaId, InboundDownwardMessage, InboundHrmpMessage, Moment,
-	NodeFeatures, Nonce, OccupiedCoreAssumption, PersistedValidationData, ScrapedOnChainVotes,
-	SessionInfo, Signature, ValidationCode, ValidationCodeHash, ValidatorId, ValidatorIndex,
-	PARACHAIN_KEY_TYPE_ID,
+	slashing, vstaging::HrmpChannelUsage, AccountId, AccountIndex, ApprovalVotingParams, Balance,
+	BlockNumber, CandidateEvent, CandidateHash, CommittedCandidateReceipt, CoreIndex, CoreState,
+	DisputeState, ExecutorParams, GroupRotationInfo, Hash, HrmpChannelId, Id as ParaId,
+	InboundDownwardMessage, InboundHrmpMessage, Moment, NodeFeatures, Nonce,
+	OccupiedCoreAssumption, PersistedValidationData, ScrapedOnChainVotes, SessionInfo, Signature,
+	ValidationCode, ValidationCodeHash, ValidatorId, ValidatorIndex, PARACHAIN_KEY_TYPE_ID,
 };
 use rococo_runtime_constants::system_parachain::BROKER_ID;
 use runtime_common::{
@@ -53,13 +53,14 @@ use runtime_parachains::{
 	disputes::slashing as parachains_slashing,
 	dmp as parachains_dmp, hrmp as parachains_hrmp, inclusion as parachains_inclusion,
 	inclusion::{AggregateMessageOrigin, UmpQueueId},
-	initializer as parachains_initializer, origin as parachains_origin, paras as parachains_paras,
+	initializer as parachains_initializer, origin as parachains_origin,
+	para_stats as parachains_para_stats, paras as parachains_paras,
 	paras_inherent as parachains_paras_inherent,
 	runtime_api_impl::{
 		v10 as parachains_runtime_api_impl, vstaging as vstaging_parachains_runtime_api_impl,
 	},
 	scheduler as parachains_scheduler, session_info as parachains_session_info,
-	shared as parachains_shared,
+	shared as parachains_shared, upgrade_readiness as parachains_upgrade_readiness,
 };
 use scale_info::TypeInfo;
 use sp_genesis_builder::PresetId;
@@ -209,6 +210,7 @@ impl frame_system::Config for Runtime {
 	type SystemWeightInfo = weights::frame_system::WeightInfo<Runtime>;
 	type SS58Prefix = SS58Prefix;
 	type MaxConsumers = frame_support::traits::ConstU32<16>;
+	type OnSetCode = UpgradeReadiness;
 }
 
 parameter_types! {
@@ -950,6 +952,7 @@ impl parachains_inclusion::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type DisputesHandler = ParasDisputes;
 	type RewardValidators = RewardValidators;
+	type OnCandidateEnacted = ParaStats;
 	type MessageQueue = MessageQueue;
 	type WeightInfo = weights::runtime_parachains_inclusion::WeightInfo<Runtime>;
 }
@@ -1015,6 +1018,7 @@ impl pallet_message_queue::Config for Runtime {
 		pallet_message_queue::mock_helpers::NoopMessageProcessor<AggregateMessageOrigin>;
 	type QueueChangeHandler = ParaInclusion;
 	type QueuePausedQuery = ();
+	type ServiceShares = ();
 	type WeightInfo = weights::pallet_message_queue::WeightInfo<Runtime>;
 }
 
@@ -1034,6 +1038,7 @@ impl parachains_hrmp::Config for Runtime {
 }
 
 impl parachains_paras_inherent::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = weights::runtime_parachains_paras_inherent::WeightInfo<Runtime>;
 }
 
@@ -1076,6 +1081,29 @@ impl parachains_initializer::Config for Runtime {
 	type CoretimeOnNewSession = Coretime;
 }
 
+parameter_types! {
+	pub const ParaStatsRetention: SessionIndex = 24;
+	pub const ParaStatsReportsPerCandidate: u32 = 5;
+}
+
+impl parachains_para_stats::Config for Runtime {
+	type StatsRetention = ParaStatsRetention;
+	type ReportsPerCandidate = ParaStatsReportsPerCandidate;
+	type UnsignedPriority = ParasUnsignedPriority;
+	type WeightInfo = weights::runtime_parachains_para_stats::WeightInfo<Runtime>;
+}
+
+parameter_types! {
+	pub const UpgradeReadinessThreshold: Perbill = Perbill::from_percent(66);
+}
+
+impl parachains_upgrade_readiness::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type ReadinessThreshold = UpgradeReadinessThreshold;
+	type UnsignedPriority = ParasUnsignedPriority;
+	type WeightInfo = weights::runtime_parachains_upgrade_readiness::WeightInfo<Runtime>;
+}
+
 impl parachains_disputes::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type RewardValidators = ();
@@ -1096,6 +1124,7 @@ impl parachains_slashing::Config for Runtime {
 		Offences,
 		ReportLongevity,
 	>;
+	type DisablingPolicy = parachains_slashing::ConfigurableDisabling<Runtime>;
 	type WeightInfo = parachains_slashing::TestWeightInfo;
 	type BenchmarkingConfig = parachains_slashing::BenchConfig<200>;
 }
@@ -1447,7 +1476,9 @@ construct_runtime! {
 		ParasSlashing: parachains_slashing = 63,
 		MessageQueue: pallet_message_queue = 64,
 		OnDemandAssignmentProvider: parachains_assigner_on_demand = 66,
+		UpgradeReadiness: parachains_upgrade_readiness = 67,
 		CoretimeAssignmentProvider: parachains_assigner_coretime = 68,
+		ParaStats: parachains_para_stats = 69,
 
 		// Parachain Onboarding Pallets. Start indices at 70 to leave room.
 		Registrar: paras_registrar = 70,
@@ -1693,7 +1724,9 @@ mod benches {
 		[runtime_parachains::inclusion, ParaInclusion]
 		[runtime_parachains::initializer, Initializer]
 		[runtime_parachains::paras_inherent, ParaInherent]
+		[runtime_parachains::para_stats, ParaStats]
 		[runtime_parachains::paras, Paras]
+		[runtime_parachains::upgrade_readiness, UpgradeReadiness]
 		[runtime_parachains::assigner_on_demand, OnDemandAssignmentProvider]
 		// Substrate
 		[pallet_balances, Balances]
@@ -1827,7 +1860,7 @@ sp_api::impl_runtime_apis! {
 		}
 	}
 
-	#[api_version(11)]
+	#[api_version(13)]
 	impl primitives::runtime_api::ParachainHost<Block> for Runtime {
 		fn validators() -> Vec<ValidatorId> {
 			parachains_runtime_api_impl::validators::<Runtime>()
@@ -1991,6 +2024,37 @@ sp_api::impl_runtime_apis! {
 		fn candidates_pending_availability(para_id: ParaId) -> Vec<CommittedCandidateReceipt<Hash>> {
 			vstaging_parachains_runtime_api_impl::candidates_pending_availability::<Runtime>(para_id)
 		}
+
+		fn hrmp_channel_usage(channel: HrmpChannelId) -> Option<HrmpChannelUsage> {
+			vstaging_parachains_runtime_api_impl::hrmp_channel_usage::<Runtime>(channel)
+		}
+
+		fn submit_candidate_stats(
+			stmt: primitives::vstaging::CandidateStatsStatement,
+			signature: primitives::ValidatorSignature,
+		) {
+			vstaging_parachains_runtime_api_impl::submit_candidate_stats::<Runtime>(stmt, signature)
+		}
+	}
+
+	impl parachains_para_stats::ParaStatsApi<Block> for Runtime {
+		fn session_stats(
+			session_index: SessionIndex,
+		) -> Vec<(ParaId, parachains_para_stats::ParaSessionStats)> {
+			ParaStats::session_stats(session_index)
+		}
+
+		fn para_stats(
+			para_id: ParaId,
+		) -> Vec<(SessionIndex, parachains_para_stats::ParaSessionStats)> {
+			ParaStats::para_stats(para_id)
+		}
+	}
+
+	impl parachains_upgrade_readiness::UpgradeReadinessApi<Block, Hash> for Runtime {
+		fn pending_upgrade() -> Option<parachains_upgrade_readiness::PendingUpgrade<Hash>> {
+			UpgradeReadiness::pending_upgrade()
+		}
 	}
 
 	#[api_version(3)]
@@ -2215,6 +2279,18 @@ sp_api::impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_beefy_mmr::BeefyValidatorSetApi<Block, BeefyId, Hash> for Runtime {
+		fn validator_set_commitment(
+		) -> Option<beefy_primitives::mmr::BeefyValidatorSetCommitment<BeefyId, Hash>> {
+			MmrLeaf::validator_set_commitment()
+		}
+
+		fn next_validator_set_commitment(
+		) -> Option<beefy_primitives::mmr::BeefyValidatorSetCommitment<BeefyId, Hash>> {
+			MmrLeaf::next_validator_set_commitment()
+		}
+	}
+
 	#[cfg(feature = "try-runtime")]
 	impl frame_try_runtime::TryRuntime<Block> for Runtime {
 		fn on_runtime_upgrade(checks: frame_try_runtime::UpgradeCheckSelect) -> (Weight, Weight) {
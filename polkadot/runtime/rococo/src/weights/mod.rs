@@ -56,6 +56,8 @@ pub mod runtime_parachains_disputes;
 pub mod runtime_parachains_hrmp;
 pub mod runtime_parachains_inclusion;
 pub mod runtime_parachains_initializer;
+pub mod runtime_parachains_para_stats;
 pub mod runtime_parachains_paras;
 pub mod runtime_parachains_paras_inherent;
+pub mod runtime_parachains_upgrade_readiness;
 pub mod xcm;
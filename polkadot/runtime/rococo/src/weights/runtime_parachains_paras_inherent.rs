@@ -331,4 +331,12 @@ impl<T: frame_system::Config> runtime_parachains::paras_inherent::WeightInfo for
 			.saturating_add(T::DbWeight::get().reads(28))
 			.saturating_add(T::DbWeight::get().writes(15))
 	}
+	/// Storage: `ParaInherent::SkippedComponents` (r:0 w:1)
+	/// Proof: `ParaInherent::SkippedComponents` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	fn set_skipped_components() -> Weight {
+		// Placeholder, not benchmarked: to be replaced by the output of the
+		// `set_skipped_components` benchmark.
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
 }
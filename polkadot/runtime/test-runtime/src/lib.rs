@@ -346,9 +346,11 @@ impl pallet_staking::Config for Runtime {
 	type BondingDuration = BondingDuration;
 	type SlashDeferDuration = SlashDeferDuration;
 	type AdminOrigin = frame_system::EnsureNever<()>;
+	type SlashVetoOrigin = frame_system::EnsureNever<()>;
 	type SessionInterface = Self;
 	type EraPayout = pallet_staking::ConvertCurve<RewardCurve>;
 	type MaxExposurePageSize = MaxExposurePageSize;
+	type MaxPayoutSplits = ConstU32<4>;
 	type OffendingValidatorsThreshold = OffendingValidatorsThreshold;
 	type NextNewSession = Session;
 	type ElectionProvider = onchain::OnChainExecution<OnChainSeqPhragmen>;
@@ -489,6 +491,7 @@ impl parachains_inclusion::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type DisputesHandler = ParasDisputes;
 	type RewardValidators = RewardValidatorsWithEraPoints<Runtime>;
+	type OnCandidateEnacted = ();
 	type MessageQueue = ();
 	type WeightInfo = ();
 }
@@ -513,11 +516,13 @@ impl parachains_slashing::Config for Runtime {
 		Offences,
 		ReportLongevity,
 	>;
+	type DisablingPolicy = parachains_slashing::ConfigurableDisabling<Runtime>;
 	type WeightInfo = parachains_disputes::slashing::TestWeightInfo;
 	type BenchmarkingConfig = parachains_slashing::BenchConfig<1000>;
 }
 
 impl parachains_paras_inherent::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = parachains_paras_inherent::TestWeightInfo;
 }
 
@@ -45,12 +45,13 @@ use pallet_session::historical as session_historical;
 use pallet_transaction_payment::{FeeDetails, FungibleAdapter, RuntimeDispatchInfo};
 use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
 use primitives::{
-	slashing, AccountId, AccountIndex, ApprovalVotingParams, Balance, BlockNumber, CandidateEvent,
-	CandidateHash, CommittedCandidateReceipt, CoreIndex, CoreState, DisputeState, ExecutorParams,
-	GroupRotationInfo, Hash, Id as ParaId, InboundDownwardMessage, InboundHrmpMessage, Moment,
-	NodeFeatures, Nonce, OccupiedCoreAssumption, PersistedValidationData, PvfCheckStatement,
-	ScrapedOnChainVotes, SessionInfo, Signature, ValidationCode, ValidationCodeHash, ValidatorId,
-	ValidatorIndex, ValidatorSignature, PARACHAIN_KEY_TYPE_ID,
+	slashing, vstaging::HrmpChannelUsage, AccountId, AccountIndex, ApprovalVotingParams, Balance,
+	BlockNumber, CandidateEvent, CandidateHash, CommittedCandidateReceipt, CoreIndex, CoreState,
+	DisputeState, ExecutorParams, GroupRotationInfo, Hash, HrmpChannelId, Id as ParaId,
+	InboundDownwardMessage, InboundHrmpMessage, Moment, NodeFeatures, Nonce,
+	OccupiedCoreAssumption, PersistedValidationData, PvfCheckStatement, ScrapedOnChainVotes,
+	SessionInfo, Signature, ValidationCode, ValidationCodeHash, ValidatorId, ValidatorIndex,
+	ValidatorSignature, PARACHAIN_KEY_TYPE_ID,
 };
 use runtime_common::{
 	assigned_slots, auctions, crowdloan,
@@ -71,13 +72,14 @@ use runtime_parachains::{
 	disputes::slashing as parachains_slashing,
 	dmp as parachains_dmp, hrmp as parachains_hrmp, inclusion as parachains_inclusion,
 	inclusion::{AggregateMessageOrigin, UmpQueueId},
-	initializer as parachains_initializer, origin as parachains_origin, paras as parachains_paras,
+	initializer as parachains_initializer, origin as parachains_origin,
+	para_stats as parachains_para_stats, paras as parachains_paras,
 	paras_inherent as parachains_paras_inherent, reward_points as parachains_reward_points,
 	runtime_api_impl::{
 		v10 as parachains_runtime_api_impl, vstaging as vstaging_parachains_runtime_api_impl,
 	},
 	scheduler as parachains_scheduler, session_info as parachains_session_info,
-	shared as parachains_shared,
+	shared as parachains_shared, upgrade_readiness as parachains_upgrade_readiness,
 };
 use scale_info::TypeInfo;
 use sp_core::{OpaqueMetadata, RuntimeDebug, H256};
@@ -204,6 +206,7 @@ impl frame_system::Config for Runtime {
 	type SystemWeightInfo = weights::frame_system::WeightInfo<Runtime>;
 	type SS58Prefix = SS58Prefix;
 	type MaxConsumers = frame_support::traits::ConstU32<16>;
+	type OnSetCode = UpgradeReadiness;
 }
 
 parameter_types! {
@@ -630,9 +633,11 @@ impl pallet_staking::Config for Runtime {
 	type BondingDuration = BondingDuration;
 	type SlashDeferDuration = SlashDeferDuration;
 	type AdminOrigin = EitherOf<EnsureRoot<AccountId>, StakingAdmin>;
+	type SlashVetoOrigin = EitherOf<EnsureRoot<AccountId>, StakingAdmin>;
 	type SessionInterface = Self;
 	type EraPayout = pallet_staking::ConvertCurve<RewardCurve>;
 	type MaxExposurePageSize = MaxExposurePageSize;
+	type MaxPayoutSplits = ConstU32<4>;
 	type OffendingValidatorsThreshold = OffendingValidatorsThreshold;
 	type NextNewSession = Session;
 	type ElectionProvider = ElectionProviderMultiPhase;
@@ -1000,7 +1005,8 @@ impl InstanceFilter<RuntimeCall> for ProxyType {
 				matches!(
 					c,
 					RuntimeCall::Staking(..) |
-						RuntimeCall::Session(..) | RuntimeCall::Utility(..) |
+						RuntimeCall::Session(..) |
+						RuntimeCall::Utility(..) |
 						RuntimeCall::FastUnstake(..) |
 						RuntimeCall::VoterList(..) |
 						RuntimeCall::NominationPools(..)
@@ -1084,6 +1090,7 @@ impl parachains_inclusion::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type DisputesHandler = ParasDisputes;
 	type RewardValidators = parachains_reward_points::RewardValidatorsWithEraPoints<Runtime>;
+	type OnCandidateEnacted = ParaStats;
 	type MessageQueue = MessageQueue;
 	type WeightInfo = weights::runtime_parachains_inclusion::WeightInfo<Runtime>;
 }
@@ -1149,6 +1156,7 @@ impl pallet_message_queue::Config for Runtime {
 		pallet_message_queue::mock_helpers::NoopMessageProcessor<AggregateMessageOrigin>;
 	type QueueChangeHandler = ParaInclusion;
 	type QueuePausedQuery = ();
+	type ServiceShares = ();
 	type WeightInfo = weights::pallet_message_queue::WeightInfo<Runtime>;
 }
 
@@ -1168,6 +1176,7 @@ impl parachains_hrmp::Config for Runtime {
 }
 
 impl parachains_paras_inherent::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = weights::runtime_parachains_paras_inherent::WeightInfo<Runtime>;
 }
 
@@ -1210,6 +1219,29 @@ impl parachains_initializer::Config for Runtime {
 	type CoretimeOnNewSession = Coretime;
 }
 
+parameter_types! {
+	pub const ParaStatsRetention: SessionIndex = 24;
+	pub const ParaStatsReportsPerCandidate: u32 = 5;
+}
+
+impl parachains_para_stats::Config for Runtime {
+	type StatsRetention = ParaStatsRetention;
+	type ReportsPerCandidate = ParaStatsReportsPerCandidate;
+	type UnsignedPriority = ParasUnsignedPriority;
+	type WeightInfo = weights::runtime_parachains_para_stats::WeightInfo<Runtime>;
+}
+
+parameter_types! {
+	pub const UpgradeReadinessThreshold: Perbill = Perbill::from_percent(66);
+}
+
+impl parachains_upgrade_readiness::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type ReadinessThreshold = UpgradeReadinessThreshold;
+	type UnsignedPriority = ParasUnsignedPriority;
+	type WeightInfo = weights::runtime_parachains_upgrade_readiness::WeightInfo<Runtime>;
+}
+
 impl paras_sudo_wrapper::Config for Runtime {}
 
 parameter_types! {
@@ -1248,6 +1280,7 @@ impl parachains_slashing::Config for Runtime {
 		Offences,
 		ReportLongevity,
 	>;
+	type DisablingPolicy = parachains_slashing::ConfigurableDisabling<Runtime>;
 	type WeightInfo = weights::runtime_parachains_disputes_slashing::WeightInfo<Runtime>;
 	type BenchmarkingConfig = parachains_slashing::BenchConfig<300>;
 }
@@ -1531,6 +1564,10 @@ mod runtime {
 	pub type OnDemandAssignmentProvider = parachains_assigner_on_demand;
 	#[runtime::pallet_index(57)]
 	pub type CoretimeAssignmentProvider = parachains_assigner_coretime;
+	#[runtime::pallet_index(58)]
+	pub type UpgradeReadiness = parachains_upgrade_readiness;
+	#[runtime::pallet_index(59)]
+	pub type ParaStats = parachains_para_stats;
 
 	// Parachain Onboarding Pallets. Start indices at 60 to leave room.
 	#[runtime::pallet_index(60)]
@@ -1702,7 +1739,9 @@ mod benches {
 		[runtime_parachains::hrmp, Hrmp]
 		[runtime_parachains::inclusion, ParaInclusion]
 		[runtime_parachains::initializer, Initializer]
+		[runtime_parachains::para_stats, ParaStats]
 		[runtime_parachains::paras, Paras]
+		[runtime_parachains::upgrade_readiness, UpgradeReadiness]
 		[runtime_parachains::paras_inherent, ParaInherent]
 		[runtime_parachains::assigner_on_demand, OnDemandAssignmentProvider]
 		[runtime_parachains::coretime, Coretime]
@@ -1808,7 +1847,7 @@ sp_api::impl_runtime_apis! {
 		}
 	}
 
-	#[api_version(11)]
+	#[api_version(13)]
 	impl primitives::runtime_api::ParachainHost<Block> for Runtime {
 		fn validators() -> Vec<ValidatorId> {
 			parachains_runtime_api_impl::validators::<Runtime>()
@@ -1972,6 +2011,37 @@ sp_api::impl_runtime_apis! {
 		fn candidates_pending_availability(para_id: ParaId) -> Vec<CommittedCandidateReceipt<Hash>> {
 			vstaging_parachains_runtime_api_impl::candidates_pending_availability::<Runtime>(para_id)
 		}
+
+		fn hrmp_channel_usage(channel: HrmpChannelId) -> Option<HrmpChannelUsage> {
+			vstaging_parachains_runtime_api_impl::hrmp_channel_usage::<Runtime>(channel)
+		}
+
+		fn submit_candidate_stats(
+			stmt: primitives::vstaging::CandidateStatsStatement,
+			signature: ValidatorSignature,
+		) {
+			vstaging_parachains_runtime_api_impl::submit_candidate_stats::<Runtime>(stmt, signature)
+		}
+	}
+
+	impl parachains_para_stats::ParaStatsApi<Block> for Runtime {
+		fn session_stats(
+			session_index: SessionIndex,
+		) -> Vec<(ParaId, parachains_para_stats::ParaSessionStats)> {
+			ParaStats::session_stats(session_index)
+		}
+
+		fn para_stats(
+			para_id: ParaId,
+		) -> Vec<(SessionIndex, parachains_para_stats::ParaSessionStats)> {
+			ParaStats::para_stats(para_id)
+		}
+	}
+
+	impl parachains_upgrade_readiness::UpgradeReadinessApi<Block, Hash> for Runtime {
+		fn pending_upgrade() -> Option<parachains_upgrade_readiness::PendingUpgrade<Hash>> {
+			UpgradeReadiness::pending_upgrade()
+		}
 	}
 
 	impl beefy_primitives::BeefyApi<Block, BeefyId> for Runtime {
@@ -2067,6 +2137,18 @@ sp_api::impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_beefy_mmr::BeefyValidatorSetApi<Block, BeefyId, Hash> for Runtime {
+		fn validator_set_commitment(
+		) -> Option<beefy_primitives::mmr::BeefyValidatorSetCommitment<BeefyId, Hash>> {
+			BeefyMmrLeaf::validator_set_commitment()
+		}
+
+		fn next_validator_set_commitment(
+		) -> Option<beefy_primitives::mmr::BeefyValidatorSetCommitment<BeefyId, Hash>> {
+			BeefyMmrLeaf::next_validator_set_commitment()
+		}
+	}
+
 	impl fg_primitives::GrandpaApi<Block> for Runtime {
 		fn grandpa_authorities() -> Vec<(GrandpaId, u64)> {
 			Grandpa::grandpa_authorities()
@@ -2170,6 +2252,12 @@ sp_api::impl_runtime_apis! {
 		}
 	}
 
+	impl sp_session::SessionValidatorsApi<Block, AccountId, BlockNumber> for Runtime {
+		fn queued_validator_set() -> sp_session::QueuedValidatorSet<AccountId, BlockNumber> {
+			Session::queued_validator_set()
+		}
+	}
+
 	impl frame_system_rpc_runtime_api::AccountNonceApi<Block, AccountId, Nonce> for Runtime {
 		fn account_nonce(account: AccountId) -> Nonce {
 			System::account_nonce(account)
@@ -2268,6 +2356,14 @@ sp_api::impl_runtime_apis! {
 		fn eras_stakers_page_count(era: sp_staking::EraIndex, account: AccountId) -> sp_staking::Page {
 			Staking::api_eras_stakers_page_count(era, account)
 		}
+
+		fn deferred_slashes() -> Vec<sp_staking::DeferredSlash<AccountId, Balance>> {
+			Staking::api_deferred_slashes()
+		}
+
+		fn new_era_forced() -> bool {
+			Staking::api_new_era_forced()
+		}
 	}
 
 	#[cfg(feature = "try-runtime")]
@@ -98,4 +98,10 @@ impl<T: frame_system::Config> runtime_parachains::disputes::slashing::WeightInfo
 			.saturating_add(T::DbWeight::get().writes(9))
 			.saturating_add(Weight::from_parts(0, 192).saturating_mul(n.into()))
 	}
+	/// Storage: ParasSlashing ActiveDisablingParams (r:0 w:1)
+	/// Proof Skipped: ParasSlashing ActiveDisablingParams (max_values: Some(1), max_size: None, mode: Measured)
+	fn set_disabling_params() -> Weight {
+		Weight::from_parts(7_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
 }
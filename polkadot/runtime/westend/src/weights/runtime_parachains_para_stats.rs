@@ -0,0 +1,64 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Placeholder weights for `runtime_parachains::para_stats`
+//!
+//! NOT BENCHMARKED YET: to be replaced by the output of the command below.
+
+// Executed Command:
+// ./target/production/polkadot
+// benchmark
+// pallet
+// --chain=westend-dev
+// --steps=50
+// --repeat=20
+// --pallet=runtime_parachains::para_stats
+// --extrinsic=*
+// --wasm-execution=compiled
+// --heap-pages=4096
+// --header=./polkadot/file_header.txt
+// --output=./polkadot/runtime/westend/src/weights/runtime_parachains_para_stats.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::Weight};
+use core::marker::PhantomData;
+
+/// Weight functions for `runtime_parachains::para_stats`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> runtime_parachains::para_stats::WeightInfo for WeightInfo<T> {
+	/// Storage: `ParasShared::CurrentSessionIndex` (r:1 w:0)
+	/// Proof: `ParasShared::CurrentSessionIndex` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `ParasShared::ActiveValidatorKeys` (r:1 w:0)
+	/// Proof: `ParasShared::ActiveValidatorKeys` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `ParaStats::UnreportedCandidates` (r:1 w:1)
+	/// Proof: `ParaStats::UnreportedCandidates` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `ParaStats::CandidateReports` (r:1 w:1)
+	/// Proof: `ParaStats::CandidateReports` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `ParaStats::SessionStats` (r:1 w:1)
+	/// Proof: `ParaStats::SessionStats` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn report_candidate_stats() -> Weight {
+		// Placeholder, not benchmarked: dominated by the verification of the signature, like
+		// `paras::include_pvf_check_statement`.
+		Weight::from_parts(80_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 36_000))
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+}
@@ -0,0 +1,96 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Placeholder weights for `runtime_parachains::upgrade_readiness`
+//!
+//! NOT BENCHMARKED YET: to be replaced by the output of the command below.
+
+// Executed Command:
+// ./target/production/polkadot
+// benchmark
+// pallet
+// --chain=westend-dev
+// --steps=50
+// --repeat=20
+// --pallet=runtime_parachains::upgrade_readiness
+// --extrinsic=*
+// --wasm-execution=compiled
+// --heap-pages=4096
+// --header=./polkadot/file_header.txt
+// --output=./polkadot/runtime/westend/src/weights/runtime_parachains_upgrade_readiness.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::Weight};
+use core::marker::PhantomData;
+
+/// Weight functions for `runtime_parachains::upgrade_readiness`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> runtime_parachains::upgrade_readiness::WeightInfo for WeightInfo<T> {
+	/// Storage: `ParasShared::CurrentSessionIndex` (r:1 w:0)
+	/// Proof: `ParasShared::CurrentSessionIndex` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `ParasShared::ActiveValidatorKeys` (r:1 w:0)
+	/// Proof: `ParasShared::ActiveValidatorKeys` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `UpgradeReadiness::NodeVersions` (r:1 w:1)
+	/// Proof: `UpgradeReadiness::NodeVersions` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `UpgradeReadiness::RequiredNodeVersion` (r:1 w:0)
+	/// Proof: `UpgradeReadiness::RequiredNodeVersion` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `UpgradeReadiness::ReadyValidators` (r:1 w:1)
+	/// Proof: `UpgradeReadiness::ReadyValidators` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	fn signal_readiness() -> Weight {
+		// Placeholder, not benchmarked: dominated by the verification of the signature, like
+		// `paras::include_pvf_check_statement`.
+		Weight::from_parts(80_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 32_000))
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `ParasShared::CurrentSessionIndex` (r:1 w:0)
+	/// Proof: `ParasShared::CurrentSessionIndex` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `UpgradeReadiness::NodeVersions` (r:1000 w:0)
+	/// Proof: `UpgradeReadiness::NodeVersions` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `UpgradeReadiness::ReadyValidators` (r:0 w:1)
+	/// Proof: `UpgradeReadiness::ReadyValidators` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `UpgradeReadiness::RequiredNodeVersion` (r:0 w:1)
+	/// Proof: `UpgradeReadiness::RequiredNodeVersion` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// The range of component `v` is `[1, 1000]`.
+	fn set_required_node_version(v: u32, ) -> Weight {
+		// Placeholder, not benchmarked: to be replaced by the output of the
+		// `set_required_node_version` benchmark.
+		Weight::from_parts(15_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 1_500))
+			.saturating_add(Weight::from_parts(5_000_000, 0).saturating_mul(v.into()))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(v.into())))
+			.saturating_add(T::DbWeight::get().writes(2))
+			.saturating_add(Weight::from_parts(0, 2_500).saturating_mul(v.into()))
+	}
+	/// Storage: `UpgradeReadiness::PendingCodeHash` (r:1 w:1)
+	/// Proof: `UpgradeReadiness::PendingCodeHash` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `UpgradeReadiness::PendingCode` (r:0 w:1)
+	/// Proof: `UpgradeReadiness::PendingCode` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	fn discard_pending_code() -> Weight {
+		// Placeholder, not benchmarked: to be replaced by the output of the
+		// `discard_pending_code` benchmark.
+		Weight::from_parts(20_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 1_500))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+}
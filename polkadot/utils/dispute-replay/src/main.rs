@@ -0,0 +1,306 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Replay the validation of a disputed candidate from on-chain data.
+//!
+//! The receipt of the candidate is found in the candidate events of the relay chain, searching
+//! back from a given block. The validation code, the persisted validation data and the executor
+//! parameters of its session are fetched at its relay parent, and the candidate is validated by
+//! the PVF host with the same workers, timeouts and checks as the approval voting.
+//!
+//! The PoV is not exposed over RPC by the nodes, so it must be given as a file holding the
+//! SCALE-encoded `PoV`, e.g. recovered from the availability store of a validator or kept by the
+//! collator.
+
+use clap::{Parser, ValueEnum};
+use futures::channel::oneshot;
+use parity_scale_codec::{Decode, Encode};
+use polkadot_node_core_pvf::{
+	start, Config, Metrics, PrepareJobKind, Priority, PvfPrepData, EXECUTE_BINARY_NAME,
+	PREPARE_BINARY_NAME,
+};
+use polkadot_node_primitives::{PoV, POV_BOMB_LIMIT, VALIDATION_CODE_BOMB_LIMIT};
+use polkadot_parachain_primitives::primitives::{BlockData, ValidationParams};
+use polkadot_primitives::{
+	executor_params::{
+		DEFAULT_APPROVAL_EXECUTION_TIMEOUT, DEFAULT_BACKING_EXECUTION_TIMEOUT,
+		DEFAULT_LENIENT_PREPARATION_TIMEOUT,
+	},
+	BlockNumber, CandidateCommitments, CandidateEvent, CandidateHash, CandidateReceipt,
+	ExecutorParams, Hash, Header, OccupiedCoreAssumption, PersistedValidationData, PvfExecKind,
+	PvfPrepKind, SessionIndex, ValidationCode,
+};
+use sp_core::Bytes;
+use std::{error::Error, path::PathBuf, time::Instant};
+use substrate_rpc_client::{ws_client, ChainApi, StateApi, WsClient};
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum ExecKind {
+	/// With the timeout of the approval voting and of the disputes.
+	Approval,
+	/// With the timeout of the backing.
+	Backing,
+}
+
+#[derive(Parser)]
+#[command(author, version, about)]
+struct Opt {
+	/// The hash of the disputed candidate.
+	candidate_hash: Hash,
+
+	/// The file holding the SCALE-encoded PoV of the candidate.
+	#[arg(long)]
+	pov: PathBuf,
+
+	/// The WebSocket endpoint of a relay chain node, an archive node unless the candidate is
+	/// recent.
+	#[arg(long, default_value = "ws://127.0.0.1:9944")]
+	uri: String,
+
+	/// The relay chain block to search the candidate from, the finalized head by default.
+	#[arg(long)]
+	at: Option<Hash>,
+
+	/// The number of relay chain blocks searched for the candidate events of the candidate.
+	#[arg(long, default_value_t = 600)]
+	search_depth: u32,
+
+	/// The directory of the `polkadot-prepare-worker` and `polkadot-execute-worker` binaries,
+	/// the directory of this binary by default.
+	#[arg(long)]
+	workers_path: Option<PathBuf>,
+
+	/// The timeout the candidate is executed with.
+	#[arg(long, value_enum, default_value_t = ExecKind::Approval)]
+	exec_kind: ExecKind,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+	let opt = Opt::parse();
+	let candidate_hash = CandidateHash(opt.candidate_hash);
+
+	let client = ws_client(&opt.uri).await?;
+	let at = match opt.at {
+		Some(at) => at,
+		None => ChainApi::<(), Hash, Header, ()>::finalized_head(&client).await?,
+	};
+
+	let (receipt, event_block) =
+		find_candidate_receipt(&client, candidate_hash, at, opt.search_depth).await?;
+	let descriptor = &receipt.descriptor;
+	let relay_parent = descriptor.relay_parent;
+	println!("Candidate {:?} of para {}", candidate_hash, u32::from(descriptor.para_id));
+	println!("  found in the events of block {event_block:?}, relay parent {relay_parent:?}");
+
+	let session: SessionIndex =
+		runtime_call(&client, "ParachainHost_session_index_for_child", (), relay_parent).await?;
+	let executor_params: Option<ExecutorParams> =
+		runtime_call(&client, "ParachainHost_session_executor_params", session, relay_parent)
+			.await?;
+	// The sessions before the executor parameters were introduced use the default ones.
+	let executor_params = executor_params.unwrap_or_default();
+	println!("  session {session}, executor params {executor_params:?}");
+
+	let validation_code: ValidationCode = runtime_call::<Option<ValidationCode>>(
+		&client,
+		"ParachainHost_validation_code_by_hash",
+		descriptor.validation_code_hash,
+		relay_parent,
+	)
+	.await?
+	.ok_or("The validation code of the candidate is unknown at its relay parent")?;
+	let validation_data = find_validation_data(&client, &receipt).await?;
+
+	let pov = PoV::decode(&mut &tokio::fs::read(&opt.pov).await?[..])
+		.map_err(|e| format!("Failed to decode the PoV: {e}"))?;
+
+	// Basic checks of the candidate validation, which don't need the PVF host.
+	if pov.encoded_size() > validation_data.max_pov_size as usize {
+		return verdict_invalid(format!("the PoV exceeds {} bytes", validation_data.max_pov_size))
+	}
+	if pov.hash() != descriptor.pov_hash {
+		return verdict_invalid("the PoV is not the one of the candidate".into())
+	}
+	if validation_code.hash() != descriptor.validation_code_hash {
+		return verdict_invalid("the validation code hash mismatches".into())
+	}
+	if descriptor.check_collator_signature().is_err() {
+		return verdict_invalid("the collator signature is invalid".into())
+	}
+
+	let code = sp_maybe_compressed_blob::decompress(&validation_code.0, VALIDATION_CODE_BOMB_LIMIT)
+		.map_err(|e| format!("Failed to decompress the validation code: {e}"))?;
+	let block_data = match sp_maybe_compressed_blob::decompress(&pov.block_data.0, POV_BOMB_LIMIT) {
+		Ok(block_data) => BlockData(block_data.to_vec()),
+		Err(e) => return verdict_invalid(format!("the PoV can't be decompressed: {e}")),
+	};
+	let params = ValidationParams {
+		parent_head: validation_data.parent_head.clone(),
+		block_data,
+		relay_parent_number: validation_data.relay_parent_number,
+		relay_parent_storage_root: validation_data.relay_parent_storage_root,
+	};
+
+	let workers_path = match opt.workers_path {
+		Some(path) => path,
+		None => std::env::current_exe()?
+			.parent()
+			.ok_or("The binary has no parent directory")?
+			.to_owned(),
+	};
+	// The artifacts are prepared from scratch, such that the preparation is replayed as well.
+	let cache_dir = tempfile::tempdir()?;
+	let config = Config::new(
+		cache_dir.path().to_owned(),
+		None,
+		false,
+		workers_path.join(PREPARE_BINARY_NAME),
+		workers_path.join(EXECUTE_BINARY_NAME),
+	);
+	let (mut host, task) = start(config, Metrics::default()).await?;
+	tokio::spawn(task);
+
+	let prep_timeout = executor_params
+		.pvf_prep_timeout(PvfPrepKind::Prepare)
+		.unwrap_or(DEFAULT_LENIENT_PREPARATION_TIMEOUT);
+	let (exec_kind, default_exec_timeout) = match opt.exec_kind {
+		ExecKind::Approval => (PvfExecKind::Approval, DEFAULT_APPROVAL_EXECUTION_TIMEOUT),
+		ExecKind::Backing => (PvfExecKind::Backing, DEFAULT_BACKING_EXECUTION_TIMEOUT),
+	};
+	let exec_timeout = executor_params.pvf_exec_timeout(exec_kind).unwrap_or(default_exec_timeout);
+	let pvf = PvfPrepData::from_code(
+		code.to_vec(),
+		executor_params,
+		prep_timeout,
+		PrepareJobKind::Compilation,
+	);
+
+	let started = Instant::now();
+	let (tx, rx) = oneshot::channel();
+	host.precheck_pvf(pvf.clone(), tx).await?;
+	if let Err(e) = rx.await? {
+		// Like the node, no verdict is given on a preparation failure, the code passed the
+		// pre-checking.
+		return Err(format!("Failed to prepare the validation code: {e}").into())
+	}
+	println!("Prepared in {:?} (timeout {prep_timeout:?})", started.elapsed());
+
+	let started = Instant::now();
+	let (tx, rx) = oneshot::channel();
+	host.execute_pvf(pvf, exec_timeout, params.encode(), Priority::Normal, tx)
+		.await?;
+	let result = rx.await?;
+	println!("Executed in {:?} (timeout {exec_timeout:?})", started.elapsed());
+
+	let result = match result {
+		Ok(result) => result,
+		Err(e) => return verdict_invalid(e.to_string()),
+	};
+	if result.head_data.hash() != descriptor.para_head {
+		return verdict_invalid("the para head hash mismatches".into())
+	}
+	let commitments = CandidateCommitments {
+		head_data: result.head_data,
+		upward_messages: result.upward_messages,
+		horizontal_messages: result.horizontal_messages,
+		new_validation_code: result.new_validation_code,
+		processed_downward_messages: result.processed_downward_messages,
+		hrmp_watermark: result.hrmp_watermark,
+	};
+	if commitments.hash() != receipt.commitments_hash {
+		return verdict_invalid("the commitments hash mismatches".into())
+	}
+
+	println!("Verdict: valid");
+	Ok(())
+}
+
+/// Print that the candidate is invalid for the given reason.
+fn verdict_invalid(reason: String) -> Result<()> {
+	println!("Verdict: invalid, {reason}");
+	Ok(())
+}
+
+/// Call the runtime API `method` at the relay chain block `at`.
+async fn runtime_call<R: Decode>(
+	client: &WsClient,
+	method: &str,
+	args: impl Encode,
+	at: Hash,
+) -> Result<R> {
+	let Bytes(result) =
+		StateApi::<Hash>::call(client, method.into(), Bytes(args.encode()), Some(at)).await?;
+	R::decode(&mut &result[..]).map_err(|e| format!("Failed to decode `{method}`: {e}").into())
+}
+
+/// Find the receipt of the candidate in the candidate events of the `depth` relay chain blocks
+/// up to `at`, together with the block it was found at.
+async fn find_candidate_receipt(
+	client: &WsClient,
+	candidate_hash: CandidateHash,
+	mut at: Hash,
+	depth: u32,
+) -> Result<(CandidateReceipt, Hash)> {
+	for _ in 0..depth {
+		let events: Vec<CandidateEvent> =
+			runtime_call(client, "ParachainHost_candidate_events", (), at).await?;
+		let receipt = events.into_iter().find_map(|event| match event {
+			CandidateEvent::CandidateBacked(receipt, ..) |
+			CandidateEvent::CandidateIncluded(receipt, ..) |
+			CandidateEvent::CandidateTimedOut(receipt, ..) =>
+				(receipt.hash() == candidate_hash).then_some(receipt),
+		});
+		if let Some(receipt) = receipt {
+			return Ok((receipt, at))
+		}
+
+		let header = ChainApi::<BlockNumber, Hash, Header, ()>::header(client, Some(at))
+			.await?
+			.ok_or_else(|| format!("Block {at:?} is unknown"))?;
+		if header.number == 0 {
+			break
+		}
+		at = header.parent_hash;
+	}
+	Err(format!("No candidate event of {candidate_hash:?} in the searched blocks").into())
+}
+
+/// Find the persisted validation data the candidate was built on, among the ones of the relay
+/// parent for the assumptions of the candidate validation.
+async fn find_validation_data(
+	client: &WsClient,
+	receipt: &CandidateReceipt,
+) -> Result<PersistedValidationData> {
+	let descriptor = &receipt.descriptor;
+	for assumption in [OccupiedCoreAssumption::Included, OccupiedCoreAssumption::TimedOut] {
+		let validation_data: Option<PersistedValidationData> = runtime_call(
+			client,
+			"ParachainHost_persisted_validation_data",
+			(descriptor.para_id, assumption),
+			descriptor.relay_parent,
+		)
+		.await?;
+		if let Some(validation_data) =
+			validation_data.filter(|data| data.hash() == descriptor.persisted_validation_data_hash)
+		{
+			return Ok(validation_data)
+		}
+	}
+	Err("No persisted validation data of the relay parent matches the candidate".into())
+}
@@ -0,0 +1,124 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Benchmarks for the asset registry pallet.
+
+use super::*;
+use frame_benchmarking::v2::*;
+use frame_support::traits::EnsureOrigin;
+use xcm::v4::Junction::Parachain;
+
+fn location(index: u32) -> Location {
+	Location::new(1, [Parachain(index)])
+}
+
+fn register<T: Config>(asset_id: T::AssetId, location: Location) {
+	let info = AssetInfo {
+		location: location.clone(),
+		metadata: AssetMetadata {
+			name: Default::default(),
+			symbol: Default::default(),
+			decimals: 12,
+		},
+		is_fee_asset: true,
+	};
+	Assets::<T>::insert(&asset_id, info);
+	AssetIdByLocation::<T>::insert(&location, &asset_id);
+}
+
+/// Metadata string of the maximal length.
+fn max_string<T: Config>() -> Vec<u8> {
+	sp_std::vec![b'a'; T::StringLimit::get() as usize]
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn register_asset() -> Result<(), BenchmarkError> {
+		let origin =
+			T::RegistryOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let asset_id = T::BenchmarkHelper::asset_id(0);
+
+		#[extrinsic_call]
+		_(
+			origin as T::RuntimeOrigin,
+			asset_id.clone(),
+			Box::new(location(0).into()),
+			max_string::<T>(),
+			max_string::<T>(),
+			12,
+			true,
+		);
+
+		assert_eq!(AssetIdByLocation::<T>::get(location(0)), Some(asset_id));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn update_asset() -> Result<(), BenchmarkError> {
+		let origin =
+			T::RegistryOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let asset_id = T::BenchmarkHelper::asset_id(0);
+		register::<T>(asset_id.clone(), location(0));
+
+		#[extrinsic_call]
+		_(
+			origin as T::RuntimeOrigin,
+			asset_id.clone(),
+			max_string::<T>(),
+			max_string::<T>(),
+			10,
+			false,
+		);
+
+		assert!(!Assets::<T>::get(asset_id).expect("registered above; qed").is_fee_asset);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn update_location() -> Result<(), BenchmarkError> {
+		let origin =
+			T::RegistryOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let asset_id = T::BenchmarkHelper::asset_id(0);
+		register::<T>(asset_id.clone(), location(0));
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, asset_id.clone(), Box::new(location(1).into()));
+
+		assert_eq!(AssetIdByLocation::<T>::get(location(1)), Some(asset_id));
+		assert!(AssetIdByLocation::<T>::get(location(0)).is_none());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn deregister_asset() -> Result<(), BenchmarkError> {
+		let origin =
+			T::RegistryOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let asset_id = T::BenchmarkHelper::asset_id(0);
+		register::<T>(asset_id.clone(), location(0));
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, asset_id.clone());
+
+		assert!(Assets::<T>::get(asset_id).is_none());
+		assert!(AssetIdByLocation::<T>::get(location(0)).is_none());
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
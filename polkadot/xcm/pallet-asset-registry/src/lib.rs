@@ -0,0 +1,392 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! # Asset Registry Pallet
+//!
+//! Registry mapping XCM [`Location`]s to local asset ids, along with the display metadata of the
+//! assets and whether they are accepted for fee payment.
+//!
+//! ## Overview
+//!
+//! Runtimes usually hand-roll the conversion between the locations of the assets and their local
+//! ids, and list the assets accepted for fee payment in their `XcmPaymentApi` implementation. This
+//! pallet keeps both in storage, managed by the [`Config::RegistryOrigin`], such that:
+//!
+//! - The pallet is a [`MaybeEquivalence`] between locations and asset ids, which can be used as the
+//!   location converter of the asset transactors.
+//! - [`Pallet::query_acceptable_payment_assets`] and [`Pallet::fee_asset_id`] implement the
+//!   asset-related parts of the `XcmPaymentApi`.
+//! - The [`AssetRegistryApi`] runtime API exposes the registered assets and their metadata to UIs.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+use codec::{Codec, Decode, Encode, MaxEncodedLen};
+use frame_support::{pallet_prelude::*, BoundedVec};
+use scale_info::TypeInfo;
+use sp_runtime::{traits::MaybeEquivalence, RuntimeDebug};
+use sp_std::{boxed::Box, vec::Vec};
+use xcm::{
+	v4::{AssetId, Location},
+	VersionedAssetId, VersionedLocation,
+};
+use xcm_fee_payment_runtime_api::Error as XcmPaymentApiError;
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+/// Display metadata of an asset.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct AssetMetadata<BoundedString> {
+	/// The user friendly name of the asset.
+	pub name: BoundedString,
+	/// The ticker symbol of the asset.
+	pub symbol: BoundedString,
+	/// The number of decimals of the asset.
+	pub decimals: u8,
+}
+
+/// A registered asset.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct AssetInfo<BoundedString> {
+	/// The location of the asset.
+	pub location: Location,
+	/// The display metadata of the asset.
+	pub metadata: AssetMetadata<BoundedString>,
+	/// Whether the asset is accepted for fee payment.
+	pub is_fee_asset: bool,
+}
+
+/// A registered asset as returned by the [`AssetRegistryApi`].
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct RegisteredAsset<AssetIdOf> {
+	/// The local id of the asset.
+	pub asset_id: AssetIdOf,
+	/// The location of the asset.
+	pub location: VersionedLocation,
+	/// The display metadata of the asset.
+	pub metadata: AssetMetadata<Vec<u8>>,
+	/// Whether the asset is accepted for fee payment.
+	pub is_fee_asset: bool,
+}
+
+/// Helper to create the asset ids used by the benchmarks.
+#[cfg(feature = "runtime-benchmarks")]
+pub trait BenchmarkHelper<AssetIdOf> {
+	/// Create the asset id with the given index.
+	fn asset_id(index: u32) -> AssetIdOf;
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+impl<AssetIdOf: From<u32>> BenchmarkHelper<AssetIdOf> for () {
+	fn asset_id(index: u32) -> AssetIdOf {
+		index.into()
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The local identifier of the assets.
+		type AssetId: Member + Parameter + MaxEncodedLen;
+
+		/// The origin allowed to manage the registry.
+		type RegistryOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The maximum length of the name and symbol of the assets.
+		#[pallet::constant]
+		type StringLimit: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+
+		/// Helper to create the asset ids used by the benchmarks.
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: BenchmarkHelper<Self::AssetId>;
+	}
+
+	/// The registered assets by their local id.
+	#[pallet::storage]
+	pub type Assets<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AssetId,
+		AssetInfo<BoundedVec<u8, T::StringLimit>>,
+		OptionQuery,
+	>;
+
+	/// The local ids of the registered assets by their location.
+	#[pallet::storage]
+	pub type AssetIdByLocation<T: Config> =
+		StorageMap<_, Blake2_128Concat, Location, T::AssetId, OptionQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// An asset was registered.
+		AssetRegistered { asset_id: T::AssetId, location: Location },
+		/// The metadata of an asset was updated.
+		AssetUpdated { asset_id: T::AssetId },
+		/// The location of an asset was changed.
+		LocationUpdated { asset_id: T::AssetId, old_location: Location, new_location: Location },
+		/// An asset was removed from the registry.
+		AssetDeregistered { asset_id: T::AssetId },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// An asset with the given id is already registered.
+		AssetAlreadyRegistered,
+		/// An asset with the given location is already registered.
+		LocationAlreadyRegistered,
+		/// No asset with the given id is registered.
+		AssetNotRegistered,
+		/// The location can't be converted to the latest XCM version.
+		BadVersion,
+		/// The name or symbol is longer than [`Config::StringLimit`].
+		MetadataTooLong,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Register an asset at the given location.
+		///
+		/// Must be called by the [`Config::RegistryOrigin`].
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::register_asset())]
+		pub fn register_asset(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			location: Box<VersionedLocation>,
+			name: Vec<u8>,
+			symbol: Vec<u8>,
+			decimals: u8,
+			is_fee_asset: bool,
+		) -> DispatchResult {
+			T::RegistryOrigin::ensure_origin(origin)?;
+
+			let location: Location = (*location).try_into().map_err(|()| Error::<T>::BadVersion)?;
+			ensure!(!Assets::<T>::contains_key(&asset_id), Error::<T>::AssetAlreadyRegistered);
+			ensure!(
+				!AssetIdByLocation::<T>::contains_key(&location),
+				Error::<T>::LocationAlreadyRegistered
+			);
+
+			let info = AssetInfo {
+				location: location.clone(),
+				metadata: Self::bounded_metadata(name, symbol, decimals)?,
+				is_fee_asset,
+			};
+			Assets::<T>::insert(&asset_id, info);
+			AssetIdByLocation::<T>::insert(&location, &asset_id);
+
+			Self::deposit_event(Event::AssetRegistered { asset_id, location });
+			Ok(())
+		}
+
+		/// Update the metadata of a registered asset and whether it's accepted for fee payment.
+		///
+		/// Must be called by the [`Config::RegistryOrigin`].
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::update_asset())]
+		pub fn update_asset(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			name: Vec<u8>,
+			symbol: Vec<u8>,
+			decimals: u8,
+			is_fee_asset: bool,
+		) -> DispatchResult {
+			T::RegistryOrigin::ensure_origin(origin)?;
+
+			let metadata = Self::bounded_metadata(name, symbol, decimals)?;
+			Assets::<T>::try_mutate(&asset_id, |info| {
+				let info = info.as_mut().ok_or(Error::<T>::AssetNotRegistered)?;
+				info.metadata = metadata;
+				info.is_fee_asset = is_fee_asset;
+				Ok::<_, DispatchError>(())
+			})?;
+
+			Self::deposit_event(Event::AssetUpdated { asset_id });
+			Ok(())
+		}
+
+		/// Change the location of a registered asset, e.g. when the asset was reanchored.
+		///
+		/// Must be called by the [`Config::RegistryOrigin`].
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::update_location())]
+		pub fn update_location(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			location: Box<VersionedLocation>,
+		) -> DispatchResult {
+			T::RegistryOrigin::ensure_origin(origin)?;
+
+			let new_location: Location =
+				(*location).try_into().map_err(|()| Error::<T>::BadVersion)?;
+			ensure!(
+				!AssetIdByLocation::<T>::contains_key(&new_location),
+				Error::<T>::LocationAlreadyRegistered
+			);
+
+			let old_location = Assets::<T>::try_mutate(&asset_id, |info| {
+				let info = info.as_mut().ok_or(Error::<T>::AssetNotRegistered)?;
+				Ok::<_, DispatchError>(sp_std::mem::replace(
+					&mut info.location,
+					new_location.clone(),
+				))
+			})?;
+			AssetIdByLocation::<T>::remove(&old_location);
+			AssetIdByLocation::<T>::insert(&new_location, &asset_id);
+
+			Self::deposit_event(Event::LocationUpdated { asset_id, old_location, new_location });
+			Ok(())
+		}
+
+		/// Remove an asset from the registry.
+		///
+		/// Must be called by the [`Config::RegistryOrigin`].
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::deregister_asset())]
+		pub fn deregister_asset(origin: OriginFor<T>, asset_id: T::AssetId) -> DispatchResult {
+			T::RegistryOrigin::ensure_origin(origin)?;
+
+			let info = Assets::<T>::take(&asset_id).ok_or(Error::<T>::AssetNotRegistered)?;
+			AssetIdByLocation::<T>::remove(&info.location);
+
+			Self::deposit_event(Event::AssetDeregistered { asset_id });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The registered asset with the given id.
+	pub fn registered_asset(asset_id: T::AssetId) -> Option<RegisteredAsset<T::AssetId>> {
+		Assets::<T>::get(&asset_id).map(|info| Self::to_registered_asset(asset_id, info))
+	}
+
+	/// All registered assets.
+	pub fn registered_assets() -> Vec<RegisteredAsset<T::AssetId>> {
+		Assets::<T>::iter()
+			.map(|(asset_id, info)| Self::to_registered_asset(asset_id, info))
+			.collect()
+	}
+
+	/// The assets accepted for fee payment, for `XcmPaymentApi::query_acceptable_payment_assets`.
+	pub fn query_acceptable_payment_assets(
+		xcm_version: xcm::Version,
+	) -> Result<Vec<VersionedAssetId>, XcmPaymentApiError> {
+		if !matches!(xcm_version, 3 | 4) {
+			return Err(XcmPaymentApiError::UnhandledXcmVersion)
+		}
+
+		Ok(Assets::<T>::iter_values()
+			.filter(|info| info.is_fee_asset)
+			.filter_map(|info| {
+				VersionedAssetId::V4(AssetId(info.location)).into_version(xcm_version).ok()
+			})
+			.collect())
+	}
+
+	/// The local id of an asset accepted for fee payment, for
+	/// `XcmPaymentApi::query_weight_to_asset_fee`.
+	pub fn fee_asset_id(asset: VersionedAssetId) -> Result<T::AssetId, XcmPaymentApiError> {
+		let VersionedAssetId::V4(AssetId(location)) = asset
+			.into_version(4)
+			.map_err(|_| XcmPaymentApiError::VersionedConversionFailed)?
+		else {
+			return Err(XcmPaymentApiError::VersionedConversionFailed)
+		};
+
+		AssetIdByLocation::<T>::get(&location)
+			.filter(|asset_id| Assets::<T>::get(asset_id).map_or(false, |info| info.is_fee_asset))
+			.ok_or(XcmPaymentApiError::AssetNotFound)
+	}
+
+	fn bounded_metadata(
+		name: Vec<u8>,
+		symbol: Vec<u8>,
+		decimals: u8,
+	) -> Result<AssetMetadata<BoundedVec<u8, T::StringLimit>>, Error<T>> {
+		Ok(AssetMetadata {
+			name: name.try_into().map_err(|_| Error::<T>::MetadataTooLong)?,
+			symbol: symbol.try_into().map_err(|_| Error::<T>::MetadataTooLong)?,
+			decimals,
+		})
+	}
+
+	fn to_registered_asset(
+		asset_id: T::AssetId,
+		info: AssetInfo<BoundedVec<u8, T::StringLimit>>,
+	) -> RegisteredAsset<T::AssetId> {
+		RegisteredAsset {
+			asset_id,
+			location: info.location.into(),
+			metadata: AssetMetadata {
+				name: info.metadata.name.into_inner(),
+				symbol: info.metadata.symbol.into_inner(),
+				decimals: info.metadata.decimals,
+			},
+			is_fee_asset: info.is_fee_asset,
+		}
+	}
+}
+
+impl<T: Config> MaybeEquivalence<Location, T::AssetId> for Pallet<T> {
+	fn convert(location: &Location) -> Option<T::AssetId> {
+		AssetIdByLocation::<T>::get(location)
+	}
+
+	fn convert_back(asset_id: &T::AssetId) -> Option<Location> {
+		Assets::<T>::get(asset_id).map(|info| info.location)
+	}
+}
+
+sp_api::decl_runtime_apis! {
+	/// API to query the assets of the registry.
+	pub trait AssetRegistryApi<AssetIdOf>
+	where
+		AssetIdOf: Codec,
+	{
+		/// The local id of the asset at the given location.
+		fn asset_id(location: VersionedLocation) -> Option<AssetIdOf>;
+
+		/// The registered asset with the given id.
+		fn asset(asset_id: AssetIdOf) -> Option<RegisteredAsset<AssetIdOf>>;
+
+		/// All registered assets.
+		fn assets() -> Vec<RegisteredAsset<AssetIdOf>>;
+	}
+}
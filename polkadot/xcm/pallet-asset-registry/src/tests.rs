@@ -0,0 +1,243 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Tests for the asset registry pallet.
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{new_test_ext, AssetRegistry, RuntimeEvent, RuntimeOrigin, System, Test};
+use sp_runtime::DispatchError;
+use xcm::v4::Junction::{GeneralIndex, PalletInstance, Parachain};
+
+const ASSET_ID: u32 = 42;
+
+fn usdt() -> Location {
+	Location::new(1, [Parachain(1000), PalletInstance(50), GeneralIndex(1984)])
+}
+
+fn register(asset_id: u32, location: Location, is_fee_asset: bool) {
+	assert_ok!(AssetRegistry::register_asset(
+		RuntimeOrigin::root(),
+		asset_id,
+		Box::new(location.into()),
+		b"Tether USD".to_vec(),
+		b"USDT".to_vec(),
+		6,
+		is_fee_asset,
+	));
+}
+
+#[test]
+fn register_asset_works() {
+	new_test_ext().execute_with(|| {
+		register(ASSET_ID, usdt(), true);
+
+		assert_eq!(AssetIdByLocation::<Test>::get(usdt()), Some(ASSET_ID));
+		let asset = AssetRegistry::registered_asset(ASSET_ID).unwrap();
+		assert_eq!(asset.location, VersionedLocation::from(usdt()));
+		assert_eq!(asset.metadata.symbol, b"USDT".to_vec());
+		assert_eq!(asset.metadata.decimals, 6);
+		System::assert_last_event(RuntimeEvent::AssetRegistry(Event::AssetRegistered {
+			asset_id: ASSET_ID,
+			location: usdt(),
+		}));
+	});
+}
+
+#[test]
+fn register_asset_requires_registry_origin() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			AssetRegistry::register_asset(
+				RuntimeOrigin::signed(1),
+				ASSET_ID,
+				Box::new(usdt().into()),
+				Vec::new(),
+				Vec::new(),
+				6,
+				true,
+			),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn register_asset_rejects_duplicates() {
+	new_test_ext().execute_with(|| {
+		register(ASSET_ID, usdt(), true);
+
+		assert_noop!(
+			AssetRegistry::register_asset(
+				RuntimeOrigin::root(),
+				ASSET_ID,
+				Box::new(Location::parent().into()),
+				Vec::new(),
+				Vec::new(),
+				10,
+				true,
+			),
+			Error::<Test>::AssetAlreadyRegistered
+		);
+		assert_noop!(
+			AssetRegistry::register_asset(
+				RuntimeOrigin::root(),
+				ASSET_ID + 1,
+				Box::new(usdt().into()),
+				Vec::new(),
+				Vec::new(),
+				6,
+				true,
+			),
+			Error::<Test>::LocationAlreadyRegistered
+		);
+	});
+}
+
+#[test]
+fn register_asset_rejects_long_metadata() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			AssetRegistry::register_asset(
+				RuntimeOrigin::root(),
+				ASSET_ID,
+				Box::new(usdt().into()),
+				b"A name longer than the limit".to_vec(),
+				b"USDT".to_vec(),
+				6,
+				true,
+			),
+			Error::<Test>::MetadataTooLong
+		);
+	});
+}
+
+#[test]
+fn update_asset_works() {
+	new_test_ext().execute_with(|| {
+		register(ASSET_ID, usdt(), true);
+
+		assert_ok!(AssetRegistry::update_asset(
+			RuntimeOrigin::root(),
+			ASSET_ID,
+			b"Tether".to_vec(),
+			b"USDt".to_vec(),
+			6,
+			false,
+		));
+
+		let asset = AssetRegistry::registered_asset(ASSET_ID).unwrap();
+		assert_eq!(asset.metadata.name, b"Tether".to_vec());
+		assert!(!asset.is_fee_asset);
+		assert_noop!(
+			AssetRegistry::update_asset(
+				RuntimeOrigin::root(),
+				ASSET_ID + 1,
+				Vec::new(),
+				Vec::new(),
+				6,
+				false,
+			),
+			Error::<Test>::AssetNotRegistered
+		);
+	});
+}
+
+#[test]
+fn update_location_works() {
+	new_test_ext().execute_with(|| {
+		register(ASSET_ID, usdt(), true);
+		register(ASSET_ID + 1, Location::parent(), true);
+		let new_location = Location::new(2, [Parachain(1000)]);
+
+		assert_noop!(
+			AssetRegistry::update_location(
+				RuntimeOrigin::root(),
+				ASSET_ID,
+				Box::new(Location::parent().into()),
+			),
+			Error::<Test>::LocationAlreadyRegistered
+		);
+		assert_ok!(AssetRegistry::update_location(
+			RuntimeOrigin::root(),
+			ASSET_ID,
+			Box::new(new_location.clone().into()),
+		));
+
+		assert!(AssetIdByLocation::<Test>::get(usdt()).is_none());
+		assert_eq!(AssetIdByLocation::<Test>::get(&new_location), Some(ASSET_ID));
+		System::assert_last_event(RuntimeEvent::AssetRegistry(Event::LocationUpdated {
+			asset_id: ASSET_ID,
+			old_location: usdt(),
+			new_location,
+		}));
+	});
+}
+
+#[test]
+fn deregister_asset_works() {
+	new_test_ext().execute_with(|| {
+		register(ASSET_ID, usdt(), true);
+
+		assert_ok!(AssetRegistry::deregister_asset(RuntimeOrigin::root(), ASSET_ID));
+
+		assert!(AssetRegistry::registered_asset(ASSET_ID).is_none());
+		assert!(AssetIdByLocation::<Test>::get(usdt()).is_none());
+		assert_noop!(
+			AssetRegistry::deregister_asset(RuntimeOrigin::root(), ASSET_ID),
+			Error::<Test>::AssetNotRegistered
+		);
+	});
+}
+
+#[test]
+fn converts_between_locations_and_asset_ids() {
+	new_test_ext().execute_with(|| {
+		register(ASSET_ID, usdt(), true);
+
+		assert_eq!(<AssetRegistry as MaybeEquivalence<_, _>>::convert(&usdt()), Some(ASSET_ID));
+		assert_eq!(
+			<AssetRegistry as MaybeEquivalence<_, _>>::convert_back(&ASSET_ID),
+			Some(usdt())
+		);
+		assert_eq!(<AssetRegistry as MaybeEquivalence<_, u32>>::convert(&Location::parent()), None);
+	});
+}
+
+#[test]
+fn payment_assets_are_the_fee_assets() {
+	new_test_ext().execute_with(|| {
+		register(ASSET_ID, usdt(), true);
+		register(ASSET_ID + 1, Location::parent(), false);
+
+		assert_eq!(
+			AssetRegistry::query_acceptable_payment_assets(4),
+			Ok(vec![VersionedAssetId::V4(AssetId(usdt()))])
+		);
+		assert_eq!(
+			AssetRegistry::query_acceptable_payment_assets(2),
+			Err(XcmPaymentApiError::UnhandledXcmVersion)
+		);
+		assert_eq!(
+			AssetRegistry::fee_asset_id(VersionedAssetId::V4(AssetId(usdt()))),
+			Ok(ASSET_ID)
+		);
+		assert_eq!(
+			AssetRegistry::fee_asset_id(VersionedAssetId::V4(AssetId(Location::parent()))),
+			Err(XcmPaymentApiError::AssetNotFound)
+		);
+	});
+}
@@ -0,0 +1,87 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Benchmarks for the XCM fee rebate pallet.
+
+use super::*;
+use frame_benchmarking::v2::*;
+use frame_support::traits::EnsureOrigin;
+use xcm::v4::Junction::Parachain;
+
+fn location(index: u32) -> Location {
+	Location::new(1, [Parachain(index)])
+}
+
+fn schedule<T: Config>(index: u32) -> RebateSchedule<T::AccountId, BalanceOf<T>> {
+	RebateSchedule {
+		beneficiary: account("beneficiary", index, 0),
+		rate: Perbill::one(),
+		max_per_period: Some(T::Currency::minimum_balance() * 100u32.into()),
+	}
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn set_schedule() -> Result<(), BenchmarkError> {
+		let origin =
+			T::RebateOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, Box::new(location(0).into()), schedule::<T>(0));
+
+		assert!(Schedules::<T>::contains_key(location(0)));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn remove_schedule() -> Result<(), BenchmarkError> {
+		let origin =
+			T::RebateOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		Schedules::<T>::insert(location(0), schedule::<T>(0));
+		FeesPaid::<T>::insert(location(0), T::Currency::minimum_balance());
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, Box::new(location(0).into()));
+
+		assert!(!Schedules::<T>::contains_key(location(0)));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn pay_rebates(s: Linear<0, { T::MaxSchedules::get() }>) {
+		let fees = T::Currency::minimum_balance() * 10u32.into();
+		for index in 0..s {
+			Schedules::<T>::insert(location(index), schedule::<T>(index));
+			FeesPaid::<T>::insert(location(index), fees);
+		}
+		let funds = fees * (s + 1).into() + T::Currency::minimum_balance();
+		T::Currency::set_balance(&Pallet::<T>::account_id(), funds);
+
+		#[block]
+		{
+			Pallet::<T>::pay_rebates();
+		}
+
+		if s > 0 {
+			assert_eq!(T::Currency::balance(&schedule::<T>(0).beneficiary), fees);
+		}
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,342 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! # XCM Fee Rebate Pallet
+//!
+//! Rebates of part of the XCM execution fees paid by configured origins, e.g. the partner
+//! parachains of a fee sharing agreement.
+//!
+//! ## Overview
+//!
+//! The [`Config::RebateOrigin`] sets a [`RebateSchedule`] for an origin location: the account
+//! the rebates are paid to, the share of the fees rebated and an optional cap per period. The
+//! schedule of a location applies to the messages of the locations within it as well, e.g. the
+//! schedule of a parachain applies to the messages of its accounts.
+//!
+//! The fees are tracked by wrapping the `Trader` of the XCM executor in [`TrackRebatableFees`],
+//! which records the [`Config::FeeAsset`] paid for execution, net of the refunds, by the origins
+//! with a schedule. Every [`Config::PayoutPeriod`] blocks the rebates of the fees recorded over
+//! the period are paid from the account of the [`Config::PalletId`], which must be funded by
+//! governance.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	pallet_prelude::*,
+	traits::{
+		fungible::{Inspect, Mutate},
+		tokens::Preservation,
+	},
+	PalletId,
+};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{AccountIdConversion, SaturatedConversion, Saturating, Zero},
+	Perbill, RuntimeDebug,
+};
+use sp_std::{boxed::Box, marker::PhantomData};
+use xcm::{
+	v4::{
+		Asset, AssetId, Error as XcmError, Fungibility, Location, Weight as XcmWeight, XcmContext,
+	},
+	VersionedLocation,
+};
+use xcm_executor::{traits::WeightTrader, AssetsInHolding};
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+/// The balance of the [`Config::Currency`].
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Inspect<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// Rebates of the fees paid by an origin.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct RebateSchedule<AccountId, Balance> {
+	/// The account the rebates are paid to.
+	pub beneficiary: AccountId,
+	/// The share of the fees paid over a period which is rebated.
+	pub rate: Perbill,
+	/// The maximum rebate of a period, `None` if unlimited.
+	pub max_per_period: Option<Balance>,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The currency the rebates are paid in.
+		type Currency: Mutate<Self::AccountId>;
+
+		/// The asset of the [`Config::Currency`] as seen by the XCM executor, the fees paid in
+		/// other assets are not rebated.
+		#[pallet::constant]
+		type FeeAsset: Get<AssetId>;
+
+		/// The origin allowed to manage the rebate schedules.
+		type RebateOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The id of the pallet, whose account pays the rebates.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// The number of blocks between two payouts of the rebates.
+		#[pallet::constant]
+		type PayoutPeriod: Get<BlockNumberFor<Self>>;
+
+		/// The maximum number of rebate schedules, bounding the weight of the payouts.
+		#[pallet::constant]
+		type MaxSchedules: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// The rebate schedules by origin location.
+	#[pallet::storage]
+	pub type Schedules<T: Config> = CountedStorageMap<
+		_,
+		Blake2_128Concat,
+		Location,
+		RebateSchedule<T::AccountId, BalanceOf<T>>,
+		OptionQuery,
+	>;
+
+	/// The fees paid by the origins with a schedule since the last payout.
+	#[pallet::storage]
+	pub type FeesPaid<T: Config> =
+		StorageMap<_, Blake2_128Concat, Location, BalanceOf<T>, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The rebate schedule of an origin was set.
+		ScheduleSet { origin: Location, schedule: RebateSchedule<T::AccountId, BalanceOf<T>> },
+		/// The rebate schedule of an origin was removed.
+		ScheduleRemoved { origin: Location },
+		/// The rebate of the fees paid by an origin over a period was paid.
+		RebatePaid {
+			origin: Location,
+			beneficiary: T::AccountId,
+			fees: BalanceOf<T>,
+			rebate: BalanceOf<T>,
+		},
+		/// The rebate of the fees paid by an origin over a period couldn't be paid, e.g. because
+		/// the account of the pallet lacks funds. It is not paid later.
+		RebateFailed { origin: Location, beneficiary: T::AccountId, rebate: BalanceOf<T> },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The location can't be converted to the latest XCM version.
+		BadVersion,
+		/// There are already [`Config::MaxSchedules`] schedules.
+		TooManySchedules,
+		/// The origin has no rebate schedule.
+		NoSchedule,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+			let period = T::PayoutPeriod::get();
+			if period.is_zero() || !(n % period).is_zero() {
+				return Weight::zero()
+			}
+
+			let schedules = Schedules::<T>::count();
+			Self::pay_rebates();
+			T::WeightInfo::pay_rebates(schedules)
+		}
+
+		fn integrity_test() {
+			assert!(!T::PayoutPeriod::get().is_zero(), "The payout period must not be zero");
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set the rebate schedule of the given origin location, replacing its current one.
+		///
+		/// The fees already paid by the origin over the current period are rebated according to
+		/// the new schedule. Must be called by the [`Config::RebateOrigin`].
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::set_schedule())]
+		pub fn set_schedule(
+			origin: OriginFor<T>,
+			location: Box<VersionedLocation>,
+			schedule: RebateSchedule<T::AccountId, BalanceOf<T>>,
+		) -> DispatchResult {
+			T::RebateOrigin::ensure_origin(origin)?;
+
+			let location: Location = (*location).try_into().map_err(|()| Error::<T>::BadVersion)?;
+			ensure!(
+				Schedules::<T>::contains_key(&location) ||
+					Schedules::<T>::count() < T::MaxSchedules::get(),
+				Error::<T>::TooManySchedules
+			);
+			Schedules::<T>::insert(&location, &schedule);
+
+			Self::deposit_event(Event::ScheduleSet { origin: location, schedule });
+			Ok(())
+		}
+
+		/// Remove the rebate schedule of the given origin location.
+		///
+		/// The fees paid by the origin over the current period are not rebated. Must be called by
+		/// the [`Config::RebateOrigin`].
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::remove_schedule())]
+		pub fn remove_schedule(
+			origin: OriginFor<T>,
+			location: Box<VersionedLocation>,
+		) -> DispatchResult {
+			T::RebateOrigin::ensure_origin(origin)?;
+
+			let location: Location = (*location).try_into().map_err(|()| Error::<T>::BadVersion)?;
+			Schedules::<T>::take(&location).ok_or(Error::<T>::NoSchedule)?;
+			FeesPaid::<T>::remove(&location);
+
+			Self::deposit_event(Event::ScheduleRemoved { origin: location });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The account paying the rebates.
+	pub fn account_id() -> T::AccountId {
+		T::PalletId::get().into_account_truncating()
+	}
+
+	/// The location with a schedule the fees paid by `origin` are accounted to, if any.
+	///
+	/// This is the origin itself or the closest location containing it.
+	pub fn rebated_location(origin: &Location) -> Option<Location> {
+		let mut location = origin.clone();
+		loop {
+			if Schedules::<T>::contains_key(&location) {
+				return Some(location)
+			}
+			match location.split_last_interior() {
+				(parent, Some(_)) => location = parent,
+				(_, None) => return None,
+			}
+		}
+	}
+
+	/// Record that `origin` paid `amount` of fees, or was refunded it if `refund` is set.
+	fn note_fees(origin: &Location, amount: u128, refund: bool) {
+		if amount == 0 {
+			return
+		}
+		let Some(location) = Self::rebated_location(origin) else { return };
+
+		let amount: BalanceOf<T> = amount.saturated_into();
+		FeesPaid::<T>::mutate(location, |fees| match refund {
+			true => *fees = fees.saturating_sub(amount),
+			false => *fees = fees.saturating_add(amount),
+		});
+	}
+
+	/// Pay the rebates of the fees paid since the last payout.
+	fn pay_rebates() {
+		let payer = Self::account_id();
+
+		for (origin, schedule) in Schedules::<T>::iter() {
+			let fees = FeesPaid::<T>::take(&origin);
+			let mut rebate = schedule.rate.mul_floor(fees);
+			if let Some(max) = schedule.max_per_period {
+				rebate = rebate.min(max);
+			}
+			if rebate.is_zero() {
+				continue
+			}
+
+			let beneficiary = schedule.beneficiary;
+			match T::Currency::transfer(&payer, &beneficiary, rebate, Preservation::Expendable) {
+				Ok(_) =>
+					Self::deposit_event(Event::RebatePaid { origin, beneficiary, fees, rebate }),
+				Err(e) => {
+					log::warn!(
+						target: "runtime::xcm-fee-rebate",
+						"Failed to pay the rebate of {rebate:?} for {origin:?}: {e:?}",
+					);
+					Self::deposit_event(Event::RebateFailed { origin, beneficiary, rebate })
+				},
+			}
+		}
+	}
+}
+
+/// [`WeightTrader`] recording the fees paid through `Trader` by the origins with a rebate
+/// schedule.
+///
+/// Only the fees paid in the [`Config::FeeAsset`] are recorded.
+pub struct TrackRebatableFees<T, Trader>(Trader, PhantomData<T>);
+
+impl<T: Config, Trader: WeightTrader> WeightTrader for TrackRebatableFees<T, Trader> {
+	fn new() -> Self {
+		Self(Trader::new(), PhantomData)
+	}
+
+	fn buy_weight(
+		&mut self,
+		weight: XcmWeight,
+		payment: AssetsInHolding,
+		context: &XcmContext,
+	) -> Result<AssetsInHolding, XcmError> {
+		let fee_asset = T::FeeAsset::get();
+		let offered = payment.fungible.get(&fee_asset).copied().unwrap_or_default();
+
+		let unused = self.0.buy_weight(weight, payment, context)?;
+
+		if let Some(origin) = &context.origin {
+			let left = unused.fungible.get(&fee_asset).copied().unwrap_or_default();
+			Pallet::<T>::note_fees(origin, offered.saturating_sub(left), false);
+		}
+		Ok(unused)
+	}
+
+	fn refund_weight(&mut self, weight: XcmWeight, context: &XcmContext) -> Option<Asset> {
+		let refund = self.0.refund_weight(weight, context)?;
+
+		if let (Some(origin), Fungibility::Fungible(amount)) = (&context.origin, &refund.fun) {
+			if refund.id == T::FeeAsset::get() {
+				Pallet::<T>::note_fees(origin, *amount, true);
+			}
+		}
+		Some(refund)
+	}
+}
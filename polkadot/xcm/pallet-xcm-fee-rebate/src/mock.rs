@@ -0,0 +1,103 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Test environment for the XCM fee rebate pallet.
+
+use crate as pallet_xcm_fee_rebate;
+use frame_support::{
+	derive_impl, parameter_types,
+	traits::{ConstU32, ConstU64},
+	PalletId,
+};
+use frame_system::EnsureRoot;
+use sp_runtime::BuildStorage;
+use xcm::v4::{Asset, AssetId, Error as XcmError, Location, Weight as XcmWeight, XcmContext};
+use xcm_executor::{traits::WeightTrader, AssetsInHolding};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test
+	{
+		System: frame_system,
+		Balances: pallet_balances,
+		XcmFeeRebate: pallet_xcm_fee_rebate,
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+	type Block = Block;
+	type AccountData = pallet_balances::AccountData<u64>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+	type AccountStore = System;
+}
+
+parameter_types! {
+	pub NativeAsset: AssetId = AssetId(Location::here());
+	pub const RebatePalletId: PalletId = PalletId(*b"py/xcmrb");
+}
+
+/// Number of blocks between two payouts.
+pub const PERIOD: u64 = 10;
+
+impl pallet_xcm_fee_rebate::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type FeeAsset = NativeAsset;
+	type RebateOrigin = EnsureRoot<u64>;
+	type PalletId = RebatePalletId;
+	type PayoutPeriod = ConstU64<PERIOD>;
+	type MaxSchedules = ConstU32<2>;
+	type WeightInfo = ();
+}
+
+/// Trader charging one unit of the native asset per unit of `ref_time`.
+pub struct UnitTrader;
+
+impl WeightTrader for UnitTrader {
+	fn new() -> Self {
+		Self
+	}
+
+	fn buy_weight(
+		&mut self,
+		weight: XcmWeight,
+		payment: AssetsInHolding,
+		_context: &XcmContext,
+	) -> Result<AssetsInHolding, XcmError> {
+		let fee: Asset = (NativeAsset::get(), weight.ref_time() as u128).into();
+		payment.checked_sub(fee).map_err(|_| XcmError::TooExpensive)
+	}
+
+	fn refund_weight(&mut self, weight: XcmWeight, _context: &XcmContext) -> Option<Asset> {
+		Some((NativeAsset::get(), weight.ref_time() as u128).into())
+	}
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	pallet_balances::GenesisConfig::<Test> { balances: vec![(XcmFeeRebate::account_id(), 1_000)] }
+		.assimilate_storage(&mut storage)
+		.unwrap();
+
+	let mut ext: sp_io::TestExternalities = storage.into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,230 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Tests for the XCM fee rebate pallet.
+
+use super::*;
+use frame_support::{assert_noop, assert_ok, traits::OnInitialize};
+use mock::{
+	new_test_ext, Balances, NativeAsset, RuntimeEvent, RuntimeOrigin, System, Test, UnitTrader,
+	XcmFeeRebate, PERIOD,
+};
+use sp_runtime::DispatchError;
+use xcm::v4::Junction::{AccountId32, GeneralIndex, Parachain};
+
+const BENEFICIARY: u64 = 7;
+
+fn partner() -> Location {
+	Location::new(1, [Parachain(2000)])
+}
+
+fn set_schedule(location: Location, rate: Perbill, max_per_period: Option<u64>) {
+	assert_ok!(XcmFeeRebate::set_schedule(
+		RuntimeOrigin::root(),
+		Box::new(location.into()),
+		RebateSchedule { beneficiary: BENEFICIARY, rate, max_per_period },
+	));
+}
+
+fn context(origin: Location) -> XcmContext {
+	XcmContext { origin: Some(origin), message_id: [0; 32], topic: None }
+}
+
+/// Buy `weight` units of execution for `origin`, refunding `refund` of them.
+fn pay_fees(origin: Location, weight: u64, refund: u64) {
+	let mut trader = TrackRebatableFees::<Test, UnitTrader>::new();
+	let payment: AssetsInHolding = Asset::from((NativeAsset::get(), 1_000u128)).into();
+	let context = context(origin);
+
+	let unused = trader.buy_weight(XcmWeight::from_parts(weight, 0), payment, &context).unwrap();
+	assert_eq!(unused.fungible.get(&NativeAsset::get()), Some(&(1_000 - weight as u128)));
+	if refund > 0 {
+		trader.refund_weight(XcmWeight::from_parts(refund, 0), &context).unwrap();
+	}
+}
+
+fn run_to_payout() {
+	let next = (System::block_number() / PERIOD + 1) * PERIOD;
+	System::set_block_number(next);
+	XcmFeeRebate::on_initialize(next);
+}
+
+#[test]
+fn set_schedule_works() {
+	new_test_ext().execute_with(|| {
+		set_schedule(partner(), Perbill::from_percent(50), None);
+
+		let schedule = RebateSchedule {
+			beneficiary: BENEFICIARY,
+			rate: Perbill::from_percent(50),
+			max_per_period: None,
+		};
+		assert_eq!(Schedules::<Test>::get(partner()), Some(schedule.clone()));
+		System::assert_last_event(RuntimeEvent::XcmFeeRebate(Event::ScheduleSet {
+			origin: partner(),
+			schedule,
+		}));
+
+		// Replacing a schedule doesn't count against the limit.
+		set_schedule(partner(), Perbill::from_percent(10), Some(5));
+		assert_eq!(Schedules::<Test>::count(), 1);
+	});
+}
+
+#[test]
+fn set_schedule_checks_origin_and_limit() {
+	new_test_ext().execute_with(|| {
+		let schedule = RebateSchedule {
+			beneficiary: BENEFICIARY,
+			rate: Perbill::from_percent(50),
+			max_per_period: None,
+		};
+		assert_noop!(
+			XcmFeeRebate::set_schedule(
+				RuntimeOrigin::signed(1),
+				Box::new(partner().into()),
+				schedule.clone(),
+			),
+			DispatchError::BadOrigin
+		);
+
+		set_schedule(partner(), Perbill::from_percent(50), None);
+		set_schedule(Location::new(1, [Parachain(2001)]), Perbill::from_percent(50), None);
+		assert_noop!(
+			XcmFeeRebate::set_schedule(
+				RuntimeOrigin::root(),
+				Box::new(Location::new(1, [Parachain(2002)]).into()),
+				schedule,
+			),
+			Error::<Test>::TooManySchedules
+		);
+	});
+}
+
+#[test]
+fn fees_are_tracked_net_of_refunds() {
+	new_test_ext().execute_with(|| {
+		set_schedule(partner(), Perbill::from_percent(50), None);
+
+		pay_fees(partner(), 100, 30);
+		assert_eq!(FeesPaid::<Test>::get(partner()), 70);
+
+		// The messages of the accounts of the partner are accounted to it.
+		let account = partner().pushed_with_interior(AccountId32 { network: None, id: [1; 32] });
+		pay_fees(account.unwrap(), 50, 0);
+		assert_eq!(FeesPaid::<Test>::get(partner()), 120);
+
+		// The fees of the origins without a schedule are not tracked.
+		pay_fees(Location::new(1, [Parachain(3000)]), 100, 0);
+		assert!(!FeesPaid::<Test>::contains_key(Location::new(1, [Parachain(3000)])));
+	});
+}
+
+#[test]
+fn fees_paid_in_other_assets_are_not_tracked() {
+	new_test_ext().execute_with(|| {
+		set_schedule(partner(), Perbill::from_percent(50), None);
+
+		let mut trader = TrackRebatableFees::<Test, UnitTrader>::new();
+		let other = AssetId(Location::new(0, [GeneralIndex(1)]));
+		let payment: AssetsInHolding = Asset::from((other, 1_000u128)).into();
+		assert!(trader
+			.buy_weight(XcmWeight::from_parts(100, 0), payment, &context(partner()))
+			.is_err());
+		assert_eq!(FeesPaid::<Test>::get(partner()), 0);
+	});
+}
+
+#[test]
+fn rebates_are_paid_every_period() {
+	new_test_ext().execute_with(|| {
+		set_schedule(partner(), Perbill::from_percent(50), None);
+		pay_fees(partner(), 101, 0);
+
+		// Nothing is paid before the end of the period.
+		XcmFeeRebate::on_initialize(PERIOD - 1);
+		assert_eq!(Balances::balance(&BENEFICIARY), 0);
+
+		run_to_payout();
+		assert_eq!(Balances::balance(&BENEFICIARY), 50);
+		assert_eq!(Balances::balance(&XcmFeeRebate::account_id()), 950);
+		assert_eq!(FeesPaid::<Test>::get(partner()), 0);
+		System::assert_last_event(RuntimeEvent::XcmFeeRebate(Event::RebatePaid {
+			origin: partner(),
+			beneficiary: BENEFICIARY,
+			fees: 101,
+			rebate: 50,
+		}));
+
+		// The fees of a period are only rebated once.
+		run_to_payout();
+		assert_eq!(Balances::balance(&BENEFICIARY), 50);
+	});
+}
+
+#[test]
+fn rebates_are_capped() {
+	new_test_ext().execute_with(|| {
+		set_schedule(partner(), Perbill::from_percent(50), Some(20));
+		pay_fees(partner(), 100, 0);
+
+		run_to_payout();
+		assert_eq!(Balances::balance(&BENEFICIARY), 20);
+	});
+}
+
+#[test]
+fn rebates_failing_are_reported() {
+	new_test_ext().execute_with(|| {
+		set_schedule(partner(), Perbill::one(), None);
+		for _ in 0..2 {
+			pay_fees(partner(), 600, 0);
+		}
+
+		run_to_payout();
+		assert_eq!(Balances::balance(&BENEFICIARY), 0);
+		System::assert_last_event(RuntimeEvent::XcmFeeRebate(Event::RebateFailed {
+			origin: partner(),
+			beneficiary: BENEFICIARY,
+			rebate: 1_200,
+		}));
+		assert_eq!(FeesPaid::<Test>::get(partner()), 0);
+	});
+}
+
+#[test]
+fn remove_schedule_works() {
+	new_test_ext().execute_with(|| {
+		set_schedule(partner(), Perbill::from_percent(50), None);
+		pay_fees(partner(), 100, 0);
+
+		assert_ok!(XcmFeeRebate::remove_schedule(
+			RuntimeOrigin::root(),
+			Box::new(partner().into())
+		));
+		assert!(!FeesPaid::<Test>::contains_key(partner()));
+		System::assert_last_event(RuntimeEvent::XcmFeeRebate(Event::ScheduleRemoved {
+			origin: partner(),
+		}));
+		assert_noop!(
+			XcmFeeRebate::remove_schedule(RuntimeOrigin::root(), Box::new(partner().into())),
+			Error::<Test>::NoSchedule
+		);
+
+		run_to_payout();
+		assert_eq!(Balances::balance(&BENEFICIARY), 0);
+	});
+}
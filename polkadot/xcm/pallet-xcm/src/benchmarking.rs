@@ -394,6 +394,18 @@ benchmarks! {
 		let versioned_assets = VersionedAssets::V4(asset.into());
 	}: _<RuntimeOrigin<T>>(claim_origin.into(), Box::new(versioned_assets), Box::new(VersionedLocation::V4(claim_location)))
 
+	force_query_expiry {}: _(RawOrigin::Root, Some(10u32.into()))
+
+	cancel_query {
+		// The owner of the query, rather than the admin, checked against the query.
+		let owner_origin = RawOrigin::Signed(whitelisted_caller());
+		let owner = T::ExecuteXcmOrigin::try_origin(owner_origin.clone().into()).map_err(|_| BenchmarkError::Override(BenchmarkResult::from_weight(Weight::MAX)))?;
+		let query_id = crate::Pallet::<T>::new_query_for(owner, Parent, 1u32.into(), Here);
+	}: _<RuntimeOrigin<T>>(owner_origin.into(), query_id)
+	verify {
+		assert!(!crate::Queries::<T>::contains_key(query_id));
+	}
+
 	impl_benchmark_test_suite!(
 		Pallet,
 		crate::mock::new_test_ext_with_balances(Vec::new()),
@@ -89,6 +89,8 @@ pub trait WeightInfo {
 	fn claim_assets() -> Weight;
 	fn execute_blob() -> Weight;
 	fn send_blob() -> Weight;
+	fn force_query_expiry() -> Weight;
+	fn cancel_query() -> Weight;
 }
 
 /// fallback implementation
@@ -181,6 +183,14 @@ impl WeightInfo for TestWeightInfo {
 	fn send_blob() -> Weight {
 		Weight::from_parts(100_000_000, 0)
 	}
+
+	fn force_query_expiry() -> Weight {
+		Weight::from_parts(100_000_000, 0)
+	}
+
+	fn cancel_query() -> Weight {
+		Weight::from_parts(100_000_000, 0)
+	}
 }
 
 #[frame_support::pallet]
@@ -360,7 +370,9 @@ pub mod pallet {
 			match_querier: VersionedLocation,
 		) -> Result<QueryId, DispatchError> {
 			let responder = <T as Config>::ExecuteXcmOrigin::ensure_origin(origin)?;
-			let query_id = <Self as QueryHandler>::new_query(
+			// The origin creating the query is allowed to cancel it.
+			let query_id = Self::new_query_for(
+				responder.clone(),
 				responder,
 				timeout,
 				Location::try_from(match_querier)
@@ -474,6 +486,12 @@ pub mod pallet {
 		AssetsClaimed { hash: H256, origin: Location, assets: VersionedAssets },
 		/// A XCM version migration finished.
 		VersionMigrationFinished { version: XcmVersion },
+		/// A query was still pending when it expired and has been removed.
+		QueryExpired { query_id: QueryId },
+		/// A pending query has been cancelled and removed.
+		QueryCancelled { query_id: QueryId },
+		/// The delay after their timeout at which pending queries expire has been changed.
+		QueryExpiryDelaySet { maybe_delay: Option<BlockNumberFor<T>> },
 	}
 
 	#[pallet::origin]
@@ -550,6 +568,10 @@ pub mod pallet {
 		/// XCM encoded length is too large.
 		/// Returned when an XCM encoded length is larger than `MaxXcmEncodedSize`.
 		XcmTooLarge,
+		/// The query does not exist.
+		QueryNotFound,
+		/// The query is a version notification subscription and cannot be cancelled.
+		QueryNotCancellable,
 	}
 
 	impl<T: Config> From<SendError> for Error<T> {
@@ -635,6 +657,28 @@ pub mod pallet {
 	#[pallet::getter(fn asset_trap)]
 	pub(super) type AssetTraps<T: Config> = StorageMap<_, Identity, H256, u32, ValueQuery>;
 
+	/// The number of blocks after their timeout at which pending queries are removed. If `None`,
+	/// then pending queries are kept until they are answered or cancelled.
+	#[pallet::storage]
+	pub(super) type QueryExpiryDelay<T: Config> = StorageValue<_, BlockNumberFor<T>, OptionQuery>;
+
+	/// The queries to remove at a given block if they are still pending.
+	#[pallet::storage]
+	pub(super) type QueryExpiries<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		BlockNumberFor<T>,
+		Twox64Concat,
+		QueryId,
+		(),
+		OptionQuery,
+	>;
+
+	/// The locations on behalf of which queries have been created, allowed to cancel them.
+	#[pallet::storage]
+	pub(super) type QueryOwners<T: Config> =
+		StorageMap<_, Blake2_128Concat, QueryId, VersionedLocation, OptionQuery>;
+
 	/// Default version to encode XCM when latest version of destination is unknown. If `None`,
 	/// then the destinations whose XCM version is unknown are considered unreachable.
 	#[pallet::storage]
@@ -774,8 +818,8 @@ pub mod pallet {
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
-		fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
-			let mut weight_used = Weight::zero();
+		fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+			let mut weight_used = Self::expire_queries(n);
 			if let Some(migration) = CurrentMigration::<T>::get() {
 				// Consume 10% of block at most
 				let max_weight = T::BlockWeights::get().max_block / 10;
@@ -1480,6 +1524,53 @@ pub mod pallet {
 				weight_limit,
 			)
 		}
+
+		/// Set the number of blocks after their timeout at which pending queries expire.
+		///
+		/// Only the queries created afterwards are affected.
+		///
+		/// - `origin`: Must be an origin specified by AdminOrigin.
+		/// - `maybe_delay`: The expiry delay, or `None` to keep pending queries forever.
+		#[pallet::call_index(16)]
+		#[pallet::weight(T::WeightInfo::force_query_expiry())]
+		pub fn force_query_expiry(
+			origin: OriginFor<T>,
+			maybe_delay: Option<BlockNumberFor<T>>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			QueryExpiryDelay::<T>::set(maybe_delay);
+			Self::deposit_event(Event::QueryExpiryDelaySet { maybe_delay });
+			Ok(())
+		}
+
+		/// Cancel a query, removing it along with any response already received.
+		///
+		/// No deposit is taken when a query is created, so there is nothing to refund.
+		///
+		/// - `origin`: Must be an origin specified by AdminOrigin, or the location on behalf of
+		///   which the query was created with [`Pallet::new_query_for`] or
+		///   [`Pallet::new_notify_query_for`].
+		/// - `query_id`: The query to cancel. Version notification subscriptions cannot be
+		///   cancelled, use `force_unsubscribe_version_notify` instead.
+		#[pallet::call_index(17)]
+		#[pallet::weight(T::WeightInfo::cancel_query())]
+		pub fn cancel_query(origin: OriginFor<T>, query_id: QueryId) -> DispatchResult {
+			let query = Queries::<T>::get(query_id).ok_or(Error::<T>::QueryNotFound)?;
+			if T::AdminOrigin::try_origin(origin.clone()).is_err() {
+				let origin_location = T::ExecuteXcmOrigin::ensure_origin(origin)?;
+				let owner = QueryOwners::<T>::get(query_id)
+					.and_then(|owner| Location::try_from(owner).ok());
+				ensure!(owner == Some(origin_location), DispatchError::BadOrigin);
+			}
+			ensure!(
+				!matches!(query, QueryStatus::VersionNotifier { .. }),
+				Error::<T>::QueryNotCancellable
+			);
+			Queries::<T>::remove(query_id);
+			QueryOwners::<T>::remove(query_id);
+			Self::deposit_event(Event::QueryCancelled { query_id });
+			Ok(())
+		}
 	}
 }
 
@@ -1519,7 +1610,7 @@ impl<T: Config> QueryHandler for Pallet<T> {
 		timeout: BlockNumberFor<T>,
 		match_querier: impl Into<Location>,
 	) -> QueryId {
-		Self::do_new_query(responder, None, timeout, match_querier)
+		Self::do_new_query(None, responder, None, timeout, match_querier)
 	}
 
 	/// To check the status of the query, use `fn query()` passing the resultant `QueryId`
@@ -1546,6 +1637,7 @@ impl<T: Config> QueryHandler for Pallet<T> {
 			Some(QueryStatus::Ready { response, at }) => match response.try_into() {
 				Ok(response) => {
 					Queries::<T>::remove(query_id);
+					QueryOwners::<T>::remove(query_id);
 					Self::deposit_event(Event::ResponseTaken { query_id });
 					QueryResponseStatus::Ready { response, at }
 				},
@@ -2510,11 +2602,21 @@ impl<T: Config> Pallet<T> {
 
 	/// Create a new expectation of a query response with the querier being here.
 	fn do_new_query(
+		maybe_owner: Option<Location>,
 		responder: impl Into<Location>,
 		maybe_notify: Option<(u8, u8)>,
 		timeout: BlockNumberFor<T>,
 		match_querier: impl Into<Location>,
 	) -> u64 {
+		if let Some(delay) = QueryExpiryDelay::<T>::get() {
+			// Timeouts are sometimes relative, never expire queries before their creation.
+			let now = frame_system::Pallet::<T>::block_number();
+			let expiry = timeout.max(now).saturating_add(delay);
+			QueryExpiries::<T>::insert(expiry, QueryCounter::<T>::get(), ());
+		}
+		if let Some(owner) = maybe_owner {
+			QueryOwners::<T>::insert(QueryCounter::<T>::get(), VersionedLocation::from(owner));
+		}
 		QueryCounter::<T>::mutate(|q| {
 			let r = *q;
 			q.saturating_inc();
@@ -2531,6 +2633,47 @@ impl<T: Config> Pallet<T> {
 		})
 	}
 
+	/// Remove the queries expiring at `now` which are still pending.
+	///
+	/// Queries which have been answered are kept until their response is taken.
+	fn expire_queries(now: BlockNumberFor<T>) -> Weight {
+		let mut weight_used = T::DbWeight::get().reads(1);
+		for (query_id, ()) in QueryExpiries::<T>::drain_prefix(now) {
+			weight_used.saturating_accrue(T::DbWeight::get().reads_writes(2, 1));
+			if let Some(QueryStatus::Pending { .. }) = Queries::<T>::get(query_id) {
+				Queries::<T>::remove(query_id);
+				QueryOwners::<T>::remove(query_id);
+				weight_used.saturating_accrue(T::DbWeight::get().writes(2));
+				Self::deposit_event(Event::QueryExpired { query_id });
+			}
+		}
+		weight_used
+	}
+
+	/// The pending queries created on behalf of `owner`.
+	pub fn outstanding_queries(owner: &Location) -> Vec<(QueryId, QueryStatus<BlockNumberFor<T>>)> {
+		QueryOwners::<T>::iter()
+			.filter(|(_, o)| Location::try_from(o.clone()).map_or(false, |o| &o == owner))
+			.filter_map(|(query_id, _)| match Queries::<T>::get(query_id) {
+				Some(query @ QueryStatus::Pending { .. }) => Some((query_id, query)),
+				_ => None,
+			})
+			.collect()
+	}
+
+	/// Register a new query on behalf of `owner`, like [`QueryHandler::new_query`].
+	///
+	/// The query is listed by [`Self::outstanding_queries`] for `owner`, which may cancel it with
+	/// [`Call::cancel_query`].
+	pub fn new_query_for(
+		owner: impl Into<Location>,
+		responder: impl Into<Location>,
+		timeout: BlockNumberFor<T>,
+		match_querier: impl Into<Location>,
+	) -> QueryId {
+		Self::do_new_query(Some(owner.into()), responder, None, timeout, match_querier)
+	}
+
 	/// Consume `message` and return another which is equivalent to it except that it reports
 	/// back the outcome and dispatches `notify` on this chain.
 	///
@@ -2580,10 +2723,30 @@ impl<T: Config> Pallet<T> {
 		timeout: BlockNumberFor<T>,
 		match_querier: impl Into<Location>,
 	) -> u64 {
-		let notify = notify.into().using_encoded(|mut bytes| Decode::decode(&mut bytes)).expect(
-			"decode input is output of Call encode; Call guaranteed to have two enums; qed",
-		);
-		Self::do_new_query(responder, Some(notify), timeout, match_querier)
+		let notify = Self::notify_indices(notify);
+		Self::do_new_query(None, responder, Some(notify), timeout, match_querier)
+	}
+
+	/// Register a new query on behalf of `owner`, like [`Self::new_notify_query`].
+	///
+	/// See [`Self::new_query_for`].
+	pub fn new_notify_query_for(
+		owner: impl Into<Location>,
+		responder: impl Into<Location>,
+		notify: impl Into<<T as Config>::RuntimeCall>,
+		timeout: BlockNumberFor<T>,
+		match_querier: impl Into<Location>,
+	) -> u64 {
+		let notify = Self::notify_indices(notify);
+		Self::do_new_query(Some(owner.into()), responder, Some(notify), timeout, match_querier)
+	}
+
+	/// The pallet and call indices of `notify`.
+	fn notify_indices(notify: impl Into<<T as Config>::RuntimeCall>) -> (u8, u8) {
+		notify
+			.into()
+			.using_encoded(|mut bytes| Decode::decode(&mut bytes))
+			.expect("decode input is output of Call encode; Call guaranteed to have two enums; qed")
 	}
 
 	/// Note that a particular destination to whom we would like to send a message is unknown
@@ -3100,6 +3263,7 @@ impl<T: Config> OnResponse for Pallet<T> {
 							<T as Config>::RuntimeCall::decode(&mut bytes)
 						}) {
 							Queries::<T>::remove(query_id);
+							QueryOwners::<T>::remove(query_id);
 							let weight = call.get_dispatch_info().weight;
 							if weight.any_gt(max_weight) {
 								let e = Event::NotifyOverweight {
@@ -30,9 +30,12 @@ use frame_support::{
 	weights::Weight,
 };
 use polkadot_parachain_primitives::primitives::Id as ParaId;
-use sp_runtime::traits::{AccountIdConversion, BlakeTwo256, Hash};
+use sp_runtime::{
+	traits::{AccountIdConversion, BlakeTwo256, Hash},
+	DispatchError,
+};
 use xcm::{latest::QueryResponseInfo, prelude::*};
-use xcm_builder::AllowKnownQueryResponses;
+use xcm_builder::{AllowKnownQueryResponses, QueryController};
 use xcm_executor::{
 	traits::{Properties, QueryHandler, QueryResponseStatus, ShouldExecute},
 	XcmExecutor,
@@ -286,6 +289,118 @@ fn custom_querier_works() {
 	});
 }
 
+#[test]
+fn pending_queries_expire() {
+	new_test_ext_with_balances(vec![]).execute_with(|| {
+		let querier: Location = AccountId32 { network: None, id: ALICE.into() }.into();
+
+		// Queries created without an expiry delay are kept.
+		assert_ok!(TestNotifier::prepare_new_query(RuntimeOrigin::signed(ALICE), querier.clone()));
+		assert_noop!(
+			XcmPallet::force_query_expiry(RuntimeOrigin::signed(ALICE), Some(10)),
+			DispatchError::BadOrigin,
+		);
+		assert_ok!(XcmPallet::force_query_expiry(RuntimeOrigin::root(), Some(10)));
+		assert_eq!(
+			last_event(),
+			RuntimeEvent::XcmPallet(crate::Event::QueryExpiryDelaySet { maybe_delay: Some(10) })
+		);
+		assert_ok!(TestNotifier::prepare_new_query(RuntimeOrigin::signed(ALICE), querier.clone()));
+		assert_ok!(TestNotifier::prepare_new_query(RuntimeOrigin::signed(ALICE), querier));
+		// Query 2 is answered before expiring.
+		let ready = QueryStatus::Ready { response: Response::Null.into(), at: 1 };
+		Queries::<Test>::insert(2, ready.clone());
+
+		XcmPallet::on_initialize(109);
+		assert_eq!(Queries::<Test>::iter_keys().count(), 3);
+
+		XcmPallet::on_initialize(110);
+		assert_eq!(
+			last_event(),
+			RuntimeEvent::XcmPallet(crate::Event::QueryExpired { query_id: 1 })
+		);
+		assert!(Queries::<Test>::contains_key(0));
+		assert!(!Queries::<Test>::contains_key(1));
+		assert_eq!(Queries::<Test>::get(2), Some(ready));
+	});
+}
+
+#[test]
+fn cancel_query_works() {
+	new_test_ext_with_balances(vec![]).execute_with(|| {
+		let owner: Location = AccountId32 { network: None, id: ALICE.into() }.into();
+		XcmPallet::new_query_for(owner.clone(), Parent, 100, Here);
+		XcmPallet::new_query_for(owner.clone(), Parent, 100, Here);
+		// Queries created without an owner are only cancellable by the admin.
+		XcmPallet::new_query(Parent, 100, Here);
+		assert_eq!(
+			XcmPallet::outstanding_queries(&owner)
+				.into_iter()
+				.map(|(query_id, _)| query_id)
+				.collect::<Vec<_>>(),
+			vec![0, 1],
+		);
+
+		// Only the owner or the admin may cancel a query.
+		assert_noop!(
+			XcmPallet::cancel_query(RuntimeOrigin::signed(ALICE), 2),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			XcmPallet::cancel_query(RuntimeOrigin::signed(BOB), 0),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(XcmPallet::cancel_query(RuntimeOrigin::signed(ALICE), 0));
+		assert_eq!(
+			last_event(),
+			RuntimeEvent::XcmPallet(crate::Event::QueryCancelled { query_id: 0 })
+		);
+		assert_noop!(
+			XcmPallet::cancel_query(RuntimeOrigin::signed(ALICE), 0),
+			Error::<Test>::QueryNotFound,
+		);
+		assert!(!QueryOwners::<Test>::contains_key(0));
+		assert_ok!(XcmPallet::cancel_query(RuntimeOrigin::root(), 1));
+		assert!(XcmPallet::outstanding_queries(&owner).is_empty());
+		assert_ok!(XcmPallet::cancel_query(RuntimeOrigin::root(), 2));
+
+		// Version notification subscriptions must be unsubscribed instead.
+		let notifier = QueryStatus::VersionNotifier { origin: owner.into(), is_active: true };
+		Queries::<Test>::insert(3, notifier);
+		assert_noop!(
+			XcmPallet::cancel_query(RuntimeOrigin::root(), 3),
+			Error::<Test>::QueryNotCancellable,
+		);
+	});
+}
+
+#[test]
+fn queries_of_the_query_controller_are_cancellable_by_their_origin() {
+	new_test_ext_with_balances(vec![]).execute_with(|| {
+		let owner: Location = AccountId32 { network: None, id: ALICE.into() }.into();
+		let query_id = <XcmPallet as QueryController<_, _>>::query(
+			RuntimeOrigin::signed(ALICE),
+			100,
+			Location::here().into(),
+		)
+		.unwrap();
+		assert_eq!(
+			XcmPallet::outstanding_queries(&owner)
+				.into_iter()
+				.map(|(query_id, _)| query_id)
+				.collect::<Vec<_>>(),
+			vec![query_id],
+		);
+
+		assert_noop!(
+			XcmPallet::cancel_query(RuntimeOrigin::signed(BOB), query_id),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(XcmPallet::cancel_query(RuntimeOrigin::signed(ALICE), query_id));
+		assert!(!Queries::<Test>::contains_key(query_id));
+	});
+}
+
 /// Test sending an `XCM` message (`XCM::ReserveAssetDeposit`)
 ///
 /// Asserts that the expected message is sent and the event is emitted
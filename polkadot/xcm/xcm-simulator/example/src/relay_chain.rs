@@ -279,6 +279,7 @@ impl pallet_message_queue::Config for Runtime {
 	type MessageProcessor = MessageProcessor;
 	type QueueChangeHandler = ();
 	type QueuePausedQuery = ();
+	type ServiceShares = ();
 	type WeightInfo = ();
 }
 
@@ -305,4 +305,8 @@ impl sc_transaction_pool_api::TransactionPool for Transactions {
 	fn ready_transaction(&self, _hash: &TxHash<Self>) -> Option<Arc<Self::InPoolTransaction>> {
 		unimplemented!()
 	}
+
+	fn revalidate_ready(&self) -> PoolFuture<usize, Self::Error> {
+		unimplemented!()
+	}
 }
@@ -61,6 +61,7 @@ fn new_node(tokio_handle: Handle) -> node_cli::service::NewFullBase {
 			future: PoolLimit { count: 100_000, total_bytes: 100 * 1024 * 1024 },
 			reject_future_transactions: false,
 			ban_time: Duration::from_secs(30 * 60),
+			revalidation: Default::default(),
 		},
 		network: network_config,
 		keystore: KeystoreConfig::InMemory,
@@ -68,6 +69,7 @@ fn new_node(tokio_handle: Handle) -> node_cli::service::NewFullBase {
 		trie_cache_maximum_size: Some(64 * 1024 * 1024),
 		state_pruning: Some(PruningMode::ArchiveAll),
 		blocks_pruning: BlocksPruning::KeepAll,
+		block_compression: Default::default(),
 		chain_spec: spec,
 		wasm_method: Default::default(),
 		rpc_addr: None,
@@ -82,6 +84,43 @@ fn new_node(tokio_handle: Handle) -> node_cli::service::NewFullBase {
 		rpc_message_buffer_capacity: Default::default(),
 		rpc_batch_config: RpcBatchRequestConfig::Unlimited,
 		rpc_rate_limit: None,
+		rpc_tls: None,
+		rpc_ip_filter: None,
+		rpc_ws_compression: None,
+		rpc_auth: None,
+		rpc_http2: None,
+		rpc_rate_limit_whitelisted_ips: Default::default(),
+		rpc_audit_log: None,
+		rpc_method_filter: None,
+		rpc_ws_ping: Default::default(),
+		rpc_response_cache: None,
+		rpc_method_cors: Vec::new(),
+		rpc_load_shed: None,
+		rpc_listeners: Vec::new(),
+		rpc_trusted_proxies: Vec::new(),
+		rpc_max_connections_per_ip: None,
+		rpc_api_keys: None,
+		rpc_call_cost: None,
+		rpc_subscription_backpressure: Vec::new(),
+		rpc_http_compression: None,
+		rpc_timeout: None,
+		rpc_method_aliases: Vec::new(),
+		rpc_access_log: false,
+		rpc_sse: None,
+		rpc_socket: Default::default(),
+		rpc_quota: None,
+		rpc_slow_call_threshold: None,
+		rpc_circuit_breaker: None,
+		rpc_metrics: Default::default(),
+		rpc_call_spans: false,
+		rpc_deprecated_methods: Default::default(),
+		rpc_middleware_extensions: Default::default(),
+		rpc_max_persisted_broadcasts: None,
+		rpc_chain_head: Default::default(),
+		rpc_transaction: Default::default(),
+		rpc_transaction_broadcast: Default::default(),
+		rpc_archive: Default::default(),
+		rpc_sessions: None,
 		prometheus_config: None,
 		telemetry_endpoints: None,
 		default_heap_pages: None,
@@ -98,6 +137,8 @@ fn new_node(tokio_handle: Handle) -> node_cli::service::NewFullBase {
 		base_path,
 		informant_output_format: Default::default(),
 		wasm_runtime_overrides: None,
+		execution_witness: None,
+		justification_verifier: Default::default(),
 	};
 
 	tokio_handle.block_on(async move {
@@ -80,6 +80,9 @@ pub enum Subcommand {
 	/// Validate blocks.
 	CheckBlock(sc_cli::CheckBlockCmd),
 
+	/// Re-execute a block from its execution witness.
+	ReplayWitness(sc_cli::ReplayWitnessCmd),
+
 	/// Export blocks.
 	ExportBlocks(sc_cli::ExportBlocksCmd),
 
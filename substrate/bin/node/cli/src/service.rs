@@ -120,6 +120,7 @@ pub fn create_extrinsic(
 				best_block.saturated_into(),
 			)),
 			frame_system::CheckNonce::<kitchensink_runtime::Runtime>::from(nonce),
+			pallet_block_space::CheckBlockSpace::<kitchensink_runtime::Runtime>::new(),
 			frame_system::CheckWeight::<kitchensink_runtime::Runtime>::new(),
 			pallet_skip_feeless_payment::SkipCheckIfFeeless::from(
 				pallet_asset_conversion_tx_payment::ChargeAssetTxPayment::<
@@ -140,6 +141,7 @@ pub fn create_extrinsic(
 			(),
 			(),
 			(),
+			(),
 		),
 	);
 	let signature = raw_payload.using_encoded(|e| sender.sign(e));
@@ -229,6 +231,8 @@ pub fn new_partial(
 		select_chain.clone(),
 		telemetry.as_ref().map(|x| x.handle()),
 	)?;
+	let grandpa_block_import =
+		grandpa_block_import.with_justification_verifier(config.justification_verifier);
 	let justification_import = grandpa_block_import.clone();
 
 	let (beefy_block_import, beefy_voter_links, beefy_rpc_links) =
@@ -238,6 +242,8 @@ pub fn new_partial(
 			client.clone(),
 			config.prometheus_registry().cloned(),
 		);
+	let beefy_block_import =
+		beefy_block_import.with_justification_verifier(config.justification_verifier);
 
 	let (block_import, babe_link) = sc_consensus_babe::block_import(
 		sc_consensus_babe::configuration(&*client)?,
@@ -488,11 +494,14 @@ pub fn new_full_base<N: NetworkBackend<Block, <Block as BlockT>::Hash>>(
 		notification_service
 	});
 
-	let warp_sync = Arc::new(grandpa::warp_proof::NetworkProvider::new(
-		backend.clone(),
-		import_setup.1.shared_authority_set().clone(),
-		Vec::default(),
-	));
+	let warp_sync = Arc::new(
+		grandpa::warp_proof::NetworkProvider::new(
+			backend.clone(),
+			import_setup.1.shared_authority_set().clone(),
+			Vec::default(),
+		)
+		.with_justification_verifier(config.justification_verifier),
+	);
 
 	let (network, system_rpc_tx, tx_handler_controller, network_starter, sync_service) =
 		sc_service::build_network(sc_service::BuildNetworkParams {
@@ -1037,6 +1046,7 @@ mod tests {
 				let check_genesis = frame_system::CheckGenesis::new();
 				let check_era = frame_system::CheckEra::from(Era::Immortal);
 				let check_nonce = frame_system::CheckNonce::from(index);
+				let check_block_space = pallet_block_space::CheckBlockSpace::new();
 				let check_weight = frame_system::CheckWeight::new();
 				let tx_payment = pallet_skip_feeless_payment::SkipCheckIfFeeless::from(
 					pallet_asset_conversion_tx_payment::ChargeAssetTxPayment::from(0, None),
@@ -1048,13 +1058,24 @@ mod tests {
 					check_genesis,
 					check_era,
 					check_nonce,
+					check_block_space,
 					check_weight,
 					tx_payment,
 				);
 				let raw_payload = SignedPayload::from_raw(
 					function,
 					extra,
-					((), spec_version, transaction_version, genesis_hash, genesis_hash, (), (), ()),
+					(
+						(),
+						spec_version,
+						transaction_version,
+						genesis_hash,
+						genesis_hash,
+						(),
+						(),
+						(),
+						(),
+					),
 				);
 				let signature = raw_payload.using_encoded(|payload| signer.sign(payload));
 				let (function, extra, _) = raw_payload.deconstruct();
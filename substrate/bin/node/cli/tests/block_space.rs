@@ -0,0 +1,96 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use frame_support::weights::{constants::WEIGHT_REF_TIME_PER_SECOND, Weight};
+use kitchensink_runtime::{constants::time::SLOT_DURATION, CheckedExtrinsic, Runtime, RuntimeCall};
+use node_testing::keyring::*;
+use pallet_block_space::{ConsumedReservedWeight, Reservations};
+
+pub mod common;
+use self::common::*;
+
+#[test]
+fn reserved_block_space_is_consumed_by_the_transactions_of_the_reserver() {
+	let mut t = new_test_ext(compact_code_unwrap());
+	let mut tt = new_test_ext(compact_code_unwrap());
+	let reserved = Weight::from_parts(WEIGHT_REF_TIME_PER_SECOND / 100, 64 * 1024);
+
+	let time1 = 42 * 1000;
+	let block1 = construct_block(
+		&mut tt,
+		1,
+		GENESIS_HASH.into(),
+		vec![
+			CheckedExtrinsic {
+				signed: None,
+				function: RuntimeCall::Timestamp(pallet_timestamp::Call::set { now: time1 }),
+			},
+			CheckedExtrinsic {
+				signed: Some((alice(), signed_extra(0, 0))),
+				function: RuntimeCall::BlockSpace(pallet_block_space::Call::reserve {
+					start: 2,
+					length: 1,
+					weight: reserved,
+				}),
+			},
+		],
+		(time1 / SLOT_DURATION).into(),
+	);
+
+	let time2 = 52 * 1000;
+	let block2 = construct_block(
+		&mut tt,
+		2,
+		block1.1,
+		vec![
+			CheckedExtrinsic {
+				signed: None,
+				function: RuntimeCall::Timestamp(pallet_timestamp::Call::set { now: time2 }),
+			},
+			CheckedExtrinsic {
+				signed: Some((alice(), signed_extra(1, 0))),
+				function: RuntimeCall::System(frame_system::Call::remark { remark: vec![0; 1] }),
+			},
+			CheckedExtrinsic {
+				signed: Some((bob(), signed_extra(0, 0))),
+				function: RuntimeCall::System(frame_system::Call::remark { remark: vec![0; 1] }),
+			},
+		],
+		(time2 / SLOT_DURATION).into(),
+	);
+
+	executor_call(&mut t, "Core_execute_block", &block1.0).0.unwrap();
+
+	t.execute_with(|| {
+		let reservation = Reservations::<Runtime>::get(alice()).unwrap();
+		assert_eq!((reservation.start, reservation.end), (2, 3));
+		assert_eq!(reservation.consumed, Weight::zero());
+	});
+
+	executor_call(&mut t, "Core_execute_block", &block2.0).0.unwrap();
+
+	t.execute_with(|| {
+		// Only the remark of Alice was accounted in her reservation.
+		let reservation = Reservations::<Runtime>::get(alice()).unwrap();
+		assert_eq!(reservation.consumed_at, 2);
+		assert!(!reservation.consumed.is_zero());
+		assert!(reservation.consumed.all_lte(reserved));
+		assert_eq!(reservation.consumed_in_block, reservation.consumed);
+		assert_eq!(ConsumedReservedWeight::<Runtime>::get(), (2, reservation.consumed));
+		assert_eq!(Reservations::<Runtime>::get(bob()), None);
+	});
+}
@@ -0,0 +1,91 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use codec::{Decode, Encode};
+use kitchensink_runtime::{
+	constants::{currency::*, time::SLOT_DURATION},
+	Block, CheckedExtrinsic, Runtime, RuntimeCall,
+};
+use node_primitives::Hash;
+use node_testing::keyring::*;
+use sp_runtime::{traits::BlakeTwo256, AccountId32};
+use sp_trie::{LayoutV1 as Layout, TrieConfiguration};
+
+pub mod common;
+use self::common::*;
+
+fn transfer(from: AccountId32, to: AccountId32) -> CheckedExtrinsic {
+	CheckedExtrinsic {
+		signed: Some((from, signed_extra(0, 0))),
+		function: RuntimeCall::Balances(pallet_balances::Call::transfer_allow_death {
+			dest: to.into(),
+			value: 10 * DOLLARS,
+		}),
+	}
+}
+
+/// A block with a transfer of each of Alice, Bob and Charlie.
+fn transfers_block() -> (Vec<u8>, Hash) {
+	let time = 42 * 1000;
+	construct_block(
+		&mut new_test_ext(compact_code_unwrap()),
+		1,
+		GENESIS_HASH.into(),
+		vec![
+			CheckedExtrinsic {
+				signed: None,
+				function: RuntimeCall::Timestamp(pallet_timestamp::Call::set { now: time }),
+			},
+			transfer(alice(), bob()),
+			transfer(bob(), charlie()),
+			transfer(charlie(), alice()),
+		],
+		(time / SLOT_DURATION).into(),
+	)
+}
+
+#[test]
+fn block_with_good_signatures_is_executed() {
+	let mut t = new_test_ext(compact_code_unwrap());
+	let (block, _) = transfers_block();
+
+	executor_call(&mut t, "Core_execute_block", &block).0.unwrap();
+
+	t.execute_with(|| {
+		for who in [alice(), bob(), charlie()] {
+			assert_eq!(frame_system::Pallet::<Runtime>::account_nonce(who), 1);
+		}
+	});
+}
+
+#[test]
+fn block_with_one_bad_signature_is_rejected() {
+	let mut t = new_test_ext(compact_code_unwrap());
+	let (block, _) = transfers_block();
+	let mut block = Block::decode(&mut &block[..]).unwrap();
+
+	// Bob's transfer carries the signature of Alice's transfer.
+	let signature = block.extrinsics[1].signature.as_ref().unwrap().1.clone();
+	block.extrinsics[2].signature.as_mut().unwrap().1 = signature;
+	block.header.extrinsics_root =
+		Layout::<BlakeTwo256>::ordered_trie_root(block.extrinsics.iter().map(Encode::encode))
+			.to_fixed_bytes()
+			.into();
+
+	let error = executor_call(&mut t, "Core_execute_block", &block.encode()).0.unwrap_err();
+	assert!(format!("{:?}", error).contains("Transaction has a bad signature"));
+}
@@ -161,10 +161,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	// and set impl_version to 0. If only runtime
 	// implementation changes and behavior does not, then leave spec_version as
 	// is and increment impl_version.
-	spec_version: 268,
+	spec_version: 269,
 	impl_version: 0,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 2,
+	transaction_version: 3,
 	state_version: 1,
 };
 
@@ -686,10 +686,15 @@ impl pallet_staking::Config for Runtime {
 		EnsureRoot<AccountId>,
 		pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 3, 4>,
 	>;
+	type SlashVetoOrigin = EitherOfDiverse<
+		EnsureRoot<AccountId>,
+		pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 3, 4>,
+	>;
 	type SessionInterface = Self;
 	type EraPayout = pallet_staking::ConvertCurve<RewardCurve>;
 	type NextNewSession = Session;
 	type MaxExposurePageSize = ConstU32<256>;
+	type MaxPayoutSplits = ConstU32<4>;
 	type OffendingValidatorsThreshold = OffendingValidatorsThreshold;
 	type ElectionProvider = ElectionProviderMultiPhase;
 	type GenesisElectionProvider = onchain::OnChainExecution<OnChainSeqPhragmen>;
@@ -1282,6 +1287,39 @@ parameter_types! {
 	pub const BountyUpdatePeriod: BlockNumber = 14 * DAYS;
 }
 
+parameter_types! {
+	pub const BlockSpaceMaxReservedShare: Perbill = Perbill::from_percent(25);
+	pub const BlockSpaceReservedPriority: TransactionPriority = TransactionPriority::max_value() / 4;
+}
+
+/// Block space is priced like the weight of the transactions.
+pub struct BlockSpacePrice;
+impl Convert<Weight, Balance> for BlockSpacePrice {
+	fn convert(weight: Weight) -> Balance {
+		TransactionPayment::weight_to_fee(weight)
+	}
+}
+
+impl pallet_block_space::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type WeightPrice = BlockSpacePrice;
+	type MaxReservedShare = BlockSpaceMaxReservedShare;
+	type MaxLength = ConstU32<{ 1 * HOURS }>;
+	type MaxLeadTime = ConstU32<{ 1 * DAYS }>;
+	type ReservedPriority = BlockSpaceReservedPriority;
+	type WeightInfo = pallet_block_space::weights::SubstrateWeight<Runtime>;
+}
+
+impl pallet_bootnodes::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type ManagerOrigin = EnsureRoot<AccountId>;
+	type MaxPeers = ConstU32<32>;
+	type MaxAddressLength = ConstU32<256>;
+	type WeightInfo = pallet_bootnodes::weights::SubstrateWeight<Runtime>;
+}
+
 impl pallet_bounties::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type BountyDepositBase = BountyDepositBase;
@@ -1312,6 +1350,7 @@ impl pallet_message_queue::Config for Runtime {
 	type Size = u32;
 	type QueueChangeHandler = ();
 	type QueuePausedQuery = ();
+	type ServiceShares = ();
 	type HeapSize = ConstU32<{ 64 * 1024 }>;
 	type MaxStale = ConstU32<128>;
 	type ServiceWeight = MessageQueueServiceWeight;
@@ -1429,6 +1468,7 @@ where
 			frame_system::CheckGenesis::<Runtime>::new(),
 			frame_system::CheckEra::<Runtime>::from(era),
 			frame_system::CheckNonce::<Runtime>::from(nonce),
+			pallet_block_space::CheckBlockSpace::<Runtime>::new(),
 			frame_system::CheckWeight::<Runtime>::new(),
 			pallet_skip_feeless_payment::SkipCheckIfFeeless::from(
 				pallet_asset_conversion_tx_payment::ChargeAssetTxPayment::<Runtime>::from(
@@ -1661,6 +1701,7 @@ impl pallet_assets::Config<Instance1> for Runtime {
 	type Freezer = ();
 	type Extra = ();
 	type CallbackHandle = ();
+	type FreezeConsumer = ();
 	type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
 	type RemoveItemsLimit = ConstU32<1000>;
 	#[cfg(feature = "runtime-benchmarks")]
@@ -1690,6 +1731,7 @@ impl pallet_assets::Config<Instance2> for Runtime {
 	type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
 	type RemoveItemsLimit = ConstU32<1000>;
 	type CallbackHandle = ();
+	type FreezeConsumer = ();
 	#[cfg(feature = "runtime-benchmarks")]
 	type BenchmarkHelper = ();
 }
@@ -2474,6 +2516,12 @@ mod runtime {
 
 	#[runtime::pallet_index(78)]
 	pub type PalletExampleMbms = pallet_example_mbm;
+
+	#[runtime::pallet_index(79)]
+	pub type Bootnodes = pallet_bootnodes;
+
+	#[runtime::pallet_index(80)]
+	pub type BlockSpace = pallet_block_space;
 }
 
 /// The address format for describing accounts.
@@ -2498,6 +2546,7 @@ pub type SignedExtra = (
 	frame_system::CheckGenesis<Runtime>,
 	frame_system::CheckEra<Runtime>,
 	frame_system::CheckNonce<Runtime>,
+	pallet_block_space::CheckBlockSpace<Runtime>,
 	frame_system::CheckWeight<Runtime>,
 	pallet_skip_feeless_payment::SkipCheckIfFeeless<
 		Runtime,
@@ -2576,6 +2625,8 @@ mod benches {
 		[pallet_babe, Babe]
 		[pallet_bags_list, VoterList]
 		[pallet_balances, Balances]
+		[pallet_block_space, BlockSpace]
+		[pallet_bootnodes, Bootnodes]
 		[pallet_bounties, Bounties]
 		[pallet_broker, Broker]
 		[pallet_child_bounties, ChildBounties]
@@ -2643,7 +2694,7 @@ impl_runtime_apis! {
 		}
 
 		fn execute_block(block: Block) {
-			Executive::execute_block(block);
+			Executive::execute_block_with_batch_verification(block);
 		}
 
 		fn initialize_block(header: &<Block as BlockT>::Header) -> sp_runtime::ExtrinsicInclusionMode {
@@ -2665,6 +2716,16 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl sp_bootnodes::BootnodesApi<Block> for Runtime {
+		fn bootnodes() -> Vec<Vec<u8>> {
+			Bootnodes::bootnodes()
+		}
+
+		fn reserved_peers() -> Vec<Vec<u8>> {
+			Bootnodes::reserved_peers()
+		}
+	}
+
 	impl sp_block_builder::BlockBuilder<Block> for Runtime {
 		fn apply_extrinsic(extrinsic: <Block as BlockT>::Extrinsic) -> ApplyExtrinsicResult {
 			Executive::apply_extrinsic(extrinsic)
@@ -2766,6 +2827,14 @@ impl_runtime_apis! {
 		fn eras_stakers_page_count(era: sp_staking::EraIndex, account: AccountId) -> sp_staking::Page {
 			Staking::api_eras_stakers_page_count(era, account)
 		}
+
+		fn deferred_slashes() -> Vec<sp_staking::DeferredSlash<AccountId, Balance>> {
+			Staking::api_deferred_slashes()
+		}
+
+		fn new_era_forced() -> bool {
+			Staking::api_new_era_forced()
+		}
 	}
 
 	impl sp_consensus_babe::BabeApi<Block> for Runtime {
@@ -3129,6 +3198,18 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl sp_session::SessionValidatorsApi<Block, AccountId, BlockNumber> for Runtime {
+		fn queued_validator_set() -> sp_session::QueuedValidatorSet<AccountId, BlockNumber> {
+			Session::queued_validator_set()
+		}
+	}
+
+	impl frame_support::storage::usage::runtime_api::StorageUsageApi<Block> for Runtime {
+		fn storage_usage() -> Vec<frame_support::storage::usage::PalletStorageUsage> {
+			frame_support::storage::usage::storage_usage::<AllPalletsWithSystem>()
+		}
+	}
+
 	#[cfg(feature = "try-runtime")]
 	impl frame_try_runtime::TryRuntime<Block> for Runtime {
 		fn on_runtime_upgrade(checks: frame_try_runtime::UpgradeCheckSelect) -> (Weight, Weight) {
@@ -240,6 +240,19 @@ pub struct IoInfo {
 	pub state_writes_cache: u64,
 	/// State write (trie nodes) to backend db.
 	pub state_writes_nodes: u64,
+	/// Sizes of the block bodies written compressed.
+	pub body_compression: CompressionInfo,
+	/// Sizes of the justifications written compressed.
+	pub justification_compression: CompressionInfo,
+}
+
+/// Sizes of the values of a database column written compressed, since the start.
+#[derive(Default, Clone, Copy, Debug)]
+pub struct CompressionInfo {
+	/// Total size of the values before their compression.
+	pub uncompressed_bytes: u64,
+	/// Total size of the values stored.
+	pub compressed_bytes: u64,
 }
 
 /// Usage statistics for running client instance.
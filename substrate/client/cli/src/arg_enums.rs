@@ -154,6 +154,25 @@ pub enum ExecutionStrategy {
 	NativeElseWasm,
 }
 
+/// Blocks to capture the execution witness of during import.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "kebab-case")]
+pub enum ExecutionWitnessMode {
+	/// Blocks which fail to execute or result in an unexpected state root.
+	Failed,
+	/// All executed blocks.
+	All,
+}
+
+impl From<ExecutionWitnessMode> for sc_service::config::ExecutionWitnessMode {
+	fn from(mode: ExecutionWitnessMode) -> Self {
+		match mode {
+			ExecutionWitnessMode::Failed => Self::Failed,
+			ExecutionWitnessMode::All => Self::All,
+		}
+	}
+}
+
 /// Available RPC methods.
 #[allow(missing_docs)]
 #[derive(Debug, Copy, Clone, PartialEq, ValueEnum)]
@@ -222,6 +241,85 @@ impl FromStr for Cors {
 	}
 }
 
+/// Additional RPC listener.
+///
+/// Parsed from a comma-separated list of `<KEY>=<VALUE>` settings. The settings which aren't
+/// given default to the ones of the main RPC listener.
+#[derive(Clone, Debug)]
+pub struct RpcListenerSpec {
+	/// Binding address, the `addr` setting which is required.
+	pub addr: std::net::SocketAddr,
+	/// RPC methods to expose, the `methods` setting.
+	pub methods: Option<RpcMethods>,
+	/// CORS setting, the `cors` setting which can be given multiple times.
+	pub cors: Option<Cors>,
+	/// Maximum number of connections, the `max-connections` setting.
+	pub max_connections: Option<u32>,
+	/// Maximum number of connections per peer IP address, the `max-connections-per-ip` setting.
+	pub max_connections_per_ip: Option<std::num::NonZeroU32>,
+	/// Rate limit per minute, the `rate-limit` setting.
+	pub rate_limit: Option<std::num::NonZeroU32>,
+	/// Methods to expose, the `allow-method` setting which can be given multiple times.
+	pub allow_methods: Vec<sc_service::config::RpcMethodPattern>,
+	/// Methods not to expose, the `deny-method` setting which can be given multiple times.
+	pub deny_methods: Vec<sc_service::config::RpcMethodPattern>,
+}
+
+impl FromStr for RpcListenerSpec {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let mut addr = None;
+		let mut methods = None;
+		let mut cors: Option<Cors> = None;
+		let mut max_connections = None;
+		let mut max_connections_per_ip = None;
+		let mut rate_limit = None;
+		let mut allow_methods = Vec::new();
+		let mut deny_methods = Vec::new();
+
+		for setting in s.split(',') {
+			let (key, value) = setting.split_once('=').ok_or_else(|| {
+				format!("Invalid listener setting `{setting}`, expected KEY=VALUE")
+			})?;
+			let invalid = |e: &dyn std::fmt::Display| format!("Invalid listener `{key}`: {e}");
+
+			match key {
+				"addr" => addr = Some(value.parse().map_err(|e| invalid(&e))?),
+				"methods" => methods = Some(<RpcMethods as ValueEnum>::from_str(value, true)?),
+				"cors" =>
+					cors = match (cors, value) {
+						(_, "all" | "*") | (Some(Cors::All), _) => Some(Cors::All),
+						(Some(Cors::List(mut origins)), origin) => {
+							origins.push(origin.to_owned());
+							Some(Cors::List(origins))
+						},
+						(None, origin) => Some(Cors::List(vec![origin.to_owned()])),
+					},
+				"max-connections" =>
+					max_connections = Some(value.parse().map_err(|e| invalid(&e))?),
+				"max-connections-per-ip" =>
+					max_connections_per_ip = Some(value.parse().map_err(|e| invalid(&e))?),
+				"rate-limit" => rate_limit = Some(value.parse().map_err(|e| invalid(&e))?),
+				"allow-method" => allow_methods.push(value.parse().map_err(|e| invalid(&e))?),
+				"deny-method" => deny_methods.push(value.parse().map_err(|e| invalid(&e))?),
+				other => return Err(format!("Unknown listener setting `{other}`")),
+			}
+		}
+
+		Ok(Self {
+			addr: addr.ok_or_else(|| "Missing listener `addr` setting".to_string())?,
+			methods,
+			cors,
+			max_connections,
+			max_connections_per_ip,
+			rate_limit,
+			allow_methods,
+			deny_methods,
+		})
+	}
+}
+
 /// Database backend
 #[derive(Debug, Clone, PartialEq, Copy, clap::ValueEnum)]
 #[value(rename_all = "lower")]
@@ -316,3 +414,22 @@ impl Into<sc_network::config::NetworkBackendType> for NetworkBackendType {
 		}
 	}
 }
+
+/// The order in which the transaction pool revalidates its transactions.
+#[derive(Debug, Clone, Copy, ValueEnum, PartialEq)]
+#[value(rename_all = "lower")]
+pub enum RevalidationPriority {
+	/// Revalidate the transactions that have been in the pool for the longest time first.
+	Oldest,
+	/// Revalidate the most recently submitted transactions first.
+	Newest,
+}
+
+impl Into<sc_service::config::TransactionPoolRevalidationPriority> for RevalidationPriority {
+	fn into(self) -> sc_service::config::TransactionPoolRevalidationPriority {
+		match self {
+			Self::Oldest => sc_service::config::TransactionPoolRevalidationPriority::Oldest,
+			Self::Newest => sc_service::config::TransactionPoolRevalidationPriority::Newest,
+		}
+	}
+}
@@ -31,6 +31,7 @@ mod inspect_key;
 mod inspect_node_key;
 mod key;
 mod purge_chain_cmd;
+mod replay_witness_cmd;
 mod revert_cmd;
 mod run_cmd;
 mod sign;
@@ -44,6 +45,6 @@ pub use self::{
 	export_blocks_cmd::ExportBlocksCmd, export_state_cmd::ExportStateCmd, generate::GenerateCmd,
 	generate_node_key::GenerateKeyCmdCommon, import_blocks_cmd::ImportBlocksCmd,
 	insert_key::InsertKeyCmd, inspect_key::InspectKeyCmd, inspect_node_key::InspectNodeKeyCmd,
-	key::KeySubcommand, purge_chain_cmd::PurgeChainCmd, revert_cmd::RevertCmd, run_cmd::RunCmd,
-	sign::SignCmd, vanity::VanityCmd, verify::VerifyCmd,
+	key::KeySubcommand, purge_chain_cmd::PurgeChainCmd, replay_witness_cmd::ReplayWitnessCmd,
+	revert_cmd::RevertCmd, run_cmd::RunCmd, sign::SignCmd, vanity::VanityCmd, verify::VerifyCmd,
 };
@@ -0,0 +1,64 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{error, params::SharedParams, CliConfiguration};
+use clap::Parser;
+use sc_service::client::ExecutionWitness;
+use sp_core::traits::CodeExecutor;
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
+use std::path::PathBuf;
+
+/// The `replay-witness` command used to re-execute a block from its execution witness.
+#[derive(Debug, Clone, Parser)]
+pub struct ReplayWitnessCmd {
+	/// Execution witness file, as written with `--execution-witness`.
+	#[arg(value_name = "PATH")]
+	pub input: PathBuf,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+}
+
+impl ReplayWitnessCmd {
+	/// Run the replay-witness command
+	pub fn run<B, E>(&self, executor: E) -> error::Result<()>
+	where
+		B: BlockT,
+		E: CodeExecutor + Clone + 'static,
+	{
+		let witness = ExecutionWitness::<B>::read(&self.input)?;
+		let header = witness.block.header();
+		println!("Replaying block #{} ({})", header.number(), header.hash());
+		if let Some(error) = &witness.error {
+			println!("The execution failed on the node which captured the witness: {error}");
+		}
+
+		let start = std::time::Instant::now();
+		let result = witness.replay(&executor);
+		println!("Completed in {} ms.", start.elapsed().as_millis());
+
+		result.map_err(|e| format!("Block execution failed: {e}").into())
+	}
+}
+
+impl CliConfiguration for ReplayWitnessCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+}
@@ -17,7 +17,7 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
-	arg_enums::{Cors, RpcMethods},
+	arg_enums::{Cors, RpcListenerSpec, RpcMethods},
 	error::{Error, Result},
 	params::{
 		ImportParams, KeystoreParams, NetworkParams, OffchainWorkerParams, SharedParams,
@@ -30,13 +30,25 @@ use crate::{
 use clap::Parser;
 use regex::Regex;
 use sc_service::{
-	config::{BasePath, PrometheusConfig, RpcBatchRequestConfig, TransactionPoolOptions},
+	config::{
+		BasePath, PrometheusConfig, RpcApiKeysConfig, RpcArchiveConfig, RpcAuditLogConfig,
+		RpcAuthConfig, RpcAuthKeys, RpcBatchRequestConfig, RpcCallCostConfig, RpcChainHeadConfig,
+		RpcCircuitBreakerConfig, RpcHttp2Config, RpcHttpCompressionConfig, RpcIpFilter,
+		RpcIpNetwork, RpcListener, RpcLoadShedConfig, RpcMethodAlias, RpcMethodCors, RpcMethodCost,
+		RpcMethodFilter, RpcMethodPattern, RpcMethodTimeout, RpcMetricsConfig, RpcQuotaConfig,
+		RpcQuotaLimit, RpcResponseCacheConfig, RpcSessionConfig, RpcSocketConfig, RpcSseConfig,
+		RpcSubscriptionBackpressure, RpcTimeoutConfig, RpcTlsCertificate, RpcTlsConfig,
+		RpcTransactionBroadcastConfig, RpcTransactionConfig, RpcWsCompressionConfig,
+		RpcWsPingConfig, TransactionPoolOptions, RPC_DEFAULT_CACHED_METHODS,
+	},
 	ChainSpec, Role,
 };
 use sc_telemetry::TelemetryEndpoints;
 use std::{
 	net::{IpAddr, Ipv4Addr, SocketAddr},
 	num::NonZeroU32,
+	path::PathBuf,
+	time::Duration,
 };
 
 /// The `run` command used to run a node.
@@ -94,6 +106,13 @@ pub struct RunCmd {
 	#[arg(long)]
 	pub rpc_rate_limit: Option<NonZeroU32>,
 
+	/// Exempt peers with the given IP address, or in the given CIDR range such as `10.0.0.0/8`,
+	/// from `--rpc-rate-limit` and `--rpc-max-connections-per-ip`.
+	///
+	/// Can be passed multiple times.
+	#[arg(long, value_name = "IP[/PREFIX]", value_parser = parse_ip_network)]
+	pub rpc_rate_limit_whitelisted_ips: Vec<RpcIpNetwork>,
+
 	/// Set the maximum RPC request payload size for both HTTP and WS in megabytes.
 	#[arg(long, default_value_t = RPC_DEFAULT_MAX_REQUEST_SIZE_MB)]
 	pub rpc_max_request_size: u32,
@@ -114,6 +133,13 @@ pub struct RunCmd {
 	#[arg(long, value_name = "COUNT", default_value_t = RPC_DEFAULT_MAX_CONNECTIONS)]
 	pub rpc_max_connections: u32,
 
+	/// Maximum number of concurrent RPC server connections per peer IP address.
+	///
+	/// Prevents a single peer from using up `--rpc-max-connections`. The peers of
+	/// `--rpc-rate-limit-whitelisted-ips` are exempt.
+	#[arg(long, value_name = "COUNT")]
+	pub rpc_max_connections_per_ip: Option<NonZeroU32>,
+
 	/// The number of messages the RPC server is allowed to keep in memory.
 	///
 	/// If the buffer becomes full then the server will not process
@@ -125,6 +151,17 @@ pub struct RunCmd {
 	#[arg(long, default_value_t = RPC_DEFAULT_MESSAGE_CAPACITY_PER_CONN)]
 	pub rpc_message_buffer_capacity_per_connection: u32,
 
+	/// What to do with the notifications of a subscription whose client is too slow.
+	///
+	/// Formatted as `<METHOD>=<POLICY>`, where `<METHOD>` is the notification method of the
+	/// subscriptions, e.g. `chain_newHead`, and `<POLICY>` is one of `drop-oldest`,
+	/// `drop-newest` or `close`. Can be passed multiple times. The subscriptions without a policy
+	/// are closed once their buffer is full. The throttled notifications are counted by the
+	/// `substrate_rpc_subscription_throttled` metric, and the subscriptions closed by the
+	/// `substrate_rpc_subscription_overflows` metric.
+	#[arg(long, value_name = "METHOD=POLICY")]
+	pub rpc_subscription_backpressure: Vec<RpcSubscriptionBackpressure>,
+
 	/// Disable RPC batch requests
 	#[arg(long, alias = "rpc_no_batch_requests", conflicts_with_all = &["rpc_max_batch_request_len"])]
 	pub rpc_disable_batch_requests: bool,
@@ -133,6 +170,306 @@ pub struct RunCmd {
 	#[arg(long, conflicts_with_all = &["rpc_disable_batch_requests"], value_name = "LEN")]
 	pub rpc_max_batch_request_len: Option<u32>,
 
+	/// Cost the RPC calls of an IP address may add up to per minute.
+	///
+	/// Each call costs `--rpc-default-method-cost` unless its method has a cost set with
+	/// `--rpc-method-cost`. The budget is shared by the connections of the address and refilled
+	/// continuously, the calls exceeding it are rejected, including the remaining calls of a
+	/// batch. The peers of
+	/// `--rpc-rate-limit-whitelisted-ips` are exempt. This is disabled by default.
+	#[arg(long, value_name = "COST")]
+	pub rpc_call_budget: Option<NonZeroU32>,
+
+	/// Cost of the RPC calls of the methods matching a pattern.
+	///
+	/// Formatted as `<PATTERN>=<COST>`, e.g. `state_queryStorageAt=50`. Can be passed multiple
+	/// times, the first matching pattern applies.
+	#[arg(long, value_name = "PATTERN=COST", requires = "rpc_call_budget")]
+	pub rpc_method_cost: Vec<RpcMethodCost>,
+
+	/// Cost of the RPC calls of the methods without a `--rpc-method-cost`.
+	#[arg(long, value_name = "COST", default_value_t = 1, requires = "rpc_call_budget")]
+	pub rpc_default_method_cost: u32,
+
+	/// Quota on the RPC calls and bandwidth of each API key, or IP address for the requests
+	/// without a key.
+	///
+	/// Formatted as `<PERIOD>:calls=<CALLS>,bytes=<BYTES>` where the period is `minute`, `hour`
+	/// or `day`, e.g. `day:calls=100000,bytes=1000000000`. Either limit can be omitted. Can be
+	/// passed multiple times, all the quotas being enforced. The windows are rolling, the calls
+	/// exceeding a quota are rejected until enough of the usage of the last period expired. The
+	/// peers of `--rpc-rate-limit-whitelisted-ips` are exempt. This is disabled by default.
+	#[arg(long, value_name = "PERIOD:LIMITS")]
+	pub rpc_quota: Vec<RpcQuotaLimit>,
+
+	/// File the usage of the `--rpc-quota` is saved to, such that restarts don't reset it.
+	///
+	/// Without it the usage is only kept in memory.
+	#[arg(long, value_name = "PATH", requires = "rpc_quota")]
+	pub rpc_quota_path: Option<PathBuf>,
+
+	/// Drop the expired usage of the `--rpc-quota`, and save it to `--rpc-quota-path`, every
+	/// given number of seconds. The usage is saved when the node stops as well.
+	#[arg(
+		long,
+		value_name = "SECONDS",
+		default_value_t = 60,
+		value_parser = clap::value_parser!(u64).range(1..),
+		requires = "rpc_quota"
+	)]
+	pub rpc_quota_save_interval: u64,
+
+	/// Timeout in seconds of the RPC calls.
+	///
+	/// The calls taking longer are aborted with an error, unless their method has a timeout set
+	/// with `--rpc-method-timeout`. This is disabled by default.
+	#[arg(long, value_name = "SECONDS")]
+	pub rpc_call_timeout: Option<u64>,
+
+	/// Timeout of the RPC calls of the methods matching a pattern.
+	///
+	/// Formatted as `<PATTERN>=<SECONDS>`, e.g. `state_traceBlock=60`. Can be passed multiple
+	/// times, the first matching pattern applies.
+	#[arg(long, value_name = "PATTERN=SECONDS", requires = "rpc_call_timeout")]
+	pub rpc_method_timeout: Vec<RpcMethodTimeout>,
+
+	/// Share of failed RPC calls of a method, between 0 and 1, above which its calls are
+	/// rejected for `--rpc-circuit-breaker-cool-down`.
+	///
+	/// Only the internal errors and the timeouts count as failures. The rejected calls get an
+	/// error telling when to retry. This is disabled by default.
+	#[arg(long, value_name = "RATE")]
+	pub rpc_circuit_breaker_error_rate: Option<f64>,
+
+	/// Mean duration in milliseconds of the RPC calls of a method above which its calls are
+	/// rejected for `--rpc-circuit-breaker-cool-down`.
+	///
+	/// This is disabled by default.
+	#[arg(long, value_name = "MILLISECONDS")]
+	pub rpc_circuit_breaker_latency: Option<u64>,
+
+	/// Number of seconds the calls of a method are measured over by the RPC circuit breaker.
+	#[arg(long, value_name = "SECONDS", default_value_t = 10)]
+	pub rpc_circuit_breaker_window: u64,
+
+	/// Minimum number of calls of a method in a window for the RPC circuit breaker to reject its
+	/// calls.
+	#[arg(long, value_name = "COUNT", default_value_t = 20)]
+	pub rpc_circuit_breaker_min_calls: u32,
+
+	/// Number of seconds the calls of a method are rejected for once the RPC circuit breaker
+	/// tripped.
+	#[arg(long, value_name = "SECONDS", default_value_t = 30)]
+	pub rpc_circuit_breaker_cool_down: u64,
+
+	/// Alias of an RPC method, e.g. for legacy clients calling it by an old name.
+	///
+	/// Formatted as `<ALIAS>=<METHOD>`, e.g. `chain_getHead=chain_getBlockHash`. Can be passed
+	/// multiple times. The aliases are listed by `rpc_methods`.
+	#[arg(long, value_name = "ALIAS=METHOD")]
+	pub rpc_method_alias: Vec<RpcMethodAlias>,
+
+	/// Write a structured JSON access log record of every RPC call to the `rpc::access` log
+	/// target.
+	///
+	/// The records carry the trace id of the call, which is taken from the `traceparent` header
+	/// of the HTTP requests if any, and is attached to the tracing span the call is served in.
+	#[arg(long)]
+	pub rpc_access_log: bool,
+
+	/// Log the RPC calls taking longer than the given number of milliseconds.
+	///
+	/// The slow calls are logged at warn level with their method, a digest of their parameters
+	/// and the address of the caller, and are counted by the `substrate_rpc_slow_calls_total`
+	/// metric. This is disabled by default.
+	#[arg(long, value_name = "MILLISECONDS")]
+	pub rpc_slow_call_threshold: Option<u64>,
+
+	/// Maximum number of blocks pinned by the `chainHead_follow` subscriptions, across all
+	/// connections.
+	///
+	/// Defaults to 512. Should not exceed the 2048 blocks kept by the pinned blocks cache of the
+	/// database.
+	#[arg(long, value_name = "COUNT")]
+	pub rpc_chain_head_max_pinned_blocks: Option<usize>,
+
+	/// Maximum number of seconds a block may stay pinned by a `chainHead_follow` subscription
+	/// before the subscription is stopped.
+	///
+	/// Defaults to 60 seconds.
+	#[arg(long, value_name = "SECONDS")]
+	pub rpc_chain_head_max_pinned_duration: Option<u64>,
+
+	/// Maximum number of `chainHead_follow` subscriptions per connection.
+	///
+	/// Defaults to 4.
+	#[arg(long, value_name = "COUNT")]
+	pub rpc_chain_head_max_follow_subscriptions: Option<usize>,
+
+	/// Maximum number of ongoing operations per `chainHead_follow` subscription.
+	///
+	/// Defaults to 16, the lower limit imposed by the specification.
+	#[arg(long, value_name = "COUNT", value_parser = clap::value_parser!(u64).range(16..))]
+	pub rpc_chain_head_max_ongoing_operations: Option<u64>,
+
+	/// Maximum number of items reported by `chainHead_storage` before pagination is required.
+	///
+	/// Defaults to 5.
+	#[arg(long, value_name = "COUNT")]
+	pub rpc_chain_head_max_storage_items: Option<usize>,
+
+	/// Maximum number of milliseconds the `chainHead_call` and `chainHead_storage` operations
+	/// wait for the ongoing operations of their subscription when the limit of ongoing operations
+	/// is reached.
+	///
+	/// The waiting operations are started by decreasing priority, as requested by their optional
+	/// `priority` parameter. Defaults to 0, rejecting the operations right away as required by
+	/// the specification.
+	#[arg(long, value_name = "MILLISECONDS")]
+	pub rpc_chain_head_max_operation_queue_duration: Option<u64>,
+
+	/// Maximum number of `chainHead_follow` subscriptions which can be resumed with a resumption
+	/// token after their connection is lost, across all connections.
+	///
+	/// The tokens of the least recently used subscriptions are evicted once the limit is reached.
+	/// Defaults to 64. Set to 0 to disable the resumption tokens.
+	#[arg(long, value_name = "COUNT")]
+	pub rpc_chain_head_max_resumable_subscriptions: Option<usize>,
+
+	/// Maximum number of seconds a resumable `chainHead_follow` subscription is kept alive after
+	/// its connection is lost, waiting to be resumed.
+	///
+	/// Defaults to 30 seconds.
+	#[arg(long, value_name = "SECONDS")]
+	pub rpc_chain_head_resumption_grace_period: Option<u64>,
+
+	/// Maximum number of `transactionWatch` subscriptions across all connections.
+	///
+	/// Defaults to 4096.
+	#[arg(long, value_name = "COUNT")]
+	pub rpc_transaction_max_subscriptions: Option<usize>,
+
+	/// Maximum number of extrinsics submitted by a `transactionWatch_submitAndWatchBatch`
+	/// subscription.
+	///
+	/// Defaults to 128.
+	#[arg(long, value_name = "COUNT")]
+	pub rpc_transaction_max_batch_size: Option<usize>,
+
+	/// Maximum number of `transaction_broadcast` operations running at a time.
+	///
+	/// The server reports being overloaded for the operations over the limit. Defaults to 1024.
+	#[arg(long, value_name = "COUNT")]
+	pub rpc_transaction_broadcast_max_operations: Option<usize>,
+
+	/// Maximum number of items queried by an `archive_storage` call.
+	///
+	/// Defaults to 8.
+	#[arg(long, value_name = "COUNT")]
+	pub rpc_archive_max_queried_items: Option<usize>,
+
+	/// Maximum number of items reported by `archive_storage` for a descendant query before
+	/// pagination is required.
+	///
+	/// Defaults to 5.
+	#[arg(long, value_name = "COUNT")]
+	pub rpc_archive_max_descendant_responses: Option<usize>,
+
+	/// Persist up to the given number of pending `transaction_broadcast` operations in the
+	/// database.
+	///
+	/// The persisted operations are resubmitted to the transaction pool when the node restarts,
+	/// instead of being silently dropped. This is disabled by default.
+	#[arg(long, value_name = "COUNT")]
+	pub rpc_max_persisted_broadcasts: Option<u32>,
+
+	/// Keep the `chainHead_follow` and `transactionWatch_submitAndWatch` subscriptions of the
+	/// connections with a session alive after a disconnection, for up to the given number of
+	/// sessions.
+	///
+	/// The clients start a session with `session_unstable_start` and resume it from another
+	/// connection with `session_unstable_resume`. This is disabled by default.
+	#[arg(long, value_name = "COUNT")]
+	pub rpc_max_sessions: Option<usize>,
+
+	/// Maximum number of seconds a session stays detached from any connection before its
+	/// subscriptions are stopped.
+	///
+	/// Defaults to 60 seconds.
+	#[arg(long, value_name = "SECONDS")]
+	pub rpc_session_max_detached_duration: Option<u64>,
+
+	/// Maximum number of events kept for each subscription of a detached session.
+	///
+	/// The subscriptions missing more events are stopped. Defaults to 512.
+	#[arg(long, value_name = "COUNT")]
+	pub rpc_session_max_missed_events: Option<usize>,
+
+	/// Report the calls of the RPC methods matching the given pattern as deprecated.
+	///
+	/// The calls are counted by the `substrate_rpc_deprecated_method_calls_total` metric. The
+	/// object results and the error data of the responses to them carry a `deprecationWarning`
+	/// member, and the HTTP responses a `Warning` header. Can be passed multiple times, e.g.
+	/// `--rpc-deprecated-method 'state_*'`.
+	#[arg(long, value_name = "PATTERN")]
+	pub rpc_deprecated_method: Vec<RpcMethodPattern>,
+
+	/// Comma separated upper bounds in microseconds of the buckets of the
+	/// `substrate_rpc_calls_time` histogram.
+	#[arg(long, value_name = "MICROSECONDS", value_delimiter = ',')]
+	pub rpc_metrics_buckets: Option<Vec<f64>>,
+
+	/// Number of calls below which the calls of an RPC method are labelled `other` rather than
+	/// with the method in the metrics.
+	///
+	/// Bounds the number of metrics on the nodes serving many rarely called methods. By default
+	/// every method is labelled on its own.
+	#[arg(long, value_name = "COUNT", default_value_t = 0)]
+	pub rpc_metrics_method_min_calls: u64,
+
+	/// Do not record the metrics of the RPC calls over WebSocket.
+	#[arg(long)]
+	pub no_rpc_ws_metrics: bool,
+
+	/// Do not record the metrics of the RPC calls over HTTP, including Server-Sent Events.
+	#[arg(long)]
+	pub no_rpc_http_metrics: bool,
+
+	/// Serve the RPC subscriptions over Server-Sent Events, for the clients unable to hold a
+	/// WebSocket.
+	///
+	/// A subscription call `POST`ed to `/sse` is answered with a `text/event-stream` of its
+	/// response and notifications. The subscription is closed along with the stream.
+	#[arg(long)]
+	pub rpc_sse: bool,
+
+	/// Number of messages buffered for a Server-Sent Events stream whose client doesn't keep up
+	/// with the notifications.
+	#[arg(long, value_name = "COUNT", default_value_t = 64, requires = "rpc_sse")]
+	pub rpc_sse_buffer_capacity: usize,
+
+	/// Set `TCP_NODELAY` on the RPC connections, sending the responses without waiting to fill
+	/// a TCP segment.
+	#[arg(long)]
+	pub rpc_tcp_nodelay: bool,
+
+	/// Interval in seconds of the TCP keepalive probes sent on idle RPC connections.
+	///
+	/// The probes are disabled by default.
+	#[arg(long, value_name = "SECONDS")]
+	pub rpc_tcp_keepalive: Option<u64>,
+
+	/// Set `SO_REUSEPORT` on the RPC listeners, allowing several node processes to listen on
+	/// the same port, the kernel spreading the connections between them.
+	///
+	/// Only supported on Unix.
+	#[arg(long)]
+	pub rpc_reuse_port: bool,
+
+	/// Maximum number of RPC connections waiting to be accepted.
+	#[arg(long, value_name = "COUNT", default_value_t = 1024)]
+	pub rpc_listen_backlog: u32,
+
 	/// Specify browser *origins* allowed to access the HTTP & WS RPC servers.
 	///
 	/// A comma-separated list of origins (protocol://domain or special `null`
@@ -142,6 +479,227 @@ pub struct RunCmd {
 	#[arg(long, value_name = "ORIGINS")]
 	pub rpc_cors: Option<Cors>,
 
+	/// Specify browser *origins* allowed to call the RPC methods matching a pattern.
+	///
+	/// Formatted as `<PATTERN>=<ORIGINS>`, where `ORIGINS` is a comma-separated list of origins
+	/// or `*` to allow any origin, e.g. `author=https://dashboard.example.com`. The origins
+	/// replace the ones of `--rpc-cors` for the matching methods. Can be passed multiple times,
+	/// the first matching rule applies.
+	#[arg(long, value_name = "PATTERN=ORIGINS")]
+	pub rpc_method_cors: Vec<RpcMethodCors>,
+
+	/// Path to a PEM-encoded certificate chain to serve the RPC server over TLS.
+	///
+	/// When set, the RPC server only accepts `https://` and `wss://` connections.
+	#[arg(long, value_name = "PATH", requires = "rpc_tls_key")]
+	pub rpc_tls_cert: Option<PathBuf>,
+
+	/// Path to the PEM-encoded private key of `--rpc-tls-cert`.
+	#[arg(long, value_name = "PATH", requires = "rpc_tls_cert")]
+	pub rpc_tls_key: Option<PathBuf>,
+
+	/// Additional TLS certificate selected by the server name (SNI) requested by the client.
+	///
+	/// Expected format is `<SERVER_NAME>=<CERT_PATH>,<KEY_PATH>`, where the server name may
+	/// start with `*.` to match any subdomain. Can be passed multiple times.
+	/// Clients requesting an unknown server name are served `--rpc-tls-cert`.
+	#[arg(long, value_name = "SNI_CERT", requires = "rpc_tls_cert", value_parser = parse_tls_sni_cert)]
+	pub rpc_tls_sni_cert: Vec<RpcTlsCertificate>,
+
+	/// Check the TLS certificate files for changes every given number of seconds
+	/// and reload them without restarting the node.
+	///
+	/// This is disabled by default.
+	#[arg(long, value_name = "SECONDS", requires = "rpc_tls_cert")]
+	pub rpc_tls_reload_interval: Option<u64>,
+
+	/// Only accept RPC connections from the given IP address or CIDR range.
+	///
+	/// Can be passed multiple times, e.g. `--rpc-allow-ip 10.0.0.0/8 --rpc-allow-ip ::1`.
+	/// By default connections from any address are accepted.
+	#[arg(long, value_name = "IP[/PREFIX]", value_parser = parse_ip_network)]
+	pub rpc_allow_ip: Vec<RpcIpNetwork>,
+
+	/// Reject RPC connections from the given IP address or CIDR range.
+	///
+	/// Can be passed multiple times and takes precedence over `--rpc-allow-ip`.
+	#[arg(long, value_name = "IP[/PREFIX]", value_parser = parse_ip_network)]
+	pub rpc_deny_ip: Vec<RpcIpNetwork>,
+
+	/// Trust the `X-Forwarded-For` and `X-Real-IP` headers of the RPC requests relayed by the
+	/// proxies with the given IP address or CIDR range.
+	///
+	/// The address of the client reported by the headers is then used for the rate limit, e.g.
+	/// to exempt the addresses of `--rpc-rate-limit-whitelisted-ips`. Can be passed multiple
+	/// times. By default the headers are ignored.
+	#[arg(long, value_name = "IP[/PREFIX]", value_parser = parse_ip_network)]
+	pub rpc_trusted_proxy: Vec<RpcIpNetwork>,
+
+	/// Only expose the RPC methods matching the given pattern.
+	///
+	/// `*` matches any sequence of characters and a pattern without `_` or `*` matches a whole
+	/// namespace, e.g. `--rpc-allow-method chainHead_* --rpc-allow-method chainSpec`.
+	/// Can be passed multiple times. By default all methods are exposed.
+	#[arg(long, value_name = "PATTERN")]
+	pub rpc_allow_method: Vec<RpcMethodPattern>,
+
+	/// Don't expose the RPC methods matching the given pattern.
+	///
+	/// Can be passed multiple times and takes precedence over `--rpc-allow-method`.
+	#[arg(long, value_name = "PATTERN")]
+	pub rpc_deny_method: Vec<RpcMethodPattern>,
+
+	/// Listen to RPC requests on an additional address, with its own settings.
+	///
+	/// Formatted as a comma-separated list of `<KEY>=<VALUE>` settings, e.g.
+	/// `addr=0.0.0.0:9945,methods=safe,max-connections=1000`. The supported settings are
+	/// `addr` (required), `methods`, `cors`, `max-connections`, `max-connections-per-ip`,
+	/// `rate-limit`, `allow-method` and `deny-method`, of which `cors`, `allow-method` and
+	/// `deny-method` can be given multiple times. Settings which aren't given default to the ones
+	/// of the main listener, except `methods` which defaults to `auto`.
+	/// Can be passed multiple times.
+	#[arg(long, value_name = "SETTINGS")]
+	pub rpc_listener: Vec<RpcListenerSpec>,
+
+	/// Enable WebSocket `permessage-deflate` compression for clients supporting it.
+	#[arg(long)]
+	pub rpc_ws_compression: bool,
+
+	/// WebSocket messages smaller than this number of bytes are sent uncompressed.
+	#[arg(long, value_name = "BYTES", default_value_t = 1024, requires = "rpc_ws_compression")]
+	pub rpc_ws_compression_min_size: usize,
+
+	/// Enable gzip and brotli compression of the HTTP responses, as negotiated with the
+	/// `Accept-Encoding` header of the requests.
+	#[arg(long)]
+	pub rpc_http_compression: bool,
+
+	/// HTTP responses smaller than this number of bytes are sent uncompressed.
+	#[arg(long, value_name = "BYTES", default_value_t = 1024, requires = "rpc_http_compression")]
+	pub rpc_http_compression_min_size: u16,
+
+	/// Send a WebSocket ping to RPC clients every given number of seconds.
+	#[arg(long, value_name = "SECONDS", default_value_t = 30)]
+	pub rpc_ws_ping_interval: u64,
+
+	/// Regard RPC clients which didn't answer a WebSocket ping within the given number of
+	/// seconds as inactive.
+	#[arg(long, value_name = "SECONDS", default_value_t = 60)]
+	pub rpc_ws_inactive_limit: u64,
+
+	/// Close WebSocket connections of RPC clients after they were inactive the given number
+	/// of times.
+	#[arg(long, value_name = "COUNT", default_value = "3")]
+	pub rpc_ws_max_ping_failures: NonZeroU32,
+
+	/// Cache the responses of idempotent RPC methods.
+	///
+	/// Cached responses are dropped whenever the best or the finalized block changes.
+	#[arg(long)]
+	pub rpc_cache: bool,
+
+	/// Drop cached RPC responses after the given number of seconds.
+	#[arg(long, value_name = "SECONDS", default_value_t = 60, requires = "rpc_cache")]
+	pub rpc_cache_ttl: u64,
+
+	/// Maximum number of cached RPC responses.
+	#[arg(long, value_name = "COUNT", default_value_t = 1024, requires = "rpc_cache")]
+	pub rpc_cache_max_entries: usize,
+
+	/// Cache the responses of the given RPC method.
+	///
+	/// Can be passed multiple times and replaces the default methods, which are
+	/// `chain_getBlockHash`, `state_getMetadata`, `state_getRuntimeVersion` and the
+	/// `system_*` methods describing the node and the chain.
+	#[arg(long, value_name = "METHOD", requires = "rpc_cache")]
+	pub rpc_cache_method: Vec<String>,
+
+	/// Reject RPC calls with a "server is busy" error while the given number of calls are in
+	/// flight.
+	#[arg(long, value_name = "COUNT")]
+	pub rpc_max_in_flight: Option<usize>,
+
+	/// Reject RPC calls with a "server is busy" error while tasks wait longer than the given
+	/// number of milliseconds to be run.
+	#[arg(long, value_name = "MILLISECONDS")]
+	pub rpc_max_queue_latency: Option<u64>,
+
+	/// Serve the RPC methods matching the given pattern even while the server is busy.
+	///
+	/// Can be passed multiple times. `system_health` and `chainHead_unfollow` are always
+	/// served.
+	#[arg(long, value_name = "PATTERN")]
+	pub rpc_priority_method: Vec<RpcMethodPattern>,
+
+	/// Require RPC requests to carry a JWT bearer token signed with the secret in the given file.
+	///
+	/// Tokens must be signed with `HS256`, `HS384` or `HS512`. Trailing whitespace of the
+	/// file is ignored.
+	#[arg(long, value_name = "PATH", conflicts_with = "rpc_auth_jwks_url")]
+	pub rpc_auth_secret_file: Option<PathBuf>,
+
+	/// Require RPC requests to carry a JWT bearer token signed with a key of the
+	/// JSON Web Key Set served at the given URL.
+	///
+	/// The URL must be `https`, such that the key set can't be replaced on the network path.
+	#[arg(long, value_name = "URL")]
+	pub rpc_auth_jwks_url: Option<String>,
+
+	/// Fetch the key set of `--rpc-auth-jwks-url` again every given number of seconds.
+	#[arg(long, value_name = "SECONDS", default_value_t = 300, requires = "rpc_auth_jwks_url")]
+	pub rpc_auth_jwks_refresh_interval: u64,
+
+	/// Only accept RPC bearer tokens issued for the given audience (`aud` claim).
+	#[arg(long, value_name = "AUDIENCE")]
+	pub rpc_auth_audience: Option<String>,
+
+	/// Only accept RPC bearer tokens issued by the given issuer (`iss` claim).
+	#[arg(long, value_name = "ISSUER")]
+	pub rpc_auth_issuer: Option<String>,
+
+	/// Serve the RPC server over HTTP/2 in addition to HTTP/1.1.
+	///
+	/// Cleartext HTTP/2 requires clients to use prior knowledge (h2c), over TLS it is
+	/// negotiated via ALPN. WebSocket connections keep using HTTP/1.1.
+	#[arg(long)]
+	pub rpc_http2: bool,
+
+	/// Maximum number of concurrent HTTP/2 streams, i.e. in-flight calls, per RPC connection.
+	#[arg(long, value_name = "COUNT", default_value_t = 100, requires = "rpc_http2")]
+	pub rpc_http2_max_concurrent_streams: u32,
+
+	/// Write a JSON line for each sampled RPC call to the given file.
+	///
+	/// Lines contain the method name, the size of the params and of the response, the
+	/// latency and the IP address of the caller.
+	#[arg(long, value_name = "PATH")]
+	pub rpc_audit_log: Option<PathBuf>,
+
+	/// Fraction of the RPC calls written to `--rpc-audit-log`, between 0 and 1.
+	#[arg(long, value_name = "RATE", default_value_t = 1.0, requires = "rpc_audit_log")]
+	pub rpc_audit_log_sample_rate: f64,
+
+	/// Rotate `--rpc-audit-log` once it exceeds the given number of megabytes.
+	#[arg(long, value_name = "MB", default_value_t = 100, requires = "rpc_audit_log")]
+	pub rpc_audit_log_max_size: u64,
+
+	/// Number of rotated `--rpc-audit-log` files to keep.
+	#[arg(long, value_name = "COUNT", default_value_t = 5, requires = "rpc_audit_log")]
+	pub rpc_audit_log_max_files: u32,
+
+	/// Grant the RPC requests carrying an `X-Api-Key` header the limits of the key's tier.
+	///
+	/// The given JSON file maps tier names to their limits and keys to their tier, e.g.
+	/// `{"tiers": {"pro": {"rateLimit": 6000, "maxSubscriptions": 4096}}, "keys": {"<key>":
+	/// "pro"}}`. A tier without `rateLimit` isn't rate limited. Requests without a key are
+	/// subject to `--rpc-rate-limit`, requests with an unknown key are rejected.
+	#[arg(long, value_name = "PATH")]
+	pub rpc_api_keys: Option<PathBuf>,
+
+	/// Check `--rpc-api-keys` for changes every given number of seconds.
+	#[arg(long, value_name = "SECONDS", default_value_t = 10, requires = "rpc_api_keys")]
+	pub rpc_api_keys_reload_interval: u64,
+
 	/// The human-readable name for this node.
 	///
 	/// It's used as network node name.
@@ -371,6 +929,10 @@ impl CliConfiguration for RunCmd {
 		Ok(self.rpc_max_connections)
 	}
 
+	fn rpc_method_cors(&self) -> Result<Vec<RpcMethodCors>> {
+		Ok(self.rpc_method_cors.clone())
+	}
+
 	fn rpc_cors(&self, is_dev: bool) -> Result<Option<Vec<String>>> {
 		Ok(self
 			.rpc_cors
@@ -423,6 +985,10 @@ impl CliConfiguration for RunCmd {
 		Ok(self.rpc_message_buffer_capacity_per_connection)
 	}
 
+	fn rpc_subscription_backpressure(&self) -> Result<Vec<RpcSubscriptionBackpressure>> {
+		Ok(self.rpc_subscription_backpressure.clone())
+	}
+
 	fn rpc_batch_config(&self) -> Result<RpcBatchRequestConfig> {
 		let cfg = if self.rpc_disable_batch_requests {
 			RpcBatchRequestConfig::Disabled
@@ -439,6 +1005,362 @@ impl CliConfiguration for RunCmd {
 		Ok(self.rpc_rate_limit)
 	}
 
+	fn rpc_max_connections_per_ip(&self) -> Result<Option<NonZeroU32>> {
+		Ok(self.rpc_max_connections_per_ip)
+	}
+
+	fn rpc_rate_limit_whitelisted_ips(&self) -> Result<Vec<RpcIpNetwork>> {
+		Ok(self.rpc_rate_limit_whitelisted_ips.clone())
+	}
+
+	fn rpc_tls(&self) -> Result<Option<RpcTlsConfig>> {
+		let (Some(cert_path), Some(key_path)) = (&self.rpc_tls_cert, &self.rpc_tls_key) else {
+			return Ok(None)
+		};
+
+		let default = RpcTlsCertificate {
+			cert_path: cert_path.clone(),
+			key_path: key_path.clone(),
+			server_names: Vec::new(),
+		};
+
+		Ok(Some(RpcTlsConfig {
+			certificates: std::iter::once(default).chain(self.rpc_tls_sni_cert.clone()).collect(),
+			reload_interval: self.rpc_tls_reload_interval.map(Duration::from_secs),
+		}))
+	}
+
+	fn rpc_ip_filter(&self) -> Result<Option<RpcIpFilter>> {
+		if self.rpc_allow_ip.is_empty() && self.rpc_deny_ip.is_empty() {
+			return Ok(None)
+		}
+
+		Ok(Some(RpcIpFilter { allow: self.rpc_allow_ip.clone(), deny: self.rpc_deny_ip.clone() }))
+	}
+
+	fn rpc_trusted_proxies(&self) -> Result<Vec<RpcIpNetwork>> {
+		Ok(self.rpc_trusted_proxy.clone())
+	}
+
+	fn rpc_api_keys(&self) -> Result<Option<RpcApiKeysConfig>> {
+		Ok(self.rpc_api_keys.clone().map(|path| RpcApiKeysConfig {
+			path,
+			reload_interval: Duration::from_secs(self.rpc_api_keys_reload_interval),
+		}))
+	}
+
+	fn rpc_call_cost(&self) -> Result<Option<RpcCallCostConfig>> {
+		Ok(self.rpc_call_budget.map(|budget_per_minute| RpcCallCostConfig {
+			method_costs: self.rpc_method_cost.clone(),
+			default_cost: self.rpc_default_method_cost,
+			budget_per_minute,
+		}))
+	}
+
+	fn rpc_quota(&self) -> Result<Option<RpcQuotaConfig>> {
+		if self.rpc_quota.is_empty() {
+			return Ok(None)
+		}
+
+		Ok(Some(RpcQuotaConfig {
+			limits: self.rpc_quota.clone(),
+			path: self.rpc_quota_path.clone(),
+			save_interval: Duration::from_secs(self.rpc_quota_save_interval),
+		}))
+	}
+
+	fn rpc_timeout(&self) -> Result<Option<RpcTimeoutConfig>> {
+		Ok(self.rpc_call_timeout.map(|secs| RpcTimeoutConfig {
+			method_timeouts: self.rpc_method_timeout.clone(),
+			default_timeout: Duration::from_secs(secs),
+		}))
+	}
+
+	fn rpc_circuit_breaker(&self) -> Result<Option<RpcCircuitBreakerConfig>> {
+		if self.rpc_circuit_breaker_error_rate.is_none() &&
+			self.rpc_circuit_breaker_latency.is_none()
+		{
+			return Ok(None)
+		}
+
+		Ok(Some(RpcCircuitBreakerConfig {
+			max_error_rate: self.rpc_circuit_breaker_error_rate,
+			max_latency: self.rpc_circuit_breaker_latency.map(Duration::from_millis),
+			window: Duration::from_secs(self.rpc_circuit_breaker_window),
+			min_calls: self.rpc_circuit_breaker_min_calls,
+			cool_down: Duration::from_secs(self.rpc_circuit_breaker_cool_down),
+		}))
+	}
+
+	fn rpc_method_aliases(&self) -> Result<Vec<RpcMethodAlias>> {
+		Ok(self.rpc_method_alias.clone())
+	}
+
+	fn rpc_metrics(&self) -> Result<RpcMetricsConfig> {
+		let default = RpcMetricsConfig::default();
+		Ok(RpcMetricsConfig {
+			call_time_buckets: self
+				.rpc_metrics_buckets
+				.clone()
+				.unwrap_or(default.call_time_buckets),
+			method_min_calls: self.rpc_metrics_method_min_calls,
+			ws_calls: !self.no_rpc_ws_metrics,
+			http_calls: !self.no_rpc_http_metrics,
+		})
+	}
+
+	fn rpc_access_log(&self) -> Result<bool> {
+		Ok(self.rpc_access_log)
+	}
+
+	fn rpc_slow_call_threshold(&self) -> Result<Option<Duration>> {
+		Ok(self.rpc_slow_call_threshold.map(Duration::from_millis))
+	}
+
+	fn rpc_deprecated_methods(&self) -> Result<Vec<RpcMethodPattern>> {
+		Ok(self.rpc_deprecated_method.clone())
+	}
+
+	fn rpc_chain_head(&self) -> Result<RpcChainHeadConfig> {
+		let mut config = RpcChainHeadConfig::default();
+		if let Some(max) = self.rpc_chain_head_max_pinned_blocks {
+			config.global_max_pinned_blocks = max;
+		}
+		if let Some(secs) = self.rpc_chain_head_max_pinned_duration {
+			config.subscription_max_pinned_duration = Duration::from_secs(secs);
+		}
+		if let Some(max) = self.rpc_chain_head_max_follow_subscriptions {
+			config.max_follow_subscriptions_per_connection = max;
+		}
+		if let Some(max) = self.rpc_chain_head_max_ongoing_operations {
+			config.subscription_max_ongoing_operations = max as usize;
+		}
+		if let Some(max) = self.rpc_chain_head_max_storage_items {
+			config.operation_max_storage_items = max;
+		}
+		if let Some(millis) = self.rpc_chain_head_max_operation_queue_duration {
+			config.operation_max_queue_duration = Duration::from_millis(millis);
+		}
+		if let Some(max) = self.rpc_chain_head_max_resumable_subscriptions {
+			config.max_resumable_subscriptions = max;
+		}
+		if let Some(secs) = self.rpc_chain_head_resumption_grace_period {
+			config.resumable_subscription_grace_period = Duration::from_secs(secs);
+		}
+		Ok(config)
+	}
+
+	fn rpc_transaction(&self) -> Result<RpcTransactionConfig> {
+		let mut config = RpcTransactionConfig::default();
+		if let Some(max) = self.rpc_transaction_max_subscriptions {
+			config.max_subscriptions = max;
+		}
+		if let Some(max) = self.rpc_transaction_max_batch_size {
+			config.max_batch_size = max;
+		}
+		Ok(config)
+	}
+
+	fn rpc_transaction_broadcast(&self) -> Result<RpcTransactionBroadcastConfig> {
+		let mut config = RpcTransactionBroadcastConfig::default();
+		if let Some(max) = self.rpc_transaction_broadcast_max_operations {
+			config.max_operations = max;
+		}
+		Ok(config)
+	}
+
+	fn rpc_archive(&self) -> Result<RpcArchiveConfig> {
+		let mut config = RpcArchiveConfig::default();
+		if let Some(max) = self.rpc_archive_max_queried_items {
+			config.max_queried_items = max;
+		}
+		if let Some(max) = self.rpc_archive_max_descendant_responses {
+			config.max_descendant_responses = max;
+		}
+		Ok(config)
+	}
+
+	fn rpc_max_persisted_broadcasts(&self) -> Result<Option<u32>> {
+		Ok(self.rpc_max_persisted_broadcasts)
+	}
+
+	fn rpc_sessions(&self) -> Result<Option<RpcSessionConfig>> {
+		let Some(max_sessions) = self.rpc_max_sessions else { return Ok(None) };
+
+		let mut config = RpcSessionConfig { max_sessions, ..Default::default() };
+		if let Some(secs) = self.rpc_session_max_detached_duration {
+			config.max_detached_duration = Duration::from_secs(secs);
+		}
+		if let Some(max) = self.rpc_session_max_missed_events {
+			config.max_missed_events = max;
+		}
+		Ok(Some(config))
+	}
+
+	fn rpc_socket(&self) -> Result<RpcSocketConfig> {
+		Ok(RpcSocketConfig {
+			nodelay: self.rpc_tcp_nodelay,
+			keepalive: self.rpc_tcp_keepalive.map(Duration::from_secs),
+			reuse_port: self.rpc_reuse_port,
+			backlog: self.rpc_listen_backlog,
+		})
+	}
+
+	fn rpc_sse(&self) -> Result<Option<RpcSseConfig>> {
+		Ok(self
+			.rpc_sse
+			.then(|| RpcSseConfig { buffer_capacity: self.rpc_sse_buffer_capacity }))
+	}
+
+	fn rpc_method_filter(&self) -> Result<Option<RpcMethodFilter>> {
+		if self.rpc_allow_method.is_empty() && self.rpc_deny_method.is_empty() {
+			return Ok(None)
+		}
+
+		Ok(Some(RpcMethodFilter {
+			allow: self.rpc_allow_method.clone(),
+			deny: self.rpc_deny_method.clone(),
+		}))
+	}
+
+	fn rpc_listeners(&self, is_dev: bool) -> Result<Vec<RpcListener>> {
+		let cors = self.rpc_cors(is_dev)?;
+		let method_filter = self.rpc_method_filter()?;
+
+		Ok(self
+			.rpc_listener
+			.iter()
+			.map(|spec| RpcListener {
+				addr: spec.addr,
+				methods: spec.methods.unwrap_or(RpcMethods::Auto).into(),
+				cors: spec.cors.clone().map_or_else(|| cors.clone(), Into::into),
+				method_cors: self.rpc_method_cors.clone(),
+				max_connections: spec.max_connections.unwrap_or(self.rpc_max_connections),
+				max_connections_per_ip: spec
+					.max_connections_per_ip
+					.or(self.rpc_max_connections_per_ip),
+				rate_limit: spec.rate_limit.or(self.rpc_rate_limit),
+				rate_limit_whitelisted_ips: self.rpc_rate_limit_whitelisted_ips.clone(),
+				method_filter: if spec.allow_methods.is_empty() && spec.deny_methods.is_empty() {
+					method_filter.clone()
+				} else {
+					Some(RpcMethodFilter {
+						allow: spec.allow_methods.clone(),
+						deny: spec.deny_methods.clone(),
+					})
+				},
+			})
+			.collect())
+	}
+
+	fn rpc_ws_compression(&self) -> Result<Option<RpcWsCompressionConfig>> {
+		Ok(self
+			.rpc_ws_compression
+			.then(|| RpcWsCompressionConfig { min_size: self.rpc_ws_compression_min_size }))
+	}
+
+	fn rpc_http_compression(&self) -> Result<Option<RpcHttpCompressionConfig>> {
+		Ok(self
+			.rpc_http_compression
+			.then(|| RpcHttpCompressionConfig { min_size: self.rpc_http_compression_min_size }))
+	}
+
+	fn rpc_ws_ping(&self) -> Result<RpcWsPingConfig> {
+		Ok(RpcWsPingConfig {
+			interval: Duration::from_secs(self.rpc_ws_ping_interval),
+			inactive_limit: Duration::from_secs(self.rpc_ws_inactive_limit),
+			max_failures: self.rpc_ws_max_ping_failures,
+		})
+	}
+
+	fn rpc_response_cache(&self) -> Result<Option<RpcResponseCacheConfig>> {
+		if !self.rpc_cache {
+			return Ok(None)
+		}
+
+		let methods = if self.rpc_cache_method.is_empty() {
+			RPC_DEFAULT_CACHED_METHODS.iter().map(|method| method.to_string()).collect()
+		} else {
+			self.rpc_cache_method.clone()
+		};
+
+		Ok(Some(RpcResponseCacheConfig {
+			methods,
+			ttl: Duration::from_secs(self.rpc_cache_ttl),
+			max_entries: self.rpc_cache_max_entries,
+		}))
+	}
+
+	fn rpc_load_shed(&self) -> Result<Option<RpcLoadShedConfig>> {
+		if self.rpc_max_in_flight.is_none() && self.rpc_max_queue_latency.is_none() {
+			return Ok(None)
+		}
+
+		Ok(Some(RpcLoadShedConfig {
+			max_in_flight: self.rpc_max_in_flight,
+			max_queue_latency: self.rpc_max_queue_latency.map(Duration::from_millis),
+			priority_methods: self.rpc_priority_method.clone(),
+		}))
+	}
+
+	fn rpc_auth(&self) -> Result<Option<RpcAuthConfig>> {
+		let keys = match (&self.rpc_auth_secret_file, &self.rpc_auth_jwks_url) {
+			(Some(path), _) => {
+				let secret = std::fs::read(path)?;
+				let len = secret.iter().rposition(|b| !b.is_ascii_whitespace()).map_or(0, |i| i + 1);
+				if len == 0 {
+					return Err(Error::Input(format!("RPC auth secret file {path:?} is empty")))
+				}
+				RpcAuthKeys::Secret(secret[..len].to_vec())
+			},
+			(None, Some(url)) if !url.starts_with("https://") =>
+				return Err(Error::Input(format!(
+					"`--rpc-auth-jwks-url` must be an `https` URL, got `{url}`"
+				))),
+			(None, Some(url)) => RpcAuthKeys::Jwks {
+				url: url.clone(),
+				refresh_interval: Duration::from_secs(self.rpc_auth_jwks_refresh_interval),
+			},
+			(None, None) if self.rpc_auth_audience.is_some() || self.rpc_auth_issuer.is_some() =>
+				return Err(Error::Input(
+					"`--rpc-auth-audience` and `--rpc-auth-issuer` require `--rpc-auth-secret-file` \
+					or `--rpc-auth-jwks-url`"
+						.into(),
+				)),
+			(None, None) => return Ok(None),
+		};
+
+		Ok(Some(RpcAuthConfig {
+			keys,
+			audience: self.rpc_auth_audience.clone(),
+			issuer: self.rpc_auth_issuer.clone(),
+		}))
+	}
+
+	fn rpc_http2(&self) -> Result<Option<RpcHttp2Config>> {
+		Ok(self.rpc_http2.then(|| RpcHttp2Config {
+			max_concurrent_streams: self.rpc_http2_max_concurrent_streams,
+		}))
+	}
+
+	fn rpc_audit_log(&self) -> Result<Option<RpcAuditLogConfig>> {
+		let Some(path) = &self.rpc_audit_log else { return Ok(None) };
+
+		if !(0.0..=1.0).contains(&self.rpc_audit_log_sample_rate) {
+			return Err(Error::Input(format!(
+				"`--rpc-audit-log-sample-rate` must be between 0 and 1, got {}",
+				self.rpc_audit_log_sample_rate,
+			)))
+		}
+
+		Ok(Some(RpcAuditLogConfig {
+			path: path.clone(),
+			sample_rate: self.rpc_audit_log_sample_rate,
+			max_file_size: self.rpc_audit_log_max_size.saturating_mul(1024 * 1024),
+			max_files: self.rpc_audit_log_max_files,
+		}))
+	}
+
 	fn transaction_pool(&self, is_dev: bool) -> Result<TransactionPoolOptions> {
 		Ok(self.pool_config.transaction_pool(is_dev))
 	}
@@ -492,6 +1414,37 @@ pub fn is_node_name_valid(_name: &str) -> std::result::Result<(), &str> {
 	Ok(())
 }
 
+/// Parse a `<SERVER_NAME>=<CERT_PATH>,<KEY_PATH>` TLS certificate.
+fn parse_tls_sni_cert(s: &str) -> std::result::Result<RpcTlsCertificate, String> {
+	let (server_name, paths) = s
+		.split_once('=')
+		.ok_or_else(|| "Expected `<SERVER_NAME>=<CERT_PATH>,<KEY_PATH>`".to_string())?;
+	let (cert_path, key_path) = paths
+		.split_once(',')
+		.ok_or_else(|| "Expected `<CERT_PATH>,<KEY_PATH>` after `=`".to_string())?;
+
+	if server_name.is_empty() || cert_path.is_empty() || key_path.is_empty() {
+		return Err("Server name, certificate and key paths must not be empty".into())
+	}
+
+	Ok(RpcTlsCertificate {
+		cert_path: cert_path.into(),
+		key_path: key_path.into(),
+		server_names: vec![server_name.to_string()],
+	})
+}
+
+/// Parse an IP address or a CIDR range such as `10.0.0.0/8`.
+fn parse_ip_network(s: &str) -> std::result::Result<RpcIpNetwork, String> {
+	if s.contains('/') {
+		s.parse().map_err(|e| format!("Invalid CIDR range `{s}`: {e}"))
+	} else {
+		s.parse::<IpAddr>()
+			.map(Into::into)
+			.map_err(|e| format!("Invalid IP address `{s}`: {e}"))
+	}
+}
+
 fn rpc_interface(
 	is_external: bool,
 	is_unsafe_external: bool,
@@ -534,6 +1487,33 @@ mod tests {
 		assert!(is_node_name_valid("www aa").is_ok());
 	}
 
+	#[test]
+	fn parse_tls_sni_cert_works() {
+		let cert = parse_tls_sni_cert("*.example.com=/certs/rpc.pem,/certs/rpc.key").unwrap();
+		assert_eq!(cert.server_names, vec!["*.example.com".to_string()]);
+		assert_eq!(cert.cert_path, PathBuf::from("/certs/rpc.pem"));
+		assert_eq!(cert.key_path, PathBuf::from("/certs/rpc.key"));
+
+		assert!(parse_tls_sni_cert("example.com").is_err());
+		assert!(parse_tls_sni_cert("example.com=/certs/rpc.pem").is_err());
+		assert!(parse_tls_sni_cert("=/certs/rpc.pem,/certs/rpc.key").is_err());
+	}
+
+	#[test]
+	fn parse_ip_network_works() {
+		let single = parse_ip_network("192.168.1.1").unwrap();
+		assert!(single.contains(Ipv4Addr::new(192, 168, 1, 1)));
+		assert!(!single.contains(Ipv4Addr::new(192, 168, 1, 2)));
+
+		let range = parse_ip_network("10.0.0.0/8").unwrap();
+		assert!(range.contains(Ipv4Addr::new(10, 20, 30, 40)));
+		assert!(parse_ip_network("::1").is_ok());
+
+		assert!(parse_ip_network("10.0.0.1/8").is_err());
+		assert!(parse_ip_network("10.0.0.0/33").is_err());
+		assert!(parse_ip_network("localhost").is_err());
+	}
+
 	#[test]
 	fn tests_node_name_bad() {
 		assert!(is_node_name_valid("").is_err());
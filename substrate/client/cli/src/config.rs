@@ -26,15 +26,27 @@ use log::warn;
 use names::{Generator, Name};
 use sc_service::{
 	config::{
-		BasePath, Configuration, DatabaseSource, KeystoreConfig, NetworkConfiguration,
-		NodeKeyConfig, OffchainWorkerConfig, OutputFormat, PrometheusConfig, PruningMode, Role,
-		RpcBatchRequestConfig, RpcMethods, TelemetryEndpoints, TransactionPoolOptions,
-		WasmExecutionMethod,
+		BasePath, BlockCompression, Configuration, DatabaseSource, ExecutionWitnessConfig,
+		KeystoreConfig, NetworkConfiguration, NodeKeyConfig, OffchainWorkerConfig, OutputFormat,
+		PrometheusConfig, PruningMode, Role, RpcApiKeysConfig, RpcArchiveConfig, RpcAuditLogConfig,
+		RpcAuthConfig, RpcBatchRequestConfig, RpcCallCostConfig, RpcChainHeadConfig,
+		RpcCircuitBreakerConfig, RpcHttp2Config, RpcHttpCompressionConfig, RpcIpFilter,
+		RpcIpNetwork, RpcListener, RpcLoadShedConfig, RpcMethodAlias, RpcMethodCors,
+		RpcMethodFilter, RpcMethodPattern, RpcMethods, RpcMetricsConfig, RpcQuotaConfig,
+		RpcResponseCacheConfig, RpcSessionConfig, RpcSocketConfig, RpcSseConfig,
+		RpcSubscriptionBackpressure, RpcTimeoutConfig, RpcTlsConfig, RpcTransactionBroadcastConfig,
+		RpcTransactionConfig, RpcWsCompressionConfig, RpcWsPingConfig, SignatureVerifier,
+		TelemetryEndpoints, TransactionPoolOptions, WasmExecutionMethod,
 	},
 	BlocksPruning, ChainSpec, TracingReceiver,
 };
 use sc_tracing::logging::LoggerBuilder;
-use std::{net::SocketAddr, num::NonZeroU32, path::PathBuf};
+use std::{
+	net::SocketAddr,
+	num::NonZeroU32,
+	path::{Path, PathBuf},
+	time::Duration,
+};
 
 /// The maximum number of characters for a node name.
 pub(crate) const NODE_NAME_MAX_LENGTH: usize = 64;
@@ -212,6 +224,14 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 		Ok(self.database_params().and_then(|x| x.database()))
 	}
 
+	/// Get the compression of the block bodies and justifications in the database.
+	///
+	/// By default this is retrieved from `DatabaseParams` if it is available. Otherwise the block
+	/// bodies and justifications are stored uncompressed.
+	fn block_compression(&self) -> Result<BlockCompression> {
+		Ok(self.database_params().map(|x| x.block_compression()).unwrap_or_default())
+	}
+
 	/// Get the database configuration object for the parameters provided
 	fn database_config(
 		&self,
@@ -294,6 +314,22 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 		self.import_params().map(|x| x.wasm_runtime_overrides()).unwrap_or_default()
 	}
 
+	/// Get the capture of the execution witness of imported blocks.
+	///
+	/// By default this is retrieved from `ImportParams` if it is available. Otherwise it's
+	/// `None`.
+	fn execution_witness(&self, config_dir: &Path) -> Result<Option<ExecutionWitnessConfig>> {
+		Ok(self.import_params().and_then(|x| x.execution_witness(config_dir)))
+	}
+
+	/// Get the verifier of the justification signatures of imported blocks.
+	///
+	/// By default this is retrieved from `ImportParams` if it is available. Otherwise the
+	/// signatures are verified sequentially.
+	fn justification_verifier(&self) -> Result<SignatureVerifier> {
+		Ok(self.import_params().map(|x| x.justification_verifier()).unwrap_or_default())
+	}
+
 	/// Get the RPC address.
 	fn rpc_addr(&self, _default_listen_port: u16) -> Result<Option<SocketAddr>> {
 		Ok(None)
@@ -319,6 +355,13 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 		Ok(Some(Vec::new()))
 	}
 
+	/// Get the RPC cors of specific methods, overriding [`Self::rpc_cors`] for them.
+	///
+	/// By default this is empty.
+	fn rpc_method_cors(&self) -> Result<Vec<RpcMethodCors>> {
+		Ok(Vec::new())
+	}
+
 	/// Get maximum RPC request payload size.
 	fn rpc_max_request_size(&self) -> Result<u32> {
 		Ok(RPC_DEFAULT_MAX_REQUEST_SIZE_MB)
@@ -349,6 +392,254 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 		Ok(None)
 	}
 
+	/// Maximum number of concurrent RPC connections per peer IP address.
+	///
+	/// By default this is `None`, i.e. unlimited.
+	fn rpc_max_connections_per_ip(&self) -> Result<Option<NonZeroU32>> {
+		Ok(None)
+	}
+
+	/// IP addresses, or CIDR ranges, of peers exempt from the RPC rate limit and the per-IP
+	/// connection limit.
+	///
+	/// By default this is empty.
+	fn rpc_rate_limit_whitelisted_ips(&self) -> Result<Vec<RpcIpNetwork>> {
+		Ok(Vec::new())
+	}
+
+	/// RPC server TLS configuration (`None` if disabled).
+	///
+	/// By default this is `None`.
+	fn rpc_tls(&self) -> Result<Option<RpcTlsConfig>> {
+		Ok(None)
+	}
+
+	/// Filter on the IP addresses allowed to connect to the RPC server (`None` if disabled).
+	///
+	/// By default this is `None`.
+	fn rpc_ip_filter(&self) -> Result<Option<RpcIpFilter>> {
+		Ok(None)
+	}
+
+	/// RPC server WebSocket compression configuration (`None` if disabled).
+	///
+	/// By default this is `None`.
+	fn rpc_ws_compression(&self) -> Result<Option<RpcWsCompressionConfig>> {
+		Ok(None)
+	}
+
+	/// RPC server HTTP response compression configuration (`None` if disabled).
+	///
+	/// By default this is `None`.
+	fn rpc_http_compression(&self) -> Result<Option<RpcHttpCompressionConfig>> {
+		Ok(None)
+	}
+
+	/// RPC server bearer token authentication configuration (`None` if disabled).
+	///
+	/// By default this is `None`.
+	fn rpc_auth(&self) -> Result<Option<RpcAuthConfig>> {
+		Ok(None)
+	}
+
+	/// RPC server HTTP/2 configuration (`None` if disabled).
+	///
+	/// By default this is `None`.
+	fn rpc_http2(&self) -> Result<Option<RpcHttp2Config>> {
+		Ok(None)
+	}
+
+	/// RPC server audit log configuration (`None` if disabled).
+	///
+	/// By default this is `None`.
+	fn rpc_audit_log(&self) -> Result<Option<RpcAuditLogConfig>> {
+		Ok(None)
+	}
+
+	/// Filter on the methods exposed by the RPC server (`None` if all of them are exposed).
+	///
+	/// By default this is `None`.
+	fn rpc_method_filter(&self) -> Result<Option<RpcMethodFilter>> {
+		Ok(None)
+	}
+
+	/// Proxies trusted to report the address of the client of RPC requests.
+	///
+	/// By default this is empty.
+	fn rpc_trusted_proxies(&self) -> Result<Vec<RpcIpNetwork>> {
+		Ok(Vec::new())
+	}
+
+	/// RPC server API keys configuration (`None` if disabled).
+	///
+	/// By default this is `None`.
+	fn rpc_api_keys(&self) -> Result<Option<RpcApiKeysConfig>> {
+		Ok(None)
+	}
+
+	/// Cost of the RPC calls paid from a budget of each IP address (`None` if disabled).
+	///
+	/// By default this is `None`.
+	fn rpc_call_cost(&self) -> Result<Option<RpcCallCostConfig>> {
+		Ok(None)
+	}
+
+	/// Quotas on the RPC calls and bandwidth of each API key or IP address (`None` if disabled).
+	///
+	/// By default this is `None`.
+	fn rpc_quota(&self) -> Result<Option<RpcQuotaConfig>> {
+		Ok(None)
+	}
+
+	/// Timeout of the RPC calls (`None` if disabled).
+	///
+	/// By default this is `None`.
+	fn rpc_timeout(&self) -> Result<Option<RpcTimeoutConfig>> {
+		Ok(None)
+	}
+
+	/// Rejection of the calls of the RPC methods failing or slowing down (`None` if disabled).
+	///
+	/// By default this is `None`.
+	fn rpc_circuit_breaker(&self) -> Result<Option<RpcCircuitBreakerConfig>> {
+		Ok(None)
+	}
+
+	/// Aliases of the RPC methods.
+	///
+	/// By default this is empty.
+	fn rpc_method_aliases(&self) -> Result<Vec<RpcMethodAlias>> {
+		Ok(Vec::new())
+	}
+
+	/// Write a structured access log record of every RPC call.
+	///
+	/// By default this is `false`.
+	fn rpc_access_log(&self) -> Result<bool> {
+		Ok(false)
+	}
+
+	/// Threshold above which the RPC calls are logged as slow (`None` if disabled).
+	///
+	/// By default this is `None`.
+	fn rpc_slow_call_threshold(&self) -> Result<Option<Duration>> {
+		Ok(None)
+	}
+
+	/// Serve the RPC calls and subscriptions within tracing spans.
+	///
+	/// By default this is enabled when the spans are exported to an OTLP endpoint.
+	fn rpc_call_spans(&self) -> Result<bool> {
+		Ok(self.otlp_endpoint()?.is_some())
+	}
+
+	/// The deprecated RPC methods, whose calls are counted and warned about.
+	///
+	/// By default this is empty.
+	fn rpc_deprecated_methods(&self) -> Result<Vec<RpcMethodPattern>> {
+		Ok(Vec::new())
+	}
+
+	/// Limits of the `chainHead` subscriptions.
+	///
+	/// By default this is [`RpcChainHeadConfig::default`].
+	fn rpc_chain_head(&self) -> Result<RpcChainHeadConfig> {
+		Ok(Default::default())
+	}
+
+	/// Limits of the `transactionWatch` subscriptions.
+	///
+	/// By default this is [`RpcTransactionConfig::default`].
+	fn rpc_transaction(&self) -> Result<RpcTransactionConfig> {
+		Ok(Default::default())
+	}
+
+	/// Limits of the `transaction_broadcast` operations.
+	///
+	/// By default this is [`RpcTransactionBroadcastConfig::default`].
+	fn rpc_transaction_broadcast(&self) -> Result<RpcTransactionBroadcastConfig> {
+		Ok(Default::default())
+	}
+
+	/// Limits of the `archive` methods.
+	///
+	/// By default this is [`RpcArchiveConfig::default`].
+	fn rpc_archive(&self) -> Result<RpcArchiveConfig> {
+		Ok(Default::default())
+	}
+
+	/// Sessions resuming the `chainHead` and `transactionWatch` subscriptions from another
+	/// connection.
+	///
+	/// By default this is `None`.
+	fn rpc_sessions(&self) -> Result<Option<RpcSessionConfig>> {
+		Ok(None)
+	}
+
+	/// Maximum number of `transaction_broadcast` operations persisted across restarts.
+	///
+	/// By default this is `None`.
+	fn rpc_max_persisted_broadcasts(&self) -> Result<Option<u32>> {
+		Ok(None)
+	}
+
+	/// Configuration of the prometheus metrics of the RPC calls.
+	///
+	/// By default all the calls are recorded, labelled with their method.
+	fn rpc_metrics(&self) -> Result<RpcMetricsConfig> {
+		Ok(Default::default())
+	}
+
+	/// Server-Sent Events transport of the RPC subscriptions.
+	///
+	/// By default this is `None`.
+	fn rpc_sse(&self) -> Result<Option<RpcSseConfig>> {
+		Ok(None)
+	}
+
+	/// TCP socket options of the RPC listeners.
+	///
+	/// By default this is `RpcSocketConfig::default()`.
+	fn rpc_socket(&self) -> Result<RpcSocketConfig> {
+		Ok(Default::default())
+	}
+
+	/// Backpressure policies of the RPC subscriptions whose client is too slow.
+	///
+	/// By default this is empty, the subscriptions are closed once their buffer is full.
+	fn rpc_subscription_backpressure(&self) -> Result<Vec<RpcSubscriptionBackpressure>> {
+		Ok(Vec::new())
+	}
+
+	/// RPC listeners in addition to the one at [`Self::rpc_addr`].
+	///
+	/// By default this is empty.
+	fn rpc_listeners(&self, _is_dev: bool) -> Result<Vec<RpcListener>> {
+		Ok(Vec::new())
+	}
+
+	/// WebSocket keepalive of the RPC server.
+	///
+	/// By default pings are sent every 30 seconds and connections are closed after 3 pings
+	/// weren't answered within 60 seconds.
+	fn rpc_ws_ping(&self) -> Result<RpcWsPingConfig> {
+		Ok(Default::default())
+	}
+
+	/// Cache of the responses of idempotent RPC methods (`None` if disabled).
+	///
+	/// By default this is `None`.
+	fn rpc_response_cache(&self) -> Result<Option<RpcResponseCacheConfig>> {
+		Ok(None)
+	}
+
+	/// Rejection of RPC calls while the server is under pressure (`None` if disabled).
+	///
+	/// By default this is `None`.
+	fn rpc_load_shed(&self) -> Result<Option<RpcLoadShedConfig>> {
+		Ok(None)
+	}
+
 	/// Get the prometheus configuration (`None` if disabled)
 	///
 	/// By default this is `None`.
@@ -423,6 +714,14 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 		Ok(self.shared_params().tracing_receiver())
 	}
 
+	/// Get the OTLP endpoint the tracing spans are exported to (if any)
+	///
+	/// By default this is retrieved from [`SharedParams`] if it is available. Otherwise its
+	/// `None`.
+	fn otlp_endpoint(&self) -> Result<Option<String>> {
+		Ok(self.shared_params().otlp_endpoint())
+	}
+
 	/// Get the node key from the current object
 	///
 	/// By default this is retrieved from `NodeKeyParams` if it is available. Otherwise its
@@ -487,6 +786,7 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 		let keystore = self.keystore_config(&config_dir)?;
 		let telemetry_endpoints = self.telemetry_endpoints(&chain_spec)?;
 		let runtime_cache_size = self.runtime_cache_size()?;
+		let execution_witness = self.execution_witness(&config_dir)?;
 
 		Ok(Configuration {
 			impl_name: C::impl_name(),
@@ -509,8 +809,11 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 			trie_cache_maximum_size: self.trie_cache_maximum_size()?,
 			state_pruning: self.state_pruning()?,
 			blocks_pruning: self.blocks_pruning()?,
+			block_compression: self.block_compression()?,
 			wasm_method: self.wasm_method()?,
 			wasm_runtime_overrides: self.wasm_runtime_overrides(),
+			execution_witness,
+			justification_verifier: self.justification_verifier()?,
 			rpc_addr: self.rpc_addr(DCV::rpc_listen_port())?,
 			rpc_methods: self.rpc_methods()?,
 			rpc_max_connections: self.rpc_max_connections()?,
@@ -523,6 +826,43 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 			rpc_message_buffer_capacity: self.rpc_buffer_capacity_per_connection()?,
 			rpc_batch_config: self.rpc_batch_config()?,
 			rpc_rate_limit: self.rpc_rate_limit()?,
+			rpc_rate_limit_whitelisted_ips: self.rpc_rate_limit_whitelisted_ips()?,
+			rpc_tls: self.rpc_tls()?,
+			rpc_ip_filter: self.rpc_ip_filter()?,
+			rpc_ws_compression: self.rpc_ws_compression()?,
+			rpc_auth: self.rpc_auth()?,
+			rpc_http2: self.rpc_http2()?,
+			rpc_audit_log: self.rpc_audit_log()?,
+			rpc_method_filter: self.rpc_method_filter()?,
+			rpc_ws_ping: self.rpc_ws_ping()?,
+			rpc_response_cache: self.rpc_response_cache()?,
+			rpc_method_cors: self.rpc_method_cors()?,
+			rpc_load_shed: self.rpc_load_shed()?,
+			rpc_listeners: self.rpc_listeners(is_dev)?,
+			rpc_trusted_proxies: self.rpc_trusted_proxies()?,
+			rpc_max_connections_per_ip: self.rpc_max_connections_per_ip()?,
+			rpc_api_keys: self.rpc_api_keys()?,
+			rpc_call_cost: self.rpc_call_cost()?,
+			rpc_subscription_backpressure: self.rpc_subscription_backpressure()?,
+			rpc_http_compression: self.rpc_http_compression()?,
+			rpc_timeout: self.rpc_timeout()?,
+			rpc_method_aliases: self.rpc_method_aliases()?,
+			rpc_access_log: self.rpc_access_log()?,
+			rpc_sse: self.rpc_sse()?,
+			rpc_socket: self.rpc_socket()?,
+			rpc_quota: self.rpc_quota()?,
+			rpc_slow_call_threshold: self.rpc_slow_call_threshold()?,
+			rpc_circuit_breaker: self.rpc_circuit_breaker()?,
+			rpc_metrics: self.rpc_metrics()?,
+			rpc_call_spans: self.rpc_call_spans()?,
+			rpc_deprecated_methods: self.rpc_deprecated_methods()?,
+			rpc_middleware_extensions: Default::default(),
+			rpc_max_persisted_broadcasts: self.rpc_max_persisted_broadcasts()?,
+			rpc_chain_head: self.rpc_chain_head()?,
+			rpc_transaction: self.rpc_transaction()?,
+			rpc_transaction_broadcast: self.rpc_transaction_broadcast()?,
+			rpc_archive: self.rpc_archive()?,
+			rpc_sessions: self.rpc_sessions()?,
 			prometheus_config: self
 				.prometheus_config(DCV::prometheus_listen_port(), &chain_spec)?,
 			telemetry_endpoints,
@@ -622,6 +962,10 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 			logger.with_colors(false);
 		}
 
+		if let Some(otlp_endpoint) = self.otlp_endpoint()? {
+			logger.with_otlp_export(otlp_endpoint, config.impl_name.clone());
+		}
+
 		// Call hook for custom profiling setup.
 		logger_hook(&mut logger, config);
 
@@ -18,6 +18,7 @@
 
 use crate::arg_enums::Database;
 use clap::Args;
+use sc_service::BlockCompression;
 
 /// Parameters for database
 #[derive(Debug, Clone, PartialEq, Args)]
@@ -29,6 +30,21 @@ pub struct DatabaseParams {
 	/// Limit the memory the database cache can use.
 	#[arg(long = "db-cache", value_name = "MiB")]
 	pub database_cache_size: Option<usize>,
+
+	/// Compress the block bodies written to the database with zstd at the given level.
+	///
+	/// The bodies already stored are left as they are, and all of them are read whatever this
+	/// option. The compressed bodies can't be read by the nodes not supporting the compression.
+	/// Only applies to RocksDB, ParityDb compressing the bodies already.
+	#[arg(long, value_name = "LEVEL", value_parser = clap::value_parser!(i32).range(1..=22))]
+	pub db_compress_bodies: Option<i32>,
+
+	/// Compress the justifications written to the database with zstd at the given level.
+	///
+	/// The justifications already stored are left as they are, and all of them are read whatever
+	/// this option. Only applies to RocksDB, ParityDb compressing the justifications already.
+	#[arg(long, value_name = "LEVEL", value_parser = clap::value_parser!(i32).range(1..=22))]
+	pub db_compress_justifications: Option<i32>,
 }
 
 impl DatabaseParams {
@@ -41,4 +57,12 @@ impl DatabaseParams {
 	pub fn database_cache_size(&self) -> Option<usize> {
 		self.database_cache_size
 	}
+
+	/// Compression of the block bodies and justifications.
+	pub fn block_compression(&self) -> BlockCompression {
+		BlockCompression {
+			bodies: self.db_compress_bodies,
+			justifications: self.db_compress_justifications,
+		}
+	}
 }
@@ -18,13 +18,21 @@
 
 use crate::{
 	arg_enums::{
-		ExecutionStrategy, WasmExecutionMethod, WasmtimeInstantiationStrategy,
-		DEFAULT_WASMTIME_INSTANTIATION_STRATEGY, DEFAULT_WASM_EXECUTION_METHOD,
+		ExecutionStrategy, ExecutionWitnessMode, WasmExecutionMethod,
+		WasmtimeInstantiationStrategy, DEFAULT_WASMTIME_INSTANTIATION_STRATEGY,
+		DEFAULT_WASM_EXECUTION_METHOD,
 	},
 	params::{DatabaseParams, PruningParams},
 };
 use clap::Args;
-use std::path::PathBuf;
+use sc_service::config::{ExecutionWitnessConfig, SignatureVerifier};
+use std::{
+	num::NonZeroUsize,
+	path::{Path, PathBuf},
+};
+
+/// Default sub directory of the execution witnesses.
+const DEFAULT_EXECUTION_WITNESS_PATH: &str = "execution-witnesses";
 
 /// Parameters for block import.
 #[derive(Debug, Clone, Args)]
@@ -75,6 +83,31 @@ pub struct ImportParams {
 	#[clap(flatten)]
 	pub execution_strategies: ExecutionStrategiesParams,
 
+	/// Capture the execution witness of the blocks executed during import.
+	///
+	/// The witness holds the block and all the state accessed while executing it, such that the
+	/// block can be re-executed without the database using the `replay-witness` command. Use
+	/// `all` along with `check-block` to capture the witness of a given block.
+	#[arg(long, value_name = "MODE", value_enum, ignore_case = true)]
+	pub execution_witness: Option<ExecutionWitnessMode>,
+
+	/// Directory the execution witnesses are written to.
+	///
+	/// Defaults to the `execution-witnesses` directory of the chain's data directory.
+	#[arg(long, value_name = "PATH", requires = "execution_witness")]
+	pub execution_witness_dir: Option<PathBuf>,
+
+	/// Number of threads verifying the signatures of the justifications imported during sync.
+	///
+	/// Applies to GRANDPA justifications, including the ones of a warp sync proof, and BEEFY
+	/// signed commitments. The signatures are verified on the import thread by default.
+	#[arg(long, value_name = "COUNT", default_value_t = NonZeroUsize::MIN)]
+	pub justification_verification_threads: NonZeroUsize,
+
+	/// Number of signatures a justification verification thread claims at once.
+	#[arg(long, value_name = "COUNT", default_value = "64")]
+	pub justification_verification_batch_size: NonZeroUsize,
+
 	/// Specify the state cache size.
 	///
 	/// Providing `0` will disable the cache.
@@ -112,6 +145,25 @@ impl ImportParams {
 	pub fn wasm_runtime_overrides(&self) -> Option<PathBuf> {
 		self.wasm_runtime_overrides.clone()
 	}
+
+	/// Get the capture of the execution witness of imported blocks.
+	pub fn execution_witness(&self, config_dir: &Path) -> Option<ExecutionWitnessConfig> {
+		self.execution_witness.map(|mode| ExecutionWitnessConfig {
+			dir: self
+				.execution_witness_dir
+				.clone()
+				.unwrap_or_else(|| config_dir.join(DEFAULT_EXECUTION_WITNESS_PATH)),
+			mode: mode.into(),
+		})
+	}
+
+	/// Get the verifier of the justification signatures of imported blocks.
+	pub fn justification_verifier(&self) -> SignatureVerifier {
+		SignatureVerifier::new(
+			self.justification_verification_threads,
+			self.justification_verification_batch_size,
+		)
+	}
 }
 
 /// Execution strategies parameters.
@@ -23,7 +23,8 @@ use crate::{
 use clap::Args;
 use sc_network::{
 	config::{
-		NetworkConfiguration, NodeKeyConfig, NonReservedPeerMode, SetConfig, TransportConfig,
+		LightClientServerConfig, NetworkConfiguration, NodeKeyConfig, NonReservedPeerMode,
+		SetConfig, TransportConfig,
 	},
 	multiaddr::Protocol,
 };
@@ -152,6 +153,29 @@ pub struct NetworkParams {
 	#[arg(long)]
 	pub ipfs_server: bool,
 
+	/// Cache the proofs requested by the light clients and generate the most requested ones
+	/// ahead of the requests at every finalized block.
+	///
+	/// Meant for the nodes serving many light clients, such as public bootnodes.
+	#[arg(long)]
+	pub light_client_server: bool,
+
+	/// Maximum number of responses to the light client requests kept in the cache.
+	#[arg(long, value_name = "COUNT", default_value_t = 4096, requires = "light_client_server")]
+	pub light_client_cache_capacity: u32,
+
+	/// Number of the most requested light client calls and storage reads generated at every
+	/// finalized block.
+	#[arg(long, value_name = "COUNT", default_value_t = 32, requires = "light_client_server")]
+	pub light_client_precomputed_requests: usize,
+
+	/// Periodically fetch bootnodes and reserved peers from the chain.
+	///
+	/// The peers are read through the `BootnodesApi` runtime api of the best block and are used in
+	/// addition to the `--bootnodes` and `--reserved-nodes`.
+	#[arg(long)]
+	pub onchain_peers: bool,
+
 	/// Blockchain syncing mode.
 	#[arg(
 		long,
@@ -277,6 +301,11 @@ impl NetworkParams {
 			kademlia_replication_factor: self.kademlia_replication_factor,
 			yamux_window_size: None,
 			ipfs_server: self.ipfs_server,
+			light_client_server: self.light_client_server.then(|| LightClientServerConfig {
+				cache_capacity: self.light_client_cache_capacity,
+				precomputed_requests: self.light_client_precomputed_requests,
+			}),
+			onchain_peers: self.onchain_peers,
 			sync_mode: self.sync.into(),
 			network_backend: self.network_backend.into(),
 		}
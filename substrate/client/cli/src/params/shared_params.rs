@@ -87,6 +87,13 @@ pub struct SharedParams {
 	/// Receiver to process tracing messages.
 	#[arg(long, value_name = "RECEIVER", value_enum, ignore_case = true, default_value_t = TracingReceiver::Log)]
 	pub tracing_receiver: TracingReceiver,
+
+	/// Export the tracing spans to the OpenTelemetry collector at the given URL, over OTLP/gRPC.
+	///
+	/// The exported spans are the ones enabled by the log filter (`--log`), among which the
+	/// spans of the RPC calls and subscriptions, e.g. `http://localhost:4317`.
+	#[arg(long, value_name = "URL")]
+	pub otlp_endpoint: Option<String>,
 }
 
 impl SharedParams {
@@ -147,4 +154,9 @@ impl SharedParams {
 	pub fn tracing_targets(&self) -> Option<String> {
 		self.tracing_targets.clone()
 	}
+
+	/// URL of the OTLP endpoint the tracing spans are exported to.
+	pub fn otlp_endpoint(&self) -> Option<String> {
+		self.otlp_endpoint.clone()
+	}
 }
@@ -16,8 +16,10 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
+use crate::arg_enums::RevalidationPriority;
 use clap::Args;
 use sc_service::config::TransactionPoolOptions;
+use std::time::Duration;
 
 /// Parameters used to create the pool configuration.
 #[derive(Debug, Clone, Args)]
@@ -35,6 +37,20 @@ pub struct TransactionPoolParams {
 	/// If it is considered invalid. Defaults to 1800s.
 	#[arg(long, value_name = "SECONDS")]
 	pub tx_ban_seconds: Option<u64>,
+
+	/// Number of transactions revalidated in the background at once.
+	///
+	/// Defaults to a quarter of the transactions waiting for revalidation, but at least 20.
+	#[arg(long, value_name = "COUNT")]
+	pub pool_revalidation_batch_size: Option<usize>,
+
+	/// How often a batch of transactions is revalidated in the background.
+	#[arg(long, value_name = "MILLISECONDS", default_value_t = 200)]
+	pub pool_revalidation_interval: u64,
+
+	/// Which transactions are revalidated first.
+	#[arg(long, value_name = "PRIORITY", value_enum, ignore_case = true, default_value_t = RevalidationPriority::Oldest)]
+	pub pool_revalidation_priority: RevalidationPriority,
 }
 
 impl TransactionPoolParams {
@@ -52,13 +68,18 @@ impl TransactionPoolParams {
 		opts.future.total_bytes = self.pool_kbytes * 1024 / factor;
 
 		opts.ban_time = if let Some(ban_seconds) = self.tx_ban_seconds {
-			std::time::Duration::from_secs(ban_seconds)
+			Duration::from_secs(ban_seconds)
 		} else if is_dev {
-			std::time::Duration::from_secs(0)
+			Duration::from_secs(0)
 		} else {
-			std::time::Duration::from_secs(30 * 60)
+			Duration::from_secs(30 * 60)
 		};
 
+		// background revalidation
+		opts.revalidation.batch_size = self.pool_revalidation_batch_size;
+		opts.revalidation.interval = Duration::from_millis(self.pool_revalidation_interval);
+		opts.revalidation.priority = self.pool_revalidation_priority.into();
+
 		opts
 	}
 }
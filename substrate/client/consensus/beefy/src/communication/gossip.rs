@@ -343,9 +343,12 @@ where
 			guard
 				.validator_set()
 				.map(|validator_set| {
-					if let Err((_, signatures_checked)) =
-						verify_with_validator_set::<B>(round, validator_set, &proof)
-					{
+					if let Err((_, signatures_checked)) = verify_with_validator_set::<B>(
+						round,
+						validator_set,
+						&proof,
+						&Default::default(),
+					) {
 						debug!(
 							target: LOG_TARGET,
 							"🥩 Bad signatures on message: {:?}, from: {:?}", proof, sender
@@ -211,6 +211,7 @@ impl<B: Block> OnDemandJustificationsEngine<B> {
 					&encoded[..],
 					req_info.block,
 					&req_info.active_set,
+					&Default::default(),
 				)
 				.map_err(|(err, signatures_checked)| {
 					metric_inc!(self.metrics, beefy_on_demand_justification_invalid_proof);
@@ -29,7 +29,9 @@ use sp_runtime::{
 };
 
 use sc_client_api::backend::Backend;
-use sc_consensus::{BlockCheckParams, BlockImport, BlockImportParams, ImportResult};
+use sc_consensus::{
+	BlockCheckParams, BlockImport, BlockImportParams, ImportResult, SignatureVerifier,
+};
 
 use crate::{
 	communication::notification::BeefyVersionedFinalityProofSender,
@@ -51,6 +53,7 @@ pub struct BeefyBlockImport<Block: BlockT, Backend, RuntimeApi, I> {
 	inner: I,
 	justification_sender: BeefyVersionedFinalityProofSender<Block>,
 	metrics: Option<BlockImportMetrics>,
+	justification_verifier: SignatureVerifier,
 }
 
 impl<Block: BlockT, BE, Runtime, I: Clone> Clone for BeefyBlockImport<Block, BE, Runtime, I> {
@@ -61,6 +64,7 @@ impl<Block: BlockT, BE, Runtime, I: Clone> Clone for BeefyBlockImport<Block, BE,
 			inner: self.inner.clone(),
 			justification_sender: self.justification_sender.clone(),
 			metrics: self.metrics.clone(),
+			justification_verifier: self.justification_verifier,
 		}
 	}
 }
@@ -74,7 +78,22 @@ impl<Block: BlockT, BE, Runtime, I> BeefyBlockImport<Block, BE, Runtime, I> {
 		justification_sender: BeefyVersionedFinalityProofSender<Block>,
 		metrics: Option<BlockImportMetrics>,
 	) -> BeefyBlockImport<Block, BE, Runtime, I> {
-		BeefyBlockImport { backend, runtime, inner, justification_sender, metrics }
+		BeefyBlockImport {
+			backend,
+			runtime,
+			inner,
+			justification_sender,
+			metrics,
+			justification_verifier: Default::default(),
+		}
+	}
+
+	/// Verify the signatures of the imported justifications with the given verifier.
+	///
+	/// By default the signatures are verified one after the other.
+	pub fn with_justification_verifier(mut self, verifier: SignatureVerifier) -> Self {
+		self.justification_verifier = verifier;
+		self
 	}
 }
 
@@ -108,8 +127,13 @@ where
 			.map_err(|e| ImportError(e.to_string()))?
 			.ok_or_else(|| ImportError("Unknown validator set".to_string()))?;
 
-		decode_and_verify_finality_proof::<Block>(&encoded[..], number, &validator_set)
-			.map_err(|(err, _)| err)
+		decode_and_verify_finality_proof::<Block>(
+			&encoded[..],
+			number,
+			&validator_set,
+			&self.justification_verifier,
+		)
+		.map_err(|(err, _)| err)
 	}
 }
 
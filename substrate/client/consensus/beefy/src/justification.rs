@@ -18,6 +18,7 @@
 
 use crate::keystore::BeefyKeystore;
 use codec::{DecodeAll, Encode};
+use sc_consensus::SignatureVerifier;
 use sp_consensus::Error as ConsensusError;
 use sp_consensus_beefy::{
 	ecdsa_crypto::{AuthorityId, Signature},
@@ -42,10 +43,12 @@ pub(crate) fn decode_and_verify_finality_proof<Block: BlockT>(
 	encoded: &[u8],
 	target_number: NumberFor<Block>,
 	validator_set: &ValidatorSet<AuthorityId>,
+	verifier: &SignatureVerifier,
 ) -> Result<BeefyVersionedFinalityProof<Block>, (ConsensusError, u32)> {
 	let proof = <BeefyVersionedFinalityProof<Block>>::decode_all(&mut &*encoded)
 		.map_err(|_| (ConsensusError::InvalidJustification, 0))?;
-	verify_with_validator_set::<Block>(target_number, validator_set, &proof).map(|_| proof)
+	verify_with_validator_set::<Block>(target_number, validator_set, &proof, verifier)
+		.map(|_| proof)
 }
 
 /// Verify the Beefy finality proof against the validator set at the block it was generated.
@@ -53,8 +56,8 @@ pub(crate) fn verify_with_validator_set<Block: BlockT>(
 	target_number: NumberFor<Block>,
 	validator_set: &ValidatorSet<AuthorityId>,
 	proof: &BeefyVersionedFinalityProof<Block>,
+	verifier: &SignatureVerifier,
 ) -> Result<(), (ConsensusError, u32)> {
-	let mut signatures_checked = 0u32;
 	match proof {
 		VersionedFinalityProof::V1(signed_commitment) => {
 			if signed_commitment.signatures.len() != validator_set.len() ||
@@ -67,20 +70,16 @@ pub(crate) fn verify_with_validator_set<Block: BlockT>(
 			// Arrangement of signatures in the commitment should be in the same order
 			// as validators for that set.
 			let message = signed_commitment.commitment.encode();
-			let valid_signatures = validator_set
+			let signatures = validator_set
 				.validators()
-				.into_iter()
+				.iter()
 				.zip(signed_commitment.signatures.iter())
-				.filter(|(id, signature)| {
-					signature
-						.as_ref()
-						.map(|sig| {
-							signatures_checked += 1;
-							BeefyKeystore::verify(*id, sig, &message[..])
-						})
-						.unwrap_or(false)
-				})
-				.count();
+				.filter_map(|(id, signature)| signature.as_ref().map(|sig| (id, sig)))
+				.collect::<Vec<_>>();
+			let signatures_checked = signatures.len() as u32;
+			let valid_signatures = verifier.count_valid(&signatures, |(id, sig)| {
+				BeefyKeystore::verify(*id, sig, &message[..])
+			});
 			if valid_signatures >= crate::round::threshold(validator_set.len()) {
 				Ok(())
 			} else {
@@ -127,11 +126,22 @@ pub(crate) mod tests {
 
 		let good_proof = proof.clone().into();
 		// should verify successfully
-		verify_with_validator_set::<Block>(block_num, &validator_set, &good_proof).unwrap();
+		verify_with_validator_set::<Block>(
+			block_num,
+			&validator_set,
+			&good_proof,
+			&Default::default(),
+		)
+		.unwrap();
 
 		// wrong block number -> should fail verification
 		let good_proof = proof.clone().into();
-		match verify_with_validator_set::<Block>(block_num + 1, &validator_set, &good_proof) {
+		match verify_with_validator_set::<Block>(
+			block_num + 1,
+			&validator_set,
+			&good_proof,
+			&Default::default(),
+		) {
 			Err((ConsensusError::InvalidJustification, 0)) => (),
 			e => assert!(false, "Got unexpected {:?}", e),
 		};
@@ -139,7 +149,12 @@ pub(crate) mod tests {
 		// wrong validator set id -> should fail verification
 		let good_proof = proof.clone().into();
 		let other = ValidatorSet::new(make_beefy_ids(keys), 1).unwrap();
-		match verify_with_validator_set::<Block>(block_num, &other, &good_proof) {
+		match verify_with_validator_set::<Block>(
+			block_num,
+			&other,
+			&good_proof,
+			&Default::default(),
+		) {
 			Err((ConsensusError::InvalidJustification, 0)) => (),
 			e => assert!(false, "Got unexpected {:?}", e),
 		};
@@ -151,7 +166,12 @@ pub(crate) mod tests {
 			VersionedFinalityProof::V1(ref mut sc) => sc,
 		};
 		bad_signed_commitment.signatures.pop().flatten().unwrap();
-		match verify_with_validator_set::<Block>(block_num + 1, &validator_set, &bad_proof.into()) {
+		match verify_with_validator_set::<Block>(
+			block_num + 1,
+			&validator_set,
+			&bad_proof.into(),
+			&Default::default(),
+		) {
 			Err((ConsensusError::InvalidJustification, 0)) => (),
 			e => assert!(false, "Got unexpected {:?}", e),
 		};
@@ -163,7 +183,12 @@ pub(crate) mod tests {
 		};
 		// remove a signature (but same length)
 		*bad_signed_commitment.signatures.first_mut().unwrap() = None;
-		match verify_with_validator_set::<Block>(block_num, &validator_set, &bad_proof.into()) {
+		match verify_with_validator_set::<Block>(
+			block_num,
+			&validator_set,
+			&bad_proof.into(),
+			&Default::default(),
+		) {
 			Err((ConsensusError::InvalidJustification, 2)) => (),
 			e => assert!(false, "Got unexpected {:?}", e),
 		};
@@ -176,7 +201,12 @@ pub(crate) mod tests {
 		// change a signature to a different key
 		*bad_signed_commitment.signatures.first_mut().unwrap() =
 			Some(Keyring::<AuthorityId>::Dave.sign(&bad_signed_commitment.commitment.encode()));
-		match verify_with_validator_set::<Block>(block_num, &validator_set, &bad_proof.into()) {
+		match verify_with_validator_set::<Block>(
+			block_num,
+			&validator_set,
+			&bad_proof.into(),
+			&Default::default(),
+		) {
 			Err((ConsensusError::InvalidJustification, 3)) => (),
 			e => assert!(false, "Got unexpected {:?}", e),
 		};
@@ -194,8 +224,13 @@ pub(crate) mod tests {
 		let encoded = versioned_proof.encode();
 
 		// should successfully decode and verify
-		let verified =
-			decode_and_verify_finality_proof::<Block>(&encoded, block_num, &validator_set).unwrap();
+		let verified = decode_and_verify_finality_proof::<Block>(
+			&encoded,
+			block_num,
+			&validator_set,
+			&Default::default(),
+		)
+		.unwrap();
 		assert_eq!(verified, versioned_proof);
 	}
 }
@@ -25,14 +25,26 @@ use sp_crypto_hashing::keccak_256;
 use sp_keystore::KeystorePtr;
 
 use codec::Decode;
+use futures::{
+	channel::oneshot,
+	future::{self, Either},
+};
 use log::warn;
-use std::marker::PhantomData;
+use std::{marker::PhantomData, thread, time::Duration};
+use wasm_timer::Delay;
 
 use crate::{error, LOG_TARGET};
 
+/// Time given to the keystore to sign a message before the attempt is abandoned.
+pub(crate) const SIGNING_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Number of times signing a message is retried after a failed or timed out attempt.
+pub(crate) const SIGNING_RETRIES: usize = 2;
+
 /// A BEEFY specific keystore implemented as a `Newtype`. This is basically a
 /// wrapper around [`sp_keystore::Keystore`] and allows to customize
 /// common cryptographic functionality.
+#[derive(Clone)]
 pub(crate) struct BeefyKeystore<AuthorityId: AuthorityIdBound>(
 	Option<KeystorePtr>,
 	PhantomData<fn() -> AuthorityId>,
@@ -128,6 +140,53 @@ impl<AuthorityId: AuthorityIdBound> BeefyKeystore<AuthorityId> {
 		Ok(signature)
 	}
 
+	/// Sign `message` with the `public` key without blocking the caller.
+	///
+	/// The keystore is called from a dedicated thread, so that keystores slow to sign, e.g. the
+	/// ones backed by a remote signer, don't stall the voter. Each attempt is given
+	/// [`SIGNING_TIMEOUT`] to complete, and is retried up to [`SIGNING_RETRIES`] times.
+	pub async fn sign_async(
+		&self,
+		public: AuthorityId,
+		message: Vec<u8>,
+	) -> Result<<AuthorityId as RuntimeAppPublic>::Signature, error::Error>
+	where
+		AuthorityId: Send + 'static,
+		<AuthorityId as RuntimeAppPublic>::Signature: Send,
+	{
+		let mut attempt = 0;
+		loop {
+			let (tx, rx) = oneshot::channel();
+			let (store, public, message) = (self.clone(), public.clone(), message.clone());
+			thread::Builder::new()
+				.name("beefy-signer".into())
+				.spawn(move || {
+					let _ = tx.send(store.sign(&public, &message));
+				})
+				.map_err(|e| error::Error::Keystore(e.to_string()))?;
+
+			let result = match future::select(rx, Delay::new(SIGNING_TIMEOUT)).await {
+				Either::Left((Ok(result), _)) => result,
+				Either::Left((Err(_), _)) =>
+					Err(error::Error::Keystore("signer thread terminated".into())),
+				Either::Right(_) => Err(error::Error::Signature(format!(
+					"signing timed out after {:?}",
+					SIGNING_TIMEOUT
+				))),
+			};
+			match result {
+				Err(err) if attempt < SIGNING_RETRIES => {
+					attempt += 1;
+					warn!(
+						target: LOG_TARGET,
+						"🥩 Signing attempt {} failed: {}, retrying", attempt, err
+					);
+				},
+				result => return result,
+			}
+		}
+	}
+
 	/// Returns a vector of [`sp_consensus_beefy::crypto::Public`] keys which are currently
 	/// supported (i.e. found in the keystore).
 	pub fn public_keys(&self) -> Result<Vec<AuthorityId>, error::Error> {
@@ -470,6 +529,30 @@ pub mod tests {
 		assert_eq!(sig, err);
 	}
 
+	#[tokio::test]
+	async fn sign_async_works() {
+		let store = keystore();
+
+		generate_in_store::<ecdsa_crypto::AuthorityId>(
+			store.clone(),
+			BEEFY_KEY_TYPE,
+			Some(Keyring::Alice),
+		);
+
+		let store: BeefyKeystore<ecdsa_crypto::AuthorityId> = Some(store).into();
+
+		let alice = Keyring::Alice.public();
+		let msg = b"are you involved or committed?";
+
+		let sig = store.sign_async(alice.clone(), msg.to_vec()).await.unwrap();
+		assert_eq!(sig, store.sign(&alice, msg).unwrap());
+
+		// errors are returned once the retries are exhausted.
+		let store: BeefyKeystore<ecdsa_crypto::AuthorityId> = None.into();
+		let err = store.sign_async(alice, msg.to_vec()).await.err().unwrap();
+		assert_eq!(err, Error::Keystore("no Keystore".to_string()));
+	}
+
 	fn verify_works<
 		AuthorityId: AuthorityIdBound + From<<<AuthorityId as AppCrypto>::Pair as AppCrypto>::Public>,
 	>()
@@ -311,6 +311,7 @@ where
 			key_store: self.key_store,
 			metrics: self.metrics,
 			persisted_state: self.persisted_state,
+			pending_vote: futures::future::Fuse::terminated(),
 			payload_provider,
 			sync,
 			comms,
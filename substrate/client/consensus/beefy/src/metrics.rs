@@ -20,7 +20,10 @@
 
 use crate::LOG_TARGET;
 use log::{debug, error};
-use prometheus::{register, Counter, Gauge, PrometheusError, Registry, U64};
+use prometheus::{
+	exponential_buckets, register, Counter, Gauge, Histogram, HistogramOpts, PrometheusError,
+	Registry, U64,
+};
 
 /// Helper trait for registering BEEFY metrics to Prometheus registry.
 pub(crate) trait PrometheusRegister<T: Sized = Self>: Sized {
@@ -61,6 +64,10 @@ pub struct VoterMetrics {
 	pub beefy_imported_justifications: Counter<U64>,
 	/// Number of justifications dropped due to full buffers
 	pub beefy_buffered_justifications_dropped: Counter<U64>,
+	/// Time taken by the keystore to sign our votes
+	pub beefy_signing_duration: Histogram,
+	/// Number of votes the keystore failed to sign
+	pub beefy_signing_failures: Counter<U64>,
 }
 
 impl PrometheusRegister for VoterMetrics {
@@ -157,6 +164,23 @@ impl PrometheusRegister for VoterMetrics {
 				)?,
 				registry,
 			)?,
+			beefy_signing_duration: register(
+				Histogram::with_opts(
+					HistogramOpts::new(
+						"substrate_beefy_signing_duration_seconds",
+						"Time taken by the keystore to sign our votes, retries included",
+					)
+					.buckets(exponential_buckets(0.001, 4.0, 8)?),
+				)?,
+				registry,
+			)?,
+			beefy_signing_failures: register(
+				Counter::new(
+					"substrate_beefy_signing_failures",
+					"Number of votes the keystore failed to sign",
+				)?,
+				registry,
+			)?,
 		})
 	}
 }
@@ -31,7 +31,11 @@ use crate::{
 	BeefyComms, BeefyVoterLinks, LOG_TARGET,
 };
 use codec::{Codec, Decode, DecodeAll, Encode};
-use futures::{stream::Fuse, FutureExt, StreamExt};
+use futures::{
+	future::{self, BoxFuture, FusedFuture},
+	stream::Fuse,
+	FutureExt, StreamExt,
+};
 use log::{debug, error, info, trace, warn};
 use sc_client_api::{Backend, FinalityNotification, FinalityNotifications, HeaderBackend};
 use sc_utils::notification::NotificationReceiver;
@@ -53,6 +57,7 @@ use std::{
 	collections::{BTreeMap, VecDeque},
 	fmt::Debug,
 	sync::Arc,
+	time::Instant,
 };
 
 /// Bound for the number of pending justifications - use 2400 - the max number
@@ -372,6 +377,11 @@ impl<B: Block> PersistedState<B> {
 	}
 }
 
+/// Our vote being signed by the keystore, terminated if there's none.
+pub(crate) type PendingVote<B> = future::Fuse<
+	BoxFuture<'static, Result<VoteMessage<NumberFor<B>, AuthorityId, Signature>, Error>>,
+>;
+
 /// A BEEFY worker/voter that follows the BEEFY protocol
 pub(crate) struct BeefyWorker<B: Block, BE, P, RuntimeApi, S, N> {
 	// utilities
@@ -393,6 +403,8 @@ pub(crate) struct BeefyWorker<B: Block, BE, P, RuntimeApi, S, N> {
 	pub pending_justifications: BTreeMap<NumberFor<B>, BeefyVersionedFinalityProof<B>>,
 	/// Persisted voter state.
 	pub persisted_state: PersistedState<B>,
+	/// Our vote being signed, the voter doesn't wait for the keystore.
+	pub pending_vote: PendingVote<B>,
 	/// BEEFY voter metrics
 	pub metrics: Option<VoterMetrics>,
 	/// Node runs under "Authority" role.
@@ -692,6 +704,10 @@ where
 
 	/// Decide if should vote, then vote.. or don't..
 	fn try_to_vote(&mut self) -> Result<(), Error> {
+		// Wait for the vote being signed, if any, before voting again.
+		if !self.pending_vote.is_terminated() {
+			return Ok(())
+		}
 		// Vote if there's now a new vote target.
 		if let Some(target) = self.voting_oracle().voting_target() {
 			metric_set!(self.metrics, beefy_should_vote_on, target);
@@ -702,9 +718,9 @@ where
 		Ok(())
 	}
 
-	/// Create and gossip Signed Commitment for block number `target_number`.
+	/// Create the commitment for block number `target_number`, and have it signed.
 	///
-	/// Also handle this self vote by calling `self.handle_vote()` for it.
+	/// The vote is handled by `self.handle_signed_vote()` once signed.
 	fn do_vote(&mut self, target_number: NumberFor<B>) -> Result<(), Error> {
 		debug!(target: LOG_TARGET, "🥩 Try voting on {}", target_number);
 
@@ -757,24 +773,46 @@ where
 		};
 
 		let commitment = Commitment { payload, block_number: target_number, validator_set_id };
-		let encoded_commitment = commitment.encode();
-
-		let signature = match self.key_store.sign(&authority_id, &encoded_commitment) {
-			Ok(sig) => sig,
-			Err(err) => {
-				warn!(target: LOG_TARGET, "🥩 Error signing commitment: {:?}", err);
-				return Ok(())
-			},
-		};
+		let key_store = self.key_store.clone();
+		let metrics = self.metrics.clone();
+		self.pending_vote = async move {
+			let encoded_commitment = commitment.encode();
+			let started = Instant::now();
+			let signature = key_store.sign_async(authority_id.clone(), encoded_commitment).await;
+			if let Some(metrics) = metrics.as_ref() {
+				metrics.beefy_signing_duration.observe(started.elapsed().as_secs_f64());
+			}
+			Ok(VoteMessage { commitment, id: authority_id, signature: signature? })
+		}
+		.boxed()
+		.fuse();
+		Ok(())
+	}
 
+	/// Gossip our vote once signed.
+	///
+	/// Also handle this self vote by calling `self.handle_vote()` for it.
+	fn handle_signed_vote(
+		&mut self,
+		vote: VoteMessage<NumberFor<B>, AuthorityId, Signature>,
+	) -> Result<(), Error> {
+		let target_number = vote.commitment.block_number;
 		trace!(
 			target: LOG_TARGET,
 			"🥩 Produced signature using {:?}, is_valid: {:?}",
-			authority_id,
-			BeefyKeystore::verify(&authority_id, &signature, &encoded_commitment)
+			vote.id,
+			BeefyKeystore::verify(&vote.id, &vote.signature, &vote.commitment.encode())
 		);
 
-		let vote = VoteMessage { commitment, id: authority_id, signature };
+		// The state may have moved on while the vote was being signed.
+		match self.voting_oracle().triage_round(target_number)? {
+			RoundAction::Process => (),
+			RoundAction::Enqueue | RoundAction::Drop => {
+				debug!(target: LOG_TARGET, "🥩 Dropping obsolete self vote for: {:?}", target_number);
+				return Ok(())
+			},
+		}
+
 		if let Some(finality_proof) = self.handle_vote(vote.clone()).map_err(|err| {
 			error!(target: LOG_TARGET, "🥩 Error handling self vote: {}", err);
 			err
@@ -920,6 +958,18 @@ where
 						break Error::FinalityProofGossipStreamTerminated;
 					}
 				},
+				// Then handle our vote once signed.
+				vote = self.pending_vote => {
+					match vote {
+						Ok(vote) => if let Err(err) = self.handle_signed_vote(vote) {
+							debug!(target: LOG_TARGET, "🥩 {}", err);
+						},
+						Err(err) => {
+							metric_inc!(self.metrics, beefy_signing_failures);
+							warn!(target: LOG_TARGET, "🥩 Error signing commitment: {:?}", err);
+						},
+					}
+				},
 				// Finally process incoming votes.
 				vote = votes.next() => {
 					if let Some(vote) = vote {
@@ -1176,6 +1226,7 @@ pub(crate) mod tests {
 			comms,
 			pending_justifications: BTreeMap::new(),
 			persisted_state,
+			pending_vote: future::Fuse::terminated(),
 			is_authority: true,
 		}
 	}
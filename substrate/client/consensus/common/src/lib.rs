@@ -21,6 +21,7 @@
 pub mod block_import;
 pub mod import_queue;
 pub mod metrics;
+mod signature_verifier;
 
 pub use block_import::{
 	BlockCheckParams, BlockImport, BlockImportParams, ForkChoiceStrategy, ImportResult,
@@ -31,6 +32,7 @@ pub use import_queue::{
 	import_single_block, BasicQueue, BlockImportError, BlockImportStatus, BoxBlockImport,
 	BoxJustificationImport, DefaultImportQueue, ImportQueue, IncomingBlock, Link, Verifier,
 };
+pub use signature_verifier::SignatureVerifier;
 
 mod longest_chain;
 
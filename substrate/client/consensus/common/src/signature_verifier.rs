@@ -0,0 +1,161 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Verification of many signatures spread over multiple threads.
+
+use std::{
+	num::NonZeroUsize,
+	sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+	thread,
+};
+
+/// Verifies signatures in batches on multiple threads.
+///
+/// Used for the justifications imported during sync, whose signatures are otherwise verified one
+/// after the other, which is slow for large validator sets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SignatureVerifier {
+	threads: NonZeroUsize,
+	batch_size: NonZeroUsize,
+}
+
+impl Default for SignatureVerifier {
+	fn default() -> Self {
+		Self::sequential()
+	}
+}
+
+impl SignatureVerifier {
+	/// Create a verifier using up to `threads` threads, each verifying `batch_size` signatures
+	/// at a time.
+	pub fn new(threads: NonZeroUsize, batch_size: NonZeroUsize) -> Self {
+		Self { threads, batch_size }
+	}
+
+	/// Create a verifier verifying the signatures on the calling thread.
+	pub fn sequential() -> Self {
+		Self { threads: NonZeroUsize::MIN, batch_size: NonZeroUsize::MAX }
+	}
+
+	/// Maximum number of threads verifying signatures.
+	pub fn threads(&self) -> NonZeroUsize {
+		self.threads
+	}
+
+	/// Number of signatures a thread verifies at a time.
+	pub fn batch_size(&self) -> NonZeroUsize {
+		self.batch_size
+	}
+
+	/// Whether `verify` holds for all the items.
+	///
+	/// Stops at the first batch containing an invalid item.
+	pub fn verify_all<T: Sync>(&self, items: &[T], verify: impl Fn(&T) -> bool + Sync) -> bool {
+		self.verify(items, verify, true) == items.len()
+	}
+
+	/// Number of items for which `verify` holds.
+	pub fn count_valid<T: Sync>(&self, items: &[T], verify: impl Fn(&T) -> bool + Sync) -> usize {
+		self.verify(items, verify, false)
+	}
+
+	fn verify<T: Sync>(
+		&self,
+		items: &[T],
+		verify: impl Fn(&T) -> bool + Sync,
+		stop_on_invalid: bool,
+	) -> usize {
+		let batch_size = self.batch_size.get();
+		let threads = self.threads.get().min(items.len().div_ceil(batch_size));
+		if threads <= 1 {
+			return if stop_on_invalid {
+				items.iter().take_while(|item| verify(item)).count()
+			} else {
+				items.iter().filter(|item| verify(item)).count()
+			}
+		}
+
+		let next_batch = AtomicUsize::new(0);
+		let found_invalid = AtomicBool::new(false);
+		let worker = || {
+			let mut valid = 0;
+			loop {
+				let start = next_batch.fetch_add(batch_size, Ordering::Relaxed);
+				if start >= items.len() || found_invalid.load(Ordering::Relaxed) {
+					return valid
+				}
+
+				let batch = &items[start..items.len().min(start.saturating_add(batch_size))];
+				let batch_valid = batch.iter().filter(|item| verify(item)).count();
+				if stop_on_invalid && batch_valid < batch.len() {
+					found_invalid.store(true, Ordering::Relaxed);
+				}
+				valid += batch_valid;
+			}
+		};
+
+		thread::scope(|scope| {
+			let workers = (0..threads)
+				.map(|_| {
+					thread::Builder::new()
+						.name("signature-verifier".into())
+						.spawn_scoped(scope, worker)
+				})
+				.collect::<Vec<_>>();
+
+			workers
+				.into_iter()
+				.map(|handle| match handle {
+					Ok(handle) =>
+						handle.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic)),
+					// Verify the batches left on this thread if no thread could be spawned.
+					Err(_) => worker(),
+				})
+				.sum()
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn verifier(threads: usize, batch_size: usize) -> SignatureVerifier {
+		SignatureVerifier::new(
+			NonZeroUsize::new(threads).unwrap(),
+			NonZeroUsize::new(batch_size).unwrap(),
+		)
+	}
+
+	#[test]
+	fn counts_valid_items() {
+		let items = (0..1000).collect::<Vec<u32>>();
+
+		for verifier in [SignatureVerifier::sequential(), verifier(4, 7), verifier(16, 1000)] {
+			assert_eq!(verifier.count_valid(&items, |item| item % 3 == 0), 334);
+			assert!(verifier.verify_all(&items, |item| *item < 1000));
+			assert!(!verifier.verify_all(&items, |item| *item != 999));
+		}
+	}
+
+	#[test]
+	fn handles_no_items() {
+		assert_eq!(verifier(4, 7).count_valid(&[] as &[u32], |_| true), 0);
+		assert!(verifier(4, 7).verify_all(&[] as &[u32], |_| false));
+	}
+}
@@ -29,8 +29,8 @@
 //! In the future, there will be a fallback for allowing sending the same message
 //! under certain conditions that are used to un-stick the protocol.
 
-use futures::{channel::mpsc, prelude::*};
-use log::{debug, trace};
+use futures::{channel::mpsc, future::BoxFuture, prelude::*};
+use log::{debug, trace, warn};
 use parking_lot::Mutex;
 use prometheus_endpoint::Registry;
 use std::{
@@ -65,6 +65,7 @@ use sp_consensus_grandpa::{AuthorityId, AuthoritySignature, RoundNumber, SetId a
 
 pub mod gossip;
 mod periodic;
+mod signing;
 
 #[cfg(test)]
 pub(crate) mod tests;
@@ -136,6 +137,7 @@ mod benefit {
 
 /// A type that ties together our local authority id and a keystore where it is
 /// available for signing.
+#[derive(Clone)]
 pub struct LocalIdKeystore((AuthorityId, KeystorePtr));
 
 impl LocalIdKeystore {
@@ -234,6 +236,9 @@ pub(crate) struct NetworkBridge<B: BlockT, N: Network<B>, S: Syncing<B>> {
 	// multi-producer-*multi*-consumer channel implementation.
 	gossip_validator_report_stream: Arc<Mutex<TracingUnboundedReceiver<PeerReport>>>,
 
+	/// Metrics of the signing of our votes.
+	signing_metrics: Option<signing::Metrics>,
+
 	telemetry: Option<TelemetryHandle>,
 }
 
@@ -304,6 +309,15 @@ impl<B: BlockT, N: Network<B>, S: Syncing<B>> NetworkBridge<B, N, S> {
 		let (neighbor_packet_worker, neighbor_packet_sender) =
 			periodic::NeighborPacketWorker::new(NEIGHBOR_REBROADCAST_PERIOD);
 
+		let signing_metrics = match prometheus_registry.map(signing::Metrics::register) {
+			Some(Ok(metrics)) => Some(metrics),
+			Some(Err(e)) => {
+				debug!(target: LOG_TARGET, "Failed to register metrics: {:?}", e);
+				None
+			},
+			None => None,
+		};
+
 		NetworkBridge {
 			service,
 			sync,
@@ -312,6 +326,7 @@ impl<B: BlockT, N: Network<B>, S: Syncing<B>> NetworkBridge<B, N, S> {
 			neighbor_sender: neighbor_packet_sender,
 			neighbor_packet_worker: Arc::new(Mutex::new(neighbor_packet_worker)),
 			gossip_validator_report_stream: Arc::new(Mutex::new(report_stream)),
+			signing_metrics,
 			telemetry,
 		}
 	}
@@ -426,9 +441,14 @@ impl<B: BlockT, N: Network<B>, S: Syncing<B>> NetworkBridge<B, N, S> {
 			network: self.gossip_engine.clone(),
 			sender: tx,
 			has_voted,
+			signing_metrics: self.signing_metrics.clone(),
 			telemetry: self.telemetry.clone(),
 		};
 
+		// Our votes are signed, and gossiped, while polling the incoming stream, the voter
+		// doesn't wait for the keystore to send its next messages.
+		let out_rx = out_rx.then(|vote| vote).filter_map(future::ready);
+
 		// Combine incoming votes from external GRANDPA nodes with outgoing
 		// votes from our own GRANDPA voter to have a single
 		// vote-import-pipeline.
@@ -688,6 +708,7 @@ impl<B: BlockT, N: Network<B>, S: Syncing<B>> Clone for NetworkBridge<B, N, S> {
 			neighbor_sender: self.neighbor_sender.clone(),
 			neighbor_packet_worker: self.neighbor_packet_worker.clone(),
 			gossip_validator_report_stream: self.gossip_validator_report_stream.clone(),
+			signing_metrics: self.signing_metrics.clone(),
 			telemetry: self.telemetry.clone(),
 		}
 	}
@@ -712,9 +733,10 @@ pub(crate) struct OutgoingMessages<Block: BlockT> {
 	round: RoundNumber,
 	set_id: SetIdNumber,
 	keystore: Option<LocalIdKeystore>,
-	sender: mpsc::Sender<SignedMessage<Block::Header>>,
+	sender: mpsc::Sender<BoxFuture<'static, Option<SignedMessage<Block::Header>>>>,
 	network: Arc<Mutex<GossipEngine<Block>>>,
 	has_voted: HasVoted<Block::Header>,
+	signing_metrics: Option<signing::Metrics>,
 	telemetry: Option<TelemetryHandle>,
 }
 
@@ -756,51 +778,66 @@ impl<Block: BlockT> Sink<Message<Block::Header>> for OutgoingMessages<Block> {
 
 		// when locals exist, sign messages on import
 		if let Some(ref keystore) = self.keystore {
+			let (round, set_id) = (self.round, self.set_id);
 			let target_hash = *(msg.target().0);
-			let signed = sp_consensus_grandpa::sign_message(
-				keystore.keystore(),
+			let signing = signing::sign_message(
+				keystore.clone(),
 				msg,
-				keystore.local_id().clone(),
-				self.round,
-				self.set_id,
-			)
-			.ok_or_else(|| {
-				Error::Signing(format!(
-					"Failed to sign GRANDPA vote for round {} targeting {:?}",
-					self.round, target_hash
-				))
-			})?;
-
-			let message = GossipMessage::Vote(VoteMessage::<Block> {
-				message: signed.clone(),
-				round: Round(self.round),
-				set_id: SetId(self.set_id),
-			});
-
-			debug!(
-				target: LOG_TARGET,
-				"Announcing block {} to peers which we voted on in round {} in set {}",
-				target_hash,
-				self.round,
-				self.set_id,
+				round,
+				set_id,
+				self.signing_metrics.clone(),
 			);
+			let network = self.network.clone();
+			let telemetry = self.telemetry.clone();
+
+			let vote = async move {
+				let signed = match signing.await {
+					Ok(signed) => signed,
+					Err(e) => {
+						warn!(
+							target: LOG_TARGET,
+							"Failed to sign GRANDPA vote for round {} targeting {:?}: {}",
+							round,
+							target_hash,
+							e,
+						);
+						return None
+					},
+				};
 
-			telemetry!(
-				self.telemetry;
-				CONSENSUS_DEBUG;
-				"afg.announcing_blocks_to_voted_peers";
-				"block" => ?target_hash, "round" => ?self.round, "set_id" => ?self.set_id,
-			);
+				let message = GossipMessage::Vote(VoteMessage::<Block> {
+					message: signed.clone(),
+					round: Round(round),
+					set_id: SetId(set_id),
+				});
 
-			// announce the block we voted on to our peers.
-			self.network.lock().announce(target_hash, None);
+				debug!(
+					target: LOG_TARGET,
+					"Announcing block {} to peers which we voted on in round {} in set {}",
+					target_hash,
+					round,
+					set_id,
+				);
 
-			// propagate the message to peers
-			let topic = round_topic::<Block>(self.round, self.set_id);
-			self.network.lock().gossip_message(topic, message.encode(), false);
+				telemetry!(
+					telemetry;
+					CONSENSUS_DEBUG;
+					"afg.announcing_blocks_to_voted_peers";
+					"block" => ?target_hash, "round" => ?round, "set_id" => ?set_id,
+				);
+
+				// announce the block we voted on to our peers.
+				network.lock().announce(target_hash, None);
+
+				// propagate the message to peers
+				let topic = round_topic::<Block>(round, set_id);
+				network.lock().gossip_message(topic, message.encode(), false);
+
+				Some(signed)
+			};
 
-			// forward the message to the inner sender.
-			return self.sender.start_send(signed).map_err(|e| {
+			// forward the vote to the inner sender, it's signed on the way.
+			return self.sender.start_send(vote.boxed()).map_err(|e| {
 				Error::Network(format!("Failed to start_send on channel sender: {:?}", e))
 			})
 		};
@@ -0,0 +1,137 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Signing of the local votes.
+//!
+//! The keystore is called from a dedicated thread, so that keystores slow to sign, e.g. the ones
+//! backed by a remote signer, don't stall the voter. Each attempt is given [`SIGNING_TIMEOUT`] to
+//! complete, and is retried up to [`SIGNING_RETRIES`] times.
+
+use futures::{
+	channel::oneshot,
+	future::{self, Either},
+};
+use futures_timer::Delay;
+use log::warn;
+use prometheus_endpoint::{
+	exponential_buckets, register, Counter, Histogram, HistogramOpts, PrometheusError, Registry,
+	U64,
+};
+use sp_consensus_grandpa::{RoundNumber, SetId as SetIdNumber};
+use sp_runtime::traits::Header as HeaderT;
+use std::{
+	thread,
+	time::{Duration, Instant},
+};
+
+use super::LocalIdKeystore;
+use crate::{Error, Message, SignedMessage, LOG_TARGET};
+
+/// Time given to the keystore to sign a vote before the attempt is abandoned.
+pub(crate) const SIGNING_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Number of times signing a vote is retried after a failed or timed out attempt.
+pub(crate) const SIGNING_RETRIES: usize = 2;
+
+/// Prometheus metrics of the signing of the local votes.
+#[derive(Clone)]
+pub(crate) struct Metrics {
+	signing_duration: Histogram,
+	signing_failures: Counter<U64>,
+}
+
+impl Metrics {
+	pub(crate) fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		Ok(Self {
+			signing_duration: register(
+				Histogram::with_opts(
+					HistogramOpts::new(
+						"substrate_finality_grandpa_signing_duration_seconds",
+						"Time taken by the keystore to sign the local votes, retries included.",
+					)
+					.buckets(exponential_buckets(0.001, 4.0, 8)?),
+				)?,
+				registry,
+			)?,
+			signing_failures: register(
+				Counter::new(
+					"substrate_finality_grandpa_signing_failures_total",
+					"Total number of local votes the keystore failed to sign.",
+				)?,
+				registry,
+			)?,
+		})
+	}
+}
+
+/// Sign `message` with our local authority id for the given round and set.
+pub(crate) async fn sign_message<H: HeaderT>(
+	keystore: LocalIdKeystore,
+	message: Message<H>,
+	round: RoundNumber,
+	set_id: SetIdNumber,
+	metrics: Option<Metrics>,
+) -> Result<SignedMessage<H>, Error> {
+	let started = Instant::now();
+	let mut attempt = 0;
+	let result = loop {
+		let (tx, rx) = oneshot::channel();
+		let (keystore, message) = (keystore.clone(), message.clone());
+		thread::Builder::new()
+			.name("grandpa-signer".into())
+			.spawn(move || {
+				let signed = sp_consensus_grandpa::sign_message(
+					keystore.keystore(),
+					message,
+					keystore.local_id().clone(),
+					round,
+					set_id,
+				);
+				let _ = tx.send(signed);
+			})
+			.map_err(|e| Error::Signing(e.to_string()))?;
+
+		let result = match future::select(rx, Delay::new(SIGNING_TIMEOUT)).await {
+			Either::Left((Ok(Some(signed)), _)) => Ok(signed),
+			Either::Left((Ok(None), _)) => Err(Error::Signing(format!(
+				"Failed to sign GRANDPA vote for round {} in set {}",
+				round, set_id
+			))),
+			Either::Left((Err(_), _)) => Err(Error::Signing("Signer thread terminated".into())),
+			Either::Right(_) => Err(Error::Signing(format!(
+				"Signing GRANDPA vote for round {} in set {} timed out after {:?}",
+				round, set_id, SIGNING_TIMEOUT
+			))),
+		};
+		match result {
+			Err(err) if attempt < SIGNING_RETRIES => {
+				attempt += 1;
+				warn!(target: LOG_TARGET, "Signing attempt {} failed: {}, retrying", attempt, err);
+			},
+			result => break result,
+		}
+	};
+
+	if let Some(metrics) = metrics {
+		metrics.signing_duration.observe(started.elapsed().as_secs_f64());
+		if result.is_err() {
+			metrics.signing_failures.inc();
+		}
+	}
+	result
+}
@@ -751,3 +751,36 @@ fn grandpa_protocol_name() {
 	let proto_name = grandpa_protocol_name::standard_name(&genesis_hash, &chain_spec);
 	assert_eq!(proto_name.to_string(), expected);
 }
+
+#[test]
+fn local_votes_are_signed_off_the_voter() {
+	use sp_keystore::{testing::MemoryKeystore, Keystore, KeystorePtr};
+
+	let keystore = MemoryKeystore::new();
+	let public = keystore
+		.ed25519_generate_new(
+			sp_consensus_grandpa::KEY_TYPE,
+			Some(&Ed25519Keyring::Alice.to_seed()),
+		)
+		.unwrap();
+	let local_id: sp_consensus_grandpa::AuthorityId = public.into();
+	let keystore: super::LocalIdKeystore =
+		(local_id.clone(), Arc::new(keystore) as KeystorePtr).into();
+
+	let prevote = finality_grandpa::Prevote { target_hash: Hash::random(), target_number: 42 };
+	let message = finality_grandpa::Message::Prevote(prevote);
+	let signed = futures::executor::block_on(super::signing::sign_message::<
+		<Block as sp_runtime::traits::Block>::Header,
+	>(keystore, message.clone(), 1, 0, None))
+	.unwrap();
+
+	assert_eq!(signed.id, local_id);
+	assert_eq!(signed.message, message);
+	assert!(sp_consensus_grandpa::check_message_signature(
+		&signed.message,
+		&signed.id,
+		&signed.signature,
+		1,
+		0,
+	));
+}
@@ -292,7 +292,7 @@ mod tests {
 			Decode::decode(&mut &proof.justification[..])
 				.map_err(|_| ClientError::JustificationDecode)?;
 
-		justification.verify(current_set_id, &current_authorities)?;
+		justification.verify(current_set_id, &current_authorities, &Default::default())?;
 
 		Ok(proof)
 	}
@@ -25,6 +25,7 @@ use sc_client_api::{backend::Backend, utils::is_descendent_of};
 use sc_consensus::{
 	shared_data::{SharedDataLocked, SharedDataLockedUpgradable},
 	BlockCheckParams, BlockImport, BlockImportParams, ImportResult, JustificationImport,
+	SignatureVerifier,
 };
 use sc_telemetry::TelemetryHandle;
 use sc_utils::mpsc::TracingUnboundedSender;
@@ -64,6 +65,7 @@ pub struct GrandpaBlockImport<Backend, Block: BlockT, Client, SC> {
 	send_voter_commands: TracingUnboundedSender<VoterCommand<Block::Hash, NumberFor<Block>>>,
 	authority_set_hard_forks: HashMap<Block::Hash, PendingChange<Block::Hash, NumberFor<Block>>>,
 	justification_sender: GrandpaJustificationSender<Block>,
+	justification_verifier: SignatureVerifier,
 	telemetry: Option<TelemetryHandle>,
 	_phantom: PhantomData<Backend>,
 }
@@ -80,6 +82,7 @@ impl<Backend, Block: BlockT, Client, SC: Clone> Clone
 			send_voter_commands: self.send_voter_commands.clone(),
 			authority_set_hard_forks: self.authority_set_hard_forks.clone(),
 			justification_sender: self.justification_sender.clone(),
+			justification_verifier: self.justification_verifier,
 			telemetry: self.telemetry.clone(),
 			_phantom: PhantomData,
 		}
@@ -752,10 +755,19 @@ impl<Backend, Block: BlockT, Client, SC> GrandpaBlockImport<Backend, Block, Clie
 			send_voter_commands,
 			authority_set_hard_forks,
 			justification_sender,
+			justification_verifier: SignatureVerifier::default(),
 			telemetry,
 			_phantom: PhantomData,
 		}
 	}
+
+	/// Verify the signatures of the imported justifications with the given verifier.
+	///
+	/// By default the signatures are verified one after the other.
+	pub fn with_justification_verifier(mut self, verifier: SignatureVerifier) -> Self {
+		self.justification_verifier = verifier;
+		self
+	}
 }
 
 impl<BE, Block: BlockT, Client, SC> GrandpaBlockImport<BE, Block, Client, SC>
@@ -790,6 +802,7 @@ where
 			(hash, number),
 			self.authority_set.set_id(),
 			&self.authority_set.current_authorities(),
+			&self.justification_verifier,
 		);
 
 		let justification = match justification {
@@ -24,6 +24,7 @@ use std::{
 
 use finality_grandpa::{voter_set::VoterSet, Error as GrandpaError};
 use parity_scale_codec::{Decode, DecodeAll, Encode};
+use sc_consensus::SignatureVerifier;
 use sp_blockchain::{Error as ClientError, HeaderBackend};
 use sp_consensus_grandpa::AuthorityId;
 use sp_runtime::traits::{Block as BlockT, Header as HeaderT, NumberFor};
@@ -132,6 +133,7 @@ impl<Block: BlockT> GrandpaJustification<Block> {
 		finalized_target: (Block::Hash, NumberFor<Block>),
 		set_id: u64,
 		voters: &VoterSet<AuthorityId>,
+		verifier: &SignatureVerifier,
 	) -> Result<Self, ClientError>
 	where
 		NumberFor<Block>: finality_grandpa::BlockNumberOps,
@@ -147,19 +149,26 @@ impl<Block: BlockT> GrandpaJustification<Block> {
 			let msg = "invalid commit target in grandpa justification".to_string();
 			Err(ClientError::BadJustification(msg))
 		} else {
-			justification.verify_with_voter_set(set_id, voters).map(|_| justification)
+			justification
+				.verify_with_voter_set(set_id, voters, verifier)
+				.map(|_| justification)
 		}
 	}
 
 	/// Validate the commit and the votes' ancestry proofs.
-	pub fn verify(&self, set_id: u64, authorities: &AuthorityList) -> Result<(), ClientError>
+	pub fn verify(
+		&self,
+		set_id: u64,
+		authorities: &AuthorityList,
+		verifier: &SignatureVerifier,
+	) -> Result<(), ClientError>
 	where
 		NumberFor<Block>: finality_grandpa::BlockNumberOps,
 	{
 		let voters = VoterSet::new(authorities.iter().cloned())
 			.ok_or(ClientError::Consensus(sp_consensus::Error::InvalidAuthoritiesSet))?;
 
-		self.verify_with_voter_set(set_id, &voters)
+		self.verify_with_voter_set(set_id, &voters, verifier)
 	}
 
 	/// Validate the commit and the votes' ancestry proofs.
@@ -167,6 +176,7 @@ impl<Block: BlockT> GrandpaJustification<Block> {
 		&self,
 		set_id: u64,
 		voters: &VoterSet<AuthorityId>,
+		verifier: &SignatureVerifier,
 	) -> Result<(), ClientError>
 	where
 		NumberFor<Block>: finality_grandpa::BlockNumberOps,
@@ -202,22 +212,23 @@ impl<Block: BlockT> GrandpaJustification<Block> {
 				 qed.",
 			);
 
-		let mut buf = Vec::new();
-		let mut visited_hashes = HashSet::new();
-		for signed in self.justification.commit.precommits.iter() {
-			if !sp_consensus_grandpa::check_message_signature_with_buffer(
+		let round = self.justification.round;
+		if !verifier.verify_all(&self.justification.commit.precommits, |signed| {
+			sp_consensus_grandpa::check_message_signature(
 				&finality_grandpa::Message::Precommit(signed.precommit.clone()),
 				&signed.id,
 				&signed.signature,
-				self.justification.round,
+				round,
 				set_id,
-				&mut buf,
-			) {
-				return Err(ClientError::BadJustification(
-					"invalid signature for precommit in grandpa justification".to_string(),
-				))
-			}
+			)
+		}) {
+			return Err(ClientError::BadJustification(
+				"invalid signature for precommit in grandpa justification".to_string(),
+			))
+		}
 
+		let mut visited_hashes = HashSet::new();
+		for signed in self.justification.commit.precommits.iter() {
 			if base_hash == signed.precommit.target_hash {
 				continue
 			}
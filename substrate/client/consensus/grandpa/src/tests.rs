@@ -1959,7 +1959,7 @@ async fn justification_with_equivocation() {
 
 	// the justification should include the minimal necessary vote ancestry and
 	// the commit should be valid
-	assert!(justification.verify(set_id, &voters).is_ok());
+	assert!(justification.verify(set_id, &voters, &Default::default()).is_ok());
 }
 
 #[tokio::test]
@@ -23,6 +23,7 @@ use crate::{
 	BlockNumberOps, GrandpaJustification, SharedAuthoritySet,
 };
 use sc_client_api::Backend as ClientBackend;
+use sc_consensus::SignatureVerifier;
 use sc_network_sync::strategy::warp::{EncodedProof, VerificationResult, WarpSyncProvider};
 use sp_blockchain::{Backend as BlockchainBackend, HeaderBackend};
 use sp_consensus_grandpa::{AuthorityList, SetId, GRANDPA_ENGINE_ID};
@@ -193,6 +194,7 @@ impl<Block: BlockT> WarpSyncProof<Block> {
 		set_id: SetId,
 		authorities: AuthorityList,
 		hard_forks: &HashMap<(Block::Hash, NumberFor<Block>), (SetId, AuthorityList)>,
+		verifier: &SignatureVerifier,
 	) -> Result<(SetId, AuthorityList), Error>
 	where
 		NumberFor<Block>: BlockNumberOps,
@@ -210,7 +212,7 @@ impl<Block: BlockT> WarpSyncProof<Block> {
 			} else {
 				proof
 					.justification
-					.verify(current_set_id, &current_authorities)
+					.verify(current_set_id, &current_authorities, verifier)
 					.map_err(|err| Error::InvalidProof(err.to_string()))?;
 
 				if proof.justification.target().1 != hash {
@@ -243,6 +245,7 @@ where
 	backend: Arc<Backend>,
 	authority_set: SharedAuthoritySet<Block::Hash, NumberFor<Block>>,
 	hard_forks: HashMap<(Block::Hash, NumberFor<Block>), (SetId, AuthorityList)>,
+	justification_verifier: SignatureVerifier,
 }
 
 impl<Block: BlockT, Backend: ClientBackend<Block>> NetworkProvider<Block, Backend>
@@ -262,8 +265,17 @@ where
 				.into_iter()
 				.map(|fork| (fork.block, (fork.set_id, fork.authorities)))
 				.collect(),
+			justification_verifier: SignatureVerifier::default(),
 		}
 	}
+
+	/// Verify the signatures of the justifications of the proofs with the given verifier.
+	///
+	/// By default the signatures are verified one after the other.
+	pub fn with_justification_verifier(mut self, verifier: SignatureVerifier) -> Self {
+		self.justification_verifier = verifier;
+		self
+	}
 }
 
 impl<Block: BlockT, Backend: ClientBackend<Block>> WarpSyncProvider<Block>
@@ -298,8 +310,9 @@ where
 			.last()
 			.map(|p| p.header.clone())
 			.ok_or_else(|| "Empty proof".to_string())?;
-		let (next_set_id, next_authorities) =
-			proof.verify(set_id, authorities, &self.hard_forks).map_err(Box::new)?;
+		let (next_set_id, next_authorities) = proof
+			.verify(set_id, authorities, &self.hard_forks, &self.justification_verifier)
+			.map_err(Box::new)?;
 		if proof.is_finished {
 			Ok(VerificationResult::<Block>::Complete(next_set_id, next_authorities, last_header))
 		} else {
@@ -435,8 +448,9 @@ mod tests {
 			WarpSyncProof::generate(&*backend, genesis_hash, &authority_set_changes).unwrap();
 
 		// verifying the proof should yield the last set id and authorities
-		let (new_set_id, new_authorities) =
-			warp_sync_proof.verify(0, genesis_authorities, &Default::default()).unwrap();
+		let (new_set_id, new_authorities) = warp_sync_proof
+			.verify(0, genesis_authorities, &Default::default(), &Default::default())
+			.unwrap();
 
 		let expected_authorities = current_authorities
 			.iter()
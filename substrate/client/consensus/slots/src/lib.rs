@@ -40,7 +40,7 @@ use sc_telemetry::{telemetry, TelemetryHandle, CONSENSUS_DEBUG, CONSENSUS_INFO,
 use sp_arithmetic::traits::BaseArithmetic;
 use sp_consensus::{Proposal, Proposer, SelectChain, SyncOracle};
 use sp_consensus_slots::{Slot, SlotDuration};
-use sp_inherents::CreateInherentDataProviders;
+use sp_inherents::{CreateInherentDataProviders, HealthSeverity};
 use sp_runtime::traits::{Block as BlockT, HashingFor, Header as HeaderT};
 use std::{
 	fmt::Debug,
@@ -247,11 +247,43 @@ pub trait SimpleSlotWorker<B: BlockT> {
 	}
 
 	/// Calls `create_inherent_data` and handles errors.
+	///
+	/// The health issues of the inherent data providers are reported, no inherent data is created
+	/// if any of them is not ready.
 	async fn create_inherent_data(
 		slot_info: &SlotInfo<B>,
 		logging_target: &str,
 		end_proposing_at: Instant,
 	) -> Option<sp_inherents::InherentData> {
+		let mut ready = true;
+		for issue in slot_info.create_inherent_data.check_health().await {
+			match issue.severity {
+				HealthSeverity::Stale => warn!(
+					target: logging_target,
+					"Inherent data for slot {} is stale: {}",
+					slot_info.slot,
+					issue,
+				),
+				HealthSeverity::NotReady => {
+					warn!(
+						target: logging_target,
+						"Inherent data for slot {} is not ready: {}",
+						slot_info.slot,
+						issue,
+					);
+					ready = false;
+				},
+			}
+		}
+
+		if !ready {
+			info!(
+				target: logging_target,
+				"Skipping slot {}, the inherent data is not ready", slot_info.slot,
+			);
+			return None
+		}
+
 		let remaining_duration = end_proposing_at.saturating_duration_since(Instant::now());
 		let delay = Delay::new(remaining_duration);
 		let cid = slot_info.create_inherent_data.create_inherent_data();
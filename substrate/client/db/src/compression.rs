@@ -0,0 +1,170 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Compression of the block bodies and justifications.
+//!
+//! The values are compressed with zstd and prefixed with [`COMPRESSED_PREFIX`], which can't start
+//! the SCALE encoding of a vector. The values stored uncompressed, e.g. before the compression was
+//! enabled, are thus read as they are, whatever the configuration.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use sc_client_api::CompressionInfo;
+use sp_blockchain::{Error as ClientError, Result as ClientResult};
+
+/// Prefix of the compressed values.
+///
+/// The length of a vector is a `Compact<u32>`, whose encoding never starts with this byte.
+const COMPRESSED_PREFIX: u8 = 0xff;
+
+/// Compression of the block bodies and justifications stored in the database.
+///
+/// The values stored compressed can't be read by the nodes not supporting it. Ignored with
+/// ParityDb, whose columns of the bodies and justifications are compressed with LZ4.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BlockCompression {
+	/// zstd level the block bodies are compressed at, `None` to store them uncompressed.
+	pub bodies: Option<i32>,
+	/// zstd level the justifications are compressed at, `None` to store them uncompressed.
+	pub justifications: Option<i32>,
+}
+
+/// Sizes of the values of a column written compressed.
+#[derive(Debug, Default)]
+struct ColumnStats {
+	uncompressed: AtomicU64,
+	compressed: AtomicU64,
+}
+
+impl ColumnStats {
+	fn record(&self, uncompressed: usize, compressed: usize) {
+		self.uncompressed.fetch_add(uncompressed as u64, Ordering::Relaxed);
+		self.compressed.fetch_add(compressed as u64, Ordering::Relaxed);
+	}
+
+	fn info(&self) -> CompressionInfo {
+		CompressionInfo {
+			uncompressed_bytes: self.uncompressed.load(Ordering::Relaxed),
+			compressed_bytes: self.compressed.load(Ordering::Relaxed),
+		}
+	}
+}
+
+/// Compresses the block bodies and justifications as configured, and measures the ratio.
+#[derive(Debug, Default)]
+pub(crate) struct Compressor {
+	config: BlockCompression,
+	bodies: ColumnStats,
+	justifications: ColumnStats,
+}
+
+impl Compressor {
+	pub fn new(config: BlockCompression) -> Self {
+		Self { config, ..Default::default() }
+	}
+
+	/// The value to store for an encoded block body.
+	pub fn body(&self, body: Vec<u8>) -> Vec<u8> {
+		compress(self.config.bodies, &self.bodies, body)
+	}
+
+	/// The value to store for encoded justifications.
+	pub fn justifications(&self, justifications: Vec<u8>) -> Vec<u8> {
+		compress(self.config.justifications, &self.justifications, justifications)
+	}
+
+	/// Sizes of the block bodies written compressed.
+	pub fn bodies_info(&self) -> CompressionInfo {
+		self.bodies.info()
+	}
+
+	/// Sizes of the justifications written compressed.
+	pub fn justifications_info(&self) -> CompressionInfo {
+		self.justifications.info()
+	}
+}
+
+fn compress(level: Option<i32>, stats: &ColumnStats, value: Vec<u8>) -> Vec<u8> {
+	let Some(level) = level else { return value };
+
+	let mut compressed = vec![COMPRESSED_PREFIX];
+	match zstd::bulk::compress(&value, level) {
+		Ok(value) => compressed.extend(value),
+		Err(e) => {
+			log::warn!(target: "db", "Failed to compress a value: {e}");
+			stats.record(value.len(), value.len());
+			return value
+		},
+	}
+
+	// The values that don't compress well are stored as they are.
+	if compressed.len() < value.len() {
+		stats.record(value.len(), compressed.len());
+		compressed
+	} else {
+		stats.record(value.len(), value.len());
+		value
+	}
+}
+
+/// The encoded value of a stored block body or justifications.
+pub(crate) fn decompress(value: Vec<u8>) -> ClientResult<Vec<u8>> {
+	match value.split_first() {
+		Some((&COMPRESSED_PREFIX, compressed)) => zstd::stream::decode_all(compressed)
+			.map_err(|e| ClientError::Backend(format!("Error decompressing value: {e}"))),
+		_ => Ok(value),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use codec::Encode;
+
+	#[test]
+	fn compressed_values_are_decompressed() {
+		let compressor =
+			Compressor::new(BlockCompression { bodies: Some(3), justifications: None });
+		let body = vec![vec![0u8; 64]; 16].encode();
+
+		let stored = compressor.body(body.clone());
+		assert_eq!(stored[0], COMPRESSED_PREFIX);
+		assert!(stored.len() < body.len());
+		assert_eq!(decompress(stored.clone()).unwrap(), body);
+
+		let info = compressor.bodies_info();
+		assert_eq!(info.uncompressed_bytes, body.len() as u64);
+		assert_eq!(info.compressed_bytes, stored.len() as u64);
+	}
+
+	#[test]
+	fn uncompressed_values_are_read_as_they_are() {
+		let compressor = Compressor::new(Default::default());
+		let justifications = vec![(*b"FRNK", vec![1u8, 2, 3])].encode();
+
+		let stored = compressor.justifications(justifications.clone());
+		assert_eq!(stored, justifications);
+		assert_eq!(decompress(stored).unwrap(), justifications);
+		assert_eq!(compressor.justifications_info().uncompressed_bytes, 0);
+
+		// Too small to be compressed.
+		let compressor =
+			Compressor::new(BlockCompression { bodies: None, justifications: Some(3) });
+		assert_eq!(compressor.justifications(justifications.clone()), justifications);
+	}
+}
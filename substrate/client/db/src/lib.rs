@@ -33,6 +33,7 @@ pub mod offchain;
 pub mod bench;
 
 mod children;
+mod compression;
 mod parity_db;
 mod pinned_blocks_cache;
 mod record_stats_state;
@@ -52,10 +53,11 @@ use std::{
 };
 
 use crate::{
+	compression::Compressor,
 	pinned_blocks_cache::PinnedBlocksCache,
 	record_stats_state::RecordStatsState,
 	stats::StateUsageStats,
-	utils::{meta_keys, read_db, read_meta, DatabaseType, Meta},
+	utils::{meta_keys, read_compressed_db, read_meta, DatabaseType, Meta},
 };
 use codec::{Decode, Encode};
 use hash_db::Prefix;
@@ -97,6 +99,7 @@ pub use sc_state_db::PruningMode;
 pub use sp_database::Database;
 
 pub use bench::BenchmarkingState;
+pub use compression::BlockCompression;
 
 const CACHE_HEADERS: usize = 8;
 
@@ -310,6 +313,10 @@ pub struct DatabaseSettings {
 	///
 	/// NOTE: only finalized blocks are subject for removal!
 	pub blocks_pruning: BlocksPruning,
+	/// Compression of the block bodies and justifications.
+	///
+	/// Only applies to RocksDB, ParityDb compressing these columns already.
+	pub block_compression: BlockCompression,
 }
 
 /// Block pruning settings.
@@ -584,7 +591,7 @@ impl<Block: BlockT> BlockchainDb<Block> {
 	}
 
 	fn justifications_uncached(&self, hash: Block::Hash) -> ClientResult<Option<Justifications>> {
-		match read_db(
+		match read_compressed_db(
 			&*self.db,
 			columns::KEY_LOOKUP,
 			columns::JUSTIFICATIONS,
@@ -603,9 +610,12 @@ impl<Block: BlockT> BlockchainDb<Block> {
 	}
 
 	fn body_uncached(&self, hash: Block::Hash) -> ClientResult<Option<Vec<Block::Extrinsic>>> {
-		if let Some(body) =
-			read_db(&*self.db, columns::KEY_LOOKUP, columns::BODY, BlockId::Hash::<Block>(hash))?
-		{
+		if let Some(body) = read_compressed_db(
+			&*self.db,
+			columns::KEY_LOOKUP,
+			columns::BODY,
+			BlockId::Hash::<Block>(hash),
+		)? {
 			// Plain body
 			match Decode::decode(&mut &body[..]) {
 				Ok(body) => return Ok(Some(body)),
@@ -617,7 +627,7 @@ impl<Block: BlockT> BlockchainDb<Block> {
 			}
 		}
 
-		if let Some(index) = read_db(
+		if let Some(index) = read_compressed_db(
 			&*self.db,
 			columns::KEY_LOOKUP,
 			columns::BODY_INDEX,
@@ -773,7 +783,7 @@ impl<Block: BlockT> sc_client_api::blockchain::Backend<Block> for BlockchainDb<B
 	}
 
 	fn block_indexed_body(&self, hash: Block::Hash) -> ClientResult<Option<Vec<Vec<u8>>>> {
-		let body = match read_db(
+		let body = match read_compressed_db(
 			&*self.db,
 			columns::KEY_LOOKUP,
 			columns::BODY_INDEX,
@@ -1130,6 +1140,7 @@ pub struct Backend<Block: BlockT> {
 	state_usage: Arc<StateUsageStats>,
 	genesis_state: RwLock<Option<Arc<DbGenesisStorage<Block>>>>,
 	shared_trie_cache: Option<sp_trie::cache::SharedTrieCache<HashingFor<Block>>>,
+	compressor: Compressor,
 }
 
 impl<Block: BlockT> Backend<Block> {
@@ -1186,6 +1197,7 @@ impl<Block: BlockT> Backend<Block> {
 			state_pruning: Some(state_pruning),
 			source: DatabaseSource::Custom { db, require_create_flag: true },
 			blocks_pruning,
+			block_compression: Default::default(),
 		};
 
 		Self::new(db_setting, canonicalization_delay).expect("failed to create test-db")
@@ -1239,6 +1251,20 @@ impl<Block: BlockT> Backend<Block> {
 
 		let offchain_storage = offchain::LocalStorage::new(db.clone());
 
+		// ParityDb, the only database supporting ref counting, already compresses the block
+		// bodies and justifications with LZ4.
+		let block_compression = if db.supports_ref_counting() {
+			if config.block_compression != BlockCompression::default() {
+				log::warn!(
+					target: "db",
+					"Block compression is only supported with RocksDB, ignoring it with ParityDb",
+				);
+			}
+			BlockCompression::default()
+		} else {
+			config.block_compression
+		};
+
 		let backend = Backend {
 			storage: Arc::new(storage_db),
 			offchain_storage,
@@ -1253,6 +1279,7 @@ impl<Block: BlockT> Backend<Block> {
 			shared_trie_cache: config.trie_cache_maximum_size.map(|maximum_size| {
 				SharedTrieCache::new(sp_trie::cache::CacheSize::new(maximum_size))
 			}),
+			compressor: Compressor::new(block_compression),
 		};
 
 		// Older DB versions have no last state key. Check if the state is available and set it.
@@ -1396,7 +1423,8 @@ impl<Block: BlockT> Backend<Block> {
 			transaction.set_from_vec(
 				columns::JUSTIFICATIONS,
 				&utils::number_and_hash_to_lookup_key(number, hash)?,
-				Justifications::from(justification.clone()).encode(),
+				self.compressor
+					.justifications(Justifications::from(justification.clone()).encode()),
 			);
 			current_transaction_justifications.insert(hash, justification);
 		}
@@ -1509,11 +1537,19 @@ impl<Block: BlockT> Backend<Block> {
 				// If we have any index operations we save block in the new format with indexed
 				// extrinsic headers Otherwise we save the body as a single blob.
 				if operation.index_ops.is_empty() {
-					transaction.set_from_vec(columns::BODY, &lookup_key, body.encode());
+					transaction.set_from_vec(
+						columns::BODY,
+						&lookup_key,
+						self.compressor.body(body.encode()),
+					);
 				} else {
 					let body =
 						apply_index_ops::<Block>(&mut transaction, body, operation.index_ops);
-					transaction.set_from_vec(columns::BODY_INDEX, &lookup_key, body);
+					transaction.set_from_vec(
+						columns::BODY_INDEX,
+						&lookup_key,
+						self.compressor.body(body),
+					);
 				}
 			}
 			if let Some(body) = pending_block.indexed_body {
@@ -1523,7 +1559,7 @@ impl<Block: BlockT> Backend<Block> {
 				transaction.set_from_vec(
 					columns::JUSTIFICATIONS,
 					&lookup_key,
-					justifications.encode(),
+					self.compressor.justifications(justifications.encode()),
 				);
 			}
 
@@ -1714,7 +1750,8 @@ impl<Block: BlockT> Backend<Block> {
 						);
 					}
 				} else if number > best_num + One::one() &&
-					number > One::one() && self.blockchain.header(parent_hash)?.is_none()
+					number > One::one() &&
+					self.blockchain.header(parent_hash)?.is_none()
 				{
 					let gap = (best_num + One::one(), number - One::one());
 					transaction.set(columns::META, meta_keys::BLOCK_GAP, &gap.encode());
@@ -1911,7 +1948,7 @@ impl<Block: BlockT> Backend<Block> {
 			id,
 		)?;
 		if let Some(index) =
-			read_db(&*self.storage.db, columns::KEY_LOOKUP, columns::BODY_INDEX, id)?
+			read_compressed_db(&*self.storage.db, columns::KEY_LOOKUP, columns::BODY_INDEX, id)?
 		{
 			utils::remove_from_db(
 				transaction,
@@ -2174,7 +2211,7 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 		transaction.set_from_vec(
 			columns::JUSTIFICATIONS,
 			&utils::number_and_hash_to_lookup_key(number, hash)?,
-			justifications.encode(),
+			self.compressor.justifications(justifications.encode()),
 		);
 
 		self.storage.db.commit(transaction)?;
@@ -2213,6 +2250,8 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 				state_writes_cache: state_stats.overlay_writes.ops,
 				state_reads_cache: state_stats.cache_reads.ops,
 				state_writes_nodes: state_stats.nodes_writes.ops,
+				body_compression: self.compressor.bodies_info(),
+				justification_compression: self.compressor.justifications_info(),
 			},
 		})
 	}
@@ -2708,6 +2747,7 @@ pub(crate) mod tests {
 				state_pruning: Some(PruningMode::blocks_pruning(1)),
 				source: DatabaseSource::Custom { db: backing, require_create_flag: false },
 				blocks_pruning: BlocksPruning::KeepFinalized,
+				block_compression: Default::default(),
 			},
 			0,
 		)
@@ -3273,6 +3313,87 @@ pub(crate) mod tests {
 		assert_eq!(backend.blockchain().justifications(block1).unwrap(), Some(justifications),);
 	}
 
+	#[test]
+	fn compressed_blocks_are_read_whatever_the_compression() {
+		use sc_client_api::blockchain::Backend as BlockChainBackend;
+
+		fn settings(
+			db: Arc<dyn Database<DbHash>>,
+			require_create_flag: bool,
+			block_compression: BlockCompression,
+		) -> DatabaseSettings {
+			DatabaseSettings {
+				trie_cache_maximum_size: Some(16 * 1024 * 1024),
+				state_pruning: Some(PruningMode::ArchiveAll),
+				source: DatabaseSource::Custom { db, require_create_flag },
+				blocks_pruning: BlocksPruning::KeepAll,
+				block_compression,
+			}
+		}
+
+		let body: Vec<ExtrinsicWrapper<u64>> = vec![42.into(); 64];
+		let justification = (CONS0_ENGINE_ID, vec![7; 256]);
+
+		let db = sp_database::as_database(kvdb_memorydb::create(crate::utils::NUM_COLUMNS));
+		let compression = BlockCompression { bodies: Some(3), justifications: Some(3) };
+		let backend = Backend::<Block>::new(settings(db.clone(), true, compression), 0).unwrap();
+		let hash = insert_block(
+			&backend,
+			0,
+			Default::default(),
+			None,
+			Default::default(),
+			body.clone(),
+			None,
+		)
+		.unwrap();
+		backend.finalize_block(hash, Some(justification.clone().into())).unwrap();
+
+		let io = backend.usage_info().unwrap().io;
+		for info in [io.body_compression, io.justification_compression] {
+			assert!(info.compressed_bytes < info.uncompressed_bytes);
+		}
+		assert_eq!(backend.blockchain().body(hash).unwrap(), Some(body.clone()));
+		drop(backend);
+
+		let backend = Backend::<Block>::new(settings(db, false, Default::default()), 0).unwrap();
+		assert_eq!(backend.blockchain().body(hash).unwrap(), Some(body));
+		assert_eq!(
+			backend.blockchain().justifications(hash).unwrap(),
+			Some(Justifications::from(justification)),
+		);
+	}
+
+	#[test]
+	fn block_compression_is_ignored_with_parity_db() {
+		use sc_client_api::blockchain::Backend as BlockChainBackend;
+
+		let dir = tempfile::tempdir().unwrap();
+		let settings = DatabaseSettings {
+			trie_cache_maximum_size: Some(16 * 1024 * 1024),
+			state_pruning: Some(PruningMode::ArchiveAll),
+			source: DatabaseSource::ParityDb { path: dir.path().to_owned() },
+			blocks_pruning: BlocksPruning::KeepAll,
+			block_compression: BlockCompression { bodies: Some(3), justifications: Some(3) },
+		};
+		let backend = Backend::<Block>::new(settings, 0).unwrap();
+
+		let body: Vec<ExtrinsicWrapper<u64>> = vec![42.into(); 64];
+		let hash = insert_block(
+			&backend,
+			0,
+			Default::default(),
+			None,
+			Default::default(),
+			body.clone(),
+			None,
+		)
+		.unwrap();
+
+		assert_eq!(backend.usage_info().unwrap().io.body_compression.uncompressed_bytes, 0);
+		assert_eq!(backend.blockchain().body(hash).unwrap(), Some(body));
+	}
+
 	#[test]
 	fn test_finalize_multiple_blocks_in_single_op() {
 		let backend = Backend::<Block>::new_test(10, 10);
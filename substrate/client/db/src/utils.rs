@@ -431,6 +431,19 @@ where
 	})
 }
 
+/// Read database column entry for the given block, decompressing it if it is stored compressed.
+pub fn read_compressed_db<Block>(
+	db: &dyn Database<DbHash>,
+	col_index: u32,
+	col: u32,
+	id: BlockId<Block>,
+) -> sp_blockchain::Result<Option<DBValue>>
+where
+	Block: BlockT,
+{
+	read_db(db, col_index, col, id)?.map(crate::compression::decompress).transpose()
+}
+
 /// Remove database column entry for the given block.
 pub fn remove_from_db<Block>(
 	transaction: &mut Transaction<DbHash>,
@@ -19,6 +19,7 @@
 //! Definitions for a wasm runtime.
 
 use crate::error::Error;
+use std::time::Duration;
 
 pub use sc_allocator::AllocationStats;
 
@@ -69,6 +70,11 @@ pub trait WasmInstance: Send {
 	fn call_export(&mut self, method: &str, data: &[u8]) -> Result<Vec<u8>, Error> {
 		self.call(method.into(), data)
 	}
+
+	/// The time it took to instantiate the runtime for the last call, if known.
+	fn last_instantiation_time(&self) -> Option<Duration> {
+		None
+	}
 }
 
 /// Defines the heap pages allocation strategy the wasm runtime should use.
@@ -18,11 +18,13 @@
 
 use crate::{
 	error::{Error, Result},
+	metrics::Metrics,
 	wasm_runtime::{RuntimeCache, WasmExecutionMethod},
 	RuntimeVersionOf,
 };
 
 use std::{
+	collections::HashMap,
 	marker::PhantomData,
 	panic::{AssertUnwindSafe, UnwindSafe},
 	path::PathBuf,
@@ -30,6 +32,7 @@ use std::{
 };
 
 use codec::Encode;
+use prometheus_endpoint::Registry;
 use sc_executor_common::{
 	runtime_blob::RuntimeBlob,
 	wasm_runtime::{
@@ -82,6 +85,25 @@ fn unwrap_heap_pages(pages: Option<HeapAllocStrategy>) -> HeapAllocStrategy {
 	pages.unwrap_or_else(|| DEFAULT_HEAP_ALLOC_STRATEGY)
 }
 
+/// Execution settings of the calls of a runtime API method, overriding those of the executor.
+///
+/// For example, a node serving many transaction validation requests may want
+/// `TaggedTransactionQueue_validate_transaction` to always reuse pooled instances, regardless of
+/// the instantiation strategy used for the other calls.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CallOverride {
+	/// Whether to use the native runtime when it can be called, instead of the Wasm one.
+	///
+	/// Only applies to the [`NativeElseWasmExecutor`].
+	pub use_native: Option<bool>,
+	/// The method used to execute the Wasm code, including its instantiation strategy.
+	///
+	/// The pooling strategies reuse the instances across the calls, whereas the other ones
+	/// recreate them at every call. Using another method than the executor one compiles the
+	/// runtime again, such that both are kept in the runtime cache.
+	pub wasm_method: Option<WasmExecutionMethod>,
+}
+
 /// Builder for creating a [`WasmExecutor`] instance.
 pub struct WasmExecutorBuilder<H> {
 	_phantom: PhantomData<H>,
@@ -93,6 +115,8 @@ pub struct WasmExecutorBuilder<H> {
 	cache_path: Option<PathBuf>,
 	allow_missing_host_functions: bool,
 	runtime_cache_size: u8,
+	call_overrides: HashMap<String, CallOverride>,
+	metrics: Option<Metrics>,
 }
 
 impl<H> WasmExecutorBuilder<H> {
@@ -110,6 +134,8 @@ impl<H> WasmExecutorBuilder<H> {
 			runtime_cache_size: 4,
 			allow_missing_host_functions: false,
 			cache_path: None,
+			call_overrides: HashMap::new(),
+			metrics: None,
 		}
 	}
 
@@ -193,6 +219,39 @@ impl<H> WasmExecutorBuilder<H> {
 		self
 	}
 
+	/// Create the wasm executor with the given `call_override` for the calls of `method`.
+	///
+	/// The `method` is the name of the runtime function, e.g.
+	/// `TaggedTransactionQueue_validate_transaction`.
+	///
+	/// By default all the calls use the settings of the executor.
+	pub fn with_call_override(
+		mut self,
+		method: impl Into<String>,
+		call_override: CallOverride,
+	) -> Self {
+		self.call_overrides.insert(method.into(), call_override);
+		self
+	}
+
+	/// Create the wasm executor reporting the runtime instantiation times to `registry`.
+	///
+	/// By default no metrics are reported.
+	pub fn with_prometheus_registry(mut self, registry: &Registry) -> Self {
+		self.metrics = match Metrics::register(registry) {
+			Ok(metrics) => Some(metrics),
+			Err(error) => {
+				tracing::warn!(
+					target: "executor",
+					%error,
+					"Failed to register the executor metrics",
+				);
+				None
+			},
+		};
+		self
+	}
+
 	/// Build the configured [`WasmExecutor`].
 	pub fn build(self) -> WasmExecutor<H> {
 		WasmExecutor {
@@ -211,6 +270,8 @@ impl<H> WasmExecutorBuilder<H> {
 			)),
 			cache_path: self.cache_path,
 			allow_missing_host_functions: self.allow_missing_host_functions,
+			call_overrides: Arc::new(self.call_overrides),
+			metrics: self.metrics,
 			phantom: PhantomData,
 		}
 	}
@@ -234,6 +295,9 @@ pub struct WasmExecutor<H> {
 	cache_path: Option<PathBuf>,
 	/// Ignore missing function imports.
 	allow_missing_host_functions: bool,
+	/// Execution settings of the runtime API methods, overriding the executor ones.
+	call_overrides: Arc<HashMap<String, CallOverride>>,
+	metrics: Option<Metrics>,
 	phantom: PhantomData<H>,
 }
 
@@ -247,6 +311,8 @@ impl<H> Clone for WasmExecutor<H> {
 			cache: self.cache.clone(),
 			cache_path: self.cache_path.clone(),
 			allow_missing_host_functions: self.allow_missing_host_functions,
+			call_overrides: self.call_overrides.clone(),
+			metrics: self.metrics.clone(),
 			phantom: self.phantom,
 		}
 	}
@@ -298,6 +364,8 @@ where
 			)),
 			cache_path,
 			allow_missing_host_functions: false,
+			call_overrides: Default::default(),
+			metrics: None,
 			phantom: PhantomData,
 		}
 	}
@@ -333,6 +401,26 @@ where
 		heap_alloc_strategy: HeapAllocStrategy,
 		f: F,
 	) -> Result<R>
+	where
+		F: FnOnce(
+			AssertUnwindSafe<&dyn WasmModule>,
+			AssertUnwindSafe<&mut dyn WasmInstance>,
+			Option<&RuntimeVersion>,
+			AssertUnwindSafe<&mut dyn Externalities>,
+		) -> Result<Result<R>>,
+	{
+		self.with_instance_using(runtime_code, ext, self.method, heap_alloc_strategy, f)
+	}
+
+	/// Same as [`Self::with_instance`], except that the runtime is executed with `wasm_method`.
+	fn with_instance_using<R, F>(
+		&self,
+		runtime_code: &RuntimeCode,
+		ext: &mut dyn Externalities,
+		wasm_method: WasmExecutionMethod,
+		heap_alloc_strategy: HeapAllocStrategy,
+		f: F,
+	) -> Result<R>
 	where
 		F: FnOnce(
 			AssertUnwindSafe<&dyn WasmModule>,
@@ -344,7 +432,7 @@ where
 		match self.cache.with_instance::<H, _, _>(
 			runtime_code,
 			ext,
-			self.method,
+			wasm_method,
 			heap_alloc_strategy,
 			self.allow_missing_host_functions,
 			|module, instance, version, ext| {
@@ -359,6 +447,35 @@ where
 		}
 	}
 
+	/// The method used to execute the Wasm code of the calls of `method`.
+	fn wasm_method_of(&self, method: &str) -> WasmExecutionMethod {
+		self.call_overrides
+			.get(method)
+			.and_then(|call_override| call_override.wasm_method)
+			.unwrap_or(self.method)
+	}
+
+	/// Call the exported `method` of the runtime `instance`, reporting its instantiation time.
+	fn call_export(
+		&self,
+		mut instance: AssertUnwindSafe<&mut dyn WasmInstance>,
+		mut ext: AssertUnwindSafe<&mut dyn Externalities>,
+		wasm_method: WasmExecutionMethod,
+		method: &str,
+		data: &[u8],
+	) -> Result<Result<Vec<u8>>> {
+		let result = {
+			let mut instance = AssertUnwindSafe(&mut **instance);
+			with_externalities_safe(&mut **ext, move || instance.call_export(method, data))
+		};
+
+		if let (Some(metrics), Some(time)) = (&self.metrics, instance.last_instantiation_time()) {
+			metrics.on_instantiation(method, wasm_method, time);
+		}
+
+		result
+	}
+
 	/// Perform a call into the given runtime.
 	///
 	/// The runtime is passed as a [`RuntimeBlob`]. The runtime will be instantiated with the
@@ -514,12 +631,14 @@ where
 			CallContext::Onchain => on_chain_heap_alloc_strategy,
 		};
 
-		let result = self.with_instance(
+		let wasm_method = self.wasm_method_of(method);
+		let result = self.with_instance_using(
 			runtime_code,
 			ext,
+			wasm_method,
 			heap_alloc_strategy,
-			|_, mut instance, _on_chain_version, mut ext| {
-				with_externalities_safe(&mut **ext, move || instance.call_export(method, data))
+			|_, instance, _on_chain_version, ext| {
+				self.call_export(instance, ext, wasm_method, method, data)
 			},
 		);
 
@@ -655,7 +774,13 @@ impl<D: NativeExecutionDispatch + 'static> CodeExecutor for NativeElseWasmExecut
 		data: &[u8],
 		context: CallContext,
 	) -> (Result<Vec<u8>>, bool) {
-		let use_native = self.use_native;
+		let use_native = self
+			.wasm
+			.call_overrides
+			.get(method)
+			.and_then(|call_override| call_override.use_native)
+			.unwrap_or(self.use_native);
+		let wasm_method = self.wasm.wasm_method_of(method);
 
 		tracing::trace!(
 			target: "executor",
@@ -678,11 +803,12 @@ impl<D: NativeExecutionDispatch + 'static> CodeExecutor for NativeElseWasmExecut
 		};
 
 		let mut used_native = false;
-		let result = self.wasm.with_instance(
+		let result = self.wasm.with_instance_using(
 			runtime_code,
 			ext,
+			wasm_method,
 			heap_alloc_strategy,
-			|_, mut instance, on_chain_version, mut ext| {
+			|_, instance, on_chain_version, mut ext| {
 				let on_chain_version =
 					on_chain_version.ok_or_else(|| Error::ApiError("Unknown version".into()))?;
 
@@ -710,7 +836,7 @@ impl<D: NativeExecutionDispatch + 'static> CodeExecutor for NativeElseWasmExecut
 						);
 					}
 
-					with_externalities_safe(&mut **ext, move || instance.call_export(method, data))
+					self.wasm.call_export(instance, ext, wasm_method, method, data)
 				}
 			},
 		);
@@ -788,4 +914,36 @@ mod tests {
 
 		my_interface::say_hello_world("hey");
 	}
+
+	#[test]
+	fn call_override_disables_native_execution() {
+		let code = sp_core::traits::WrappedRuntimeCode(
+			substrate_test_runtime::wasm_binary_unwrap().into(),
+		);
+		let runtime_code = RuntimeCode { code_fetcher: &code, heap_pages: None, hash: vec![1] };
+		let mut ext = sp_io::TestExternalities::default();
+		let mut ext = ext.ext();
+
+		let mut call = |executor: &NativeElseWasmExecutor<MyExecutorDispatch>| {
+			let (result, used_native) =
+				executor.call(&mut ext, &runtime_code, "Core_version", &[], CallContext::Offchain);
+			result.unwrap();
+			used_native
+		};
+
+		let executor = NativeElseWasmExecutor::<MyExecutorDispatch>::new_with_wasm_executor(
+			WasmExecutor::builder().build(),
+		);
+		assert!(call(&executor));
+
+		let executor = NativeElseWasmExecutor::<MyExecutorDispatch>::new_with_wasm_executor(
+			WasmExecutor::builder()
+				.with_call_override(
+					"Core_version",
+					CallOverride { use_native: Some(false), ..Default::default() },
+				)
+				.build(),
+		);
+		assert!(!call(&executor));
+	}
 }
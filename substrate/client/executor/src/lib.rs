@@ -34,11 +34,13 @@
 mod executor;
 #[cfg(test)]
 mod integration_tests;
+mod metrics;
 mod wasm_runtime;
 
 pub use self::{
 	executor::{
-		with_externalities_safe, NativeElseWasmExecutor, NativeExecutionDispatch, WasmExecutor,
+		with_externalities_safe, CallOverride, NativeElseWasmExecutor, NativeExecutionDispatch,
+		WasmExecutor,
 	},
 	wasm_runtime::{read_embedded_version, WasmExecutionMethod},
 };
@@ -0,0 +1,62 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Metrics of the executor.
+
+use crate::wasm_runtime::WasmExecutionMethod;
+use prometheus_endpoint::{
+	exponential_buckets, register, HistogramOpts, HistogramVec, PrometheusError, Registry,
+};
+use std::time::Duration;
+
+/// Metrics of the runtime calls.
+#[derive(Clone)]
+pub(crate) struct Metrics {
+	instantiation_time: HistogramVec,
+}
+
+impl Metrics {
+	pub(crate) fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		Ok(Self {
+			instantiation_time: register(
+				HistogramVec::new(
+					HistogramOpts::new(
+						"substrate_executor_instantiation_time",
+						"Time it took to instantiate the runtime for a call, in seconds",
+					)
+					.buckets(exponential_buckets(0.000_01, 4.0, 10)?),
+					&["method", "strategy"],
+				)?,
+				registry,
+			)?,
+		})
+	}
+
+	/// Report the time it took to instantiate the runtime to call `method`.
+	pub(crate) fn on_instantiation(
+		&self,
+		method: &str,
+		wasm_method: WasmExecutionMethod,
+		time: Duration,
+	) {
+		let WasmExecutionMethod::Compiled { instantiation_strategy } = wasm_method;
+		self.instantiation_time
+			.with_label_values(&[method, &format!("{instantiation_strategy:?}")])
+			.observe(time.as_secs_f64());
+	}
+}
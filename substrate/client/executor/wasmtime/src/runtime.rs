@@ -40,6 +40,7 @@ use std::{
 		atomic::{AtomicBool, Ordering},
 		Arc,
 	},
+	time::{Duration, Instant},
 };
 use wasmtime::{AsContext, Engine, Memory};
 
@@ -149,7 +150,7 @@ impl WasmModule for WasmtimeRuntime {
 			}),
 		};
 
-		Ok(Box::new(WasmtimeInstance { strategy }))
+		Ok(Box::new(WasmtimeInstance { strategy, instantiation_time: None }))
 	}
 }
 
@@ -157,6 +158,9 @@ impl WasmModule for WasmtimeRuntime {
 /// to execute the compiled code.
 pub struct WasmtimeInstance {
 	strategy: Strategy,
+	/// The time it took to instantiate the runtime for the last call, including the time spent
+	/// waiting for a free instance.
+	instantiation_time: Option<Duration>,
 }
 
 impl WasmtimeInstance {
@@ -168,7 +172,9 @@ impl WasmtimeInstance {
 	) -> Result<Vec<u8>> {
 		match &mut self.strategy {
 			Strategy::RecreateInstance(ref mut instance_creator) => {
+				let started = Instant::now();
 				let mut instance_wrapper = instance_creator.instantiate()?;
+				self.instantiation_time = Some(started.elapsed());
 				let heap_base = instance_wrapper.extract_heap_base()?;
 				let entrypoint = instance_wrapper.resolve_entrypoint(method)?;
 				let allocator = FreeingBumpHeapAllocator::new(heap_base);
@@ -189,6 +195,10 @@ impl WasmInstance for WasmtimeInstance {
 		let result = self.call_impl(method, data, &mut allocation_stats);
 		(result, allocation_stats)
 	}
+
+	fn last_instantiation_time(&self) -> Option<Duration> {
+		self.instantiation_time
+	}
 }
 
 /// Prepare a directory structure and a config file to enable wasmtime caching.
@@ -23,6 +23,7 @@ use sp_runtime::traits::Block;
 
 use std::time::Duration;
 
+mod cache;
 /// For incoming light client requests.
 pub mod handler;
 
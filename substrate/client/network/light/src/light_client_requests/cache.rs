@@ -0,0 +1,169 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Cache of the responses to the light client requests.
+//!
+//! The requests refer to their block by hash, so a response never becomes outdated. The requests
+//! are also counted regardless of their block, such that the responses to the most requested
+//! ones can be generated at every finalized block, before the light clients following the chain
+//! request them.
+
+use crate::schema::v1::light::{request::Request as RequestKind, Request};
+use prometheus_endpoint::{register, CounterVec, Opts, PrometheusError, Registry, U64};
+use prost::Message;
+use sc_network::config::LightClientServerConfig;
+use schnellru::{ByLength, LruMap};
+
+/// Number of distinct requests, regardless of their block, counted to find the most requested
+/// ones.
+const COUNTED_REQUESTS: u32 = 1024;
+
+/// Cache of the responses to the light client requests.
+pub(crate) struct ResponseCache {
+	/// Encoded responses by encoded request.
+	responses: LruMap<Vec<u8>, Vec<u8>>,
+	/// Number of times the requests were received, by encoded request without its block.
+	///
+	/// The counts are halved at every precomputation, such that the most requested ones are
+	/// those of the recent blocks.
+	request_counts: LruMap<Vec<u8>, u32>,
+	precomputed_requests: usize,
+	metrics: Option<Metrics>,
+}
+
+impl ResponseCache {
+	pub(crate) fn new(
+		config: LightClientServerConfig,
+		registry: Option<&Registry>,
+	) -> Result<Self, PrometheusError> {
+		Ok(Self {
+			responses: LruMap::new(ByLength::new(config.cache_capacity)),
+			request_counts: LruMap::new(ByLength::new(COUNTED_REQUESTS)),
+			precomputed_requests: config.precomputed_requests,
+			metrics: registry.map(Metrics::register).transpose()?,
+		})
+	}
+
+	/// The cached response to a request received from a peer.
+	pub(crate) fn get(&mut self, request: &Request) -> Option<Vec<u8>> {
+		if let Some(count) = self.request_counts.get_or_insert(without_block(request), || 0) {
+			*count = count.saturating_add(1);
+		}
+
+		let response = self.responses.get(&request.encode_to_vec()).cloned();
+		if let Some(metrics) = &self.metrics {
+			let result = if response.is_some() { "hit" } else { "miss" };
+			metrics.requests.with_label_values(&[kind(request), result]).inc();
+		}
+		response
+	}
+
+	/// Whether the response to the request is cached.
+	pub(crate) fn contains(&self, request: &Request) -> bool {
+		self.responses.peek(&request.encode_to_vec()).is_some()
+	}
+
+	/// Cache the response to a request.
+	pub(crate) fn insert(&mut self, request: &Request, response: Vec<u8>) {
+		self.responses.insert(request.encode_to_vec(), response);
+	}
+
+	/// Cache the response to a request generated ahead of it.
+	pub(crate) fn insert_precomputed(&mut self, request: &Request, response: Vec<u8>) {
+		if let Some(metrics) = &self.metrics {
+			metrics.precomputed.with_label_values(&[kind(request)]).inc();
+		}
+		self.insert(request, response);
+	}
+
+	/// The most requested requests, at the given block.
+	pub(crate) fn most_requested_at(&mut self, block: &[u8]) -> Vec<Request> {
+		let mut counts = self.request_counts.drain().collect::<Vec<_>>();
+		counts.sort_by(|a, b| b.1.cmp(&a.1));
+
+		let mut requests = Vec::with_capacity(self.precomputed_requests);
+		for (request, count) in counts {
+			if requests.len() < self.precomputed_requests {
+				if let Ok(request) = Request::decode(&request[..]) {
+					requests.push(with_block(request, block));
+				}
+			}
+			if count > 1 {
+				self.request_counts.insert(request, count / 2);
+			}
+		}
+		requests
+	}
+}
+
+/// The encoded request, without its block.
+fn without_block(request: &Request) -> Vec<u8> {
+	with_block(request.clone(), &[]).encode_to_vec()
+}
+
+/// The request at the given block.
+fn with_block(mut request: Request, block: &[u8]) -> Request {
+	match &mut request.request {
+		Some(RequestKind::RemoteCallRequest(r)) => r.block = block.to_vec(),
+		Some(RequestKind::RemoteReadRequest(r)) => r.block = block.to_vec(),
+		Some(RequestKind::RemoteReadChildRequest(r)) => r.block = block.to_vec(),
+		None => {},
+	}
+	request
+}
+
+fn kind(request: &Request) -> &'static str {
+	match request.request {
+		Some(RequestKind::RemoteCallRequest(_)) => "call",
+		Some(RequestKind::RemoteReadRequest(_)) => "read",
+		Some(RequestKind::RemoteReadChildRequest(_)) => "read_child",
+		None => "unknown",
+	}
+}
+
+struct Metrics {
+	requests: CounterVec<U64>,
+	precomputed: CounterVec<U64>,
+}
+
+impl Metrics {
+	fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		Ok(Self {
+			requests: register(
+				CounterVec::new(
+					Opts::new(
+						"substrate_light_client_cache_requests",
+						"Number of light client requests by whether their response was cached",
+					),
+					&["request", "result"],
+				)?,
+				registry,
+			)?,
+			precomputed: register(
+				CounterVec::new(
+					Opts::new(
+						"substrate_light_client_cache_precomputed",
+						"Number of responses to light client requests generated ahead of them",
+					),
+					&["request"],
+				)?,
+				registry,
+			)?,
+		})
+	}
+}
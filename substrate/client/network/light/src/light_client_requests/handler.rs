@@ -22,14 +22,16 @@
 //! `crate::request_responses::RequestResponsesBehaviour` with
 //! [`LightClientRequestHandler`](handler::LightClientRequestHandler).
 
+use super::cache::ResponseCache;
 use crate::schema;
 use codec::{self, Decode, Encode};
 use futures::prelude::*;
 use log::{debug, trace};
+use prometheus_endpoint::{PrometheusError, Registry};
 use prost::Message;
-use sc_client_api::{BlockBackend, ProofProvider};
+use sc_client_api::{BlockBackend, BlockchainEvents, ProofProvider};
 use sc_network::{
-	config::ProtocolId,
+	config::{LightClientServerConfig, ProtocolId},
 	request_responses::{IncomingRequest, OutgoingResponse},
 	NetworkBackend, ReputationChange,
 };
@@ -39,7 +41,7 @@ use sp_core::{
 	storage::{ChildInfo, ChildType, PrefixedStorageKey},
 };
 use sp_runtime::traits::Block;
-use std::{marker::PhantomData, sync::Arc};
+use std::{fmt::Display, marker::PhantomData, sync::Arc};
 
 const LOG_TARGET: &str = "light-client-request-handler";
 
@@ -52,13 +54,15 @@ pub struct LightClientRequestHandler<B, Client> {
 	request_receiver: async_channel::Receiver<IncomingRequest>,
 	/// Blockchain client.
 	client: Arc<Client>,
+	/// Cache of the responses, `None` to generate each of them on demand.
+	cache: Option<ResponseCache>,
 	_block: PhantomData<B>,
 }
 
 impl<B, Client> LightClientRequestHandler<B, Client>
 where
 	B: Block,
-	Client: BlockBackend<B> + ProofProvider<B> + Send + Sync + 'static,
+	Client: BlockBackend<B> + BlockchainEvents<B> + ProofProvider<B> + Send + Sync + 'static,
 {
 	/// Create a new [`LightClientRequestHandler`].
 	pub fn new<N: NetworkBackend<B, <B as Block>::Hash>>(
@@ -79,65 +83,94 @@ where
 			tx,
 		);
 
-		(Self { client, request_receiver, _block: PhantomData::default() }, protocol_config)
+		(
+			Self { client, request_receiver, cache: None, _block: PhantomData::default() },
+			protocol_config,
+		)
+	}
+
+	/// Cache the responses and precompute the most requested ones at every finalized block.
+	pub fn with_cache(
+		mut self,
+		config: LightClientServerConfig,
+		registry: Option<&Registry>,
+	) -> Result<Self, PrometheusError> {
+		self.cache = Some(ResponseCache::new(config, registry)?);
+		Ok(self)
 	}
 
 	/// Run [`LightClientRequestHandler`].
 	pub async fn run(mut self) {
-		while let Some(request) = self.request_receiver.next().await {
-			let IncomingRequest { peer, payload, pending_response } = request;
-
-			match self.handle_request(peer, payload) {
-				Ok(response_data) => {
-					let response = OutgoingResponse {
-						result: Ok(response_data),
-						reputation_changes: Vec::new(),
-						sent_feedback: None,
-					};
-
-					match pending_response.send(response) {
-						Ok(()) => trace!(
-							target: LOG_TARGET,
-							"Handled light client request from {}.",
-							peer,
-						),
-						Err(_) => debug!(
-							target: LOG_TARGET,
-							"Failed to handle light client request from {}: {}",
-							peer,
-							HandleRequestError::SendResponse,
-						),
-					};
+		let mut requests = self.request_receiver.clone();
+		let mut finalized = match self.cache {
+			Some(_) => self.client.finality_notification_stream().map(|n| n.hash).boxed(),
+			None => stream::pending().boxed(),
+		}
+		.fuse();
+
+		loop {
+			futures::select! {
+				request = requests.next() => match request {
+					Some(request) => self.on_request(request),
+					None => return,
+				},
+				hash = finalized.next() => if let Some(hash) = hash {
+					self.precompute(hash);
 				},
-				Err(e) => {
+			}
+		}
+	}
+
+	fn on_request(&mut self, request: IncomingRequest) {
+		let IncomingRequest { peer, payload, pending_response } = request;
+
+		match self.handle_request(peer, payload) {
+			Ok(response_data) => {
+				let response = OutgoingResponse {
+					result: Ok(response_data),
+					reputation_changes: Vec::new(),
+					sent_feedback: None,
+				};
+
+				match pending_response.send(response) {
+					Ok(()) => trace!(
+						target: LOG_TARGET,
+						"Handled light client request from {}.",
+						peer,
+					),
+					Err(_) => debug!(
+						target: LOG_TARGET,
+						"Failed to handle light client request from {}: {}",
+						peer,
+						HandleRequestError::SendResponse,
+					),
+				};
+			},
+			Err(e) => {
+				debug!(
+					target: LOG_TARGET,
+					"Failed to handle light client request from {}: {}", peer, e,
+				);
+
+				let reputation_changes = match e {
+					HandleRequestError::BadRequest(_) => {
+						vec![ReputationChange::new(-(1 << 12), "bad request")]
+					},
+					_ => Vec::new(),
+				};
+
+				let response =
+					OutgoingResponse { result: Err(()), reputation_changes, sent_feedback: None };
+
+				if pending_response.send(response).is_err() {
 					debug!(
 						target: LOG_TARGET,
-						"Failed to handle light client request from {}: {}", peer, e,
+						"Failed to handle light client request from {}: {}",
+						peer,
+						HandleRequestError::SendResponse,
 					);
-
-					let reputation_changes = match e {
-						HandleRequestError::BadRequest(_) => {
-							vec![ReputationChange::new(-(1 << 12), "bad request")]
-						},
-						_ => Vec::new(),
-					};
-
-					let response = OutgoingResponse {
-						result: Err(()),
-						reputation_changes,
-						sent_feedback: None,
-					};
-
-					if pending_response.send(response).is_err() {
-						debug!(
-							target: LOG_TARGET,
-							"Failed to handle light client request from {}: {}",
-							peer,
-							HandleRequestError::SendResponse,
-						);
-					};
-				},
-			}
+				};
+			},
 		}
 	}
 
@@ -148,26 +181,77 @@ where
 	) -> Result<Vec<u8>, HandleRequestError> {
 		let request = schema::v1::light::Request::decode(&payload[..])?;
 
+		if let Some(response) = self.cache.as_mut().and_then(|cache| cache.get(&request)) {
+			trace!(target: LOG_TARGET, "Cached response to light client request from {}.", peer);
+			return Ok(response)
+		}
+
+		let (data, has_proof) = self.answer(&peer, &request)?;
+		// Failures, e.g. because the block is not imported yet, are not cached.
+		if let (Some(cache), true) = (&mut self.cache, has_proof) {
+			cache.insert(&request, data.clone());
+		}
+
+		Ok(data)
+	}
+
+	/// Generate the encoded response to a request, along with whether it carries a proof.
+	fn answer(
+		&self,
+		peer: &dyn Display,
+		request: &schema::v1::light::Request,
+	) -> Result<(Vec<u8>, bool), HandleRequestError> {
 		let response = match &request.request {
 			Some(schema::v1::light::request::Request::RemoteCallRequest(r)) =>
-				self.on_remote_call_request(&peer, r)?,
+				self.on_remote_call_request(peer, r)?,
 			Some(schema::v1::light::request::Request::RemoteReadRequest(r)) =>
-				self.on_remote_read_request(&peer, r)?,
+				self.on_remote_read_request(peer, r)?,
 			Some(schema::v1::light::request::Request::RemoteReadChildRequest(r)) =>
-				self.on_remote_read_child_request(&peer, r)?,
+				self.on_remote_read_child_request(peer, r)?,
 			None =>
 				return Err(HandleRequestError::BadRequest("Remote request without request data.")),
 		};
+		let has_proof = match &response.response {
+			Some(schema::v1::light::response::Response::RemoteCallResponse(r)) => r.proof.is_some(),
+			Some(schema::v1::light::response::Response::RemoteReadResponse(r)) => r.proof.is_some(),
+			None => false,
+		};
 
 		let mut data = Vec::new();
 		response.encode(&mut data)?;
 
-		Ok(data)
+		Ok((data, has_proof))
+	}
+
+	/// Generate the responses to the most requested requests at a newly finalized block.
+	fn precompute(&mut self, hash: B::Hash) {
+		let requests = match &mut self.cache {
+			Some(cache) => cache.most_requested_at(&hash.encode()),
+			None => return,
+		};
+
+		for request in requests {
+			if self.cache.as_ref().map_or(true, |cache| cache.contains(&request)) {
+				continue
+			}
+
+			match self.answer(&"precomputation", &request) {
+				Ok((data, true)) =>
+					if let Some(cache) = &mut self.cache {
+						cache.insert_precomputed(&request, data);
+					},
+				Ok((_, false)) => {},
+				Err(e) => debug!(
+					target: LOG_TARGET,
+					"Failed to precompute light client response at {:?}: {}", hash, e,
+				),
+			}
+		}
 	}
 
 	fn on_remote_call_request(
-		&mut self,
-		peer: &PeerId,
+		&self,
+		peer: &dyn Display,
 		request: &schema::v1::light::RemoteCallRequest,
 	) -> Result<schema::v1::light::Response, HandleRequestError> {
 		trace!("Remote call request from {} ({} at {:?}).", peer, request.method, request.block,);
@@ -194,8 +278,8 @@ where
 	}
 
 	fn on_remote_read_request(
-		&mut self,
-		peer: &PeerId,
+		&self,
+		peer: &dyn Display,
 		request: &schema::v1::light::RemoteReadRequest,
 	) -> Result<schema::v1::light::Response, HandleRequestError> {
 		if request.keys.is_empty() {
@@ -233,8 +317,8 @@ where
 	}
 
 	fn on_remote_read_child_request(
-		&mut self,
-		peer: &PeerId,
+		&self,
+		peer: &dyn Display,
 		request: &schema::v1::light::RemoteReadChildRequest,
 	) -> Result<schema::v1::light::Response, HandleRequestError> {
 		if request.keys.is_empty() {
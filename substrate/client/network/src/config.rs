@@ -652,6 +652,14 @@ pub struct NetworkConfiguration {
 	/// Enable serving block data over IPFS bitswap.
 	pub ipfs_server: bool,
 
+	/// Cache and precompute the responses to the light client requests, `None` to generate
+	/// each of them on demand.
+	pub light_client_server: Option<LightClientServerConfig>,
+
+	/// Periodically fetch bootnodes and reserved peers from the `BootnodesApi` runtime api of the
+	/// best block, in addition to the configured ones.
+	pub onchain_peers: bool,
+
 	/// Size of Yamux receive window of all substreams. `None` for the default (256kiB).
 	/// Any value less than 256kiB is invalid.
 	///
@@ -677,6 +685,25 @@ pub struct NetworkConfiguration {
 	pub network_backend: NetworkBackendType,
 }
 
+/// Configuration of the responses to the light client requests cached by the node.
+///
+/// Meant for the nodes serving many light clients, such as public bootnodes: the light clients
+/// following the chain request the same proofs at every block.
+#[derive(Clone, Debug)]
+pub struct LightClientServerConfig {
+	/// Maximum number of cached responses.
+	pub cache_capacity: u32,
+	/// Number of the most requested calls and storage reads whose responses are generated
+	/// ahead of the requests at every finalized block.
+	pub precomputed_requests: usize,
+}
+
+impl Default for LightClientServerConfig {
+	fn default() -> Self {
+		Self { cache_capacity: 4096, precomputed_requests: 32 }
+	}
+}
+
 impl NetworkConfiguration {
 	/// Create new default configuration
 	pub fn new<SN: Into<String>, SV: Into<String>>(
@@ -707,6 +734,8 @@ impl NetworkConfiguration {
 				.expect("value is a constant; constant is non-zero; qed."),
 			yamux_window_size: None,
 			ipfs_server: false,
+			light_client_server: None,
+			onchain_peers: false,
 			network_backend: NetworkBackendType::Libp2p,
 		}
 	}
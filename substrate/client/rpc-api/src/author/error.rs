@@ -123,7 +123,7 @@ impl From<Error> for ErrorObjectOwned {
 					Some(format!("{:?}", e)),
 				)
 			},
-			Error::Pool(PoolError::TemporarilyBanned) =>
+			Error::Pool(PoolError::TemporarilyBanned { .. }) =>
 				ErrorObject::owned(
 				POOL_TEMPORARILY_BANNED,
 				"Transaction is temporarily banned",
@@ -66,6 +66,12 @@ pub trait AuthorApi<Hash, BlockHash> {
 		bytes_or_hash: Vec<hash::ExtrinsicOrHash<Hash>>,
 	) -> Result<Vec<Hash>, Error>;
 
+	/// Revalidate all the ready transactions in the pool right away.
+	///
+	/// Returns the number of transactions scheduled for revalidation.
+	#[method(name = "author_revalidatePool")]
+	async fn revalidate_pool(&self) -> Result<usize, Error>;
+
 	/// Submit an extrinsic to watch.
 	///
 	/// See [`TransactionStatus`](sc_transaction_pool_api::TransactionStatus) for details on
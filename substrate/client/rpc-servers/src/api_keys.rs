@@ -0,0 +1,194 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! API keys granting their holders the limits of a tier instead of the global ones.
+
+use std::{
+	collections::HashMap,
+	error::Error as StdError,
+	num::NonZeroU32,
+	path::PathBuf,
+	sync::{Arc, RwLock, Weak},
+	time::{Duration, SystemTime},
+};
+
+use http::{HeaderMap, StatusCode};
+use hyper::{Body, Response};
+use serde::Deserialize;
+
+use crate::middleware::RateLimit;
+
+/// Header carrying the API key of a request.
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// API keys configuration.
+#[derive(Debug, Clone)]
+pub struct ApiKeysConfig {
+	/// JSON file with the tiers and the keys granting them.
+	///
+	/// The file has a `tiers` object mapping the name of each tier to its [`ApiKeyTier`], and
+	/// a `keys` object mapping each key to the name of its tier.
+	pub path: PathBuf,
+	/// How often the file is checked for changes.
+	pub reload_interval: Duration,
+}
+
+/// Limits of the requests carrying an API key of the tier.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ApiKeyTier {
+	/// Calls per minute, shared by all the connections using the same key. `None` for no
+	/// limit.
+	#[serde(default)]
+	pub rate_limit: Option<NonZeroU32>,
+	/// Maximum subscriptions per connection.
+	///
+	/// Connections with a different limit than the server's are counted separately against
+	/// the maximum connections of the listener.
+	pub max_subscriptions: u32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct KeysFile {
+	tiers: HashMap<String, ApiKeyTier>,
+	keys: HashMap<String, String>,
+}
+
+/// API key known to the server.
+#[derive(Debug)]
+pub(crate) struct ApiKey {
+	tier: ApiKeyTier,
+	rate_limit: Option<RateLimit>,
+}
+
+impl ApiKey {
+	fn new(tier: ApiKeyTier) -> Self {
+		Self { tier, rate_limit: tier.rate_limit.map(RateLimit::per_minute) }
+	}
+
+	/// The rate limit of the calls made with the key.
+	pub(crate) fn rate_limit(&self) -> Option<RateLimit> {
+		self.rate_limit.clone()
+	}
+
+	/// The maximum subscriptions per connection of the key.
+	pub(crate) fn max_subscriptions(&self) -> u32 {
+		self.tier.max_subscriptions
+	}
+}
+
+struct Keys {
+	modified: Option<SystemTime>,
+	keys: HashMap<String, Arc<ApiKey>>,
+}
+
+/// API keys loaded from a file, reloaded when it changes.
+pub(crate) struct ApiKeys {
+	path: PathBuf,
+	keys: RwLock<Keys>,
+}
+
+impl ApiKeys {
+	/// Load the API keys.
+	///
+	/// A task checking the file for changes is spawned.
+	pub(crate) fn new(config: ApiKeysConfig) -> Result<Arc<Self>, Box<dyn StdError + Send + Sync>> {
+		let api_keys = Arc::new(Self {
+			path: config.path,
+			keys: RwLock::new(Keys { modified: None, keys: HashMap::new() }),
+		});
+		api_keys.reload()?;
+		tokio::spawn(reload_api_keys(config.reload_interval, Arc::downgrade(&api_keys)));
+
+		Ok(api_keys)
+	}
+
+	/// Load the file again if it was modified since it was last loaded.
+	///
+	/// Keys whose tier limits didn't change keep their rate limit state.
+	fn reload(&self) -> Result<(), Box<dyn StdError + Send + Sync>> {
+		let modified = std::fs::metadata(&self.path)?.modified().ok();
+		if modified.is_some() && self.keys.read().expect("Lock poisoned; qed").modified == modified
+		{
+			return Ok(())
+		}
+
+		let file: KeysFile = serde_json::from_slice(&std::fs::read(&self.path)?)?;
+		let mut keys = self.keys.write().expect("Lock poisoned; qed");
+		let mut loaded = HashMap::with_capacity(file.keys.len());
+		for (key, tier_name) in file.keys {
+			let tier = *file
+				.tiers
+				.get(&tier_name)
+				.ok_or_else(|| format!("Unknown tier {tier_name:?} of an API key"))?;
+			let api_key = match keys.keys.get(&key) {
+				Some(api_key) if api_key.tier == tier => api_key.clone(),
+				_ => Arc::new(ApiKey::new(tier)),
+			};
+			loaded.insert(key, api_key);
+		}
+
+		log::info!(
+			"Loaded {} JSON-RPC API keys of {} tiers from {:?}",
+			loaded.len(),
+			file.tiers.len(),
+			self.path,
+		);
+		*keys = Keys { modified, keys: loaded };
+		Ok(())
+	}
+
+	/// Look up the API key of a request.
+	///
+	/// Returns `None` for anonymous requests and fails for requests with an unknown key.
+	pub(crate) fn authenticate(&self, headers: &HeaderMap) -> Result<Option<Arc<ApiKey>>, String> {
+		let Some(key) = headers.get(API_KEY_HEADER) else { return Ok(None) };
+
+		key.to_str()
+			.ok()
+			.and_then(|key| self.keys.read().expect("Lock poisoned; qed").keys.get(key).cloned())
+			.map(Some)
+			.ok_or_else(|| "Unknown API key".to_string())
+	}
+}
+
+/// Check the file for changes every `interval` until the keys are dropped.
+async fn reload_api_keys(interval: Duration, api_keys: Weak<ApiKeys>) {
+	let mut interval = tokio::time::interval(interval);
+	interval.tick().await;
+
+	loop {
+		interval.tick().await;
+
+		let Some(api_keys) = api_keys.upgrade() else { return };
+
+		// Keep using the previous keys, the file may be in the middle of being replaced.
+		if let Err(e) = api_keys.reload() {
+			log::warn!("Failed to reload JSON-RPC API keys from {:?}: {e}", api_keys.path);
+		}
+	}
+}
+
+/// Response to a request with an unknown API key.
+pub(crate) fn unauthorized() -> Response<Body> {
+	Response::builder()
+		.status(StatusCode::UNAUTHORIZED)
+		.body(Body::empty())
+		.expect("Unauthorized response is valid; qed")
+}
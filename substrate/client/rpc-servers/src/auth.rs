@@ -0,0 +1,407 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Bearer token (JWT) authentication of RPC requests.
+
+use std::{
+	error::Error as StdError,
+	future::Future,
+	pin::Pin,
+	sync::{Arc, RwLock, Weak},
+	task::{Context, Poll},
+	time::Duration,
+};
+
+use http::{header, Method, StatusCode};
+use hyper::{Body, Request, Response};
+use jsonwebtoken::{jwk::JwkSet, Algorithm, DecodingKey, Validation};
+use serde_json::{Map, Value};
+
+/// Maximum size of a JWKS document.
+const MAX_JWKS_SIZE: usize = 1024 * 1024;
+
+/// Authentication configuration.
+#[derive(Debug, Clone)]
+pub struct AuthConfig {
+	/// Keys the tokens are verified with.
+	pub keys: AuthKeys,
+	/// Expected `aud` claim, any audience is accepted if `None`.
+	pub audience: Option<String>,
+	/// Expected `iss` claim, any issuer is accepted if `None`.
+	pub issuer: Option<String>,
+}
+
+/// Keys the tokens are verified with.
+#[derive(Clone)]
+pub enum AuthKeys {
+	/// Shared secret for HMAC signed tokens (`HS256`, `HS384` and `HS512`).
+	Secret(Vec<u8>),
+	/// JSON Web Key Set fetched from the given URL.
+	Jwks {
+		/// URL of the key set.
+		url: String,
+		/// How often the key set is fetched again to pick up rotated keys.
+		refresh_interval: Duration,
+	},
+}
+
+impl std::fmt::Debug for AuthKeys {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Secret(_) => f.write_str("Secret(..)"),
+			Self::Jwks { url, refresh_interval } => f
+				.debug_struct("Jwks")
+				.field("url", url)
+				.field("refresh_interval", refresh_interval)
+				.finish(),
+		}
+	}
+}
+
+/// Claims of the token a request was authenticated with.
+///
+/// Inserted into the extensions of authenticated requests for the use of the middleware
+/// further down.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Claims(pub Map<String, Value>);
+
+impl Claims {
+	/// The subject (`sub` claim) of the token.
+	pub fn subject(&self) -> Option<&str> {
+		self.0.get("sub").and_then(Value::as_str)
+	}
+}
+
+enum Keys {
+	Secret(DecodingKey),
+	Jwks(JwkSet),
+}
+
+/// Verifies bearer tokens.
+pub(crate) struct Authenticator {
+	keys: RwLock<Keys>,
+	validation: Validation,
+}
+
+impl Authenticator {
+	/// Create an authenticator, fetching the key set if necessary.
+	///
+	/// For a key set, a task refreshing it periodically is spawned.
+	pub(crate) async fn new(
+		config: AuthConfig,
+	) -> Result<Arc<Self>, Box<dyn StdError + Send + Sync>> {
+		let mut validation = Validation::new(Algorithm::HS256);
+		validation.validate_aud = config.audience.is_some();
+		if let Some(audience) = &config.audience {
+			validation.set_audience(&[audience]);
+		}
+		if let Some(issuer) = &config.issuer {
+			validation.set_issuer(&[issuer]);
+		}
+
+		let (keys, refresh) = match config.keys {
+			AuthKeys::Secret(secret) => {
+				validation.algorithms = vec![Algorithm::HS256, Algorithm::HS384, Algorithm::HS512];
+				(Keys::Secret(DecodingKey::from_secret(&secret)), None)
+			},
+			AuthKeys::Jwks { url, refresh_interval } =>
+				(Keys::Jwks(fetch_jwks(&url).await?), Some((url, refresh_interval))),
+		};
+
+		let authenticator = Arc::new(Self { keys: RwLock::new(keys), validation });
+		if let Some((url, interval)) = refresh {
+			tokio::spawn(refresh_jwks(url, interval, Arc::downgrade(&authenticator)));
+		}
+
+		Ok(authenticator)
+	}
+
+	/// Verify the bearer token of the request.
+	fn authenticate<B>(&self, req: &Request<B>) -> Result<Claims, String> {
+		let token = req
+			.headers()
+			.get(header::AUTHORIZATION)
+			.and_then(|value| value.to_str().ok())
+			.and_then(|value| value.strip_prefix("Bearer "))
+			.ok_or_else(|| "Missing bearer token".to_string())?;
+
+		let keys = self.keys.read().expect("Lock poisoned; qed");
+		let claims = match &*keys {
+			Keys::Secret(key) => jsonwebtoken::decode(token, key, &self.validation),
+			Keys::Jwks(set) => {
+				let header = jsonwebtoken::decode_header(token).map_err(|e| e.to_string())?;
+				let jwk = match (&header.kid, set.keys.as_slice()) {
+					(Some(kid), _) => set.find(kid),
+					(None, [jwk]) => Some(jwk),
+					(None, _) => None,
+				}
+				.ok_or_else(|| format!("Unknown key id {:?}", header.kid))?;
+
+				let key = DecodingKey::from_jwk(jwk).map_err(|e| e.to_string())?;
+				// The key family is checked against the algorithm while decoding.
+				let mut validation = self.validation.clone();
+				validation.algorithms = vec![header.alg];
+				jsonwebtoken::decode(token, &key, &validation)
+			},
+		};
+
+		claims.map(|data| Claims(data.claims)).map_err(|e| e.to_string())
+	}
+}
+
+async fn fetch_jwks(url: &str) -> Result<JwkSet, Box<dyn StdError + Send + Sync>> {
+	// Only fetched over TLS, the key set deciding which tokens are accepted.
+	let connector = hyper_rustls::HttpsConnectorBuilder::new()
+		.with_native_roots()?
+		.https_only()
+		.enable_http1()
+		.build();
+	let client = hyper::Client::builder().build::<_, Body>(connector);
+
+	let response = client.get(url.parse()?).await?;
+	if !response.status().is_success() {
+		return Err(format!("Fetching JWKS from {url} failed: {}", response.status()).into())
+	}
+
+	let body = http_body_limited(response.into_body()).await?;
+	Ok(serde_json::from_slice(&body)?)
+}
+
+async fn http_body_limited(body: Body) -> Result<Vec<u8>, Box<dyn StdError + Send + Sync>> {
+	use futures::TryStreamExt;
+
+	body.map_err(Into::into)
+		.try_fold(Vec::new(), |mut acc, chunk| async move {
+			if acc.len() + chunk.len() > MAX_JWKS_SIZE {
+				return Err("JWKS document too large".into())
+			}
+			acc.extend_from_slice(&chunk);
+			Ok(acc)
+		})
+		.await
+}
+
+/// Fetch the key set every `interval` until the authenticator is dropped.
+async fn refresh_jwks(url: String, interval: Duration, authenticator: Weak<Authenticator>) {
+	let mut interval = tokio::time::interval(interval);
+	interval.tick().await;
+
+	loop {
+		interval.tick().await;
+
+		let Some(authenticator) = authenticator.upgrade() else { return };
+
+		match fetch_jwks(&url).await {
+			Ok(set) => {
+				*authenticator.keys.write().expect("Lock poisoned; qed") = Keys::Jwks(set);
+				log::debug!(target: "rpc", "Refreshed JSON-RPC server JWKS from {url}");
+			},
+			// Keep using the previous keys, the endpoint may be temporarily unavailable.
+			Err(e) => log::warn!("Failed to refresh JSON-RPC server JWKS from {url}: {e}"),
+		}
+	}
+}
+
+/// Layer rejecting requests without a valid bearer token.
+#[derive(Clone)]
+pub(crate) struct AuthLayer(pub(crate) Arc<Authenticator>);
+
+impl<S> tower::Layer<S> for AuthLayer {
+	type Service = Auth<S>;
+
+	fn layer(&self, inner: S) -> Self::Service {
+		Auth { inner, authenticator: self.0.clone() }
+	}
+}
+
+/// Service rejecting requests without a valid bearer token.
+///
+/// The claims of the token are inserted into the request extensions.
+#[derive(Clone)]
+pub(crate) struct Auth<S> {
+	inner: S,
+	authenticator: Arc<Authenticator>,
+}
+
+impl<S> tower::Service<Request<Body>> for Auth<S>
+where
+	S: tower::Service<Request<Body>, Response = Response<Body>>,
+	S::Error: Into<Box<dyn StdError + Send + Sync>> + 'static,
+	S::Future: Send + 'static,
+{
+	type Response = S::Response;
+	type Error = Box<dyn StdError + Send + Sync>;
+	type Future =
+		Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send + 'static>>;
+
+	fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+		self.inner.poll_ready(cx).map_err(Into::into)
+	}
+
+	fn call(&mut self, mut req: Request<Body>) -> Self::Future {
+		// CORS preflight requests never carry credentials.
+		if req.method() != Method::OPTIONS {
+			match self.authenticator.authenticate(&req) {
+				Ok(claims) => {
+					req.extensions_mut().insert(claims);
+				},
+				Err(e) => {
+					log::debug!(target: "rpc", "Rejected unauthenticated request: {e}");
+					return Box::pin(async { Ok(unauthorized()) })
+				},
+			}
+		}
+
+		let fut = self.inner.call(req);
+		Box::pin(async move { fut.await.map_err(Into::into) })
+	}
+}
+
+fn unauthorized() -> Response<Body> {
+	Response::builder()
+		.status(StatusCode::UNAUTHORIZED)
+		.header(header::WWW_AUTHENTICATE, "Bearer")
+		.body(Body::empty())
+		.expect("Unauthorized response is valid; qed")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use jsonwebtoken::{EncodingKey, Header};
+	use serde_json::json;
+	use std::time::{SystemTime, UNIX_EPOCH};
+	use tower::{Layer, ServiceExt};
+
+	const SECRET: &[u8] = b"secret";
+	/// `{"alg":"RS256","typ":"JWT"}`, base64url encoded.
+	const RS256_HEADER: &str = "eyJhbGciOiJSUzI1NiIsInR5cCI6IkpXVCJ9";
+
+	fn authenticator() -> Arc<Authenticator> {
+		let config =
+			AuthConfig { keys: AuthKeys::Secret(SECRET.to_vec()), audience: None, issuer: None };
+		futures::executor::block_on(Authenticator::new(config)).unwrap()
+	}
+
+	fn jwks_authenticator() -> Arc<Authenticator> {
+		let authenticator = authenticator();
+		// `c2VjcmV0` is `SECRET`, base64url encoded.
+		let set = serde_json::from_value(json!({
+			"keys": [{ "kty": "oct", "kid": "1", "k": "c2VjcmV0" }]
+		}))
+		.unwrap();
+		*authenticator.keys.write().unwrap() = Keys::Jwks(set);
+		authenticator
+	}
+
+	fn token(header: Header, exp_offset: i64) -> String {
+		let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() as i64;
+		let claims = json!({ "sub": "alice", "exp": now + exp_offset });
+		jsonwebtoken::encode(&header, &claims, &EncodingKey::from_secret(SECRET)).unwrap()
+	}
+
+	/// Swap the header of the token for one claiming `RS256`, keeping its HMAC signature.
+	fn with_rs256_header(token: &str) -> String {
+		let (_, rest) = token.split_once('.').unwrap();
+		format!("{RS256_HEADER}.{rest}")
+	}
+
+	fn request(method: Method, token: Option<&str>) -> Request<Body> {
+		let mut req = Request::builder().method(method);
+		if let Some(token) = token {
+			req = req.header(header::AUTHORIZATION, format!("Bearer {token}"));
+		}
+		req.body(Body::empty()).unwrap()
+	}
+
+	fn call(authenticator: Arc<Authenticator>, req: Request<Body>) -> (StatusCode, Option<Claims>) {
+		let inner = tower::service_fn(|req: Request<Body>| async move {
+			let claims = req.extensions().get::<Claims>().cloned();
+			let mut res = Response::new(Body::empty());
+			res.extensions_mut().insert(claims);
+			Ok::<_, std::convert::Infallible>(res)
+		});
+		let res = futures::executor::block_on(AuthLayer(authenticator).layer(inner).oneshot(req))
+			.unwrap();
+		(res.status(), res.extensions().get::<Option<Claims>>().cloned().flatten())
+	}
+
+	#[test]
+	fn valid_token_is_accepted() {
+		let token = token(Header::default(), 3600);
+		let (status, claims) = call(authenticator(), request(Method::POST, Some(&token)));
+		assert_eq!(status, StatusCode::OK);
+		assert_eq!(claims.unwrap().subject(), Some("alice"));
+	}
+
+	#[test]
+	fn valid_token_is_accepted_with_jwks() {
+		let token = token(Header { kid: Some("1".into()), ..Default::default() }, 3600);
+		let (status, claims) = call(jwks_authenticator(), request(Method::POST, Some(&token)));
+		assert_eq!(status, StatusCode::OK);
+		assert_eq!(claims.unwrap().subject(), Some("alice"));
+	}
+
+	#[test]
+	fn expired_token_is_rejected() {
+		let token = token(Header::default(), -3600);
+		let err = authenticator().authenticate(&request(Method::POST, Some(&token))).unwrap_err();
+		assert!(err.contains("ExpiredSignature"), "{err}");
+
+		let (status, _) = call(authenticator(), request(Method::POST, Some(&token)));
+		assert_eq!(status, StatusCode::UNAUTHORIZED);
+	}
+
+	#[test]
+	fn token_with_unexpected_algorithm_is_rejected() {
+		let token = with_rs256_header(&token(Header::default(), 3600));
+		let err = authenticator().authenticate(&request(Method::POST, Some(&token))).unwrap_err();
+		assert!(err.contains("InvalidAlgorithm"), "{err}");
+	}
+
+	#[test]
+	fn token_with_algorithm_not_matching_the_key_family_is_rejected() {
+		// The algorithm is taken from the header with a key set, so only the family of the key
+		// stops an `RS256` token from being checked against a symmetric key.
+		let token = with_rs256_header(&token(Header::default(), 3600));
+		let err = jwks_authenticator()
+			.authenticate(&request(Method::POST, Some(&token)))
+			.unwrap_err();
+		assert!(err.contains("InvalidAlgorithm"), "{err}");
+	}
+
+	#[test]
+	fn missing_token_is_rejected() {
+		let (status, _) = call(authenticator(), request(Method::POST, None));
+		assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+		let req = Request::builder()
+			.header(header::AUTHORIZATION, "Basic YWxpY2U6c2VjcmV0")
+			.body(Body::empty())
+			.unwrap();
+		let (status, _) = call(authenticator(), req);
+		assert_eq!(status, StatusCode::UNAUTHORIZED);
+	}
+
+	#[test]
+	fn preflight_requests_are_not_authenticated() {
+		let (status, claims) = call(authenticator(), request(Method::OPTIONS, None));
+		assert_eq!(status, StatusCode::OK);
+		assert_eq!(claims, None);
+	}
+}
@@ -0,0 +1,164 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Address of the client of requests relayed by trusted proxies.
+
+use std::net::{IpAddr, SocketAddr};
+
+use http::HeaderMap;
+use ip_network::IpNetwork;
+
+const X_FORWARDED_FOR: &str = "x-forwarded-for";
+const X_REAL_IP: &str = "x-real-ip";
+
+/// The address of the client which sent a request.
+///
+/// The `X-Forwarded-For` and `X-Real-IP` headers are only honored if the peer is one of the
+/// trusted proxies, as they can be set to anything by the client. The `X-Forwarded-For` chain is
+/// walked from the right, skipping the trusted proxies, such that the entries prepended by the
+/// client are ignored.
+pub(crate) fn client_ip(
+	remote_ip: IpAddr,
+	headers: &HeaderMap,
+	trusted_proxies: &[IpNetwork],
+) -> IpAddr {
+	if !is_trusted(remote_ip, trusted_proxies) {
+		return remote_ip
+	}
+
+	if headers.contains_key(X_FORWARDED_FOR) {
+		let hops = headers
+			.get_all(X_FORWARDED_FOR)
+			.iter()
+			.flat_map(|value| value.to_str().unwrap_or_default().split(','))
+			.collect::<Vec<_>>();
+
+		let mut client_ip = remote_ip;
+		for hop in hops.into_iter().rev() {
+			// The hops to the left of an invalid one can't be trusted.
+			let Some(ip) = parse_hop(hop) else { break };
+			client_ip = ip;
+			if !is_trusted(ip, trusted_proxies) {
+				break
+			}
+		}
+		return client_ip
+	}
+
+	headers
+		.get(X_REAL_IP)
+		.and_then(|value| value.to_str().ok())
+		.and_then(parse_hop)
+		.unwrap_or(remote_ip)
+}
+
+fn is_trusted(ip: IpAddr, trusted_proxies: &[IpNetwork]) -> bool {
+	let ip = ip.to_canonical();
+	trusted_proxies.iter().any(|network| network.contains(ip))
+}
+
+/// Parse an address, which some proxies append the port to.
+fn parse_hop(hop: &str) -> Option<IpAddr> {
+	let hop = hop.trim();
+	hop.parse::<IpAddr>()
+		.or_else(|_| hop.parse::<SocketAddr>().map(|addr| addr.ip()))
+		.ok()
+		.map(|ip| ip.to_canonical())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use http::HeaderValue;
+
+	fn trusted_proxies() -> Vec<IpNetwork> {
+		vec![IpNetwork::from_str_truncate("10.0.0.0/8").unwrap()]
+	}
+
+	fn header_map(headers: &[(&'static str, &'static str)]) -> HeaderMap {
+		let mut map = HeaderMap::new();
+		for (name, value) in headers {
+			map.append(*name, HeaderValue::from_static(value));
+		}
+		map
+	}
+
+	fn ip(ip: &str) -> IpAddr {
+		ip.parse().unwrap()
+	}
+
+	#[test]
+	fn headers_from_untrusted_peers_are_ignored() {
+		let headers = header_map(&[
+			(X_FORWARDED_FOR, "1.1.1.1"),
+			(X_REAL_IP, "1.1.1.1"),
+			("forwarded", "for=1.1.1.1"),
+		]);
+		assert_eq!(client_ip(ip("2.2.2.2"), &headers, &trusted_proxies()), ip("2.2.2.2"));
+		assert_eq!(client_ip(ip("2.2.2.2"), &headers, &[]), ip("2.2.2.2"));
+	}
+
+	#[test]
+	fn peer_is_the_client_without_headers() {
+		assert_eq!(
+			client_ip(ip("10.0.0.1"), &HeaderMap::new(), &trusted_proxies()),
+			ip("10.0.0.1")
+		);
+	}
+
+	#[test]
+	fn multi_hop_chain_resolves_to_the_right_most_untrusted_address() {
+		// `1.1.1.1` was prepended by the client and must not be picked.
+		let headers = header_map(&[(X_FORWARDED_FOR, "1.1.1.1, 2.2.2.2, 10.0.0.3, 10.0.0.2")]);
+		assert_eq!(client_ip(ip("10.0.0.1"), &headers, &trusted_proxies()), ip("2.2.2.2"));
+	}
+
+	#[test]
+	fn multi_hop_chain_spans_header_lines() {
+		let headers =
+			header_map(&[(X_FORWARDED_FOR, "1.1.1.1, 2.2.2.2"), (X_FORWARDED_FOR, "10.0.0.2")]);
+		assert_eq!(client_ip(ip("10.0.0.1"), &headers, &trusted_proxies()), ip("2.2.2.2"));
+	}
+
+	#[test]
+	fn chain_of_trusted_proxies_resolves_to_the_left_most_address() {
+		let headers = header_map(&[(X_FORWARDED_FOR, "10.0.0.3, 10.0.0.2")]);
+		assert_eq!(client_ip(ip("10.0.0.1"), &headers, &trusted_proxies()), ip("10.0.0.3"));
+	}
+
+	#[test]
+	fn hops_left_of_an_invalid_hop_are_ignored() {
+		let headers = header_map(&[(X_FORWARDED_FOR, "2.2.2.2, unknown, 10.0.0.2")]);
+		assert_eq!(client_ip(ip("10.0.0.1"), &headers, &trusted_proxies()), ip("10.0.0.2"));
+	}
+
+	#[test]
+	fn hops_with_ports_and_mapped_addresses_are_parsed() {
+		let headers = header_map(&[(X_FORWARDED_FOR, "2.2.2.2:1234, [::ffff:10.0.0.2]:80")]);
+		assert_eq!(client_ip(ip("::ffff:10.0.0.1"), &headers, &trusted_proxies()), ip("2.2.2.2"));
+	}
+
+	#[test]
+	fn real_ip_is_used_without_forwarded_for() {
+		let headers = header_map(&[(X_REAL_IP, "2.2.2.2")]);
+		assert_eq!(client_ip(ip("10.0.0.1"), &headers, &trusted_proxies()), ip("2.2.2.2"));
+
+		let headers = header_map(&[(X_REAL_IP, "2.2.2.2"), (X_FORWARDED_FOR, "3.3.3.3")]);
+		assert_eq!(client_ip(ip("10.0.0.1"), &headers, &trusted_proxies()), ip("3.3.3.3"));
+	}
+}
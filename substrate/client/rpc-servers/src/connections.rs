@@ -0,0 +1,277 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Tracking of the active connections, exposed by the `rpc_connections` admin methods.
+
+use std::{
+	collections::BTreeMap,
+	future::Future,
+	net::SocketAddr,
+	sync::{
+		atomic::{AtomicBool, AtomicU64, Ordering},
+		Arc, Mutex,
+	},
+	time::{SystemTime, UNIX_EPOCH},
+};
+
+use futures::future::{BoxFuture, FutureExt};
+use jsonrpsee::{
+	server::{middleware::rpc::RpcServiceT, ServerHandle, StopHandle},
+	types::Request,
+	MethodResponse, RpcModule,
+};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Notify;
+
+/// Transport of a connection.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Transport {
+	/// Plain HTTP requests.
+	Http,
+	/// WebSocket session.
+	Ws,
+}
+
+/// Active connection as reported by `rpc_connections_list`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectionInfo {
+	/// Identifier to close the connection with.
+	pub id: u64,
+	/// Address of the peer.
+	pub remote_addr: SocketAddr,
+	/// Transport of the connection.
+	pub transport: Transport,
+	/// Time the connection was opened at, in milliseconds since the Unix epoch.
+	pub connected_since: u64,
+	/// Number of subscriptions opened over the connection.
+	pub subscriptions: u64,
+	/// Number of calls served over the connection.
+	pub calls: u64,
+}
+
+/// State of an active connection.
+#[derive(Debug)]
+pub(crate) struct Connection {
+	id: u64,
+	remote_addr: SocketAddr,
+	connected_since: u64,
+	is_websocket: AtomicBool,
+	subscriptions: AtomicU64,
+	calls: AtomicU64,
+	is_closed: AtomicBool,
+	close: Notify,
+}
+
+impl Connection {
+	fn info(&self) -> ConnectionInfo {
+		ConnectionInfo {
+			id: self.id,
+			remote_addr: self.remote_addr,
+			transport: if self.is_websocket.load(Ordering::Relaxed) {
+				Transport::Ws
+			} else {
+				Transport::Http
+			},
+			connected_since: self.connected_since,
+			subscriptions: self.subscriptions.load(Ordering::Relaxed),
+			calls: self.calls.load(Ordering::Relaxed),
+		}
+	}
+
+	pub(crate) fn id(&self) -> u64 {
+		self.id
+	}
+
+	/// Whether the connection was closed via the admin API.
+	///
+	/// Requests on a closed connection are rejected, which makes `hyper` drop HTTP/1.1
+	/// connections.
+	pub(crate) fn is_closed(&self) -> bool {
+		self.is_closed.load(Ordering::Acquire)
+	}
+
+	async fn closed(&self) {
+		let notified = self.close.notified();
+		if self.is_closed() {
+			return
+		}
+		notified.await
+	}
+
+	/// Wait for the WebSocket session of the connection to end.
+	///
+	/// The session is stopped along with the server or when the connection is closed via
+	/// the admin API.
+	pub(crate) async fn serve_websocket(
+		&self,
+		session: ServerHandle,
+		server_stop: StopHandle,
+		on_disconnect: impl Future<Output = ()>,
+	) {
+		self.is_websocket.store(true, Ordering::Relaxed);
+
+		let mut on_disconnect = Box::pin(on_disconnect);
+		let stop =
+			futures::future::select(Box::pin(self.closed()), Box::pin(server_stop.shutdown()));
+		if let futures::future::Either::Right(_) =
+			futures::future::select(&mut on_disconnect, stop).await
+		{
+			let _ = session.stop();
+			on_disconnect.await;
+		}
+	}
+}
+
+/// Registry of the active connections.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Connections {
+	next_id: Arc<AtomicU64>,
+	active: Arc<Mutex<BTreeMap<u64, Arc<Connection>>>>,
+}
+
+impl Connections {
+	/// Register a newly accepted connection, it's unregistered when the returned value is
+	/// dropped.
+	pub(crate) fn register(&self, remote_addr: SocketAddr) -> Registration {
+		let connected_since = SystemTime::now()
+			.duration_since(UNIX_EPOCH)
+			.map_or(0, |since| since.as_millis() as u64);
+		let connection = Arc::new(Connection {
+			id: self.next_id.fetch_add(1, Ordering::Relaxed),
+			remote_addr,
+			connected_since,
+			is_websocket: AtomicBool::new(false),
+			subscriptions: AtomicU64::new(0),
+			calls: AtomicU64::new(0),
+			is_closed: AtomicBool::new(false),
+			close: Notify::new(),
+		});
+
+		self.active
+			.lock()
+			.expect("Lock poisoned; qed")
+			.insert(connection.id, connection.clone());
+
+		Registration { connection, connections: self.clone() }
+	}
+
+	fn list(&self) -> Vec<ConnectionInfo> {
+		self.active
+			.lock()
+			.expect("Lock poisoned; qed")
+			.values()
+			.map(|connection| connection.info())
+			.collect()
+	}
+
+	/// Close the connection with the given id, returns `false` if there is no such connection.
+	fn close(&self, id: u64) -> bool {
+		let Some(connection) = self.active.lock().expect("Lock poisoned; qed").get(&id).cloned()
+		else {
+			return false
+		};
+
+		log::debug!(target: "rpc", "Closing connection {id} from {}", connection.remote_addr);
+		connection.is_closed.store(true, Ordering::Release);
+		connection.close.notify_waiters();
+		true
+	}
+
+	/// Register the `rpc_connections_list` and `rpc_connections_close` methods.
+	pub(crate) fn register_methods<M: Send + Sync + 'static>(&self, rpc_api: &mut RpcModule<M>) {
+		let connections = self.clone();
+		rpc_api
+			.register_method("rpc_connections_list", move |_, _| connections.list())
+			.expect("infallible all other methods have their own address space; qed");
+
+		let connections = self.clone();
+		rpc_api
+			.register_method("rpc_connections_close", move |params, _| {
+				params.one::<u64>().map(|id| connections.close(id))
+			})
+			.expect("infallible all other methods have their own address space; qed");
+	}
+}
+
+/// Registered connection, unregistered on drop.
+#[derive(Debug)]
+pub(crate) struct Registration {
+	connection: Arc<Connection>,
+	connections: Connections,
+}
+
+impl Registration {
+	pub(crate) fn connection(&self) -> &Arc<Connection> {
+		&self.connection
+	}
+}
+
+impl Drop for Registration {
+	fn drop(&mut self) {
+		self.connections
+			.active
+			.lock()
+			.expect("Lock poisoned; qed")
+			.remove(&self.connection.id);
+	}
+}
+
+/// Layer counting the calls and subscriptions of a connection.
+#[derive(Debug, Clone)]
+pub(crate) struct CountCallsLayer(pub(crate) Arc<Connection>);
+
+impl<S> tower::Layer<S> for CountCallsLayer {
+	type Service = CountCalls<S>;
+
+	fn layer(&self, service: S) -> Self::Service {
+		CountCalls { service, connection: self.0.clone() }
+	}
+}
+
+/// JSON-RPC middleware counting the calls and subscriptions of a connection.
+#[derive(Debug, Clone)]
+pub(crate) struct CountCalls<S> {
+	service: S,
+	connection: Arc<Connection>,
+}
+
+impl<'a, S> RpcServiceT<'a> for CountCalls<S>
+where
+	S: RpcServiceT<'a>,
+	S::Future: Send + 'a,
+{
+	type Future = BoxFuture<'a, MethodResponse>;
+
+	fn call(&self, req: Request<'a>) -> Self::Future {
+		self.connection.calls.fetch_add(1, Ordering::Relaxed);
+
+		let connection = self.connection.clone();
+		let fut = self.service.call(req);
+
+		async move {
+			let rp = fut.await;
+			if rp.is_subscription() && rp.is_success() {
+				connection.subscriptions.fetch_add(1, Ordering::Relaxed);
+			}
+			rp
+		}
+		.boxed()
+	}
+}
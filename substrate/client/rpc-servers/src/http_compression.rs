@@ -0,0 +1,104 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! HTTP response compression.
+//!
+//! The responses are compressed with gzip or brotli, as negotiated with the `Accept-Encoding`
+//! header of the request. The event streams are never compressed, their events would be
+//! delayed until enough of them fill a compressed block.
+
+use std::{
+	error::Error as StdError,
+	future::Future,
+	pin::Pin,
+	task::{Context, Poll},
+};
+
+use hyper::{body::HttpBody, Body, Request, Response};
+use tower_http::compression::{
+	predicate::{And, NotForContentType, Predicate, SizeAbove},
+	Compression, CompressionLayer,
+};
+
+/// HTTP compression configuration.
+#[derive(Debug, Copy, Clone)]
+pub struct HttpCompressionConfig {
+	/// Responses smaller than this number of bytes are sent uncompressed.
+	pub min_size: u16,
+}
+
+impl Default for HttpCompressionConfig {
+	fn default() -> Self {
+		Self { min_size: 1024 }
+	}
+}
+
+/// Layer compressing the HTTP responses.
+#[derive(Clone)]
+pub(crate) struct HttpCompressionLayer(CompressionLayer<And<SizeAbove, NotForContentType>>);
+
+impl HttpCompressionLayer {
+	pub(crate) fn new(config: HttpCompressionConfig) -> Self {
+		Self(CompressionLayer::new().gzip(true).br(true).compress_when(
+			SizeAbove::new(config.min_size).and(NotForContentType::const_new("text/event-stream")),
+		))
+	}
+}
+
+impl<S> tower::Layer<S> for HttpCompressionLayer {
+	type Service = HttpCompression<S>;
+
+	fn layer(&self, inner: S) -> Self::Service {
+		HttpCompression(self.0.layer(inner))
+	}
+}
+
+/// Service compressing the HTTP responses.
+///
+/// `jsonrpsee` requires the HTTP middleware to respond with a [`Body`], so the compressed body
+/// is streamed into one.
+#[derive(Clone)]
+pub(crate) struct HttpCompression<S>(Compression<S, And<SizeAbove, NotForContentType>>);
+
+impl<S> tower::Service<Request<Body>> for HttpCompression<S>
+where
+	S: tower::Service<Request<Body>, Response = Response<Body>>,
+	S::Error: Into<Box<dyn StdError + Send + Sync>> + 'static,
+	S::Future: Send + 'static,
+{
+	type Response = Response<Body>;
+	type Error = Box<dyn StdError + Send + Sync>;
+	type Future =
+		Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send + 'static>>;
+
+	fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+		self.0.poll_ready(cx).map_err(Into::into)
+	}
+
+	fn call(&mut self, req: Request<Body>) -> Self::Future {
+		let fut = self.0.call(req);
+
+		Box::pin(async move {
+			let response = fut.await.map_err(Into::into)?;
+			Ok(response.map(|body| {
+				let mut body = Box::pin(body);
+				Body::wrap_stream(futures::stream::poll_fn(move |cx| body.as_mut().poll_data(cx)))
+			}))
+		})
+	}
+}
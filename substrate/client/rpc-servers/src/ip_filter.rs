@@ -0,0 +1,105 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Filtering of RPC connections by the IP address of the peer.
+
+use std::net::IpAddr;
+
+use ip_network::IpNetwork;
+
+/// Allowlist and denylist of IP addresses permitted to connect to the RPC server.
+///
+/// Single addresses are expressed as networks with the full prefix length,
+/// e.g. `10.0.0.1/32`.
+#[derive(Debug, Clone, Default)]
+pub struct IpFilter {
+	/// Networks allowed to connect.
+	///
+	/// If empty, every address that is not denied is allowed.
+	pub allow: Vec<IpNetwork>,
+	/// Networks not allowed to connect, takes precedence over `allow`.
+	pub deny: Vec<IpNetwork>,
+}
+
+impl IpFilter {
+	/// Whether a peer with the given address may connect.
+	pub fn is_allowed(&self, ip: IpAddr) -> bool {
+		// IPv4 peers connecting to a dual-stack socket show up as IPv4-mapped IPv6 addresses.
+		let ip = ip.to_canonical();
+
+		if self.deny.iter().any(|net| net.contains(ip)) {
+			return false
+		}
+
+		self.allow.is_empty() || self.allow.iter().any(|net| net.contains(ip))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn filter(allow: &[&str], deny: &[&str]) -> IpFilter {
+		let parse = |nets: &[&str]| {
+			nets.iter().map(|net| IpNetwork::from_str_truncate(net).unwrap()).collect()
+		};
+		IpFilter { allow: parse(allow), deny: parse(deny) }
+	}
+
+	fn ip(ip: &str) -> IpAddr {
+		ip.parse().unwrap()
+	}
+
+	#[test]
+	fn empty_filter_allows_everything() {
+		let filter = IpFilter::default();
+		assert!(filter.is_allowed(ip("127.0.0.1")));
+		assert!(filter.is_allowed(ip("::1")));
+	}
+
+	#[test]
+	fn allow_list_restricts_to_its_networks() {
+		let filter = filter(&["10.0.0.0/8", "2001:db8::/32"], &[]);
+		assert!(filter.is_allowed(ip("10.1.2.3")));
+		assert!(filter.is_allowed(ip("2001:db8::1")));
+		assert!(!filter.is_allowed(ip("11.0.0.1")));
+		assert!(!filter.is_allowed(ip("2001:db9::1")));
+	}
+
+	#[test]
+	fn deny_list_only_rejects_its_networks() {
+		let filter = filter(&[], &["192.168.1.0/24"]);
+		assert!(!filter.is_allowed(ip("192.168.1.7")));
+		assert!(filter.is_allowed(ip("192.168.2.7")));
+	}
+
+	#[test]
+	fn deny_takes_precedence_over_allow() {
+		let filter = filter(&["10.0.0.0/8"], &["10.0.0.1/32"]);
+		assert!(!filter.is_allowed(ip("10.0.0.1")));
+		assert!(filter.is_allowed(ip("10.0.0.2")));
+	}
+
+	#[test]
+	fn ipv4_mapped_ipv6_addresses_are_matched_as_ipv4() {
+		let filter = filter(&["10.0.0.0/8"], &["10.0.0.1/32"]);
+		assert!(filter.is_allowed(ip("::ffff:10.0.0.2")));
+		assert!(!filter.is_allowed(ip("::ffff:10.0.0.1")));
+		assert!(!filter.is_allowed(ip("::ffff:11.0.0.1")));
+	}
+}
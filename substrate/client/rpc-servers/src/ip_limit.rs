@@ -0,0 +1,91 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Limit on the number of concurrent connections per peer IP address.
+
+use std::{
+	collections::HashMap,
+	net::IpAddr,
+	num::NonZeroU32,
+	sync::{Arc, Mutex},
+};
+
+use crate::RpcMetrics;
+
+/// Number of concurrent connections of the peers of a listener, by their IP address.
+#[derive(Debug, Clone)]
+pub(crate) struct IpConnections {
+	max_per_ip: Option<NonZeroU32>,
+	active: Arc<Mutex<HashMap<IpAddr, usize>>>,
+	metrics: Option<RpcMetrics>,
+}
+
+impl IpConnections {
+	pub(crate) fn new(max_per_ip: Option<NonZeroU32>, metrics: Option<RpcMetrics>) -> Self {
+		Self { max_per_ip, active: Default::default(), metrics }
+	}
+
+	/// Account a new connection of the peer with the given address, unless it would exceed the
+	/// limit and the peer isn't exempt from it.
+	///
+	/// The connection is accounted until the returned guard is dropped.
+	pub(crate) fn try_acquire(&self, ip: IpAddr, is_exempt: bool) -> Option<IpConnectionGuard> {
+		let ip = ip.to_canonical();
+		let mut active = self.active.lock().expect("Lock poisoned; qed");
+		let connections = active.entry(ip).or_default();
+
+		if let Some(max) = self.max_per_ip.filter(|_| !is_exempt) {
+			if *connections >= max.get() as usize {
+				return None
+			}
+		}
+
+		*connections += 1;
+		if let Some(metrics) = &self.metrics {
+			metrics.on_peer_connections_changed(*connections - 1, *connections);
+		}
+
+		Some(IpConnectionGuard { ip, connections: self.clone() })
+	}
+
+	fn release(&self, ip: IpAddr) {
+		let mut active = self.active.lock().expect("Lock poisoned; qed");
+		let Some(connections) = active.get_mut(&ip) else { return };
+
+		*connections -= 1;
+		if let Some(metrics) = &self.metrics {
+			metrics.on_peer_connections_changed(*connections + 1, *connections);
+		}
+		if *connections == 0 {
+			active.remove(&ip);
+		}
+	}
+}
+
+/// Connection accounted by [`IpConnections`], released when dropped.
+#[derive(Debug)]
+pub(crate) struct IpConnectionGuard {
+	ip: IpAddr,
+	connections: IpConnections,
+}
+
+impl Drop for IpConnectionGuard {
+	fn drop(&mut self) {
+		self.connections.release(self.ip);
+	}
+}
@@ -20,28 +20,59 @@
 
 #![warn(missing_docs)]
 
+mod api_keys;
+mod auth;
+mod client_ip;
+mod connections;
+mod http_compression;
+mod ip_filter;
+mod ip_limit;
+mod method_alias;
+mod method_cors;
+mod method_filter;
 pub mod middleware;
+mod reload;
+mod socket;
+mod sse;
+mod tls;
+mod ws_compression;
 
 use std::{
-	convert::Infallible, error::Error as StdError, net::SocketAddr, num::NonZeroU32, time::Duration,
+	collections::HashMap,
+	error::Error as StdError,
+	io,
+	net::SocketAddr,
+	num::NonZeroU32,
+	sync::{
+		atomic::{AtomicU32, Ordering},
+		Arc, Mutex,
+	},
+	time::Duration,
 };
 
 use http::header::HeaderValue;
-use hyper::{
-	server::conn::AddrStream,
-	service::{make_service_fn, service_fn},
-};
+use hyper::service::{make_service_fn, service_fn};
 use jsonrpsee::{
 	server::{
-		middleware::http::{HostFilterLayer, ProxyGetRequestLayer},
-		stop_channel, ws, PingConfig, StopHandle, TowerServiceBuilder,
+		middleware::{
+			http::{HostFilterLayer, ProxyGetRequestLayer},
+			rpc::either::Either as RpcEither,
+		},
+		stop_channel, ws, AlreadyStoppedError, PingConfig, ServerHandle, StopHandle,
+		TowerServiceBuilder,
 	},
+	types::SubscriptionId,
 	Methods, RpcModule,
 };
-use tokio::net::TcpListener;
-use tower::Service;
+use tower::{layer::util::Identity, Service, ServiceExt};
 use tower_http::cors::{AllowOrigin, CorsLayer};
 
+pub use api_keys::{ApiKeyTier, ApiKeysConfig, API_KEY_HEADER};
+pub use auth::{AuthConfig, AuthKeys, Claims};
+pub use connections::{ConnectionInfo, Transport};
+pub use http_compression::HttpCompressionConfig;
+pub use ip_filter::IpFilter;
+pub use ip_network::IpNetwork;
 pub use jsonrpsee::{
 	core::{
 		id_providers::{RandomIntegerIdProvider, RandomStringIdProvider},
@@ -49,22 +80,146 @@ pub use jsonrpsee::{
 	},
 	server::{middleware::rpc::RpcServiceBuilder, BatchRequestConfig},
 };
-pub use middleware::{Metrics, MiddlewareLayer, RpcMetrics};
+pub use method_alias::MethodAlias;
+pub use method_cors::MethodCors;
+pub use method_filter::{MethodFilter, MethodPattern};
+pub use middleware::{
+	AccessLogLayer, AuditLayer, AuditLog, AuditLogConfig, BoxedRpcService, CallCostBudgets,
+	CallCostConfig, CallCostLayer, CallSpanLayer, CircuitBreaker, CircuitBreakerConfig,
+	CircuitBreakerLayer, DeprecationLayer, DeprecationWarnings, LoadShedConfig, LoadShedLayer,
+	MethodCost, MethodTimeout, Metrics, MetricsConfig, MiddlewareContext, MiddlewareLayer,
+	QuotaConfig, QuotaConsumer, QuotaLayer, QuotaLimit, QuotaPeriod, Quotas, RateLimit,
+	ResponseCache, ResponseCacheConfig, ResponseCacheLayer, ResponseLimitLayer, RpcMetrics,
+	RpcMiddlewareExtension, SlowCallLayer, TimeoutConfig, TimeoutLayer, ACCESS_LOG_TARGET,
+	ALWAYS_SERVED_METHODS, CIRCUIT_OPEN_CODE, COST_BUDGET_EXCEEDED_CODE, DEFAULT_CACHED_METHODS,
+	DEPRECATION_WARNING_MEMBER, QUOTA_EXCEEDED_CODE, RESPONSE_TOO_LARGE_CODE, TIMEOUT_CODE,
+};
+pub use reload::{ReloadHandle, ReloadableConfig};
+pub use socket::SocketConfig;
+pub use sse::{SseConfig, SSE_PATH};
+pub use tls::{TlsCertificate, TlsConfig};
+pub use ws_compression::WsCompressionConfig;
 
 const MEGABYTE: u32 = 1024 * 1024;
 
-/// Type alias for the JSON-RPC server.
-pub type Server = jsonrpsee::server::ServerHandle;
+/// Handle of a running JSON-RPC server.
+#[derive(Debug, Clone)]
+pub struct Server {
+	handle: ServerHandle,
+	listeners: Vec<Listener>,
+}
 
-/// RPC server configuration.
+impl Server {
+	/// Tell the server to stop without waiting for it to shut down.
+	pub fn stop(&self) -> Result<(), AlreadyStoppedError> {
+		self.handle.stop()
+	}
+
+	/// Wait for the server to shut down.
+	pub async fn stopped(self) {
+		self.handle.stopped().await
+	}
+
+	/// The listeners of the server, in the order they were configured.
+	pub fn listeners(&self) -> &[Listener] {
+		&self.listeners
+	}
+}
+
+/// Listener of a running JSON-RPC server.
+#[derive(Debug, Clone)]
+pub struct Listener {
+	local_addr: Option<SocketAddr>,
+	reload: ReloadHandle,
+}
+
+impl Listener {
+	/// The address the listener is bound to.
+	pub fn local_addr(&self) -> Option<SocketAddr> {
+		self.local_addr
+	}
+
+	/// Handle to change the settings of the listener while it is running.
+	pub fn reload_handle(&self) -> &ReloadHandle {
+		&self.reload
+	}
+}
+
+/// Address to listen on along with the RPC API and the settings of the connections accepted on
+/// it.
 #[derive(Debug)]
-pub struct Config<'a, M: Send + Sync + 'static> {
-	/// Socket addresses.
-	pub addrs: [SocketAddr; 2],
-	/// CORS.
-	pub cors: Option<&'a Vec<String>>,
+pub struct ListenerConfig<M: Send + Sync + 'static> {
+	/// Socket address.
+	pub addr: SocketAddr,
+	/// Listen on a port assigned by the OS if the port of `addr` can't be bound.
+	pub retry_random_port: bool,
+	/// RPC API.
+	pub rpc_api: RpcModule<M>,
+	/// Expose the `rpc_connections_list` and `rpc_connections_close` methods to list and
+	/// close the active connections of all listeners.
+	pub connections_api: bool,
+	/// CORS, `None` to allow any origin.
+	pub cors: Option<Vec<String>>,
+	/// CORS of specific methods, overriding `cors` for them.
+	pub method_cors: Vec<MethodCors>,
 	/// Maximum connections.
 	pub max_connections: u32,
+	/// Maximum concurrent connections per peer IP address, `None` for no limit.
+	///
+	/// The limit applies to the address of the peer of the TCP connection, proxies relaying
+	/// many clients may be exempted via `rate_limit_whitelisted_ips`.
+	pub max_connections_per_ip: Option<NonZeroU32>,
+	/// Rate limit calls per minute.
+	pub rate_limit: Option<NonZeroU32>,
+	/// IP addresses, or CIDR ranges, of peers exempt from the rate limit and the per-IP
+	/// connection limit.
+	pub rate_limit_whitelisted_ips: Vec<IpNetwork>,
+	/// Filter on the exposed methods, `None` to expose all of them.
+	pub method_filter: Option<MethodFilter>,
+}
+
+/// HTTP/2 configuration.
+///
+/// HTTP/2 is served over cleartext connections using prior knowledge (h2c) and negotiated via
+/// ALPN over TLS. WebSocket connections always use HTTP/1.1.
+#[derive(Debug, Copy, Clone)]
+pub struct Http2Config {
+	/// Maximum number of concurrent streams, i.e. in-flight calls, per connection.
+	pub max_concurrent_streams: u32,
+}
+
+impl Default for Http2Config {
+	fn default() -> Self {
+		Self { max_concurrent_streams: 100 }
+	}
+}
+
+/// WebSocket keepalive configuration.
+#[derive(Debug, Copy, Clone)]
+pub struct WsPingConfig {
+	/// Interval between the pings sent to the peer.
+	pub interval: Duration,
+	/// Time after which a peer which didn't answer a ping is regarded as inactive.
+	pub inactive_limit: Duration,
+	/// Number of times a peer may be inactive before the connection is closed.
+	pub max_failures: NonZeroU32,
+}
+
+impl Default for WsPingConfig {
+	fn default() -> Self {
+		Self {
+			interval: Duration::from_secs(30),
+			inactive_limit: Duration::from_secs(60),
+			max_failures: NonZeroU32::new(3).expect("3 is not zero; qed"),
+		}
+	}
+}
+
+/// RPC server configuration.
+#[derive(Debug)]
+pub struct Config<M: Send + Sync + 'static> {
+	/// Listeners.
+	pub listeners: Vec<ListenerConfig<M>>,
 	/// Maximum subscriptions per connection.
 	pub max_subs_per_conn: u32,
 	/// Maximum rpc request payload size.
@@ -75,157 +230,686 @@ pub struct Config<'a, M: Send + Sync + 'static> {
 	pub metrics: Option<RpcMetrics>,
 	/// Message buffer size
 	pub message_buffer_capacity: u32,
-	/// RPC API.
-	pub rpc_api: RpcModule<M>,
+	/// WebSocket keepalive.
+	pub ws_ping: WsPingConfig,
 	/// Subscription ID provider.
 	pub id_provider: Option<Box<dyn IdProvider>>,
 	/// Tokio runtime handle.
 	pub tokio_handle: tokio::runtime::Handle,
 	/// Batch request config.
 	pub batch_config: BatchRequestConfig,
-	/// Rate limit calls per minute.
-	pub rate_limit: Option<NonZeroU32>,
+	/// TLS configuration, `None` to serve plain HTTP/WS.
+	pub tls: Option<TlsConfig>,
+	/// Filter on the IP addresses allowed to connect, `None` to accept any address.
+	pub ip_filter: Option<IpFilter>,
+	/// WebSocket `permessage-deflate` compression, `None` to disable it.
+	pub ws_compression: Option<WsCompressionConfig>,
+	/// Compression of the HTTP responses, `None` to disable it.
+	pub http_compression: Option<HttpCompressionConfig>,
+	/// Bearer token authentication, `None` to accept unauthenticated requests.
+	pub auth: Option<AuthConfig>,
+	/// HTTP/2 support, `None` to only serve HTTP/1.1.
+	pub http2: Option<Http2Config>,
+	/// Audit log of a sample of the calls, `None` to disable it.
+	pub audit_log: Option<AuditLogConfig>,
+	/// Cache of the responses of idempotent methods, `None` to disable it.
+	pub response_cache: Option<ResponseCache>,
+	/// Rejection of calls while the server is under pressure, `None` to disable it.
+	pub load_shed: Option<LoadShedConfig>,
+	/// Proxies trusted to report the address of the client in the `X-Forwarded-For` and
+	/// `X-Real-IP` headers, which is then used for the rate limit and the audit log.
+	pub trusted_proxies: Vec<IpNetwork>,
+	/// API keys granting the requests carrying them the rate limit and subscription limit of
+	/// a tier, `None` to ignore the `X-Api-Key` header.
+	///
+	/// Requests without a key are subject to the limits of the listener, requests with an
+	/// unknown key are rejected.
+	pub api_keys: Option<ApiKeysConfig>,
+	/// Cost of the calls paid from a budget of each IP address, `None` to disable it.
+	///
+	/// The peers exempt from the rate limit are exempt from the budget as well.
+	pub call_cost: Option<CallCostConfig>,
+	/// Quotas on the calls and bandwidth of each API key, or IP address for the requests without
+	/// a key, `None` to disable them.
+	///
+	/// The peers exempt from the rate limit are exempt from the quotas as well.
+	pub quota: Option<QuotaConfig>,
+	/// Timeout of the calls, `None` to let them run for as long as they take.
+	pub timeout: Option<TimeoutConfig>,
+	/// Rejection of the calls of the methods failing or slowing down, `None` to disable it.
+	pub circuit_breaker: Option<CircuitBreakerConfig>,
+	/// Aliases of the methods, registered on the listeners exposing the aliased methods.
+	pub method_aliases: Vec<MethodAlias>,
+	/// Write a structured access log record of every call to the [`ACCESS_LOG_TARGET`] log
+	/// target.
+	pub access_log: bool,
+	/// Log the calls taking longer than this at warn level, `None` to disable it.
+	pub slow_call_threshold: Option<Duration>,
+	/// Serve every call within a `rpc_call` tracing span and every subscription within a
+	/// `rpc_subscription` span, for them to be exported to a tracing backend.
+	pub call_spans: bool,
+	/// Deprecated methods, whose calls are counted by a metric and answered with a warning.
+	///
+	/// The warning is added to the object results and the error data as a
+	/// [`DEPRECATION_WARNING_MEMBER`] member, and as a `Warning` header of the HTTP responses.
+	pub deprecated_methods: Vec<MethodPattern>,
+	/// Middleware of the embedder, see [`RpcMiddlewareExtension`].
+	pub middleware_extensions: Vec<Arc<dyn RpcMiddlewareExtension>>,
+	/// Server-Sent Events transport of the subscriptions, `None` to disable it.
+	pub sse: Option<SseConfig>,
+	/// TCP socket options of the listeners.
+	pub socket: SocketConfig,
+}
+
+/// [`IdProvider`] shared by the services of the connections with different subscription
+/// limits.
+#[derive(Debug, Clone)]
+struct SharedIdProvider(Arc<dyn IdProvider>);
+
+impl IdProvider for SharedIdProvider {
+	fn next_id(&self) -> SubscriptionId<'static> {
+		self.0.next_id()
+	}
+}
+
+type NewServiceBuilder = Arc<dyn Fn(u32) -> TowerServiceBuilder<Identity, Identity> + Send + Sync>;
+
+/// Builders of the services of a listener by the subscription limit of the connections.
+#[derive(Clone)]
+struct ServiceBuilders {
+	default: TowerServiceBuilder<Identity, Identity>,
+	max_subs_per_conn: u32,
+	max_connections: u32,
+	new_builder: NewServiceBuilder,
+	by_subscription_limit: Arc<Mutex<HashMap<u32, TowerServiceBuilder<Identity, Identity>>>>,
+	next_conn_id: Arc<AtomicU32>,
+}
+
+impl ServiceBuilders {
+	/// The builder of the service of a connection allowed the given number of subscriptions,
+	/// the default one if `None`.
+	fn get(&self, max_subscriptions: Option<u32>) -> TowerServiceBuilder<Identity, Identity> {
+		let builder = match max_subscriptions {
+			Some(max) if max != self.max_subs_per_conn => self
+				.by_subscription_limit
+				.lock()
+				.expect("Lock poisoned; qed")
+				.entry(max)
+				.or_insert_with(|| (self.new_builder)(max).max_connections(self.max_connections))
+				.clone(),
+			_ => self.default.clone(),
+		};
+
+		// Connection ids must be unique across the listeners and the subscription limits.
+		builder.connection_id(self.next_conn_id.fetch_add(1, Ordering::Relaxed))
+	}
+}
+
+impl std::fmt::Debug for ServiceBuilders {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("ServiceBuilders")
+			.field("default", &self.default)
+			.field("max_subs_per_conn", &self.max_subs_per_conn)
+			.field("max_connections", &self.max_connections)
+			.field("by_subscription_limit", &self.by_subscription_limit)
+			.finish()
+	}
 }
 
 #[derive(Debug, Clone)]
-struct PerConnection<RpcMiddleware, HttpMiddleware> {
+struct PerConnection {
 	methods: Methods,
 	stop_handle: StopHandle,
 	metrics: Option<RpcMetrics>,
 	tokio_handle: tokio::runtime::Handle,
-	service_builder: TowerServiceBuilder<RpcMiddleware, HttpMiddleware>,
+	service_builders: ServiceBuilders,
+	ws_compression: Option<WsCompressionConfig>,
+	message_limits: ws_compression::MessageLimits,
+	connections: connections::Connections,
+	sse: Option<SseConfig>,
+	id_provider: Arc<dyn IdProvider>,
 }
 
-/// Start RPC server listening on given address.
-pub async fn start_server<M>(
-	config: Config<'_, M>,
-) -> Result<Server, Box<dyn StdError + Send + Sync>>
+/// Start RPC server listening on the given addresses.
+pub async fn start_server<M>(config: Config<M>) -> Result<Server, Box<dyn StdError + Send + Sync>>
 where
 	M: Send + Sync,
 {
 	let Config {
-		addrs,
+		listeners,
 		batch_config,
-		cors,
 		max_payload_in_mb,
 		max_payload_out_mb,
-		max_connections,
 		max_subs_per_conn,
 		metrics,
 		message_buffer_capacity,
+		ws_ping,
 		id_provider,
 		tokio_handle,
-		rpc_api,
-		rate_limit,
+		tls,
+		ip_filter,
+		ws_compression,
+		http_compression,
+		auth,
+		http2,
+		audit_log,
+		response_cache,
+		load_shed,
+		trusted_proxies,
+		api_keys,
+		call_cost,
+		quota,
+		timeout,
+		circuit_breaker,
+		method_aliases,
+		access_log,
+		slow_call_threshold,
+		call_spans,
+		deprecated_methods,
+		middleware_extensions,
+		sse,
+		socket,
 	} = config;
 
-	let std_listener = TcpListener::bind(addrs.as_slice()).await?.into_std()?;
-	let local_addr = std_listener.local_addr().ok();
-	let host_filter = hosts_filtering(cors.is_some(), local_addr);
-
-	let http_middleware = tower::ServiceBuilder::new()
-		.option_layer(host_filter)
-		// Proxy `GET /health` requests to internal `system_health` method.
-		.layer(ProxyGetRequestLayer::new("/health", "system_health")?)
-		.layer(try_into_cors(cors)?);
-
-	let mut builder = jsonrpsee::server::Server::builder()
-		.max_request_body_size(max_payload_in_mb.saturating_mul(MEGABYTE))
-		.max_response_body_size(max_payload_out_mb.saturating_mul(MEGABYTE))
-		.max_connections(max_connections)
-		.max_subscriptions_per_connection(max_subs_per_conn)
-		.enable_ws_ping(
-			PingConfig::new()
-				.ping_interval(Duration::from_secs(30))
-				.inactive_limit(Duration::from_secs(60))
-				.max_failures(3),
-		)
-		.set_http_middleware(http_middleware)
-		.set_message_buffer_capacity(message_buffer_capacity)
-		.set_batch_request_config(batch_config)
-		.custom_tokio_runtime(tokio_handle.clone());
-
-	if let Some(provider) = id_provider {
-		builder = builder.set_id_provider(provider);
-	} else {
-		builder = builder.set_id_provider(RandomStringIdProvider::new(16));
+	let is_tls = tls.is_some();
+	let tls_acceptor = tls.map(|tls| tls::acceptor(tls, http2.is_some())).transpose()?;
+	let auth = match auth {
+		Some(config) => Some(auth::AuthLayer(auth::Authenticator::new(config).await?)),
+		None => None,
 	};
+	let http_compression = http_compression.map(http_compression::HttpCompressionLayer::new);
+	let audit_log = audit_log.map(AuditLog::new).transpose()?;
+	let load_shed = load_shed.map(LoadShedLayer::new);
+	let trusted_proxies: Arc<[IpNetwork]> = trusted_proxies.into();
+	let api_keys = api_keys.map(api_keys::ApiKeys::new).transpose()?;
+	let call_cost = call_cost.map(CallCostBudgets::new);
+	let quotas = quota.map(Quotas::new).transpose()?;
+	let timeout = timeout.map(Arc::new);
+	let circuit_breaker = circuit_breaker.map(CircuitBreaker::new).transpose()?;
+	let deprecated_methods: Arc<[MethodPattern]> = deprecated_methods.into();
+	let middleware_extensions: Arc<[_]> = middleware_extensions.into();
+	// Proxy `GET /health` requests to internal `system_health` method.
+	let health_proxy = ProxyGetRequestLayer::new("/health", "system_health")?;
+
+	let id_provider = SharedIdProvider(match id_provider {
+		Some(provider) => provider.into(),
+		None => Arc::new(RandomStringIdProvider::new(16)),
+	});
+	let builder_tokio_handle = tokio_handle.clone();
+	let builder_id_provider = id_provider.clone();
+	let new_builder: NewServiceBuilder = Arc::new(move |max_subs_per_conn| {
+		jsonrpsee::server::Server::builder()
+			.max_request_body_size(max_payload_in_mb.saturating_mul(MEGABYTE))
+			// The responses of the calls are limited by the `ResponseLimitLayer`, which reports
+			// the rejected ones.
+			.max_response_body_size(u32::MAX)
+			.max_subscriptions_per_connection(max_subs_per_conn)
+			.enable_ws_ping(
+				PingConfig::new()
+					.ping_interval(ws_ping.interval)
+					.inactive_limit(ws_ping.inactive_limit)
+					.max_failures(ws_ping.max_failures.get() as usize),
+			)
+			.set_message_buffer_capacity(message_buffer_capacity)
+			.set_batch_request_config(batch_config)
+			.set_id_provider(builder_id_provider.clone())
+			.custom_tokio_runtime(builder_tokio_handle.clone())
+			.to_service_builder()
+	});
 
-	let (stop_handle, server_handle) = stop_channel();
-	let cfg = PerConnection {
-		methods: build_rpc_api(rpc_api).into(),
-		service_builder: builder.to_service_builder(),
-		metrics,
-		tokio_handle,
-		stop_handle: stop_handle.clone(),
+	// The listeners share the connection ids and the registry of the connections.
+	let service_builder = new_builder(max_subs_per_conn);
+	let next_conn_id = Arc::new(AtomicU32::new(0));
+	let connections = connections::Connections::default();
+	let message_limits = ws_compression::MessageLimits {
+		request: max_payload_in_mb.saturating_mul(MEGABYTE) as usize,
+		response: max_payload_out_mb.saturating_mul(MEGABYTE) as usize,
 	};
+	let (stop_handle, server_handle) = stop_channel();
+	let mut server_listeners = Vec::with_capacity(listeners.len());
 
-	let make_service = make_service_fn(move |_conn: &AddrStream| {
-		let cfg = cfg.clone();
-
-		async move {
+	for ListenerConfig {
+		addr,
+		retry_random_port,
+		rpc_api,
+		connections_api,
+		cors,
+		method_cors,
+		max_connections,
+		max_connections_per_ip,
+		rate_limit,
+		rate_limit_whitelisted_ips,
+		method_filter,
+	} in listeners
+	{
+		let listener = match socket::bind(addr, &socket) {
+			Ok(listener) => listener,
+			// If binding the specified port failed then a random port is assigned by the OS.
+			Err(_) if retry_random_port => socket::bind(SocketAddr::new(addr.ip(), 0), &socket)?,
+			Err(e) => return Err(e.into()),
+		};
+		let local_addr = listener.local_addr().ok();
+		let reload = ReloadHandle::new(
+			ReloadableConfig {
+				cors: cors.clone(),
+				method_cors,
+				rate_limit,
+				rate_limit_whitelisted_ips,
+			},
+			local_addr,
+		)?;
+		let cfg = PerConnection {
+			methods: build_rpc_api(
+				rpc_api,
+				connections_api.then_some(&connections),
+				method_filter.as_ref(),
+				&method_aliases,
+			)
+			.into(),
+			service_builders: ServiceBuilders {
+				default: service_builder.clone().max_connections(max_connections),
+				max_subs_per_conn,
+				max_connections,
+				new_builder: new_builder.clone(),
+				by_subscription_limit: Default::default(),
+				next_conn_id: next_conn_id.clone(),
+			},
+			metrics: metrics.clone(),
+			tokio_handle: tokio_handle.clone(),
+			stop_handle: stop_handle.clone(),
+			ws_compression,
+			message_limits,
+			connections: connections.clone(),
+			sse,
+			id_provider: id_provider.0.clone(),
+		};
+		let ip_filter = ip_filter.clone();
+		let ip_connections = ip_limit::IpConnections::new(max_connections_per_ip, metrics.clone());
+		let auth = auth.clone();
+		let http_compression = http_compression.clone();
+		let api_keys = api_keys.clone();
+		let call_cost = call_cost.clone();
+		let quotas = quotas.clone();
+		let timeout = timeout.clone();
+		let circuit_breaker = circuit_breaker.clone();
+		let deprecated_methods = deprecated_methods.clone();
+		let middleware_extensions = middleware_extensions.clone();
+		let audit_log = audit_log.clone();
+		let response_cache = response_cache.clone();
+		let load_shed = load_shed.clone();
+		let trusted_proxies = trusted_proxies.clone();
+		let health_proxy = health_proxy.clone();
+		let stop_handle = stop_handle.clone();
+
+		let server_reload = reload.clone();
+		let make_service = make_service_fn(move |conn: &tls::MaybeTlsStream| {
 			let cfg = cfg.clone();
+			let remote_addr = conn.remote_addr();
+			let remote_ip = remote_addr.ip();
+			let is_allowed = ip_filter.as_ref().map_or(true, |filter| filter.is_allowed(remote_ip));
+
+			// Connections keep the settings they were opened with.
+			let snapshot = reload.snapshot();
+			let ip_connection = ip_connections
+				.try_acquire(remote_ip, snapshot.config.is_whitelisted(remote_ip))
+				.map(Arc::new);
+			let audit_log = audit_log.clone();
+			let response_cache = response_cache.clone();
+			let load_shed = load_shed.clone();
+			let trusted_proxies = trusted_proxies.clone();
+			let api_keys = api_keys.clone();
+			let quotas = quotas.clone();
+			let timeout = timeout.clone();
+			let circuit_breaker = circuit_breaker.clone();
+			let deprecated_methods = deprecated_methods.clone();
+			let middleware_extensions = middleware_extensions.clone();
+			let call_cost = call_cost.clone();
+			let http_middleware = tower::ServiceBuilder::new()
+				.option_layer(snapshot.host_filter.clone())
+				// Reject requests without a valid bearer token, including WebSocket upgrades.
+				.option_layer(auth.clone())
+				.layer(health_proxy.clone())
+				.layer(snapshot.cors.clone())
+				.option_layer(http_compression.clone());
+
+			async move {
+				if !is_allowed {
+					log::debug!(target: "rpc", "Rejected connection from {remote_ip} by the IP filter");
+					if let Some(metrics) = &cfg.metrics {
+						metrics.connection_rejected("ip_filter");
+					}
+					return Err(io::Error::new(
+						io::ErrorKind::PermissionDenied,
+						format!("Connection from {remote_ip} is not allowed"),
+					))
+				}
 
-			Ok::<_, Infallible>(service_fn(move |req| {
-				let PerConnection { service_builder, metrics, tokio_handle, stop_handle, methods } =
-					cfg.clone();
-
-				let is_websocket = ws::is_upgrade_request(&req);
-				let transport_label = if is_websocket { "ws" } else { "http" };
-
-				let middleware_layer = match (metrics, rate_limit) {
-					(None, None) => None,
-					(Some(metrics), None) => Some(
-						MiddlewareLayer::new().with_metrics(Metrics::new(metrics, transport_label)),
-					),
-					(None, Some(rate_limit)) =>
-						Some(MiddlewareLayer::new().with_rate_limit_per_minute(rate_limit)),
-					(Some(metrics), Some(rate_limit)) => Some(
-						MiddlewareLayer::new()
-							.with_metrics(Metrics::new(metrics, transport_label))
-							.with_rate_limit_per_minute(rate_limit),
-					),
+				let Some(ip_connection) = ip_connection else {
+					log::debug!(
+						target: "rpc",
+						"Rejected connection from {remote_ip}, too many connections from the address"
+					);
+					if let Some(metrics) = &cfg.metrics {
+						metrics.connection_rejected("ip_limit");
+					}
+					return Err(io::Error::new(
+						io::ErrorKind::ConnectionRefused,
+						format!("Too many connections from {remote_ip}"),
+					))
 				};
 
-				let rpc_middleware =
-					RpcServiceBuilder::new().option_layer(middleware_layer.clone());
-
-				let mut svc =
-					service_builder.set_rpc_middleware(rpc_middleware).build(methods, stop_handle);
-
-				async move {
-					if is_websocket {
-						let on_disconnect = svc.on_session_closed();
-
-						// Spawn a task to handle when the connection is closed.
-						tokio_handle.spawn(async move {
-							let now = std::time::Instant::now();
-							middleware_layer.as_ref().map(|m| m.ws_connect());
-							on_disconnect.await;
-							middleware_layer.as_ref().map(|m| m.ws_disconnect(now));
+				let cfg = cfg.clone();
+				let registration = Arc::new(cfg.connections.register(remote_addr));
+
+				Ok(service_fn(move |req| {
+					let PerConnection {
+						service_builders,
+						metrics,
+						tokio_handle,
+						stop_handle,
+						methods,
+						ws_compression,
+						message_limits,
+						connections: _,
+						sse,
+						id_provider,
+					} = cfg.clone();
+					let registration = registration.clone();
+					let ip_connection = ip_connection.clone();
+					let snapshot = snapshot.clone();
+					let audit_log = audit_log.clone();
+					let response_cache = response_cache.clone();
+					let load_shed = load_shed.clone();
+					// Requests relayed by a proxy are accounted to the client it relays.
+					let client_ip =
+						client_ip::client_ip(remote_ip, req.headers(), &trusted_proxies);
+					let api_key = api_keys
+						.as_ref()
+						.map_or(Ok(None), |api_keys| api_keys.authenticate(req.headers()));
+					let (rate_limit, service_builder) = match &api_key {
+						Ok(Some(api_key)) => (
+							api_key
+								.rate_limit()
+								.filter(|_| !snapshot.config.is_whitelisted(client_ip)),
+							service_builders.get(Some(api_key.max_subscriptions())),
+						),
+						_ => (
+							snapshot.config.rate_limit_for(client_ip).map(RateLimit::per_minute),
+							service_builders.get(None),
+						),
+					};
+
+					let is_websocket = ws::is_upgrade_request(&req);
+					let sse = sse.filter(|_| sse::is_sse_request(&req));
+					let transport_label = match (is_websocket, sse.is_some()) {
+						(true, _) => "ws",
+						(false, true) => "sse",
+						(false, false) => "http",
+					};
+					// Only browsers send an origin, which is what CORS is about.
+					let method_cors = req
+						.headers()
+						.get(http::header::ORIGIN)
+						.and_then(|origin| origin.to_str().ok())
+						.filter(|_| !snapshot.config.method_cors.is_empty())
+						.map(|origin| method_cors::MethodCorsLayer::new(snapshot.clone(), origin));
+
+					let call_cost = call_cost
+						.clone()
+						.filter(|_| !snapshot.config.is_whitelisted(client_ip))
+						// The budget is shared by the connections of the address.
+						.map(|budgets| CallCostLayer::new(budgets, client_ip))
+						.map(|layer| match metrics.clone() {
+							// Each HTTP request is served by its own service.
+							Some(metrics) if !is_websocket => layer
+								.with_metrics(Metrics::new(metrics, transport_label))
+								.measure_batches(),
+							Some(metrics) =>
+								layer.with_metrics(Metrics::new(metrics, transport_label)),
+							None => layer,
+						});
+					// The requests with a key are accounted to it, whatever their address.
+					let quota = quotas
+						.clone()
+						.filter(|_| !snapshot.config.is_whitelisted(client_ip))
+						.map(|quotas| {
+							let consumer = match (&api_key, req.headers().get(API_KEY_HEADER)) {
+								(Ok(Some(_)), Some(key)) => QuotaConsumer::ApiKey(
+									String::from_utf8_lossy(key.as_bytes()).into_owned(),
+								),
+								_ => QuotaConsumer::Ip(client_ip),
+							};
+							let layer = QuotaLayer::new(quotas, consumer);
+							match metrics.clone() {
+								Some(metrics) =>
+									layer.with_metrics(Metrics::new(metrics, transport_label)),
+								None => layer,
+							}
+						});
+					// Cached responses are served without waiting, the timeout only applies to
+					// the calls reaching the methods.
+					let timeout = timeout.clone().map(|config| {
+						TimeoutLayer::new(
+							config,
+							metrics.clone().map(|metrics| Metrics::new(metrics, transport_label)),
+						)
+					});
+					let circuit_breaker = circuit_breaker.clone().map(|breaker| {
+						let layer = CircuitBreakerLayer::new(breaker);
+						match metrics.clone() {
+							Some(metrics) =>
+								layer.with_metrics(Metrics::new(metrics, transport_label)),
+							None => layer,
+						}
+					});
+					let response_limit = ResponseLimitLayer::new(
+						message_limits.response,
+						metrics.clone().map(|metrics| Metrics::new(metrics, transport_label)),
+					);
+					let slow_call = slow_call_threshold.map(|threshold| {
+						SlowCallLayer::new(
+							threshold,
+							client_ip,
+							metrics.clone().map(|metrics| Metrics::new(metrics, transport_label)),
+						)
+					});
+					let deprecation = (!deprecated_methods.is_empty()).then(|| {
+						DeprecationLayer::new(
+							deprecated_methods.clone(),
+							metrics.clone().map(|metrics| Metrics::new(metrics, transport_label)),
+						)
+					});
+					// The SSE streams and WebSocket connections have no response to carry them.
+					let deprecation_warnings = deprecation
+						.as_ref()
+						.filter(|_| !is_websocket && sse.is_none())
+						.map(DeprecationLayer::warnings);
+					let middleware_layer = match (metrics, rate_limit) {
+						(None, None) => None,
+						(Some(metrics), None) => Some(
+							MiddlewareLayer::new()
+								.with_metrics(Metrics::new(metrics, transport_label)),
+						),
+						(None, Some(rate_limit)) =>
+							Some(MiddlewareLayer::new().with_rate_limit(rate_limit)),
+						(Some(metrics), Some(rate_limit)) => Some(
+							MiddlewareLayer::new()
+								.with_metrics(Metrics::new(metrics, transport_label))
+								.with_rate_limit(rate_limit),
+						),
+					};
+
+					// The calls of an HTTP request share the trace id sent by the client, if any.
+					let access_log = access_log.then(|| {
+						let layer = AccessLogLayer::new(
+							registration.connection().id(),
+							client_ip,
+							transport_label,
+						);
+						match middleware::trace_id(req.headers()).filter(|_| !is_websocket) {
+							Some(trace_id) => layer.with_trace_id(trace_id),
+							None => layer,
+						}
+					});
+
+					let extensions =
+						middleware::ExtensionsLayer::new(middleware_extensions.clone(), || {
+							MiddlewareContext {
+								connection_id: registration.connection().id(),
+								client_ip,
+								transport: transport_label,
+								headers: req.headers().clone(),
+							}
 						});
-					}
-
-					svc.call(req).await
-				}
-			}))
-		}
-	});
-
-	let server = hyper::Server::from_tcp(std_listener)?.serve(make_service);
 
-	tokio::spawn(async move {
-		let graceful = server.with_graceful_shutdown(async move { stop_handle.shutdown().await });
-		let _ = graceful.await;
-	});
+					let call_span = call_spans.then(|| {
+						CallSpanLayer::new(registration.connection().id(), transport_label)
+					});
+
+					// Built with `tower` rather than `RpcServiceBuilder`, which can't be applied to
+					// the service of the SSE streams.
+					let rpc_middleware = tower::ServiceBuilder::new()
+						.layer(option_layer(call_span))
+						.layer(option_layer(access_log))
+						.layer(option_layer(slow_call))
+						.layer(option_layer(deprecation))
+						.layer(middleware::BoxLayer)
+						.layer(connections::CountCallsLayer(registration.connection().clone()))
+						.layer(option_layer(method_cors))
+						.layer(option_layer(load_shed))
+						.layer(option_layer(
+							audit_log.map(|log| AuditLayer::new(log, client_ip, transport_label)),
+						))
+						.layer(option_layer(middleware_layer.clone()))
+						.layer(extensions)
+						.layer(option_layer(call_cost))
+						.layer(option_layer(quota))
+						.layer(response_limit)
+						.layer(option_layer(response_cache.map(ResponseCacheLayer::new)))
+						.layer(option_layer(circuit_breaker))
+						.layer(option_layer(timeout));
+					// The calls to the SSE endpoint go through the same middleware, around their
+					// own service.
+					let sse_service = sse.map(|config| {
+						let methods = methods.clone();
+						let rpc_middleware = rpc_middleware.clone();
+						let conn_id = registration.connection().id() as usize;
+						http_middleware.clone().service(tower::service_fn(move |req| {
+							let (sink, rx) = sse::channel(config, message_limits);
+							let service = sse::SseService::new(
+								methods.clone(),
+								sink,
+								id_provider.clone(),
+								conn_id,
+							);
+							let service: Box<dyn sse::RpcCall> =
+								Box::new(rpc_middleware.service(service));
+							sse::call(req, service, rx, message_limits)
+						}))
+					});
+
+					// WebSocket sessions get their own stop handle such that they can be closed
+					// individually.
+					let (session_stop, session) = if is_websocket {
+						let (session_stop, session) = stop_channel();
+						(session_stop, Some(session))
+					} else {
+						(stop_handle.clone(), None)
+					};
+
+					let mut svc = service_builder
+						.set_http_middleware(http_middleware.clone())
+						.set_rpc_middleware(RpcServiceBuilder::new().layer(rpc_middleware))
+						.build(methods, session_stop);
+
+					async move {
+						if registration.connection().is_closed() {
+							return Err("Connection closed".into())
+						}
+
+						if let Err(e) = api_key {
+							log::debug!(target: "rpc", "Rejected request from {client_ip}: {e}");
+							return Ok(api_keys::unauthorized())
+						}
+
+						if let Some(session) = session {
+							let on_disconnect = svc.on_session_closed();
+
+							// Spawn a task to handle when the connection is closed.
+							tokio_handle.spawn(async move {
+								// The peer's connection is accounted until the session ends.
+								let _ip_connection = ip_connection;
+								let now = std::time::Instant::now();
+								middleware_layer.as_ref().map(|m| m.ws_connect());
+								registration
+									.connection()
+									.serve_websocket(session, stop_handle, on_disconnect)
+									.await;
+								middleware_layer.as_ref().map(|m| m.ws_disconnect(now));
+							});
+						}
+
+						if let Some(sse_service) = sse_service {
+							return sse_service.oneshot(req).await
+						}
+
+						match ws_compression {
+							Some(config) if is_websocket =>
+								ws_compression::call(svc, req, config, message_limits).await,
+							_ => {
+								let mut response = svc.call(req).await?;
+								for warning in deprecation_warnings
+									.map_or_else(Vec::new, |warnings| warnings.take_header_values())
+								{
+									response.headers_mut().append(http::header::WARNING, warning);
+								}
+								Ok(response)
+							},
+						}
+					}
+				}))
+			}
+		});
+
+		let mut server =
+			hyper::Server::builder(tls::incoming(listener, tls_acceptor.clone(), &socket)?);
+		server = match http2 {
+			Some(config) => server.http2_max_concurrent_streams(config.max_concurrent_streams),
+			None => server.http1_only(true),
+		};
+		let server = server.serve(make_service);
+
+		tokio::spawn(async move {
+			let graceful =
+				server.with_graceful_shutdown(async move { stop_handle.shutdown().await });
+			let _ = graceful.await;
+		});
+
+		log::info!(
+			"Running JSON-RPC server: addr={}, allowed origins={}, max connections={}, max connections per ip={:?}, tls={}, http2={}, ws ping interval={:?}, ws inactive limit={:?}, ws max ping failures={}",
+			local_addr.map_or_else(|| "unknown".to_string(), |a| a.to_string()),
+			format_cors(cors.as_ref()),
+			max_connections,
+			max_connections_per_ip,
+			is_tls,
+			http2.is_some(),
+			ws_ping.interval,
+			ws_ping.inactive_limit,
+			ws_ping.max_failures,
+		);
+
+		server_listeners.push(Listener { local_addr, reload: server_reload });
+	}
 
-	log::info!(
-		"Running JSON-RPC server: addr={}, allowed origins={}",
-		local_addr.map_or_else(|| "unknown".to_string(), |a| a.to_string()),
-		format_cors(cors)
-	);
+	Ok(Server { handle: server_handle, listeners: server_listeners })
+}
 
-	Ok(server_handle)
+/// The layer if any, such that it can be stacked like [`RpcServiceBuilder::option_layer`].
+fn option_layer<L>(layer: Option<L>) -> RpcEither<L, Identity> {
+	match layer {
+		Some(layer) => RpcEither::Left(layer),
+		None => RpcEither::Right(Identity::new()),
+	}
 }
 
 fn hosts_filtering(enabled: bool, addr: Option<SocketAddr>) -> Option<HostFilterLayer> {
@@ -242,7 +926,31 @@ fn hosts_filtering(enabled: bool, addr: Option<SocketAddr>) -> Option<HostFilter
 	}
 }
 
-fn build_rpc_api<M: Send + Sync + 'static>(mut rpc_api: RpcModule<M>) -> RpcModule<M> {
+fn build_rpc_api<M: Send + Sync + 'static>(
+	mut rpc_api: RpcModule<M>,
+	connections: Option<&connections::Connections>,
+	method_filter: Option<&MethodFilter>,
+	method_aliases: &[MethodAlias],
+) -> RpcModule<()> {
+	if let Some(connections) = connections {
+		connections.register_methods(&mut rpc_api);
+	}
+
+	let mut rpc_api = match method_filter {
+		Some(filter) => {
+			let mut exposed = RpcModule::new(());
+			for name in rpc_api.method_names().filter(|name| filter.is_exposed(name)) {
+				let callback = rpc_api.method(name).expect("Name of a registered method; qed");
+				exposed
+					.verify_and_insert(name, callback.clone())
+					.expect("Methods of a module have unique names; qed");
+			}
+			exposed
+		},
+		None => rpc_api.remove_context(),
+	};
+	let aliases = method_alias::register(&mut rpc_api, method_aliases);
+
 	let mut available_methods = rpc_api.method_names().collect::<Vec<_>>();
 	// The "rpc_methods" is defined below and we want it to be part of the reported methods.
 	available_methods.push("rpc_methods");
@@ -250,9 +958,13 @@ fn build_rpc_api<M: Send + Sync + 'static>(mut rpc_api: RpcModule<M>) -> RpcModu
 
 	rpc_api
 		.register_method("rpc_methods", move |_, _| {
-			serde_json::json!({
+			let mut methods = serde_json::json!({
 				"methods": available_methods,
-			})
+			});
+			if !aliases.is_empty() {
+				methods["aliases"] = serde_json::json!(aliases);
+			}
+			methods
 		})
 		.expect("infallible all other methods have their own address space; qed");
 
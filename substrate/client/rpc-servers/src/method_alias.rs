@@ -0,0 +1,85 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Aliases of the RPC methods, e.g. for legacy clients calling them by an old name.
+
+use std::{collections::BTreeMap, str::FromStr};
+
+use jsonrpsee::RpcModule;
+
+/// Alias of an RPC method.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MethodAlias {
+	/// Name the method is called by.
+	pub alias: String,
+	/// Name of the method, as registered in the RPC API.
+	pub method: String,
+}
+
+impl FromStr for MethodAlias {
+	type Err = String;
+
+	/// Parse an alias formatted as `<ALIAS>=<METHOD>`.
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let (alias, method) = s
+			.split_once('=')
+			.ok_or_else(|| format!("Expected `<ALIAS>=<METHOD>`, got `{s}`"))?;
+		let (alias, method) = (alias.trim(), method.trim());
+
+		for name in [alias, method] {
+			if name.is_empty() {
+				return Err(format!("Empty method name in alias `{s}`"))
+			}
+			if let Some(c) = name.chars().find(|c| !c.is_ascii_alphanumeric() && *c != '_') {
+				return Err(format!("Invalid character `{c}` in method name `{name}`"))
+			}
+		}
+
+		Ok(Self { alias: alias.into(), method: method.into() })
+	}
+}
+
+/// Register the `aliases` of the methods of `rpc_api`, returning the registered ones.
+///
+/// The aliases of the methods not exposed by `rpc_api`, or clashing with the name of another
+/// method, are skipped.
+pub(crate) fn register(
+	rpc_api: &mut RpcModule<()>,
+	aliases: &[MethodAlias],
+) -> BTreeMap<&'static str, &'static str> {
+	let mut registered = BTreeMap::new();
+
+	for MethodAlias { alias, method } in aliases {
+		let Some((method, _)) = rpc_api.method_with_name(method) else {
+			log::warn!(target: "rpc", "Skipping alias `{alias}` of unknown method `{method}`");
+			continue
+		};
+
+		// The names of the methods must be static, the aliases are only registered when starting
+		// the server.
+		let alias: &'static str = Box::leak(alias.clone().into_boxed_str());
+		match rpc_api.register_alias(alias, method) {
+			Ok(()) => {
+				registered.insert(alias, method);
+			},
+			Err(e) => log::warn!(target: "rpc", "Skipping alias `{alias}` of `{method}`: {e}"),
+		}
+	}
+
+	registered
+}
@@ -0,0 +1,158 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! CORS rules applying to a subset of the RPC methods.
+//!
+//! Browsers only get to see responses for origins allowed by the CORS headers of the server,
+//! which are set per request and can't depend on the methods called. The server therefore
+//! allows the origins of all rules and the calls are checked against the origins allowed for
+//! the called method.
+
+use std::{str::FromStr, sync::Arc};
+
+use futures::future::{BoxFuture, FutureExt};
+use jsonrpsee::{
+	server::middleware::rpc::RpcServiceT,
+	types::{ErrorObject, Request},
+	MethodResponse,
+};
+
+use crate::{reload::Snapshot, MethodPattern};
+
+/// Origins allowed to call the methods matching a pattern.
+///
+/// Overrides the allowed origins of the server for these methods, the first matching rule
+/// applies.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MethodCors {
+	/// Methods the rule applies to.
+	pub methods: MethodPattern,
+	/// Allowed origins, `*` allows any origin.
+	pub origins: Vec<String>,
+}
+
+impl FromStr for MethodCors {
+	type Err = String;
+
+	/// Parse a rule formatted as `<PATTERN>=<ORIGIN>[,<ORIGIN>...]`.
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let (methods, origins) = s
+			.split_once('=')
+			.ok_or_else(|| format!("Expected `<PATTERN>=<ORIGIN>[,<ORIGIN>...]`, got `{s}`"))?;
+		let origins = origins
+			.split(',')
+			.map(|origin| origin.trim().to_string())
+			.filter(|origin| !origin.is_empty())
+			.collect::<Vec<_>>();
+		if origins.is_empty() {
+			return Err(format!("No origins allowed for `{methods}`"))
+		}
+
+		Ok(Self { methods: methods.parse()?, origins })
+	}
+}
+
+/// The origins the server has to allow to serve the given rules, `None` to allow any origin.
+pub(crate) fn allowed_origins(
+	cors: Option<&Vec<String>>,
+	rules: &[MethodCors],
+) -> Option<Vec<String>> {
+	let mut origins = cors?.clone();
+	for origin in rules.iter().flat_map(|rule| &rule.origins) {
+		if origin == "*" {
+			return None
+		}
+		if !origins.contains(origin) {
+			origins.push(origin.clone());
+		}
+	}
+	Some(origins)
+}
+
+/// Whether the `origin` may call `method`.
+fn is_allowed(
+	cors: Option<&Vec<String>>,
+	rules: &[MethodCors],
+	origin: &str,
+	method: &str,
+) -> bool {
+	let origins = match rules.iter().find(|rule| rule.methods.matches(method)) {
+		Some(rule) => &rule.origins,
+		None => match cors {
+			Some(origins) => origins,
+			None => return true,
+		},
+	};
+
+	origins.iter().any(|allowed| allowed == "*" || allowed == origin)
+}
+
+/// Layer checking the calls of a request against the [`MethodCors`] rules.
+#[derive(Debug, Clone)]
+pub(crate) struct MethodCorsLayer {
+	snapshot: Arc<Snapshot>,
+	origin: Arc<str>,
+}
+
+impl MethodCorsLayer {
+	/// Create a layer for a request from the given origin, checked against the rules of the
+	/// snapshot.
+	pub(crate) fn new(snapshot: Arc<Snapshot>, origin: &str) -> Self {
+		Self { snapshot, origin: origin.into() }
+	}
+}
+
+impl<S> tower::Layer<S> for MethodCorsLayer {
+	type Service = MethodCorsCheck<S>;
+
+	fn layer(&self, service: S) -> Self::Service {
+		MethodCorsCheck { service, layer: self.clone() }
+	}
+}
+
+/// JSON-RPC middleware rejecting calls to methods the origin of the request isn't allowed to
+/// call.
+#[derive(Debug, Clone)]
+pub(crate) struct MethodCorsCheck<S> {
+	service: S,
+	layer: MethodCorsLayer,
+}
+
+impl<'a, S> RpcServiceT<'a> for MethodCorsCheck<S>
+where
+	S: RpcServiceT<'a>,
+	S::Future: Send + 'a,
+{
+	type Future = BoxFuture<'a, MethodResponse>;
+
+	fn call(&self, req: Request<'a>) -> Self::Future {
+		let MethodCorsLayer { snapshot, origin } = &self.layer;
+		let config = &snapshot.config;
+		if is_allowed(config.cors.as_ref(), &config.method_cors, origin, req.method_name()) {
+			return self.service.call(req).boxed()
+		}
+
+		log::debug!(
+			target: "rpc",
+			"Rejected call to {} from origin {origin} by the method CORS rules",
+			req.method_name(),
+		);
+		let error = ErrorObject::owned(-32998, "Origin not allowed to call the method", None::<()>);
+		futures::future::ready(MethodResponse::error(req.id, error)).boxed()
+	}
+}
@@ -0,0 +1,102 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Filtering of the RPC methods exposed by a server.
+
+use std::{fmt, str::FromStr};
+
+/// Pattern matching RPC method names.
+///
+/// `*` matches any sequence of characters, e.g. `chainHead_*` matches all versions of the
+/// `chainHead` methods. A pattern without `_` and `*` is a namespace and matches all the methods
+/// in it, e.g. `system` matches `system_health`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MethodPattern(String);
+
+impl MethodPattern {
+	/// Whether the given method name matches the pattern.
+	pub fn matches(&self, method: &str) -> bool {
+		let pattern = self.0.as_str();
+
+		if !pattern.contains(['_', '*']) {
+			return method.strip_prefix(pattern).map_or(false, |rest| rest.starts_with('_'))
+		}
+
+		let mut parts = pattern.split('*');
+		let Some(mut rest) = method.strip_prefix(parts.next().unwrap_or_default()) else {
+			return false
+		};
+		let mut parts = parts.collect::<Vec<_>>();
+		let Some(last) = parts.pop() else { return rest.is_empty() };
+
+		for part in parts {
+			match rest.find(part) {
+				Some(index) => rest = &rest[index + part.len()..],
+				None => return false,
+			}
+		}
+
+		rest.ends_with(last)
+	}
+}
+
+impl FromStr for MethodPattern {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		if s.is_empty() {
+			return Err("Empty method pattern".into())
+		}
+
+		if let Some(c) = s.chars().find(|c| !c.is_ascii_alphanumeric() && !matches!(c, '_' | '*')) {
+			return Err(format!("Invalid character `{c}` in method pattern `{s}`"))
+		}
+
+		Ok(Self(s.into()))
+	}
+}
+
+impl fmt::Display for MethodPattern {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str(&self.0)
+	}
+}
+
+/// Allowlist and denylist of the RPC methods exposed by a server.
+///
+/// `rpc_methods` is always exposed and only lists the exposed methods.
+#[derive(Debug, Clone, Default)]
+pub struct MethodFilter {
+	/// Methods to expose.
+	///
+	/// If empty, every method that is not denied is exposed.
+	pub allow: Vec<MethodPattern>,
+	/// Methods not to expose, takes precedence over `allow`.
+	pub deny: Vec<MethodPattern>,
+}
+
+impl MethodFilter {
+	/// Whether the method with the given name is exposed.
+	pub fn is_exposed(&self, method: &str) -> bool {
+		if self.deny.iter().any(|pattern| pattern.matches(method)) {
+			return false
+		}
+
+		self.allow.is_empty() || self.allow.iter().any(|pattern| pattern.matches(method))
+	}
+}
@@ -0,0 +1,146 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! RPC middleware writing an access log record of every call.
+//!
+//! The records are JSON objects logged to the [`ACCESS_LOG_TARGET`] target. Every call gets a
+//! trace id, which is taken from the W3C `traceparent` header of the HTTP request if any, and is
+//! served within a `rpc_call` tracing span carrying it. The node logs emitted while serving the
+//! call can then be correlated with its record, unless they are emitted by another task, e.g. for
+//! the methods running on a blocking thread.
+
+use std::{
+	collections::hash_map::RandomState,
+	hash::{BuildHasher, Hasher},
+	net::IpAddr,
+	sync::{
+		atomic::{AtomicU64, Ordering},
+		OnceLock,
+	},
+	time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+use futures::future::{BoxFuture, FutureExt};
+use http::HeaderMap;
+use jsonrpsee::{server::middleware::rpc::RpcServiceT, types::Request, MethodResponse};
+use tracing::Instrument;
+
+/// Log target of the access log records.
+pub const ACCESS_LOG_TARGET: &str = "rpc::access";
+
+/// Header carrying the trace context of a request, see <https://www.w3.org/TR/trace-context/>.
+const TRACEPARENT_HEADER: &str = "traceparent";
+
+/// A new trace id, unique across the calls served by the node.
+fn new_trace_id() -> String {
+	static SEED: OnceLock<u64> = OnceLock::new();
+	static NEXT: AtomicU64 = AtomicU64::new(0);
+
+	let seed = SEED.get_or_init(|| RandomState::new().build_hasher().finish());
+	format!("{seed:016x}{:016x}", NEXT.fetch_add(1, Ordering::Relaxed))
+}
+
+/// The trace id of the `traceparent` header, if valid.
+pub(crate) fn trace_id(headers: &HeaderMap) -> Option<String> {
+	let traceparent = headers.get(TRACEPARENT_HEADER)?.to_str().ok()?;
+	let mut fields = traceparent.split('-');
+	let (_version, trace_id) = (fields.next()?, fields.next()?);
+
+	let is_valid = trace_id.len() == 32 &&
+		trace_id.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f')) &&
+		trace_id.bytes().any(|b| b != b'0');
+	is_valid.then(|| trace_id.to_owned())
+}
+
+/// Layer writing an access log record of the calls of a connection.
+#[derive(Debug, Clone)]
+pub struct AccessLogLayer {
+	connection_id: u64,
+	client_ip: IpAddr,
+	transport_label: &'static str,
+	trace_id: Option<String>,
+}
+
+impl AccessLogLayer {
+	/// Create a layer logging the calls of the connection with the given id.
+	pub fn new(connection_id: u64, client_ip: IpAddr, transport_label: &'static str) -> Self {
+		Self { connection_id, client_ip, transport_label, trace_id: None }
+	}
+
+	/// Use the given trace id for the calls, instead of a new one for each of them.
+	pub fn with_trace_id(mut self, trace_id: String) -> Self {
+		self.trace_id = Some(trace_id);
+		self
+	}
+}
+
+impl<S> tower::Layer<S> for AccessLogLayer {
+	type Service = AccessLog<S>;
+
+	fn layer(&self, service: S) -> Self::Service {
+		AccessLog { service, layer: self.clone() }
+	}
+}
+
+/// JSON-RPC middleware writing an access log record of every call.
+pub struct AccessLog<S> {
+	service: S,
+	layer: AccessLogLayer,
+}
+
+impl<'a, S> RpcServiceT<'a> for AccessLog<S>
+where
+	S: RpcServiceT<'a>,
+	S::Future: Send + 'a,
+{
+	type Future = BoxFuture<'a, MethodResponse>;
+
+	fn call(&self, req: Request<'a>) -> Self::Future {
+		let now = Instant::now();
+		let trace_id = self.layer.trace_id.clone().unwrap_or_else(new_trace_id);
+		let method = req.method_name().to_owned();
+		let span = tracing::info_span!(
+			target: "rpc",
+			"rpc_call",
+			trace_id = %trace_id,
+			conn_id = self.layer.connection_id,
+			method = %method,
+		);
+		let layer = self.layer.clone();
+		let fut = self.service.call(req).instrument(span);
+
+		async move {
+			let rp = fut.await;
+			let timestamp =
+				SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_millis() as u64);
+			let record = serde_json::json!({
+				"timestamp": timestamp,
+				"trace_id": trace_id,
+				"conn_id": layer.connection_id,
+				"ip": layer.client_ip,
+				"transport": layer.transport_label,
+				"method": method,
+				"duration_us": now.elapsed().as_micros() as u64,
+				"code": rp.as_error_code().unwrap_or(0),
+			});
+			log::info!(target: ACCESS_LOG_TARGET, "{record}");
+			rp
+		}
+		.boxed()
+	}
+}
@@ -0,0 +1,245 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! RPC middleware writing an audit log of a sample of the calls.
+//!
+//! Every sampled call is written as a JSON line to a dedicated file, which is rotated once it
+//! exceeds the configured size.
+
+use std::{
+	fs::{self, File, OpenOptions},
+	io::{self, BufWriter, Write},
+	net::IpAddr,
+	path::{Path, PathBuf},
+	sync::{
+		atomic::{AtomicU64, Ordering},
+		mpsc, Arc,
+	},
+	time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+use futures::future::{BoxFuture, FutureExt};
+use jsonrpsee::{server::middleware::rpc::RpcServiceT, types::Request, MethodResponse};
+
+/// Number of log lines buffered before lines are dropped.
+const BUFFER_LINES: usize = 4096;
+
+/// Audit log configuration.
+#[derive(Debug, Clone)]
+pub struct AuditLogConfig {
+	/// Path of the log file, rotated files get a `.1`, `.2`, ... suffix.
+	pub path: PathBuf,
+	/// Fraction of the calls to log, between `0.0` and `1.0`.
+	pub sample_rate: f64,
+	/// Size in bytes after which the log file is rotated.
+	pub max_file_size: u64,
+	/// Number of rotated files to keep.
+	pub max_files: u32,
+}
+
+/// Handle to the audit log, cheap to clone.
+///
+/// Lines are written by a dedicated thread which stops once all handles are dropped.
+#[derive(Debug, Clone)]
+pub struct AuditLog {
+	sampler: Arc<Sampler>,
+	lines: mpsc::SyncSender<String>,
+}
+
+impl AuditLog {
+	/// Open the log file and start the thread writing to it.
+	pub fn new(config: AuditLogConfig) -> io::Result<Self> {
+		if !(0.0..=1.0).contains(&config.sample_rate) {
+			return Err(io::Error::new(
+				io::ErrorKind::InvalidInput,
+				format!("Audit log sample rate {} is not between 0 and 1", config.sample_rate),
+			))
+		}
+
+		let mut file = RotatingFile::open(config.path, config.max_file_size, config.max_files)?;
+		let (lines, rx) = mpsc::sync_channel::<String>(BUFFER_LINES);
+
+		std::thread::Builder::new().name("rpc-audit-log".into()).spawn(move || {
+			while let Ok(line) = rx.recv() {
+				// Flush only once the pending lines are written.
+				let result = std::iter::once(line)
+					.chain(rx.try_iter())
+					.try_for_each(|line| file.write_line(&line))
+					.and_then(|()| file.flush());
+				if let Err(e) = result {
+					log::warn!("Failed to write the JSON-RPC audit log: {e}");
+				}
+			}
+		})?;
+
+		Ok(Self { sampler: Arc::new(Sampler::new(config.sample_rate)), lines })
+	}
+
+	fn write(&self, line: String) {
+		if self.lines.try_send(line).is_err() {
+			log::debug!(target: "rpc", "JSON-RPC audit log is lagging behind, dropped a line");
+		}
+	}
+}
+
+/// Picks a fraction of the calls, spread evenly.
+#[derive(Debug)]
+struct Sampler {
+	rate: f64,
+	calls: AtomicU64,
+}
+
+impl Sampler {
+	fn new(rate: f64) -> Self {
+		Self { rate, calls: AtomicU64::new(0) }
+	}
+
+	fn sample(&self) -> bool {
+		if self.rate >= 1.0 {
+			return true
+		}
+
+		let n = self.calls.fetch_add(1, Ordering::Relaxed) as f64;
+		((n + 1.0) * self.rate).floor() > (n * self.rate).floor()
+	}
+}
+
+/// Log file rotated once it exceeds a size.
+struct RotatingFile {
+	path: PathBuf,
+	file: BufWriter<File>,
+	size: u64,
+	max_size: u64,
+	max_files: u32,
+}
+
+impl RotatingFile {
+	fn open(path: PathBuf, max_size: u64, max_files: u32) -> io::Result<Self> {
+		let file = OpenOptions::new().create(true).append(true).open(&path)?;
+		let size = file.metadata()?.len();
+		Ok(Self { path, file: BufWriter::new(file), size, max_size, max_files })
+	}
+
+	fn write_line(&mut self, line: &str) -> io::Result<()> {
+		if self.size > 0 && self.size + line.len() as u64 + 1 > self.max_size {
+			self.rotate()?;
+		}
+
+		self.file.write_all(line.as_bytes())?;
+		self.file.write_all(b"\n")?;
+		self.size += line.len() as u64 + 1;
+		Ok(())
+	}
+
+	fn flush(&mut self) -> io::Result<()> {
+		self.file.flush()
+	}
+
+	fn rotate(&mut self) -> io::Result<()> {
+		self.file.flush()?;
+
+		if self.max_files > 0 {
+			for index in (1..self.max_files).rev() {
+				let from = rotated_path(&self.path, index);
+				if from.exists() {
+					fs::rename(from, rotated_path(&self.path, index + 1))?;
+				}
+			}
+			fs::rename(&self.path, rotated_path(&self.path, 1))?;
+		}
+
+		let file = OpenOptions::new().create(true).write(true).truncate(true).open(&self.path)?;
+		self.file = BufWriter::new(file);
+		self.size = 0;
+		Ok(())
+	}
+}
+
+fn rotated_path(path: &Path, index: u32) -> PathBuf {
+	let mut name = path.as_os_str().to_owned();
+	name.push(format!(".{index}"));
+	name.into()
+}
+
+/// Layer auditing the calls of a connection.
+#[derive(Debug, Clone)]
+pub struct AuditLayer {
+	log: AuditLog,
+	remote_ip: IpAddr,
+	transport_label: &'static str,
+}
+
+impl AuditLayer {
+	/// Create a layer auditing calls of a peer with the given address.
+	pub fn new(log: AuditLog, remote_ip: IpAddr, transport_label: &'static str) -> Self {
+		Self { log, remote_ip, transport_label }
+	}
+}
+
+impl<S> tower::Layer<S> for AuditLayer {
+	type Service = Audit<S>;
+
+	fn layer(&self, service: S) -> Self::Service {
+		Audit { service, layer: self.clone() }
+	}
+}
+
+/// JSON-RPC middleware writing a sample of the calls to the audit log.
+pub struct Audit<S> {
+	service: S,
+	layer: AuditLayer,
+}
+
+impl<'a, S> RpcServiceT<'a> for Audit<S>
+where
+	S: RpcServiceT<'a>,
+	S::Future: Send + 'a,
+{
+	type Future = BoxFuture<'a, MethodResponse>;
+
+	fn call(&self, req: Request<'a>) -> Self::Future {
+		if !self.layer.log.sampler.sample() {
+			return self.service.call(req).boxed()
+		}
+
+		let now = Instant::now();
+		let method = req.method_name().to_owned();
+		let params_size = req.params.as_ref().map_or(0, |params| params.get().len());
+		let layer = self.layer.clone();
+		let fut = self.service.call(req);
+
+		async move {
+			let rp = fut.await;
+			let timestamp =
+				SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_millis() as u64);
+			let line = serde_json::json!({
+				"timestamp": timestamp,
+				"ip": layer.remote_ip,
+				"transport": layer.transport_label,
+				"method": method,
+				"params_size": params_size,
+				"response_size": rp.as_result().len(),
+				"latency_us": now.elapsed().as_micros() as u64,
+				"success": rp.is_success(),
+			});
+			layer.log.write(line.to_string());
+			rp
+		}
+		.boxed()
+	}
+}
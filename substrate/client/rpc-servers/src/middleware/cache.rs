@@ -0,0 +1,211 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! RPC middleware caching the responses of idempotent methods.
+
+use std::{
+	collections::{HashMap, HashSet},
+	sync::{Arc, Mutex},
+	time::{Duration, Instant},
+};
+
+use futures::future::{BoxFuture, FutureExt};
+use jsonrpsee::{
+	server::middleware::rpc::RpcServiceT, types::Request, MethodResponse, ResponsePayload,
+};
+use serde::Deserialize;
+use serde_json::value::RawValue;
+
+/// Methods cached by default.
+pub const DEFAULT_CACHED_METHODS: &[&str] = &[
+	"chain_getBlockHash",
+	"state_getMetadata",
+	"state_getRuntimeVersion",
+	"system_chain",
+	"system_chainType",
+	"system_name",
+	"system_properties",
+	"system_version",
+];
+
+/// Response cache configuration.
+#[derive(Debug, Clone)]
+pub struct ResponseCacheConfig {
+	/// Methods whose responses are cached, they must not have side effects.
+	pub methods: Vec<String>,
+	/// Time after which a cached response expires.
+	pub ttl: Duration,
+	/// Maximum number of cached responses, the oldest ones are evicted first.
+	pub max_entries: usize,
+}
+
+/// Cache of the responses of idempotent methods, keyed by method name and params.
+///
+/// Responses may depend on the best or the finalized block, e.g. `chain_getBlockHash` without
+/// params, so the cache must be [cleared](Self::clear) whenever either of them changes.
+/// Cheap to clone, clones share the cached responses.
+#[derive(Debug, Clone)]
+pub struct ResponseCache {
+	methods: Arc<HashSet<String>>,
+	ttl: Duration,
+	max_entries: usize,
+	entries: Arc<Mutex<Entries>>,
+}
+
+type Key = (String, String);
+
+#[derive(Debug, Default)]
+struct Entries {
+	responses: HashMap<Key, Entry>,
+	/// Incremented on every clear, such that responses computed before aren't cached.
+	generation: u64,
+}
+
+#[derive(Debug)]
+struct Entry {
+	inserted: Instant,
+	result: Box<RawValue>,
+}
+
+impl ResponseCache {
+	/// Create an empty cache.
+	pub fn new(config: ResponseCacheConfig) -> Self {
+		Self {
+			methods: Arc::new(config.methods.into_iter().collect()),
+			ttl: config.ttl,
+			max_entries: config.max_entries,
+			entries: Default::default(),
+		}
+	}
+
+	/// Drop all the cached responses.
+	pub fn clear(&self) {
+		let mut entries = self.entries.lock().expect("Lock poisoned; qed");
+		entries.responses.clear();
+		entries.generation += 1;
+	}
+
+	fn generation(&self) -> u64 {
+		self.entries.lock().expect("Lock poisoned; qed").generation
+	}
+
+	fn get(&self, key: &Key) -> Option<Box<RawValue>> {
+		let entries = self.entries.lock().expect("Lock poisoned; qed");
+		entries
+			.responses
+			.get(key)
+			.filter(|entry| entry.inserted.elapsed() < self.ttl)
+			.map(|entry| entry.result.clone())
+	}
+
+	fn insert(&self, key: Key, result: Box<RawValue>, generation: u64) {
+		let mut entries = self.entries.lock().expect("Lock poisoned; qed");
+		if entries.generation != generation || self.max_entries == 0 {
+			return
+		}
+
+		let now = Instant::now();
+		if entries.responses.len() >= self.max_entries && !entries.responses.contains_key(&key) {
+			entries
+				.responses
+				.retain(|_, entry| now.duration_since(entry.inserted) < self.ttl);
+		}
+		if entries.responses.len() >= self.max_entries && !entries.responses.contains_key(&key) {
+			let oldest = entries
+				.responses
+				.iter()
+				.min_by_key(|(_, entry)| entry.inserted)
+				.map(|(key, _)| key.clone());
+			if let Some(oldest) = oldest {
+				entries.responses.remove(&oldest);
+			}
+		}
+
+		entries.responses.insert(key, Entry { inserted: now, result });
+	}
+}
+
+/// Layer answering calls from the [`ResponseCache`].
+#[derive(Debug, Clone)]
+pub struct ResponseCacheLayer(ResponseCache);
+
+impl ResponseCacheLayer {
+	/// Create a layer using the given cache.
+	pub fn new(cache: ResponseCache) -> Self {
+		Self(cache)
+	}
+}
+
+impl<S> tower::Layer<S> for ResponseCacheLayer {
+	type Service = CachedResponses<S>;
+
+	fn layer(&self, service: S) -> Self::Service {
+		CachedResponses { service, cache: self.0.clone() }
+	}
+}
+
+/// JSON-RPC middleware answering calls of the cached methods from the [`ResponseCache`].
+#[derive(Debug, Clone)]
+pub struct CachedResponses<S> {
+	service: S,
+	cache: ResponseCache,
+}
+
+#[derive(Deserialize)]
+struct Success {
+	result: Box<RawValue>,
+}
+
+impl<'a, S> RpcServiceT<'a> for CachedResponses<S>
+where
+	S: RpcServiceT<'a>,
+	S::Future: Send + 'a,
+{
+	type Future = BoxFuture<'a, MethodResponse>;
+
+	fn call(&self, req: Request<'a>) -> Self::Future {
+		if !self.cache.methods.contains(req.method_name()) {
+			return self.service.call(req).boxed()
+		}
+
+		let key = (
+			req.method_name().to_owned(),
+			req.params.as_ref().map_or_else(String::new, |params| params.get().to_owned()),
+		);
+		if let Some(result) = self.cache.get(&key) {
+			// The response was within the limit when it was cached.
+			let rp = MethodResponse::response(req.id, ResponsePayload::success(result), usize::MAX);
+			return futures::future::ready(rp).boxed()
+		}
+
+		let cache = self.cache.clone();
+		let generation = cache.generation();
+		let fut = self.service.call(req);
+
+		async move {
+			let rp = fut.await;
+			if rp.is_success() && !rp.is_subscription() {
+				if let Ok(Success { result }) = serde_json::from_str(rp.as_result()) {
+					cache.insert(key, result, generation);
+				}
+			}
+			rp
+		}
+		.boxed()
+	}
+}
@@ -0,0 +1,249 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! RPC middleware limiting the cost of the calls of an IP address.
+//!
+//! Each method has a cost, the calls of an address are paid from a budget which is refilled
+//! over time. Unlike the rate limit, which counts the calls, expensive calls such as
+//! `state_queryStorageAt` use up the budget faster, including when they are batched. The calls of
+//! a batch are processed in order, those exceeding the remaining budget are rejected.
+//!
+//! The budget is shared by the connections of the address, such that HTTP clients don't get a
+//! full budget by reconnecting.
+
+use std::{
+	net::IpAddr,
+	num::NonZeroU32,
+	str::FromStr,
+	sync::{
+		atomic::{AtomicU32, AtomicU64, Ordering},
+		Arc, Weak,
+	},
+	time::Duration,
+};
+
+use futures::future::{BoxFuture, FutureExt};
+use jsonrpsee::{
+	server::middleware::rpc::RpcServiceT,
+	types::{ErrorObject, Request},
+	MethodResponse,
+};
+
+use super::Metrics;
+use crate::MethodPattern;
+
+/// Error code of the calls rejected for exceeding the cost budget of their address.
+pub const COST_BUDGET_EXCEEDED_CODE: i32 = -32996;
+
+/// Cost of the calls of the methods matching a pattern.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MethodCost {
+	/// Methods the cost applies to.
+	pub methods: MethodPattern,
+	/// Cost of a call, `0` for free calls.
+	pub cost: u32,
+}
+
+impl FromStr for MethodCost {
+	type Err = String;
+
+	/// Parse a cost formatted as `<PATTERN>=<COST>`.
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let (methods, cost) = s
+			.split_once('=')
+			.ok_or_else(|| format!("Expected `<PATTERN>=<COST>`, got `{s}`"))?;
+		let cost = cost.trim().parse().map_err(|e| format!("Invalid cost `{cost}`: {e}"))?;
+
+		Ok(Self { methods: methods.parse()?, cost })
+	}
+}
+
+/// Call cost configuration.
+#[derive(Debug, Clone)]
+pub struct CallCostConfig {
+	/// Cost of the methods matching a pattern, the first matching one applies.
+	pub method_costs: Vec<MethodCost>,
+	/// Cost of the methods not matching any of `method_costs`.
+	pub default_cost: u32,
+	/// Cost the calls of an IP address may add up to per minute.
+	///
+	/// The budget is refilled continuously and can be spent at once, a batch costing more than
+	/// the budget is therefore never fully processed.
+	pub budget_per_minute: NonZeroU32,
+}
+
+impl CallCostConfig {
+	/// The cost of a call of the given method.
+	pub fn cost(&self, method: &str) -> u32 {
+		self.method_costs
+			.iter()
+			.find(|cost| cost.methods.matches(method))
+			.map_or(self.default_cost, |cost| cost.cost)
+	}
+}
+
+/// Budgets of the IP addresses, shared by the listeners of the server.
+#[derive(Debug)]
+pub struct CallCostBudgets {
+	config: CallCostConfig,
+	budgets: governor::DefaultKeyedRateLimiter<IpAddr>,
+}
+
+impl CallCostBudgets {
+	/// Create the budgets, full for every address.
+	///
+	/// A task dropping the budgets that are full again every minute is spawned.
+	pub fn new(config: CallCostConfig) -> Arc<Self> {
+		let budgets =
+			governor::RateLimiter::keyed(governor::Quota::per_minute(config.budget_per_minute));
+		let budgets = Arc::new(Self { config, budgets });
+		tokio::spawn(prune_budgets(Arc::downgrade(&budgets)));
+		budgets
+	}
+}
+
+/// Drop the full budgets every minute until the budgets are dropped.
+async fn prune_budgets(budgets: Weak<CallCostBudgets>) {
+	let mut interval = tokio::time::interval(Duration::from_secs(60));
+	interval.tick().await;
+
+	loop {
+		interval.tick().await;
+
+		let Some(budgets) = budgets.upgrade() else { return };
+		budgets.budgets.retain_recent();
+		budgets.budgets.shrink_to_fit();
+	}
+}
+
+/// Layer paying the calls of an IP address from its budget.
+#[derive(Debug, Clone)]
+pub struct CallCostLayer {
+	budgets: Arc<CallCostBudgets>,
+	ip: IpAddr,
+	metrics: Option<Metrics>,
+	measure_batches: bool,
+}
+
+impl CallCostLayer {
+	/// Create a layer paying the calls from the budget of the given address.
+	pub fn new(budgets: Arc<CallCostBudgets>, ip: IpAddr) -> Self {
+		Self { budgets, ip, metrics: None, measure_batches: false }
+	}
+
+	/// Report the rejected calls to the metrics.
+	pub fn with_metrics(self, metrics: Metrics) -> Self {
+		Self { metrics: Some(metrics), ..self }
+	}
+
+	/// Also report the total cost of the batches to the metrics.
+	///
+	/// Each service built by the layer must serve a single request, i.e. an HTTP request.
+	/// The requests with a single call aren't reported.
+	pub fn measure_batches(self) -> Self {
+		Self { measure_batches: true, ..self }
+	}
+}
+
+impl<S> tower::Layer<S> for CallCostLayer {
+	type Service = CallCost<S>;
+
+	fn layer(&self, service: S) -> Self::Service {
+		let batch = self.metrics.clone().filter(|_| self.measure_batches).map(|metrics| {
+			Arc::new(BatchCost { calls: AtomicU32::new(0), cost: AtomicU64::new(0), metrics })
+		});
+		CallCost {
+			service,
+			budgets: self.budgets.clone(),
+			ip: self.ip,
+			metrics: self.metrics.clone(),
+			batch,
+		}
+	}
+}
+
+/// Cost of the calls of a request, reported once the request is served.
+#[derive(Debug)]
+struct BatchCost {
+	calls: AtomicU32,
+	cost: AtomicU64,
+	metrics: Metrics,
+}
+
+impl BatchCost {
+	fn add(&self, cost: u32) {
+		self.calls.fetch_add(1, Ordering::Relaxed);
+		self.cost.fetch_add(cost.into(), Ordering::Relaxed);
+	}
+}
+
+impl Drop for BatchCost {
+	fn drop(&mut self) {
+		if *self.calls.get_mut() > 1 {
+			self.metrics.on_batch_cost(*self.cost.get_mut());
+		}
+	}
+}
+
+/// JSON-RPC middleware rejecting the calls exceeding the cost budget of their address.
+#[derive(Debug, Clone)]
+pub struct CallCost<S> {
+	service: S,
+	budgets: Arc<CallCostBudgets>,
+	ip: IpAddr,
+	metrics: Option<Metrics>,
+	batch: Option<Arc<BatchCost>>,
+}
+
+impl<'a, S> RpcServiceT<'a> for CallCost<S>
+where
+	S: RpcServiceT<'a>,
+	S::Future: Send + 'a,
+{
+	type Future = BoxFuture<'a, MethodResponse>;
+
+	fn call(&self, req: Request<'a>) -> Self::Future {
+		let config = &self.budgets.config;
+		let cost = config.cost(req.method_name());
+		if let Some(batch) = &self.batch {
+			batch.add(cost);
+		}
+
+		let Some(cost) = NonZeroU32::new(cost) else { return self.service.call(req).boxed() };
+		if matches!(self.budgets.budgets.check_key_n(&self.ip, cost), Ok(Ok(()))) {
+			return self.service.call(req).boxed()
+		}
+
+		log::debug!(
+			target: "rpc",
+			"Rejected call to {} of cost {cost} from {}, exceeding the budget of the address",
+			req.method_name(),
+			self.ip,
+		);
+		if let Some(metrics) = &self.metrics {
+			metrics.on_cost_budget_exceeded(req.method_name());
+		}
+
+		let error = ErrorObject::owned(
+			COST_BUDGET_EXCEEDED_CODE,
+			"RPC call cost budget exceeded",
+			Some(serde_json::json!({ "cost": cost, "budget": config.budget_per_minute })),
+		);
+		futures::future::ready(MethodResponse::error(req.id, error)).boxed()
+	}
+}
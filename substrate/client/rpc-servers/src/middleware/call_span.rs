@@ -0,0 +1,155 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! RPC middleware serving the calls within tracing spans.
+//!
+//! Every call is served within a `rpc_call` span carrying its method, connection id and outcome,
+//! such that it can be exported to a tracing backend along with the spans of the node it caused.
+//! Every subscription opened gets a long-lived `rpc_subscription` span, closed when it is
+//! unsubscribed or when its connection is closed. The subscriptions closed by the server are only
+//! seen as closed along with their connection.
+
+use std::{
+	collections::HashMap,
+	sync::{Arc, Mutex},
+};
+
+use futures::future::{BoxFuture, FutureExt};
+use jsonrpsee::{server::middleware::rpc::RpcServiceT, types::Request, MethodResponse};
+use tracing::{field, Instrument, Span};
+
+/// Maximum number of subscriptions of a connection with a span.
+const MAX_SUBSCRIPTION_SPANS: usize = 1024;
+
+/// Layer serving the calls of a connection within tracing spans.
+#[derive(Debug, Clone)]
+pub struct CallSpanLayer {
+	connection_id: u64,
+	transport_label: &'static str,
+	subscriptions: Arc<Mutex<HashMap<String, Span>>>,
+}
+
+impl CallSpanLayer {
+	/// Create a layer serving the calls of the connection with the given id within spans.
+	pub fn new(connection_id: u64, transport_label: &'static str) -> Self {
+		Self { connection_id, transport_label, subscriptions: Default::default() }
+	}
+}
+
+impl<S> tower::Layer<S> for CallSpanLayer {
+	type Service = CallSpan<S>;
+
+	fn layer(&self, service: S) -> Self::Service {
+		CallSpan { service, layer: self.clone() }
+	}
+}
+
+/// JSON-RPC middleware serving every call within a tracing span.
+#[derive(Debug, Clone)]
+pub struct CallSpan<S> {
+	service: S,
+	layer: CallSpanLayer,
+}
+
+impl<'a, S> RpcServiceT<'a> for CallSpan<S>
+where
+	S: RpcServiceT<'a>,
+	S::Future: Send + 'a,
+{
+	type Future = BoxFuture<'a, MethodResponse>;
+
+	fn call(&self, req: Request<'a>) -> Self::Future {
+		let method = req.method_name().to_owned();
+		let span = tracing::info_span!(
+			target: "rpc",
+			"rpc_call",
+			method = %method,
+			conn_id = self.layer.connection_id,
+			transport = self.layer.transport_label,
+			outcome = field::Empty,
+			code = field::Empty,
+		);
+		// The subscription closed by the call, if it is an unsubscription.
+		let unsubscribed = is_unsubscribe(&method).then(|| first_param(&req)).flatten();
+		let layer = self.layer.clone();
+		let fut = self.service.call(req).instrument(span.clone());
+
+		async move {
+			let rp = fut.await;
+			match rp.as_error_code() {
+				Some(code) => {
+					span.record("outcome", "error");
+					span.record("code", code);
+				},
+				None => {
+					span.record("outcome", "success");
+				},
+			}
+
+			let mut subscriptions = layer.subscriptions.lock().unwrap_or_else(|e| e.into_inner());
+			if rp.is_subscription() && rp.is_success() {
+				if let Some(id) = subscription_id(&rp) {
+					if subscriptions.len() < MAX_SUBSCRIPTION_SPANS {
+						let subscription_span = tracing::info_span!(
+							target: "rpc",
+							"rpc_subscription",
+							method = %method,
+							conn_id = layer.connection_id,
+							subscription = %id,
+						);
+						subscription_span.follows_from(&span);
+						subscriptions.insert(id, subscription_span);
+					}
+				}
+			} else if let Some(id) = unsubscribed.filter(|_| rp.is_success()) {
+				// Dropping the span closes it.
+				subscriptions.remove(&id);
+			}
+			drop(subscriptions);
+
+			rp
+		}
+		.boxed()
+	}
+}
+
+/// Whether the method closes the subscription passed as its first parameter, judged by its name.
+fn is_unsubscribe(method: &str) -> bool {
+	let name = method.rsplit('_').next().unwrap_or_default().to_ascii_lowercase();
+	name.starts_with("unsubscribe") ||
+		name.starts_with("unfollow") ||
+		name.starts_with("unwatch") ||
+		name == "stop"
+}
+
+/// The first parameter of a call, as JSON.
+fn first_param(req: &Request) -> Option<String> {
+	let params: serde_json::Value = serde_json::from_str(req.params().as_str()?).ok()?;
+	let first = match params {
+		serde_json::Value::Array(params) => params.into_iter().next()?,
+		serde_json::Value::Object(params) => params.into_iter().next()?.1,
+		_ => return None,
+	};
+	Some(first.to_string())
+}
+
+/// The id of the subscription opened by a call, as JSON.
+fn subscription_id(rp: &MethodResponse) -> Option<String> {
+	let response: serde_json::Value = serde_json::from_str(rp.as_result()).ok()?;
+	Some(response.get("result")?.to_string())
+}
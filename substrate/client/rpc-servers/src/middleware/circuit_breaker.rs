@@ -0,0 +1,235 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! RPC middleware short-circuiting the calls of the methods failing or slowing down.
+//!
+//! The calls of each method are measured over a window. When the share of failed calls or their
+//! mean duration exceeds its threshold, e.g. because the state backend is thrashing, the circuit
+//! of the method opens: its calls are rejected without being served until the cool-down period
+//! has elapsed. The calls are then served again, in a new window.
+//!
+//! Only the internal errors and the timeouts count as failures, not the errors caused by the
+//! request itself.
+
+use std::{
+	collections::HashMap,
+	io,
+	sync::{Arc, Mutex},
+	time::{Duration, Instant},
+};
+
+use futures::future::{BoxFuture, FutureExt};
+use jsonrpsee::{
+	server::middleware::rpc::RpcServiceT,
+	types::{
+		error::{ErrorCode, INTERNAL_ERROR_CODE},
+		ErrorObject, Request,
+	},
+	MethodResponse,
+};
+
+use super::{Metrics, ALWAYS_SERVED_METHODS, TIMEOUT_CODE};
+
+/// Error code of the calls rejected while the circuit of their method is open.
+pub const CIRCUIT_OPEN_CODE: i32 = -32993;
+
+/// Circuit breaker configuration.
+#[derive(Debug, Clone)]
+pub struct CircuitBreakerConfig {
+	/// Share of failed calls, between `0.0` and `1.0`, above which the circuit opens.
+	pub max_error_rate: Option<f64>,
+	/// Mean duration of the calls above which the circuit opens.
+	pub max_latency: Option<Duration>,
+	/// Length of the window the calls are measured over.
+	pub window: Duration,
+	/// Minimum number of calls in a window for the circuit to open.
+	pub min_calls: u32,
+	/// How long the calls are rejected once the circuit opened.
+	pub cool_down: Duration,
+}
+
+/// Calls of a method in the current window.
+#[derive(Debug)]
+struct MethodCircuit {
+	window_start: Instant,
+	calls: u32,
+	failures: u32,
+	total_duration: Duration,
+	open_until: Option<Instant>,
+}
+
+impl MethodCircuit {
+	fn new(now: Instant) -> Self {
+		Self {
+			window_start: now,
+			calls: 0,
+			failures: 0,
+			total_duration: Duration::ZERO,
+			open_until: None,
+		}
+	}
+}
+
+/// State of the circuits of all methods, shared by all connections.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+	config: CircuitBreakerConfig,
+	circuits: Mutex<HashMap<String, MethodCircuit>>,
+}
+
+impl CircuitBreaker {
+	/// Create a new [`CircuitBreaker`], with all circuits closed.
+	pub fn new(config: CircuitBreakerConfig) -> io::Result<Arc<Self>> {
+		if let Some(rate) = config.max_error_rate.filter(|rate| !(0.0..=1.0).contains(rate)) {
+			return Err(io::Error::new(
+				io::ErrorKind::InvalidInput,
+				format!("Circuit breaker error rate {rate} is not between 0 and 1"),
+			))
+		}
+
+		Ok(Arc::new(Self { config, circuits: Default::default() }))
+	}
+
+	/// Time left before the circuit of `method` closes, `None` if it's closed.
+	fn open_for(&self, method: &str, now: Instant) -> Option<Duration> {
+		let circuits = self.circuits.lock().expect("Lock poisoned; qed");
+		let open_until = circuits.get(method)?.open_until?;
+		(open_until > now).then(|| open_until - now)
+	}
+
+	/// Record a call of `method`, returns whether it opened the circuit.
+	fn on_response(&self, method: &str, failed: bool, duration: Duration, now: Instant) -> bool {
+		let mut circuits = self.circuits.lock().expect("Lock poisoned; qed");
+		let circuit = circuits.entry(method.to_owned()).or_insert_with(|| MethodCircuit::new(now));
+
+		if circuit.open_until.map_or(false, |open_until| open_until > now) {
+			// Call started before the circuit opened.
+			return false
+		}
+		if circuit.open_until.is_some() ||
+			now.saturating_duration_since(circuit.window_start) > self.config.window
+		{
+			*circuit = MethodCircuit::new(now);
+		}
+
+		circuit.calls += 1;
+		circuit.failures += failed as u32;
+		circuit.total_duration += duration;
+
+		if circuit.calls < self.config.min_calls {
+			return false
+		}
+
+		let too_many_failures = self
+			.config
+			.max_error_rate
+			.map_or(false, |max| circuit.failures as f64 / circuit.calls as f64 > max);
+		let too_slow = self
+			.config
+			.max_latency
+			.map_or(false, |max| circuit.total_duration / circuit.calls > max);
+		if too_many_failures || too_slow {
+			circuit.open_until = Some(now + self.config.cool_down);
+		}
+		circuit.open_until.is_some()
+	}
+}
+
+/// Layer rejecting the calls of the methods whose circuit is open.
+#[derive(Debug, Clone)]
+pub struct CircuitBreakerLayer {
+	breaker: Arc<CircuitBreaker>,
+	metrics: Option<Metrics>,
+}
+
+impl CircuitBreakerLayer {
+	/// Create a new [`CircuitBreakerLayer`].
+	pub fn new(breaker: Arc<CircuitBreaker>) -> Self {
+		Self { breaker, metrics: None }
+	}
+
+	/// Report the rejected calls to the metrics.
+	pub fn with_metrics(self, metrics: Metrics) -> Self {
+		Self { metrics: Some(metrics), ..self }
+	}
+}
+
+impl<S> tower::Layer<S> for CircuitBreakerLayer {
+	type Service = CircuitBreakerService<S>;
+
+	fn layer(&self, service: S) -> Self::Service {
+		CircuitBreakerService { service, layer: self.clone() }
+	}
+}
+
+/// JSON-RPC middleware rejecting the calls of the methods whose circuit is open.
+#[derive(Debug, Clone)]
+pub struct CircuitBreakerService<S> {
+	service: S,
+	layer: CircuitBreakerLayer,
+}
+
+impl<'a, S> RpcServiceT<'a> for CircuitBreakerService<S>
+where
+	S: RpcServiceT<'a>,
+	S::Future: Send + 'a,
+{
+	type Future = BoxFuture<'a, MethodResponse>;
+
+	fn call(&self, req: Request<'a>) -> Self::Future {
+		let method = req.method_name().to_owned();
+		if ALWAYS_SERVED_METHODS.contains(&method.as_str()) {
+			return self.service.call(req).boxed()
+		}
+
+		let started = Instant::now();
+		if let Some(retry_after) = self.layer.breaker.open_for(&method, started) {
+			log::debug!(target: "rpc", "Rejected call to {method}, its circuit is open");
+			if let Some(metrics) = &self.layer.metrics {
+				metrics.on_short_circuit(&method);
+			}
+			let error = ErrorObject::owned(
+				CIRCUIT_OPEN_CODE,
+				"RPC method temporarily unavailable",
+				Some(serde_json::json!({ "retry_after_ms": retry_after.as_millis() as u64 })),
+			);
+			return futures::future::ready(MethodResponse::error(req.id, error)).boxed()
+		}
+
+		let layer = self.layer.clone();
+		let call = self.service.call(req);
+
+		async move {
+			let rp = call.await;
+			let failed = matches!(rp.as_error_code(), Some(INTERNAL_ERROR_CODE | TIMEOUT_CODE));
+			// Calls to unknown methods aren't tracked, there's no limit to their names.
+			if rp.as_error_code() != Some(ErrorCode::MethodNotFound.code()) {
+				let now = Instant::now();
+				if layer.breaker.on_response(&method, failed, now - started, now) {
+					log::warn!(
+						target: "rpc",
+						"Circuit of {method} opened, its calls are rejected for {:?}",
+						layer.breaker.config.cool_down,
+					);
+				}
+			}
+			rp
+		}
+		.boxed()
+	}
+}
@@ -0,0 +1,210 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! RPC middleware reporting the calls of deprecated methods.
+//!
+//! The calls are counted by the `substrate_rpc_deprecated_method_calls_total` metric, such that
+//! the remaining usage of the methods can be measured before they are removed.
+//!
+//! The clients are warned in the JSON-RPC responses, whatever their transport: the warning is
+//! added as a [`DEPRECATION_WARNING_MEMBER`] member of the object results and of the `data` of the
+//! errors. The other results, e.g. strings, can't carry it without changing their type, and the
+//! subscriptions are left as is. The HTTP responses carry a `Warning` header as well.
+
+use std::sync::{Arc, Mutex};
+
+use futures::future::{BoxFuture, FutureExt};
+use http::header::HeaderValue;
+use jsonrpsee::{
+	server::middleware::rpc::RpcServiceT,
+	types::{ErrorCode, ErrorObject, Id, Request},
+	MethodResponse, ResponsePayload,
+};
+use serde::Deserialize;
+use serde_json::{value::RawValue, Map, Value};
+
+use super::Metrics;
+use crate::MethodPattern;
+
+/// Member of the results and error data carrying the warning about a deprecated method.
+pub const DEPRECATION_WARNING_MEMBER: &str = "deprecationWarning";
+
+/// Layer reporting the calls of the deprecated methods.
+#[derive(Debug, Clone)]
+pub struct DeprecationLayer {
+	methods: Arc<[MethodPattern]>,
+	metrics: Option<Metrics>,
+	warnings: DeprecationWarnings,
+}
+
+impl DeprecationLayer {
+	/// Create a layer reporting the calls of the methods matching any of `methods`.
+	pub fn new(methods: Arc<[MethodPattern]>, metrics: Option<Metrics>) -> Self {
+		Self { methods, metrics, warnings: Default::default() }
+	}
+
+	/// The warnings about the deprecated methods called through this layer.
+	pub fn warnings(&self) -> DeprecationWarnings {
+		self.warnings.clone()
+	}
+}
+
+impl<S> tower::Layer<S> for DeprecationLayer {
+	type Service = Deprecation<S>;
+
+	fn layer(&self, service: S) -> Self::Service {
+		Deprecation { service, layer: self.clone() }
+	}
+}
+
+/// JSON-RPC middleware reporting the calls of the deprecated methods.
+#[derive(Debug, Clone)]
+pub struct Deprecation<S> {
+	service: S,
+	layer: DeprecationLayer,
+}
+
+impl<'a, S> RpcServiceT<'a> for Deprecation<S>
+where
+	S: RpcServiceT<'a>,
+	S::Future: Send + 'a,
+{
+	type Future = BoxFuture<'a, MethodResponse>;
+
+	fn call(&self, req: Request<'a>) -> Self::Future {
+		let method = req.method_name();
+		if !self.layer.methods.iter().any(|pattern| pattern.matches(method)) {
+			return self.service.call(req).boxed()
+		}
+
+		let method = method.to_owned();
+		let id = req.id.clone().into_owned();
+		let layer = self.layer.clone();
+		let call = self.service.call(req);
+
+		async move {
+			let rp = call.await;
+			// The patterns may match methods which don't exist, whose calls aren't counted such
+			// that the clients can't add labels to the metric.
+			if rp.as_error_code() == Some(ErrorCode::MethodNotFound.code()) {
+				return rp
+			}
+
+			// Labelled with the method whatever its number of calls, to measure the usage of the
+			// rarely called ones as well.
+			if let Some(metrics) = &layer.metrics {
+				metrics.on_deprecated_call(&method);
+			}
+			layer.warnings.push(&method);
+			with_warning(rp, id, &method)
+		}
+		.boxed()
+	}
+}
+
+/// A JSON-RPC response, of which only the members carrying the warning are decoded.
+#[derive(Deserialize)]
+struct Response<'a> {
+	#[serde(borrow)]
+	result: Option<&'a RawValue>,
+	#[serde(borrow)]
+	error: Option<ErrorObject<'a>>,
+}
+
+fn warning(method: &str) -> String {
+	format!("Method {method} is deprecated and will be removed")
+}
+
+/// Decode an object, `None` if the value is not one.
+fn object(value: &RawValue) -> Option<Map<String, Value>> {
+	serde_json::from_str(value.get()).ok()
+}
+
+/// Add the warning to an object, unless it already has a member of the same name.
+fn with_member(mut object: Map<String, Value>, method: &str) -> Option<Map<String, Value>> {
+	if object.contains_key(DEPRECATION_WARNING_MEMBER) {
+		return None
+	}
+	object.insert(DEPRECATION_WARNING_MEMBER.into(), warning(method).into());
+	Some(object)
+}
+
+/// Add the warning about `method` to its response, if the response can carry it.
+fn with_warning(rp: MethodResponse, id: Id<'static>, method: &str) -> MethodResponse {
+	if rp.is_subscription() {
+		return rp
+	}
+
+	let warned = match serde_json::from_str::<Response>(rp.as_result()) {
+		Ok(Response { result: Some(result), .. }) =>
+			object(result).and_then(|result| with_member(result, method)).map(|result| {
+				MethodResponse::response(id, ResponsePayload::success(result), usize::MAX)
+			}),
+		Ok(Response { error: Some(error), .. }) => error
+			.data()
+			.map_or(Some(Map::new()), object)
+			.and_then(|data| with_member(data, method))
+			.map(|data| {
+				MethodResponse::error(
+					id,
+					ErrorObject::owned(error.code(), error.message(), Some(data)),
+				)
+			}),
+		_ => None,
+	};
+
+	let Some(warned) = warned else { return rp };
+	// The response replacing it is sent right after, its completion is reported now.
+	let is_success = rp.is_success();
+	if let (_, Some(on_close)) = rp.into_parts() {
+		on_close.notify(is_success);
+	}
+	warned
+}
+
+/// Warnings about the deprecated methods called, shared between the clones.
+#[derive(Debug, Clone, Default)]
+pub struct DeprecationWarnings(Arc<Mutex<Vec<String>>>);
+
+impl DeprecationWarnings {
+	/// Maximum number of warnings, such that a batch can't make the headers grow unbounded.
+	const MAX_WARNINGS: usize = 16;
+
+	fn push(&self, method: &str) {
+		// Only the names of the existing methods are pushed, but they are used in a header.
+		if !method.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+			return
+		}
+		let mut methods = self.0.lock().unwrap_or_else(|e| e.into_inner());
+		if methods.len() < Self::MAX_WARNINGS && !methods.iter().any(|m| m == method) {
+			methods.push(method.to_owned());
+		}
+	}
+
+	/// Take the warnings as `Warning` header values, with the `299` miscellaneous persistent
+	/// warning code.
+	pub fn take_header_values(&self) -> Vec<HeaderValue> {
+		let methods = std::mem::take(&mut *self.0.lock().unwrap_or_else(|e| e.into_inner()));
+		methods
+			.into_iter()
+			.filter_map(|method| {
+				HeaderValue::from_str(&format!("299 - \"{}\"", warning(&method))).ok()
+			})
+			.collect()
+	}
+}
@@ -0,0 +1,98 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Extension point for the RPC middleware of the embedders of the server.
+
+use std::{fmt, net::IpAddr, sync::Arc};
+
+use http::HeaderMap;
+use jsonrpsee::server::middleware::rpc::RpcServiceT;
+
+use super::BoxedRpcService;
+
+/// Middleware of an embedder of the server, e.g. for billing or custom authentication.
+///
+/// The extensions are registered in [`Config::middleware_extensions`], and wrap the service of
+/// every connection, or every request for plain HTTP. They are called after the authentication,
+/// rate limit and metrics middleware, and before the cost budget, the quotas and the response
+/// cache. The extensions registered first see the calls first.
+///
+/// [`Config::middleware_extensions`]: crate::Config::middleware_extensions
+pub trait RpcMiddlewareExtension: Send + Sync + 'static {
+	/// Name of the extension, for the logs.
+	fn name(&self) -> &'static str;
+
+	/// Wrap the service serving the calls of the connection described by `context`.
+	///
+	/// This is called when the connection is opened, the returned service must be cheap to clone.
+	fn layer(&self, service: BoxedRpcService, context: &MiddlewareContext) -> BoxedRpcService;
+}
+
+impl fmt::Debug for dyn RpcMiddlewareExtension {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_tuple("RpcMiddlewareExtension").field(&self.name()).finish()
+	}
+}
+
+/// Connection whose calls are served by an [`RpcMiddlewareExtension`].
+#[derive(Debug, Clone)]
+pub struct MiddlewareContext {
+	/// Identifier of the connection, as reported by `rpc_connections_list`.
+	pub connection_id: u64,
+	/// Address of the client, the one reported by a trusted proxy if any.
+	pub client_ip: IpAddr,
+	/// Transport of the calls: `ws`, `http` or `sse`.
+	pub transport: &'static str,
+	/// Headers of the HTTP request, of the upgrade request for WebSocket.
+	pub headers: HeaderMap,
+}
+
+/// Layer applying the [`RpcMiddlewareExtension`]s, erasing the type of the services below it
+/// like [`BoxLayer`](super::BoxLayer).
+#[derive(Debug, Clone)]
+pub(crate) struct ExtensionsLayer {
+	extensions: Arc<[Arc<dyn RpcMiddlewareExtension>]>,
+	context: Option<MiddlewareContext>,
+}
+
+impl ExtensionsLayer {
+	/// Layer applying `extensions`, `context` is only built if there are any.
+	pub(crate) fn new(
+		extensions: Arc<[Arc<dyn RpcMiddlewareExtension>]>,
+		context: impl FnOnce() -> MiddlewareContext,
+	) -> Self {
+		let context = (!extensions.is_empty()).then(context);
+		Self { extensions, context }
+	}
+}
+
+impl<S> tower::Layer<S> for ExtensionsLayer
+where
+	S: for<'a> RpcServiceT<'a> + Send + Sync + 'static,
+{
+	type Service = BoxedRpcService;
+
+	fn layer(&self, service: S) -> Self::Service {
+		let service = BoxedRpcService::new(service);
+		let Some(context) = &self.context else { return service };
+		self.extensions
+			.iter()
+			.rev()
+			.fold(service, |service, extension| extension.layer(service, context))
+	}
+}
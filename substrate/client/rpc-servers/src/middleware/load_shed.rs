@@ -0,0 +1,179 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! RPC middleware rejecting calls while the server is under pressure.
+//!
+//! The pressure is measured by the number of calls in flight and by the latency of the tokio
+//! runtime, i.e. how late a task is polled after it's woken up.
+
+use std::{
+	sync::{
+		atomic::{AtomicU64, AtomicUsize, Ordering},
+		Arc, Weak,
+	},
+	time::Duration,
+};
+
+use futures::future::{BoxFuture, FutureExt};
+use jsonrpsee::{
+	server::middleware::rpc::RpcServiceT,
+	types::{error::ErrorCode, ErrorObject, Request},
+	MethodResponse,
+};
+use tokio::time::Instant;
+
+use crate::MethodPattern;
+
+/// Methods which are served under any pressure.
+///
+/// Health checks must keep working and unfollowing frees resources of the server.
+pub const ALWAYS_SERVED_METHODS: &[&str] =
+	&["system_health", "chainHead_unfollow", "chainHead_v1_unfollow"];
+
+/// Interval at which the latency of the runtime is sampled.
+const LATENCY_SAMPLE_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Load shedding configuration.
+#[derive(Debug, Clone, Default)]
+pub struct LoadShedConfig {
+	/// Number of calls in flight, across all connections, above which calls are rejected.
+	pub max_in_flight: Option<usize>,
+	/// Latency of the tokio runtime above which calls are rejected.
+	pub max_queue_latency: Option<Duration>,
+	/// Methods served under any pressure, in addition to the [`ALWAYS_SERVED_METHODS`].
+	pub priority_methods: Vec<MethodPattern>,
+}
+
+/// Pressure of the server, shared by all connections.
+#[derive(Debug)]
+struct Pressure {
+	config: LoadShedConfig,
+	in_flight: AtomicUsize,
+	queue_latency_us: AtomicU64,
+}
+
+impl Pressure {
+	fn is_overloaded(&self) -> bool {
+		let too_many_calls = self
+			.config
+			.max_in_flight
+			.map_or(false, |max| self.in_flight.load(Ordering::Relaxed) >= max);
+		let too_slow = self.config.max_queue_latency.map_or(false, |max| {
+			self.queue_latency_us.load(Ordering::Relaxed) > max.as_micros() as u64
+		});
+		too_many_calls || too_slow
+	}
+
+	fn is_priority(&self, method: &str) -> bool {
+		ALWAYS_SERVED_METHODS.contains(&method) ||
+			self.config.priority_methods.iter().any(|pattern| pattern.matches(method))
+	}
+}
+
+/// Layer rejecting calls with a "server is busy" error while the server is under pressure.
+#[derive(Debug, Clone)]
+pub struct LoadShedLayer(Arc<Pressure>);
+
+impl LoadShedLayer {
+	/// Create a layer and start sampling the latency of the current tokio runtime, which stops
+	/// once the layer and all its clones are dropped.
+	pub fn new(config: LoadShedConfig) -> Self {
+		let sample_latency = config.max_queue_latency.is_some();
+		let pressure = Arc::new(Pressure {
+			config,
+			in_flight: AtomicUsize::new(0),
+			queue_latency_us: AtomicU64::new(0),
+		});
+
+		if sample_latency {
+			tokio::spawn(sample_queue_latency(Arc::downgrade(&pressure)));
+		}
+
+		Self(pressure)
+	}
+}
+
+/// Measure how late the runtime wakes up a sleeping task.
+async fn sample_queue_latency(pressure: Weak<Pressure>) {
+	loop {
+		let deadline = Instant::now() + LATENCY_SAMPLE_INTERVAL;
+		tokio::time::sleep_until(deadline).await;
+		let latency = Instant::now().saturating_duration_since(deadline);
+
+		let Some(pressure) = pressure.upgrade() else { return };
+		pressure.queue_latency_us.store(latency.as_micros() as u64, Ordering::Relaxed);
+	}
+}
+
+impl<S> tower::Layer<S> for LoadShedLayer {
+	type Service = LoadShed<S>;
+
+	fn layer(&self, service: S) -> Self::Service {
+		LoadShed { service, pressure: self.0.clone() }
+	}
+}
+
+/// JSON-RPC middleware rejecting calls of non-priority methods while the server is under
+/// pressure.
+#[derive(Debug, Clone)]
+pub struct LoadShed<S> {
+	service: S,
+	pressure: Arc<Pressure>,
+}
+
+impl<'a, S> RpcServiceT<'a> for LoadShed<S>
+where
+	S: RpcServiceT<'a>,
+	S::Future: Send + 'a,
+{
+	type Future = BoxFuture<'a, MethodResponse>;
+
+	fn call(&self, req: Request<'a>) -> Self::Future {
+		if !self.pressure.is_priority(req.method_name()) && self.pressure.is_overloaded() {
+			log::debug!(target: "rpc", "Rejected call to {} under load", req.method_name());
+			let rp = MethodResponse::error(req.id, ErrorObject::from(ErrorCode::ServerIsBusy));
+			return futures::future::ready(rp).boxed()
+		}
+
+		let in_flight = InFlight::new(self.pressure.clone());
+		let fut = self.service.call(req);
+
+		async move {
+			let rp = fut.await;
+			drop(in_flight);
+			rp
+		}
+		.boxed()
+	}
+}
+
+/// Call in flight, no longer counted once dropped.
+struct InFlight(Arc<Pressure>);
+
+impl InFlight {
+	fn new(pressure: Arc<Pressure>) -> Self {
+		pressure.in_flight.fetch_add(1, Ordering::Relaxed);
+		Self(pressure)
+	}
+}
+
+impl Drop for InFlight {
+	fn drop(&mut self) {
+		self.0.in_flight.fetch_sub(1, Ordering::Relaxed);
+	}
+}
@@ -18,12 +18,16 @@
 
 //! RPC middleware to collect prometheus metrics on RPC calls.
 
-use std::time::Instant;
+use std::{
+	collections::HashMap,
+	sync::{Arc, Mutex},
+	time::Instant,
+};
 
 use jsonrpsee::{types::Request, MethodResponse};
 use prometheus_endpoint::{
-	register, Counter, CounterVec, HistogramOpts, HistogramVec, Opts, PrometheusError, Registry,
-	U64,
+	register, Counter, CounterVec, GaugeVec, HistogramOpts, HistogramVec, Opts, PrometheusError,
+	Registry, U64,
 };
 
 /// Histogram time buckets in microseconds.
@@ -41,6 +45,58 @@ const HISTOGRAM_BUCKETS: [f64; 11] = [
 	10_000_000.0,
 ];
 
+/// Label of the methods called too rarely to be labelled on their own.
+const OTHER_METHOD_LABEL: &str = "other";
+
+/// Maximum number of methods whose calls are counted to tell whether they are called rarely.
+///
+/// The clients can call any method name, the calls of the methods beyond this are labelled
+/// [`OTHER_METHOD_LABEL`].
+const MAX_COUNTED_METHODS: usize = 1024;
+
+/// Histogram buckets of the total cost of the calls of a batch.
+const BATCH_COST_BUCKETS: [f64; 10] =
+	[5.0, 10.0, 25.0, 50.0, 100.0, 250.0, 500.0, 1_000.0, 5_000.0, 10_000.0];
+
+/// Buckets of the number of concurrent connections of a peer IP address.
+const CONNECTIONS_BUCKETS: [(usize, &str); 5] =
+	[(1, "1"), (4, "2-4"), (9, "5-9"), (49, "10-49"), (usize::MAX, "50+")];
+
+fn connections_bucket(connections: usize) -> Option<&'static str> {
+	if connections == 0 {
+		return None
+	}
+	CONNECTIONS_BUCKETS
+		.iter()
+		.find(|(max, _)| connections <= *max)
+		.map(|(_, label)| *label)
+}
+
+/// Configuration of the [`RpcMetrics`].
+#[derive(Debug, Clone)]
+pub struct MetricsConfig {
+	/// Upper bounds [μs] of the buckets of the histogram over the RPC call times.
+	pub call_time_buckets: Vec<f64>,
+	/// Number of calls below which the calls of a method are labelled `other` rather than with
+	/// the method, to bound the cardinality of the metrics.
+	pub method_min_calls: u64,
+	/// Whether the calls over WebSocket are recorded.
+	pub ws_calls: bool,
+	/// Whether the calls over HTTP, including Server-Sent Events, are recorded.
+	pub http_calls: bool,
+}
+
+impl Default for MetricsConfig {
+	fn default() -> Self {
+		Self {
+			call_time_buckets: HISTOGRAM_BUCKETS.to_vec(),
+			method_min_calls: 0,
+			ws_calls: true,
+			http_calls: true,
+		}
+	}
+}
+
 /// Metrics for RPC middleware storing information about the number of requests started/completed,
 /// calls started/completed and their timings.
 #[derive(Debug, Clone)]
@@ -57,11 +113,42 @@ pub struct RpcMetrics {
 	ws_sessions_closed: Option<Counter<U64>>,
 	/// Histogram over RPC websocket sessions.
 	ws_sessions_time: HistogramVec,
+	/// Number of connections rejected before being served.
+	connections_rejected: CounterVec<U64>,
+	/// Number of responses rejected for exceeding the maximum response size.
+	responses_oversized: CounterVec<U64>,
+	/// Number of peer IP addresses by their number of concurrent connections.
+	peers_by_connections: GaugeVec<U64>,
+	/// Histogram over the total cost of the calls of the batches.
+	batch_cost: HistogramVec,
+	/// Number of calls rejected for exceeding the cost budget of their address.
+	calls_over_budget: CounterVec<U64>,
+	/// Number of calls aborted for exceeding their timeout.
+	calls_timed_out: CounterVec<U64>,
+	/// Number of calls rejected for exceeding a quota of their consumer.
+	calls_over_quota: CounterVec<U64>,
+	/// Number of calls taking longer than the slow call threshold.
+	slow_calls: CounterVec<U64>,
+	/// Number of calls rejected while the circuit of their method is open.
+	calls_short_circuited: CounterVec<U64>,
+	/// Number of calls of the deprecated methods.
+	deprecated_method_calls: CounterVec<U64>,
+	/// Number of calls of each method, while they are called too rarely to be labelled.
+	method_calls: Arc<Mutex<HashMap<String, u64>>>,
+	/// Number of calls below which a method is labelled `other`.
+	method_min_calls: u64,
+	/// Whether the calls over WebSocket are recorded.
+	ws_calls: bool,
+	/// Whether the calls over HTTP are recorded.
+	http_calls: bool,
 }
 
 impl RpcMetrics {
 	/// Create an instance of metrics
-	pub fn new(metrics_registry: Option<&Registry>) -> Result<Option<Self>, PrometheusError> {
+	pub fn new(
+		metrics_registry: Option<&Registry>,
+		config: MetricsConfig,
+	) -> Result<Option<Self>, PrometheusError> {
 		if let Some(metrics_registry) = metrics_registry {
 			Ok(Some(Self {
 				calls_time: register(
@@ -70,7 +157,7 @@ impl RpcMetrics {
 							"substrate_rpc_calls_time",
 							"Total time [μs] of processed RPC calls",
 						)
-						.buckets(HISTOGRAM_BUCKETS.to_vec()),
+						.buckets(config.call_time_buckets),
 						&["protocol", "method", "is_rate_limited"],
 					)?,
 					metrics_registry,
@@ -122,12 +209,153 @@ impl RpcMetrics {
 					)?,
 					metrics_registry,
 				)?,
+				connections_rejected: register(
+					CounterVec::new(
+						Opts::new(
+							"substrate_rpc_connections_rejected",
+							"Number of RPC connections rejected before being served",
+						),
+						&["reason"],
+					)?,
+					metrics_registry,
+				)?,
+				responses_oversized: register(
+					CounterVec::new(
+						Opts::new(
+							"substrate_rpc_responses_oversized",
+							"Number of RPC responses rejected for exceeding the maximum response size",
+						),
+						&["protocol", "method"],
+					)?,
+					metrics_registry,
+				)?,
+				peers_by_connections: register(
+					GaugeVec::new(
+						Opts::new(
+							"substrate_rpc_peers_by_connections",
+							"Number of peer IP addresses by their number of concurrent RPC connections",
+						),
+						&["connections"],
+					)?,
+					metrics_registry,
+				)?,
+				batch_cost: register(
+					HistogramVec::new(
+						HistogramOpts::new(
+							"substrate_rpc_batch_cost",
+							"Total cost of the calls of the RPC batch requests",
+						)
+						.buckets(BATCH_COST_BUCKETS.to_vec()),
+						&["protocol"],
+					)?,
+					metrics_registry,
+				)?,
+				calls_over_budget: register(
+					CounterVec::new(
+						Opts::new(
+							"substrate_rpc_calls_over_budget",
+							"Number of RPC calls rejected for exceeding the cost budget of their address",
+						),
+						&["protocol", "method"],
+					)?,
+					metrics_registry,
+				)?,
+				calls_timed_out: register(
+					CounterVec::new(
+						Opts::new(
+							"substrate_rpc_calls_timed_out",
+							"Number of RPC calls aborted for exceeding their timeout",
+						),
+						&["protocol", "method"],
+					)?,
+					metrics_registry,
+				)?,
+				calls_over_quota: register(
+					CounterVec::new(
+						Opts::new(
+							"substrate_rpc_calls_over_quota",
+							"Number of RPC calls rejected for exceeding a quota of their consumer",
+						),
+						&["protocol", "method"],
+					)?,
+					metrics_registry,
+				)?,
+				slow_calls: register(
+					CounterVec::new(
+						Opts::new(
+							"substrate_rpc_slow_calls_total",
+							"Number of RPC calls taking longer than the slow call threshold",
+						),
+						&["protocol", "method"],
+					)?,
+					metrics_registry,
+				)?,
+				calls_short_circuited: register(
+					CounterVec::new(
+						Opts::new(
+							"substrate_rpc_calls_short_circuited",
+							"Number of RPC calls rejected while the circuit of their method is open",
+						),
+						&["protocol", "method"],
+					)?,
+					metrics_registry,
+				)?,
+				deprecated_method_calls: register(
+					CounterVec::new(
+						Opts::new(
+							"substrate_rpc_deprecated_method_calls_total",
+							"Number of RPC calls of the deprecated methods",
+						),
+						&["protocol", "method"],
+					)?,
+					metrics_registry,
+				)?,
+				method_calls: Default::default(),
+				method_min_calls: config.method_min_calls,
+				ws_calls: config.ws_calls,
+				http_calls: config.http_calls,
 			}))
 		} else {
 			Ok(None)
 		}
 	}
 
+	/// Whether the calls over the transport are recorded.
+	fn records_calls(&self, transport_label: &str) -> bool {
+		match transport_label {
+			"ws" => self.ws_calls,
+			_ => self.http_calls,
+		}
+	}
+
+	/// Count a call of `method`.
+	fn count_call(&self, method: &str) {
+		if self.method_min_calls == 0 {
+			return
+		}
+		let mut method_calls = self.method_calls.lock().expect("Lock poisoned; qed");
+		let is_full = method_calls.len() >= MAX_COUNTED_METHODS;
+		match method_calls.get_mut(method) {
+			Some(calls) => *calls = calls.saturating_add(1),
+			None if !is_full => {
+				method_calls.insert(method.to_owned(), 1);
+			},
+			None => (),
+		}
+	}
+
+	/// The label of `method`, which is `other` while it is called too rarely.
+	fn method_label<'a>(&self, method: &'a str) -> &'a str {
+		if self.method_min_calls == 0 {
+			return method
+		}
+		let method_calls = self.method_calls.lock().expect("Lock poisoned; qed");
+		match method_calls.get(method) {
+			Some(calls) if *calls >= self.method_min_calls => method,
+			_ => OTHER_METHOD_LABEL,
+		}
+	}
+
 	pub(crate) fn ws_connect(&self) {
 		self.ws_sessions_opened.as_ref().map(|counter| counter.inc());
 	}
@@ -139,6 +367,62 @@ impl RpcMetrics {
 		self.ws_sessions_time.with_label_values(&["ws"]).observe(micros as _);
 	}
 
+	pub(crate) fn connection_rejected(&self, reason: &str) {
+		self.connections_rejected.with_label_values(&[reason]).inc();
+	}
+
+	/// Move a peer IP address to the bucket of its new number of concurrent connections.
+	pub(crate) fn on_peer_connections_changed(&self, old: usize, new: usize) {
+		let (old, new) = (connections_bucket(old), connections_bucket(new));
+		if old == new {
+			return
+		}
+		if let Some(old) = old {
+			self.peers_by_connections.with_label_values(&[old]).dec();
+		}
+		if let Some(new) = new {
+			self.peers_by_connections.with_label_values(&[new]).inc();
+		}
+	}
+
+	pub(crate) fn on_oversized_response(&self, method: &str, transport_label: &'static str) {
+		let method = self.method_label(method);
+		self.responses_oversized.with_label_values(&[transport_label, method]).inc();
+	}
+
+	pub(crate) fn on_batch_cost(&self, cost: u64, transport_label: &'static str) {
+		self.batch_cost.with_label_values(&[transport_label]).observe(cost as _);
+	}
+
+	pub(crate) fn on_cost_budget_exceeded(&self, method: &str, transport_label: &'static str) {
+		let method = self.method_label(method);
+		self.calls_over_budget.with_label_values(&[transport_label, method]).inc();
+	}
+
+	pub(crate) fn on_timeout(&self, method: &str, transport_label: &'static str) {
+		let method = self.method_label(method);
+		self.calls_timed_out.with_label_values(&[transport_label, method]).inc();
+	}
+
+	pub(crate) fn on_quota_exceeded(&self, method: &str, transport_label: &'static str) {
+		let method = self.method_label(method);
+		self.calls_over_quota.with_label_values(&[transport_label, method]).inc();
+	}
+
+	pub(crate) fn on_slow_call(&self, method: &str, transport_label: &'static str) {
+		let method = self.method_label(method);
+		self.slow_calls.with_label_values(&[transport_label, method]).inc();
+	}
+
+	pub(crate) fn on_short_circuit(&self, method: &str, transport_label: &'static str) {
+		let method = self.method_label(method);
+		self.calls_short_circuited.with_label_values(&[transport_label, method]).inc();
+	}
+
+	pub(crate) fn on_deprecated_call(&self, method: &str, transport_label: &'static str) {
+		self.deprecated_method_calls.with_label_values(&[transport_label, method]).inc();
+	}
+
 	pub(crate) fn on_call(&self, req: &Request, transport_label: &'static str) {
 		log::trace!(
 			target: "rpc_metrics",
@@ -147,8 +431,12 @@ impl RpcMetrics {
 			req.params(),
 		);
 
+		self.count_call(req.method_name());
+		if !self.records_calls(transport_label) {
+			return
+		}
 		self.calls_started
-			.with_label_values(&[transport_label, req.method_name()])
+			.with_label_values(&[transport_label, self.method_label(req.method_name())])
 			.inc();
 	}
 
@@ -170,17 +458,21 @@ impl RpcMetrics {
 			req.method_name(),
 			micros,
 		);
+		if !self.records_calls(transport_label) {
+			return
+		}
+		let method = self.method_label(req.method_name());
 		self.calls_time
 			.with_label_values(&[
 				transport_label,
-				req.method_name(),
+				method,
 				if is_rate_limited { "true" } else { "false" },
 			])
 			.observe(micros as _);
 		self.calls_finished
 			.with_label_values(&[
 				transport_label,
-				req.method_name(),
+				method,
 				// the label "is_error", so `success` should be regarded as false
 				// and vice-versa to be registered correctly.
 				if rp.is_success() { "false" } else { "true" },
@@ -215,6 +507,38 @@ impl Metrics {
 		self.inner.on_call(req, self.transport_label)
 	}
 
+	pub(crate) fn on_batch_cost(&self, cost: u64) {
+		self.inner.on_batch_cost(cost, self.transport_label)
+	}
+
+	pub(crate) fn on_cost_budget_exceeded(&self, method: &str) {
+		self.inner.on_cost_budget_exceeded(method, self.transport_label)
+	}
+
+	pub(crate) fn on_timeout(&self, method: &str) {
+		self.inner.on_timeout(method, self.transport_label)
+	}
+
+	pub(crate) fn on_quota_exceeded(&self, method: &str) {
+		self.inner.on_quota_exceeded(method, self.transport_label)
+	}
+
+	pub(crate) fn on_slow_call(&self, method: &str) {
+		self.inner.on_slow_call(method, self.transport_label)
+	}
+
+	pub(crate) fn on_short_circuit(&self, method: &str) {
+		self.inner.on_short_circuit(method, self.transport_label)
+	}
+
+	pub(crate) fn on_deprecated_call(&self, method: &str) {
+		self.inner.on_deprecated_call(method, self.transport_label)
+	}
+
+	pub(crate) fn on_oversized_response(&self, method: &str) {
+		self.inner.on_oversized_response(method, self.transport_label)
+	}
+
 	pub(crate) fn on_response(
 		&self,
 		req: &Request,
@@ -20,6 +20,7 @@
 
 use std::{
 	num::NonZeroU32,
+	sync::Arc,
 	time::{Duration, Instant},
 };
 
@@ -31,11 +32,39 @@ use jsonrpsee::{
 	MethodResponse,
 };
 
+use crate::sse::RpcCall;
+
+mod access_log;
+mod audit;
+mod cache;
+mod call_cost;
+mod call_span;
+mod circuit_breaker;
+mod deprecation;
+mod extension;
+mod load_shed;
 mod metrics;
+mod quota;
 mod rate_limit;
-
+mod response_limit;
+mod slow_call;
+mod timeout;
+
+pub use access_log::*;
+pub use audit::*;
+pub use cache::*;
+pub use call_cost::*;
+pub use call_span::*;
+pub use circuit_breaker::*;
+pub use deprecation::*;
+pub use extension::*;
+pub use load_shed::*;
 pub use metrics::*;
+pub use quota::*;
 pub use rate_limit::*;
+pub use response_limit::*;
+pub use slow_call::*;
+pub use timeout::*;
 
 const MAX_JITTER: Duration = Duration::from_millis(50);
 const MAX_RETRIES: usize = 10;
@@ -55,7 +84,12 @@ impl MiddlewareLayer {
 
 	/// Enable new rate limit middleware enforced per minute.
 	pub fn with_rate_limit_per_minute(self, n: NonZeroU32) -> Self {
-		Self { rate_limit: Some(RateLimit::per_minute(n)), metrics: self.metrics }
+		self.with_rate_limit(RateLimit::per_minute(n))
+	}
+
+	/// Enable rate limit middleware sharing the given limit.
+	pub fn with_rate_limit(self, rate_limit: RateLimit) -> Self {
+		Self { rate_limit: Some(rate_limit), metrics: self.metrics }
 	}
 
 	/// Enable metrics middleware.
@@ -143,6 +177,47 @@ where
 	}
 }
 
+/// Layer erasing the type of the services below it.
+///
+/// The time it takes to compile the middleware stack grows exponentially with its depth, the
+/// layers below this one are compiled on their own.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct BoxLayer;
+
+impl<S> tower::Layer<S> for BoxLayer
+where
+	S: for<'a> RpcServiceT<'a> + Send + Sync + 'static,
+{
+	type Service = BoxedRpcService;
+
+	fn layer(&self, service: S) -> Self::Service {
+		BoxedRpcService::new(service)
+	}
+}
+
+/// JSON-RPC service with its type erased, cheap to clone.
+#[derive(Clone)]
+pub struct BoxedRpcService(Arc<dyn RpcCall>);
+
+impl BoxedRpcService {
+	/// Erase the type of the given service.
+	pub fn new<S>(service: S) -> Self
+	where
+		S: for<'a> RpcServiceT<'a> + Send + Sync + 'static,
+	{
+		Self(Arc::new(service))
+	}
+}
+
+impl<'a> RpcServiceT<'a> for BoxedRpcService {
+	type Future = BoxFuture<'a, MethodResponse>;
+
+	fn call(&self, req: Request<'a>) -> Self::Future {
+		let service = self.0.clone();
+		async move { service.call(req).await }.boxed()
+	}
+}
+
 fn reject_too_many_calls(id: Id) -> MethodResponse {
 	MethodResponse::error(id, ErrorObject::owned(-32999, "RPC rate limit exceeded", None::<()>))
 }
@@ -0,0 +1,589 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! RPC middleware enforcing quotas on the calls and the bandwidth of each consumer.
+//!
+//! A consumer is identified by the hash of its API key, or by its IP address for the requests
+//! without a key. Its calls and the bytes of their requests and responses are counted over rolling
+//! windows of the last minute, hour or day, the calls being rejected once a limit is reached.
+//!
+//! Each window is split in [`SLOTS_PER_WINDOW`] slots, the usage of a slot leaving the window
+//! as a whole once the window moved past it. A consumer reaching a limit can call again once
+//! enough of its oldest slots left the window, rather than having to wait for the end of a fixed
+//! window, and can't spend twice its limit across the boundary of two fixed windows.
+//!
+//! The counters are saved to a file periodically and when the server stops, such that restarting
+//! the node doesn't reset the quotas. The usage that left the windows is dropped at the same
+//! interval, whether or not the counters are saved.
+
+use std::{
+	collections::{HashMap, VecDeque},
+	fmt, io,
+	net::IpAddr,
+	path::PathBuf,
+	str::FromStr,
+	sync::{Arc, Mutex, Weak},
+	time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use futures::future::{BoxFuture, FutureExt};
+use jsonrpsee::{
+	server::middleware::rpc::RpcServiceT,
+	types::{ErrorObject, Request},
+	MethodResponse,
+};
+use serde::{Deserialize, Serialize};
+
+use super::Metrics;
+
+/// Error code of the calls rejected for exceeding a quota of their consumer.
+pub const QUOTA_EXCEEDED_CODE: i32 = -32994;
+
+/// Number of slots the usage of a window is counted in.
+pub const SLOTS_PER_WINDOW: u64 = 60;
+
+/// Duration of a quota window.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum QuotaPeriod {
+	/// A minute.
+	Minute,
+	/// An hour.
+	Hour,
+	/// A day.
+	Day,
+}
+
+impl QuotaPeriod {
+	fn secs(self) -> u64 {
+		match self {
+			Self::Minute => 60,
+			Self::Hour => 60 * 60,
+			Self::Day => 24 * 60 * 60,
+		}
+	}
+
+	fn as_str(self) -> &'static str {
+		match self {
+			Self::Minute => "minute",
+			Self::Hour => "hour",
+			Self::Day => "day",
+		}
+	}
+
+	/// Start of the slot containing `now`, in seconds since the UNIX epoch.
+	fn slot_start(self, now: u64) -> u64 {
+		now - now % (self.secs() / SLOTS_PER_WINDOW)
+	}
+}
+
+impl FromStr for QuotaPeriod {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"minute" => Ok(Self::Minute),
+			"hour" => Ok(Self::Hour),
+			"day" => Ok(Self::Day),
+			_ => Err(format!("Unknown period `{s}`, expected `minute`, `hour` or `day`")),
+		}
+	}
+}
+
+/// Limits of the calls and bandwidth of a consumer over a window.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuotaLimit {
+	/// Duration of the window.
+	pub period: QuotaPeriod,
+	/// Maximum calls per window, `None` for no limit.
+	pub max_calls: Option<u64>,
+	/// Maximum bytes of the requests and responses per window, `None` for no limit.
+	pub max_bytes: Option<u64>,
+}
+
+impl FromStr for QuotaLimit {
+	type Err = String;
+
+	/// Parse a limit formatted as `<PERIOD>:calls=<CALLS>,bytes=<BYTES>`, either of the limits
+	/// being optional.
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let (period, limits) = s
+			.split_once(':')
+			.ok_or_else(|| format!("Expected `<PERIOD>:calls=<CALLS>,bytes=<BYTES>`, got `{s}`"))?;
+		let mut limit = Self { period: period.trim().parse()?, max_calls: None, max_bytes: None };
+
+		for item in limits.split(',') {
+			let (name, value) = item
+				.split_once('=')
+				.ok_or_else(|| format!("Expected `<NAME>=<VALUE>`, got `{item}`"))?;
+			let value =
+				value.trim().parse().map_err(|e| format!("Invalid limit `{value}`: {e}"))?;
+			match name.trim() {
+				"calls" => limit.max_calls = Some(value),
+				"bytes" => limit.max_bytes = Some(value),
+				name => return Err(format!("Unknown limit `{name}`, expected `calls` or `bytes`")),
+			}
+		}
+
+		Ok(limit)
+	}
+}
+
+/// Quota configuration.
+#[derive(Debug, Clone)]
+pub struct QuotaConfig {
+	/// Limits of each consumer, all of them being enforced.
+	pub limits: Vec<QuotaLimit>,
+	/// File the counters are saved to and restored from, `None` to only keep them in memory.
+	pub path: Option<PathBuf>,
+	/// How often the expired usage is dropped and the counters are saved.
+	pub save_interval: Duration,
+}
+
+/// Consumer the quotas are accounted to.
+///
+/// API keys are displayed as their hash, such that they aren't written to the quota file or logs.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum QuotaConsumer {
+	/// The requests carrying an API key.
+	ApiKey(String),
+	/// The requests without an API key from an IP address.
+	Ip(IpAddr),
+}
+
+impl fmt::Display for QuotaConsumer {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::ApiKey(key) => {
+				f.write_str("key:")?;
+				sp_crypto_hashing::blake2_256(key.as_bytes())
+					.iter()
+					.try_for_each(|byte| write!(f, "{byte:02x}"))
+			},
+			Self::Ip(ip) => write!(f, "ip:{ip}"),
+		}
+	}
+}
+
+/// Usage of a consumer over a slot of a window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct Slot {
+	start: u64,
+	calls: u64,
+	bytes: u64,
+}
+
+/// Usage of a consumer over the rolling window of a period.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Usage {
+	period: QuotaPeriod,
+	/// The slots with some usage, oldest first.
+	slots: VecDeque<Slot>,
+}
+
+impl Usage {
+	/// Drop the slots that left the window ending at `now`.
+	fn prune(&mut self, now: u64) {
+		let (current, window) = (self.period.slot_start(now), self.period.secs());
+		while self.slots.front().map_or(false, |slot| slot.start + window <= current) {
+			self.slots.pop_front();
+		}
+	}
+
+	/// Calls and bytes over the window.
+	fn total(&self) -> (u64, u64) {
+		self.slots.iter().fold((0, 0), |(calls, bytes), slot| {
+			(calls.saturating_add(slot.calls), bytes.saturating_add(slot.bytes))
+		})
+	}
+
+	/// Account calls and bytes to the slot containing `now`.
+	fn add(&mut self, now: u64, calls: u64, bytes: u64) {
+		let start = self.period.slot_start(now);
+		match self.slots.back_mut() {
+			Some(slot) if slot.start == start => {
+				slot.calls = slot.calls.saturating_add(calls);
+				slot.bytes = slot.bytes.saturating_add(bytes);
+			},
+			_ => self.slots.push_back(Slot { start, calls, bytes }),
+		}
+	}
+
+	/// Time at which the oldest slot leaves the window, lowering the usage.
+	fn next_release(&self, now: u64) -> u64 {
+		self.slots.front().map_or(now, |slot| slot.start + self.period.secs())
+	}
+}
+
+/// A limit reached by a consumer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Exceeded {
+	period: QuotaPeriod,
+	resource: &'static str,
+	limit: u64,
+	used: u64,
+	reset_at: u64,
+}
+
+/// Counters of the consumers, shared by the listeners of the server.
+#[derive(Debug)]
+pub struct Quotas {
+	config: QuotaConfig,
+	usages: Mutex<HashMap<String, Vec<Usage>>>,
+}
+
+impl Quotas {
+	/// Restore the counters saved to the file of the configuration, if any.
+	///
+	/// A task dropping the expired usage, and saving the counters if a file is configured, every
+	/// `save_interval` is spawned.
+	pub fn new(config: QuotaConfig) -> io::Result<Arc<Self>> {
+		let usages = match &config.path {
+			Some(path) => match std::fs::read(path) {
+				Ok(bytes) => serde_json::from_slice(&bytes)?,
+				Err(e) if e.kind() == io::ErrorKind::NotFound => HashMap::new(),
+				Err(e) => return Err(e),
+			},
+			None => HashMap::new(),
+		};
+		let quotas = Arc::new(Self { config, usages: Mutex::new(usages) });
+
+		tokio::spawn(save_quotas(quotas.config.save_interval, Arc::downgrade(&quotas)));
+
+		Ok(quotas)
+	}
+
+	/// Account a call of `bytes` to the consumer, unless one of its limits is already reached.
+	fn try_call(&self, consumer: &str, now: u64, bytes: u64) -> Result<(), Exceeded> {
+		let mut usages = self.usages.lock().expect("Lock poisoned; qed");
+		let usages = usages.entry(consumer.to_owned()).or_default();
+
+		for limit in &self.config.limits {
+			let usage = usage_mut(usages, limit.period, now);
+			let (calls, used_bytes) = usage.total();
+			let reached =
+				[("calls", limit.max_calls, calls), ("bytes", limit.max_bytes, used_bytes)]
+					.into_iter()
+					.find_map(|(resource, max, used)| {
+						max.filter(|max| used >= *max).map(|limit| (resource, limit, used))
+					});
+			if let Some((resource, limit, used)) = reached {
+				return Err(Exceeded {
+					period: usage.period,
+					resource,
+					limit,
+					used,
+					reset_at: usage.next_release(now),
+				})
+			}
+		}
+
+		for limit in &self.config.limits {
+			usage_mut(usages, limit.period, now).add(now, 1, bytes);
+		}
+		Ok(())
+	}
+
+	/// Account the bytes of a response to the consumer.
+	fn add_bytes(&self, consumer: &str, now: u64, bytes: u64) {
+		let mut usages = self.usages.lock().expect("Lock poisoned; qed");
+		let usages = usages.entry(consumer.to_owned()).or_default();
+
+		for limit in &self.config.limits {
+			usage_mut(usages, limit.period, now).add(now, 0, bytes);
+		}
+	}
+
+	/// Drop the usage that left the windows, and the consumers left without any.
+	///
+	/// Then save the counters to the file of the configuration, if any.
+	fn save(&self) -> io::Result<()> {
+		let now = unix_now();
+		let (path, json) = {
+			let mut usages = self.usages.lock().expect("Lock poisoned; qed");
+			usages.retain(|_, usages| {
+				usages.retain_mut(|usage| {
+					usage.prune(now);
+					!usage.slots.is_empty()
+				});
+				!usages.is_empty()
+			});
+
+			let Some(path) = &self.config.path else { return Ok(()) };
+			(path, serde_json::to_vec(&*usages)?)
+		};
+
+		// Written next to the file and renamed, such that a crash doesn't leave it truncated.
+		let tmp_path = path.with_extension("tmp");
+		std::fs::write(&tmp_path, json)?;
+		std::fs::rename(tmp_path, path)
+	}
+}
+
+impl Drop for Quotas {
+	fn drop(&mut self) {
+		if let Err(e) = self.save() {
+			log::warn!("Failed to save the JSON-RPC quotas: {e}");
+		}
+	}
+}
+
+/// The usage of the window of `period` ending at `now`, created if the consumer has none.
+fn usage_mut(usages: &mut Vec<Usage>, period: QuotaPeriod, now: u64) -> &mut Usage {
+	let index = match usages.iter().position(|usage| usage.period == period) {
+		Some(index) => index,
+		None => {
+			usages.push(Usage { period, slots: VecDeque::new() });
+			usages.len() - 1
+		},
+	};
+	let usage = &mut usages[index];
+	usage.prune(now);
+	usage
+}
+
+fn unix_now() -> u64 {
+	SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs())
+}
+
+/// Drop the expired usage and save the counters every `interval` until the quotas are dropped.
+async fn save_quotas(interval: Duration, quotas: Weak<Quotas>) {
+	let mut interval = tokio::time::interval(interval);
+	interval.tick().await;
+
+	loop {
+		interval.tick().await;
+
+		let Some(quotas) = quotas.upgrade() else { return };
+
+		if let Err(e) = quotas.save() {
+			log::warn!("Failed to save the JSON-RPC quotas: {e}");
+		}
+	}
+}
+
+/// Layer accounting the calls of a consumer to its quotas.
+#[derive(Debug, Clone)]
+pub struct QuotaLayer {
+	quotas: Arc<Quotas>,
+	consumer: Arc<str>,
+	metrics: Option<Metrics>,
+}
+
+impl QuotaLayer {
+	/// Create a layer accounting the calls to the given consumer.
+	pub fn new(quotas: Arc<Quotas>, consumer: QuotaConsumer) -> Self {
+		Self { quotas, consumer: consumer.to_string().into(), metrics: None }
+	}
+
+	/// Report the rejected calls to the metrics.
+	pub fn with_metrics(self, metrics: Metrics) -> Self {
+		Self { metrics: Some(metrics), ..self }
+	}
+}
+
+impl<S> tower::Layer<S> for QuotaLayer {
+	type Service = Quota<S>;
+
+	fn layer(&self, service: S) -> Self::Service {
+		Quota { service, layer: self.clone() }
+	}
+}
+
+/// JSON-RPC middleware rejecting the calls of the consumers exceeding their quotas.
+#[derive(Debug, Clone)]
+pub struct Quota<S> {
+	service: S,
+	layer: QuotaLayer,
+}
+
+impl<'a, S> RpcServiceT<'a> for Quota<S>
+where
+	S: RpcServiceT<'a>,
+	S::Future: Send + 'a,
+{
+	type Future = BoxFuture<'a, MethodResponse>;
+
+	fn call(&self, req: Request<'a>) -> Self::Future {
+		let layer = self.layer.clone();
+		let request_bytes = req.params.as_ref().map_or(0, |params| params.get().len()) as u64;
+
+		let now = unix_now();
+		if let Err(exceeded) = layer.quotas.try_call(&layer.consumer, now, request_bytes) {
+			log::debug!(
+				target: "rpc",
+				"Rejected call to {} from {}, exceeding its {} {} quota",
+				req.method_name(),
+				layer.consumer,
+				exceeded.period.as_str(),
+				exceeded.resource,
+			);
+			if let Some(metrics) = &layer.metrics {
+				metrics.on_quota_exceeded(req.method_name());
+			}
+
+			let error = ErrorObject::owned(
+				QUOTA_EXCEEDED_CODE,
+				"RPC quota exceeded",
+				Some(serde_json::json!({
+					"period": exceeded.period.as_str(),
+					"resource": exceeded.resource,
+					"limit": exceeded.limit,
+					"used": exceeded.used,
+					"reset_at": exceeded.reset_at,
+					"reset_in_secs": exceeded.reset_at.saturating_sub(now),
+				})),
+			);
+			return futures::future::ready(MethodResponse::error(req.id, error)).boxed()
+		}
+
+		let fut = self.service.call(req);
+		async move {
+			let rp = fut.await;
+			layer.quotas.add_bytes(&layer.consumer, unix_now(), rp.as_result().len() as u64);
+			rp
+		}
+		.boxed()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const CONSUMER: &str = "ip:127.0.0.1";
+
+	fn config(limits: &str, path: Option<PathBuf>) -> QuotaConfig {
+		QuotaConfig {
+			limits: limits.split(';').map(|limit| limit.parse().unwrap()).collect(),
+			path,
+			save_interval: Duration::from_secs(60),
+		}
+	}
+
+	fn quotas(limits: &str) -> Quotas {
+		Quotas { config: config(limits, None), usages: Default::default() }
+	}
+
+	#[test]
+	fn parse_limits() {
+		assert_eq!(
+			"hour:calls=10,bytes=1000".parse(),
+			Ok(QuotaLimit {
+				period: QuotaPeriod::Hour,
+				max_calls: Some(10),
+				max_bytes: Some(1000)
+			})
+		);
+		assert_eq!(
+			"day:bytes=1000".parse(),
+			Ok(QuotaLimit { period: QuotaPeriod::Day, max_calls: None, max_bytes: Some(1000) })
+		);
+		assert!("week:calls=10".parse::<QuotaLimit>().is_err());
+		assert!("minute:requests=10".parse::<QuotaLimit>().is_err());
+	}
+
+	#[test]
+	fn slots_leave_the_window_at_its_boundary() {
+		let quotas = quotas("minute:calls=2");
+
+		assert_eq!(quotas.try_call(CONSUMER, 1000, 0), Ok(()));
+		assert_eq!(quotas.try_call(CONSUMER, 1030, 0), Ok(()));
+		assert!(quotas.try_call(CONSUMER, 1059, 0).is_err());
+
+		// The call at 1000 left the window, but not the one at 1030.
+		assert_eq!(quotas.try_call(CONSUMER, 1060, 0), Ok(()));
+		assert!(quotas.try_call(CONSUMER, 1089, 0).is_err());
+		assert_eq!(quotas.try_call(CONSUMER, 1090, 0), Ok(()));
+	}
+
+	#[test]
+	fn usage_is_pruned_per_slot() {
+		let mut usage = Usage { period: QuotaPeriod::Hour, slots: VecDeque::new() };
+		// Slots of an hour window are a minute long.
+		usage.add(3600, 1, 10);
+		usage.add(3659, 1, 10);
+		usage.add(3660, 1, 10);
+		assert_eq!(usage.slots.len(), 2);
+		assert_eq!(usage.total(), (3, 30));
+
+		usage.prune(7199);
+		assert_eq!(usage.total(), (3, 30));
+		usage.prune(7200);
+		assert_eq!(usage.total(), (1, 10));
+		usage.prune(7260);
+		assert!(usage.slots.is_empty());
+	}
+
+	#[test]
+	fn exceeded_reports_when_the_oldest_slot_leaves_the_window() {
+		let quotas = quotas("minute:calls=10;hour:calls=2");
+
+		assert_eq!(quotas.try_call(CONSUMER, 3630, 0), Ok(()));
+		assert_eq!(quotas.try_call(CONSUMER, 3700, 0), Ok(()));
+		assert_eq!(
+			quotas.try_call(CONSUMER, 3800, 0),
+			Err(Exceeded {
+				period: QuotaPeriod::Hour,
+				resource: "calls",
+				limit: 2,
+				used: 2,
+				reset_at: 3600 + 3600,
+			})
+		);
+		assert_eq!(quotas.try_call(CONSUMER, 7200, 0), Ok(()));
+	}
+
+	#[test]
+	fn bytes_of_requests_and_responses_are_limited() {
+		let quotas = quotas("minute:bytes=100");
+
+		assert_eq!(quotas.try_call(CONSUMER, 1000, 40), Ok(()));
+		quotas.add_bytes(CONSUMER, 1000, 60);
+		let exceeded = quotas.try_call(CONSUMER, 1001, 0).unwrap_err();
+		assert_eq!((exceeded.resource, exceeded.used, exceeded.reset_at), ("bytes", 100, 1060));
+
+		// Other consumers have their own quotas.
+		assert_eq!(quotas.try_call("ip:127.0.0.2", 1001, 0), Ok(()));
+	}
+
+	#[tokio::test]
+	async fn saved_quotas_are_restored() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("quotas.json");
+		let now = unix_now();
+
+		let quotas = Quotas::new(config("day:calls=2", Some(path.clone()))).unwrap();
+		assert_eq!(quotas.try_call(CONSUMER, now, 0), Ok(()));
+		assert_eq!(quotas.try_call(CONSUMER, now, 0), Ok(()));
+		quotas.save().unwrap();
+		drop(quotas);
+
+		let quotas = Quotas::new(config("day:calls=2", Some(path))).unwrap();
+		let exceeded = quotas.try_call(CONSUMER, now, 0).unwrap_err();
+		assert_eq!((exceeded.period, exceeded.used), (QuotaPeriod::Day, 2));
+		assert_eq!(quotas.try_call("ip:127.0.0.2", now, 0), Ok(()));
+	}
+
+	#[tokio::test]
+	async fn missing_quota_file_is_not_an_error() {
+		let dir = tempfile::tempdir().unwrap();
+		let quotas = Quotas::new(config("day:calls=2", Some(dir.path().join("quotas.json"))));
+		assert!(quotas.unwrap().usages.lock().unwrap().is_empty());
+	}
+}
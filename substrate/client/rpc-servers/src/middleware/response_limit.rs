@@ -0,0 +1,109 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! RPC middleware rejecting responses which exceed the maximum response size.
+//!
+//! Unlike the limit of `jsonrpsee`, which stops serializing a response once it's exceeded, the
+//! rejected responses are reported along with their size, such that the methods in need of
+//! pagination can be spotted.
+
+use futures::future::{BoxFuture, FutureExt};
+use jsonrpsee::{
+	server::middleware::rpc::RpcServiceT,
+	types::{ErrorObject, Request},
+	MethodResponse,
+};
+
+use super::Metrics;
+
+/// Error code of the responses exceeding the maximum response size.
+///
+/// The error data holds the `size` of the response and the `limit` it exceeded, in bytes.
+pub const RESPONSE_TOO_LARGE_CODE: i32 = -32997;
+
+/// Layer limiting the size of the responses of a connection.
+#[derive(Debug, Clone)]
+pub struct ResponseLimitLayer {
+	max_size: usize,
+	metrics: Option<Metrics>,
+}
+
+impl ResponseLimitLayer {
+	/// Create a layer rejecting the responses larger than `max_size` bytes.
+	pub fn new(max_size: usize, metrics: Option<Metrics>) -> Self {
+		Self { max_size, metrics }
+	}
+}
+
+impl<S> tower::Layer<S> for ResponseLimitLayer {
+	type Service = ResponseLimit<S>;
+
+	fn layer(&self, service: S) -> Self::Service {
+		ResponseLimit { service, layer: self.clone() }
+	}
+}
+
+/// JSON-RPC middleware rejecting the responses exceeding the maximum response size.
+#[derive(Debug, Clone)]
+pub struct ResponseLimit<S> {
+	service: S,
+	layer: ResponseLimitLayer,
+}
+
+impl<'a, S> RpcServiceT<'a> for ResponseLimit<S>
+where
+	S: RpcServiceT<'a>,
+	S::Future: Send + 'a,
+{
+	type Future = BoxFuture<'a, MethodResponse>;
+
+	fn call(&self, req: Request<'a>) -> Self::Future {
+		let id = req.id.clone();
+		let method = req.method_name().to_owned();
+		let layer = self.layer.clone();
+		let fut = self.service.call(req);
+
+		async move {
+			let rp = fut.await;
+			let size = rp.as_result().len();
+			// Subscriptions are already running once their id is returned.
+			if size <= layer.max_size || rp.is_subscription() {
+				return rp
+			}
+
+			log::debug!(
+				target: "rpc",
+				"Rejected response of {method} of {size} bytes, exceeding the limit of {} bytes",
+				layer.max_size,
+			);
+			if let Some(metrics) = &layer.metrics {
+				metrics.on_oversized_response(&method);
+			}
+
+			MethodResponse::error(
+				id,
+				ErrorObject::owned(
+					RESPONSE_TOO_LARGE_CODE,
+					"Response exceeds the maximum response size",
+					Some(serde_json::json!({ "size": size, "limit": layer.max_size })),
+				),
+			)
+		}
+		.boxed()
+	}
+}
@@ -0,0 +1,104 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! RPC middleware reporting the calls taking longer than a threshold.
+//!
+//! The slow calls are logged at warn level along with a digest of their parameters, such that
+//! the pathological queries can be told apart without logging the parameters themselves.
+
+use std::{
+	collections::hash_map::DefaultHasher,
+	hash::{Hash, Hasher},
+	net::IpAddr,
+	time::{Duration, Instant},
+};
+
+use futures::future::{BoxFuture, FutureExt};
+use jsonrpsee::{server::middleware::rpc::RpcServiceT, types::Request, MethodResponse};
+
+use super::Metrics;
+
+/// Layer reporting the calls of a client taking longer than a threshold.
+#[derive(Debug, Clone)]
+pub struct SlowCallLayer {
+	threshold: Duration,
+	client_ip: IpAddr,
+	metrics: Option<Metrics>,
+}
+
+impl SlowCallLayer {
+	/// Create a layer reporting the calls of `client_ip` taking longer than `threshold`.
+	pub fn new(threshold: Duration, client_ip: IpAddr, metrics: Option<Metrics>) -> Self {
+		Self { threshold, client_ip, metrics }
+	}
+}
+
+impl<S> tower::Layer<S> for SlowCallLayer {
+	type Service = SlowCall<S>;
+
+	fn layer(&self, service: S) -> Self::Service {
+		SlowCall { service, layer: self.clone() }
+	}
+}
+
+/// JSON-RPC middleware reporting the calls taking longer than a threshold.
+#[derive(Debug, Clone)]
+pub struct SlowCall<S> {
+	service: S,
+	layer: SlowCallLayer,
+}
+
+impl<'a, S> RpcServiceT<'a> for SlowCall<S>
+where
+	S: RpcServiceT<'a>,
+	S::Future: Send + 'a,
+{
+	type Future = BoxFuture<'a, MethodResponse>;
+
+	fn call(&self, req: Request<'a>) -> Self::Future {
+		let now = Instant::now();
+		let method = req.method_name().to_owned();
+		let params_digest = params_digest(&req);
+		let layer = self.layer.clone();
+		let call = self.service.call(req);
+
+		async move {
+			let rp = call.await;
+			let elapsed = now.elapsed();
+			if elapsed > layer.threshold {
+				log::warn!(
+					target: "rpc",
+					"Slow call to {method} from {} took {elapsed:?}, params digest {params_digest:016x}",
+					layer.client_ip,
+				);
+				if let Some(metrics) = &layer.metrics {
+					metrics.on_slow_call(&method);
+				}
+			}
+			rp
+		}
+		.boxed()
+	}
+}
+
+/// Digest of the parameters of a call, the same for the calls with the same parameters.
+fn params_digest(req: &Request) -> u64 {
+	let mut hasher = DefaultHasher::new();
+	req.params().as_str().unwrap_or_default().hash(&mut hasher);
+	hasher.finish()
+}
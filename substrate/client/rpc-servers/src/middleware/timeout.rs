@@ -0,0 +1,144 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! RPC middleware aborting the calls taking too long.
+//!
+//! The call is dropped once its timeout elapses, which frees the connection to serve other calls.
+//! Work the method handed off to other tasks may still run to completion.
+
+use std::{str::FromStr, sync::Arc, time::Duration};
+
+use futures::future::{BoxFuture, FutureExt};
+use jsonrpsee::{
+	server::middleware::rpc::RpcServiceT,
+	types::{ErrorObject, Request},
+	MethodResponse,
+};
+
+use super::Metrics;
+use crate::MethodPattern;
+
+/// Error code of the calls aborted for exceeding their timeout.
+pub const TIMEOUT_CODE: i32 = -32995;
+
+/// Timeout of the calls of the methods matching a pattern.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MethodTimeout {
+	/// Methods the timeout applies to.
+	pub methods: MethodPattern,
+	/// Timeout of a call.
+	pub timeout: Duration,
+}
+
+impl FromStr for MethodTimeout {
+	type Err = String;
+
+	/// Parse a timeout formatted as `<PATTERN>=<SECONDS>`.
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let (methods, secs) = s
+			.split_once('=')
+			.ok_or_else(|| format!("Expected `<PATTERN>=<SECONDS>`, got `{s}`"))?;
+		let secs = secs.trim().parse().map_err(|e| format!("Invalid timeout `{secs}`: {e}"))?;
+
+		Ok(Self { methods: methods.parse()?, timeout: Duration::from_secs(secs) })
+	}
+}
+
+/// Call timeout configuration.
+#[derive(Debug, Clone)]
+pub struct TimeoutConfig {
+	/// Timeout of the methods matching a pattern, the first matching one applies.
+	pub method_timeouts: Vec<MethodTimeout>,
+	/// Timeout of the methods not matching any of `method_timeouts`.
+	pub default_timeout: Duration,
+}
+
+impl TimeoutConfig {
+	/// The timeout of a call of the given method.
+	pub fn timeout(&self, method: &str) -> Duration {
+		self.method_timeouts
+			.iter()
+			.find(|timeout| timeout.methods.matches(method))
+			.map_or(self.default_timeout, |timeout| timeout.timeout)
+	}
+}
+
+/// Layer aborting the calls exceeding their timeout.
+#[derive(Debug, Clone)]
+pub struct TimeoutLayer {
+	config: Arc<TimeoutConfig>,
+	metrics: Option<Metrics>,
+}
+
+impl TimeoutLayer {
+	/// Create a new [`TimeoutLayer`].
+	pub fn new(config: Arc<TimeoutConfig>, metrics: Option<Metrics>) -> Self {
+		Self { config, metrics }
+	}
+}
+
+impl<S> tower::Layer<S> for TimeoutLayer {
+	type Service = Timeout<S>;
+
+	fn layer(&self, service: S) -> Self::Service {
+		Timeout { service, config: self.config.clone(), metrics: self.metrics.clone() }
+	}
+}
+
+/// JSON-RPC middleware aborting the calls exceeding their timeout.
+#[derive(Debug, Clone)]
+pub struct Timeout<S> {
+	service: S,
+	config: Arc<TimeoutConfig>,
+	metrics: Option<Metrics>,
+}
+
+impl<'a, S> RpcServiceT<'a> for Timeout<S>
+where
+	S: RpcServiceT<'a>,
+	S::Future: Send + 'a,
+{
+	type Future = BoxFuture<'a, MethodResponse>;
+
+	fn call(&self, req: Request<'a>) -> Self::Future {
+		let timeout = self.config.timeout(req.method_name());
+		let id = req.id.clone();
+		let method = req.method_name().to_owned();
+		let metrics = self.metrics.clone();
+		let call = self.service.call(req);
+
+		async move {
+			if let Ok(rp) = tokio::time::timeout(timeout, call).await {
+				return rp
+			}
+
+			log::debug!(target: "rpc", "Call to {method} timed out after {timeout:?}");
+			if let Some(metrics) = &metrics {
+				metrics.on_timeout(&method);
+			}
+
+			let error = ErrorObject::owned(
+				TIMEOUT_CODE,
+				"RPC call timed out",
+				Some(serde_json::json!({ "timeout_ms": timeout.as_millis() as u64 })),
+			);
+			MethodResponse::error(id, error)
+		}
+		.boxed()
+	}
+}
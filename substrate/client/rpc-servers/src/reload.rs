@@ -0,0 +1,131 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Settings of a running RPC server that can be changed without restarting it.
+
+use std::{
+	error::Error as StdError,
+	net::{IpAddr, SocketAddr},
+	num::NonZeroU32,
+	sync::{Arc, RwLock},
+};
+
+use ip_network::IpNetwork;
+use jsonrpsee::server::middleware::http::HostFilterLayer;
+use tower_http::cors::CorsLayer;
+
+use crate::{format_cors, hosts_filtering, method_cors, try_into_cors, MethodCors};
+
+/// Settings of the server that can be changed while it is running.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReloadableConfig {
+	/// Allowed CORS origins, `None` to allow any origin.
+	pub cors: Option<Vec<String>>,
+	/// Allowed CORS origins of specific methods, overriding `cors` for them.
+	pub method_cors: Vec<MethodCors>,
+	/// Rate limit calls per minute.
+	pub rate_limit: Option<NonZeroU32>,
+	/// IP addresses, or CIDR ranges, of peers exempt from the rate limit and the per-IP
+	/// connection limit.
+	pub rate_limit_whitelisted_ips: Vec<IpNetwork>,
+}
+
+impl ReloadableConfig {
+	/// The rate limit applying to a peer with the given address.
+	pub fn rate_limit_for(&self, ip: IpAddr) -> Option<NonZeroU32> {
+		if self.is_whitelisted(ip) {
+			return None
+		}
+
+		self.rate_limit
+	}
+
+	/// Whether the peer with the given address is exempt from the rate limit and the per-IP
+	/// connection limit.
+	pub fn is_whitelisted(&self, ip: IpAddr) -> bool {
+		let ip = ip.to_canonical();
+		self.rate_limit_whitelisted_ips.iter().any(|network| network.contains(ip))
+	}
+}
+
+/// The [`ReloadableConfig`] along with the middleware built from it.
+#[derive(Debug)]
+pub(crate) struct Snapshot {
+	pub(crate) config: ReloadableConfig,
+	pub(crate) host_filter: Option<HostFilterLayer>,
+	pub(crate) cors: CorsLayer,
+}
+
+impl Snapshot {
+	fn new(
+		config: ReloadableConfig,
+		local_addr: Option<SocketAddr>,
+	) -> Result<Self, Box<dyn StdError + Send + Sync>> {
+		let cors = try_into_cors(
+			method_cors::allowed_origins(config.cors.as_ref(), &config.method_cors).as_ref(),
+		)?;
+		let host_filter = hosts_filtering(config.cors.is_some(), local_addr);
+		Ok(Self { config, host_filter, cors })
+	}
+}
+
+/// Handle to change the [`ReloadableConfig`] of a running server.
+///
+/// Changes apply to connections opened afterwards, established connections keep the
+/// settings they were opened with.
+#[derive(Debug, Clone)]
+pub struct ReloadHandle {
+	current: Arc<RwLock<Arc<Snapshot>>>,
+	local_addr: Option<SocketAddr>,
+}
+
+impl ReloadHandle {
+	pub(crate) fn new(
+		config: ReloadableConfig,
+		local_addr: Option<SocketAddr>,
+	) -> Result<Self, Box<dyn StdError + Send + Sync>> {
+		let snapshot = Snapshot::new(config, local_addr)?;
+		Ok(Self { current: Arc::new(RwLock::new(Arc::new(snapshot))), local_addr })
+	}
+
+	/// The settings currently in use.
+	pub fn config(&self) -> ReloadableConfig {
+		self.snapshot().config.clone()
+	}
+
+	/// Replace the settings.
+	///
+	/// Fails without changing anything if the configuration is invalid, e.g. contains a
+	/// CORS origin which isn't a valid header value.
+	pub fn update(&self, config: ReloadableConfig) -> Result<(), Box<dyn StdError + Send + Sync>> {
+		let snapshot = Snapshot::new(config, self.local_addr)?;
+		log::info!(
+			"Updated JSON-RPC server config: allowed origins={}, method origins={:?}, rate limit={:?}, whitelisted ips={:?}",
+			format_cors(snapshot.config.cors.as_ref()),
+			snapshot.config.method_cors,
+			snapshot.config.rate_limit,
+			snapshot.config.rate_limit_whitelisted_ips,
+		);
+		*self.current.write().expect("Lock poisoned; qed") = Arc::new(snapshot);
+		Ok(())
+	}
+
+	pub(crate) fn snapshot(&self) -> Arc<Snapshot> {
+		self.current.read().expect("Lock poisoned; qed").clone()
+	}
+}
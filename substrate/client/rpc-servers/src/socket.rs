@@ -0,0 +1,78 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! TCP socket options of the listeners.
+
+use std::{io, net::SocketAddr, time::Duration};
+
+use hyper::server::conn::AddrIncoming;
+use tokio::net::{TcpListener, TcpSocket};
+
+/// TCP socket options of the listeners and of the connections they accept.
+#[derive(Debug, Copy, Clone)]
+pub struct SocketConfig {
+	/// Set `TCP_NODELAY` on the connections, sending the responses without waiting to fill a
+	/// segment.
+	pub nodelay: bool,
+	/// Interval of the TCP keepalive probes sent on idle connections, `None` to disable them.
+	pub keepalive: Option<Duration>,
+	/// Set `SO_REUSEPORT` on the listeners, allowing several processes to listen on the same
+	/// port, the kernel spreading the connections between them.
+	///
+	/// Only supported on Unix.
+	pub reuse_port: bool,
+	/// Maximum number of connections waiting to be accepted.
+	pub backlog: u32,
+}
+
+impl Default for SocketConfig {
+	fn default() -> Self {
+		Self { nodelay: false, keepalive: None, reuse_port: false, backlog: 1024 }
+	}
+}
+
+/// Bind a listener to `addr`.
+pub(crate) fn bind(addr: SocketAddr, config: &SocketConfig) -> io::Result<TcpListener> {
+	let socket = if addr.is_ipv4() { TcpSocket::new_v4()? } else { TcpSocket::new_v6()? };
+
+	// Same as `TcpListener::bind`, the port can be bound again while the connections of a
+	// previous listener are in `TIME_WAIT`.
+	#[cfg(not(windows))]
+	socket.set_reuseaddr(true)?;
+
+	if config.reuse_port {
+		#[cfg(all(unix, not(any(target_os = "solaris", target_os = "illumos"))))]
+		socket.set_reuseport(true)?;
+		#[cfg(not(all(unix, not(any(target_os = "solaris", target_os = "illumos")))))]
+		return Err(io::Error::new(
+			io::ErrorKind::Unsupported,
+			"SO_REUSEPORT is not supported on this platform",
+		))
+	}
+
+	socket.bind(addr)?;
+	socket.listen(config.backlog)
+}
+
+/// Apply the options of the connections to the ones accepted by `incoming`.
+pub(crate) fn configure(incoming: &mut AddrIncoming, config: &SocketConfig) {
+	incoming
+		.set_nodelay(config.nodelay)
+		.set_keepalive(config.keepalive)
+		.set_keepalive_interval(config.keepalive);
+}
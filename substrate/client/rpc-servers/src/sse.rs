@@ -0,0 +1,197 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Server-Sent Events transport of the subscriptions.
+//!
+//! Clients unable to hold a WebSocket, e.g. in serverless environments, can `POST` a subscription
+//! call, e.g. to `chainHead_v1_follow` or `transactionWatch_v1_submitAndWatch`, to [`SSE_PATH`].
+//! The response is a `text/event-stream` whose first event is the response to the call, followed
+//! by an event for each notification of the subscription. The subscription is closed along with
+//! the stream, there is no need to unsubscribe.
+//!
+//! The calls go through the same RPC middleware as the calls made over HTTP and WebSocket.
+
+use std::{convert::Infallible, error::Error as StdError, sync::Arc};
+
+use futures::{
+	future::{self, BoxFuture, FutureExt},
+	stream::{self, StreamExt},
+};
+use http::{header, Method, StatusCode};
+use hyper::{body::HttpBody, Body, Request, Response};
+use jsonrpsee::{
+	core::traits::IdProvider,
+	server::middleware::rpc::RpcServiceT,
+	types::{error::ErrorCode, ErrorObject, Id, Request as RpcRequest},
+	BoundedSubscriptions, MethodCallback, MethodResponse, MethodSink, Methods, SubscriptionState,
+};
+use tokio::sync::mpsc;
+
+use crate::ws_compression::MessageLimits;
+
+/// Path of the SSE endpoint.
+pub const SSE_PATH: &str = "/sse";
+
+/// Server-Sent Events transport configuration.
+#[derive(Debug, Copy, Clone)]
+pub struct SseConfig {
+	/// Number of messages buffered for a stream whose client doesn't keep up with the
+	/// notifications.
+	pub buffer_capacity: usize,
+}
+
+impl Default for SseConfig {
+	fn default() -> Self {
+		Self { buffer_capacity: 64 }
+	}
+}
+
+/// The RPC middleware around an [`SseService`], with its type erased.
+pub(crate) trait RpcCall: Send + Sync {
+	fn call<'a>(&'a self, req: RpcRequest<'a>) -> BoxFuture<'a, MethodResponse>;
+}
+
+impl<S> RpcCall for S
+where
+	S: for<'a> RpcServiceT<'a> + Send + Sync,
+{
+	fn call<'a>(&'a self, req: RpcRequest<'a>) -> BoxFuture<'a, MethodResponse> {
+		RpcServiceT::call(self, req).boxed()
+	}
+}
+
+/// Whether the request is a call to the SSE endpoint.
+pub(crate) fn is_sse_request(req: &Request<Body>) -> bool {
+	req.method() == Method::POST && req.uri().path() == SSE_PATH
+}
+
+/// JSON-RPC service serving the subscription of a stream.
+#[derive(Clone)]
+pub(crate) struct SseService {
+	methods: Methods,
+	sink: MethodSink,
+	id_provider: Arc<dyn IdProvider>,
+	conn_id: usize,
+	// A stream carries a single subscription.
+	subscriptions: BoundedSubscriptions,
+}
+
+impl SseService {
+	pub(crate) fn new(
+		methods: Methods,
+		sink: MethodSink,
+		id_provider: Arc<dyn IdProvider>,
+		conn_id: usize,
+	) -> Self {
+		Self { methods, sink, id_provider, conn_id, subscriptions: BoundedSubscriptions::new(1) }
+	}
+}
+
+impl<'a> RpcServiceT<'a> for SseService {
+	type Future = BoxFuture<'a, MethodResponse>;
+
+	fn call(&self, req: RpcRequest<'a>) -> Self::Future {
+		let id = req.id();
+		let callback = match self.methods.method_with_name(req.method_name()) {
+			Some((_, MethodCallback::Subscription(callback))) => callback,
+			Some(_) => {
+				let error = ErrorObject::owned(
+					ErrorCode::InvalidRequest.code(),
+					"Only subscriptions are served over SSE",
+					None::<()>,
+				);
+				return future::ready(MethodResponse::error(id, error)).boxed()
+			},
+			None => {
+				let error = ErrorObject::from(ErrorCode::MethodNotFound);
+				return future::ready(MethodResponse::error(id, error)).boxed()
+			},
+		};
+
+		let Some(subscription_permit) = self.subscriptions.acquire() else {
+			let error = ErrorObject::owned(
+				ErrorCode::InvalidRequest.code(),
+				"A stream carries a single subscription",
+				None::<()>,
+			);
+			return future::ready(MethodResponse::error(id, error)).boxed()
+		};
+		let state = SubscriptionState {
+			conn_id: self.conn_id,
+			id_provider: &*self.id_provider,
+			subscription_permit,
+		};
+		callback(id, req.params(), self.sink.clone(), state)
+	}
+}
+
+/// The sink of the messages of a stream, and the buffer they are read from.
+pub(crate) fn channel(
+	config: SseConfig,
+	limits: MessageLimits,
+) -> (MethodSink, mpsc::Receiver<String>) {
+	let (tx, rx) = mpsc::channel(config.buffer_capacity);
+	(MethodSink::new_with_limit(tx, limits.response as u32), rx)
+}
+
+/// Serve a call to the SSE endpoint with the RPC middleware built around the [`SseService`]
+/// sending the messages of the stream to `rx`.
+pub(crate) async fn call(
+	req: Request<Body>,
+	service: Box<dyn RpcCall>,
+	mut rx: mpsc::Receiver<String>,
+	limits: MessageLimits,
+) -> Result<Response<Body>, Box<dyn StdError + Send + Sync>> {
+	let Some(body) = read_body(req.into_body(), limits.request).await? else {
+		return Ok(Response::builder().status(StatusCode::PAYLOAD_TOO_LARGE).body(Body::empty())?)
+	};
+
+	let rp = match serde_json::from_slice::<RpcRequest>(&body) {
+		Ok(request) => service.call(request).await,
+		Err(_) => MethodResponse::error(Id::Null, ErrorObject::from(ErrorCode::ParseError)),
+	};
+	// The stream ends once the subscription drops the other senders.
+	drop(service);
+
+	// The subscriptions send their response along with their notifications, the responses of
+	// the calls rejected beforehand are sent here.
+	let messages = if rp.is_subscription() {
+		stream::poll_fn(move |cx| rx.poll_recv(cx)).boxed()
+	} else {
+		stream::once(future::ready(rp.into_result())).boxed()
+	};
+	let events = messages.map(|message| Ok::<_, Infallible>(format!("data: {message}\n\n")));
+
+	Ok(Response::builder()
+		.header(header::CONTENT_TYPE, "text/event-stream")
+		.header(header::CACHE_CONTROL, "no-cache")
+		.body(Body::wrap_stream(events))?)
+}
+
+/// The body of the request, `None` if larger than `max_size`.
+async fn read_body(mut body: Body, max_size: usize) -> Result<Option<Vec<u8>>, hyper::Error> {
+	let mut bytes = Vec::new();
+	while let Some(chunk) = body.data().await {
+		let chunk = chunk?;
+		if bytes.len() + chunk.len() > max_size {
+			return Ok(None)
+		}
+		bytes.extend_from_slice(&chunk);
+	}
+	Ok(Some(bytes))
+}
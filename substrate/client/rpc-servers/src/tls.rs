@@ -0,0 +1,344 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! TLS termination for the RPC server.
+
+use std::{
+	collections::HashMap,
+	fs::File,
+	io::{self, BufReader},
+	net::SocketAddr,
+	path::{Path, PathBuf},
+	pin::Pin,
+	sync::{Arc, RwLock},
+	task::{Context, Poll},
+	time::{Duration, SystemTime},
+};
+
+use futures::{Stream, StreamExt};
+use hyper::server::{
+	accept::{self, Accept},
+	conn::{AddrIncoming, AddrStream},
+};
+use tokio::{
+	io::{AsyncRead, AsyncWrite, ReadBuf},
+	net::TcpListener,
+	sync::mpsc,
+};
+use tokio_rustls::{
+	rustls::{
+		server::{ClientHello, ResolvesServerCert},
+		sign::CertifiedKey,
+		ServerConfig,
+	},
+	server::TlsStream,
+	TlsAcceptor,
+};
+
+use crate::socket::{self, SocketConfig};
+
+/// Maximum time a client may take to complete the TLS handshake.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+/// Maximum number of established connections waiting to be picked up by the server.
+const ACCEPT_QUEUE_SIZE: usize = 128;
+
+/// TLS configuration.
+#[derive(Debug, Clone)]
+pub struct TlsConfig {
+	/// Certificates served by the RPC server.
+	///
+	/// The first certificate is used when the client doesn't indicate a server name (SNI) or
+	/// when no other certificate matches the requested name.
+	pub certificates: Vec<TlsCertificate>,
+	/// How often to check the certificate files for changes and reload them.
+	///
+	/// `None` disables reloading.
+	pub reload_interval: Option<Duration>,
+}
+
+/// A certificate chain together with its private key.
+#[derive(Debug, Clone)]
+pub struct TlsCertificate {
+	/// Path to the PEM-encoded certificate chain.
+	pub cert_path: PathBuf,
+	/// Path to the PEM-encoded private key.
+	pub key_path: PathBuf,
+	/// Server names this certificate is selected for.
+	///
+	/// A leading `*.` matches exactly one label, e.g. `*.example.com` matches
+	/// `rpc.example.com` but not `example.com`.
+	pub server_names: Vec<String>,
+}
+
+/// Resolved certificates, keyed by server name.
+#[derive(Debug)]
+struct Certificates {
+	default: Arc<CertifiedKey>,
+	by_name: HashMap<String, Arc<CertifiedKey>>,
+}
+
+impl Certificates {
+	fn load(config: &[TlsCertificate]) -> io::Result<Self> {
+		let mut default = None;
+		let mut by_name = HashMap::new();
+
+		for cert in config {
+			let key = Arc::new(load_certified_key(&cert.cert_path, &cert.key_path)?);
+			for name in &cert.server_names {
+				by_name.insert(name.to_ascii_lowercase(), key.clone());
+			}
+			default.get_or_insert(key);
+		}
+
+		let default = default.ok_or_else(|| invalid_data("No TLS certificate configured"))?;
+		Ok(Self { default, by_name })
+	}
+
+	fn resolve(&self, server_name: Option<&str>) -> Arc<CertifiedKey> {
+		let Some(name) = server_name.map(|n| n.to_ascii_lowercase()) else {
+			return self.default.clone()
+		};
+
+		if let Some(key) = self.by_name.get(&name) {
+			return key.clone()
+		}
+
+		name.split_once('.')
+			.and_then(|(_, parent)| self.by_name.get(&format!("*.{parent}")))
+			.unwrap_or(&self.default)
+			.clone()
+	}
+}
+
+/// Certificate resolver selecting the certificate based on SNI.
+#[derive(Debug)]
+struct CertResolver(RwLock<Certificates>);
+
+impl ResolvesServerCert for CertResolver {
+	fn resolve(&self, client_hello: ClientHello) -> Option<Arc<CertifiedKey>> {
+		let certs = self.0.read().expect("Lock poisoned; qed");
+		Some(certs.resolve(client_hello.server_name()))
+	}
+}
+
+/// Build a TLS acceptor from the configuration.
+///
+/// If a reload interval is configured, a task watching the certificate files is spawned.
+/// HTTP/2 is offered to clients via ALPN if `http2` is set.
+pub(crate) fn acceptor(config: TlsConfig, http2: bool) -> io::Result<TlsAcceptor> {
+	let resolver = Arc::new(CertResolver(RwLock::new(Certificates::load(&config.certificates)?)));
+
+	let mut server_config = ServerConfig::builder()
+		.with_no_client_auth()
+		.with_cert_resolver(resolver.clone());
+	server_config.alpn_protocols =
+		if http2 { vec![b"h2".to_vec(), b"http/1.1".to_vec()] } else { vec![b"http/1.1".to_vec()] };
+
+	if let Some(interval) = config.reload_interval {
+		tokio::spawn(reload(config.certificates, interval, Arc::downgrade(&resolver)));
+	}
+
+	Ok(TlsAcceptor::from(Arc::new(server_config)))
+}
+
+/// Reload the certificates whenever one of the files changes.
+///
+/// Stops once the resolver has been dropped, i.e. the server was stopped.
+async fn reload(
+	certificates: Vec<TlsCertificate>,
+	interval: Duration,
+	resolver: std::sync::Weak<CertResolver>,
+) {
+	let modified = |certificates: &[TlsCertificate]| -> Vec<Option<SystemTime>> {
+		certificates
+			.iter()
+			.flat_map(|c| [&c.cert_path, &c.key_path])
+			.map(|p| std::fs::metadata(p).and_then(|m| m.modified()).ok())
+			.collect()
+	};
+
+	let mut last_modified = modified(&certificates);
+	let mut interval = tokio::time::interval(interval);
+	interval.tick().await;
+
+	loop {
+		interval.tick().await;
+
+		let Some(resolver) = resolver.upgrade() else { return };
+
+		let now_modified = modified(&certificates);
+		if now_modified == last_modified {
+			continue
+		}
+
+		match Certificates::load(&certificates) {
+			Ok(certs) => {
+				*resolver.0.write().expect("Lock poisoned; qed") = certs;
+				last_modified = now_modified;
+				log::info!("Reloaded JSON-RPC server TLS certificates");
+			},
+			Err(e) => {
+				// The files may be in the middle of being replaced, retry on the next tick.
+				log::warn!("Failed to reload JSON-RPC server TLS certificates: {e}");
+			},
+		}
+	}
+}
+
+fn load_certified_key(cert_path: &Path, key_path: &Path) -> io::Result<CertifiedKey> {
+	let certs = rustls_pemfile::certs(&mut BufReader::new(File::open(cert_path)?))
+		.collect::<Result<Vec<_>, _>>()?;
+	if certs.is_empty() {
+		return Err(invalid_data(format!("No certificate found in {}", cert_path.display())))
+	}
+
+	let key = rustls_pemfile::private_key(&mut BufReader::new(File::open(key_path)?))?
+		.ok_or_else(|| invalid_data(format!("No private key found in {}", key_path.display())))?;
+	let key = tokio_rustls::rustls::crypto::ring::sign::any_supported_type(&key)
+		.map_err(|e| invalid_data(format!("Invalid private key {}: {e}", key_path.display())))?;
+
+	Ok(CertifiedKey::new(certs, key))
+}
+
+fn invalid_data(msg: impl Into<String>) -> io::Error {
+	io::Error::new(io::ErrorKind::InvalidData, msg.into())
+}
+
+/// A plain or TLS-encrypted connection.
+pub(crate) enum MaybeTlsStream {
+	/// Plain TCP.
+	Plain(AddrStream),
+	/// TLS over TCP.
+	Tls(Box<TlsStream<AddrStream>>),
+}
+
+impl MaybeTlsStream {
+	/// The address of the peer.
+	pub(crate) fn remote_addr(&self) -> SocketAddr {
+		match self {
+			Self::Plain(stream) => stream.remote_addr(),
+			Self::Tls(stream) => stream.get_ref().0.remote_addr(),
+		}
+	}
+}
+
+impl AsyncRead for MaybeTlsStream {
+	fn poll_read(
+		self: Pin<&mut Self>,
+		cx: &mut Context<'_>,
+		buf: &mut ReadBuf<'_>,
+	) -> Poll<io::Result<()>> {
+		match self.get_mut() {
+			Self::Plain(stream) => Pin::new(stream).poll_read(cx, buf),
+			Self::Tls(stream) => Pin::new(stream).poll_read(cx, buf),
+		}
+	}
+}
+
+impl AsyncWrite for MaybeTlsStream {
+	fn poll_write(
+		self: Pin<&mut Self>,
+		cx: &mut Context<'_>,
+		buf: &[u8],
+	) -> Poll<io::Result<usize>> {
+		match self.get_mut() {
+			Self::Plain(stream) => Pin::new(stream).poll_write(cx, buf),
+			Self::Tls(stream) => Pin::new(stream).poll_write(cx, buf),
+		}
+	}
+
+	fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+		match self.get_mut() {
+			Self::Plain(stream) => Pin::new(stream).poll_flush(cx),
+			Self::Tls(stream) => Pin::new(stream).poll_flush(cx),
+		}
+	}
+
+	fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+		match self.get_mut() {
+			Self::Plain(stream) => Pin::new(stream).poll_shutdown(cx),
+			Self::Tls(stream) => Pin::new(stream).poll_shutdown(cx),
+		}
+	}
+}
+
+/// Accept connections on `listener`, performing the TLS handshake if an acceptor is given.
+pub(crate) fn incoming(
+	listener: TcpListener,
+	acceptor: Option<TlsAcceptor>,
+	socket_config: &SocketConfig,
+) -> Result<impl Accept<Conn = MaybeTlsStream, Error = io::Error>, hyper::Error> {
+	let mut incoming = AddrIncoming::from_listener(listener)?;
+	socket::configure(&mut incoming, socket_config);
+	let plain = futures::stream::poll_fn(move |cx| Pin::new(&mut incoming).poll_accept(cx));
+
+	let Some(acceptor) = acceptor else {
+		let stream: Pin<Box<dyn Stream<Item = _> + Send>> =
+			Box::pin(plain.map(|conn| conn.map(MaybeTlsStream::Plain)));
+		return Ok(accept::from_stream(stream))
+	};
+
+	// The handshakes are performed in separate tasks such that a slow client
+	// can't hold up accepting other connections.
+	let (tx, mut rx) = mpsc::channel(ACCEPT_QUEUE_SIZE);
+	tokio::spawn(async move {
+		let mut plain = std::pin::pin!(plain);
+
+		loop {
+			let conn = tokio::select! {
+				conn = plain.next() => conn,
+				// The server was stopped.
+				_ = tx.closed() => return,
+			};
+
+			match conn {
+				Some(Ok(stream)) => {
+					let acceptor = acceptor.clone();
+					let tx = tx.clone();
+
+					tokio::spawn(async move {
+						let remote_addr = stream.remote_addr();
+						match tokio::time::timeout(HANDSHAKE_TIMEOUT, acceptor.accept(stream)).await
+						{
+							Ok(Ok(stream)) => {
+								let _ = tx.send(Ok(MaybeTlsStream::Tls(Box::new(stream)))).await;
+							},
+							Ok(Err(e)) => log::debug!(
+								target: "rpc",
+								"TLS handshake with {remote_addr} failed: {e}"
+							),
+							Err(_) => log::debug!(
+								target: "rpc",
+								"TLS handshake with {remote_addr} timed out"
+							),
+						}
+					});
+				},
+				Some(Err(e)) =>
+					if tx.send(Err(e)).await.is_err() {
+						return
+					},
+				None => return,
+			}
+		}
+	});
+
+	let stream: Pin<Box<dyn Stream<Item = _> + Send>> =
+		Box::pin(futures::stream::poll_fn(move |cx| rx.poll_recv(cx)));
+	Ok(accept::from_stream(stream))
+}
@@ -0,0 +1,240 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! WebSocket `permessage-deflate` compression (RFC 7692).
+//!
+//! `jsonrpsee` doesn't support WebSocket extensions, so compressed connections are terminated
+//! here and relayed, message by message, to a plain WebSocket connection served by `jsonrpsee`
+//! over an in-memory stream.
+
+use std::{error::Error as StdError, mem};
+
+use futures::io::{AsyncRead, AsyncWrite, BufReader, BufWriter};
+use hyper::{Body, Request, Response, StatusCode};
+use soketto::{
+	base::Header,
+	connection::{Error as ConnectionError, Receiver, Sender},
+	extension::{deflate, Extension, Param},
+	handshake::{
+		client::{Client, Header as ClientHeader, ServerResponse},
+		http::Server,
+	},
+	BoxedError, Data, Mode, Storage,
+};
+use tokio_util::compat::TokioAsyncReadCompatExt;
+use tower::Service;
+
+/// Size of the in-memory stream between the relay and `jsonrpsee`.
+const RELAY_BUFFER_SIZE: usize = 64 * 1024;
+/// Name of the WebSocket extensions header.
+const SEC_WEBSOCKET_EXTENSIONS: &str = "sec-websocket-extensions";
+
+/// WebSocket compression configuration.
+#[derive(Debug, Copy, Clone)]
+pub struct WsCompressionConfig {
+	/// Messages smaller than this number of bytes are sent uncompressed.
+	pub min_size: usize,
+}
+
+impl Default for WsCompressionConfig {
+	fn default() -> Self {
+		Self { min_size: 1024 }
+	}
+}
+
+/// Maximum message sizes of a relayed connection.
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct MessageLimits {
+	/// Maximum size of a message received from the client.
+	pub request: usize,
+	/// Maximum size of a message sent to the client.
+	pub response: usize,
+}
+
+/// `permessage-deflate` extension skipping messages below a size threshold.
+#[derive(Debug)]
+struct Deflate {
+	inner: deflate::Deflate,
+	min_size: usize,
+}
+
+impl Extension for Deflate {
+	fn is_enabled(&self) -> bool {
+		self.inner.is_enabled()
+	}
+
+	fn name(&self) -> &str {
+		self.inner.name()
+	}
+
+	fn params(&self) -> &[Param<'_>] {
+		self.inner.params()
+	}
+
+	fn configure(&mut self, params: &[Param<'_>]) -> Result<(), BoxedError> {
+		self.inner.configure(params)
+	}
+
+	fn encode(&mut self, header: &mut Header, data: &mut Storage) -> Result<(), BoxedError> {
+		// Every message is compressed independently, so leaving some of them uncompressed
+		// (i.e. without the RSV1 bit set) is allowed.
+		if data.as_ref().len() < self.min_size {
+			return Ok(())
+		}
+		self.inner.encode(header, data)
+	}
+
+	fn decode(&mut self, header: &mut Header, data: &mut Vec<u8>) -> Result<(), BoxedError> {
+		self.inner.decode(header, data)
+	}
+
+	fn reserved_bits(&self) -> (bool, bool, bool) {
+		self.inner.reserved_bits()
+	}
+}
+
+/// Serve a WebSocket upgrade request, compressing the connection if the client supports it.
+///
+/// Requests not negotiating compression are passed to `svc` untouched.
+pub(crate) async fn call<S>(
+	mut svc: S,
+	req: Request<Body>,
+	config: WsCompressionConfig,
+	limits: MessageLimits,
+) -> Result<Response<Body>, Box<dyn StdError + Send + Sync>>
+where
+	S: Service<Request<Body>, Response = Response<Body>> + Send + 'static,
+	S::Error: Into<Box<dyn StdError + Send + Sync>>,
+	S::Future: Send,
+{
+	let mut server = Server::new();
+	server.add_extension(Box::new(Deflate {
+		inner: deflate::Deflate::new(Mode::Server),
+		min_size: config.min_size,
+	}));
+
+	let negotiated = match server.receive_request(&req) {
+		Ok(response)
+			if response
+				.headers()
+				.get(SEC_WEBSOCKET_EXTENSIONS)
+				.map_or(false, |value| !value.is_empty()) =>
+			response,
+		// Let `jsonrpsee` deal with the request and any error in it.
+		_ => return svc.call(req).await.map_err(Into::into),
+	};
+
+	let (relay_io, server_io) = tokio::io::duplex(RELAY_BUFFER_SIZE);
+	tokio::spawn(async move {
+		if let Err(e) = hyper::server::conn::Http::new()
+			.serve_connection(server_io, svc)
+			.with_upgrades()
+			.await
+		{
+			log::debug!(target: "rpc", "Relayed WebSocket connection failed: {e}");
+		}
+	});
+
+	// Forward the handshake to `jsonrpsee` such that the HTTP middleware applies to it.
+	let host = req
+		.headers()
+		.get(hyper::header::HOST)
+		.and_then(|host| host.to_str().ok())
+		.unwrap_or("localhost");
+	let resource = req.uri().path_and_query().map_or("/", |p| p.as_str());
+	let headers = req
+		.headers()
+		.iter()
+		.filter(|(name, _)| !is_handshake_header(name.as_str()))
+		.map(|(name, value)| ClientHeader { name: name.as_str(), value: value.as_bytes() })
+		.collect::<Vec<_>>();
+
+	let mut client = Client::new(BufReader::new(BufWriter::new(relay_io.compat())), host, resource);
+	client.set_headers(&headers);
+	match client.handshake().await? {
+		ServerResponse::Accepted { .. } => (),
+		ServerResponse::Redirect { status_code, .. } | ServerResponse::Rejected { status_code } => {
+			let status = StatusCode::from_u16(status_code).unwrap_or(StatusCode::BAD_REQUEST);
+			return Ok(Response::builder().status(status).body(Body::empty())?)
+		},
+	}
+	let mut builder = client.into_builder();
+	builder.set_max_message_size(limits.response);
+	let (relay_tx, relay_rx) = builder.finish();
+
+	tokio::spawn(async move {
+		let upgraded = match hyper::upgrade::on(req).await {
+			Ok(upgraded) => upgraded,
+			Err(e) => {
+				log::debug!(target: "rpc", "WS upgrade handshake failed: {e}");
+				return
+			},
+		};
+
+		let mut builder = server.into_builder(BufReader::new(BufWriter::new(upgraded.compat())));
+		builder.set_max_message_size(limits.request);
+		let (client_tx, client_rx) = builder.finish();
+
+		// Once either side is closed, the other one is dropped and thereby closed as well.
+		futures::future::select(
+			Box::pin(relay(client_rx, relay_tx)),
+			Box::pin(relay(relay_rx, client_tx)),
+		)
+		.await;
+	});
+
+	Ok(negotiated.map(|()| Body::empty()))
+}
+
+/// Headers set by the WebSocket handshake itself.
+fn is_handshake_header(name: &str) -> bool {
+	matches!(name, "host" | "connection" | "upgrade") || name.starts_with("sec-websocket-")
+}
+
+/// Relay the data messages received by `from` to `to` until either side is closed.
+///
+/// Pings are answered by each side independently.
+async fn relay<R, W>(mut from: Receiver<R>, mut to: Sender<W>)
+where
+	R: AsyncRead + AsyncWrite + Unpin,
+	W: AsyncRead + AsyncWrite + Unpin,
+{
+	let mut message = Vec::new();
+
+	loop {
+		let sent = match from.receive_data(&mut message).await {
+			Ok(Data::Text(_)) => match String::from_utf8(mem::take(&mut message)) {
+				Ok(text) => to.send_text_owned(text).await,
+				Err(_) => break,
+			},
+			Ok(Data::Binary(_)) => to.send_binary_mut(&mut message).await,
+			Err(ConnectionError::Closed) => break,
+			Err(e) => {
+				log::debug!(target: "rpc", "Relayed WebSocket connection failed: {e}");
+				break
+			},
+		};
+		message.clear();
+
+		if sent.is_err() || to.flush().await.is_err() {
+			return
+		}
+	}
+
+	let _ = to.close().await;
+}
@@ -19,7 +19,10 @@
 //! API trait of the archive methods.
 
 use crate::{
-	common::events::{ArchiveStorageResult, PaginatedStorageQuery},
+	common::events::{
+		ArchiveFinalizedBlock, ArchiveStateStreamEvent, ArchiveStorageDiffEvent,
+		ArchiveStoragePrefixEvent, ArchiveStorageResult, PaginatedStorageQuery,
+	},
 	MethodResult,
 };
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
@@ -104,4 +107,88 @@ pub trait ArchiveApi<Hash> {
 		items: Vec<PaginatedStorageQuery<String>>,
 		child_trie: Option<String>,
 	) -> RpcResult<ArchiveStorageResult>;
+
+	/// Streams the descendants of a key at a specific block's state.
+	///
+	/// The query must be a descendants query. The descendants are reported in chunks, the next
+	/// chunk being read only once the previous one was sent to the client. The iteration resumes
+	/// after the `paginationStartKey` of the query if provided.
+	///
+	/// # Unstable
+	///
+	/// This method is unstable and subject to change in the future.
+	#[subscription(
+		name = "archive_unstable_storagePrefix" => "archive_unstable_storagePrefixEvent",
+		unsubscribe = "archive_unstable_stopStoragePrefix",
+		item = ArchiveStoragePrefixEvent,
+	)]
+	fn archive_unstable_storage_prefix(
+		&self,
+		hash: Hash,
+		item: PaginatedStorageQuery<String>,
+		child_trie: Option<String>,
+	);
+
+	/// Streams the keys changed between two blocks under the given prefixes.
+	///
+	/// Each change reports the key, its type (`added`, `modified` or `deleted`) and the value at
+	/// `hash` for added and modified keys. The whole state is compared if no prefix is provided.
+	/// The changes are reported in chunks, the next chunk being read only once the previous one
+	/// was sent to the client.
+	///
+	/// # Unstable
+	///
+	/// This method is unstable and subject to change in the future.
+	#[subscription(
+		name = "archive_unstable_storageDiff" => "archive_unstable_storageDiffEvent",
+		unsubscribe = "archive_unstable_stopStorageDiff",
+		item = ArchiveStorageDiffEvent,
+	)]
+	fn archive_unstable_storage_diff(
+		&self,
+		hash: Hash,
+		previous_hash: Hash,
+		prefixes: Vec<String>,
+		child_trie: Option<String>,
+	);
+
+	/// Streams the key/value pairs of the state of a finalized block, optionally restricted to
+	/// the keys under `prefix`.
+	///
+	/// The pairs are reported in chunks ordered by key, the next chunk being read only once the
+	/// previous one was sent to the client. Each chunk but the last one carries a `cursor`; the
+	/// iteration can be resumed after that chunk by calling the method again with the same block,
+	/// prefix and cursor.
+	///
+	/// # Unstable
+	///
+	/// This method is unstable and subject to change in the future.
+	#[subscription(
+		name = "archive_unstable_stateStream" => "archive_unstable_stateStreamEvent",
+		unsubscribe = "archive_unstable_stopStateStream",
+		item = ArchiveStateStreamEvent,
+	)]
+	fn archive_unstable_state_stream(
+		&self,
+		hash: Hash,
+		prefix: Option<String>,
+		cursor: Option<String>,
+	);
+
+	/// Follows the finalized blocks of the chain.
+	///
+	/// The current finalized block is reported first, followed by every newly finalized block in
+	/// order of height, including the blocks implicitly finalized by the finalization of a
+	/// descendant. Unlike `chainHead_follow`, the blocks are not pinned and no operation can be
+	/// performed on them.
+	///
+	/// # Unstable
+	///
+	/// This method is unstable and subject to change in the future.
+	#[subscription(
+		name = "archive_unstable_finalizedHeightFollow" => "archive_unstable_finalizedHeightEvent",
+		unsubscribe = "archive_unstable_stopFinalizedHeightFollow",
+		item = ArchiveFinalizedBlock<Hash>,
+	)]
+	fn archive_unstable_finalized_height_follow(&self);
 }
@@ -20,12 +20,21 @@
 
 use crate::{
 	archive::{error::Error as ArchiveError, ArchiveApiServer},
-	common::events::{ArchiveStorageResult, PaginatedStorageQuery},
-	hex_string, MethodResult,
+	common::{
+		events::{
+			ArchiveFinalizedBlock, ArchiveStorageResult, PaginatedStorageQuery, StorageQueryType,
+		},
+		storage::{IterQueryType, QueryIter},
+	},
+	hex_string, MethodResult, SubscriptionTaskExecutor,
 };
 
 use codec::Encode;
-use jsonrpsee::core::{async_trait, RpcResult};
+use futures::{future, stream, FutureExt, StreamExt};
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	PendingSubscriptionSink,
+};
 use sc_client_api::{
 	Backend, BlockBackend, BlockchainEvents, CallExecutor, ChildInfo, ExecutorProvider, StorageKey,
 	StorageProvider,
@@ -44,12 +53,19 @@ use std::{collections::HashSet, marker::PhantomData, sync::Arc};
 use super::archive_storage::ArchiveStorage;
 
 /// The configuration of [`Archive`].
+#[derive(Debug, Clone)]
 pub struct ArchiveConfig {
 	/// The maximum number of items the `archive_storage` can return for a descendant query before
 	/// pagination is required.
 	pub max_descendant_responses: usize,
 	/// The maximum number of queried items allowed for the `archive_storage` at a time.
 	pub max_queried_items: usize,
+	/// The maximum number of items reported by an event of the `archive_storagePrefix`
+	/// subscription.
+	pub max_prefix_chunk_size: usize,
+	/// The maximum number of items reported by an event of the `archive_stateStream`
+	/// subscription.
+	pub max_state_chunk_size: usize,
 }
 
 /// The maximum number of items the `archive_storage` can return for a descendant query before
@@ -64,11 +80,19 @@ const MAX_DESCENDANT_RESPONSES: usize = 5;
 /// `MAX_DESCENDANT_RESPONSES`.
 const MAX_QUERIED_ITEMS: usize = 8;
 
+/// The maximum number of items reported by an event of the `archive_storagePrefix` subscription.
+const MAX_PREFIX_CHUNK_SIZE: usize = 256;
+
+/// The maximum number of items reported by an event of the `archive_stateStream` subscription.
+const MAX_STATE_CHUNK_SIZE: usize = 1024;
+
 impl Default for ArchiveConfig {
 	fn default() -> Self {
 		Self {
 			max_descendant_responses: MAX_DESCENDANT_RESPONSES,
 			max_queried_items: MAX_QUERIED_ITEMS,
+			max_prefix_chunk_size: MAX_PREFIX_CHUNK_SIZE,
+			max_state_chunk_size: MAX_STATE_CHUNK_SIZE,
 		}
 	}
 }
@@ -79,6 +103,8 @@ pub struct Archive<BE: Backend<Block>, Block: BlockT, Client> {
 	client: Arc<Client>,
 	/// Backend of the chain.
 	backend: Arc<BE>,
+	/// Executor to spawn subscriptions.
+	executor: SubscriptionTaskExecutor,
 	/// The hexadecimal encoded hash of the genesis block.
 	genesis_hash: String,
 	/// The maximum number of items the `archive_storage` can return for a descendant query before
@@ -86,6 +112,12 @@ pub struct Archive<BE: Backend<Block>, Block: BlockT, Client> {
 	storage_max_descendant_responses: usize,
 	/// The maximum number of queried items allowed for the `archive_storage` at a time.
 	storage_max_queried_items: usize,
+	/// The maximum number of items reported by an event of the `archive_storagePrefix`
+	/// subscription.
+	storage_max_prefix_chunk_size: usize,
+	/// The maximum number of items reported by an event of the `archive_stateStream`
+	/// subscription.
+	storage_max_state_chunk_size: usize,
 	/// Phantom member to pin the block type.
 	_phantom: PhantomData<Block>,
 }
@@ -95,6 +127,7 @@ impl<BE: Backend<Block>, Block: BlockT, Client> Archive<BE, Block, Client> {
 	pub fn new<GenesisHash: AsRef<[u8]>>(
 		client: Arc<Client>,
 		backend: Arc<BE>,
+		executor: SubscriptionTaskExecutor,
 		genesis_hash: GenesisHash,
 		config: ArchiveConfig,
 	) -> Self {
@@ -102,9 +135,12 @@ impl<BE: Backend<Block>, Block: BlockT, Client> Archive<BE, Block, Client> {
 		Self {
 			client,
 			backend,
+			executor,
 			genesis_hash,
 			storage_max_descendant_responses: config.max_descendant_responses,
 			storage_max_queried_items: config.max_queried_items,
+			storage_max_prefix_chunk_size: config.max_prefix_chunk_size,
+			storage_max_state_chunk_size: config.max_state_chunk_size,
 			_phantom: PhantomData,
 		}
 	}
@@ -277,4 +313,219 @@ where
 		);
 		Ok(storage_client.handle_query(hash, items, child_trie))
 	}
+
+	fn archive_unstable_storage_prefix(
+		&self,
+		pending: PendingSubscriptionSink,
+		hash: Block::Hash,
+		item: PaginatedStorageQuery<String>,
+		child_trie: Option<String>,
+	) {
+		let parse_query = || -> Result<_, ArchiveError> {
+			let ty = match item.query_type {
+				StorageQueryType::DescendantsValues => IterQueryType::Value,
+				StorageQueryType::DescendantsHashes => IterQueryType::Hash,
+				_ =>
+					return Err(ArchiveError::InvalidParam(
+						"Only descendants queries can be streamed".to_string(),
+					)),
+			};
+			let query = QueryIter {
+				query_key: StorageKey(parse_hex_param(item.key)?),
+				pagination_start_key: item
+					.pagination_start_key
+					.map(|key| parse_hex_param(key).map(StorageKey))
+					.transpose()?,
+				ty,
+			};
+			let child_trie = child_trie
+				.map(parse_hex_param)
+				.transpose()?
+				.map(ChildInfo::new_default_from_vec);
+
+			Ok((query, child_trie))
+		};
+		let query = parse_query();
+
+		let storage_client = ArchiveStorage::new(
+			self.client.clone(),
+			self.storage_max_prefix_chunk_size,
+			self.storage_max_queried_items,
+		);
+
+		let fut = async move {
+			let (query, child_trie) = match query {
+				Ok(query) => query,
+				Err(error) => {
+					pending.reject(error).await;
+					return
+				},
+			};
+			let Ok(sink) = pending.accept().await else { return };
+
+			storage_client.stream_descendants(hash, query, child_trie, sink).await;
+		};
+
+		// Storage is read while iterating.
+		self.executor
+			.spawn_blocking("substrate-rpc-subscription", Some("rpc"), fut.boxed());
+	}
+	fn archive_unstable_storage_diff(
+		&self,
+		pending: PendingSubscriptionSink,
+		hash: Block::Hash,
+		previous_hash: Block::Hash,
+		prefixes: Vec<String>,
+		child_trie: Option<String>,
+	) {
+		let parse_query = || -> Result<_, ArchiveError> {
+			let mut prefixes = prefixes
+				.into_iter()
+				.map(|prefix| parse_hex_param(prefix).map(StorageKey))
+				.collect::<Result<Vec<_>, _>>()?;
+			if prefixes.is_empty() {
+				prefixes.push(StorageKey(Vec::new()));
+			}
+
+			// Report the keys under overlapping prefixes only once.
+			prefixes.sort();
+			let mut disjoint_prefixes: Vec<StorageKey> = Vec::with_capacity(prefixes.len());
+			for prefix in prefixes {
+				if !disjoint_prefixes.last().map_or(false, |last| prefix.0.starts_with(&last.0)) {
+					disjoint_prefixes.push(prefix);
+				}
+			}
+
+			let child_trie = child_trie
+				.map(parse_hex_param)
+				.transpose()?
+				.map(ChildInfo::new_default_from_vec);
+
+			Ok((disjoint_prefixes, child_trie))
+		};
+		let query = parse_query();
+
+		let storage_client = ArchiveStorage::new(
+			self.client.clone(),
+			self.storage_max_prefix_chunk_size,
+			self.storage_max_queried_items,
+		);
+
+		let fut = async move {
+			let (prefixes, child_trie) = match query {
+				Ok(query) => query,
+				Err(error) => {
+					pending.reject(error).await;
+					return
+				},
+			};
+			let Ok(sink) = pending.accept().await else { return };
+
+			storage_client
+				.stream_diff(hash, previous_hash, prefixes, child_trie, sink)
+				.await;
+		};
+
+		// Storage is read while iterating.
+		self.executor
+			.spawn_blocking("substrate-rpc-subscription", Some("rpc"), fut.boxed());
+	}
+
+	fn archive_unstable_state_stream(
+		&self,
+		pending: PendingSubscriptionSink,
+		hash: Block::Hash,
+		prefix: Option<String>,
+		cursor: Option<String>,
+	) {
+		let parse_query = || -> Result<_, ArchiveError> {
+			// The state of the blocks which may still be pruned or reverted can't be exported.
+			let is_finalized = match self.client.number(hash) {
+				Ok(Some(number)) =>
+					number <= self.client.info().finalized_number &&
+						self.client.hash(number).ok().flatten() == Some(hash),
+				_ => false,
+			};
+			if !is_finalized {
+				return Err(ArchiveError::InvalidParam(format!("Block {hash:?} is not finalized")))
+			}
+
+			let prefix = StorageKey(prefix.map(parse_hex_param).transpose()?.unwrap_or_default());
+			let cursor = cursor.map(parse_hex_param).transpose()?.map(StorageKey);
+			if cursor.as_ref().map_or(false, |cursor| !cursor.0.starts_with(&prefix.0)) {
+				return Err(ArchiveError::InvalidParam(
+					"The cursor doesn't belong to the prefix".to_string(),
+				))
+			}
+
+			Ok(QueryIter {
+				query_key: prefix,
+				pagination_start_key: cursor,
+				ty: IterQueryType::Value,
+			})
+		};
+		let query = parse_query();
+
+		let storage_client = ArchiveStorage::new(
+			self.client.clone(),
+			self.storage_max_state_chunk_size,
+			self.storage_max_queried_items,
+		);
+
+		let fut = async move {
+			let query = match query {
+				Ok(query) => query,
+				Err(error) => {
+					pending.reject(error).await;
+					return
+				},
+			};
+			let Ok(sink) = pending.accept().await else { return };
+
+			storage_client.stream_state(hash, query, sink).await;
+		};
+
+		// Storage is read while iterating.
+		self.executor
+			.spawn_blocking("substrate-rpc-subscription", Some("rpc"), fut.boxed());
+	}
+
+	fn archive_unstable_finalized_height_follow(&self, pending: PendingSubscriptionSink) {
+		// Subscribe before reading the current finalized block, such that no block is missed.
+		let notifications = self.client.finality_notification_stream();
+		let info = self.client.info();
+		let mut last_height: u64 = info.finalized_number.saturated_into();
+		let current = ArchiveFinalizedBlock { height: last_height, hash: info.finalized_hash };
+
+		// The notifications are dropped right away, which unpins their blocks.
+		let finalized = notifications
+			.flat_map(|notification| {
+				let height: u64 = (*notification.header.number()).saturated_into();
+				// The implicitly finalized blocks are the ancestors of the finalized block.
+				let first_height = height.saturating_sub(notification.tree_route.len() as u64);
+				let blocks: Vec<_> = notification
+					.tree_route
+					.iter()
+					.copied()
+					.chain(std::iter::once(notification.hash))
+					.zip(first_height..)
+					.map(|(hash, height)| ArchiveFinalizedBlock { height, hash })
+					.collect();
+				stream::iter(blocks)
+			})
+			.filter(move |block| {
+				// Skip the blocks finalized before the current one was read.
+				let is_new = block.height > last_height;
+				if is_new {
+					last_height = block.height;
+				}
+				future::ready(is_new)
+			});
+		let stream = stream::once(future::ready(current)).chain(finalized).boxed();
+
+		sc_rpc::utils::spawn_subscription_task(
+			&self.executor,
+			sc_rpc::utils::pipe_from_stream(pending, stream),
+		);
+	}
 }
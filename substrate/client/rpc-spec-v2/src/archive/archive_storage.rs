@@ -20,12 +20,22 @@
 
 use std::sync::Arc;
 
+use jsonrpsee::SubscriptionSink;
 use sc_client_api::{Backend, ChildInfo, StorageKey, StorageProvider};
+use sc_rpc::utils::to_sub_message;
 use sp_runtime::traits::Block as BlockT;
 
-use crate::common::{
-	events::{ArchiveStorageResult, PaginatedStorageQuery, StorageQueryType},
-	storage::{IterQueryType, QueryIter, Storage},
+use crate::{
+	common::{
+		events::{
+			ArchiveStateStreamEvent, ArchiveStateStreamItems, ArchiveStorageDiffEvent,
+			ArchiveStorageDiffItems, ArchiveStorageMethodErr, ArchiveStoragePrefixEvent,
+			ArchiveStoragePrefixItems, ArchiveStorageResult, PaginatedStorageQuery,
+			StorageQueryType,
+		},
+		storage::{IterQueryType, QueryIter, Storage},
+	},
+	hex_string,
 };
 
 /// Generates the events of the `archive_storage` method.
@@ -106,6 +116,10 @@ where
 						Err(error) => return ArchiveStorageResult::err(error),
 					}
 				},
+				StorageQueryType::Proof =>
+					return ArchiveStorageResult::err(
+						"Proof queries are only supported by `chainHead_storage`".into(),
+					),
 				StorageQueryType::DescendantsHashes => {
 					match self.client.query_iter_pagination(
 						QueryIter {
@@ -126,4 +140,136 @@ where
 
 		ArchiveStorageResult::ok(storage_results, discarded_items)
 	}
+
+	/// Report the descendants of the queried key to the `archive_storagePrefix` subscription.
+	///
+	/// At most `storage_max_descendant_responses` items are reported by each event and the next
+	/// ones are only read once the event was sent, such that slow clients don't make the results
+	/// pile up in memory.
+	pub async fn stream_descendants(
+		&self,
+		hash: Block::Hash,
+		query: QueryIter,
+		child_key: Option<ChildInfo>,
+		sink: SubscriptionSink,
+	) {
+		let mut next_query = Some(query);
+
+		while let Some(query) = next_query.take() {
+			let event = match self.client.query_iter_pagination(
+				query,
+				hash,
+				child_key.as_ref(),
+				self.storage_max_descendant_responses,
+			) {
+				Ok((items, next)) => {
+					next_query = next;
+					ArchiveStoragePrefixEvent::Items(ArchiveStoragePrefixItems { items })
+				},
+				Err(error) => ArchiveStoragePrefixEvent::Error(ArchiveStorageMethodErr { error }),
+			};
+
+			// The client unsubscribed or disconnected.
+			if sink.send(to_sub_message(&sink, &event)).await.is_err() {
+				return
+			}
+
+			if let ArchiveStoragePrefixEvent::Error(_) = event {
+				return
+			}
+		}
+
+		let _ = sink.send(to_sub_message(&sink, &ArchiveStoragePrefixEvent::Done)).await;
+	}
+
+	/// Report the keys changed between `previous_hash` and `hash` under the given prefixes to
+	/// the `archive_storageDiff` subscription.
+	///
+	/// Like [`Self::stream_descendants`], at most `storage_max_descendant_responses` changes are
+	/// reported by each event and the next ones are only read once the event was sent.
+	pub async fn stream_diff(
+		&self,
+		hash: Block::Hash,
+		previous_hash: Block::Hash,
+		prefixes: Vec<StorageKey>,
+		child_key: Option<ChildInfo>,
+		sink: SubscriptionSink,
+	) {
+		for prefix in prefixes {
+			let mut start_key = None;
+
+			loop {
+				let event = match self.client.query_diff_pagination(
+					hash,
+					previous_hash,
+					&prefix,
+					start_key.as_ref(),
+					child_key.as_ref(),
+					self.storage_max_descendant_responses,
+				) {
+					Ok((items, next)) => {
+						start_key = next;
+						ArchiveStorageDiffEvent::Items(ArchiveStorageDiffItems { items })
+					},
+					Err(error) => ArchiveStorageDiffEvent::Error(ArchiveStorageMethodErr { error }),
+				};
+
+				let is_empty = matches!(&event, ArchiveStorageDiffEvent::Items(chunk) if chunk.items.is_empty());
+				// The client unsubscribed or disconnected.
+				if !is_empty && sink.send(to_sub_message(&sink, &event)).await.is_err() {
+					return
+				}
+
+				if let ArchiveStorageDiffEvent::Error(_) = event {
+					return
+				}
+				if start_key.is_none() {
+					break
+				}
+			}
+		}
+
+		let _ = sink.send(to_sub_message(&sink, &ArchiveStorageDiffEvent::Done)).await;
+	}
+
+	/// Report the key/value pairs of the state under the queried key to the `archive_stateStream`
+	/// subscription.
+	///
+	/// Like [`Self::stream_descendants`], at most `storage_max_descendant_responses` pairs are
+	/// reported by each event and the next ones are only read once the event was sent. Each chunk
+	/// but the last one carries the last key it covers, such that the iteration can be resumed
+	/// after it.
+	pub async fn stream_state(&self, hash: Block::Hash, query: QueryIter, sink: SubscriptionSink) {
+		let mut next_query = Some(query);
+
+		while let Some(query) = next_query.take() {
+			let event = match self.client.query_iter_pagination(
+				query,
+				hash,
+				None,
+				self.storage_max_descendant_responses,
+			) {
+				Ok((items, next)) => {
+					let cursor = next
+						.as_ref()
+						.and_then(|next| next.pagination_start_key.as_ref())
+						.map(|key| hex_string(&key.0));
+					next_query = next;
+					ArchiveStateStreamEvent::Items(ArchiveStateStreamItems { items, cursor })
+				},
+				Err(error) => ArchiveStateStreamEvent::Error(ArchiveStorageMethodErr { error }),
+			};
+
+			// The client unsubscribed or disconnected.
+			if sink.send(to_sub_message(&sink, &event)).await.is_err() {
+				return
+			}
+
+			if let ArchiveStateStreamEvent::Error(_) = event {
+				return
+			}
+		}
+
+		let _ = sink.send(to_sub_message(&sink, &ArchiveStateStreamEvent::Done)).await;
+	}
 }
@@ -18,7 +18,10 @@
 
 use crate::{
 	common::events::{
-		ArchiveStorageMethodOk, ArchiveStorageResult, PaginatedStorageQuery, StorageQueryType,
+		ArchiveFinalizedBlock, ArchiveStateStreamEvent, ArchiveStateStreamItems,
+		ArchiveStorageDiffEvent, ArchiveStorageDiffItems, ArchiveStorageDiffResult,
+		ArchiveStorageDiffType, ArchiveStorageMethodOk, ArchiveStoragePrefixEvent,
+		ArchiveStoragePrefixItems, ArchiveStorageResult, PaginatedStorageQuery, StorageQueryType,
 		StorageResultType,
 	},
 	hex_string, MethodResult,
@@ -38,7 +41,7 @@ use sc_block_builder::BlockBuilderBuilder;
 use sc_client_api::ChildInfo;
 use sp_blockchain::HeaderBackend;
 use sp_consensus::BlockOrigin;
-use sp_core::{Blake2Hasher, Hasher};
+use sp_core::{testing::TaskExecutor, Blake2Hasher, Hasher};
 use sp_runtime::{
 	traits::{Block as BlockT, Header as HeaderT},
 	SaturatedConversion,
@@ -53,6 +56,8 @@ const CHAIN_GENESIS: [u8; 32] = [0; 32];
 const INVALID_HASH: [u8; 32] = [1; 32];
 const MAX_PAGINATION_LIMIT: usize = 5;
 const MAX_QUERIED_LIMIT: usize = 5;
+const MAX_PREFIX_CHUNK_SIZE: usize = 2;
+const MAX_STATE_CHUNK_SIZE: usize = 2;
 const KEY: &[u8] = b":mock";
 const VALUE: &[u8] = b"hello world";
 const CHILD_STORAGE_KEY: &[u8] = b"child";
@@ -77,8 +82,14 @@ fn setup_api(
 	let api = Archive::new(
 		client.clone(),
 		backend,
+		Arc::new(TaskExecutor::default()),
 		CHAIN_GENESIS,
-		ArchiveConfig { max_descendant_responses, max_queried_items },
+		ArchiveConfig {
+			max_descendant_responses,
+			max_queried_items,
+			max_prefix_chunk_size: MAX_PREFIX_CHUNK_SIZE,
+			max_state_chunk_size: MAX_STATE_CHUNK_SIZE,
+		},
 	)
 	.into_rpc();
 
@@ -174,6 +185,47 @@ async fn archive_finalized_height() {
 	assert_eq!(client_height, height);
 }
 
+#[tokio::test]
+async fn archive_finalized_height_follow() {
+	let (mut client, api) = setup_api(MAX_PAGINATION_LIMIT, MAX_QUERIED_LIMIT);
+
+	let mut sub = api
+		.subscribe_unbounded("archive_unstable_finalizedHeightFollow", EmptyParams::new())
+		.await
+		.unwrap();
+
+	// The current finalized block is reported first.
+	let (block, _) = sub.next::<ArchiveFinalizedBlock<String>>().await.unwrap().unwrap();
+	assert_eq!(
+		block,
+		ArchiveFinalizedBlock { height: 0, hash: format!("{:?}", client.genesis_hash()) }
+	);
+
+	// Import two blocks and finalize the second one only.
+	let mut hashes = Vec::new();
+	let mut parent_hash = client.chain_info().genesis_hash;
+	for parent_number in 0..2 {
+		let block = BlockBuilderBuilder::new(&*client)
+			.on_parent_block(parent_hash)
+			.with_parent_block_number(parent_number)
+			.build()
+			.unwrap()
+			.build()
+			.unwrap()
+			.block;
+		parent_hash = block.header.hash();
+		client.import(BlockOrigin::Own, block).await.unwrap();
+		hashes.push(parent_hash);
+	}
+	client.finalize_block(parent_hash, None).unwrap();
+
+	// The implicitly finalized block is reported as well.
+	for (height, hash) in (1..).zip(hashes) {
+		let (block, _) = sub.next::<ArchiveFinalizedBlock<String>>().await.unwrap().unwrap();
+		assert_eq!(block, ArchiveFinalizedBlock { height, hash: format!("{:?}", hash) });
+	}
+}
+
 #[tokio::test]
 async fn archive_hash_by_height() {
 	let (mut client, api) = setup_api(MAX_PAGINATION_LIMIT, MAX_QUERIED_LIMIT);
@@ -838,3 +890,255 @@ async fn archive_storage_discarded_items() {
 		_ => panic!("Unexpected result"),
 	};
 }
+
+#[tokio::test]
+async fn archive_storage_prefix_streams_chunks() {
+	let (mut client, api) = setup_api(MAX_PAGINATION_LIMIT, MAX_QUERIED_LIMIT);
+
+	// Import a new block with storage changes.
+	let mut builder = BlockBuilderBuilder::new(&*client)
+		.on_parent_block(client.chain_info().genesis_hash)
+		.with_parent_block_number(0)
+		.build()
+		.unwrap();
+	builder.push_storage_change(b":m".to_vec(), Some(b"a".to_vec())).unwrap();
+	builder.push_storage_change(b":mo".to_vec(), Some(b"ab".to_vec())).unwrap();
+	builder.push_storage_change(b":moc".to_vec(), Some(b"abc".to_vec())).unwrap();
+	builder.push_storage_change(b":moD".to_vec(), Some(b"abcmoD".to_vec())).unwrap();
+	builder.push_storage_change(b":mock".to_vec(), Some(b"abcd".to_vec())).unwrap();
+	let block = builder.build().unwrap().block;
+	let block_hash = format!("{:?}", block.header.hash());
+	client.import(BlockOrigin::Own, block.clone()).await.unwrap();
+
+	// Only descendants queries can be streamed.
+	let err = api
+		.subscribe_unbounded(
+			"archive_unstable_storagePrefix",
+			rpc_params![
+				&block_hash,
+				PaginatedStorageQuery {
+					key: hex_string(b":m"),
+					query_type: StorageQueryType::Value,
+					pagination_start_key: None,
+				}
+			],
+		)
+		.await
+		.unwrap_err();
+	assert_matches!(err, Error::JsonRpc(err) if err.code() == 3001);
+
+	let mut sub = api
+		.subscribe_unbounded(
+			"archive_unstable_storagePrefix",
+			rpc_params![
+				&block_hash,
+				PaginatedStorageQuery {
+					key: hex_string(b":m"),
+					query_type: StorageQueryType::DescendantsValues,
+					pagination_start_key: None,
+				}
+			],
+		)
+		.await
+		.unwrap();
+
+	let mut keys = Vec::new();
+	loop {
+		let (event, _) = sub.next::<ArchiveStoragePrefixEvent>().await.unwrap().unwrap();
+		match event {
+			ArchiveStoragePrefixEvent::Items(ArchiveStoragePrefixItems { items }) => {
+				assert!(!items.is_empty() && items.len() <= MAX_PREFIX_CHUNK_SIZE);
+				keys.extend(items.into_iter().map(|item| item.key));
+			},
+			ArchiveStoragePrefixEvent::Done => break,
+			ArchiveStoragePrefixEvent::Error(err) => panic!("Unexpected error {}", err.error),
+		}
+	}
+
+	let expected: Vec<_> = [&b":m"[..], b":mo", b":moD", b":moc", b":mock"]
+		.into_iter()
+		.map(|key| hex_string(&key))
+		.collect();
+	assert_eq!(keys, expected);
+}
+
+#[tokio::test]
+async fn archive_state_stream_resumes_from_cursor() {
+	let (mut client, api) = setup_api(MAX_PAGINATION_LIMIT, MAX_QUERIED_LIMIT);
+
+	let mut builder = BlockBuilderBuilder::new(&*client)
+		.on_parent_block(client.chain_info().genesis_hash)
+		.with_parent_block_number(0)
+		.build()
+		.unwrap();
+	builder.push_storage_change(b":m".to_vec(), Some(b"a".to_vec())).unwrap();
+	builder.push_storage_change(b":mo".to_vec(), Some(b"ab".to_vec())).unwrap();
+	builder.push_storage_change(b":moc".to_vec(), Some(b"abc".to_vec())).unwrap();
+	builder.push_storage_change(b":moD".to_vec(), Some(b"abcmoD".to_vec())).unwrap();
+	builder.push_storage_change(b":mock".to_vec(), Some(b"abcd".to_vec())).unwrap();
+	let block = builder.build().unwrap().block;
+	let block_hash = block.header.hash();
+	client.import(BlockOrigin::Own, block.clone()).await.unwrap();
+
+	// The state of non-finalized blocks can't be streamed.
+	let err = api
+		.subscribe_unbounded(
+			"archive_unstable_stateStream",
+			rpc_params![format!("{:?}", block_hash), hex_string(b":m"), None::<String>],
+		)
+		.await
+		.unwrap_err();
+	assert_matches!(err, Error::JsonRpc(err) if err.code() == 3001);
+
+	client.finalize_block(block_hash, None).unwrap();
+
+	// The cursor must be under the prefix.
+	let err = api
+		.subscribe_unbounded(
+			"archive_unstable_stateStream",
+			rpc_params![format!("{:?}", block_hash), hex_string(b":m"), hex_string(b":a")],
+		)
+		.await
+		.unwrap_err();
+	assert_matches!(err, Error::JsonRpc(err) if err.code() == 3001);
+
+	// Read the first chunk only.
+	let mut sub = api
+		.subscribe_unbounded(
+			"archive_unstable_stateStream",
+			rpc_params![format!("{:?}", block_hash), hex_string(b":m"), None::<String>],
+		)
+		.await
+		.unwrap();
+	let (event, _) = sub.next::<ArchiveStateStreamEvent>().await.unwrap().unwrap();
+	let ArchiveStateStreamEvent::Items(ArchiveStateStreamItems { items, cursor: Some(cursor) }) =
+		event
+	else {
+		panic!("Unexpected event {:?}", event)
+	};
+	assert_eq!(items.len(), MAX_STATE_CHUNK_SIZE);
+	assert_eq!(cursor, items.last().unwrap().key);
+	let mut keys: Vec<_> = items.into_iter().map(|item| item.key).collect();
+	drop(sub);
+
+	// Resume after the first chunk.
+	let mut sub = api
+		.subscribe_unbounded(
+			"archive_unstable_stateStream",
+			rpc_params![format!("{:?}", block_hash), hex_string(b":m"), cursor],
+		)
+		.await
+		.unwrap();
+	loop {
+		let (event, _) = sub.next::<ArchiveStateStreamEvent>().await.unwrap().unwrap();
+		match event {
+			ArchiveStateStreamEvent::Items(ArchiveStateStreamItems { items, cursor }) => {
+				assert!(!items.is_empty() && items.len() <= MAX_STATE_CHUNK_SIZE);
+				// The cursor is the last reported key.
+				if let Some(cursor) = cursor {
+					assert_eq!(cursor, items.last().unwrap().key);
+				}
+				keys.extend(items.into_iter().map(|item| item.key));
+			},
+			ArchiveStateStreamEvent::Done => break,
+			ArchiveStateStreamEvent::Error(err) => panic!("Unexpected error {}", err.error),
+		}
+	}
+
+	let expected: Vec<_> = [&b":m"[..], b":mo", b":moD", b":moc", b":mock"]
+		.into_iter()
+		.map(|key| hex_string(&key))
+		.collect();
+	assert_eq!(keys, expected);
+}
+
+#[tokio::test]
+async fn archive_storage_diff_reports_changes() {
+	let (mut client, api) = setup_api(MAX_PAGINATION_LIMIT, MAX_QUERIED_LIMIT);
+
+	let mut builder = BlockBuilderBuilder::new(&*client)
+		.on_parent_block(client.chain_info().genesis_hash)
+		.with_parent_block_number(0)
+		.build()
+		.unwrap();
+	builder.push_storage_change(b":m".to_vec(), Some(b"a".to_vec())).unwrap();
+	builder.push_storage_change(b":mo".to_vec(), Some(b"ab".to_vec())).unwrap();
+	builder.push_storage_change(b":moc".to_vec(), Some(b"abc".to_vec())).unwrap();
+	let previous_block = builder.build().unwrap().block;
+	let previous_hash = previous_block.header.hash();
+	client.import(BlockOrigin::Own, previous_block.clone()).await.unwrap();
+
+	let mut builder = BlockBuilderBuilder::new(&*client)
+		.on_parent_block(previous_hash)
+		.with_parent_block_number(1)
+		.build()
+		.unwrap();
+	builder.push_storage_change(b":mo".to_vec(), Some(b"abx".to_vec())).unwrap();
+	builder.push_storage_change(b":moc".to_vec(), None).unwrap();
+	builder.push_storage_change(b":mock".to_vec(), Some(b"abcd".to_vec())).unwrap();
+	let block = builder.build().unwrap().block;
+	let block_hash = block.header.hash();
+	client.import(BlockOrigin::Own, block.clone()).await.unwrap();
+
+	let mut sub = api
+		.subscribe_unbounded(
+			"archive_unstable_storageDiff",
+			rpc_params![
+				format!("{:?}", block_hash),
+				format!("{:?}", previous_hash),
+				vec![hex_string(b":m"), hex_string(b":mo")]
+			],
+		)
+		.await
+		.unwrap();
+
+	let mut changes = Vec::new();
+	loop {
+		let (event, _) = sub.next::<ArchiveStorageDiffEvent>().await.unwrap().unwrap();
+		match event {
+			ArchiveStorageDiffEvent::Items(ArchiveStorageDiffItems { items }) => {
+				assert!(!items.is_empty() && items.len() <= MAX_PREFIX_CHUNK_SIZE);
+				changes.extend(items);
+			},
+			ArchiveStorageDiffEvent::Done => break,
+			ArchiveStorageDiffEvent::Error(err) => panic!("Unexpected error {}", err.error),
+		}
+	}
+
+	// The overlapping prefixes report each key once, the unchanged `:m` is not reported.
+	assert_eq!(
+		changes,
+		vec![
+			ArchiveStorageDiffResult {
+				key: hex_string(b":mo"),
+				value: Some(hex_string(b"abx")),
+				diff_type: ArchiveStorageDiffType::Modified,
+			},
+			ArchiveStorageDiffResult {
+				key: hex_string(b":moc"),
+				value: None,
+				diff_type: ArchiveStorageDiffType::Deleted,
+			},
+			ArchiveStorageDiffResult {
+				key: hex_string(b":mock"),
+				value: Some(hex_string(b"abcd")),
+				diff_type: ArchiveStorageDiffType::Added,
+			},
+		]
+	);
+
+	// Identical states report no change.
+	let mut sub = api
+		.subscribe_unbounded(
+			"archive_unstable_storageDiff",
+			rpc_params![
+				format!("{:?}", block_hash),
+				format!("{:?}", block_hash),
+				Vec::<String>::new()
+			],
+		)
+		.await
+		.unwrap();
+	let (event, _) = sub.next::<ArchiveStorageDiffEvent>().await.unwrap().unwrap();
+	assert_eq!(event, ArchiveStorageDiffEvent::Done);
+}
@@ -22,7 +22,7 @@
 use crate::{
 	chain_head::{
 		error::Error,
-		event::{FollowEvent, MethodResponse},
+		event::{FollowEvent, FollowOptions, MethodResponse, OperationPriority},
 	},
 	common::events::StorageQuery,
 };
@@ -33,6 +33,10 @@ pub use sp_rpc::list::ListOrValue;
 pub trait ChainHeadApi<Hash> {
 	/// Track the state of the head of the chain: the finalized, non-finalized, and best blocks.
 	///
+	/// If the `resumable` option is set, the `Initialized` event reports a resumption token to
+	/// resume the subscription with `chainHead_unstable_resumeFollow` after the connection is
+	/// lost, see [`FollowOptions`].
+	///
 	/// # Unstable
 	///
 	/// This method is unstable and subject to change in the future.
@@ -41,7 +45,46 @@ pub trait ChainHeadApi<Hash> {
 		unsubscribe = "chainHead_unstable_unfollow",
 		item = FollowEvent<Hash>,
 	)]
-	fn chain_head_unstable_follow(&self, with_runtime: bool);
+	fn chain_head_unstable_follow(&self, with_runtime: bool, options: Option<FollowOptions>);
+
+	/// Resume the `chainHead_unstable_follow` subscription of the given resumption token on the
+	/// current connection.
+	///
+	/// The subscription is kept alive for a short grace period after its connection is lost,
+	/// along with its pinned blocks. The events it generated in the meantime are reported first,
+	/// then its new events. The subscription keeps its identifier, which can be used with the
+	/// other `chainHead` methods of the current connection.
+	///
+	/// Unsubscribing has the same effect as losing the connection: the subscription can be
+	/// resumed again until the grace period elapses.
+	///
+	/// # Unstable
+	///
+	/// This method is unstable and subject to change in the future.
+	#[subscription(
+		name = "chainHead_unstable_resumeFollow" => "chainHead_unstable_resumedFollowEvent",
+		unsubscribe = "chainHead_unstable_unfollowResumed",
+		item = FollowEvent<Hash>,
+	)]
+	fn chain_head_unstable_resume_follow(&self, resumption_token: String);
+
+	/// Track the finalized blocks of the chain.
+	///
+	/// Behaves like `chainHead_unstable_follow`, except that only finalized blocks are reported.
+	/// Each finalized block is reported by a `newBlock` event, followed by a `finalized` event.
+	/// No `bestBlockChanged` events are generated and no blocks are ever reported as pruned.
+	///
+	/// The subscription ID can be used with the other `chainHead` methods.
+	///
+	/// # Unstable
+	///
+	/// This method is unstable and subject to change in the future.
+	#[subscription(
+		name = "chainHead_unstable_followFinalized" => "chainHead_unstable_followFinalizedEvent",
+		unsubscribe = "chainHead_unstable_unfollowFinalized",
+		item = FollowEvent<Hash>,
+	)]
+	fn chain_head_unstable_follow_finalized(&self, with_runtime: bool);
 
 	/// Retrieves the body (list of transactions) of a pinned block.
 	///
@@ -82,6 +125,9 @@ pub trait ChainHeadApi<Hash> {
 
 	/// Returns storage entries at a specific block's state.
 	///
+	/// The optional `priority` orders the operation relative to the other operations of the
+	/// subscription when their limit is reached, see [`OperationPriority`].
+	///
 	/// # Unstable
 	///
 	/// This method is unstable and subject to change in the future.
@@ -92,10 +138,18 @@ pub trait ChainHeadApi<Hash> {
 		hash: Hash,
 		items: Vec<StorageQuery<String>>,
 		child_trie: Option<String>,
+		priority: Option<OperationPriority>,
 	) -> ResponsePayload<'static, MethodResponse>;
 
 	/// Call into the Runtime API at a specified block's state.
 	///
+	/// The optional `priority` orders the operation relative to the other operations of the
+	/// subscription when their limit is reached, see [`OperationPriority`].
+	///
+	/// When `with_proof` is `true`, the storage accessed by the call is recorded and the
+	/// `operationCallDone` event contains its proof against the state root of the block, such
+	/// that the output of the call can be verified by executing it on the proof.
+	///
 	/// # Unstable
 	///
 	/// This method is unstable and subject to change in the future.
@@ -106,6 +160,8 @@ pub trait ChainHeadApi<Hash> {
 		hash: Hash,
 		function: String,
 		call_parameters: String,
+		priority: Option<OperationPriority>,
+		with_proof: Option<bool>,
 	) -> ResponsePayload<'static, MethodResponse>;
 
 	/// Unpin a block or multiple blocks reported by the `follow` method.
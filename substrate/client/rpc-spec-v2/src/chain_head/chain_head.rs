@@ -27,34 +27,43 @@ use crate::{
 		api::ChainHeadApiServer,
 		chain_head_follow::ChainHeadFollower,
 		error::Error as ChainHeadRpcError,
-		event::{FollowEvent, MethodResponse, OperationError},
-		subscription::{SubscriptionManagement, SubscriptionManagementError},
+		event::{FollowEvent, FollowOptions, MethodResponse, OperationError, OperationPriority},
+		metrics::ChainHeadMetrics,
+		resumption::{FollowSink, ResumableSubscriptions},
+		subscription::{BlockGuard, SubscriptionManagement, SubscriptionManagementError},
 	},
 	common::events::StorageQuery,
-	hex_string, SubscriptionTaskExecutor,
+	hex_string,
+	session::{ResumableSink, SessionManager},
+	SubscriptionTaskExecutor,
 };
 use codec::Encode;
 use futures::{channel::oneshot, future::FutureExt};
 use jsonrpsee::{
 	core::async_trait, server::ResponsePayload, types::SubscriptionId, ConnectionDetails,
-	MethodResponseFuture, PendingSubscriptionSink, SubscriptionSink,
+	ConnectionId, MethodResponseFuture, PendingSubscriptionSink, SubscriptionSink,
 };
 use log::debug;
 use sc_client_api::{
-	Backend, BlockBackend, BlockchainEvents, CallExecutor, ChildInfo, ExecutorProvider, StorageKey,
-	StorageProvider,
+	Backend, BlockBackend, BlockchainEvents, CallExecutor, ChildInfo, ExecutorProvider,
+	ProofProvider, StorageKey, StorageProvider,
 };
 use sc_rpc::utils::to_sub_message;
 use sp_api::CallApiAt;
 use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
 use sp_core::{traits::CallContext, Bytes};
 use sp_rpc::list::ListOrValue;
-use sp_runtime::traits::Block as BlockT;
-use std::{marker::PhantomData, sync::Arc, time::Duration};
+use sp_runtime::traits::{Block as BlockT, HashingFor, Header as HeaderT};
+use std::{
+	marker::PhantomData,
+	sync::Arc,
+	time::{Duration, Instant},
+};
 
 pub(crate) const LOG_TARGET: &str = "rpc-spec-v2";
 
 /// The configuration of [`ChainHead`].
+#[derive(Debug, Clone)]
 pub struct ChainHeadConfig {
 	/// The maximum number of pinned blocks across all subscriptions.
 	pub global_max_pinned_blocks: usize,
@@ -70,6 +79,21 @@ pub struct ChainHeadConfig {
 	pub operation_max_storage_items: usize,
 	/// The maximum number of `chainHead_follow` subscriptions per connection.
 	pub max_follow_subscriptions_per_connection: usize,
+	/// The maximum duration the `chainHead_call` and `chainHead_storage` operations wait for the
+	/// ongoing operations of their subscription to end when the limit of ongoing operations is
+	/// reached, started by decreasing priority.
+	///
+	/// The operations are rejected right away with `limitReached` if zero.
+	pub operation_max_queue_duration: Duration,
+	/// The maximum number of `chainHead_follow` subscriptions which can be resumed with a
+	/// resumption token, across all connections. The tokens of the least recently used
+	/// subscriptions are evicted once the limit is reached.
+	///
+	/// The resumption tokens are disabled if zero.
+	pub max_resumable_subscriptions: usize,
+	/// The maximum duration a resumable `chainHead_follow` subscription is kept alive after its
+	/// connection is lost, waiting to be resumed.
+	pub resumable_subscription_grace_period: Duration,
 }
 
 /// Maximum pinned blocks across all connections.
@@ -98,6 +122,17 @@ const MAX_LAGGING_DISTANCE: usize = 128;
 /// The maximum number of `chainHead_follow` subscriptions per connection.
 const MAX_FOLLOW_SUBSCRIPTIONS_PER_CONNECTION: usize = 4;
 
+/// The maximum duration an operation waits for the ongoing operations of its subscription.
+/// Note: The operations are not queued by default, as required by the spec.
+const MAX_OPERATION_QUEUE_DURATION: Duration = Duration::ZERO;
+
+/// The maximum number of resumable `chainHead_follow` subscriptions.
+const MAX_RESUMABLE_SUBSCRIPTIONS: usize = 64;
+
+/// The maximum duration a resumable subscription waits to be resumed.
+/// Note: This should be enough to reconnect after a short network outage.
+const RESUMABLE_SUBSCRIPTION_GRACE_PERIOD: Duration = Duration::from_secs(30);
+
 impl Default for ChainHeadConfig {
 	fn default() -> Self {
 		ChainHeadConfig {
@@ -107,6 +142,9 @@ impl Default for ChainHeadConfig {
 			max_lagging_distance: MAX_LAGGING_DISTANCE,
 			operation_max_storage_items: MAX_STORAGE_ITER_ITEMS,
 			max_follow_subscriptions_per_connection: MAX_FOLLOW_SUBSCRIPTIONS_PER_CONNECTION,
+			operation_max_queue_duration: MAX_OPERATION_QUEUE_DURATION,
+			max_resumable_subscriptions: MAX_RESUMABLE_SUBSCRIPTIONS,
+			resumable_subscription_grace_period: RESUMABLE_SUBSCRIPTION_GRACE_PERIOD,
 		}
 	}
 }
@@ -127,6 +165,14 @@ pub struct ChainHead<BE: Backend<Block>, Block: BlockT, Client> {
 	/// Stop all subscriptions if the distance between the leaves and the current finalized
 	/// block is larger than this value.
 	max_lagging_distance: usize,
+	/// The maximum duration an operation waits for the ongoing operations of its subscription.
+	operation_max_queue_duration: Duration,
+	/// The metrics of the operations, if enabled.
+	metrics: Option<ChainHeadMetrics>,
+	/// The sessions the follow subscriptions may be part of, if enabled.
+	sessions: Option<SessionManager>,
+	/// The follow subscriptions which can be resumed with a token, if enabled.
+	resumable: Option<ResumableSubscriptions>,
 	/// Phantom member to pin the block type.
 	_phantom: PhantomData<Block>,
 }
@@ -152,9 +198,80 @@ impl<BE: Backend<Block>, Block: BlockT, Client> ChainHead<BE, Block, Client> {
 			),
 			operation_max_storage_items: config.operation_max_storage_items,
 			max_lagging_distance: config.max_lagging_distance,
+			operation_max_queue_duration: config.operation_max_queue_duration,
+			metrics: None,
+			sessions: None,
+			resumable: (config.max_resumable_subscriptions > 0).then(|| {
+				ResumableSubscriptions::new(
+					config.max_resumable_subscriptions,
+					config.resumable_subscription_grace_period,
+				)
+			}),
 			_phantom: PhantomData,
 		}
 	}
+
+	/// Report the metrics of the operations.
+	pub fn with_metrics(mut self, metrics: ChainHeadMetrics) -> Self {
+		self.metrics = Some(metrics);
+		self
+	}
+
+	/// Keep the follow subscriptions of the connections with a session alive after a
+	/// disconnection, such that they can be resumed from another connection.
+	pub fn with_sessions(mut self, sessions: SessionManager) -> Self {
+		self.sessions = Some(sessions);
+		self
+	}
+
+	/// Check if the given connection contains the given follow subscription, directly, through
+	/// the session attached to it, or because the subscription was resumed on it.
+	fn contains_subscription(
+		&self,
+		connection_id: ConnectionId,
+		follow_subscription: &str,
+	) -> bool {
+		let in_session = self.sessions.as_ref().map_or(false, |sessions| {
+			sessions.contains_subscription(connection_id, follow_subscription)
+		});
+		let resumed = self.resumable.as_ref().map_or(false, |resumable| {
+			resumable.contains_subscription(connection_id, follow_subscription)
+		});
+
+		self.subscriptions.contains_subscription(connection_id, follow_subscription) ||
+			in_session ||
+			resumed
+	}
+
+	/// Ensure the block remains pinned while the `to_reserve` operations of the given priority
+	/// are executed.
+	///
+	/// Waits for at most [`ChainHeadConfig::operation_max_queue_duration`] if the limit of
+	/// ongoing operations of the subscription is reached.
+	async fn lock_block_with_priority(
+		&self,
+		follow_subscription: &str,
+		hash: Block::Hash,
+		to_reserve: usize,
+		priority: OperationPriority,
+	) -> Result<BlockGuard<Block, BE>, SubscriptionManagementError> {
+		let started = Instant::now();
+		let result = self
+			.subscriptions
+			.lock_block_with_priority(
+				follow_subscription,
+				hash,
+				to_reserve,
+				priority,
+				self.operation_max_queue_duration,
+			)
+			.await;
+
+		if let Some(metrics) = &self.metrics {
+			metrics.observe_queue_wait(priority, started.elapsed());
+		}
+		result
+	}
 }
 
 /// Helper to convert the `subscription ID` to a string.
@@ -180,8 +297,7 @@ fn parse_hex_param(param: String) -> Result<Vec<u8>, ChainHeadRpcError> {
 	}
 }
 
-#[async_trait]
-impl<BE, Block, Client> ChainHeadApiServer<Block::Hash> for ChainHead<BE, Block, Client>
+impl<BE, Block, Client> ChainHead<BE, Block, Client>
 where
 	Block: BlockT + 'static,
 	Block::Header: Unpin,
@@ -195,11 +311,23 @@ where
 		+ StorageProvider<Block, BE>
 		+ 'static,
 {
-	fn chain_head_unstable_follow(&self, pending: PendingSubscriptionSink, with_runtime: bool) {
+	/// Start a `chainHead_follow` subscription.
+	///
+	/// If `finalized_only` is set, only the finalized blocks are reported. If `resumable` is set,
+	/// the subscription can be resumed with a token, unless it's part of a session.
+	fn follow(
+		&self,
+		pending: PendingSubscriptionSink,
+		with_runtime: bool,
+		finalized_only: bool,
+		resumable: bool,
+	) {
 		let subscriptions = self.subscriptions.clone();
 		let backend = self.backend.clone();
 		let client = self.client.clone();
 		let max_lagging_distance = self.max_lagging_distance;
+		let sessions = self.sessions.clone();
+		let resumable = self.resumable.clone().filter(|_| resumable);
 
 		let fut = async move {
 			// Ensure the current connection ID has enough space to accept a new subscription.
@@ -228,14 +356,22 @@ where
 				return
 			};
 			debug!(target: LOG_TARGET, "[follow][id={:?}] Subscription accepted", sub_id);
+			let has_session =
+				sessions.as_ref().map_or(false, |sessions| sessions.has_session(connection_id));
+			let sink = match resumable {
+				Some(resumable) if !has_session => resumable.insert(sub_id.clone(), sink),
+				_ => FollowSink::Sink(ResumableSink::new(sink, sessions.as_ref())),
+			};
 
 			let mut chain_head_follow = ChainHeadFollower::new(
 				client,
 				backend,
 				subscriptions,
 				with_runtime,
+				finalized_only,
 				sub_id.clone(),
 				max_lagging_distance,
+				sink.resumption_token(),
 			);
 			let result = chain_head_follow.generate_events(sink, sub_data).await;
 			if let Err(SubscriptionManagementError::BlockDistanceTooLarge) = result {
@@ -248,6 +384,62 @@ where
 
 		self.executor.spawn("substrate-rpc-subscription", Some("rpc"), fut.boxed());
 	}
+}
+
+#[async_trait]
+impl<BE, Block, Client> ChainHeadApiServer<Block::Hash> for ChainHead<BE, Block, Client>
+where
+	Block: BlockT + 'static,
+	Block::Header: Unpin,
+	BE: Backend<Block> + 'static,
+	Client: BlockBackend<Block>
+		+ ExecutorProvider<Block>
+		+ HeaderBackend<Block>
+		+ HeaderMetadata<Block, Error = BlockChainError>
+		+ BlockchainEvents<Block>
+		+ CallApiAt<Block>
+		+ StorageProvider<Block, BE>
+		+ ProofProvider<Block>
+		+ 'static,
+{
+	fn chain_head_unstable_follow(
+		&self,
+		pending: PendingSubscriptionSink,
+		with_runtime: bool,
+		options: Option<FollowOptions>,
+	) {
+		let resumable = options.unwrap_or_default().resumable;
+		self.follow(pending, with_runtime, false, resumable)
+	}
+
+	fn chain_head_unstable_resume_follow(
+		&self,
+		pending: PendingSubscriptionSink,
+		resumption_token: String,
+	) {
+		let resumable = self.resumable.clone();
+
+		let fut = async move {
+			let Some(follow) = resumable.and_then(|resumable| resumable.get(&resumption_token))
+			else {
+				pending.reject(ChainHeadRpcError::InvalidResumptionToken).await;
+				return
+			};
+
+			let Ok(sink) = pending.accept().await else { return };
+			follow.resume(sink).await;
+		};
+
+		self.executor.spawn("substrate-rpc-subscription", Some("rpc"), fut.boxed());
+	}
+
+	fn chain_head_unstable_follow_finalized(
+		&self,
+		pending: PendingSubscriptionSink,
+		with_runtime: bool,
+	) {
+		self.follow(pending, with_runtime, true, false)
+	}
 
 	async fn chain_head_unstable_body(
 		&self,
@@ -255,10 +447,7 @@ where
 		follow_subscription: String,
 		hash: Block::Hash,
 	) -> ResponsePayload<'static, MethodResponse> {
-		if !self
-			.subscriptions
-			.contains_subscription(connection_details.id(), &follow_subscription)
-		{
+		if !self.contains_subscription(connection_details.id(), &follow_subscription) {
 			// The spec says to return `LimitReached` if the follow subscription is invalid or
 			// stale.
 			return ResponsePayload::success(MethodResponse::LimitReached);
@@ -339,10 +528,7 @@ where
 		follow_subscription: String,
 		hash: Block::Hash,
 	) -> Result<Option<String>, ChainHeadRpcError> {
-		if !self
-			.subscriptions
-			.contains_subscription(connection_details.id(), &follow_subscription)
-		{
+		if !self.contains_subscription(connection_details.id(), &follow_subscription) {
 			return Ok(None);
 		}
 
@@ -376,11 +562,9 @@ where
 		hash: Block::Hash,
 		items: Vec<StorageQuery<String>>,
 		child_trie: Option<String>,
+		priority: Option<OperationPriority>,
 	) -> ResponsePayload<'static, MethodResponse> {
-		if !self
-			.subscriptions
-			.contains_subscription(connection_details.id(), &follow_subscription)
-		{
+		if !self.contains_subscription(connection_details.id(), &follow_subscription) {
 			// The spec says to return `LimitReached` if the follow subscription is invalid or
 			// stale.
 			return ResponsePayload::success(MethodResponse::LimitReached);
@@ -407,19 +591,22 @@ where
 			Err(e) => return ResponsePayload::error(e),
 		};
 
-		let mut block_guard =
-			match self.subscriptions.lock_block(&follow_subscription, hash, items.len()) {
-				Ok(block) => block,
-				Err(SubscriptionManagementError::SubscriptionAbsent) |
-				Err(SubscriptionManagementError::ExceededLimits) => {
-					return ResponsePayload::success(MethodResponse::LimitReached);
-				},
-				Err(SubscriptionManagementError::BlockHashAbsent) => {
-					// Block is not part of the subscription.
-					return ResponsePayload::error(ChainHeadRpcError::InvalidBlock)
-				},
-				Err(_) => return ResponsePayload::error(ChainHeadRpcError::InvalidBlock),
-			};
+		let priority = priority.unwrap_or_default();
+		let mut block_guard = match self
+			.lock_block_with_priority(&follow_subscription, hash, items.len(), priority)
+			.await
+		{
+			Ok(block) => block,
+			Err(SubscriptionManagementError::SubscriptionAbsent) |
+			Err(SubscriptionManagementError::ExceededLimits) => {
+				return ResponsePayload::success(MethodResponse::LimitReached);
+			},
+			Err(SubscriptionManagementError::BlockHashAbsent) => {
+				// Block is not part of the subscription.
+				return ResponsePayload::error(ChainHeadRpcError::InvalidBlock)
+			},
+			Err(_) => return ResponsePayload::error(ChainHeadRpcError::InvalidBlock),
+		};
 
 		let mut storage_client = ChainHeadStorage::<Client, Block, BE>::new(
 			self.client.clone(),
@@ -457,34 +644,35 @@ where
 		hash: Block::Hash,
 		function: String,
 		call_parameters: String,
+		priority: Option<OperationPriority>,
+		with_proof: Option<bool>,
 	) -> ResponsePayload<'static, MethodResponse> {
 		let call_parameters = match parse_hex_param(call_parameters) {
 			Ok(hex) => Bytes::from(hex),
 			Err(err) => return ResponsePayload::error(err),
 		};
 
-		if !self
-			.subscriptions
-			.contains_subscription(connection_details.id(), &follow_subscription)
-		{
+		if !self.contains_subscription(connection_details.id(), &follow_subscription) {
 			// The spec says to return `LimitReached` if the follow subscription is invalid or
 			// stale.
 			return ResponsePayload::success(MethodResponse::LimitReached);
 		}
 
-		let mut block_guard = match self.subscriptions.lock_block(&follow_subscription, hash, 1) {
-			Ok(block) => block,
-			Err(SubscriptionManagementError::SubscriptionAbsent) |
-			Err(SubscriptionManagementError::ExceededLimits) => {
-				// Invalid invalid subscription ID.
-				return ResponsePayload::success(MethodResponse::LimitReached)
-			},
-			Err(SubscriptionManagementError::BlockHashAbsent) => {
-				// Block is not part of the subscription.
-				return ResponsePayload::error(ChainHeadRpcError::InvalidBlock)
-			},
-			Err(_) => return ResponsePayload::error(ChainHeadRpcError::InvalidBlock),
-		};
+		let priority = priority.unwrap_or_default();
+		let mut block_guard =
+			match self.lock_block_with_priority(&follow_subscription, hash, 1, priority).await {
+				Ok(block) => block,
+				Err(SubscriptionManagementError::SubscriptionAbsent) |
+				Err(SubscriptionManagementError::ExceededLimits) => {
+					// Invalid invalid subscription ID.
+					return ResponsePayload::success(MethodResponse::LimitReached)
+				},
+				Err(SubscriptionManagementError::BlockHashAbsent) => {
+					// Block is not part of the subscription.
+					return ResponsePayload::error(ChainHeadRpcError::InvalidBlock)
+				},
+				Err(_) => return ResponsePayload::error(ChainHeadRpcError::InvalidBlock),
+			};
 
 		// Reject subscription if with_runtime is false.
 		if !block_guard.has_runtime() {
@@ -504,21 +692,28 @@ where
 				return
 			}
 
-			let event = client
-				.executor()
-				.call(hash, &function, &call_parameters, CallContext::Offchain)
-				.map(|result| {
+			let result = if with_proof.unwrap_or_default() {
+				call_with_proof::<Block, _>(&*client, hash, &function, &call_parameters)
+					.map(|(output, proof)| (output, Some(proof)))
+			} else {
+				client
+					.executor()
+					.call(hash, &function, &call_parameters, CallContext::Offchain)
+					.map(|output| (output, None))
+					.map_err(|error| error.to_string())
+			};
+			let event = match result {
+				Ok((output, proof)) =>
 					FollowEvent::<Block::Hash>::OperationCallDone(OperationCallDone {
 						operation_id: operation_id.clone(),
-						output: hex_string(&result),
-					})
-				})
-				.unwrap_or_else(|error| {
-					FollowEvent::<Block::Hash>::OperationError(OperationError {
-						operation_id: operation_id.clone(),
-						error: error.to_string(),
-					})
-				});
+						output: hex_string(&output),
+						proof,
+					}),
+				Err(error) => FollowEvent::<Block::Hash>::OperationError(OperationError {
+					operation_id: operation_id.clone(),
+					error,
+				}),
+			};
 
 			let _ = block_guard.response_sender().unbounded_send(event);
 		};
@@ -534,10 +729,7 @@ where
 		follow_subscription: String,
 		hash_or_hashes: ListOrValue<Block::Hash>,
 	) -> Result<(), ChainHeadRpcError> {
-		if !self
-			.subscriptions
-			.contains_subscription(connection_details.id(), &follow_subscription)
-		{
+		if !self.contains_subscription(connection_details.id(), &follow_subscription) {
 			return Ok(());
 		}
 
@@ -570,10 +762,7 @@ where
 		follow_subscription: String,
 		operation_id: String,
 	) -> Result<(), ChainHeadRpcError> {
-		if !self
-			.subscriptions
-			.contains_subscription(connection_details.id(), &follow_subscription)
-		{
+		if !self.contains_subscription(connection_details.id(), &follow_subscription) {
 			return Ok(())
 		}
 
@@ -596,10 +785,7 @@ where
 		follow_subscription: String,
 		operation_id: String,
 	) -> Result<(), ChainHeadRpcError> {
-		if !self
-			.subscriptions
-			.contains_subscription(connection_details.id(), &follow_subscription)
-		{
+		if !self.contains_subscription(connection_details.id(), &follow_subscription) {
 			return Ok(())
 		}
 
@@ -622,6 +808,35 @@ fn method_started_response(
 	ResponsePayload::success(rp).notify_on_completion()
 }
 
+/// Call into the Runtime API at the state of the block while recording the storage it accesses.
+///
+/// Returns the output of the call along with the hex-encoded compact proof of the accessed
+/// storage, against the state root of the block.
+fn call_with_proof<Block, Client>(
+	client: &Client,
+	hash: Block::Hash,
+	function: &str,
+	call_parameters: &[u8],
+) -> Result<(Vec<u8>, String), String>
+where
+	Block: BlockT,
+	Client: HeaderBackend<Block> + ProofProvider<Block>,
+{
+	let header = client
+		.header(hash)
+		.map_err(|err| err.to_string())?
+		.ok_or_else(|| format!("Header of block {hash:?} not found"))?;
+
+	let (output, proof) = client
+		.execution_proof(hash, function, call_parameters)
+		.map_err(|err| err.to_string())?;
+
+	let proof = proof
+		.into_compact_proof::<HashingFor<Block>>(*header.state_root())
+		.map_err(|err| format!("Failed to compact the proof: {err:?}"))?;
+	Ok((output, hex_string(&proof.encode())))
+}
+
 /// Spawn a blocking future on the provided executor and return the result on a oneshot channel.
 ///
 /// This is a wrapper to extract the result of a `executor.spawn_blocking` future.
@@ -24,6 +24,7 @@ use crate::chain_head::{
 		BestBlockChanged, Finalized, FollowEvent, Initialized, NewBlock, RuntimeEvent,
 		RuntimeVersionEvent,
 	},
+	resumption::FollowSink,
 	subscription::{SubscriptionManagement, SubscriptionManagementError},
 };
 use futures::{
@@ -31,12 +32,10 @@ use futures::{
 	stream::{self, Stream, StreamExt},
 };
 use futures_util::future::Either;
-use jsonrpsee::SubscriptionSink;
 use log::{debug, error};
 use sc_client_api::{
 	Backend, BlockBackend, BlockImportNotification, BlockchainEvents, FinalityNotification,
 };
-use sc_rpc::utils::to_sub_message;
 use sp_api::CallApiAt;
 use sp_blockchain::{
 	Backend as BlockChainBackend, Error as BlockChainError, HeaderBackend, HeaderMetadata, Info,
@@ -65,6 +64,8 @@ pub struct ChainHeadFollower<BE: Backend<Block>, Block: BlockT, Client> {
 	sub_handle: SubscriptionManagement<Block, BE>,
 	/// Subscription was started with the runtime updates flag.
 	with_runtime: bool,
+	/// Only the finalized blocks are reported.
+	finalized_only: bool,
 	/// Subscription ID.
 	sub_id: String,
 	/// The best reported block by this subscription.
@@ -72,6 +73,8 @@ pub struct ChainHeadFollower<BE: Backend<Block>, Block: BlockT, Client> {
 	/// Stop all subscriptions if the distance between the leaves and the current finalized
 	/// block is larger than this value.
 	max_lagging_distance: usize,
+	/// The token to resume the subscription with, if it's resumable.
+	resumption_token: Option<String>,
 }
 
 impl<BE: Backend<Block>, Block: BlockT, Client> ChainHeadFollower<BE, Block, Client> {
@@ -81,17 +84,21 @@ impl<BE: Backend<Block>, Block: BlockT, Client> ChainHeadFollower<BE, Block, Cli
 		backend: Arc<BE>,
 		sub_handle: SubscriptionManagement<Block, BE>,
 		with_runtime: bool,
+		finalized_only: bool,
 		sub_id: String,
 		max_lagging_distance: usize,
+		resumption_token: Option<String>,
 	) -> Self {
 		Self {
 			client,
 			backend,
 			sub_handle,
 			with_runtime,
+			finalized_only,
 			sub_id,
 			best_block_cache: None,
 			max_lagging_distance,
+			resumption_token,
 		}
 	}
 }
@@ -237,7 +244,8 @@ where
 		finalized: Block::Hash,
 	) -> Result<InitialBlocks<Block>, SubscriptionManagementError> {
 		let blockchain = self.backend.blockchain();
-		let leaves = blockchain.leaves()?;
+		// The descendants of the finalized block are reported once they get finalized.
+		let leaves = if self.finalized_only { Vec::new() } else { blockchain.leaves()? };
 		let mut pruned_forks = HashSet::new();
 		let mut finalized_block_descendants = Vec::new();
 		let mut unique_descendants = HashSet::new();
@@ -322,6 +330,7 @@ where
 		let initialized_event = FollowEvent::Initialized(Initialized {
 			finalized_block_hashes: finalized_block_hashes.into(),
 			finalized_block_runtime,
+			resumption_token: self.resumption_token.clone(),
 			with_runtime: self.with_runtime,
 		});
 
@@ -343,7 +352,7 @@ where
 
 		// Generate a new best block event.
 		let best_block_hash = startup_point.best_hash;
-		if best_block_hash != finalized_block_hash {
+		if best_block_hash != finalized_block_hash && !self.finalized_only {
 			let best_block = FollowEvent::BestBlockChanged(BestBlockChanged { best_block_hash });
 			self.best_block_cache = Some(best_block_hash);
 			finalized_block_descendants.push(best_block);
@@ -401,6 +410,11 @@ where
 		notification: BlockImportNotification<Block>,
 		startup_point: &StartupPoint<Block>,
 	) -> Result<Vec<FollowEvent<Block::Hash>>, SubscriptionManagementError> {
+		// The block is reported by the finalized event.
+		if self.finalized_only {
+			return Ok(Default::default())
+		}
+
 		// The block was already pinned by the initial block events or by the finalized event.
 		if !self.sub_handle.pin_block(&self.sub_id, notification.hash)? {
 			return Ok(Default::default())
@@ -472,8 +486,9 @@ where
 					}
 				}
 
-				// Let's generate the `NewBlock` and `NewBestBlock` events for the block.
-				events.extend(self.generate_import_events(*hash, *parent, true))
+				// Let's generate the `NewBlock` and `NewBestBlock` events for the block. The best
+				// block is not tracked when reporting only finalized blocks.
+				events.extend(self.generate_import_events(*hash, *parent, !self.finalized_only))
 			}
 		}
 
@@ -534,9 +549,13 @@ where
 		let mut events = self.generate_finalized_events(&finalized_block_hashes)?;
 
 		// Report all pruned blocks from the notification that are not
-		// part of the fork we need to ignore.
-		let pruned_block_hashes =
-			self.get_pruned_hashes(&notification.stale_heads, last_finalized, to_ignore)?;
+		// part of the fork we need to ignore. Forks are never reported when
+		// reporting only finalized blocks.
+		let pruned_block_hashes = if self.finalized_only {
+			Vec::new()
+		} else {
+			self.get_pruned_hashes(&notification.stale_heads, last_finalized, to_ignore)?
+		};
 
 		let finalized_event = FollowEvent::Finalized(Finalized {
 			finalized_block_hashes,
@@ -590,7 +609,7 @@ where
 		startup_point: &StartupPoint<Block>,
 		mut stream: EventStream,
 		mut to_ignore: HashSet<Block::Hash>,
-		sink: SubscriptionSink,
+		sink: FollowSink,
 		rx_stop: oneshot::Receiver<()>,
 	) -> Result<(), SubscriptionManagementError>
 	where
@@ -625,23 +644,20 @@ where
 						self.sub_id,
 						err
 					);
-					let msg = to_sub_message(&sink, &FollowEvent::<String>::Stop);
-					let _ = sink.send(msg).await;
+					let _ = sink.send(&FollowEvent::<String>::Stop).await;
 					return Err(err)
 				},
 			};
 
 			for event in events {
-				let msg = to_sub_message(&sink, &event);
-				if let Err(err) = sink.send(msg).await {
+				if let Err(err) = sink.send(&event).await {
 					// Failed to submit event.
 					debug!(
 						target: LOG_TARGET,
 						"[follow][id={:?}] Failed to send event {:?}", self.sub_id, err
 					);
 
-					let msg = to_sub_message(&sink, &FollowEvent::<String>::Stop);
-					let _ = sink.send(msg).await;
+					let _ = sink.send(&FollowEvent::<String>::Stop).await;
 					// No need to propagate this error further, the client disconnected.
 					return Ok(())
 				}
@@ -655,15 +671,14 @@ where
 		// - the substrate streams have closed
 		// - the `Stop` receiver was triggered internally (cannot hold the pinned block guarantee)
 		// - the client disconnected.
-		let msg = to_sub_message(&sink, &FollowEvent::<String>::Stop);
-		let _ = sink.send(msg).await;
+		let _ = sink.send(&FollowEvent::<String>::Stop).await;
 		Ok(())
 	}
 
 	/// Generate the block events for the `chainHead_follow` method.
 	pub async fn generate_events(
 		&mut self,
-		sink: SubscriptionSink,
+		sink: FollowSink,
 		sub_data: InsertedSubscriptionData<Block>,
 	) -> Result<(), SubscriptionManagementError> {
 		// Register for the new block and finalized notifications.
@@ -691,8 +706,7 @@ where
 					self.sub_id,
 					err
 				);
-				let msg = to_sub_message(&sink, &FollowEvent::<String>::Stop);
-				let _ = sink.send(msg).await;
+				let _ = sink.send(&FollowEvent::<String>::Stop).await;
 				return Err(err)
 			},
 		};
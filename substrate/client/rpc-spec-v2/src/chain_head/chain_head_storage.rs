@@ -20,13 +20,15 @@
 
 use std::{collections::VecDeque, marker::PhantomData, sync::Arc};
 
-use sc_client_api::{Backend, ChildInfo, StorageKey, StorageProvider};
+use sc_client_api::{
+	Backend, ChildInfo, HeaderBackend, ProofProvider, StorageKey, StorageProvider,
+};
 use sc_utils::mpsc::TracingUnboundedSender;
 use sp_runtime::traits::Block as BlockT;
 
 use crate::{
 	chain_head::{
-		event::{OperationError, OperationId, OperationStorageItems},
+		event::{OperationError, OperationId, OperationStorageItems, OperationStorageProof},
 		subscription::BlockGuard,
 		FollowEvent,
 	},
@@ -64,7 +66,7 @@ impl<Client, Block, BE> ChainHeadStorage<Client, Block, BE>
 where
 	Block: BlockT + 'static,
 	BE: Backend<Block> + 'static,
-	Client: StorageProvider<Block, BE> + 'static,
+	Client: StorageProvider<Block, BE> + ProofProvider<Block> + HeaderBackend<Block> + 'static,
 {
 	/// Iterate over (key, hash) and (key, value) generating the `WaitingForContinue` event if
 	/// necessary.
@@ -140,6 +142,7 @@ where
 		let operation = block_guard.operation();
 
 		let mut storage_results = Vec::with_capacity(items.len());
+		let mut proof_keys = Vec::new();
 		for item in items {
 			match item.query_type {
 				StorageQueryType::Value => {
@@ -180,6 +183,7 @@ where
 					ty: IterQueryType::Hash,
 					pagination_start_key: None,
 				}),
+				StorageQueryType::Proof => proof_keys.push(item.key),
 			};
 		}
 
@@ -192,6 +196,21 @@ where
 			));
 		}
 
+		if !proof_keys.is_empty() {
+			match self.client.query_proof(hash, &proof_keys, child_key.as_ref()) {
+				Ok(proof) => {
+					let _ =
+						sender.unbounded_send(FollowEvent::<Block::Hash>::OperationStorageProof(
+							OperationStorageProof { operation_id: operation.operation_id(), proof },
+						));
+				},
+				Err(error) => {
+					send_error::<Block>(&sender, operation.operation_id(), error);
+					return
+				},
+			}
+		}
+
 		self.generate_storage_iter_events(block_guard, hash, child_key).await
 	}
 }
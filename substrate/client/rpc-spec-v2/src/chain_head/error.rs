@@ -38,6 +38,9 @@ pub enum Error {
 	/// Received duplicate hashes for the `chainHead_unpin` method.
 	#[error("Received duplicate hashes for the `chainHead_unpin` method")]
 	InvalidDuplicateHashes,
+	/// The resumption token is unknown or the subscription can no longer be resumed.
+	#[error("Invalid resumption token")]
+	InvalidResumptionToken,
 	/// Invalid parameter provided to the RPC method.
 	#[error("Invalid parameter: {0}")]
 	InvalidParam(String),
@@ -59,6 +62,8 @@ pub mod rpc_spec_v2 {
 	pub const INVALID_CONTINUE: i32 = -32803;
 	/// Received duplicate hashes for the `chainHead_unpin` method.
 	pub const INVALID_DUPLICATE_HASHES: i32 = -32804;
+	/// The resumption token is unknown or the subscription can no longer be resumed.
+	pub const INVALID_RESUMPTION_TOKEN: i32 = -32805;
 }
 
 /// General purpose errors, as defined in
@@ -85,6 +90,8 @@ impl From<Error> for ErrorObject<'static> {
 				ErrorObject::owned(rpc_spec_v2::INVALID_CONTINUE, msg, None::<()>),
 			Error::InvalidDuplicateHashes =>
 				ErrorObject::owned(rpc_spec_v2::INVALID_DUPLICATE_HASHES, msg, None::<()>),
+			Error::InvalidResumptionToken =>
+				ErrorObject::owned(rpc_spec_v2::INVALID_RESUMPTION_TOKEN, msg, None::<()>),
 			Error::InvalidParam(_) =>
 				ErrorObject::owned(json_rpc_spec::INVALID_PARAM_ERROR, msg, None::<()>),
 			Error::InternalError(_) =>
@@ -120,6 +120,15 @@ pub struct Initialized<Hash> {
 	/// This is present only if the `with_runtime` flag is set for
 	/// the `follow` subscription.
 	pub finalized_block_runtime: Option<RuntimeEvent>,
+	/// The token to resume the subscription with `chainHead_resumeFollow` after the connection
+	/// is lost.
+	///
+	/// # Note
+	///
+	/// This is present only if the subscription was started with the `resumable` option and
+	/// the node keeps resumable subscriptions.
+	#[serde(default)]
+	pub resumption_token: Option<String>,
 	/// Privately keep track if the `finalized_block_runtime` should be
 	/// serialized.
 	#[serde(default)]
@@ -128,21 +137,21 @@ pub struct Initialized<Hash> {
 
 impl<Hash: Serialize> Serialize for Initialized<Hash> {
 	/// Custom serialize implementation to include the `RuntimeEvent` depending
-	/// on the internal `with_runtime` flag.
+	/// on the internal `with_runtime` flag, and the resumption token if any.
 	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
 	where
 		S: Serializer,
 	{
+		let len = 1 + self.with_runtime as usize + self.resumption_token.is_some() as usize;
+		let mut state = serializer.serialize_struct("Initialized", len)?;
+		state.serialize_field("finalizedBlockHashes", &self.finalized_block_hashes)?;
 		if self.with_runtime {
-			let mut state = serializer.serialize_struct("Initialized", 2)?;
-			state.serialize_field("finalizedBlockHashes", &self.finalized_block_hashes)?;
 			state.serialize_field("finalizedBlockRuntime", &self.finalized_block_runtime)?;
-			state.end()
-		} else {
-			let mut state = serializer.serialize_struct("Initialized", 1)?;
-			state.serialize_field("finalizedBlockHashes", &self.finalized_block_hashes)?;
-			state.end()
 		}
+		if let Some(token) = &self.resumption_token {
+			state.serialize_field("resumptionToken", token)?;
+		}
+		state.end()
 	}
 }
 
@@ -233,6 +242,13 @@ pub struct OperationCallDone {
 	pub operation_id: String,
 	/// Hexadecimal-encoded output of the runtime function call.
 	pub output: String,
+	/// Hexadecimal-encoded SCALE-codec encoding of the compact proof of the storage accessed by
+	/// the call, against the state root of the block.
+	///
+	/// This is only present if the proof was requested. The proof may not contain the code of the
+	/// runtime, which the verifier is expected to know.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub proof: Option<String>,
 }
 
 /// The response of the `chainHead_call` method.
@@ -245,6 +261,17 @@ pub struct OperationStorageItems {
 	pub items: Vec<StorageResult>,
 }
 
+/// The compact Merkle proof produced by the `chainHead_storage` method.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OperationStorageProof {
+	/// The operation id of the event.
+	pub operation_id: String,
+	/// Hexadecimal-encoded SCALE-codec encoding of the compact proof of the queried keys,
+	/// against the state root of the block.
+	pub proof: String,
+}
+
 /// Indicate a problem during the operation.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -268,6 +295,7 @@ pub struct OperationError {
 /// - OperationBodyDone: The response of the `chianHead_body`
 /// - OperationCallDone: The response of the `chianHead_call`
 /// - OperationStorageItems: Items produced by the `chianHead_storage`
+/// - OperationStorageProof: Merkle proof of the `proof` items of the `chainHead_storage`
 /// - OperationWaitingForContinue: Generated after OperationStorageItems and requires the user to
 ///   call `chainHead_continue`
 /// - OperationStorageDone: The `chianHead_storage` method has produced all the results
@@ -297,6 +325,8 @@ pub enum FollowEvent<Hash> {
 	OperationCallDone(OperationCallDone),
 	/// Yield one or more items found in the storage.
 	OperationStorageItems(OperationStorageItems),
+	/// The compact Merkle proof of the keys queried with the `proof` type.
+	OperationStorageProof(OperationStorageProof),
 	/// Ask the user to call `chainHead_continue` to produce more events
 	/// regarding the operation id.
 	OperationWaitingForContinue(OperationId),
@@ -338,6 +368,45 @@ pub struct MethodResponseStarted {
 	pub discarded_items: Option<usize>,
 }
 
+/// The priority of a `chainHead_call` or `chainHead_storage` operation.
+///
+/// When the limit of ongoing operations of the subscription is reached, the waiting operations
+/// are started by decreasing priority, then in the order they were requested.
+#[derive(
+	Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum OperationPriority {
+	/// Interactive operations, e.g. of wallets.
+	High,
+	/// The default priority.
+	#[default]
+	Normal,
+	/// Bulk operations, e.g. of indexers.
+	Low,
+}
+
+/// The options of a `chainHead_follow` subscription.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(default)]
+pub struct FollowOptions {
+	/// Report a resumption token in the `Initialized` event, to resume the subscription with
+	/// `chainHead_resumeFollow` shortly after the connection is lost.
+	pub resumable: bool,
+}
+
+impl OperationPriority {
+	/// The label of the priority in the metrics.
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			OperationPriority::High => "high",
+			OperationPriority::Normal => "normal",
+			OperationPriority::Low => "low",
+		}
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use crate::common::events::StorageResultType;
@@ -350,6 +419,7 @@ mod tests {
 		let event: FollowEvent<String> = FollowEvent::Initialized(Initialized {
 			finalized_block_hashes: vec!["0x1".into()],
 			finalized_block_runtime: None,
+			resumption_token: None,
 			with_runtime: false,
 		});
 
@@ -361,6 +431,33 @@ mod tests {
 		assert_eq!(event_dec, event);
 	}
 
+	#[test]
+	fn follow_initialized_event_with_resumption_token() {
+		let event: FollowEvent<String> = FollowEvent::Initialized(Initialized {
+			finalized_block_hashes: vec!["0x1".into()],
+			finalized_block_runtime: None,
+			resumption_token: Some("token".into()),
+			with_runtime: false,
+		});
+
+		let ser = serde_json::to_string(&event).unwrap();
+		let exp =
+			r#"{"event":"initialized","finalizedBlockHashes":["0x1"],"resumptionToken":"token"}"#;
+		assert_eq!(ser, exp);
+
+		let event_dec: FollowEvent<String> = serde_json::from_str(exp).unwrap();
+		assert_eq!(event_dec, event);
+	}
+
+	#[test]
+	fn follow_options() {
+		let options: FollowOptions = serde_json::from_str(r#"{"resumable":true}"#).unwrap();
+		assert_eq!(options, FollowOptions { resumable: true });
+
+		let options: FollowOptions = serde_json::from_str("{}").unwrap();
+		assert_eq!(options, FollowOptions::default());
+	}
+
 	#[test]
 	fn follow_initialized_event_with_updates() {
 		// Runtime flag is true, block runtime must always be reported for this event.
@@ -375,6 +472,7 @@ mod tests {
 		let mut initialized = Initialized {
 			finalized_block_hashes: vec!["0x1".into()],
 			finalized_block_runtime: Some(runtime_event),
+			resumption_token: None,
 			with_runtime: true,
 		};
 		let event: FollowEvent<String> = FollowEvent::Initialized(initialized.clone());
@@ -518,6 +616,7 @@ mod tests {
 		let event: FollowEvent<String> = FollowEvent::OperationCallDone(OperationCallDone {
 			operation_id: "123".into(),
 			output: "0x1".into(),
+			proof: None,
 		});
 
 		let ser = serde_json::to_string(&event).unwrap();
@@ -526,6 +625,20 @@ mod tests {
 
 		let event_dec: FollowEvent<String> = serde_json::from_str(exp).unwrap();
 		assert_eq!(event_dec, event);
+
+		let event: FollowEvent<String> = FollowEvent::OperationCallDone(OperationCallDone {
+			operation_id: "123".into(),
+			output: "0x1".into(),
+			proof: Some("0x2".into()),
+		});
+
+		let ser = serde_json::to_string(&event).unwrap();
+		let exp =
+			r#"{"event":"operationCallDone","operationId":"123","output":"0x1","proof":"0x2"}"#;
+		assert_eq!(ser, exp);
+
+		let event_dec: FollowEvent<String> = serde_json::from_str(exp).unwrap();
+		assert_eq!(event_dec, event);
 	}
 
 	#[test]
@@ -547,6 +660,22 @@ mod tests {
 		assert_eq!(event_dec, event);
 	}
 
+	#[test]
+	fn follow_op_storage_proof_event() {
+		let event: FollowEvent<String> =
+			FollowEvent::OperationStorageProof(OperationStorageProof {
+				operation_id: "123".into(),
+				proof: "0x1".into(),
+			});
+
+		let ser = serde_json::to_string(&event).unwrap();
+		let exp = r#"{"event":"operationStorageProof","operationId":"123","proof":"0x1"}"#;
+		assert_eq!(ser, exp);
+
+		let event_dec: FollowEvent<String> = serde_json::from_str(exp).unwrap();
+		assert_eq!(event_dec, event);
+	}
+
 	#[test]
 	fn follow_op_wait_event() {
 		let event: FollowEvent<String> =
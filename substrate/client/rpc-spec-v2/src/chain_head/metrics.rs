@@ -0,0 +1,60 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Prometheus metrics of the `chainHead` operations.
+
+use prometheus_endpoint::{register, HistogramOpts, HistogramVec, PrometheusError, Registry};
+use std::time::Duration;
+
+use super::event::OperationPriority;
+
+/// Upper bounds [s] of the buckets of the histogram over the queue wait times.
+const QUEUE_WAIT_BUCKETS: [f64; 10] = [0.0001, 0.001, 0.005, 0.01, 0.05, 0.1, 0.5, 1.0, 5.0, 10.0];
+
+/// The metrics of the `chainHead` operations.
+#[derive(Debug, Clone)]
+pub struct ChainHeadMetrics {
+	/// The time the operations waited for the ongoing operations of their subscription to end.
+	queue_wait_time: HistogramVec,
+}
+
+impl ChainHeadMetrics {
+	/// Create and register the metrics in the given registry.
+	pub fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		Ok(ChainHeadMetrics {
+			queue_wait_time: register(
+				HistogramVec::new(
+					HistogramOpts::new(
+						"substrate_rpc_chain_head_operation_queue_wait_seconds",
+						"Time the chainHead operations waited to start, by priority",
+					)
+					.buckets(QUEUE_WAIT_BUCKETS.to_vec()),
+					&["priority"],
+				)?,
+				registry,
+			)?,
+		})
+	}
+
+	/// Record the time an operation of the given priority waited to start.
+	pub(crate) fn observe_queue_wait(&self, priority: OperationPriority, wait: Duration) {
+		self.queue_wait_time
+			.with_label_values(&[priority.as_str()])
+			.observe(wait.as_secs_f64());
+	}
+}
@@ -0,0 +1,394 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Resumption of the `chainHead_follow` subscriptions from another connection.
+//!
+//! A resumable subscription reports a token in its `Initialized` event. When its connection is
+//! lost, the subscription keeps running for a grace period while its events are kept, such that
+//! the client can resume it with `chainHead_resumeFollow` without losing its pinned blocks.
+//!
+//! # Note
+//!
+//! The loss of the connection can't be told apart from the client unsubscribing, such that an
+//! unsubscribed resumable subscription is also kept for the grace period.
+
+use crate::session::{generate_token, ResumableSink, SubscriptionStopped};
+use futures::FutureExt;
+use jsonrpsee::{ConnectionId, SubscriptionSink};
+use parking_lot::Mutex;
+use sc_rpc::utils::to_sub_message;
+use schnellru::{ByLength, LruMap};
+use serde::Serialize;
+use std::{collections::VecDeque, sync::Arc, time::Duration};
+use tokio::sync::watch;
+
+use super::event::FollowEvent;
+
+/// The maximum number of events kept for a resumable subscription while its connection is lost.
+///
+/// The subscriptions missing more events are stopped.
+const MAX_MISSED_EVENTS: usize = 512;
+
+/// The state of a resumable subscription.
+struct FollowState {
+	/// The sink the events are sent to, until the connection is lost.
+	sink: Option<Arc<SubscriptionSink>>,
+	/// The connection the subscription is attached to.
+	connection: Option<ConnectionId>,
+	/// Incremented every time the subscription is resumed.
+	generation: u64,
+	/// The missed events are being replayed to the sink.
+	replaying: bool,
+	/// The serialized events missed while the connection was lost.
+	missed: VecDeque<serde_json::Value>,
+	/// The subscription can be resumed, i.e. its token was not evicted.
+	resumable: bool,
+	/// The subscription is stopped.
+	stopped: bool,
+}
+
+impl FollowState {
+	/// The sink of the subscription, if it's not closed.
+	fn attached_sink(&self) -> Option<&Arc<SubscriptionSink>> {
+		self.sink.as_ref().filter(|sink| !sink.is_closed())
+	}
+}
+
+/// Where an event of a resumable subscription must be sent.
+enum Route {
+	/// To the sink of the subscription.
+	Direct(Arc<SubscriptionSink>),
+	/// Nowhere, the event was kept until the subscription is resumed.
+	Kept,
+	/// Nowhere, the subscription is stopped.
+	Stopped,
+}
+
+/// A `chainHead_follow` subscription which can be resumed with its token.
+pub(crate) struct ResumableFollow {
+	/// The resumption token.
+	token: String,
+	/// The identifier of the subscription.
+	subscription: String,
+	/// The state of the subscription.
+	state: Mutex<FollowState>,
+	/// Notified when the subscription is resumed or stopped.
+	changed: watch::Sender<()>,
+}
+
+impl ResumableFollow {
+	/// Where to send the event. The event is kept if the connection is lost, or the missed events
+	/// are being replayed.
+	fn route(&self, event: &serde_json::Value) -> Route {
+		let mut state = self.state.lock();
+		if state.stopped {
+			return Route::Stopped
+		}
+
+		match state.attached_sink() {
+			Some(sink) if !state.replaying => Route::Direct(sink.clone()),
+			_ => self.keep(&mut state, event),
+		}
+	}
+
+	fn keep(&self, state: &mut FollowState, event: &serde_json::Value) -> Route {
+		let evicted = !state.resumable && !state.replaying;
+		if evicted || state.missed.len() >= MAX_MISSED_EVENTS {
+			self.stop_locked(state);
+			return Route::Stopped
+		}
+
+		state.missed.push_back(event.clone());
+		Route::Kept
+	}
+
+	fn stop_locked(&self, state: &mut FollowState) {
+		state.sink = None;
+		state.connection = None;
+		state.missed.clear();
+		state.stopped = true;
+		self.changed.send_replace(());
+	}
+
+	/// Stop the subscription.
+	fn stop(&self) {
+		let mut state = self.state.lock();
+		self.stop_locked(&mut state);
+	}
+
+	/// The token was evicted: the subscription is stopped once its connection is lost.
+	fn evict(&self) {
+		let mut state = self.state.lock();
+		state.resumable = false;
+		if state.attached_sink().is_none() {
+			self.stop_locked(&mut state);
+		}
+	}
+
+	/// Attach the subscription to the sink of the connection it's resumed from, returning its new
+	/// generation.
+	///
+	/// The subscription is taken over from the connection it is attached to, if any: the network
+	/// of the client may change before the node notices the previous connection is lost.
+	fn attach(&self, sink: Arc<SubscriptionSink>) -> Option<u64> {
+		let mut state = self.state.lock();
+		if state.stopped || !state.resumable {
+			return None
+		}
+
+		state.connection = Some(sink.connection_id());
+		state.sink = Some(sink);
+		state.replaying = true;
+		state.generation += 1;
+		self.changed.send_replace(());
+		Some(state.generation)
+	}
+
+	/// The next missed event to replay, unless the subscription was resumed again.
+	fn next_missed(&self, generation: u64) -> Option<serde_json::Value> {
+		let mut state = self.state.lock();
+		if state.generation != generation || state.stopped {
+			return None
+		}
+
+		let event = state.missed.front().cloned();
+		if event.is_none() {
+			state.replaying = false;
+		}
+		event
+	}
+
+	/// Acknowledge the replayed event returned by [`Self::next_missed`].
+	fn replayed(&self, generation: u64) {
+		let mut state = self.state.lock();
+		if state.generation == generation {
+			state.missed.pop_front();
+		}
+	}
+
+	/// Detach the subscription from its connection, once its sink is closed.
+	fn detach(&self, generation: u64) {
+		let mut state = self.state.lock();
+		if state.generation != generation {
+			return
+		}
+
+		state.sink = None;
+		state.connection = None;
+		state.replaying = false;
+		if !state.resumable {
+			self.stop_locked(&mut state);
+		}
+	}
+
+	/// Stop the subscription, unless it was resumed since it was detached.
+	fn expire(&self, generation: u64) {
+		let mut state = self.state.lock();
+		if state.generation == generation && state.sink.is_none() {
+			self.stop_locked(&mut state);
+		}
+	}
+
+	/// Resume the subscription on the connection of the sink.
+	///
+	/// The events missed since the connection was lost are replayed first, then the new events
+	/// are sent to the sink.
+	pub async fn resume(&self, sink: SubscriptionSink) {
+		let sink = Arc::new(sink);
+		let Some(generation) = self.attach(sink.clone()) else {
+			// The subscription was stopped in the meantime.
+			let _ = sink.send(to_sub_message(&sink, &FollowEvent::<String>::Stop)).await;
+			return
+		};
+
+		while let Some(event) = self.next_missed(generation) {
+			if sink.send(to_sub_message(&sink, &event)).await.is_err() {
+				return
+			}
+			self.replayed(generation);
+		}
+	}
+}
+
+/// Keeps track of the resumable `chainHead_follow` subscriptions, by token.
+///
+/// The tokens of the least recently used subscriptions are evicted once the limit is reached.
+#[derive(Clone)]
+pub(crate) struct ResumableSubscriptions {
+	/// The resumable subscriptions by token.
+	follows: Arc<Mutex<LruMap<String, Arc<ResumableFollow>>>>,
+	/// The maximum number of resumable subscriptions.
+	max_subscriptions: usize,
+	/// The maximum duration a subscription is kept after its connection is lost.
+	grace_period: Duration,
+}
+
+impl ResumableSubscriptions {
+	/// Create a new [`ResumableSubscriptions`].
+	pub fn new(max_subscriptions: usize, grace_period: Duration) -> Self {
+		let limit = u32::try_from(max_subscriptions).unwrap_or(u32::MAX);
+		ResumableSubscriptions {
+			follows: Arc::new(Mutex::new(LruMap::new(ByLength::new(limit)))),
+			max_subscriptions,
+			grace_period,
+		}
+	}
+
+	/// Make the accepted subscription resumable, returning its sink.
+	pub fn insert(&self, subscription: String, sink: SubscriptionSink) -> FollowSink {
+		let mut follows = self.follows.lock();
+
+		let mut token = generate_token();
+		while follows.peek(&token).is_some() {
+			token = generate_token();
+		}
+		if follows.len() >= self.max_subscriptions {
+			if let Some((_, evicted)) = follows.pop_oldest() {
+				evicted.evict();
+			}
+		}
+
+		let follow = Arc::new(ResumableFollow {
+			token: token.clone(),
+			subscription,
+			state: Mutex::new(FollowState {
+				connection: Some(sink.connection_id()),
+				sink: Some(Arc::new(sink)),
+				generation: 0,
+				replaying: false,
+				missed: Default::default(),
+				resumable: true,
+				stopped: false,
+			}),
+			changed: watch::channel(()).0,
+		});
+		follows.insert(token, follow.clone());
+
+		FollowSink::Resumable(ResumableFollowSink { subscriptions: self.clone(), follow })
+	}
+
+	/// The subscription of the token.
+	pub fn get(&self, token: &str) -> Option<Arc<ResumableFollow>> {
+		self.follows.lock().get(token).cloned()
+	}
+
+	/// Whether the subscription was resumed on the connection.
+	pub fn contains_subscription(&self, connection_id: ConnectionId, subscription: &str) -> bool {
+		self.follows.lock().iter().any(|(_, follow)| {
+			follow.subscription == subscription && {
+				let state = follow.state.lock();
+				!state.stopped && state.connection == Some(connection_id)
+			}
+		})
+	}
+}
+
+/// The sink of a resumable `chainHead_follow` subscription, see [`FollowSink`].
+pub(crate) struct ResumableFollowSink {
+	/// The resumable subscriptions.
+	subscriptions: ResumableSubscriptions,
+	/// The subscription.
+	follow: Arc<ResumableFollow>,
+}
+
+impl ResumableFollowSink {
+	async fn send(&self, event: &impl Serialize) -> Result<(), SubscriptionStopped> {
+		let event = serde_json::to_value(event).expect("Serialize infallible; qed");
+		loop {
+			let sink = match self.follow.route(&event) {
+				Route::Direct(sink) => sink,
+				Route::Kept => return Ok(()),
+				Route::Stopped => return Err(SubscriptionStopped),
+			};
+			if sink.send(to_sub_message(&sink, &event)).await.is_ok() {
+				return Ok(())
+			}
+			// The sink is closed once sending fails: the event is routed again, to be kept until
+			// the subscription is resumed.
+		}
+	}
+
+	async fn closed(&self) {
+		loop {
+			let mut changed = self.follow.changed.subscribe();
+			let (sink, generation) = {
+				let state = self.follow.state.lock();
+				if state.stopped {
+					return
+				}
+				(state.sink.clone(), state.generation)
+			};
+
+			match sink {
+				Some(sink) => futures::select! {
+					_ = sink.closed().fuse() => self.follow.detach(generation),
+					_ = changed.changed().fuse() => {},
+				},
+				None => futures::select! {
+					_ = tokio::time::sleep(self.subscriptions.grace_period).fuse() =>
+						self.follow.expire(generation),
+					_ = changed.changed().fuse() => {},
+				},
+			}
+		}
+	}
+}
+
+impl Drop for ResumableFollowSink {
+	fn drop(&mut self) {
+		self.follow.stop();
+		self.subscriptions.follows.lock().remove(&self.follow.token);
+	}
+}
+
+/// The sink of a `chainHead_follow` subscription.
+pub(crate) enum FollowSink {
+	/// The subscription can't be resumed with a token, but may be part of a session.
+	Sink(ResumableSink),
+	/// The subscription can be resumed with a token.
+	Resumable(ResumableFollowSink),
+}
+
+impl FollowSink {
+	/// The token to resume the subscription with, if any.
+	pub fn resumption_token(&self) -> Option<String> {
+		match self {
+			FollowSink::Sink(_) => None,
+			FollowSink::Resumable(sink) => Some(sink.follow.token.clone()),
+		}
+	}
+
+	/// Send the event to the client, or keep it until the subscription is resumed.
+	pub async fn send(&self, event: &impl Serialize) -> Result<(), SubscriptionStopped> {
+		match self {
+			FollowSink::Sink(sink) => sink.send(event).await,
+			FollowSink::Resumable(sink) => sink.send(event).await,
+		}
+	}
+
+	/// Completes when the subscription is stopped.
+	///
+	/// A resumable subscription is stopped once the grace period elapses after its connection is
+	/// lost, unless it's resumed in the meantime.
+	pub async fn closed(&self) {
+		match self {
+			FollowSink::Sink(sink) => sink.closed().await,
+			FollowSink::Resumable(sink) => sink.closed().await,
+		}
+	}
+}
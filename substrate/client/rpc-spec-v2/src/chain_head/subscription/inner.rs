@@ -22,12 +22,14 @@ use sc_client_api::Backend;
 use sc_utils::mpsc::{tracing_unbounded, TracingUnboundedReceiver, TracingUnboundedSender};
 use sp_runtime::traits::Block as BlockT;
 use std::{
-	collections::{hash_map::Entry, HashMap, HashSet},
+	collections::{hash_map::Entry, BTreeMap, HashMap, HashSet},
 	sync::{atomic::AtomicBool, Arc},
 	time::{Duration, Instant},
 };
 
-use crate::chain_head::{subscription::SubscriptionManagementError, FollowEvent};
+use crate::chain_head::{
+	subscription::SubscriptionManagementError, FollowEvent, OperationPriority,
+};
 
 /// The queue size after which the `sc_utils::mpsc::tracing_unbounded` would produce warnings.
 const QUEUE_SIZE_WARNING: usize = 512;
@@ -109,15 +111,51 @@ impl BlockStateMachine {
 }
 
 /// Limit the number of ongoing operations across methods.
+///
+/// The operations waiting for capacity are started by decreasing priority, then in the order
+/// they were queued.
 struct LimitOperations {
-	/// Limit the number of ongoing operations for this subscription.
-	semaphore: Arc<tokio::sync::Semaphore>,
+	/// The capacity shared with the permits, which give it back on drop.
+	state: Arc<Mutex<LimitState>>,
+}
+
+/// The capacity of [`LimitOperations`] and the operations waiting for it.
+#[derive(Default)]
+struct LimitState {
+	/// The number of operations that can be started.
+	available: usize,
+	/// Orders the queued operations of the same priority.
+	next_waiter: u64,
+	/// The queued operations, with the number of items they requested.
+	///
+	/// No capacity is available while operations are queued.
+	queue: BTreeMap<(OperationPriority, u64), (usize, oneshot::Sender<PermitOperations>)>,
+}
+
+/// The capacity reserved by [`LimitOperations::reserve_or_queue()`].
+pub enum Reservation {
+	/// The capacity is reserved.
+	Reserved(PermitOperations),
+	/// The operation is queued, the capacity is sent once available.
+	Queued(oneshot::Receiver<PermitOperations>),
 }
 
 impl LimitOperations {
 	/// Constructs a new [`LimitOperations`].
 	fn new(max_operations: usize) -> Self {
-		LimitOperations { semaphore: Arc::new(tokio::sync::Semaphore::new(max_operations)) }
+		let state = LimitState { available: max_operations, ..Default::default() };
+		LimitOperations { state: Arc::new(Mutex::new(state)) }
+	}
+
+	/// Take at most `to_reserve` operations from the available capacity.
+	fn take(&self, state: &mut LimitState, to_reserve: usize) -> Option<PermitOperations> {
+		let num_ops = std::cmp::min(state.available, to_reserve);
+		if num_ops == 0 {
+			return None
+		}
+
+		state.available -= num_ops;
+		Some(PermitOperations { num_ops, limits: self.state.clone() })
 	}
 
 	/// Reserves capacity to execute at least one operation and at most the requested items.
@@ -128,17 +166,33 @@ impl LimitOperations {
 	/// Returns nothing if there's no space available, else returns a permit
 	/// that guarantees that at least one operation can be executed.
 	fn reserve_at_most(&self, to_reserve: usize) -> Option<PermitOperations> {
-		let num_ops = std::cmp::min(self.semaphore.available_permits(), to_reserve);
+		let mut state = self.state.lock();
+		self.take(&mut state, to_reserve)
+	}
 
-		if num_ops == 0 {
+	/// Reserves capacity to execute at least one operation and at most the requested items, or
+	/// queues the operation with the given priority until capacity is available.
+	///
+	/// Returns nothing if no items are requested.
+	fn reserve_or_queue(
+		&self,
+		to_reserve: usize,
+		priority: OperationPriority,
+	) -> Option<Reservation> {
+		if to_reserve == 0 {
 			return None
 		}
 
-		let permits = Arc::clone(&self.semaphore)
-			.try_acquire_many_owned(num_ops.try_into().ok()?)
-			.ok()?;
+		let mut state = self.state.lock();
+		if let Some(permit) = self.take(&mut state, to_reserve) {
+			return Some(Reservation::Reserved(permit))
+		}
 
-		Some(PermitOperations { num_ops, _permit: permits })
+		let (tx, rx) = oneshot::channel();
+		let waiter = state.next_waiter;
+		state.next_waiter += 1;
+		state.queue.insert((priority, waiter), (to_reserve, tx));
+		Some(Reservation::Queued(rx))
 	}
 }
 
@@ -147,12 +201,38 @@ impl LimitOperations {
 /// [`PermitOperations`] are returned by [`LimitOperations::reserve()`] and are used
 /// to guarantee the RPC server can execute the number of operations.
 ///
-/// The number of reserved items are given back to the [`LimitOperations`] on drop.
-struct PermitOperations {
+/// The number of reserved items are given back to the [`LimitOperations`] on drop, and handed
+/// to the queued operations.
+pub struct PermitOperations {
 	/// The number of operations permitted (reserved).
 	num_ops: usize,
-	/// The permit for these operations.
-	_permit: tokio::sync::OwnedSemaphorePermit,
+	/// The capacity the operations are given back to.
+	limits: Arc<Mutex<LimitState>>,
+}
+
+impl Drop for PermitOperations {
+	fn drop(&mut self) {
+		if self.num_ops == 0 {
+			return
+		}
+
+		let mut state = self.limits.lock();
+		state.available += self.num_ops;
+
+		while state.available > 0 {
+			let Some((_, (to_reserve, sender))) = state.queue.pop_first() else { break };
+
+			let num_ops = std::cmp::min(state.available, to_reserve);
+			state.available -= num_ops;
+			let permit = PermitOperations { num_ops, limits: self.limits.clone() };
+
+			if let Err(mut permit) = sender.send(permit) {
+				// The operation stopped waiting, give the capacity back without locking again.
+				state.available += permit.num_ops;
+				permit.num_ops = 0;
+			}
+		}
+	}
 }
 
 /// The state of one operation.
@@ -307,7 +387,23 @@ impl Operations {
 	/// Register a new operation.
 	pub fn register_operation(&mut self, to_reserve: usize) -> Option<RegisteredOperation> {
 		let permit = self.limits.reserve_at_most(to_reserve)?;
+		Some(self.register_operation_with_permit(permit))
+	}
 
+	/// Reserve capacity for a new operation, or queue it with the given priority.
+	pub fn reserve_operation(
+		&self,
+		to_reserve: usize,
+		priority: OperationPriority,
+	) -> Option<Reservation> {
+		self.limits.reserve_or_queue(to_reserve, priority)
+	}
+
+	/// Register a new operation with the capacity reserved by [`Self::reserve_operation`].
+	pub fn register_operation_with_permit(
+		&mut self,
+		permit: PermitOperations,
+	) -> RegisteredOperation {
 		let operation_id = self.next_operation_id();
 
 		// At most one message can be sent.
@@ -320,7 +416,7 @@ impl Operations {
 		let operations = self.operations.clone();
 		operations.lock().insert(operation_id.clone(), state);
 
-		Some(RegisteredOperation { shared_state, operation_id, recv_continue, operations, permit })
+		RegisteredOperation { shared_state, operation_id, recv_continue, operations, permit }
 	}
 
 	/// Get the associated operation state with the ID.
@@ -474,6 +570,20 @@ impl<Block: BlockT> SubscriptionState<Block> {
 		self.operations.register_operation(to_reserve)
 	}
 
+	/// Reserve capacity for a new operation, or queue it with the given priority.
+	fn reserve_operation(
+		&self,
+		to_reserve: usize,
+		priority: OperationPriority,
+	) -> Option<Reservation> {
+		self.operations.reserve_operation(to_reserve, priority)
+	}
+
+	/// Register a new operation with the capacity reserved by [`Self::reserve_operation`].
+	fn register_operation_with_permit(&mut self, permit: PermitOperations) -> RegisteredOperation {
+		self.operations.register_operation_with_permit(permit)
+	}
+
 	/// Get the associated operation state with the ID.
 	pub fn get_operation(&self, id: &str) -> Option<OperationState> {
 		self.operations.get_operation(id)
@@ -837,6 +947,52 @@ impl<Block: BlockT, BE: Backend<Block>> SubscriptionsInner<Block, BE> {
 		)
 	}
 
+	/// Reserve capacity for operations on the given block, or queue them with the given
+	/// priority until the ongoing operations of the subscription free some capacity.
+	pub fn reserve_operations(
+		&mut self,
+		sub_id: &str,
+		hash: Block::Hash,
+		to_reserve: usize,
+		priority: OperationPriority,
+	) -> Result<Reservation, SubscriptionManagementError> {
+		let Some(sub) = self.subs.get(sub_id) else {
+			return Err(SubscriptionManagementError::SubscriptionAbsent)
+		};
+
+		if !sub.contains_block(hash) {
+			return Err(SubscriptionManagementError::BlockHashAbsent)
+		}
+
+		sub.reserve_operation(to_reserve, priority)
+			.ok_or(SubscriptionManagementError::ExceededLimits)
+	}
+
+	/// Same as [`Self::lock_block`], with the capacity reserved by [`Self::reserve_operations`].
+	pub fn lock_block_with_permit(
+		&mut self,
+		sub_id: &str,
+		hash: Block::Hash,
+		permit: PermitOperations,
+	) -> Result<BlockGuard<Block, BE>, SubscriptionManagementError> {
+		let Some(sub) = self.subs.get_mut(sub_id) else {
+			return Err(SubscriptionManagementError::SubscriptionAbsent)
+		};
+
+		if !sub.contains_block(hash) {
+			return Err(SubscriptionManagementError::BlockHashAbsent)
+		}
+
+		let operation = sub.register_operation_with_permit(permit);
+		BlockGuard::new(
+			hash,
+			sub.with_runtime,
+			sub.response_sender.clone(),
+			operation,
+			self.backend.clone(),
+		)
+	}
+
 	pub fn get_operation(&mut self, sub_id: &str, id: &str) -> Option<OperationState> {
 		let state = self.subs.get(sub_id)?;
 		state.get_operation(id)
@@ -1367,6 +1523,48 @@ mod tests {
 		assert_eq!(permit_three.num_ops, 1);
 	}
 
+	#[test]
+	fn queued_operations_by_priority() {
+		let ops = LimitOperations::new(2);
+
+		let Some(Reservation::Reserved(permit)) = ops.reserve_or_queue(2, OperationPriority::Low)
+		else {
+			panic!("Capacity is available")
+		};
+		assert_eq!(permit.num_ops, 2);
+
+		// The limit is reached, the operations are queued.
+		let queue = |priority| match ops.reserve_or_queue(2, priority) {
+			Some(Reservation::Queued(rx)) => rx,
+			_ => panic!("The limit is reached"),
+		};
+		let mut low = queue(OperationPriority::Low);
+		let mut normal = queue(OperationPriority::Normal);
+		let mut high = queue(OperationPriority::High);
+		let mut stopped = queue(OperationPriority::High);
+		stopped.close();
+		assert!(ops.reserve_at_most(1).is_none());
+
+		// The high priority operation gets the released capacity.
+		drop(permit);
+		let high_permit = high.try_recv().unwrap().unwrap();
+		assert_eq!(high_permit.num_ops, 2);
+		assert!(normal.try_recv().unwrap().is_none());
+
+		// The operation which stopped waiting is skipped.
+		drop(high_permit);
+		let normal_permit = normal.try_recv().unwrap().unwrap();
+		assert_eq!(normal_permit.num_ops, 2);
+		assert!(low.try_recv().unwrap().is_none());
+
+		drop(normal_permit);
+		let low_permit = low.try_recv().unwrap().unwrap();
+		drop(low_permit);
+
+		// No operation is queued anymore.
+		assert_eq!(ops.reserve_at_most(2).unwrap().num_ops, 2);
+	}
+
 	#[test]
 	fn stop_all_subscriptions() {
 		let (backend, client) = init_backend();
@@ -26,11 +26,11 @@ mod error;
 mod inner;
 
 use crate::{
-	chain_head::chain_head::LOG_TARGET,
+	chain_head::{chain_head::LOG_TARGET, OperationPriority},
 	common::connections::{RegisteredConnection, ReservedConnection, RpcConnections},
 };
 
-use self::inner::SubscriptionsInner;
+use self::inner::{Reservation, SubscriptionsInner};
 
 pub use self::inner::OperationState;
 pub use error::SubscriptionManagementError;
@@ -176,6 +176,39 @@ impl<Block: BlockT, BE: Backend<Block>> SubscriptionManagement<Block, BE> {
 		inner.lock_block(sub_id, hash, to_reserve)
 	}
 
+	/// Same as [`Self::lock_block`], except that the operation waits for at most `max_wait` for
+	/// the ongoing operations of the subscription to end if their limit is reached.
+	///
+	/// The waiting operations are started by decreasing priority, then in the order they were
+	/// requested.
+	pub async fn lock_block_with_priority(
+		&self,
+		sub_id: &str,
+		hash: Block::Hash,
+		to_reserve: usize,
+		priority: OperationPriority,
+		max_wait: Duration,
+	) -> Result<BlockGuard<Block, BE>, SubscriptionManagementError> {
+		if max_wait.is_zero() {
+			return self.lock_block(sub_id, hash, to_reserve)
+		}
+
+		let reservation =
+			self.inner.write().reserve_operations(sub_id, hash, to_reserve, priority)?;
+		let permit = match reservation {
+			Reservation::Reserved(permit) => permit,
+			Reservation::Queued(permit) => match tokio::time::timeout(max_wait, permit).await {
+				Ok(Ok(permit)) => permit,
+				// The limit is still reached.
+				_ => return Err(SubscriptionManagementError::ExceededLimits),
+			},
+		};
+
+		// The block might have been unpinned in the meantime.
+		let mut inner = self.inner.write();
+		inner.lock_block_with_permit(sub_id, hash, permit)
+	}
+
 	/// Get the operation state.
 	pub fn get_operation(&self, sub_id: &str, operation_id: &str) -> Option<OperationState> {
 		let mut inner = self.inner.write();
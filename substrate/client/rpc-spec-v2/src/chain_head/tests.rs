@@ -34,7 +34,7 @@ use jsonrpsee::{
 };
 
 use sc_block_builder::BlockBuilderBuilder;
-use sc_client_api::ChildInfo;
+use sc_client_api::{ChildInfo, StorageKey, StorageProvider};
 use sc_service::client::new_in_mem;
 use sp_blockchain::HeaderBackend;
 use sp_consensus::BlockOrigin;
@@ -90,6 +90,9 @@ pub async fn run_server() -> std::net::SocketAddr {
 			operation_max_storage_items: MAX_PAGINATION_LIMIT,
 			max_follow_subscriptions_per_connection: 1,
 			max_lagging_distance: MAX_LAGGING_DISTANCE,
+			operation_max_queue_duration: Duration::ZERO,
+			max_resumable_subscriptions: 0,
+			resumable_subscription_grace_period: Duration::ZERO,
 		},
 	)
 	.into_rpc();
@@ -152,6 +155,9 @@ async fn setup_api() -> (
 			operation_max_storage_items: MAX_PAGINATION_LIMIT,
 			max_lagging_distance: MAX_LAGGING_DISTANCE,
 			max_follow_subscriptions_per_connection: MAX_FOLLOW_SUBSCRIPTIONS_PER_CONNECTION,
+			operation_max_queue_duration: Duration::ZERO,
+			max_resumable_subscriptions: 0,
+			resumable_subscription_grace_period: Duration::ZERO,
 		},
 	)
 	.into_rpc();
@@ -205,6 +211,9 @@ async fn follow_subscription_produces_blocks() {
 
 			max_lagging_distance: MAX_LAGGING_DISTANCE,
 			max_follow_subscriptions_per_connection: MAX_FOLLOW_SUBSCRIPTIONS_PER_CONNECTION,
+			operation_max_queue_duration: Duration::ZERO,
+			max_resumable_subscriptions: 0,
+			resumable_subscription_grace_period: Duration::ZERO,
 		},
 	)
 	.into_rpc();
@@ -217,6 +226,7 @@ async fn follow_subscription_produces_blocks() {
 	let expected = FollowEvent::Initialized(Initialized {
 		finalized_block_hashes: vec![format!("{:?}", finalized_hash)],
 		finalized_block_runtime: None,
+		resumption_token: None,
 		with_runtime: false,
 	});
 	assert_eq!(event, expected);
@@ -258,6 +268,97 @@ async fn follow_subscription_produces_blocks() {
 	assert_eq!(event, expected);
 }
 
+#[tokio::test]
+async fn follow_finalized_reports_only_finalized_blocks() {
+	let builder = TestClientBuilder::new();
+	let backend = builder.backend();
+	let mut client = Arc::new(builder.build());
+
+	let api = ChainHead::new(
+		client.clone(),
+		backend,
+		Arc::new(TaskExecutor::default()),
+		ChainHeadConfig {
+			global_max_pinned_blocks: MAX_PINNED_BLOCKS,
+			subscription_max_pinned_duration: Duration::from_secs(MAX_PINNED_SECS),
+			subscription_max_ongoing_operations: MAX_OPERATIONS,
+			operation_max_storage_items: MAX_PAGINATION_LIMIT,
+			max_lagging_distance: MAX_LAGGING_DISTANCE,
+			max_follow_subscriptions_per_connection: MAX_FOLLOW_SUBSCRIPTIONS_PER_CONNECTION,
+			operation_max_queue_duration: Duration::ZERO,
+			max_resumable_subscriptions: 0,
+			resumable_subscription_grace_period: Duration::ZERO,
+		},
+	)
+	.into_rpc();
+
+	let finalized_hash = client.info().finalized_hash;
+	let mut sub = api
+		.subscribe_unbounded("chainHead_unstable_followFinalized", [false])
+		.await
+		.unwrap();
+
+	let event: FollowEvent<String> = get_next_event(&mut sub).await;
+	let expected = FollowEvent::Initialized(Initialized {
+		finalized_block_hashes: vec![format!("{:?}", finalized_hash)],
+		finalized_block_runtime: None,
+		resumption_token: None,
+		with_runtime: false,
+	});
+	assert_eq!(event, expected);
+
+	// Import two blocks without finalizing them.
+	let block_1 = BlockBuilderBuilder::new(&*client)
+		.on_parent_block(client.chain_info().genesis_hash)
+		.with_parent_block_number(0)
+		.build()
+		.unwrap()
+		.build()
+		.unwrap()
+		.block;
+	let block_1_hash = block_1.header.hash();
+	client.import(BlockOrigin::Own, block_1.clone()).await.unwrap();
+
+	let block_2 = BlockBuilderBuilder::new(&*client)
+		.on_parent_block(block_1_hash)
+		.with_parent_block_number(1)
+		.build()
+		.unwrap()
+		.build()
+		.unwrap()
+		.block;
+	let block_2_hash = block_2.header.hash();
+	client.import(BlockOrigin::Own, block_2.clone()).await.unwrap();
+
+	// The blocks are only reported once finalized, without any best block events.
+	client.finalize_block(block_2_hash, None).unwrap();
+
+	let event: FollowEvent<String> = get_next_event(&mut sub).await;
+	let expected = FollowEvent::NewBlock(NewBlock {
+		block_hash: format!("{:?}", block_1_hash),
+		parent_block_hash: format!("{:?}", finalized_hash),
+		new_runtime: None,
+		with_runtime: false,
+	});
+	assert_eq!(event, expected);
+
+	let event: FollowEvent<String> = get_next_event(&mut sub).await;
+	let expected = FollowEvent::NewBlock(NewBlock {
+		block_hash: format!("{:?}", block_2_hash),
+		parent_block_hash: format!("{:?}", block_1_hash),
+		new_runtime: None,
+		with_runtime: false,
+	});
+	assert_eq!(event, expected);
+
+	let event: FollowEvent<String> = get_next_event(&mut sub).await;
+	let expected = FollowEvent::Finalized(Finalized {
+		finalized_block_hashes: vec![format!("{:?}", block_1_hash), format!("{:?}", block_2_hash)],
+		pruned_block_hashes: vec![],
+	});
+	assert_eq!(event, expected);
+}
+
 #[tokio::test]
 async fn follow_with_runtime() {
 	let builder = TestClientBuilder::new();
@@ -276,6 +377,9 @@ async fn follow_with_runtime() {
 
 			max_lagging_distance: MAX_LAGGING_DISTANCE,
 			max_follow_subscriptions_per_connection: MAX_FOLLOW_SUBSCRIPTIONS_PER_CONNECTION,
+			operation_max_queue_duration: Duration::ZERO,
+			max_resumable_subscriptions: 0,
+			resumable_subscription_grace_period: Duration::ZERO,
 		},
 	)
 	.into_rpc();
@@ -302,6 +406,7 @@ async fn follow_with_runtime() {
 	let expected = FollowEvent::Initialized(Initialized {
 		finalized_block_hashes: vec![format!("{:?}", finalized_hash)],
 		finalized_block_runtime,
+		resumption_token: None,
 		with_runtime: false,
 	});
 	pretty_assertions::assert_eq!(event, expected);
@@ -573,6 +678,64 @@ async fn call_runtime() {
 	);
 }
 
+#[tokio::test]
+async fn call_runtime_with_proof() {
+	let (client, api, mut block_sub, sub_id, block) = setup_api().await;
+	let block_hash = format!("{:?}", block.header.hash());
+
+	let alice_id = AccountKeyring::Alice.to_account_id();
+	let call_parameters = hex_string(&alice_id.encode());
+	let response: MethodResponse = api
+		.call(
+			"chainHead_unstable_call",
+			rpc_params![
+				&sub_id,
+				&block_hash,
+				"AccountNonceApi_account_nonce",
+				&call_parameters,
+				None::<OperationPriority>,
+				true
+			],
+		)
+		.await
+		.unwrap();
+	let operation_id = match response {
+		MethodResponse::Started(started) => started.operation_id,
+		MethodResponse::LimitReached => panic!("Expected started response"),
+	};
+
+	let proof = match get_next_event::<FollowEvent<String>>(&mut block_sub).await {
+		FollowEvent::OperationCallDone(done) if done.operation_id == operation_id => {
+			assert_eq!(done.output, "0x0000000000000000");
+			done.proof.expect("The proof was requested")
+		},
+		event => panic!("Expected OperationCallDone, got {event:?}"),
+	};
+
+	// The proof contains the account read by the call, at the state root of the block.
+	let proof = array_bytes::hex2bytes(&proof).unwrap();
+	let proof = sc_client_api::CompactProof::decode(&mut &proof[..]).unwrap();
+	let (proof, root) =
+		proof.to_storage_proof::<Blake2Hasher>(Some(&block.header.state_root)).unwrap();
+	assert_eq!(root, block.header.state_root);
+
+	let alice = alice_id.encode();
+	let account_key = [
+		&sp_crypto_hashing::twox_128(b"System")[..],
+		&sp_crypto_hashing::twox_128(b"Account")[..],
+		&sp_crypto_hashing::blake2_128(&alice)[..],
+		&alice[..],
+	]
+	.concat();
+	let values =
+		sp_state_machine::read_proof_check::<Blake2Hasher, _>(root, proof, [&account_key]).unwrap();
+	let expected = client
+		.storage(block.header.hash(), &StorageKey(account_key.clone()))
+		.unwrap()
+		.map(|data| data.0);
+	assert_eq!(values.get(&account_key), Some(&expected));
+}
+
 #[tokio::test]
 async fn call_runtime_without_flag() {
 	let builder = TestClientBuilder::new();
@@ -591,6 +754,9 @@ async fn call_runtime_without_flag() {
 
 			max_lagging_distance: MAX_LAGGING_DISTANCE,
 			max_follow_subscriptions_per_connection: MAX_FOLLOW_SUBSCRIPTIONS_PER_CONNECTION,
+			operation_max_queue_duration: Duration::ZERO,
+			max_resumable_subscriptions: 0,
+			resumable_subscription_grace_period: Duration::ZERO,
 		},
 	)
 	.into_rpc();
@@ -784,6 +950,67 @@ async fn get_storage_hash() {
 	);
 }
 
+#[tokio::test]
+async fn get_storage_proof() {
+	let (mut client, api, mut block_sub, sub_id, block) = setup_api().await;
+	let key = hex_string(&KEY);
+
+	// Import a new block with storage changes.
+	let mut builder = BlockBuilderBuilder::new(&*client)
+		.on_parent_block(block.hash())
+		.with_parent_block_number(1)
+		.build()
+		.unwrap();
+	builder.push_storage_change(KEY.to_vec(), Some(VALUE.to_vec())).unwrap();
+	let block = builder.build().unwrap().block;
+	let block_hash = format!("{:?}", block.header.hash());
+	client.import(BlockOrigin::Own, block.clone()).await.unwrap();
+
+	// Ensure the imported block is propagated and pinned for this subscription.
+	assert_matches!(
+		get_next_event::<FollowEvent<String>>(&mut block_sub).await,
+		FollowEvent::NewBlock(_)
+	);
+	assert_matches!(
+		get_next_event::<FollowEvent<String>>(&mut block_sub).await,
+		FollowEvent::BestBlockChanged(_)
+	);
+
+	let response: MethodResponse = api
+		.call(
+			"chainHead_unstable_storage",
+			rpc_params![
+				&sub_id,
+				&block_hash,
+				vec![StorageQuery { key: key.clone(), query_type: StorageQueryType::Proof }]
+			],
+		)
+		.await
+		.unwrap();
+	let operation_id = match response {
+		MethodResponse::Started(started) => started.operation_id,
+		MethodResponse::LimitReached => panic!("Expected started response"),
+	};
+
+	let proof = match get_next_event::<FollowEvent<String>>(&mut block_sub).await {
+		FollowEvent::OperationStorageProof(res) if res.operation_id == operation_id => res.proof,
+		event => panic!("Expected the storage proof, got {event:?}"),
+	};
+	assert_matches!(
+			get_next_event::<FollowEvent<String>>(&mut block_sub).await,
+			FollowEvent::OperationStorageDone(done) if done.operation_id == operation_id
+	);
+
+	// The proof contains the value of the key at the state root of the block.
+	let proof = array_bytes::hex2bytes(&proof).unwrap();
+	let proof = sc_client_api::CompactProof::decode(&mut &proof[..]).unwrap();
+	let (proof, root) =
+		proof.to_storage_proof::<Blake2Hasher>(Some(&block.header.state_root)).unwrap();
+	assert_eq!(root, block.header.state_root);
+	let values = sp_state_machine::read_proof_check::<Blake2Hasher, _>(root, proof, [KEY]).unwrap();
+	assert_eq!(values.get(KEY), Some(&Some(VALUE.to_vec())));
+}
+
 #[tokio::test]
 async fn get_storage_multi_query_iter() {
 	let (mut client, api, mut block_sub, sub_id, block) = setup_api().await;
@@ -1252,6 +1479,9 @@ async fn separate_operation_ids_for_subscriptions() {
 
 			max_lagging_distance: MAX_LAGGING_DISTANCE,
 			max_follow_subscriptions_per_connection: MAX_FOLLOW_SUBSCRIPTIONS_PER_CONNECTION,
+			operation_max_queue_duration: Duration::ZERO,
+			max_resumable_subscriptions: 0,
+			resumable_subscription_grace_period: Duration::ZERO,
 		},
 	)
 	.into_rpc();
@@ -1343,6 +1573,9 @@ async fn follow_generates_initial_blocks() {
 
 			max_lagging_distance: MAX_LAGGING_DISTANCE,
 			max_follow_subscriptions_per_connection: MAX_FOLLOW_SUBSCRIPTIONS_PER_CONNECTION,
+			operation_max_queue_duration: Duration::ZERO,
+			max_resumable_subscriptions: 0,
+			resumable_subscription_grace_period: Duration::ZERO,
 		},
 	)
 	.into_rpc();
@@ -1400,6 +1633,7 @@ async fn follow_generates_initial_blocks() {
 	let expected = FollowEvent::Initialized(Initialized {
 		finalized_block_hashes: vec![format!("{:?}", finalized_hash)],
 		finalized_block_runtime: None,
+		resumption_token: None,
 		with_runtime: false,
 	});
 	assert_eq!(event, expected);
@@ -1501,6 +1735,9 @@ async fn follow_exceeding_pinned_blocks() {
 
 			max_lagging_distance: MAX_LAGGING_DISTANCE,
 			max_follow_subscriptions_per_connection: MAX_FOLLOW_SUBSCRIPTIONS_PER_CONNECTION,
+			operation_max_queue_duration: Duration::ZERO,
+			max_resumable_subscriptions: 0,
+			resumable_subscription_grace_period: Duration::ZERO,
 		},
 	)
 	.into_rpc();
@@ -1580,6 +1817,9 @@ async fn follow_with_unpin() {
 
 			max_lagging_distance: MAX_LAGGING_DISTANCE,
 			max_follow_subscriptions_per_connection: MAX_FOLLOW_SUBSCRIPTIONS_PER_CONNECTION,
+			operation_max_queue_duration: Duration::ZERO,
+			max_resumable_subscriptions: 0,
+			resumable_subscription_grace_period: Duration::ZERO,
 		},
 	)
 	.into_rpc();
@@ -1694,6 +1934,9 @@ async fn unpin_duplicate_hashes() {
 
 			max_lagging_distance: MAX_LAGGING_DISTANCE,
 			max_follow_subscriptions_per_connection: MAX_FOLLOW_SUBSCRIPTIONS_PER_CONNECTION,
+			operation_max_queue_duration: Duration::ZERO,
+			max_resumable_subscriptions: 0,
+			resumable_subscription_grace_period: Duration::ZERO,
 		},
 	)
 	.into_rpc();
@@ -1799,6 +2042,9 @@ async fn follow_with_multiple_unpin_hashes() {
 
 			max_lagging_distance: MAX_LAGGING_DISTANCE,
 			max_follow_subscriptions_per_connection: MAX_FOLLOW_SUBSCRIPTIONS_PER_CONNECTION,
+			operation_max_queue_duration: Duration::ZERO,
+			max_resumable_subscriptions: 0,
+			resumable_subscription_grace_period: Duration::ZERO,
 		},
 	)
 	.into_rpc();
@@ -1955,6 +2201,9 @@ async fn follow_prune_best_block() {
 
 			max_lagging_distance: MAX_LAGGING_DISTANCE,
 			max_follow_subscriptions_per_connection: MAX_FOLLOW_SUBSCRIPTIONS_PER_CONNECTION,
+			operation_max_queue_duration: Duration::ZERO,
+			max_resumable_subscriptions: 0,
+			resumable_subscription_grace_period: Duration::ZERO,
 		},
 	)
 	.into_rpc();
@@ -1967,6 +2216,7 @@ async fn follow_prune_best_block() {
 	let expected = FollowEvent::Initialized(Initialized {
 		finalized_block_hashes: vec![format!("{:?}", finalized_hash)],
 		finalized_block_runtime: None,
+		resumption_token: None,
 		with_runtime: false,
 	});
 	assert_eq!(event, expected);
@@ -2143,6 +2393,9 @@ async fn follow_forks_pruned_block() {
 
 			max_lagging_distance: MAX_LAGGING_DISTANCE,
 			max_follow_subscriptions_per_connection: MAX_FOLLOW_SUBSCRIPTIONS_PER_CONNECTION,
+			operation_max_queue_duration: Duration::ZERO,
+			max_resumable_subscriptions: 0,
+			resumable_subscription_grace_period: Duration::ZERO,
 		},
 	)
 	.into_rpc();
@@ -2238,6 +2491,7 @@ async fn follow_forks_pruned_block() {
 			format!("{:?}", block_3_hash),
 		],
 		finalized_block_runtime: None,
+		resumption_token: None,
 		with_runtime: false,
 	});
 	assert_eq!(event, expected);
@@ -2305,6 +2559,9 @@ async fn follow_report_multiple_pruned_block() {
 
 			max_lagging_distance: MAX_LAGGING_DISTANCE,
 			max_follow_subscriptions_per_connection: MAX_FOLLOW_SUBSCRIPTIONS_PER_CONNECTION,
+			operation_max_queue_duration: Duration::ZERO,
+			max_resumable_subscriptions: 0,
+			resumable_subscription_grace_period: Duration::ZERO,
 		},
 	)
 	.into_rpc();
@@ -2395,6 +2652,7 @@ async fn follow_report_multiple_pruned_block() {
 	let expected = FollowEvent::Initialized(Initialized {
 		finalized_block_hashes: vec![format!("{:?}", finalized_hash)],
 		finalized_block_runtime: None,
+		resumption_token: None,
 		with_runtime: false,
 	});
 	assert_eq!(event, expected);
@@ -2553,6 +2811,9 @@ async fn pin_block_references() {
 
 			max_lagging_distance: MAX_LAGGING_DISTANCE,
 			max_follow_subscriptions_per_connection: MAX_FOLLOW_SUBSCRIPTIONS_PER_CONNECTION,
+			operation_max_queue_duration: Duration::ZERO,
+			max_resumable_subscriptions: 0,
+			resumable_subscription_grace_period: Duration::ZERO,
 		},
 	)
 	.into_rpc();
@@ -2693,6 +2954,9 @@ async fn follow_finalized_before_new_block() {
 
 			max_lagging_distance: MAX_LAGGING_DISTANCE,
 			max_follow_subscriptions_per_connection: MAX_FOLLOW_SUBSCRIPTIONS_PER_CONNECTION,
+			operation_max_queue_duration: Duration::ZERO,
+			max_resumable_subscriptions: 0,
+			resumable_subscription_grace_period: Duration::ZERO,
 		},
 	)
 	.into_rpc();
@@ -2723,6 +2987,7 @@ async fn follow_finalized_before_new_block() {
 	let expected = FollowEvent::Initialized(Initialized {
 		finalized_block_hashes: vec![format!("{:?}", finalized_hash)],
 		finalized_block_runtime: None,
+		resumption_token: None,
 		with_runtime: false,
 	});
 	assert_eq!(event, expected);
@@ -2810,6 +3075,9 @@ async fn ensure_operation_limits_works() {
 
 			max_lagging_distance: MAX_LAGGING_DISTANCE,
 			max_follow_subscriptions_per_connection: MAX_FOLLOW_SUBSCRIPTIONS_PER_CONNECTION,
+			operation_max_queue_duration: Duration::ZERO,
+			max_resumable_subscriptions: 0,
+			resumable_subscription_grace_period: Duration::ZERO,
 		},
 	)
 	.into_rpc();
@@ -2917,6 +3185,9 @@ async fn check_continue_operation() {
 
 			max_lagging_distance: MAX_LAGGING_DISTANCE,
 			max_follow_subscriptions_per_connection: MAX_FOLLOW_SUBSCRIPTIONS_PER_CONNECTION,
+			operation_max_queue_duration: Duration::ZERO,
+			max_resumable_subscriptions: 0,
+			resumable_subscription_grace_period: Duration::ZERO,
 		},
 	)
 	.into_rpc();
@@ -3102,6 +3373,9 @@ async fn stop_storage_operation() {
 
 			max_lagging_distance: MAX_LAGGING_DISTANCE,
 			max_follow_subscriptions_per_connection: MAX_FOLLOW_SUBSCRIPTIONS_PER_CONNECTION,
+			operation_max_queue_duration: Duration::ZERO,
+			max_resumable_subscriptions: 0,
+			resumable_subscription_grace_period: Duration::ZERO,
 		},
 	)
 	.into_rpc();
@@ -3406,6 +3680,9 @@ async fn chain_head_stop_all_subscriptions() {
 			operation_max_storage_items: MAX_PAGINATION_LIMIT,
 			max_lagging_distance: 5,
 			max_follow_subscriptions_per_connection: MAX_FOLLOW_SUBSCRIPTIONS_PER_CONNECTION,
+			operation_max_queue_duration: Duration::ZERO,
+			max_resumable_subscriptions: 0,
+			resumable_subscription_grace_period: Duration::ZERO,
 		},
 	)
 	.into_rpc();
@@ -3485,7 +3762,7 @@ async fn chain_head_single_connection_context() {
 		.unwrap();
 
 	let mut sub: RpcClientSubscription<FollowEvent<String>> =
-		ChainHeadApiClient::<String>::chain_head_unstable_follow(&client, true)
+		ChainHeadApiClient::<String>::chain_head_unstable_follow(&client, true, None)
 			.await
 			.unwrap();
 
@@ -3561,6 +3838,7 @@ async fn chain_head_single_connection_context() {
 		finalized_hash.clone(),
 		vec![StorageQuery { key: key.clone(), query_type: StorageQueryType::Hash }],
 		None,
+		None,
 	)
 	.await
 	.unwrap();
@@ -3572,6 +3850,7 @@ async fn chain_head_single_connection_context() {
 		finalized_hash.clone(),
 		vec![StorageQuery { key: key.clone(), query_type: StorageQueryType::Hash }],
 		None,
+		None,
 	)
 	.await
 	.unwrap();
@@ -3586,6 +3865,8 @@ async fn chain_head_single_connection_context() {
 		finalized_hash.clone(),
 		"AccountNonceApi_account_nonce".into(),
 		call_parameters.clone(),
+		None,
+		None,
 	)
 	.await
 	.unwrap();
@@ -3597,6 +3878,8 @@ async fn chain_head_single_connection_context() {
 		finalized_hash.clone(),
 		"AccountNonceApi_account_nonce".into(),
 		call_parameters.clone(),
+		None,
+		None,
 	)
 	.await
 	.unwrap();
@@ -3621,6 +3904,9 @@ async fn chain_head_limit_reached() {
 			operation_max_storage_items: MAX_PAGINATION_LIMIT,
 			max_lagging_distance: MAX_LAGGING_DISTANCE,
 			max_follow_subscriptions_per_connection: 1,
+			operation_max_queue_duration: Duration::ZERO,
+			max_resumable_subscriptions: 0,
+			resumable_subscription_grace_period: Duration::ZERO,
 		},
 	)
 	.into_rpc();
@@ -3644,3 +3930,147 @@ async fn chain_head_limit_reached() {
 	// Initialized must always be reported first.
 	let _event: FollowEvent<String> = get_next_event(&mut sub).await;
 }
+
+/// Set up a `chainHead` module with resumable follow subscriptions.
+fn setup_resumable_api(
+	grace_period: Duration,
+) -> (Arc<Client<Backend>>, RpcModule<ChainHead<Backend, Block, Client<Backend>>>) {
+	let builder = TestClientBuilder::new();
+	let backend = builder.backend();
+	let client = Arc::new(builder.build());
+
+	let api = ChainHead::new(
+		client.clone(),
+		backend,
+		Arc::new(TaskExecutor::default()),
+		ChainHeadConfig {
+			global_max_pinned_blocks: MAX_PINNED_BLOCKS,
+			subscription_max_pinned_duration: Duration::from_secs(MAX_PINNED_SECS),
+			subscription_max_ongoing_operations: MAX_OPERATIONS,
+			operation_max_storage_items: MAX_PAGINATION_LIMIT,
+			max_lagging_distance: MAX_LAGGING_DISTANCE,
+			max_follow_subscriptions_per_connection: MAX_FOLLOW_SUBSCRIPTIONS_PER_CONNECTION,
+			operation_max_queue_duration: Duration::ZERO,
+			max_resumable_subscriptions: 1,
+			resumable_subscription_grace_period: grace_period,
+		},
+	)
+	.into_rpc();
+
+	(client, api)
+}
+
+/// Start a resumable follow subscription, returning its identifier and resumption token.
+async fn follow_resumable(
+	api: &RpcModule<ChainHead<Backend, Block, Client<Backend>>>,
+) -> (RpcSubscription, String, String) {
+	let mut sub = api
+		.subscribe_unbounded(
+			"chainHead_unstable_follow",
+			rpc_params![false, FollowOptions { resumable: true }],
+		)
+		.await
+		.unwrap();
+	let sub_id = serde_json::to_string(&sub.subscription_id()).unwrap();
+
+	let token = match get_next_event::<FollowEvent<String>>(&mut sub).await {
+		FollowEvent::Initialized(Initialized { resumption_token: Some(token), .. }) => token,
+		event => panic!("Expected FollowEvent::Initialized with a token, got {event:?}"),
+	};
+
+	(sub, sub_id, token)
+}
+
+async fn import_best_block(client: &Arc<Client<Backend>>) -> String {
+	let info = client.info();
+	let block = BlockBuilderBuilder::new(&**client)
+		.on_parent_block(info.best_hash)
+		.with_parent_block_number(info.best_number)
+		.build()
+		.unwrap()
+		.build()
+		.unwrap()
+		.block;
+	let hash = format!("{:?}", block.hash());
+	client.clone().import(BlockOrigin::Own, block).await.unwrap();
+	hash
+}
+
+#[tokio::test]
+async fn follow_resume_with_token() {
+	let (client, api) = setup_resumable_api(Duration::from_secs(60));
+	let (sub, sub_id, token) = follow_resumable(&api).await;
+
+	// The events generated while the subscription is detached are kept.
+	drop(sub);
+	tokio::time::sleep(Duration::from_millis(500)).await;
+	let block_hash = import_best_block(&client).await;
+
+	let mut sub = api
+		.subscribe_unbounded("chainHead_unstable_resumeFollow", [&token])
+		.await
+		.unwrap();
+	assert_matches!(
+		get_next_event::<FollowEvent<String>>(&mut sub).await,
+		FollowEvent::NewBlock(new_block) if new_block.block_hash == block_hash
+	);
+	assert_matches!(
+		get_next_event::<FollowEvent<String>>(&mut sub).await,
+		FollowEvent::BestBlockChanged(best) if best.best_block_hash == block_hash
+	);
+
+	// The new events are sent to the resumed subscription.
+	let block_hash = import_best_block(&client).await;
+	assert_matches!(
+		get_next_event::<FollowEvent<String>>(&mut sub).await,
+		FollowEvent::NewBlock(new_block) if new_block.block_hash == block_hash
+	);
+
+	// The blocks remain pinned for the original subscription ID.
+	let header: Option<String> = api
+		.call("chainHead_unstable_header", rpc_params![&sub_id, &block_hash])
+		.await
+		.unwrap();
+	assert!(header.is_some());
+}
+
+#[tokio::test]
+async fn follow_resume_invalid_token() {
+	let (_client, api) = setup_resumable_api(Duration::from_secs(60));
+
+	let err = api
+		.subscribe_unbounded("chainHead_unstable_resumeFollow", ["invalid"])
+		.await
+		.unwrap_err();
+	assert_matches!(err,
+		Error::JsonRpc(ref err) if err.code() == super::error::rpc_spec_v2::INVALID_RESUMPTION_TOKEN
+	);
+
+	// The token of a subscription is evicted by the next ones once the limit is reached.
+	let (_first, _, token) = follow_resumable(&api).await;
+	let (_second, _, _) = follow_resumable(&api).await;
+	let err = api
+		.subscribe_unbounded("chainHead_unstable_resumeFollow", [&token])
+		.await
+		.unwrap_err();
+	assert_matches!(err,
+		Error::JsonRpc(ref err) if err.code() == super::error::rpc_spec_v2::INVALID_RESUMPTION_TOKEN
+	);
+}
+
+#[tokio::test]
+async fn follow_resumption_token_expires() {
+	let (_client, api) = setup_resumable_api(Duration::from_millis(100));
+	let (sub, _sub_id, token) = follow_resumable(&api).await;
+
+	drop(sub);
+	tokio::time::sleep(Duration::from_secs(1)).await;
+
+	let err = api
+		.subscribe_unbounded("chainHead_unstable_resumeFollow", [&token])
+		.await
+		.unwrap_err();
+	assert_matches!(err,
+		Error::JsonRpc(ref err) if err.code() == super::error::rpc_spec_v2::INVALID_RESUMPTION_TOKEN
+	);
+}
@@ -18,6 +18,7 @@
 
 //! API trait of the chain spec.
 
+use crate::chain_spec::error::Error;
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use sc_chain_spec::Properties;
 
@@ -38,4 +39,28 @@ pub trait ChainSpecApi {
 	/// The json whitespaces are not guaranteed to persist.
 	#[method(name = "chainSpec_v1_properties")]
 	fn chain_spec_v1_properties(&self) -> RpcResult<Properties>;
+
+	/// Get the fork identifier of the chain, as present in the chain specification.
+	///
+	/// Returns `null` if the chain specification doesn't define a fork identifier.
+	///
+	/// # Unstable
+	///
+	/// This method is unstable and subject to change in the future.
+	#[method(name = "chainSpec_unstable_forkId")]
+	fn chain_spec_unstable_fork_id(&self) -> RpcResult<Option<String>>;
+
+	/// Get the light sync state of the latest finalized block, as reported in the `lightSyncState`
+	/// extension of the chain specification by `sync_state_genSyncSpec`.
+	///
+	/// Light clients can start syncing from this checkpoint instead of the genesis block.
+	/// Returns `null` if the node doesn't generate light sync states.
+	///
+	/// # Unstable
+	///
+	/// This method is unstable and subject to change in the future.
+	#[method(name = "chainSpec_unstable_lightSyncState")]
+	async fn chain_spec_unstable_light_sync_state(
+		&self,
+	) -> Result<Option<serde_json::Value>, Error>;
 }
@@ -18,9 +18,19 @@
 
 //! API implementation for the specification of a chain.
 
-use crate::chain_spec::api::ChainSpecApiServer;
-use jsonrpsee::core::RpcResult;
+use crate::chain_spec::{api::ChainSpecApiServer, error::Error};
+use jsonrpsee::core::{async_trait, RpcResult};
 use sc_chain_spec::Properties;
+use std::sync::Arc;
+
+/// Generates the light sync state of the latest finalized block.
+///
+/// Implemented by `sc-sync-state-rpc`, which knows about the consensus of the chain.
+#[async_trait]
+pub trait LightSyncStateProvider: Send + Sync {
+	/// The JSON serialized light sync state of the latest finalized block.
+	async fn light_sync_state(&self) -> Result<serde_json::Value, String>;
+}
 
 /// An API for chain spec RPC calls.
 pub struct ChainSpec {
@@ -30,6 +40,10 @@ pub struct ChainSpec {
 	genesis_hash: String,
 	/// Chain properties.
 	properties: Properties,
+	/// The fork identifier of the chain, if any.
+	fork_id: Option<String>,
+	/// The generator of the light sync states, if enabled.
+	light_sync_state: Option<Arc<dyn LightSyncStateProvider>>,
 }
 
 impl ChainSpec {
@@ -41,10 +55,23 @@ impl ChainSpec {
 	) -> Self {
 		let genesis_hash = format!("0x{}", hex::encode(genesis_hash));
 
-		Self { name, properties, genesis_hash }
+		Self { name, properties, genesis_hash, fork_id: None, light_sync_state: None }
+	}
+
+	/// Report the fork identifier of the chain.
+	pub fn with_fork_id(mut self, fork_id: Option<String>) -> Self {
+		self.fork_id = fork_id;
+		self
+	}
+
+	/// Report the light sync states generated by the given provider.
+	pub fn with_light_sync_state(mut self, provider: Arc<dyn LightSyncStateProvider>) -> Self {
+		self.light_sync_state = Some(provider);
+		self
 	}
 }
 
+#[async_trait]
 impl ChainSpecApiServer for ChainSpec {
 	fn chain_spec_v1_chain_name(&self) -> RpcResult<String> {
 		Ok(self.name.clone())
@@ -57,4 +84,16 @@ impl ChainSpecApiServer for ChainSpec {
 	fn chain_spec_v1_properties(&self) -> RpcResult<Properties> {
 		Ok(self.properties.clone())
 	}
+
+	fn chain_spec_unstable_fork_id(&self) -> RpcResult<Option<String>> {
+		Ok(self.fork_id.clone())
+	}
+
+	async fn chain_spec_unstable_light_sync_state(
+		&self,
+	) -> Result<Option<serde_json::Value>, Error> {
+		let Some(provider) = &self.light_sync_state else { return Ok(None) };
+
+		provider.light_sync_state().await.map(Some).map_err(Error::LightSyncState)
+	}
 }
@@ -0,0 +1,47 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Error helpers for `chainSpec` RPC module.
+
+use jsonrpsee::types::error::ErrorObject;
+
+/// ChainSpec RPC errors.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	/// The light sync state could not be generated.
+	#[error("Failed to generate the light sync state: {0}")]
+	LightSyncState(String),
+}
+
+/// General purpose errors, as defined in
+/// <https://www.jsonrpc.org/specification#error_object>.
+pub mod json_rpc_spec {
+	/// Internal error.
+	pub const INTERNAL_ERROR: i32 = -32603;
+}
+
+impl From<Error> for ErrorObject<'static> {
+	fn from(e: Error) -> Self {
+		let msg = e.to_string();
+
+		match e {
+			Error::LightSyncState(_) =>
+				ErrorObject::owned(json_rpc_spec::INTERNAL_ERROR, msg, None::<()>),
+		}
+	}
+}
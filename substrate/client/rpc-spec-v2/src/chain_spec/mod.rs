@@ -22,7 +22,7 @@
 //! the specification of the chain that a JSON-RPC server is targeting.
 //!
 //! The values returned by the API are guaranteed to never change during the lifetime of the
-//! JSON-RPC server.
+//! JSON-RPC server, except the light sync state which follows the finalized block.
 //!
 //! # Note
 //!
@@ -33,6 +33,7 @@ mod tests;
 
 pub mod api;
 pub mod chain_spec;
+pub mod error;
 
 pub use api::ChainSpecApiServer;
-pub use chain_spec::ChainSpec;
+pub use chain_spec::{ChainSpec, LightSyncStateProvider};
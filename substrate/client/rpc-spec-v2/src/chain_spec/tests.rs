@@ -17,20 +17,37 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use super::*;
-use jsonrpsee::{core::EmptyServerParams as EmptyParams, RpcModule};
+use jsonrpsee::{
+	core::{async_trait, EmptyServerParams as EmptyParams},
+	MethodsError as Error, RpcModule,
+};
 use sc_chain_spec::Properties;
+use std::sync::Arc;
 
 const CHAIN_NAME: &'static str = "TEST_CHAIN_NAME";
 const CHAIN_GENESIS: [u8; 32] = [0; 32];
 const CHAIN_PROPERTIES: &'static str = r#"{"three": "123", "one": 1, "two": 12}"#;
 
-fn api() -> RpcModule<ChainSpec> {
+fn chain_spec() -> ChainSpec {
 	ChainSpec::new(
 		CHAIN_NAME.to_string(),
 		CHAIN_GENESIS,
 		serde_json::from_str(CHAIN_PROPERTIES).unwrap(),
 	)
-	.into_rpc()
+}
+
+fn api() -> RpcModule<ChainSpec> {
+	chain_spec().into_rpc()
+}
+
+/// Reports the given light sync state, or fails if none.
+struct MockLightSyncState(Option<serde_json::Value>);
+
+#[async_trait]
+impl LightSyncStateProvider for MockLightSyncState {
+	async fn light_sync_state(&self) -> Result<serde_json::Value, String> {
+		self.0.clone().ok_or_else(|| "Epoch data unavailable".into())
+	}
 }
 
 #[tokio::test]
@@ -59,3 +76,59 @@ async fn chain_spec_properties_works() {
 		.unwrap();
 	assert_eq!(properties, serde_json::from_str(CHAIN_PROPERTIES).unwrap());
 }
+
+#[tokio::test]
+async fn chain_spec_fork_id_works() {
+	let fork_id = api()
+		.call::<_, Option<String>>("chainSpec_unstable_forkId", EmptyParams::new())
+		.await
+		.unwrap();
+	assert_eq!(fork_id, None);
+
+	let fork_id = chain_spec()
+		.with_fork_id(Some("fork".into()))
+		.into_rpc()
+		.call::<_, Option<String>>("chainSpec_unstable_forkId", EmptyParams::new())
+		.await
+		.unwrap();
+	assert_eq!(fork_id, Some("fork".into()));
+}
+
+#[tokio::test]
+async fn chain_spec_light_sync_state_works() {
+	// The node doesn't generate light sync states.
+	let state = api()
+		.call::<_, Option<serde_json::Value>>(
+			"chainSpec_unstable_lightSyncState",
+			EmptyParams::new(),
+		)
+		.await
+		.unwrap();
+	assert_eq!(state, None);
+
+	let expected = serde_json::json!({ "finalizedBlockHeader": "0x00" });
+	let state = chain_spec()
+		.with_light_sync_state(Arc::new(MockLightSyncState(Some(expected.clone()))))
+		.into_rpc()
+		.call::<_, Option<serde_json::Value>>(
+			"chainSpec_unstable_lightSyncState",
+			EmptyParams::new(),
+		)
+		.await
+		.unwrap();
+	assert_eq!(state, Some(expected));
+
+	let err = chain_spec()
+		.with_light_sync_state(Arc::new(MockLightSyncState(None)))
+		.into_rpc()
+		.call::<_, Option<serde_json::Value>>(
+			"chainSpec_unstable_lightSyncState",
+			EmptyParams::new(),
+		)
+		.await
+		.unwrap_err();
+	assert!(matches!(err,
+		Error::JsonRpc(err) if err.code() == error::json_rpc_spec::INTERNAL_ERROR &&
+			err.message().contains("Epoch data unavailable")
+	));
+}
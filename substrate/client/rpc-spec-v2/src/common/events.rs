@@ -60,6 +60,8 @@ pub enum StorageQueryType {
 	DescendantsValues,
 	/// Fetch the hashes of the values of all descendants of they provided key.
 	DescendantsHashes,
+	/// Include the provided key in the compact Merkle proof of the query.
+	Proof,
 }
 
 impl StorageQueryType {
@@ -143,6 +145,110 @@ pub struct ArchiveStorageMethodErr {
 	pub error: String,
 }
 
+/// The event generated by the `archive_storagePrefix` subscription.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(tag = "event")]
+pub enum ArchiveStoragePrefixEvent {
+	/// A chunk of the descendants of the queried key.
+	Items(ArchiveStoragePrefixItems),
+	/// All descendants were reported.
+	Done,
+	/// The iteration encountered an error and stopped.
+	Error(ArchiveStorageMethodErr),
+}
+
+/// A chunk of the descendants reported by the `archive_storagePrefix` subscription.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveStoragePrefixItems {
+	/// Reported items.
+	pub items: Vec<StorageResult>,
+}
+
+/// The event generated by the `archive_storageDiff` subscription.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(tag = "event")]
+pub enum ArchiveStorageDiffEvent {
+	/// A chunk of the keys changed between the two blocks.
+	Items(ArchiveStorageDiffItems),
+	/// All changed keys were reported.
+	Done,
+	/// The iteration encountered an error and stopped.
+	Error(ArchiveStorageMethodErr),
+}
+
+/// A chunk of the changed keys reported by the `archive_storageDiff` subscription.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveStorageDiffItems {
+	/// Reported items.
+	pub items: Vec<ArchiveStorageDiffResult>,
+}
+
+/// A key changed between two blocks.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveStorageDiffResult {
+	/// The hex-encoded key.
+	pub key: String,
+	/// The hex-encoded value at the later block, absent for deleted keys.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	#[serde(default)]
+	pub value: Option<String>,
+	/// The type of the change.
+	#[serde(rename = "type")]
+	pub diff_type: ArchiveStorageDiffType,
+}
+
+/// The event generated by the `archive_stateStream` subscription.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(tag = "event")]
+pub enum ArchiveStateStreamEvent {
+	/// A chunk of the key/value pairs of the state.
+	Items(ArchiveStateStreamItems),
+	/// The whole state was reported.
+	Done,
+	/// The iteration encountered an error and stopped.
+	Error(ArchiveStorageMethodErr),
+}
+
+/// A chunk of the state reported by the `archive_stateStream` subscription.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveStateStreamItems {
+	/// Reported items.
+	pub items: Vec<StorageResult>,
+	/// The cursor to resume the iteration after this chunk, absent for the last chunk.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	#[serde(default)]
+	pub cursor: Option<String>,
+}
+
+/// A finalized block reported by the `archive_finalizedHeightFollow` subscription.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveFinalizedBlock<Hash> {
+	/// The height of the block.
+	pub height: u64,
+	/// The hash of the block.
+	pub hash: Hash,
+}
+
+/// The type of a change between two blocks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ArchiveStorageDiffType {
+	/// The key only exists at the later block.
+	Added,
+	/// The value of the key differs between the two blocks.
+	Modified,
+	/// The key only exists at the earlier block.
+	Deleted,
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -270,4 +376,62 @@ mod tests {
 		let dec: PaginatedStorageQuery<&str> = serde_json::from_str(exp).unwrap();
 		assert_eq!(dec, item);
 	}
+	#[test]
+	fn archive_storage_diff_result() {
+		let item = ArchiveStorageDiffResult {
+			key: "0x1".into(),
+			value: Some("0x2".into()),
+			diff_type: ArchiveStorageDiffType::Modified,
+		};
+		// Encode
+		let ser = serde_json::to_string(&item).unwrap();
+		let exp = r#"{"key":"0x1","value":"0x2","type":"modified"}"#;
+		assert_eq!(ser, exp);
+		// Decode
+		let dec: ArchiveStorageDiffResult = serde_json::from_str(exp).unwrap();
+		assert_eq!(dec, item);
+
+		// Deleted items have no value.
+		let item = ArchiveStorageDiffResult {
+			key: "0x1".into(),
+			value: None,
+			diff_type: ArchiveStorageDiffType::Deleted,
+		};
+		// Encode
+		let ser = serde_json::to_string(&item).unwrap();
+		let exp = r#"{"key":"0x1","type":"deleted"}"#;
+		assert_eq!(ser, exp);
+		// Decode
+		let dec: ArchiveStorageDiffResult = serde_json::from_str(exp).unwrap();
+		assert_eq!(dec, item);
+	}
+
+	#[test]
+	fn archive_state_stream_event() {
+		let event = ArchiveStateStreamEvent::Items(ArchiveStateStreamItems {
+			items: vec![StorageResult {
+				key: "0x1".into(),
+				result: StorageResultType::Value("0x2".into()),
+			}],
+			cursor: Some("0x1".into()),
+		});
+		// Encode
+		let ser = serde_json::to_string(&event).unwrap();
+		let exp = r#"{"event":"items","items":[{"key":"0x1","value":"0x2"}],"cursor":"0x1"}"#;
+		assert_eq!(ser, exp);
+		// Decode
+		let dec: ArchiveStateStreamEvent = serde_json::from_str(exp).unwrap();
+		assert_eq!(dec, event);
+
+		// The last chunk has no cursor.
+		let event =
+			ArchiveStateStreamEvent::Items(ArchiveStateStreamItems { items: vec![], cursor: None });
+		// Encode
+		let ser = serde_json::to_string(&event).unwrap();
+		let exp = r#"{"event":"items","items":[]}"#;
+		assert_eq!(ser, exp);
+		// Decode
+		let dec: ArchiveStateStreamEvent = serde_json::from_str(exp).unwrap();
+		assert_eq!(dec, event);
+	}
 }
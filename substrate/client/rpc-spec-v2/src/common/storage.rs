@@ -18,12 +18,17 @@
 
 //! Storage queries for the RPC-V2 spec.
 
-use std::{marker::PhantomData, sync::Arc};
+use std::{cmp::Ordering, marker::PhantomData, sync::Arc};
 
-use sc_client_api::{Backend, ChildInfo, StorageKey, StorageProvider};
-use sp_runtime::traits::Block as BlockT;
+use codec::Encode;
+use sc_client_api::{
+	Backend, ChildInfo, HeaderBackend, MerkleValue, ProofProvider, StorageKey, StorageProvider,
+};
+use sp_runtime::traits::{Block as BlockT, HashingFor, Header as HeaderT};
 
-use super::events::{StorageResult, StorageResultType};
+use super::events::{
+	ArchiveStorageDiffResult, ArchiveStorageDiffType, StorageResult, StorageResultType,
+};
 use crate::hex_string;
 
 /// Call into the storage of blocks.
@@ -64,6 +69,47 @@ pub type QueryResult = Result<Option<StorageResult>, String>;
 /// The result of iterating over keys.
 pub type QueryIterResult = Result<(Vec<StorageResult>, Option<QueryIter>), String>;
 
+/// The result of iterating over the keys changed between two blocks.
+///
+/// Returns the changes with a potential key after which the iteration should resume.
+pub type QueryDiffResult = Result<(Vec<ArchiveStorageDiffResult>, Option<StorageKey>), String>;
+
+impl<Client, Block, BE> Storage<Client, Block, BE>
+where
+	Block: BlockT + 'static,
+	Client: ProofProvider<Block> + HeaderBackend<Block> + 'static,
+{
+	/// Generate the compact Merkle proof of the provided keys, against the state root of the
+	/// block.
+	///
+	/// Returns the hexadecimal-encoded SCALE-encoded compact proof.
+	pub fn query_proof(
+		&self,
+		hash: Block::Hash,
+		keys: &[StorageKey],
+		child_key: Option<&ChildInfo>,
+	) -> Result<String, String> {
+		let header = self
+			.client
+			.header(hash)
+			.map_err(|err| err.to_string())?
+			.ok_or_else(|| format!("Header of block {hash:?} not found"))?;
+
+		let mut keys = keys.iter().map(|key| key.0.as_slice());
+		let proof = if let Some(child_key) = child_key {
+			self.client.read_child_proof(hash, child_key, &mut keys)
+		} else {
+			self.client.read_proof(hash, &mut keys)
+		}
+		.map_err(|err| err.to_string())?;
+
+		let proof = proof
+			.into_compact_proof::<HashingFor<Block>>(*header.state_root())
+			.map_err(|err| format!("Failed to compact the proof: {err:?}"))?;
+		Ok(hex_string(&proof.encode()))
+	}
+}
+
 impl<Client, Block, BE> Storage<Client, Block, BE>
 where
 	Block: BlockT + 'static,
@@ -195,4 +241,119 @@ where
 		});
 		Ok((ret, maybe_next_query))
 	}
+
+	/// Iterate over at most the provided number of keys changed between `previous_hash` and
+	/// `hash` under the given prefix, starting after `start_key` if provided.
+	///
+	/// The keys of both blocks are walked in order side by side. The prefix is skipped entirely
+	/// when its closest descendant merkle value is identical in both blocks.
+	pub fn query_diff_pagination(
+		&self,
+		hash: Block::Hash,
+		previous_hash: Block::Hash,
+		prefix: &StorageKey,
+		start_key: Option<&StorageKey>,
+		child_key: Option<&ChildInfo>,
+		count: usize,
+	) -> QueryDiffResult {
+		if start_key.is_none() && self.same_merkle_value(hash, previous_hash, prefix, child_key)? {
+			return Ok((Vec::new(), None))
+		}
+
+		let keys = |at: Block::Hash| {
+			let iter = if let Some(child_key) = child_key {
+				self.client
+					.child_storage_keys(at, child_key.to_owned(), Some(prefix), start_key)
+			} else {
+				self.client.storage_keys(at, Some(prefix), start_key)
+			};
+			iter.map(Iterator::peekable).map_err(|err| err.to_string())
+		};
+		let mut keys_iter = keys(hash)?;
+		let mut previous_keys_iter = keys(previous_hash)?;
+
+		let mut ret = Vec::with_capacity(count);
+		let mut last_key = None;
+		while ret.len() < count {
+			let ordering = match (keys_iter.peek(), previous_keys_iter.peek()) {
+				(None, None) => return Ok((ret, None)),
+				(Some(_), None) => Ordering::Less,
+				(None, Some(_)) => Ordering::Greater,
+				(Some(key), Some(previous_key)) => key.cmp(previous_key),
+			};
+
+			let (key, diff_type) = match ordering {
+				Ordering::Less => (keys_iter.next(), ArchiveStorageDiffType::Added),
+				Ordering::Greater => (previous_keys_iter.next(), ArchiveStorageDiffType::Deleted),
+				Ordering::Equal => {
+					previous_keys_iter.next();
+					let key = keys_iter.next().expect("Peeked above; qed");
+					let value_hash = self.value_hash(hash, &key, child_key)?;
+					if value_hash == self.value_hash(previous_hash, &key, child_key)? {
+						continue
+					}
+					(Some(key), ArchiveStorageDiffType::Modified)
+				},
+			};
+			let key = key.expect("Peeked above; qed");
+
+			let value = match diff_type {
+				ArchiveStorageDiffType::Deleted => None,
+				_ => match self.query_value(hash, &key, child_key)? {
+					Some(StorageResult { result: StorageResultType::Value(value), .. }) =>
+						Some(value),
+					_ => None,
+				},
+			};
+			ret.push(ArchiveStorageDiffResult { key: hex_string(&key.0), value, diff_type });
+			last_key = Some(key);
+		}
+
+		// Resume after the last reported key if there are more keys to compare.
+		let next_key = (keys_iter.peek().is_some() || previous_keys_iter.peek().is_some())
+			.then_some(last_key)
+			.flatten();
+		Ok((ret, next_key))
+	}
+
+	/// The hash of the value of the given key.
+	fn value_hash(
+		&self,
+		hash: Block::Hash,
+		key: &StorageKey,
+		child_key: Option<&ChildInfo>,
+	) -> Result<Option<Block::Hash>, String> {
+		if let Some(child_key) = child_key {
+			self.client.child_storage_hash(hash, child_key, key)
+		} else {
+			self.client.storage_hash(hash, key)
+		}
+		.map_err(|err| err.to_string())
+	}
+
+	/// Whether the closest descendant merkle value of the given key is identical in both blocks.
+	fn same_merkle_value(
+		&self,
+		hash: Block::Hash,
+		previous_hash: Block::Hash,
+		key: &StorageKey,
+		child_key: Option<&ChildInfo>,
+	) -> Result<bool, String> {
+		let merkle_value = |at: Block::Hash| {
+			if let Some(child_key) = child_key {
+				self.client.child_closest_merkle_value(at, child_key, key)
+			} else {
+				self.client.closest_merkle_value(at, key)
+			}
+			.map(|value| {
+				value.map(|value| match value {
+					MerkleValue::Node(data) => data,
+					MerkleValue::Hash(hash) => hash.as_ref().to_vec(),
+				})
+			})
+			.map_err(|err| err.to_string())
+		};
+
+		Ok(merkle_value(hash)? == merkle_value(previous_hash)?)
+	}
 }
@@ -31,6 +31,8 @@ mod common;
 pub mod archive;
 pub mod chain_head;
 pub mod chain_spec;
+pub mod runtime_upgrade;
+pub mod session;
 pub mod transaction;
 
 /// Task executor that is being used by RPC subscriptions.
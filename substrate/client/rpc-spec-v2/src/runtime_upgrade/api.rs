@@ -0,0 +1,40 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! API trait of the runtime upgrade methods.
+
+use crate::runtime_upgrade::event::RuntimeUpgradeEvent;
+use jsonrpsee::proc_macros::rpc;
+
+#[rpc(client, server)]
+pub trait RuntimeUpgradeApi<Hash> {
+	/// Subscribe to the runtime upgrades of the finalized chain.
+	///
+	/// See [`RuntimeUpgradeEvent`](crate::runtime_upgrade::event::RuntimeUpgradeEvent) for details
+	/// on the runtime upgrade life cycle.
+	///
+	/// # Unstable
+	///
+	/// This method is unstable and subject to change in the future.
+	#[subscription(
+		name = "runtimeUpgrade_unstable_subscribe" => "runtimeUpgrade_unstable_event",
+		unsubscribe = "runtimeUpgrade_unstable_unsubscribe",
+		item = RuntimeUpgradeEvent<Hash>,
+	)]
+	fn subscribe(&self);
+}
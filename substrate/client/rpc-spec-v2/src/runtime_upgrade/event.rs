@@ -0,0 +1,101 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! The runtime upgrade events returned as json compatible objects.
+
+use serde::{Deserialize, Serialize};
+
+/// An upgrade was authorized by the `System::AuthorizedUpgrade` storage item.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuntimeUpgradeAuthorized<Hash> {
+	/// The hash of the block authorizing the upgrade.
+	pub block_hash: Hash,
+	/// The hash of the authorized code.
+	pub code_hash: Hash,
+	/// Whether the version of the code is checked when it is applied.
+	pub check_version: bool,
+}
+
+/// A new code was written to `:code`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuntimeUpgradeScheduled<Hash> {
+	/// The hash of the block writing the new code.
+	pub block_hash: Hash,
+	/// The hash of the new code.
+	pub code_hash: Hash,
+	/// The spec version of the new code.
+	pub spec_version: u32,
+	/// The number of the first block executed with the new code.
+	pub enactment_block_number: u64,
+}
+
+/// The new code executed its first block.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuntimeUpgradeEnacted<Hash> {
+	/// The hash of the first block executed with the new code.
+	pub block_hash: Hash,
+	/// The spec name of the new code.
+	pub spec_name: String,
+	/// The spec version of the new code.
+	pub spec_version: u32,
+}
+
+/// The runtime upgrade events.
+///
+/// Only the finalized blocks are reported, a runtime upgrade goes through the following events:
+///
+/// 1. `authorized`, optional: the upgrade was authorized to be applied by any origin.
+/// 2. `scheduled`: the new code was written to `:code`, the metadata can be fetched at the block
+///    hash of the event.
+/// 3. `enacted`: the block following the `scheduled` one was executed with the new code.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(tag = "event")]
+pub enum RuntimeUpgradeEvent<Hash> {
+	/// An upgrade was authorized.
+	Authorized(RuntimeUpgradeAuthorized<Hash>),
+	/// A new code was written to `:code`.
+	Scheduled(RuntimeUpgradeScheduled<Hash>),
+	/// The new code executed its first block.
+	Enacted(RuntimeUpgradeEnacted<Hash>),
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn runtime_upgrade_event_serialize() {
+		let event: RuntimeUpgradeEvent<String> =
+			RuntimeUpgradeEvent::Scheduled(RuntimeUpgradeScheduled {
+				block_hash: "0x1".into(),
+				code_hash: "0x2".into(),
+				spec_version: 2,
+				enactment_block_number: 11,
+			});
+		let ser = serde_json::to_string(&event).unwrap();
+		let exp = r#"{"event":"scheduled","blockHash":"0x1","codeHash":"0x2","specVersion":2,"enactmentBlockNumber":11}"#;
+		assert_eq!(ser, exp);
+
+		let event_dec: RuntimeUpgradeEvent<String> = serde_json::from_str(exp).unwrap();
+		assert_eq!(event_dec, event);
+	}
+}
@@ -0,0 +1,39 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Substrate runtime upgrade API.
+//!
+//! The runtime upgrade methods allow subscribing to the lifecycle of the runtime upgrades of the
+//! finalized chain, from their authorization to their enactment.
+//!
+//! # Note
+//!
+//! Methods are prefixed by `runtimeUpgrade`.
+
+#[cfg(test)]
+mod tests;
+
+pub mod api;
+pub mod event;
+pub mod runtime_upgrade;
+
+pub use api::RuntimeUpgradeApiServer;
+pub use event::{
+	RuntimeUpgradeAuthorized, RuntimeUpgradeEnacted, RuntimeUpgradeEvent, RuntimeUpgradeScheduled,
+};
+pub use runtime_upgrade::RuntimeUpgrade;
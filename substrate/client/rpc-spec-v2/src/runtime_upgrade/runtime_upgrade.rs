@@ -0,0 +1,192 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! API implementation for the runtime upgrade subscription.
+
+use crate::{
+	runtime_upgrade::{
+		api::RuntimeUpgradeApiServer,
+		event::{
+			RuntimeUpgradeAuthorized, RuntimeUpgradeEnacted, RuntimeUpgradeEvent,
+			RuntimeUpgradeScheduled,
+		},
+	},
+	SubscriptionTaskExecutor,
+};
+use codec::Decode;
+use futures::{stream, StreamExt};
+use jsonrpsee::{core::async_trait, PendingSubscriptionSink};
+use sc_client_api::{Backend, BlockchainEvents, StorageKey, StorageProvider};
+use sc_rpc::utils::pipe_from_stream;
+use sp_api::CallApiAt;
+use sp_blockchain::{Error as BlockChainError, HeaderBackend};
+use sp_core::storage::well_known_keys;
+use sp_runtime::{
+	traits::{Block as BlockT, Header as HeaderT, NumberFor, One},
+	SaturatedConversion,
+};
+use std::{marker::PhantomData, sync::Arc};
+
+pub(crate) const LOG_TARGET: &str = "rpc-spec-v2";
+
+/// The key of the `System::AuthorizedUpgrade` storage item.
+pub(crate) fn authorized_upgrade_key() -> StorageKey {
+	StorageKey(
+		[sp_crypto_hashing::twox_128(b"System"), sp_crypto_hashing::twox_128(b"AuthorizedUpgrade")]
+			.concat(),
+	)
+}
+
+/// An API for the runtime upgrade RPC calls.
+pub struct RuntimeUpgrade<BE: Backend<Block>, Block: BlockT, Client> {
+	/// Substrate client.
+	client: Arc<Client>,
+	/// Executor to spawn subscriptions.
+	executor: SubscriptionTaskExecutor,
+	/// Phantom member to pin the block type.
+	_phantom: PhantomData<(Block, BE)>,
+}
+
+impl<BE: Backend<Block>, Block: BlockT, Client> RuntimeUpgrade<BE, Block, Client> {
+	/// Create a new [`RuntimeUpgrade`].
+	pub fn new(client: Arc<Client>, executor: SubscriptionTaskExecutor) -> Self {
+		Self { client, executor, _phantom: PhantomData }
+	}
+}
+
+#[async_trait]
+impl<BE, Block, Client> RuntimeUpgradeApiServer<Block::Hash> for RuntimeUpgrade<BE, Block, Client>
+where
+	Block: BlockT + 'static,
+	Block::Header: Unpin,
+	BE: Backend<Block> + 'static,
+	Client: BlockchainEvents<Block>
+		+ HeaderBackend<Block>
+		+ CallApiAt<Block>
+		+ StorageProvider<Block, BE>
+		+ Send
+		+ Sync
+		+ 'static,
+{
+	fn subscribe(&self, pending: PendingSubscriptionSink) {
+		let mut tracker = UpgradeTracker {
+			client: self.client.clone(),
+			pending_enactment: None,
+			_phantom: PhantomData,
+		};
+
+		let stream = self.client.finality_notification_stream().flat_map(move |notification| {
+			// The implicitly finalized blocks are reported first, in ascending order.
+			let events = notification
+				.tree_route
+				.iter()
+				.chain(std::iter::once(&notification.hash))
+				.flat_map(|hash| {
+					tracker.block_events(*hash).unwrap_or_else(|err| {
+						log::debug!(
+							target: LOG_TARGET,
+							"Failed to check the runtime upgrades of block {hash:?}: {err}"
+						);
+						Vec::new()
+					})
+				})
+				.collect::<Vec<_>>();
+			stream::iter(events)
+		});
+
+		let fut = async move {
+			pipe_from_stream(pending, stream.boxed()).await;
+		};
+
+		sc_rpc::utils::spawn_subscription_task(&self.executor, fut);
+	}
+}
+
+/// Derive the runtime upgrade events from the finalized blocks.
+struct UpgradeTracker<BE, Block: BlockT, Client> {
+	client: Arc<Client>,
+	/// The number of the block executing the code scheduled by a previous block.
+	pending_enactment: Option<NumberFor<Block>>,
+	_phantom: PhantomData<BE>,
+}
+
+impl<BE, Block, Client> UpgradeTracker<BE, Block, Client>
+where
+	Block: BlockT,
+	BE: Backend<Block>,
+	Client: HeaderBackend<Block> + CallApiAt<Block> + StorageProvider<Block, BE>,
+{
+	/// The events of the finalized block `hash`, whose parent was already processed.
+	fn block_events(
+		&mut self,
+		hash: Block::Hash,
+	) -> Result<Vec<RuntimeUpgradeEvent<Block::Hash>>, BlockChainError> {
+		let header = self
+			.client
+			.header(hash)?
+			.ok_or_else(|| BlockChainError::UnknownBlock(format!("{hash:?}")))?;
+		let number = *header.number();
+		let parent = *header.parent_hash();
+		let mut events = Vec::new();
+
+		if self.pending_enactment.map_or(false, |enactment| enactment <= number) {
+			self.pending_enactment = None;
+			let version = self.client.runtime_version_at(hash)?;
+			events.push(RuntimeUpgradeEvent::Enacted(RuntimeUpgradeEnacted {
+				block_hash: hash,
+				spec_name: version.spec_name.into(),
+				spec_version: version.spec_version,
+			}));
+		}
+
+		let authorized_key = authorized_upgrade_key();
+		let authorized = self.client.storage(hash, &authorized_key)?;
+		let parent_authorized = self.client.storage(parent, &authorized_key)?;
+		if let Some(authorized) = authorized.filter(|a| Some(a) != parent_authorized.as_ref()) {
+			match <(Block::Hash, bool)>::decode(&mut &authorized.0[..]) {
+				Ok((code_hash, check_version)) =>
+					events.push(RuntimeUpgradeEvent::Authorized(RuntimeUpgradeAuthorized {
+						block_hash: hash,
+						code_hash,
+						check_version,
+					})),
+				Err(err) => log::debug!(
+					target: LOG_TARGET,
+					"Failed to decode the authorized upgrade of block {hash:?}: {err}"
+				),
+			}
+		}
+
+		let code_key = StorageKey(well_known_keys::CODE.to_vec());
+		let code_hash = self.client.storage_hash(hash, &code_key)?;
+		let parent_code_hash = self.client.storage_hash(parent, &code_key)?;
+		if let Some(code_hash) = code_hash.filter(|h| Some(*h) != parent_code_hash) {
+			// The code written by a block is used to execute its children.
+			let enactment = number + One::one();
+			self.pending_enactment = Some(enactment);
+			events.push(RuntimeUpgradeEvent::Scheduled(RuntimeUpgradeScheduled {
+				block_hash: hash,
+				code_hash,
+				spec_version: self.client.runtime_version_at(hash)?.spec_version,
+				enactment_block_number: enactment.saturated_into(),
+			}));
+		}
+
+		Ok(events)
+	}
+}
@@ -0,0 +1,141 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::{
+	event::{
+		RuntimeUpgradeAuthorized, RuntimeUpgradeEnacted, RuntimeUpgradeEvent,
+		RuntimeUpgradeScheduled,
+	},
+	runtime_upgrade::{authorized_upgrade_key, RuntimeUpgrade},
+	*,
+};
+
+use codec::Encode;
+use jsonrpsee::{core::EmptyServerParams as EmptyParams, server::Subscription, RpcModule};
+use sc_block_builder::BlockBuilderBuilder;
+use sp_blockchain::HeaderBackend;
+use sp_consensus::BlockOrigin;
+use sp_core::{storage::well_known_keys::CODE, testing::TaskExecutor, Blake2Hasher, Hasher};
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
+use std::sync::Arc;
+use substrate_test_runtime_client::{
+	prelude::*, runtime, Backend, BlockBuilderExt, Client, ClientBlockImportExt,
+};
+
+type Block = substrate_test_runtime_client::runtime::Block;
+
+fn setup_api() -> (Arc<Client<Backend>>, RpcModule<RuntimeUpgrade<Backend, Block, Client<Backend>>>)
+{
+	let client = Arc::new(TestClientBuilder::new().build());
+	let api = RuntimeUpgrade::new(client.clone(), Arc::new(TaskExecutor::default())).into_rpc();
+
+	(client, api)
+}
+
+async fn get_next_event(sub: &mut Subscription) -> RuntimeUpgradeEvent<String> {
+	let (event, _sub_id) = tokio::time::timeout(std::time::Duration::from_secs(60), sub.next())
+		.await
+		.unwrap()
+		.unwrap()
+		.unwrap();
+	event
+}
+
+async fn import_and_finalize(
+	client: &Arc<Client<Backend>>,
+	changes: Vec<(Vec<u8>, Vec<u8>)>,
+) -> <Block as BlockT>::Hash {
+	let info = client.chain_info();
+	let mut builder = BlockBuilderBuilder::new(&**client)
+		.on_parent_block(info.finalized_hash)
+		.with_parent_block_number(info.finalized_number)
+		.build()
+		.unwrap();
+	for (key, value) in changes {
+		builder.push_storage_change(key, Some(value)).unwrap();
+	}
+	let block = builder.build().unwrap().block;
+	let hash = block.header.hash();
+	client.import(BlockOrigin::Own, block).await.unwrap();
+	client.finalize_block(hash, None).unwrap();
+	hash
+}
+
+#[tokio::test]
+async fn runtime_upgrade_lifecycle() {
+	let (client, api) = setup_api();
+	let mut sub = api
+		.subscribe_unbounded("runtimeUpgrade_unstable_subscribe", EmptyParams::new())
+		.await
+		.unwrap();
+
+	// Embed a new spec version into the runtime.
+	let wasm = sp_maybe_compressed_blob::decompress(
+		runtime::wasm_binary_unwrap(),
+		sp_maybe_compressed_blob::CODE_BLOB_BOMB_LIMIT,
+	)
+	.unwrap();
+	let mut version = runtime::VERSION;
+	version.spec_version += 1;
+	let embedded = sp_version::embed::embed_runtime_version(&wasm, version.clone()).unwrap();
+	let wasm = sp_maybe_compressed_blob::compress(
+		&embedded,
+		sp_maybe_compressed_blob::CODE_BLOB_BOMB_LIMIT,
+	)
+	.unwrap();
+	let code_hash = Blake2Hasher::hash(&wasm);
+
+	// Authorize the upgrade.
+	let block_hash = import_and_finalize(
+		&client,
+		vec![(authorized_upgrade_key().0, (code_hash, true).encode())],
+	)
+	.await;
+	assert_eq!(
+		get_next_event(&mut sub).await,
+		RuntimeUpgradeEvent::Authorized(RuntimeUpgradeAuthorized {
+			block_hash: format!("{:?}", block_hash),
+			code_hash: format!("{:?}", code_hash),
+			check_version: true,
+		})
+	);
+
+	// Apply the upgrade.
+	let block_hash = import_and_finalize(&client, vec![(CODE.to_vec(), wasm)]).await;
+	let number = client.number(block_hash).unwrap().unwrap();
+	assert_eq!(
+		get_next_event(&mut sub).await,
+		RuntimeUpgradeEvent::Scheduled(RuntimeUpgradeScheduled {
+			block_hash: format!("{:?}", block_hash),
+			code_hash: format!("{:?}", code_hash),
+			spec_version: version.spec_version,
+			enactment_block_number: number as u64 + 1,
+		})
+	);
+
+	// The child is executed with the new code.
+	let block_hash = import_and_finalize(&client, vec![]).await;
+	assert_eq!(
+		get_next_event(&mut sub).await,
+		RuntimeUpgradeEvent::Enacted(RuntimeUpgradeEnacted {
+			block_hash: format!("{:?}", block_hash),
+			spec_name: version.spec_name.to_string(),
+			spec_version: version.spec_version,
+		})
+	);
+}
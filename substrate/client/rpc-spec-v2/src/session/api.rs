@@ -0,0 +1,72 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+//! API trait of the sessions.
+
+use crate::session::event::SessionEvent;
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+
+#[rpc(client, server)]
+pub trait SessionApi {
+	/// Start a session on the current connection.
+	///
+	/// The `chainHead_follow` and `transactionWatch_submitAndWatch` subscriptions started on the
+	/// connection afterwards are part of the session. When the connection is lost, they keep
+	/// running for a limited time while their events are kept, up to a limit. The session and
+	/// its subscriptions can then be resumed from another connection with `session_resume`.
+	///
+	/// The first event reports the token of the session. Unsubscribing detaches the session from
+	/// the connection, like losing the connection.
+	///
+	/// # Unstable
+	///
+	/// This method is unstable and subject to change in the future.
+	#[subscription(
+		name = "session_unstable_start" => "session_unstable_sessionEvent",
+		unsubscribe = "session_unstable_detach",
+		item = SessionEvent,
+	)]
+	fn start(&self);
+
+	/// Resume the session of the given token on the current connection.
+	///
+	/// The events the subscriptions of the session produced while the session was detached are
+	/// reported first, then their new events. The subscriptions started on other connections
+	/// keep their identifiers and can be used with the `chainHead` methods of the current
+	/// connection.
+	///
+	/// # Unstable
+	///
+	/// This method is unstable and subject to change in the future.
+	#[subscription(
+		name = "session_unstable_resume" => "session_unstable_resumedSessionEvent",
+		unsubscribe = "session_unstable_detachResumed",
+		item = SessionEvent,
+	)]
+	fn resume(&self, token: String);
+
+	/// Stop a subscription of the session attached to the current connection.
+	///
+	/// This is the way to stop the subscriptions started on another connection, which can't be
+	/// unsubscribed from the current one. Returns whether the subscription was stopped.
+	///
+	/// # Unstable
+	///
+	/// This method is unstable and subject to change in the future.
+	#[method(name = "session_unstable_unsubscribe", raw_method)]
+	async fn unsubscribe(&self, subscription: String) -> RpcResult<bool>;
+}
@@ -0,0 +1,58 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+//! Session RPC errors.
+
+use jsonrpsee::types::error::ErrorObject;
+
+/// Session RPC errors.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	/// The maximum number of sessions is reached.
+	#[error("Maximum number of sessions has been reached")]
+	ReachedLimits,
+	/// The session token is unknown or the session expired.
+	#[error("Invalid session token")]
+	InvalidToken,
+	/// A session is already attached to the connection.
+	#[error("A session is already attached to the connection")]
+	AlreadyAttached,
+}
+
+/// Errors of the session RPC module.
+pub mod rpc_spec_v2 {
+	/// The maximum number of sessions is reached.
+	pub const REACHED_LIMITS: i32 = -32870;
+	/// The session token is unknown or the session expired.
+	pub const INVALID_TOKEN: i32 = -32871;
+	/// A session is already attached to the connection.
+	pub const ALREADY_ATTACHED: i32 = -32872;
+}
+
+impl From<Error> for ErrorObject<'static> {
+	fn from(e: Error) -> Self {
+		let msg = e.to_string();
+
+		match e {
+			Error::ReachedLimits =>
+				ErrorObject::owned(rpc_spec_v2::REACHED_LIMITS, msg, None::<()>),
+			Error::InvalidToken => ErrorObject::owned(rpc_spec_v2::INVALID_TOKEN, msg, None::<()>),
+			Error::AlreadyAttached =>
+				ErrorObject::owned(rpc_spec_v2::ALREADY_ATTACHED, msg, None::<()>),
+		}
+	}
+}
@@ -0,0 +1,63 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+//! The session events returned as json compatible objects.
+
+use serde::{Deserialize, Serialize};
+
+/// The session is attached to the connection.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionAttached {
+	/// The token resuming the session from another connection.
+	pub token: String,
+}
+
+/// An event of a subscription started on another connection.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionSubscriptionEvent {
+	/// The identifier of the subscription.
+	pub subscription: String,
+	/// The event, as reported by the subscription.
+	pub event: serde_json::Value,
+}
+
+/// The subscription missed too many events while the session was detached and was stopped.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionSubscriptionLost {
+	/// The identifier of the subscription.
+	pub subscription: String,
+}
+
+/// The event generated by the `session_start` and `session_resume` methods.
+///
+/// The [`SessionEvent::Attached`] event is generated first. It is followed by the events the
+/// subscriptions of the session missed while it was detached, then by the events the
+/// subscriptions started on other connections generate.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(tag = "event")]
+pub enum SessionEvent {
+	/// The session is attached to the connection.
+	Attached(SessionAttached),
+	/// An event of a subscription of the session.
+	SubscriptionEvent(SessionSubscriptionEvent),
+	/// A subscription of the session was stopped while the session was detached.
+	SubscriptionLost(SessionSubscriptionLost),
+}
@@ -0,0 +1,484 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+//! Bookkeeping of the sessions and of the events their subscriptions miss while detached.
+
+use crate::session::{
+	error::Error,
+	event::{SessionEvent, SessionSubscriptionEvent, SessionSubscriptionLost},
+};
+use jsonrpsee::{ConnectionId, SubscriptionSink};
+use parking_lot::Mutex;
+use rand::{distributions::Alphanumeric, Rng};
+use std::{
+	collections::{HashMap, VecDeque},
+	sync::Arc,
+	time::Duration,
+};
+use tokio::sync::watch;
+
+/// The configuration of the sessions.
+#[derive(Debug, Clone)]
+pub struct SessionConfig {
+	/// The maximum number of sessions, attached or not.
+	pub max_sessions: usize,
+	/// The maximum duration a session stays detached from any connection before it expires and
+	/// its subscriptions are stopped.
+	pub max_detached_duration: Duration,
+	/// The maximum number of events kept for each subscription while the session is detached.
+	///
+	/// The subscriptions missing more events are stopped.
+	pub max_missed_events: usize,
+}
+
+/// The maximum number of sessions.
+const MAX_SESSIONS: usize = 1024;
+
+/// The maximum duration a session stays detached.
+/// Note: This should be enough to reconnect after a network change.
+const MAX_DETACHED_DURATION: Duration = Duration::from_secs(60);
+
+/// The maximum number of events kept for each subscription of a detached session.
+const MAX_MISSED_EVENTS: usize = 512;
+
+/// The length of the session tokens.
+const TOKEN_LEN: usize = 32;
+
+impl Default for SessionConfig {
+	fn default() -> Self {
+		SessionConfig {
+			max_sessions: MAX_SESSIONS,
+			max_detached_duration: MAX_DETACHED_DURATION,
+			max_missed_events: MAX_MISSED_EVENTS,
+		}
+	}
+}
+
+/// A subscription of a session.
+struct SubscriptionData {
+	/// The connection the subscription was started on.
+	origin: ConnectionId,
+	/// The serialized events missed while the session was detached.
+	missed: VecDeque<serde_json::Value>,
+	/// The subscription missed too many events and is stopped.
+	lost: bool,
+	/// The subscription generated its last event.
+	ended: bool,
+}
+
+/// A session.
+struct SessionData {
+	/// The connection the session is attached to.
+	connection: Option<ConnectionId>,
+	/// The sink of the session subscription of the connection, once accepted.
+	sink: Option<Arc<SubscriptionSink>>,
+	/// The missed events are being replayed to the sink.
+	replaying: bool,
+	/// Incremented every time the session is resumed.
+	generation: u64,
+	/// The subscriptions of the session, by identifier.
+	subscriptions: HashMap<String, SubscriptionData>,
+}
+
+impl SessionData {
+	/// The sink of the session subscription, if it's not closed.
+	fn attached_sink(&self) -> Option<&Arc<SubscriptionSink>> {
+		self.sink.as_ref().filter(|sink| !sink.is_closed())
+	}
+}
+
+#[derive(Default)]
+struct Sessions {
+	/// The sessions by token.
+	by_token: HashMap<String, SessionData>,
+	/// The tokens of the sessions by connection.
+	by_connection: HashMap<ConnectionId, String>,
+}
+
+impl Sessions {
+	/// The session of the token, unless it was resumed since the given generation.
+	fn session_mut(&mut self, token: &str, generation: u64) -> Option<&mut SessionData> {
+		self.by_token.get_mut(token).filter(|session| session.generation == generation)
+	}
+
+	/// The subscription of the session of the token.
+	fn subscription_mut(
+		&mut self,
+		token: &str,
+		subscription: &str,
+	) -> Option<&mut SubscriptionData> {
+		self.by_token.get_mut(token)?.subscriptions.get_mut(subscription)
+	}
+}
+
+/// Where an event of a subscription of a session must be sent.
+pub(crate) enum Route {
+	/// To the sink of the subscription, whose connection the session is attached to.
+	Direct,
+	/// To the sink of the session subscription, on another connection.
+	Session(Arc<SubscriptionSink>),
+	/// Nowhere, the event was kept until the session is resumed.
+	Kept,
+	/// Nowhere, the subscription is stopped.
+	Stopped,
+}
+
+/// The next step of the replay of the missed events.
+pub(crate) enum Replay {
+	/// Send the event, then acknowledge it with [`SessionManager::replayed`].
+	Event(SessionEvent),
+	/// All the missed events were replayed.
+	Done,
+	/// The session was resumed from another connection, or expired.
+	Superseded,
+}
+
+/// Keeps track of the sessions and of their subscriptions, shared by the RPC modules.
+#[derive(Clone)]
+pub struct SessionManager {
+	/// The configuration of the sessions.
+	config: SessionConfig,
+	/// The sessions.
+	sessions: Arc<Mutex<Sessions>>,
+	/// Notified when subscriptions are stopped.
+	stopped: Arc<watch::Sender<()>>,
+}
+
+impl SessionManager {
+	/// Create a new [`SessionManager`].
+	pub fn new(config: SessionConfig) -> Self {
+		SessionManager {
+			config,
+			sessions: Default::default(),
+			stopped: Arc::new(watch::channel(()).0),
+		}
+	}
+
+	/// The maximum duration a session stays detached.
+	pub(crate) fn max_detached_duration(&self) -> Duration {
+		self.config.max_detached_duration
+	}
+
+	/// Open a session on the connection, returning its token.
+	///
+	/// The session is at generation zero.
+	pub(crate) fn open(&self, connection_id: ConnectionId) -> Result<String, Error> {
+		let mut guard = self.sessions.lock();
+		let sessions = &mut *guard;
+
+		if sessions.by_connection.contains_key(&connection_id) {
+			return Err(Error::AlreadyAttached)
+		}
+		if sessions.by_token.len() >= self.config.max_sessions {
+			return Err(Error::ReachedLimits)
+		}
+
+		let mut token = generate_token();
+		while sessions.by_token.contains_key(&token) {
+			token = generate_token();
+		}
+
+		sessions.by_token.insert(
+			token.clone(),
+			SessionData {
+				connection: Some(connection_id),
+				sink: None,
+				replaying: false,
+				generation: 0,
+				subscriptions: Default::default(),
+			},
+		);
+		sessions.by_connection.insert(connection_id, token.clone());
+		Ok(token)
+	}
+
+	/// Attach the session of the token to the connection, returning its new generation.
+	///
+	/// The session is taken over from the connection it is attached to, if any: the network of
+	/// the client may change before the node notices the previous connection is lost.
+	pub(crate) fn resume(&self, token: &str, connection_id: ConnectionId) -> Result<u64, Error> {
+		let mut guard = self.sessions.lock();
+		let sessions = &mut *guard;
+
+		if sessions.by_connection.contains_key(&connection_id) {
+			return Err(Error::AlreadyAttached)
+		}
+		let session = sessions.by_token.get_mut(token).ok_or(Error::InvalidToken)?;
+
+		session.sink = None;
+		session.replaying = false;
+		session.generation += 1;
+		if let Some(previous) = session.connection.replace(connection_id) {
+			sessions.by_connection.remove(&previous);
+		}
+		sessions.by_connection.insert(connection_id, token.to_string());
+		Ok(session.generation)
+	}
+
+	/// Set the sink of the session subscription and start replaying the missed events to it.
+	///
+	/// Returns `false` if the session was resumed from another connection in the meantime.
+	pub(crate) fn attach(&self, token: &str, generation: u64, sink: Arc<SubscriptionSink>) -> bool {
+		let mut sessions = self.sessions.lock();
+		let Some(session) = sessions.session_mut(token, generation) else { return false };
+
+		session.sink = Some(sink);
+		session.replaying = true;
+		true
+	}
+
+	/// The next missed event to replay.
+	pub(crate) fn next_missed(&self, token: &str, generation: u64) -> Replay {
+		let mut sessions = self.sessions.lock();
+		let Some(session) = sessions.session_mut(token, generation) else {
+			return Replay::Superseded
+		};
+
+		for (id, subscription) in &session.subscriptions {
+			if subscription.lost {
+				return Replay::Event(SessionEvent::SubscriptionLost(SessionSubscriptionLost {
+					subscription: id.clone(),
+				}))
+			}
+			if let Some(event) = subscription.missed.front() {
+				return Replay::Event(SessionEvent::SubscriptionEvent(SessionSubscriptionEvent {
+					subscription: id.clone(),
+					event: event.clone(),
+				}))
+			}
+		}
+
+		session.replaying = false;
+		Replay::Done
+	}
+
+	/// Acknowledge the replayed event returned by [`Self::next_missed`].
+	pub(crate) fn replayed(&self, token: &str, generation: u64, event: &SessionEvent) {
+		let mut sessions = self.sessions.lock();
+		let Some(session) = sessions.session_mut(token, generation) else { return };
+
+		match event {
+			SessionEvent::SubscriptionLost(SessionSubscriptionLost { subscription }) => {
+				session.subscriptions.remove(subscription);
+			},
+			SessionEvent::SubscriptionEvent(SessionSubscriptionEvent { subscription, .. }) => {
+				let Some(data) = session.subscriptions.get_mut(subscription) else { return };
+				// The events were discarded if the subscription was lost in the meantime.
+				if !data.lost {
+					data.missed.pop_front();
+				}
+				if data.ended && data.missed.is_empty() && !data.lost {
+					session.subscriptions.remove(subscription);
+				}
+			},
+			SessionEvent::Attached(_) => (),
+		}
+	}
+
+	/// Detach the session from its connection, once the sink of the session subscription is
+	/// closed.
+	///
+	/// Returns `false` if the session was resumed from another connection in the meantime.
+	pub(crate) fn detach(&self, token: &str, generation: u64) -> bool {
+		let mut guard = self.sessions.lock();
+		let sessions = &mut *guard;
+		let Some(session) = sessions
+			.by_token
+			.get_mut(token)
+			.filter(|session| session.generation == generation)
+		else {
+			return false
+		};
+
+		session.sink = None;
+		session.replaying = false;
+		if let Some(connection) = session.connection.take() {
+			sessions.by_connection.remove(&connection);
+		}
+		true
+	}
+
+	/// Remove the session, stopping its subscriptions, unless it was resumed since it was
+	/// detached.
+	pub(crate) fn expire(&self, token: &str, generation: u64) {
+		let mut sessions = self.sessions.lock();
+		let expired = sessions.by_token.get(token).map_or(false, |session| {
+			session.generation == generation && session.connection.is_none()
+		});
+
+		if expired {
+			sessions.by_token.remove(token);
+			self.stopped.send_replace(());
+		}
+	}
+
+	/// Add the subscription to the session attached to the connection, returning the token of
+	/// the session.
+	pub(crate) fn join(&self, connection_id: ConnectionId, subscription: &str) -> Option<String> {
+		let mut guard = self.sessions.lock();
+		let sessions = &mut *guard;
+		let token = sessions.by_connection.get(&connection_id)?;
+		let session = sessions.by_token.get_mut(token)?;
+
+		session.subscriptions.insert(
+			subscription.to_string(),
+			SubscriptionData {
+				origin: connection_id,
+				missed: Default::default(),
+				lost: false,
+				ended: false,
+			},
+		);
+		Some(token.clone())
+	}
+
+	/// Whether a session is attached to the connection.
+	pub(crate) fn has_session(&self, connection_id: ConnectionId) -> bool {
+		self.sessions.lock().by_connection.contains_key(&connection_id)
+	}
+
+	/// Whether the session attached to the connection contains the subscription.
+	pub(crate) fn contains_subscription(
+		&self,
+		connection_id: ConnectionId,
+		subscription: &str,
+	) -> bool {
+		let sessions = self.sessions.lock();
+		sessions
+			.by_connection
+			.get(&connection_id)
+			.and_then(|token| sessions.by_token.get(token))
+			.and_then(|session| session.subscriptions.get(subscription))
+			.map_or(false, |data| !data.lost && !data.ended)
+	}
+
+	/// Where to send the event of the subscription. The event is kept if the session is
+	/// detached, or its missed events are being replayed.
+	pub(crate) fn route(
+		&self,
+		token: &str,
+		subscription: &str,
+		event: &serde_json::Value,
+	) -> Route {
+		let mut sessions = self.sessions.lock();
+		let Some(session) = sessions.by_token.get_mut(token) else { return Route::Stopped };
+		let sink = session.attached_sink().cloned();
+		let connection = session.connection;
+		let replaying = session.replaying;
+
+		let Some(data) = session.subscriptions.get_mut(subscription) else { return Route::Stopped };
+		if data.lost {
+			return Route::Stopped
+		}
+
+		match sink {
+			Some(_) if connection == Some(data.origin) => Route::Direct,
+			Some(sink) if !replaying => Route::Session(sink),
+			_ => self.keep_event(data, event),
+		}
+	}
+
+	/// Keep the event of the subscription, which could not be delivered, until the session is
+	/// resumed.
+	pub(crate) fn keep(&self, token: &str, subscription: &str, event: serde_json::Value) -> Route {
+		let mut sessions = self.sessions.lock();
+		match sessions.subscription_mut(token, subscription) {
+			Some(data) if !data.lost => self.keep_event(data, &event),
+			_ => Route::Stopped,
+		}
+	}
+
+	fn keep_event(&self, data: &mut SubscriptionData, event: &serde_json::Value) -> Route {
+		if data.missed.len() >= self.config.max_missed_events {
+			data.missed.clear();
+			data.lost = true;
+			self.stopped.send_replace(());
+			return Route::Stopped
+		}
+
+		data.missed.push_back(event.clone());
+		Route::Kept
+	}
+
+	/// Whether the session is attached to the connection.
+	pub(crate) fn is_attached_to(&self, token: &str, connection_id: ConnectionId) -> bool {
+		self.sessions.lock().by_token.get(token).map_or(false, |session| {
+			session.connection == Some(connection_id) && session.attached_sink().is_some()
+		})
+	}
+
+	/// Whether the subscription is stopped.
+	pub(crate) fn is_stopped(&self, token: &str, subscription: &str) -> bool {
+		self.sessions
+			.lock()
+			.subscription_mut(token, subscription)
+			.map_or(true, |data| data.lost)
+	}
+
+	/// A receiver notified when subscriptions are stopped.
+	pub(crate) fn watch_stopped(&self) -> watch::Receiver<()> {
+		self.stopped.subscribe()
+	}
+
+	/// Stop the subscription of the session.
+	pub(crate) fn stop(&self, token: &str, subscription: &str) {
+		let mut sessions = self.sessions.lock();
+		let Some(session) = sessions.by_token.get_mut(token) else { return };
+
+		if session.subscriptions.remove(subscription).is_some() {
+			self.stopped.send_replace(());
+		}
+	}
+
+	/// Stop the subscription of the session attached to the connection.
+	///
+	/// Returns `false` if the session doesn't contain the subscription.
+	pub(crate) fn unsubscribe(&self, connection_id: ConnectionId, subscription: &str) -> bool {
+		let Some(token) = self.sessions.lock().by_connection.get(&connection_id).cloned() else {
+			return false
+		};
+		if !self.contains_subscription(connection_id, subscription) {
+			return false
+		}
+
+		self.stop(&token, subscription);
+		true
+	}
+
+	/// The subscription generated its last event.
+	///
+	/// It's removed from the session once its missed events are replayed.
+	pub(crate) fn end(&self, token: &str, subscription: &str) {
+		let mut sessions = self.sessions.lock();
+		let Some(session) = sessions.by_token.get_mut(token) else { return };
+		let Some(data) = session.subscriptions.get_mut(subscription) else { return };
+
+		data.ended = true;
+		if data.missed.is_empty() && !data.lost {
+			session.subscriptions.remove(subscription);
+		}
+	}
+}
+
+/// Generate a random session token.
+pub(crate) fn generate_token() -> String {
+	rand::thread_rng()
+		.sample_iter(Alphanumeric)
+		.take(TOKEN_LEN)
+		.map(char::from)
+		.collect::<String>()
+}
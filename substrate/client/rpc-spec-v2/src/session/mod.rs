@@ -0,0 +1,45 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+//! Substrate session API.
+//!
+//! A session keeps the `chainHead_follow` and `transactionWatch_submitAndWatch` subscriptions of
+//! a connection alive for a while after the connection is lost, such that clients behind flaky
+//! networks can resume them from a new connection instead of restarting all their operations.
+//!
+//! # Note
+//!
+//! Methods are prefixed by `session`.
+
+#[cfg(test)]
+mod tests;
+
+pub mod api;
+pub mod error;
+pub mod event;
+pub mod session;
+
+mod manager;
+mod sink;
+
+pub use api::SessionApiServer;
+pub use event::{SessionAttached, SessionEvent, SessionSubscriptionEvent, SessionSubscriptionLost};
+pub use manager::{SessionConfig, SessionManager};
+pub use session::Session;
+
+pub(crate) use manager::generate_token;
+pub(crate) use sink::{pipe_from_stream, ResumableSink, SubscriptionStopped};
@@ -0,0 +1,140 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+//! API implementation for the sessions.
+
+use crate::{
+	session::{
+		api::SessionApiServer,
+		event::{SessionAttached, SessionEvent},
+		manager::{Replay, SessionManager},
+	},
+	SubscriptionTaskExecutor,
+};
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	ConnectionDetails, PendingSubscriptionSink, SubscriptionSink,
+};
+use sc_rpc::utils::{spawn_subscription_task, to_sub_message};
+use std::sync::Arc;
+
+/// An API for the session RPC calls.
+pub struct Session {
+	/// The sessions, shared with the `chainHead` and `transactionWatch` RPC modules.
+	manager: SessionManager,
+	/// Executor to spawn subscriptions.
+	executor: SubscriptionTaskExecutor,
+}
+
+impl Session {
+	/// Create a new [`Session`].
+	pub fn new(manager: SessionManager, executor: SubscriptionTaskExecutor) -> Self {
+		Session { manager, executor }
+	}
+}
+
+#[async_trait]
+impl SessionApiServer for Session {
+	fn start(&self, pending: PendingSubscriptionSink) {
+		let manager = self.manager.clone();
+
+		let fut = async move {
+			let token = match manager.open(pending.connection_id()) {
+				Ok(token) => token,
+				Err(err) => {
+					pending.reject(err).await;
+					return
+				},
+			};
+
+			run_session(manager, pending, token, 0).await;
+		};
+
+		spawn_subscription_task(&self.executor, fut);
+	}
+
+	fn resume(&self, pending: PendingSubscriptionSink, token: String) {
+		let manager = self.manager.clone();
+
+		let fut = async move {
+			let generation = match manager.resume(&token, pending.connection_id()) {
+				Ok(generation) => generation,
+				Err(err) => {
+					pending.reject(err).await;
+					return
+				},
+			};
+
+			run_session(manager, pending, token, generation).await;
+		};
+
+		spawn_subscription_task(&self.executor, fut);
+	}
+
+	async fn unsubscribe(
+		&self,
+		connection_details: ConnectionDetails,
+		subscription: String,
+	) -> RpcResult<bool> {
+		Ok(self.manager.unsubscribe(connection_details.id(), &subscription))
+	}
+}
+
+/// Drive the session subscription of the session attached to the connection.
+///
+/// The token and the missed events are reported first. The session is detached once the
+/// subscription is closed, and expires unless it is resumed in time.
+async fn run_session(
+	manager: SessionManager,
+	pending: PendingSubscriptionSink,
+	token: String,
+	generation: u64,
+) {
+	if let Ok(sink) = pending.accept().await {
+		let sink = Arc::new(sink);
+		if !manager.attach(&token, generation, sink.clone()) {
+			return
+		}
+
+		let attached = SessionEvent::Attached(SessionAttached { token: token.clone() });
+		if sink.send(to_sub_message(&sink, &attached)).await.is_ok() {
+			replay_missed(&manager, &sink, &token, generation).await;
+			sink.closed().await;
+		}
+	}
+
+	if !manager.detach(&token, generation) {
+		return
+	}
+	tokio::time::sleep(manager.max_detached_duration()).await;
+	manager.expire(&token, generation);
+}
+
+/// Replay the events the subscriptions of the session missed while it was detached.
+async fn replay_missed(
+	manager: &SessionManager,
+	sink: &SubscriptionSink,
+	token: &str,
+	generation: u64,
+) {
+	while let Replay::Event(event) = manager.next_missed(token, generation) {
+		if sink.send(to_sub_message(sink, &event)).await.is_err() {
+			return
+		}
+		manager.replayed(token, generation, &event);
+	}
+}
@@ -0,0 +1,173 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+//! The sink of the subscriptions which may be part of a session.
+
+use crate::{
+	chain_head::chain_head::read_subscription_id_as_string,
+	session::{
+		event::{SessionEvent, SessionSubscriptionEvent},
+		manager::{Route, SessionManager},
+	},
+};
+use futures::{
+	future::{self, Either},
+	FutureExt, Stream, StreamExt,
+};
+use jsonrpsee::{ConnectionId, PendingSubscriptionSink, SubscriptionSink};
+use sc_rpc::utils::to_sub_message;
+use serde::Serialize;
+
+/// The subscription is stopped, the event was not sent.
+#[derive(Debug)]
+pub struct SubscriptionStopped;
+
+/// The subscription of a session, see [`ResumableSink`].
+struct SessionSubscription {
+	/// The sessions.
+	manager: SessionManager,
+	/// The token of the session.
+	token: String,
+	/// The identifier of the subscription.
+	subscription: String,
+	/// The connection the subscription was started on.
+	origin: ConnectionId,
+}
+
+/// The sink of a subscription, which outlives its connection if a session is attached to it.
+///
+/// The events are sent to the sink of the subscription while the session is attached to its
+/// connection, kept while the session is detached, and multiplexed to the sink of the session
+/// subscription once the session is resumed from another connection.
+pub struct ResumableSink {
+	/// The sink of the subscription.
+	sink: SubscriptionSink,
+	/// The session of the subscription, if any.
+	session: Option<SessionSubscription>,
+}
+
+impl ResumableSink {
+	/// Wrap the sink, adding the subscription to the session attached to its connection, if any.
+	pub fn new(sink: SubscriptionSink, manager: Option<&SessionManager>) -> Self {
+		let session = manager.and_then(|manager| {
+			let subscription = read_subscription_id_as_string(&sink);
+			let origin = sink.connection_id();
+			let token = manager.join(origin, &subscription)?;
+			Some(SessionSubscription { manager: manager.clone(), token, subscription, origin })
+		});
+
+		ResumableSink { sink, session }
+	}
+
+	/// Send the event to the client, or keep it until the session is resumed.
+	pub async fn send(&self, event: &impl Serialize) -> Result<(), SubscriptionStopped> {
+		let Some(session) = &self.session else {
+			return self
+				.sink
+				.send(to_sub_message(&self.sink, event))
+				.await
+				.map_err(|_| SubscriptionStopped)
+		};
+
+		let event = serde_json::to_value(event).expect("Serialize infallible; qed");
+		match session.manager.route(&session.token, &session.subscription, &event) {
+			Route::Direct => {
+				if self.sink.send(to_sub_message(&self.sink, &event)).await.is_ok() {
+					return Ok(())
+				}
+				// The subscription was unsubscribed, unless the connection was lost.
+				if session.manager.is_attached_to(&session.token, session.origin) {
+					return Err(SubscriptionStopped)
+				}
+			},
+			Route::Session(sink) => {
+				let multiplexed = SessionEvent::SubscriptionEvent(SessionSubscriptionEvent {
+					subscription: session.subscription.clone(),
+					event: event.clone(),
+				});
+				if sink.send(to_sub_message(&sink, &multiplexed)).await.is_ok() {
+					return Ok(())
+				}
+			},
+			Route::Kept => return Ok(()),
+			Route::Stopped => return Err(SubscriptionStopped),
+		}
+
+		match session.manager.keep(&session.token, &session.subscription, event) {
+			Route::Kept => Ok(()),
+			_ => Err(SubscriptionStopped),
+		}
+	}
+
+	/// Completes when the subscription is stopped.
+	///
+	/// The subscription of a session is not stopped when its connection is lost, but when it's
+	/// unsubscribed, misses too many events, or when the session expires.
+	pub async fn closed(&self) {
+		let Some(session) = &self.session else { return self.sink.closed().await };
+
+		let mut stopped = session.manager.watch_stopped();
+		let sink_closed = self.sink.closed().fuse();
+		futures::pin_mut!(sink_closed);
+		loop {
+			if session.manager.is_stopped(&session.token, &session.subscription) {
+				return
+			}
+
+			futures::select! {
+				_ = sink_closed => {
+					// The subscription was unsubscribed, unless the connection was lost.
+					if session.manager.is_attached_to(&session.token, session.origin) {
+						session.manager.stop(&session.token, &session.subscription);
+						return
+					}
+				},
+				_ = stopped.changed().fuse() => {},
+			}
+		}
+	}
+}
+
+impl Drop for ResumableSink {
+	fn drop(&mut self) {
+		if let Some(session) = &self.session {
+			session.manager.end(&session.token, &session.subscription);
+		}
+	}
+}
+
+/// Feed the items of the stream to the accepted subscription, which is part of the session
+/// attached to its connection, until the stream ends or the subscription is stopped.
+pub async fn pipe_from_stream<S, T>(
+	pending: PendingSubscriptionSink,
+	mut stream: S,
+	manager: &SessionManager,
+) where
+	S: Stream<Item = T> + Unpin,
+	T: Serialize,
+{
+	let Ok(sink) = pending.accept().await else { return };
+	let sink = ResumableSink::new(sink, Some(manager));
+
+	let closed = sink.closed();
+	futures::pin_mut!(closed);
+	while let Either::Left((Some(item), _)) = future::select(stream.next(), closed.as_mut()).await {
+		if sink.send(&item).await.is_err() {
+			break
+		}
+	}
+}
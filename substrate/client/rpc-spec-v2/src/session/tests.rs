@@ -0,0 +1,231 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+use super::*;
+use crate::{
+	chain_head::{api::ChainHeadApiClient, ChainHead, ChainHeadConfig, FollowEvent},
+	session::api::SessionApiClient,
+};
+use assert_matches::assert_matches;
+use jsonrpsee::{
+	core::client::{Subscription as RpcClientSubscription, SubscriptionKind},
+	types::SubscriptionId,
+	ws_client::{WsClient, WsClientBuilder},
+	RpcModule,
+};
+use sc_block_builder::BlockBuilderBuilder;
+use sp_blockchain::HeaderBackend;
+use sp_consensus::BlockOrigin;
+use sp_core::testing::TaskExecutor;
+use sp_runtime::traits::Block as BlockT;
+use std::{sync::Arc, time::Duration};
+use substrate_test_runtime_client::{prelude::*, Client};
+
+/// The time the server is given to notice a lost connection, or to process a new block.
+const PROCESSING_DELAY: Duration = Duration::from_millis(500);
+
+/// Start an RPC server with the chainHead and session modules.
+async fn run_server(config: SessionConfig) -> (Arc<Client<Backend>>, String) {
+	let builder = TestClientBuilder::new();
+	let backend = builder.backend();
+	let client = Arc::new(builder.build());
+	let executor = Arc::new(TaskExecutor::default());
+	let sessions = SessionManager::new(config);
+
+	let mut api = RpcModule::new(());
+	api.merge(
+		ChainHead::new(client.clone(), backend, executor.clone(), ChainHeadConfig::default())
+			.with_sessions(sessions.clone())
+			.into_rpc(),
+	)
+	.unwrap();
+	api.merge(Session::new(sessions, executor).into_rpc()).unwrap();
+
+	let server = jsonrpsee::server::ServerBuilder::default().build("127.0.0.1:0").await.unwrap();
+	let url = format!("ws://{}", server.local_addr().unwrap());
+	let handle = server.start(api);
+	tokio::spawn(handle.stopped());
+
+	(client, url)
+}
+
+async fn connect(url: &str) -> WsClient {
+	WsClientBuilder::default().build(url).await.unwrap()
+}
+
+async fn get_next_event<T: serde::de::DeserializeOwned>(sub: &mut RpcClientSubscription<T>) -> T {
+	tokio::time::timeout(Duration::from_secs(60), sub.next())
+		.await
+		.unwrap()
+		.unwrap()
+		.unwrap()
+}
+
+fn subscription_id<T>(sub: &RpcClientSubscription<T>) -> String {
+	match sub.kind() {
+		SubscriptionKind::Subscription(SubscriptionId::Num(num)) => num.to_string(),
+		SubscriptionKind::Subscription(SubscriptionId::Str(s)) => s.to_string(),
+		_ => panic!("Unexpected subscription ID"),
+	}
+}
+
+/// Start a session and a follow subscription on a new connection, returning the token of the
+/// session and the identifier of the subscription.
+async fn follow_in_session(url: &str) -> (WsClient, String, String) {
+	let client = connect(url).await;
+
+	let mut session = SessionApiClient::start(&client).await.unwrap();
+	let token = match get_next_event(&mut session).await {
+		SessionEvent::Attached(SessionAttached { token }) => token,
+		event => panic!("Expected SessionEvent::Attached, got {event:?}"),
+	};
+
+	let mut follow: RpcClientSubscription<FollowEvent<String>> =
+		ChainHeadApiClient::<String>::chain_head_unstable_follow(&client, false, None)
+			.await
+			.unwrap();
+	assert_matches!(get_next_event(&mut follow).await, FollowEvent::Initialized(_));
+	let follow_id = subscription_id(&follow);
+
+	(client, token, follow_id)
+}
+
+async fn import_block(client: &Client<Backend>) -> String {
+	let info = client.info();
+	let block = BlockBuilderBuilder::new(client)
+		.on_parent_block(info.best_hash)
+		.with_parent_block_number(info.best_number)
+		.build()
+		.unwrap()
+		.build()
+		.unwrap()
+		.block;
+	let hash = format!("{:?}", block.hash());
+	client.import(BlockOrigin::Own, block).await.unwrap();
+	hash
+}
+
+fn follow_event(event: SessionEvent, follow_id: &str) -> FollowEvent<String> {
+	match event {
+		SessionEvent::SubscriptionEvent(SessionSubscriptionEvent { subscription, event }) => {
+			assert_eq!(subscription, follow_id);
+			serde_json::from_value(event).unwrap()
+		},
+		event => panic!("Expected SessionEvent::SubscriptionEvent, got {event:?}"),
+	}
+}
+
+#[tokio::test]
+async fn resume_follow_subscription() {
+	let (client, url) = run_server(SessionConfig::default()).await;
+	let (connection, token, follow_id) = follow_in_session(&url).await;
+
+	// The events generated while the session is detached are kept.
+	drop(connection);
+	tokio::time::sleep(PROCESSING_DELAY).await;
+	let block_hash = import_block(&client).await;
+
+	let connection = connect(&url).await;
+	let mut session = SessionApiClient::resume(&connection, token.clone()).await.unwrap();
+	assert_eq!(
+		get_next_event(&mut session).await,
+		SessionEvent::Attached(SessionAttached { token })
+	);
+	assert_matches!(
+		follow_event(get_next_event(&mut session).await, &follow_id),
+		FollowEvent::NewBlock(new_block) if new_block.block_hash == block_hash
+	);
+	assert_matches!(
+		follow_event(get_next_event(&mut session).await, &follow_id),
+		FollowEvent::BestBlockChanged(best) if best.best_block_hash == block_hash
+	);
+
+	// The new events are multiplexed to the session subscription.
+	let block_hash = import_block(&client).await;
+	assert_matches!(
+		follow_event(get_next_event(&mut session).await, &follow_id),
+		FollowEvent::NewBlock(new_block) if new_block.block_hash == block_hash
+	);
+
+	// The follow subscription can be used from the new connection.
+	let header = ChainHeadApiClient::<String>::chain_head_unstable_header(
+		&connection,
+		follow_id.clone(),
+		block_hash.clone(),
+	)
+	.await
+	.unwrap();
+	assert!(header.is_some());
+
+	// Until it's stopped.
+	assert!(SessionApiClient::unsubscribe(&connection, follow_id.clone()).await.unwrap());
+	let header = ChainHeadApiClient::<String>::chain_head_unstable_header(
+		&connection,
+		follow_id.clone(),
+		block_hash,
+	)
+	.await
+	.unwrap();
+	assert!(header.is_none());
+	assert!(!SessionApiClient::unsubscribe(&connection, follow_id).await.unwrap());
+}
+
+#[tokio::test]
+async fn subscription_lost_after_max_missed_events() {
+	let config = SessionConfig { max_missed_events: 1, ..Default::default() };
+	let (client, url) = run_server(config).await;
+	let (connection, token, follow_id) = follow_in_session(&url).await;
+
+	drop(connection);
+	tokio::time::sleep(PROCESSING_DELAY).await;
+	// The `newBlock` and `bestBlockChanged` events exceed the limit.
+	import_block(&client).await;
+	tokio::time::sleep(PROCESSING_DELAY).await;
+
+	let connection = connect(&url).await;
+	let mut session = SessionApiClient::resume(&connection, token).await.unwrap();
+	assert_matches!(get_next_event(&mut session).await, SessionEvent::Attached(_));
+	assert_eq!(
+		get_next_event(&mut session).await,
+		SessionEvent::SubscriptionLost(SessionSubscriptionLost { subscription: follow_id })
+	);
+}
+
+#[tokio::test]
+async fn session_expires() {
+	let config =
+		SessionConfig { max_detached_duration: Duration::from_millis(100), ..Default::default() };
+	let (_client, url) = run_server(config).await;
+	let (connection, token, _follow_id) = follow_in_session(&url).await;
+
+	drop(connection);
+	tokio::time::sleep(PROCESSING_DELAY * 2).await;
+
+	let connection = connect(&url).await;
+	let err = SessionApiClient::resume(&connection, token).await.unwrap_err();
+	assert!(err.to_string().contains("Invalid session token"));
+}
+
+#[tokio::test]
+async fn one_session_per_connection() {
+	let (_client, url) = run_server(SessionConfig::default()).await;
+	let (connection, token, _follow_id) = follow_in_session(&url).await;
+
+	assert!(SessionApiClient::start(&connection).await.is_err());
+	// The session is already attached to the connection.
+	assert!(SessionApiClient::resume(&connection, token).await.is_err());
+}
@@ -18,7 +18,13 @@
 
 //! API trait for transactions.
 
-use crate::transaction::{error::ErrorBroadcast, event::TransactionEvent};
+use crate::transaction::{
+	error::{ErrorBroadcast, ErrorDecode, ErrorFee},
+	event::{
+		TransactionBatchEvent, TransactionBroadcastOptions, TransactionBroadcastStatus,
+		TransactionDecoded, TransactionEvent, TransactionFeeEstimate, TransactionWatchOptions,
+	},
+};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use sp_core::Bytes;
 
@@ -29,6 +35,9 @@ pub trait TransactionApi<Hash: Clone> {
 	/// See [`TransactionEvent`](crate::transaction::event::TransactionEvent) for details on
 	/// transaction life cycle.
 	///
+	/// The `options` are optional, see [`TransactionWatchOptions`] for the capabilities they
+	/// enable.
+	///
 	/// # Unstable
 	///
 	/// This method is unstable and subject to change in the future.
@@ -37,18 +46,48 @@ pub trait TransactionApi<Hash: Clone> {
 		unsubscribe = "transactionWatch_unstable_unwatch",
 		item = TransactionEvent<Hash>,
 	)]
-	fn submit_and_watch(&self, bytes: Bytes);
+	fn submit_and_watch(&self, bytes: Bytes, options: Option<TransactionWatchOptions>);
+
+	/// Submit an ordered batch of extrinsics to watch.
+	///
+	/// The extrinsics are submitted in order, each one once the previous one entered the pool,
+	/// such that consecutive transactions of an account, e.g. with the nonces `N`, `N + 1` and
+	/// `N + 2`, are all accepted. If an extrinsic is rejected, the following ones are not
+	/// submitted and a `Dropped` event is generated for them.
+	///
+	/// The events of the transactions are multiplexed over the subscription as
+	/// [`TransactionBatchEvent`]s, which identify the transactions by their index in the batch.
+	/// The subscription ends once all the transactions reached a final state.
+	///
+	/// The `options` apply to all the transactions of the batch.
+	///
+	/// # Unstable
+	///
+	/// This method is unstable and subject to change in the future.
+	#[subscription(
+		name = "transactionWatch_unstable_submitAndWatchBatch" => "transactionWatch_unstable_watchBatchEvent",
+		unsubscribe = "transactionWatch_unstable_unwatchBatch",
+		item = TransactionBatchEvent<Hash>,
+	)]
+	fn submit_and_watch_batch(&self, bytes: Vec<Bytes>, options: Option<TransactionWatchOptions>);
 }
 
 #[rpc(client, server)]
-pub trait TransactionBroadcastApi {
+pub trait TransactionBroadcastApi<Hash: Clone> {
 	/// Broadcast an extrinsic to the chain.
 	///
+	/// The `options` are optional, see [`TransactionBroadcastOptions`] for the limits of the
+	/// operation they set.
+	///
 	/// # Unstable
 	///
 	/// This method is unstable and subject to change in the future.
 	#[method(name = "transaction_unstable_broadcast")]
-	fn broadcast(&self, bytes: Bytes) -> RpcResult<Option<String>>;
+	fn broadcast(
+		&self,
+		bytes: Bytes,
+		options: Option<TransactionBroadcastOptions>,
+	) -> RpcResult<Option<String>>;
 
 	/// Broadcast an extrinsic to the chain.
 	///
@@ -57,4 +96,49 @@ pub trait TransactionBroadcastApi {
 	/// This method is unstable and subject to change in the future.
 	#[method(name = "transaction_unstable_stop")]
 	fn stop_broadcast(&self, operation_id: String) -> Result<(), ErrorBroadcast>;
+
+	/// Get the latest known state of a `transaction_unstable_broadcast` operation.
+	///
+	/// The latest events of the transaction are retained until a bounded number of operations
+	/// have ended after it, such that the clients which cannot hold a subscription, e.g. over
+	/// HTTP, can poll them. The events of a stopped operation are discarded.
+	///
+	/// # Unstable
+	///
+	/// This method is unstable and subject to change in the future.
+	#[method(name = "transaction_unstable_status")]
+	fn broadcast_status(
+		&self,
+		operation_id: String,
+	) -> Result<TransactionBroadcastStatus<Hash>, ErrorBroadcast>;
+}
+
+#[rpc(client, server)]
+pub trait TransactionFeeApi {
+	/// Estimate the fee of an extrinsic at the best block.
+	///
+	/// The fee is computed by the `TransactionPaymentApi` of the runtime, which doesn't check the
+	/// validity of the extrinsic: the signature of a signed extrinsic may be a dummy one. The
+	/// breakdown is returned as a versioned [`TransactionFeeEstimate`].
+	///
+	/// # Unstable
+	///
+	/// This method is unstable and subject to change in the future.
+	#[method(name = "transaction_unstable_estimateFee", blocking)]
+	fn estimate_fee(&self, bytes: Bytes) -> Result<TransactionFeeEstimate, ErrorFee>;
+}
+
+#[rpc(client, server)]
+pub trait TransactionDecodeApi {
+	/// Decode an extrinsic with the metadata of the runtime at the best block.
+	///
+	/// Returns the pallet and call of the extrinsic with its arguments, and for a signed extrinsic
+	/// its signer, nonce, tip and mortality window, see [`TransactionDecoded`]. The validity of
+	/// the extrinsic is not checked.
+	///
+	/// # Unstable
+	///
+	/// This method is unstable and subject to change in the future.
+	#[method(name = "transaction_unstable_decode", blocking)]
+	fn decode(&self, bytes: Bytes) -> Result<TransactionDecoded, ErrorDecode>;
 }
@@ -0,0 +1,180 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Persistence of the `transaction_broadcast` operations across restarts.
+
+use codec::{Decode, Encode};
+use parking_lot::Mutex;
+use sc_client_api::AuxStore;
+use std::sync::Arc;
+
+use super::{event::TransactionBroadcastOptions, transaction::LOG_TARGET};
+
+/// The auxiliary storage key of the pending broadcast operations.
+const BROADCAST_OPERATIONS_KEY: &[u8] = b"rpc_spec_v2_transaction_broadcast_operations";
+
+/// A broadcast operation persisted in the database.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct PersistedBroadcast {
+	/// The operation ID returned to the user.
+	pub operation_id: String,
+	/// The SCALE encoded transaction.
+	pub transaction: Vec<u8>,
+	/// The options of the operation.
+	///
+	/// The maximum duration of a resumed operation is counted from its resumption.
+	pub options: TransactionBroadcastOptions,
+}
+
+/// Object safe access to the auxiliary storage of the client.
+trait BroadcastDb: Send + Sync {
+	fn read(&self) -> sp_blockchain::Result<Option<Vec<u8>>>;
+
+	fn write(&self, value: &[u8]) -> sp_blockchain::Result<()>;
+}
+
+impl<Store: AuxStore + Send + Sync> BroadcastDb for Store {
+	fn read(&self) -> sp_blockchain::Result<Option<Vec<u8>>> {
+		self.get_aux(BROADCAST_OPERATIONS_KEY)
+	}
+
+	fn write(&self, value: &[u8]) -> sp_blockchain::Result<()> {
+		self.insert_aux(&[(BROADCAST_OPERATIONS_KEY, value)], &[])
+	}
+}
+
+/// The pending `transaction_broadcast` operations persisted in the database of the client.
+///
+/// The operations are added when the broadcast starts and removed once it ends, either because
+/// the transaction reached a final state or because the operation was stopped. The operations
+/// still pending when the node shuts down are resubmitted to the pool on startup.
+pub struct BroadcastStore {
+	/// The auxiliary storage of the client.
+	db: Arc<dyn BroadcastDb>,
+	/// The maximum number of persisted operations.
+	max_entries: usize,
+	/// The persisted operations.
+	entries: Mutex<Vec<PersistedBroadcast>>,
+	/// The operations loaded on startup and not resumed yet.
+	pending: Mutex<Vec<PersistedBroadcast>>,
+}
+
+impl BroadcastStore {
+	/// Load the operations persisted in the given store, keeping at most `max_entries` of them.
+	pub fn new<Store: AuxStore + Send + Sync + 'static>(
+		store: Arc<Store>,
+		max_entries: usize,
+	) -> Self {
+		let db: Arc<dyn BroadcastDb> = store;
+
+		let mut entries = match db.read() {
+			Ok(Some(encoded)) => Vec::<PersistedBroadcast>::decode(&mut &encoded[..])
+				.unwrap_or_else(|error| {
+					log::warn!(
+						target: LOG_TARGET,
+						"Discarding the persisted broadcast operations: {error}"
+					);
+					Vec::new()
+				}),
+			Ok(None) => Vec::new(),
+			Err(error) => {
+				log::warn!(
+					target: LOG_TARGET,
+					"Failed to read the persisted broadcast operations: {error}"
+				);
+				Vec::new()
+			},
+		};
+
+		let store = BroadcastStore {
+			db,
+			max_entries,
+			entries: Mutex::new(Vec::new()),
+			pending: Mutex::new(Vec::new()),
+		};
+
+		if entries.len() > max_entries {
+			entries.truncate(max_entries);
+			store.flush(&entries);
+		}
+		if !entries.is_empty() {
+			log::info!(
+				target: LOG_TARGET,
+				"Resuming {} persisted broadcast operations",
+				entries.len()
+			);
+		}
+
+		*store.pending.lock() = entries.clone();
+		*store.entries.lock() = entries;
+		store
+	}
+
+	/// The operations loaded on startup, returned only by the first call.
+	pub fn take_pending(&self) -> Vec<PersistedBroadcast> {
+		std::mem::take(&mut *self.pending.lock())
+	}
+
+	/// Persist the given operation.
+	///
+	/// Returns `false` if the maximum number of persisted operations is reached, in which case
+	/// the operation is not resumed after a restart.
+	pub fn insert(
+		&self,
+		operation_id: &str,
+		transaction: &[u8],
+		options: &TransactionBroadcastOptions,
+	) -> bool {
+		let mut entries = self.entries.lock();
+		if entries.len() >= self.max_entries {
+			return false
+		}
+
+		entries.push(PersistedBroadcast {
+			operation_id: operation_id.into(),
+			transaction: transaction.to_vec(),
+			options: options.clone(),
+		});
+		self.flush(&entries);
+		true
+	}
+
+	/// Remove the given operation, if persisted.
+	pub fn remove(&self, operation_id: &str) {
+		let mut entries = self.entries.lock();
+		let len = entries.len();
+		entries.retain(|entry| entry.operation_id != operation_id);
+		if entries.len() != len {
+			self.flush(&entries);
+		}
+	}
+
+	/// The persisted operations.
+	pub fn entries(&self) -> Vec<PersistedBroadcast> {
+		self.entries.lock().clone()
+	}
+
+	fn flush(&self, entries: &[PersistedBroadcast]) {
+		if let Err(error) = self.db.write(&entries.encode()) {
+			log::warn!(
+				target: LOG_TARGET,
+				"Failed to persist the broadcast operations: {error}"
+			);
+		}
+	}
+}
@@ -0,0 +1,362 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! The dispatch results of the transactions included in the blocks.
+//!
+//! The results are read from the `ExtrinsicSuccess` and `ExtrinsicFailed` events in the
+//! `System::Events` of the blocks. The events are specific to the runtime, so they are decoded
+//! with the type information of its metadata, which is fetched once per runtime version.
+
+use crate::transaction::{
+	event::{
+		TransactionDispatchError, TransactionDispatchResult, TransactionEvent,
+		TransactionModuleError,
+	},
+	transaction::LOG_TARGET,
+};
+use codec::{Compact, Decode};
+use frame_metadata::{v14::StorageEntryType, RuntimeMetadata, RuntimeMetadataPrefixed};
+use parking_lot::Mutex;
+use sc_client_api::{Backend, CallExecutor, ExecutorProvider, StorageKey, StorageProvider};
+use scale_info::{form::PortableForm, PortableRegistry, Type, TypeDef, TypeDefPrimitive, Variant};
+use sp_api::CallContext;
+use sp_blockchain::Error as BlockChainError;
+use sp_runtime::{traits::Block as BlockT, DispatchError, ModuleError};
+use std::{collections::BTreeMap, marker::PhantomData, sync::Arc};
+
+/// The key of the `System::Events` storage item.
+fn system_events_key() -> StorageKey {
+	StorageKey(
+		[sp_crypto_hashing::twox_128(b"System"), sp_crypto_hashing::twox_128(b"Events")].concat(),
+	)
+}
+
+/// Reader of the dispatch results of the transactions.
+pub(crate) struct DispatchResults<BE, Client> {
+	client: Arc<Client>,
+	/// The decoder of the events of the last runtime seen, with its spec version.
+	decoder: Mutex<Option<(u32, Arc<EventsDecoder>)>>,
+	_phantom: PhantomData<BE>,
+}
+
+impl<BE, Client> DispatchResults<BE, Client> {
+	/// Create a new [`DispatchResults`].
+	pub(crate) fn new(client: Arc<Client>) -> Self {
+		Self { client, decoder: Mutex::new(None), _phantom: PhantomData }
+	}
+
+	/// Add the dispatch result of the transaction to the block of `event`, if any.
+	pub(crate) fn add_to_event<Block>(&self, event: &mut TransactionEvent<Block::Hash>)
+	where
+		Block: BlockT,
+		BE: Backend<Block>,
+		Client: ExecutorProvider<Block> + StorageProvider<Block, BE>,
+	{
+		let block = match event {
+			TransactionEvent::BestChainBlockIncluded(Some(block)) |
+			TransactionEvent::Finalized(block) => block,
+			_ => return,
+		};
+		block.dispatch_result =
+			self.dispatch_result::<Block>(block.hash, block.index).unwrap_or_else(|err| {
+				log::debug!(
+					target: LOG_TARGET,
+					"Failed to read the dispatch result of the transaction {} of block {:?}: {err}",
+					block.index,
+					block.hash,
+				);
+				None
+			});
+	}
+
+	/// The dispatch result of the transaction at `index` in the block `hash`, `None` if the block
+	/// has no event reporting it.
+	fn dispatch_result<Block>(
+		&self,
+		hash: Block::Hash,
+		index: usize,
+	) -> Result<Option<TransactionDispatchResult>, BlockChainError>
+	where
+		Block: BlockT,
+		BE: Backend<Block>,
+		Client: ExecutorProvider<Block> + StorageProvider<Block, BE>,
+	{
+		let Some(events) = self.client.storage(hash, &system_events_key())? else {
+			return Ok(None)
+		};
+		self.decoder::<Block>(hash)?
+			.dispatch_result(&events.0, index)
+			.map_err(|err| BlockChainError::Application(Box::new(err)))
+	}
+
+	/// The decoder of the events of the runtime of the block `hash`.
+	fn decoder<Block>(&self, hash: Block::Hash) -> Result<Arc<EventsDecoder>, BlockChainError>
+	where
+		Block: BlockT,
+		Client: ExecutorProvider<Block>,
+	{
+		let executor = self.client.executor();
+		let spec_version = executor.runtime_version(hash)?.spec_version;
+		if let Some((version, decoder)) = &*self.decoder.lock() {
+			if *version == spec_version {
+				return Ok(decoder.clone())
+			}
+		}
+
+		let metadata = executor.call(hash, "Metadata_metadata", &[], CallContext::Offchain)?;
+		let decoder = Arc::new(
+			EventsDecoder::new(&metadata)
+				.map_err(|err| BlockChainError::Application(Box::new(err)))?,
+		);
+		*self.decoder.lock() = Some((spec_version, decoder.clone()));
+		Ok(decoder)
+	}
+}
+
+/// Decoder of the `System::Events` of a runtime.
+pub(crate) struct EventsDecoder {
+	/// The types of the metadata.
+	types: PortableRegistry,
+	/// The type of the `System::Events` storage item.
+	events_ty: u32,
+	/// The names of the pallets and the types of their errors, by index.
+	pallets: BTreeMap<u8, (String, Option<u32>)>,
+}
+
+impl EventsDecoder {
+	/// Create the decoder from the SCALE-encoded `OpaqueMetadata` of the runtime.
+	pub(crate) fn new(metadata: &[u8]) -> Result<Self, codec::Error> {
+		let metadata = Vec::<u8>::decode(&mut &metadata[..])?;
+		let RuntimeMetadata::V14(metadata) = RuntimeMetadataPrefixed::decode(&mut &metadata[..])?.1
+		else {
+			return Err("Unsupported metadata version".into())
+		};
+
+		let events_ty = metadata
+			.pallets
+			.iter()
+			.filter(|pallet| pallet.name == "System")
+			.flat_map(|pallet| pallet.storage.iter().flat_map(|storage| &storage.entries))
+			.find(|entry| entry.name == "Events")
+			.and_then(|entry| match &entry.ty {
+				StorageEntryType::Plain(ty) => Some(ty.id),
+				StorageEntryType::Map { .. } => None,
+			})
+			.ok_or("The runtime has no `System::Events`")?;
+		let pallets = metadata
+			.pallets
+			.into_iter()
+			.map(|pallet| (pallet.index, (pallet.name, pallet.error.map(|error| error.ty.id))))
+			.collect();
+
+		Ok(Self { types: metadata.types, events_ty, pallets })
+	}
+
+	/// The dispatch result of the transaction at `index`, from the encoded `System::Events`.
+	pub(crate) fn dispatch_result(
+		&self,
+		events: &[u8],
+		index: usize,
+	) -> Result<Option<TransactionDispatchResult>, codec::Error> {
+		let TypeDef::Sequence(records) = &self.ty(self.events_ty)?.type_def else {
+			return Err("`System::Events` is not a sequence".into())
+		};
+		let TypeDef::Composite(record) = &self.ty(records.type_param.id)?.type_def else {
+			return Err("The event records are not structs".into())
+		};
+
+		let input = &mut &events[..];
+		let len = Compact::<u32>::decode(input)?.0;
+		for _ in 0..len {
+			let (mut extrinsic, mut result) = (None, None);
+			for field in &record.fields {
+				match field.name.as_deref() {
+					Some("phase") => extrinsic = self.decode_phase(field.ty.id, input)?,
+					Some("event") => result = self.decode_event(field.ty.id, input)?,
+					_ => self.skip(field.ty.id, input)?,
+				}
+			}
+			if extrinsic.map_or(false, |extrinsic| extrinsic as usize == index) && result.is_some()
+			{
+				return Ok(result)
+			}
+		}
+		Ok(None)
+	}
+
+	/// Decode the phase of an event, returning the index of its transaction if any.
+	fn decode_phase(&self, ty: u32, input: &mut &[u8]) -> Result<Option<u32>, codec::Error> {
+		let phase = self.decode_variant(ty, input)?;
+		if phase.name == "ApplyExtrinsic" {
+			return Ok(Some(u32::decode(input)?))
+		}
+		self.skip_fields(phase, input)?;
+		Ok(None)
+	}
+
+	/// Decode an event, returning the dispatch result it reports if any.
+	fn decode_event(
+		&self,
+		ty: u32,
+		input: &mut &[u8],
+	) -> Result<Option<TransactionDispatchResult>, codec::Error> {
+		// The variants of the events of the runtime are the pallets, with their event as field.
+		let pallet = self.decode_variant(ty, input)?;
+		let [event_field] = &pallet.fields[..] else {
+			return self.skip_fields(pallet, input).map(|_| None)
+		};
+		if pallet.name != "System" {
+			return self.skip(event_field.ty.id, input).map(|_| None)
+		}
+
+		let event = self.decode_variant(event_field.ty.id, input)?;
+		let mut result = match event.name.as_str() {
+			"ExtrinsicSuccess" => Some(TransactionDispatchResult::Success),
+			_ => None,
+		};
+		for field in &event.fields {
+			if event.name != "ExtrinsicFailed" || field.name.as_deref() != Some("dispatch_error") {
+				self.skip(field.ty.id, input)?;
+				continue
+			}
+			// The error is skipped with the metadata, such that the input is left at the next
+			// field even if the runtime encodes it differently.
+			let start = *input;
+			self.skip(field.ty.id, input)?;
+			let error = DispatchError::decode(&mut &start[..start.len() - input.len()])?;
+			result = Some(TransactionDispatchResult::Failure(self.dispatch_error(error)));
+		}
+		Ok(result)
+	}
+
+	/// Convert a dispatch error, with the names of the module errors from the metadata.
+	fn dispatch_error(&self, error: DispatchError) -> TransactionDispatchError {
+		let DispatchError::Module(ModuleError { index, error, .. }) = error else {
+			return TransactionDispatchError { error: format!("{error:?}"), module: None }
+		};
+
+		let pallet = self.pallets.get(&index);
+		let name = pallet
+			.and_then(|(_, ty)| self.ty((*ty)?).ok())
+			.and_then(|ty| match &ty.type_def {
+				TypeDef::Variant(errors) =>
+					errors.variants.iter().find(|variant| variant.index == error[0]),
+				_ => None,
+			})
+			.map(|variant| variant.name.clone());
+		TransactionDispatchError {
+			error: "Module".into(),
+			module: Some(TransactionModuleError {
+				index,
+				error: format!("0x{}", hex::encode(error)),
+				pallet: pallet.map(|(name, _)| name.clone()),
+				name,
+			}),
+		}
+	}
+
+	fn ty(&self, id: u32) -> Result<&Type<PortableForm>, codec::Error> {
+		self.types.resolve(id).ok_or_else(|| "Unknown type in the metadata".into())
+	}
+
+	/// Decode the index of a variant of the enum `ty`, leaving the input at its fields.
+	fn decode_variant(
+		&self,
+		ty: u32,
+		input: &mut &[u8],
+	) -> Result<&Variant<PortableForm>, codec::Error> {
+		let TypeDef::Variant(variants) = &self.ty(ty)?.type_def else {
+			return Err("Expected an enum".into())
+		};
+		let index = u8::decode(input)?;
+		variants
+			.variants
+			.iter()
+			.find(|variant| variant.index == index)
+			.ok_or_else(|| "Unknown variant".into())
+	}
+
+	fn skip_fields(
+		&self,
+		variant: &Variant<PortableForm>,
+		input: &mut &[u8],
+	) -> Result<(), codec::Error> {
+		variant.fields.iter().try_for_each(|field| self.skip(field.ty.id, input))
+	}
+
+	/// Skip a value of the type `ty`.
+	fn skip(&self, ty: u32, input: &mut &[u8]) -> Result<(), codec::Error> {
+		match &self.ty(ty)?.type_def {
+			TypeDef::Composite(composite) =>
+				composite.fields.iter().try_for_each(|field| self.skip(field.ty.id, input)),
+			TypeDef::Variant(_) => {
+				let variant = self.decode_variant(ty, input)?;
+				self.skip_fields(variant, input)
+			},
+			TypeDef::Sequence(sequence) => {
+				let len = Compact::<u32>::decode(input)?.0;
+				(0..len).try_for_each(|_| self.skip(sequence.type_param.id, input))
+			},
+			TypeDef::Array(array) =>
+				(0..array.len).try_for_each(|_| self.skip(array.type_param.id, input)),
+			TypeDef::Tuple(tuple) => tuple.fields.iter().try_for_each(|ty| self.skip(ty.id, input)),
+			TypeDef::Primitive(TypeDefPrimitive::Str) => {
+				let len = Compact::<u32>::decode(input)?.0;
+				advance(input, len as usize)
+			},
+			TypeDef::Primitive(primitive) => advance(input, primitive_size(primitive)),
+			TypeDef::Compact(_) => {
+				// The length of a compact is given by the two lowest bits of its first byte.
+				let first = *input.first().ok_or("Not enough data")?;
+				let len = match first & 0b11 {
+					0 => 1,
+					1 => 2,
+					2 => 4,
+					_ => 5 + (first >> 2) as usize,
+				};
+				advance(input, len)
+			},
+			TypeDef::BitSequence(bits) => {
+				let TypeDef::Primitive(store) = &self.ty(bits.bit_store_type.id)?.type_def else {
+					return Err("Unsupported bit store type".into())
+				};
+				let store_size = primitive_size(store);
+				let len = Compact::<u32>::decode(input)?.0 as usize;
+				advance(input, len.div_ceil(8 * store_size) * store_size)
+			},
+		}
+	}
+}
+
+/// The encoded size of the primitives, other than `str`.
+fn primitive_size(primitive: &TypeDefPrimitive) -> usize {
+	match primitive {
+		TypeDefPrimitive::Bool | TypeDefPrimitive::U8 | TypeDefPrimitive::I8 => 1,
+		TypeDefPrimitive::U16 | TypeDefPrimitive::I16 => 2,
+		TypeDefPrimitive::Char | TypeDefPrimitive::U32 | TypeDefPrimitive::I32 => 4,
+		TypeDefPrimitive::U64 | TypeDefPrimitive::I64 => 8,
+		TypeDefPrimitive::U128 | TypeDefPrimitive::I128 => 16,
+		TypeDefPrimitive::U256 | TypeDefPrimitive::I256 => 32,
+		TypeDefPrimitive::Str => 0,
+	}
+}
+
+fn advance(input: &mut &[u8], len: usize) -> Result<(), codec::Error> {
+	*input = input.get(len..).ok_or("Not enough data")?;
+	Ok(())
+}
@@ -20,10 +20,13 @@
 //!
 //! Errors are interpreted as transaction events for subscriptions.
 
-use crate::transaction::event::{TransactionError, TransactionEvent};
+use crate::transaction::event::{
+	InvalidTransactionReason, PoolErrorReason, TransactionError, TransactionErrorCode,
+	TransactionEvent, UnknownTransactionReason,
+};
 use jsonrpsee::types::error::ErrorObject;
 use sc_transaction_pool_api::error::Error as PoolError;
-use sp_runtime::transaction_validity::InvalidTransaction;
+use sp_runtime::transaction_validity::{InvalidTransaction, UnknownTransaction};
 
 /// Transaction RPC errors.
 #[derive(Debug, thiserror::Error)]
@@ -38,68 +41,109 @@ pub enum Error {
 
 impl<Hash> From<Error> for TransactionEvent<Hash> {
 	fn from(e: Error) -> Self {
-		match e {
-			Error::Verification(e) => TransactionEvent::Invalid(TransactionError {
-				error: format!("Verification error: {}", e),
-			}),
-			Error::Pool(PoolError::InvalidTransaction(InvalidTransaction::Custom(e))) =>
-				TransactionEvent::Invalid(TransactionError {
-					error: format!("Invalid transaction with custom error: {}", e),
-				}),
+		let (error, code) = match e {
+			Error::Verification(e) =>
+				(format!("Verification error: {}", e), TransactionErrorCode::Verification),
+			Error::Pool(PoolError::InvalidTransaction(InvalidTransaction::Custom(e))) => (
+				format!("Invalid transaction with custom error: {}", e),
+				invalid_transaction_code(InvalidTransaction::Custom(e)),
+			),
 			Error::Pool(PoolError::InvalidTransaction(e)) => {
 				let msg: &str = e.into();
-				TransactionEvent::Invalid(TransactionError {
-					error: format!("Invalid transaction: {}", msg),
-				})
+				(format!("Invalid transaction: {}", msg), invalid_transaction_code(e))
 			},
 			Error::Pool(PoolError::UnknownTransaction(e)) => {
 				let msg: &str = e.into();
-				TransactionEvent::Invalid(TransactionError {
-					error: format!("Unknown transaction validity: {}", msg),
-				})
+				(format!("Unknown transaction validity: {}", msg), unknown_transaction_code(e))
 			},
-			Error::Pool(PoolError::TemporarilyBanned) =>
-				TransactionEvent::Invalid(TransactionError {
-					error: "Transaction is temporarily banned".into(),
-				}),
-			Error::Pool(PoolError::AlreadyImported(_)) =>
-				TransactionEvent::Invalid(TransactionError {
-					error: "Transaction is already imported".into(),
-				}),
-			Error::Pool(PoolError::TooLowPriority { old, new }) =>
-				TransactionEvent::Invalid(TransactionError {
-					error: format!(
-						"The priority of the transaction is too low (pool {} > current {})",
-						old, new
-					),
-				}),
-			Error::Pool(PoolError::CycleDetected) => TransactionEvent::Invalid(TransactionError {
-				error: "The transaction contains a cyclic dependency".into(),
-			}),
-			Error::Pool(PoolError::ImmediatelyDropped) =>
-				TransactionEvent::Invalid(TransactionError {
-					error: "The transaction could not enter the pool because of the limit".into(),
-				}),
-			Error::Pool(PoolError::Unactionable) => TransactionEvent::Invalid(TransactionError {
-				error: "Transaction cannot be propagated and the local node does not author blocks"
-					.into(),
-			}),
-			Error::Pool(PoolError::NoTagsProvided) => TransactionEvent::Invalid(TransactionError {
-				error: "Transaction does not provide any tags, so the pool cannot identify it"
-					.into(),
-			}),
-			Error::Pool(PoolError::InvalidBlockId(_)) =>
-				TransactionEvent::Invalid(TransactionError {
-					error: "The provided block ID is not valid".into(),
-				}),
-			Error::Pool(PoolError::RejectedFutureTransaction) =>
-				TransactionEvent::Invalid(TransactionError {
-					error: "The pool is not accepting future transactions".into(),
-				}),
-		}
+			Error::Pool(PoolError::TemporarilyBanned { expires_in }) => (
+				"Transaction is temporarily banned".into(),
+				TransactionErrorCode::Pool {
+					reason: PoolErrorReason::TemporarilyBanned,
+					ban_expires_in_ms: Some(expires_in.as_millis().try_into().unwrap_or(u64::MAX)),
+					required_priority: None,
+				},
+			),
+			Error::Pool(PoolError::AlreadyImported(_)) => (
+				"Transaction is already imported".into(),
+				pool_code(PoolErrorReason::AlreadyImported),
+			),
+			Error::Pool(PoolError::TooLowPriority { old, new }) => (
+				format!(
+					"The priority of the transaction is too low (pool {} > current {})",
+					old, new
+				),
+				TransactionErrorCode::Pool {
+					reason: PoolErrorReason::TooLowPriority,
+					ban_expires_in_ms: None,
+					required_priority: Some(old.saturating_add(1)),
+				},
+			),
+			Error::Pool(PoolError::CycleDetected) => (
+				"The transaction contains a cyclic dependency".into(),
+				pool_code(PoolErrorReason::CycleDetected),
+			),
+			Error::Pool(PoolError::ImmediatelyDropped) => (
+				"The transaction could not enter the pool because of the limit".into(),
+				pool_code(PoolErrorReason::ImmediatelyDropped),
+			),
+			Error::Pool(PoolError::Unactionable) => (
+				"Transaction cannot be propagated and the local node does not author blocks".into(),
+				pool_code(PoolErrorReason::Unactionable),
+			),
+			Error::Pool(PoolError::NoTagsProvided) => (
+				"Transaction does not provide any tags, so the pool cannot identify it".into(),
+				pool_code(PoolErrorReason::NoTagsProvided),
+			),
+			Error::Pool(PoolError::InvalidBlockId(_)) => (
+				"The provided block ID is not valid".into(),
+				pool_code(PoolErrorReason::InvalidBlockId),
+			),
+			Error::Pool(PoolError::RejectedFutureTransaction) => (
+				"The pool is not accepting future transactions".into(),
+				pool_code(PoolErrorReason::RejectedFutureTransaction),
+			),
+		};
+
+		let retryable = code.is_retryable();
+		TransactionEvent::Invalid(TransactionError { error, code: Some(code), retryable })
 	}
 }
 
+fn pool_code(reason: PoolErrorReason) -> TransactionErrorCode {
+	TransactionErrorCode::Pool { reason, ban_expires_in_ms: None, required_priority: None }
+}
+
+fn invalid_transaction_code(e: InvalidTransaction) -> TransactionErrorCode {
+	let (reason, code) = match e {
+		InvalidTransaction::Call => (InvalidTransactionReason::Call, None),
+		InvalidTransaction::Payment => (InvalidTransactionReason::Payment, None),
+		InvalidTransaction::Future => (InvalidTransactionReason::Future, None),
+		InvalidTransaction::Stale => (InvalidTransactionReason::Stale, None),
+		InvalidTransaction::BadProof => (InvalidTransactionReason::BadProof, None),
+		InvalidTransaction::AncientBirthBlock =>
+			(InvalidTransactionReason::AncientBirthBlock, None),
+		InvalidTransaction::ExhaustsResources =>
+			(InvalidTransactionReason::ExhaustsResources, None),
+		InvalidTransaction::Custom(code) => (InvalidTransactionReason::Custom, Some(code)),
+		InvalidTransaction::BadMandatory => (InvalidTransactionReason::BadMandatory, None),
+		InvalidTransaction::MandatoryValidation =>
+			(InvalidTransactionReason::MandatoryValidation, None),
+		InvalidTransaction::BadSigner => (InvalidTransactionReason::BadSigner, None),
+	};
+	TransactionErrorCode::InvalidTransaction { reason, code }
+}
+
+fn unknown_transaction_code(e: UnknownTransaction) -> TransactionErrorCode {
+	let (reason, code) = match e {
+		UnknownTransaction::CannotLookup => (UnknownTransactionReason::CannotLookup, None),
+		UnknownTransaction::NoUnsignedValidator =>
+			(UnknownTransactionReason::NoUnsignedValidator, None),
+		UnknownTransaction::Custom(code) => (UnknownTransactionReason::Custom, Some(code)),
+	};
+	TransactionErrorCode::UnknownTransaction { reason, code }
+}
+
 /// TransactionBroadcast error.
 #[derive(Debug, thiserror::Error)]
 pub enum ErrorBroadcast {
@@ -108,11 +152,58 @@ pub enum ErrorBroadcast {
 	InvalidOperationID,
 }
 
+/// Transaction watch subscription error.
+#[derive(Debug, thiserror::Error)]
+pub enum ErrorWatch {
+	/// Maximum number of `transactionWatch` subscriptions has been reached.
+	#[error("Maximum number of transactionWatch subscriptions has been reached")]
+	ReachedLimits,
+}
+
+/// Transaction batch submission error.
+#[derive(Debug, thiserror::Error)]
+pub enum ErrorBatch {
+	/// The batch is empty or contains too many extrinsics.
+	#[error("The batch must contain between 1 and {0} extrinsics")]
+	InvalidBatchSize(usize),
+}
+
+/// Transaction fee estimation error.
+#[derive(Debug, thiserror::Error)]
+pub enum ErrorFee {
+	/// The provided extrinsic cannot be decoded.
+	#[error("Extrinsic bytes cannot be decoded")]
+	InvalidTransaction,
+	/// The runtime cannot estimate the fee.
+	#[error("The fee cannot be estimated: {0}")]
+	Unavailable(String),
+}
+
+/// Errors for `transaction` RPC module, as defined in
+/// <https://github.com/paritytech/json-rpc-interface-spec>.
+pub mod rpc_spec_v2 {
+	/// Maximum number of subscriptions has been reached.
+	pub const REACHED_LIMITS: i32 = -32800;
+}
+
+/// Transaction decoding error.
+#[derive(Debug, thiserror::Error)]
+pub enum ErrorDecode {
+	/// The provided extrinsic cannot be decoded.
+	#[error("Extrinsic bytes cannot be decoded")]
+	InvalidTransaction,
+	/// The extrinsic cannot be decoded with the metadata of the runtime.
+	#[error("The extrinsic cannot be decoded with the metadata: {0}")]
+	Unavailable(String),
+}
+
 /// General purpose errors, as defined in
 /// <https://www.jsonrpc.org/specification#error_object>.
 pub mod json_rpc_spec {
 	/// Invalid parameter error.
 	pub const INVALID_PARAM_ERROR: i32 = -32602;
+	/// Internal server error.
+	pub const INTERNAL_ERROR: i32 = -32603;
 }
 
 impl From<ErrorBroadcast> for ErrorObject<'static> {
@@ -125,3 +216,102 @@ impl From<ErrorBroadcast> for ErrorObject<'static> {
 		}
 	}
 }
+
+impl From<ErrorWatch> for ErrorObject<'static> {
+	fn from(e: ErrorWatch) -> Self {
+		let msg = e.to_string();
+
+		match e {
+			ErrorWatch::ReachedLimits =>
+				ErrorObject::owned(rpc_spec_v2::REACHED_LIMITS, msg, None::<()>),
+		}
+	}
+}
+
+impl From<ErrorBatch> for ErrorObject<'static> {
+	fn from(e: ErrorBatch) -> Self {
+		let msg = e.to_string();
+
+		match e {
+			ErrorBatch::InvalidBatchSize(_) =>
+				ErrorObject::owned(json_rpc_spec::INVALID_PARAM_ERROR, msg, None::<()>),
+		}
+	}
+}
+
+impl From<ErrorFee> for ErrorObject<'static> {
+	fn from(e: ErrorFee) -> Self {
+		let msg = e.to_string();
+
+		match e {
+			ErrorFee::InvalidTransaction =>
+				ErrorObject::owned(json_rpc_spec::INVALID_PARAM_ERROR, msg, None::<()>),
+			ErrorFee::Unavailable(_) =>
+				ErrorObject::owned(json_rpc_spec::INTERNAL_ERROR, msg, None::<()>),
+		}
+	}
+}
+
+impl From<ErrorDecode> for ErrorObject<'static> {
+	fn from(e: ErrorDecode) -> Self {
+		let msg = e.to_string();
+
+		match e {
+			ErrorDecode::InvalidTransaction =>
+				ErrorObject::owned(json_rpc_spec::INVALID_PARAM_ERROR, msg, None::<()>),
+			ErrorDecode::Unavailable(_) =>
+				ErrorObject::owned(json_rpc_spec::INTERNAL_ERROR, msg, None::<()>),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::time::Duration;
+
+	fn error_of(e: PoolError) -> TransactionError {
+		match TransactionEvent::<()>::from(Error::Pool(e)) {
+			TransactionEvent::Invalid(error) => error,
+			event => panic!("Expected TransactionEvent::Invalid, got {event:?}"),
+		}
+	}
+
+	#[test]
+	fn transient_pool_errors_are_retryable() {
+		let error = error_of(PoolError::TemporarilyBanned { expires_in: Duration::from_secs(2) });
+		assert!(error.retryable);
+		assert_eq!(
+			error.code,
+			Some(TransactionErrorCode::Pool {
+				reason: PoolErrorReason::TemporarilyBanned,
+				ban_expires_in_ms: Some(2000),
+				required_priority: None,
+			})
+		);
+
+		let error = error_of(PoolError::TooLowPriority { old: 10, new: 5 });
+		assert!(error.retryable);
+		assert_eq!(
+			error.code,
+			Some(TransactionErrorCode::Pool {
+				reason: PoolErrorReason::TooLowPriority,
+				ban_expires_in_ms: None,
+				required_priority: Some(11),
+			})
+		);
+
+		assert!(error_of(PoolError::ImmediatelyDropped).retryable);
+		assert!(error_of(PoolError::InvalidTransaction(InvalidTransaction::Future)).retryable);
+	}
+
+	#[test]
+	fn invalid_transactions_are_not_retryable() {
+		assert!(!error_of(PoolError::InvalidTransaction(InvalidTransaction::BadProof)).retryable);
+		assert!(!error_of(PoolError::InvalidTransaction(InvalidTransaction::Stale)).retryable);
+		assert!(!error_of(PoolError::AlreadyImported(Box::new(()))).retryable);
+		assert!(
+			!error_of(PoolError::UnknownTransaction(UnknownTransaction::CannotLookup)).retryable
+		);
+	}
+}
@@ -18,6 +18,7 @@
 
 //! The transaction's event returned as json compatible object.
 
+use codec::{Decode, Encode};
 use serde::{Deserialize, Serialize};
 
 /// The transaction was included in a block of the chain.
@@ -28,6 +29,117 @@ pub struct TransactionBlock<Hash> {
 	pub hash: Hash,
 	/// The index (zero-based) of the transaction within the body of the block.
 	pub index: usize,
+	/// The result of the dispatch of the transaction, if requested with
+	/// [`TransactionWatchOptions::with_dispatch_result`] and known.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub dispatch_result: Option<TransactionDispatchResult>,
+}
+
+/// The result of the dispatch of a transaction included in a block.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(tag = "type")]
+pub enum TransactionDispatchResult {
+	/// The transaction was dispatched successfully.
+	Success,
+	/// The dispatch of the transaction failed, its fees were paid nevertheless.
+	Failure(TransactionDispatchError),
+}
+
+/// The error of the dispatch of a transaction.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionDispatchError {
+	/// The kind of the error, e.g. `BadOrigin` or `Module`.
+	pub error: String,
+	/// The error of the pallet, for the `Module` errors.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub module: Option<TransactionModuleError>,
+}
+
+/// The error of a pallet.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionModuleError {
+	/// The index of the pallet.
+	pub index: u8,
+	/// The hex-encoded error within the pallet.
+	pub error: String,
+	/// The name of the pallet, if found in the metadata.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub pallet: Option<String>,
+	/// The name of the error, if found in the metadata.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub name: Option<String>,
+}
+
+/// The options of the `transactionWatch_unstable_submitAndWatch` subscription.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionWatchOptions {
+	/// Add the result of the dispatch of the transaction to the blocks it is included in, read
+	/// from the events of the blocks.
+	#[serde(default)]
+	pub with_dispatch_result: bool,
+	/// The kinds of the events reported by the subscription, all of them if absent.
+	///
+	/// The final events are always reported, as they end the subscription.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub events: Option<Vec<TransactionEventKind>>,
+}
+
+impl TransactionWatchOptions {
+	/// Whether the event is reported by the subscription.
+	pub fn reports<Hash>(&self, event: &TransactionEvent<Hash>) -> bool {
+		let kind = event.kind();
+		kind.is_final() || self.events.as_ref().map_or(true, |events| events.contains(&kind))
+	}
+}
+
+/// The kind of a [`TransactionEvent`], selecting the events reported by a
+/// `transactionWatch_unstable_submitAndWatch` subscription.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TransactionEventKind {
+	/// [`TransactionEvent::Validated`].
+	Validated,
+	/// [`TransactionEvent::BestChainBlockIncluded`].
+	BestChainBlockIncluded,
+	/// [`TransactionEvent::Finalized`].
+	Finalized,
+	/// [`TransactionEvent::Error`].
+	Error,
+	/// [`TransactionEvent::Invalid`].
+	Invalid,
+	/// [`TransactionEvent::Dropped`].
+	Dropped,
+}
+
+impl TransactionEventKind {
+	/// Whether the events of this kind end the subscription.
+	pub fn is_final(&self) -> bool {
+		!matches!(self, Self::Validated | Self::BestChainBlockIncluded)
+	}
+}
+
+/// The options of the `transaction_unstable_broadcast` method.
+///
+/// By default the transaction is broadcast until it is finalized, it cannot be broadcast anymore
+/// or the operation is stopped. The limits end the operation earlier, with a final `Dropped`
+/// event.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Encode, Decode)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionBroadcastOptions {
+	/// The maximum number of seconds the transaction is broadcast for.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub max_duration_secs: Option<u64>,
+	/// The maximum number of times the transaction is submitted to the pool.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub max_attempts: Option<u32>,
+	/// End the operation once a block including the transaction is finalized, even if the pool
+	/// stopped watching the transaction before, instead of broadcasting it again.
+	#[serde(default)]
+	pub stop_on_finalization: bool,
 }
 
 /// The transaction could not be processed due to an error.
@@ -36,6 +148,153 @@ pub struct TransactionBlock<Hash> {
 pub struct TransactionError {
 	/// Reason of the error.
 	pub error: String,
+	/// Machine-readable cause of the error, if known.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub code: Option<TransactionErrorCode>,
+	/// The transaction was rejected because of a transient condition, and may be accepted if
+	/// submitted again later. Otherwise, the transaction is permanently invalid.
+	///
+	/// See [`TransactionErrorCode::is_retryable`].
+	#[serde(default)]
+	pub retryable: bool,
+}
+
+/// Machine-readable cause of a [`TransactionError`].
+///
+/// Unlike the human-readable message, the codes are stable and can be relied upon to decide
+/// whether the transaction should be resubmitted.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", rename_all_fields = "camelCase")]
+#[serde(tag = "type")]
+pub enum TransactionErrorCode {
+	/// The bytes of the transaction could not be decoded or verified.
+	Verification,
+	/// The runtime declared the transaction invalid.
+	InvalidTransaction {
+		/// The kind of the invalidity.
+		reason: InvalidTransactionReason,
+		/// The runtime specific code, for the `custom` reason.
+		#[serde(default, skip_serializing_if = "Option::is_none")]
+		code: Option<u8>,
+	},
+	/// The runtime could not determine the validity of the transaction.
+	UnknownTransaction {
+		/// The kind of the unknown validity.
+		reason: UnknownTransactionReason,
+		/// The runtime specific code, for the `custom` reason.
+		#[serde(default, skip_serializing_if = "Option::is_none")]
+		code: Option<u8>,
+	},
+	/// The transaction pool rejected or removed the transaction.
+	Pool {
+		/// The kind of the pool error.
+		reason: PoolErrorReason,
+		/// The number of milliseconds until the ban of the transaction expires, for the
+		/// `temporarilyBanned` reason.
+		#[serde(default, skip_serializing_if = "Option::is_none")]
+		ban_expires_in_ms: Option<u64>,
+		/// The minimum priority a transaction needs to replace the transactions providing the
+		/// same tags in the pool, for the `tooLowPriority` reason.
+		#[serde(default, skip_serializing_if = "Option::is_none")]
+		required_priority: Option<u64>,
+	},
+}
+
+impl TransactionErrorCode {
+	/// Returns `true` if the cause of the error is transient, such that the transaction may be
+	/// accepted if submitted again later.
+	///
+	/// This is the case for a transaction which is temporarily banned, which doesn't fit in the
+	/// pool or which has a too low priority to replace another transaction, but not for a
+	/// transaction with a bad signature or a stale nonce for example.
+	pub fn is_retryable(&self) -> bool {
+		match self {
+			TransactionErrorCode::Verification => false,
+			TransactionErrorCode::InvalidTransaction { reason, .. } => matches!(
+				reason,
+				InvalidTransactionReason::Future | InvalidTransactionReason::ExhaustsResources
+			),
+			TransactionErrorCode::UnknownTransaction { .. } => false,
+			TransactionErrorCode::Pool { reason, .. } => matches!(
+				reason,
+				PoolErrorReason::TemporarilyBanned |
+					PoolErrorReason::TooLowPriority |
+					PoolErrorReason::ImmediatelyDropped |
+					PoolErrorReason::InvalidBlockId |
+					PoolErrorReason::RejectedFutureTransaction
+			),
+		}
+	}
+}
+
+/// The kind of the invalidity of a transaction, mirroring the `InvalidTransaction` of the
+/// runtime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum InvalidTransactionReason {
+	/// The call of the transaction is not expected.
+	Call,
+	/// The transaction cannot pay its fees.
+	Payment,
+	/// The transaction is not yet valid, e.g. its nonce is too high.
+	Future,
+	/// The transaction is outdated, e.g. its nonce is too low.
+	Stale,
+	/// The signature of the transaction is invalid.
+	BadProof,
+	/// The birth block of the transaction is ancient.
+	AncientBirthBlock,
+	/// The transaction would exhaust the resources of the current block.
+	ExhaustsResources,
+	/// A runtime specific error, see the accompanying code.
+	Custom,
+	/// A mandatory transaction failed to dispatch.
+	BadMandatory,
+	/// A mandatory transaction was submitted as a regular transaction.
+	MandatoryValidation,
+	/// The signer of the transaction is not allowed to sign it.
+	BadSigner,
+}
+
+/// The kind of the unknown validity of a transaction, mirroring the `UnknownTransaction` of
+/// the runtime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum UnknownTransactionReason {
+	/// Some state required to validate the transaction could not be looked up.
+	CannotLookup,
+	/// No validator was found for the unsigned transaction.
+	NoUnsignedValidator,
+	/// A runtime specific error, see the accompanying code.
+	Custom,
+}
+
+/// The kind of error of the transaction pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PoolErrorReason {
+	/// The transaction is temporarily banned from the pool.
+	TemporarilyBanned,
+	/// The transaction is already in the pool.
+	AlreadyImported,
+	/// A transaction with the same tags and a higher priority is already in the pool.
+	TooLowPriority,
+	/// The transaction contains a cyclic dependency.
+	CycleDetected,
+	/// The transaction could not enter the pool because of its limits.
+	ImmediatelyDropped,
+	/// The transaction cannot be propagated and the node does not author blocks.
+	Unactionable,
+	/// The transaction does not provide any tags.
+	NoTagsProvided,
+	/// The block the transaction was validated at is not valid.
+	InvalidBlockId,
+	/// The pool does not accept future transactions.
+	RejectedFutureTransaction,
+	/// Another transaction with the same tags replaced the transaction.
+	Usurped,
+	/// The transaction was found invalid when revalidated by the pool.
+	Invalidated,
 }
 
 /// The transaction was dropped because of exceeding limits.
@@ -46,6 +305,137 @@ pub struct TransactionDropped {
 	pub error: String,
 }
 
+/// The state of a `transaction_broadcast` operation, returned by `transaction_unstable_status`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(bound(
+	serialize = "Hash: Serialize + Clone",
+	deserialize = "Hash: Deserialize<'de> + Clone"
+))]
+pub struct TransactionBroadcastStatus<Hash> {
+	/// The latest events of the transaction, oldest first.
+	///
+	/// The last event is the latest known state of the transaction.
+	pub events: Vec<TransactionEvent<Hash>>,
+	/// The operation ended, either because the transaction reached a final state or because it
+	/// cannot be broadcast.
+	pub ended: bool,
+}
+
+/// An event of a transaction submitted by `transactionWatch_unstable_submitAndWatchBatch`.
+///
+/// The event is serialized as the [`TransactionEvent`] of the transaction, along with the
+/// index of the transaction in the batch.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(bound(
+	serialize = "Hash: Serialize + Clone",
+	deserialize = "Hash: Deserialize<'de> + Clone"
+))]
+pub struct TransactionBatchEvent<Hash> {
+	/// The index of the transaction in the batch.
+	///
+	/// This is `None` for a `Dropped` event concerning all the transactions of the batch which
+	/// didn't reach a final state yet, which is generated if the client is too slow.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub index: Option<usize>,
+	/// The event of the transaction.
+	#[serde(flatten)]
+	pub event: TransactionEvent<Hash>,
+}
+
+/// The estimated fee of a transaction, returned by `transaction_unstable_estimateFee`.
+///
+/// The amounts are decimal strings, as they may not fit in a JSON number.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionFeeEstimate {
+	/// The version of the structure, [`TransactionFeeEstimate::VERSION`].
+	pub version: u32,
+	/// The fee charged for the inclusion of the transaction, absent if the transaction doesn't
+	/// pay any, e.g. an unsigned transaction.
+	pub inclusion_fee: Option<TransactionInclusionFee>,
+	/// The tip of the transaction.
+	pub tip: String,
+	/// The total fee, the sum of the inclusion fee and the tip.
+	pub total: String,
+}
+
+impl TransactionFeeEstimate {
+	/// The current version of the structure.
+	pub const VERSION: u32 = 1;
+}
+
+/// The breakdown of the inclusion fee of a transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionInclusionFee {
+	/// The minimum fee of any transaction.
+	pub base_fee: String,
+	/// The fee for the length of the transaction.
+	pub length_fee: String,
+	/// The fee for the weight of the transaction, adjusted to the congestion of the chain.
+	pub weight_fee: String,
+}
+
+/// The decoded content of an extrinsic, returned by `transaction_unstable_decode`.
+///
+/// The values are decoded with the type information of the metadata: the structs are JSON
+/// objects, the enums are either the name of the variant or an object with the name of the
+/// variant as key, the byte sequences are hexadecimal strings and the integers which may not fit
+/// in a JSON number are decimal strings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionDecoded {
+	/// The version of the extrinsic format.
+	pub version: u8,
+	/// The call dispatched by the extrinsic.
+	pub call: TransactionDecodedCall,
+	/// The address of the signer, absent for an unsigned extrinsic.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub signer: Option<serde_json::Value>,
+	/// The nonce of the signer, absent if the extrinsic doesn't have any.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub nonce: Option<u64>,
+	/// The tip of the extrinsic as a decimal string, absent if the extrinsic doesn't have any.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub tip: Option<String>,
+	/// The window of blocks the extrinsic is valid in, absent if the extrinsic is immortal.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub mortality: Option<TransactionMortality>,
+}
+
+/// The call dispatched by an extrinsic.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionDecodedCall {
+	/// The name of the pallet.
+	pub pallet: String,
+	/// The index of the pallet in the runtime.
+	pub pallet_index: u8,
+	/// The name of the call.
+	pub call: String,
+	/// The index of the call in the pallet.
+	pub call_index: u8,
+	/// The arguments of the call, by name.
+	pub args: serde_json::Value,
+}
+
+/// The window of blocks a mortal extrinsic is valid in.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionMortality {
+	/// The number of blocks the extrinsic is valid for.
+	pub period: u64,
+	/// The phase of the first block of the window in the period.
+	pub phase: u64,
+	/// The first block of the window, assuming the extrinsic was signed at most `period`
+	/// blocks before the best block.
+	pub birth: u64,
+	/// The first block after the window.
+	pub death: u64,
+}
+
 /// Possible transaction status events.
 ///
 /// The status events can be grouped based on their kinds as:
@@ -95,6 +485,21 @@ pub enum TransactionEvent<Hash> {
 	Dropped(TransactionDropped),
 }
 
+impl<Hash> TransactionEvent<Hash> {
+	/// The kind of the event.
+	pub fn kind(&self) -> TransactionEventKind {
+		match self {
+			TransactionEvent::Validated => TransactionEventKind::Validated,
+			TransactionEvent::BestChainBlockIncluded(_) =>
+				TransactionEventKind::BestChainBlockIncluded,
+			TransactionEvent::Finalized(_) => TransactionEventKind::Finalized,
+			TransactionEvent::Error(_) => TransactionEventKind::Error,
+			TransactionEvent::Invalid(_) => TransactionEventKind::Invalid,
+			TransactionEvent::Dropped(_) => TransactionEventKind::Dropped,
+		}
+	}
+}
+
 /// Intermediate representation (IR) for the transaction events
 /// that handles block events only.
 ///
@@ -197,6 +602,30 @@ mod tests {
 	use super::*;
 	use sp_core::H256;
 
+	#[test]
+	fn watch_options() {
+		let options: TransactionWatchOptions =
+			serde_json::from_str(r#"{"events":["bestChainBlockIncluded"]}"#).unwrap();
+		assert_eq!(
+			options,
+			TransactionWatchOptions {
+				with_dispatch_result: false,
+				events: Some(vec![TransactionEventKind::BestChainBlockIncluded]),
+			}
+		);
+
+		// The final events are always reported.
+		assert!(!options.reports(&TransactionEvent::<()>::Validated));
+		assert!(options.reports(&TransactionEvent::<()>::BestChainBlockIncluded(None)));
+		assert!(options.reports(&TransactionEvent::<()>::Dropped(TransactionDropped {
+			error: "dropped".into()
+		})));
+
+		// All the events are reported by default.
+		let options: TransactionWatchOptions = serde_json::from_str("{}").unwrap();
+		assert!(options.reports(&TransactionEvent::<()>::Validated));
+	}
+
 	#[test]
 	fn validated_event() {
 		let event: TransactionEvent<()> = TransactionEvent::Validated;
@@ -224,6 +653,7 @@ mod tests {
 			TransactionEvent::BestChainBlockIncluded(Some(TransactionBlock {
 				hash: H256::from_low_u64_be(1),
 				index: 2,
+				dispatch_result: None,
 			}));
 		let ser = serde_json::to_string(&event).unwrap();
 
@@ -239,6 +669,7 @@ mod tests {
 		let event: TransactionEvent<H256> = TransactionEvent::Finalized(TransactionBlock {
 			hash: H256::from_low_u64_be(1),
 			index: 10,
+			dispatch_result: None,
 		});
 		let ser = serde_json::to_string(&event).unwrap();
 
@@ -249,13 +680,53 @@ mod tests {
 		assert_eq!(event_dec, event);
 	}
 
+	#[test]
+	fn finalized_event_with_dispatch_result() {
+		let event: TransactionEvent<H256> = TransactionEvent::Finalized(TransactionBlock {
+			hash: H256::from_low_u64_be(1),
+			index: 10,
+			dispatch_result: Some(TransactionDispatchResult::Success),
+		});
+		let ser = serde_json::to_string(&event).unwrap();
+
+		let exp = r#"{"event":"finalized","block":{"hash":"0x0000000000000000000000000000000000000000000000000000000000000001","index":10,"dispatchResult":{"type":"success"}}}"#;
+		assert_eq!(ser, exp);
+
+		let event_dec: TransactionEvent<H256> = serde_json::from_str(exp).unwrap();
+		assert_eq!(event_dec, event);
+
+		let event: TransactionEvent<H256> = TransactionEvent::Finalized(TransactionBlock {
+			hash: H256::from_low_u64_be(1),
+			index: 10,
+			dispatch_result: Some(TransactionDispatchResult::Failure(TransactionDispatchError {
+				error: "Module".into(),
+				module: Some(TransactionModuleError {
+					index: 5,
+					error: "0x02000000".into(),
+					pallet: Some("Balances".into()),
+					name: Some("InsufficientBalance".into()),
+				}),
+			})),
+		});
+		let ser = serde_json::to_string(&event).unwrap();
+
+		let exp = r#"{"event":"finalized","block":{"hash":"0x0000000000000000000000000000000000000000000000000000000000000001","index":10,"dispatchResult":{"type":"failure","error":"Module","module":{"index":5,"error":"0x02000000","pallet":"Balances","name":"InsufficientBalance"}}}}"#;
+		assert_eq!(ser, exp);
+
+		let event_dec: TransactionEvent<H256> = serde_json::from_str(exp).unwrap();
+		assert_eq!(event_dec, event);
+	}
+
 	#[test]
 	fn error_event() {
-		let event: TransactionEvent<()> =
-			TransactionEvent::Error(TransactionError { error: "abc".to_string() });
+		let event: TransactionEvent<()> = TransactionEvent::Error(TransactionError {
+			error: "abc".to_string(),
+			code: None,
+			retryable: false,
+		});
 		let ser = serde_json::to_string(&event).unwrap();
 
-		let exp = r#"{"event":"error","error":"abc"}"#;
+		let exp = r#"{"event":"error","error":"abc","retryable":false}"#;
 		assert_eq!(ser, exp);
 
 		let event_dec: TransactionEvent<()> = serde_json::from_str(exp).unwrap();
@@ -264,15 +735,73 @@ mod tests {
 
 	#[test]
 	fn invalid_event() {
-		let event: TransactionEvent<()> =
-			TransactionEvent::Invalid(TransactionError { error: "abc".to_string() });
+		let event: TransactionEvent<()> = TransactionEvent::Invalid(TransactionError {
+			error: "abc".to_string(),
+			code: None,
+			retryable: false,
+		});
+		let ser = serde_json::to_string(&event).unwrap();
+
+		let exp = r#"{"event":"invalid","error":"abc","retryable":false}"#;
+		assert_eq!(ser, exp);
+
+		let event_dec: TransactionEvent<()> = serde_json::from_str(exp).unwrap();
+		assert_eq!(event_dec, event);
+
+		let event: TransactionEvent<()> = TransactionEvent::Invalid(TransactionError {
+			error: "abc".to_string(),
+			code: Some(TransactionErrorCode::InvalidTransaction {
+				reason: InvalidTransactionReason::Custom,
+				code: Some(3),
+			}),
+			retryable: false,
+		});
 		let ser = serde_json::to_string(&event).unwrap();
 
-		let exp = r#"{"event":"invalid","error":"abc"}"#;
+		let exp = r#"{"event":"invalid","error":"abc","code":{"type":"invalidTransaction","reason":"custom","code":3},"retryable":false}"#;
 		assert_eq!(ser, exp);
 
 		let event_dec: TransactionEvent<()> = serde_json::from_str(exp).unwrap();
 		assert_eq!(event_dec, event);
+
+		let event: TransactionEvent<()> = TransactionEvent::Invalid(TransactionError {
+			error: "abc".to_string(),
+			code: Some(TransactionErrorCode::Pool {
+				reason: PoolErrorReason::TemporarilyBanned,
+				ban_expires_in_ms: Some(1000),
+				required_priority: None,
+			}),
+			retryable: true,
+		});
+		let ser = serde_json::to_string(&event).unwrap();
+
+		let exp = r#"{"event":"invalid","error":"abc","code":{"type":"pool","reason":"temporarilyBanned","banExpiresInMs":1000},"retryable":true}"#;
+		assert_eq!(ser, exp);
+
+		let event_dec: TransactionEvent<()> = serde_json::from_str(exp).unwrap();
+		assert_eq!(event_dec, event);
+	}
+
+	#[test]
+	fn broadcast_status() {
+		let status: TransactionBroadcastStatus<H256> = TransactionBroadcastStatus {
+			events: vec![
+				TransactionEvent::Validated,
+				TransactionEvent::Finalized(TransactionBlock {
+					hash: H256::from_low_u64_be(1),
+					index: 2,
+					dispatch_result: None,
+				}),
+			],
+			ended: true,
+		};
+		let ser = serde_json::to_string(&status).unwrap();
+
+		let exp = r#"{"events":[{"event":"validated"},{"event":"finalized","block":{"hash":"0x0000000000000000000000000000000000000000000000000000000000000001","index":2}}],"ended":true}"#;
+		assert_eq!(ser, exp);
+
+		let status_dec: TransactionBroadcastStatus<H256> = serde_json::from_str(exp).unwrap();
+		assert_eq!(status_dec, status);
 	}
 
 	#[test]
@@ -287,4 +816,87 @@ mod tests {
 		let event_dec: TransactionEvent<()> = serde_json::from_str(exp).unwrap();
 		assert_eq!(event_dec, event);
 	}
+
+	#[test]
+	fn batch_event() {
+		let event: TransactionBatchEvent<H256> = TransactionBatchEvent {
+			index: Some(1),
+			event: TransactionEvent::Finalized(TransactionBlock {
+				hash: H256::from_low_u64_be(1),
+				index: 2,
+				dispatch_result: None,
+			}),
+		};
+		let ser = serde_json::to_string(&event).unwrap();
+
+		let exp = r#"{"index":1,"event":"finalized","block":{"hash":"0x0000000000000000000000000000000000000000000000000000000000000001","index":2}}"#;
+		assert_eq!(ser, exp);
+
+		let event_dec: TransactionBatchEvent<H256> = serde_json::from_str(exp).unwrap();
+		assert_eq!(event_dec, event);
+
+		let event: TransactionBatchEvent<H256> = TransactionBatchEvent {
+			index: None,
+			event: TransactionEvent::Dropped(TransactionDropped { error: "abc".to_string() }),
+		};
+		let ser = serde_json::to_string(&event).unwrap();
+
+		let exp = r#"{"event":"dropped","error":"abc"}"#;
+		assert_eq!(ser, exp);
+
+		let event_dec: TransactionBatchEvent<H256> = serde_json::from_str(exp).unwrap();
+		assert_eq!(event_dec, event);
+	}
+
+	#[test]
+	fn fee_estimate() {
+		let estimate = TransactionFeeEstimate {
+			version: TransactionFeeEstimate::VERSION,
+			inclusion_fee: Some(TransactionInclusionFee {
+				base_fee: "1".into(),
+				length_fee: "2".into(),
+				weight_fee: "3".into(),
+			}),
+			tip: "4".into(),
+			total: "10".into(),
+		};
+		let ser = serde_json::to_string(&estimate).unwrap();
+
+		let exp = r#"{"version":1,"inclusionFee":{"baseFee":"1","lengthFee":"2","weightFee":"3"},"tip":"4","total":"10"}"#;
+		assert_eq!(ser, exp);
+
+		let estimate_dec: TransactionFeeEstimate = serde_json::from_str(exp).unwrap();
+		assert_eq!(estimate_dec, estimate);
+	}
+
+	#[test]
+	fn decoded_transaction() {
+		let decoded = TransactionDecoded {
+			version: 4,
+			call: TransactionDecodedCall {
+				pallet: "System".into(),
+				pallet_index: 0,
+				call: "remark".into(),
+				call_index: 7,
+				args: serde_json::json!({ "remark": "0x01" }),
+			},
+			signer: Some(serde_json::json!({ "Id": "0x02" })),
+			nonce: Some(3),
+			tip: Some("4".into()),
+			mortality: Some(TransactionMortality { period: 64, phase: 5, birth: 69, death: 133 }),
+		};
+		let ser = serde_json::to_string(&decoded).unwrap();
+
+		let exp = r#"{"version":4,"call":{"pallet":"System","palletIndex":0,"call":"remark","callIndex":7,"args":{"remark":"0x01"}},"signer":{"Id":"0x02"},"nonce":3,"tip":"4","mortality":{"period":64,"phase":5,"birth":69,"death":133}}"#;
+		assert_eq!(ser, exp);
+
+		let decoded_dec: TransactionDecoded = serde_json::from_str(exp).unwrap();
+		assert_eq!(decoded_dec, decoded);
+
+		// The details of the signed extrinsics are absent for the unsigned ones.
+		let decoded =
+			TransactionDecoded { signer: None, nonce: None, tip: None, mortality: None, ..decoded };
+		let ser = serde_json::to_string(&decoded).unwrap();
+		assert!(!ser.contains("signer") && !ser.contains("mortality"));
+	}
 }
@@ -0,0 +1,313 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! The decoding of the extrinsics.
+//!
+//! The extrinsics are decoded with the type information of the metadata, which is fetched once per
+//! runtime version. The values of the call and of the signed extensions are converted to JSON
+//! values, without any knowledge of the pallets of the runtime.
+
+use crate::transaction::{
+	event::{TransactionDecoded, TransactionDecodedCall, TransactionMortality},
+	fee_details::decode_metadata_v15,
+};
+use codec::{Compact, Decode};
+use scale_info::{
+	form::PortableForm, Field, PortableRegistry, Type, TypeDef, TypeDefPrimitive, Variant,
+};
+use serde_json::{Map, Value};
+use sp_runtime::generic::Era;
+
+/// The bit of the version byte set for a signed extrinsic.
+const SIGNED_EXTRINSIC_BIT: u8 = 0b1000_0000;
+
+/// Decoder of the extrinsics of a runtime.
+pub(crate) struct ExtrinsicDecoder {
+	/// The types of the metadata.
+	types: PortableRegistry,
+	/// The version of the extrinsic format.
+	version: u8,
+	/// The type of the address of the signer.
+	address_ty: u32,
+	/// The type of the signature.
+	signature_ty: u32,
+	/// The type of the outer call enum.
+	call_ty: u32,
+	/// The identifiers and the types of the signed extensions, in their encoding order.
+	signed_extensions: Vec<(String, u32)>,
+}
+
+impl ExtrinsicDecoder {
+	/// Create the decoder from the SCALE-encoded `Option<OpaqueMetadata>` returned by
+	/// `Metadata_metadata_at_version(15)`.
+	pub(crate) fn new(metadata: &[u8]) -> Result<Self, codec::Error> {
+		let metadata = decode_metadata_v15(metadata)?;
+		let extrinsic = metadata.extrinsic;
+
+		Ok(Self {
+			types: metadata.types,
+			version: extrinsic.version,
+			address_ty: extrinsic.address_ty.id,
+			signature_ty: extrinsic.signature_ty.id,
+			call_ty: extrinsic.call_ty.id,
+			signed_extensions: extrinsic
+				.signed_extensions
+				.into_iter()
+				.map(|extension| (extension.identifier, extension.ty.id))
+				.collect(),
+		})
+	}
+
+	/// Decode the encoded extrinsic, with the mortality window relative to the block
+	/// `best_number`.
+	pub(crate) fn decode(
+		&self,
+		extrinsic: &[u8],
+		best_number: u64,
+	) -> Result<TransactionDecoded, codec::Error> {
+		let input = &mut &extrinsic[..];
+		// The extrinsics are encoded as vectors of bytes.
+		let len = Compact::<u32>::decode(input)?.0;
+		if input.len() != len as usize {
+			return Err("Invalid length of the extrinsic".into())
+		}
+
+		let version = u8::decode(input)?;
+		let is_signed = version & SIGNED_EXTRINSIC_BIT != 0;
+		let version = version & !SIGNED_EXTRINSIC_BIT;
+		if version != self.version {
+			return Err("Unsupported extrinsic version".into())
+		}
+
+		let (mut signer, mut nonce, mut tip, mut mortality) = (None, None, None, None);
+		if is_signed {
+			signer = Some(self.decode_value(self.address_ty, input)?);
+			// The signature is of no use once the extrinsic is decoded.
+			self.decode_value(self.signature_ty, input)?;
+
+			for (identifier, ty) in &self.signed_extensions {
+				match identifier.as_str() {
+					"CheckMortality" => {
+						let era = Era::decode(input)?;
+						if let Era::Mortal(period, phase) = era {
+							mortality = Some(TransactionMortality {
+								period,
+								phase,
+								birth: era.birth(best_number),
+								death: era.death(best_number),
+							});
+						}
+					},
+					"CheckNonce" => {
+						let value = self.decode_value(*ty, input)?;
+						nonce = Some(value.as_u64().ok_or("The nonce is not an integer")?);
+					},
+					"ChargeTransactionPayment" | "ChargeAssetTxPayment" => {
+						let value = self.decode_value(*ty, input)?;
+						tip = Some(decode_tip(&value).ok_or("The tip is not an integer")?);
+					},
+					_ => {
+						self.decode_value(*ty, input)?;
+					},
+				}
+			}
+		}
+
+		let call = self.decode_call(input)?;
+		if !input.is_empty() {
+			return Err("Trailing bytes after the call".into())
+		}
+		Ok(TransactionDecoded { version, call, signer, nonce, tip, mortality })
+	}
+
+	/// Decode the call, a variant of the outer call enum wrapping a variant of the call enum of
+	/// the pallet.
+	fn decode_call(&self, input: &mut &[u8]) -> Result<TransactionDecodedCall, codec::Error> {
+		let pallet = self.decode_variant(self.call_ty, input)?;
+		let [pallet_call] = &pallet.fields[..] else {
+			return Err("Unexpected layout of the outer call enum".into())
+		};
+		let call = self.decode_variant(pallet_call.ty.id, input)?;
+
+		Ok(TransactionDecodedCall {
+			pallet: pallet.name.clone(),
+			pallet_index: pallet.index,
+			call: call.name.clone(),
+			call_index: call.index,
+			args: self.decode_fields(&call.fields, input)?,
+		})
+	}
+
+	/// Decode the index of a variant of the enum `ty`.
+	fn decode_variant(
+		&self,
+		ty: u32,
+		input: &mut &[u8],
+	) -> Result<&Variant<PortableForm>, codec::Error> {
+		let TypeDef::Variant(variants) = &self.ty(ty)?.type_def else {
+			return Err("Expected an enum".into())
+		};
+		let index = u8::decode(input)?;
+		variants
+			.variants
+			.iter()
+			.find(|variant| variant.index == index)
+			.ok_or_else(|| "Unknown variant".into())
+	}
+
+	/// Decode a value of the type `ty`.
+	fn decode_value(&self, ty: u32, input: &mut &[u8]) -> Result<Value, codec::Error> {
+		match &self.ty(ty)?.type_def {
+			TypeDef::Composite(composite) => self.decode_fields(&composite.fields, input),
+			TypeDef::Variant(_) => {
+				let variant = self.decode_variant(ty, input)?;
+				if variant.fields.is_empty() {
+					return Ok(Value::String(variant.name.clone()))
+				}
+				let value = self.decode_fields(&variant.fields, input)?;
+				Ok(Value::Object(Map::from_iter([(variant.name.clone(), value)])))
+			},
+			TypeDef::Sequence(sequence) => {
+				let len = Compact::<u32>::decode(input)?.0;
+				self.decode_items(sequence.type_param.id, len, input)
+			},
+			TypeDef::Array(array) => self.decode_items(array.type_param.id, array.len, input),
+			TypeDef::Tuple(tuple) => tuple
+				.fields
+				.iter()
+				.map(|field| self.decode_value(field.id, input))
+				.collect::<Result<_, _>>()
+				.map(Value::Array),
+			TypeDef::Primitive(primitive) => decode_primitive(primitive, input),
+			TypeDef::Compact(compact) => self.decode_compact(compact.type_param.id, input),
+			TypeDef::BitSequence(_) => Err("Bit sequences are not supported".into()),
+		}
+	}
+
+	/// Decode the fields of a struct or of an enum variant.
+	///
+	/// The named fields are decoded as an object, a single unnamed field as its value and several
+	/// unnamed fields as an array.
+	fn decode_fields(
+		&self,
+		fields: &[Field<PortableForm>],
+		input: &mut &[u8],
+	) -> Result<Value, codec::Error> {
+		if let [field] = fields {
+			if field.name.is_none() {
+				return self.decode_value(field.ty.id, input)
+			}
+		}
+
+		if fields.iter().all(|field| field.name.is_some()) {
+			let mut object = Map::new();
+			for field in fields {
+				let name = field.name.clone().unwrap_or_default();
+				object.insert(name, self.decode_value(field.ty.id, input)?);
+			}
+			return Ok(Value::Object(object))
+		}
+
+		fields
+			.iter()
+			.map(|field| self.decode_value(field.ty.id, input))
+			.collect::<Result<_, _>>()
+			.map(Value::Array)
+	}
+
+	/// Decode `len` items of the type `ty`, as a hexadecimal string for bytes.
+	fn decode_items(&self, ty: u32, len: u32, input: &mut &[u8]) -> Result<Value, codec::Error> {
+		if let TypeDef::Primitive(TypeDefPrimitive::U8) = &self.ty(ty)?.type_def {
+			let len = len as usize;
+			if input.len() < len {
+				return Err("Not enough bytes".into())
+			}
+			let (bytes, rest) = input.split_at(len);
+			*input = rest;
+			return Ok(Value::String(format!("0x{}", hex::encode(bytes))))
+		}
+
+		(0..len)
+			.map(|_| self.decode_value(ty, input))
+			.collect::<Result<_, _>>()
+			.map(Value::Array)
+	}
+
+	/// Decode a compact integer of the type `ty`.
+	fn decode_compact(&self, ty: u32, input: &mut &[u8]) -> Result<Value, codec::Error> {
+		match &self.ty(ty)?.type_def {
+			TypeDef::Primitive(TypeDefPrimitive::U8) =>
+				Compact::<u8>::decode(input).map(|compact| compact.0.into()),
+			TypeDef::Primitive(TypeDefPrimitive::U16) =>
+				Compact::<u16>::decode(input).map(|compact| compact.0.into()),
+			TypeDef::Primitive(TypeDefPrimitive::U32) =>
+				Compact::<u32>::decode(input).map(|compact| compact.0.into()),
+			TypeDef::Primitive(TypeDefPrimitive::U64) =>
+				Compact::<u64>::decode(input).map(|compact| compact.0.into()),
+			TypeDef::Primitive(TypeDefPrimitive::U128) =>
+				Compact::<u128>::decode(input).map(|compact| compact.0.to_string().into()),
+			// A wrapper of an integer.
+			TypeDef::Composite(composite) => match &composite.fields[..] {
+				[field] => self.decode_compact(field.ty.id, input),
+				_ => Err("Unsupported compact type".into()),
+			},
+			_ => Err("Unsupported compact type".into()),
+		}
+	}
+
+	fn ty(&self, id: u32) -> Result<&Type<PortableForm>, codec::Error> {
+		self.types.resolve(id).ok_or_else(|| "Unknown type in the metadata".into())
+	}
+}
+
+/// Decode a primitive value, the integers which may not fit in a JSON number as decimal strings.
+fn decode_primitive(
+	primitive: &TypeDefPrimitive,
+	input: &mut &[u8],
+) -> Result<Value, codec::Error> {
+	Ok(match primitive {
+		TypeDefPrimitive::Bool => bool::decode(input)?.into(),
+		TypeDefPrimitive::Char => char::from_u32(u32::decode(input)?)
+			.ok_or("Invalid character")?
+			.to_string()
+			.into(),
+		TypeDefPrimitive::Str => String::decode(input)?.into(),
+		TypeDefPrimitive::U8 => u8::decode(input)?.into(),
+		TypeDefPrimitive::U16 => u16::decode(input)?.into(),
+		TypeDefPrimitive::U32 => u32::decode(input)?.into(),
+		TypeDefPrimitive::U64 => u64::decode(input)?.into(),
+		TypeDefPrimitive::U128 => u128::decode(input)?.to_string().into(),
+		TypeDefPrimitive::I8 => i8::decode(input)?.into(),
+		TypeDefPrimitive::I16 => i16::decode(input)?.into(),
+		TypeDefPrimitive::I32 => i32::decode(input)?.into(),
+		TypeDefPrimitive::I64 => i64::decode(input)?.into(),
+		TypeDefPrimitive::I128 => i128::decode(input)?.to_string().into(),
+		TypeDefPrimitive::U256 | TypeDefPrimitive::I256 =>
+			format!("0x{}", hex::encode(<[u8; 32]>::decode(input)?)).into(),
+	})
+}
+
+/// The tip of the decoded `ChargeTransactionPayment` or `ChargeAssetTxPayment` signed extension.
+fn decode_tip(value: &Value) -> Option<String> {
+	match value {
+		Value::Number(tip) if tip.is_u64() => Some(tip.to_string()),
+		Value::String(tip) if tip.parse::<u128>().is_ok() => Some(tip.clone()),
+		Value::Object(fields) => fields.get("tip").and_then(decode_tip),
+		_ => None,
+	}
+}
@@ -0,0 +1,162 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! The fee details of the transactions.
+//!
+//! The fees are computed by the `TransactionPaymentApi_query_fee_details` runtime API. Its
+//! `FeeDetails` output depends on the balance type of the runtime, so it is decoded with the type
+//! information of the runtime APIs in the metadata, which is fetched once per runtime version.
+
+use crate::transaction::event::{TransactionFeeEstimate, TransactionInclusionFee};
+use codec::{Compact, Decode};
+use frame_metadata::{v15::RuntimeMetadataV15, RuntimeMetadata, RuntimeMetadataPrefixed};
+use scale_info::{form::PortableForm, Field, PortableRegistry, Type, TypeDef, TypeDefPrimitive};
+
+/// Decode the SCALE-encoded `Option<OpaqueMetadata>` returned by
+/// `Metadata_metadata_at_version(15)`.
+pub(crate) fn decode_metadata_v15(metadata: &[u8]) -> Result<RuntimeMetadataV15, codec::Error> {
+	let Some(metadata) = Option::<Vec<u8>>::decode(&mut &metadata[..])? else {
+		return Err("The runtime has no metadata V15".into())
+	};
+	match RuntimeMetadataPrefixed::decode(&mut &metadata[..])?.1 {
+		RuntimeMetadata::V15(metadata) => Ok(metadata),
+		_ => Err("Unsupported metadata version".into()),
+	}
+}
+
+/// Decoder of the `FeeDetails` returned by the `TransactionPaymentApi` of a runtime.
+pub(crate) struct FeeDetailsDecoder {
+	/// The types of the metadata.
+	types: PortableRegistry,
+	/// The type of the output of `TransactionPaymentApi_query_fee_details`.
+	fee_details_ty: u32,
+}
+
+impl FeeDetailsDecoder {
+	/// Create the decoder from the SCALE-encoded `Option<OpaqueMetadata>` returned by
+	/// `Metadata_metadata_at_version(15)`.
+	pub(crate) fn new(metadata: &[u8]) -> Result<Self, codec::Error> {
+		// The types of the runtime APIs are part of the metadata since V15.
+		let metadata = decode_metadata_v15(metadata)?;
+		let fee_details_ty = metadata
+			.apis
+			.iter()
+			.filter(|api| api.name == "TransactionPaymentApi")
+			.flat_map(|api| &api.methods)
+			.find(|method| method.name == "query_fee_details")
+			.map(|method| method.output.id)
+			.ok_or("The runtime has no `TransactionPaymentApi`")?;
+
+		Ok(Self { types: metadata.types, fee_details_ty })
+	}
+
+	/// Decode the encoded `FeeDetails` of a transaction.
+	pub(crate) fn decode(
+		&self,
+		fee_details: &[u8],
+	) -> Result<TransactionFeeEstimate, codec::Error> {
+		let input = &mut &fee_details[..];
+		let (mut inclusion_fee, mut tip) = (None, 0);
+		for field in self.fields(self.fee_details_ty)? {
+			match field.name.as_deref() {
+				Some("inclusion_fee") =>
+					inclusion_fee = self.decode_inclusion_fee(field.ty.id, input)?,
+				Some("tip") => tip = self.decode_balance(field.ty.id, input)?,
+				_ => return Err("Unknown field of `FeeDetails`".into()),
+			}
+		}
+		if !input.is_empty() {
+			return Err("Trailing bytes after the fee details".into())
+		}
+
+		let total = inclusion_fee
+			.map_or(0, |(base, length, weight)| base.saturating_add(length).saturating_add(weight))
+			.saturating_add(tip);
+		Ok(TransactionFeeEstimate {
+			version: TransactionFeeEstimate::VERSION,
+			inclusion_fee: inclusion_fee.map(|(base, length, weight)| TransactionInclusionFee {
+				base_fee: base.to_string(),
+				length_fee: length.to_string(),
+				weight_fee: weight.to_string(),
+			}),
+			tip: tip.to_string(),
+			total: total.to_string(),
+		})
+	}
+
+	/// Decode the optional `InclusionFee`, as `(base_fee, len_fee, adjusted_weight_fee)`.
+	fn decode_inclusion_fee(
+		&self,
+		ty: u32,
+		input: &mut &[u8],
+	) -> Result<Option<(u128, u128, u128)>, codec::Error> {
+		let TypeDef::Variant(variants) = &self.ty(ty)?.type_def else {
+			return Err("The inclusion fee is not an option".into())
+		};
+		let index = u8::decode(input)?;
+		let variant = variants
+			.variants
+			.iter()
+			.find(|variant| variant.index == index)
+			.ok_or("Unknown variant")?;
+		// `None` has no field.
+		let [inclusion_fee] = &variant.fields[..] else { return Ok(None) };
+
+		let mut fees = (0, 0, 0);
+		for field in self.fields(inclusion_fee.ty.id)? {
+			let fee = self.decode_balance(field.ty.id, input)?;
+			match field.name.as_deref() {
+				Some("base_fee") => fees.0 = fee,
+				Some("len_fee") => fees.1 = fee,
+				Some("adjusted_weight_fee") => fees.2 = fee,
+				_ => return Err("Unknown field of `InclusionFee`".into()),
+			}
+		}
+		Ok(Some(fees))
+	}
+
+	/// Decode a balance of the type `ty`.
+	fn decode_balance(&self, ty: u32, input: &mut &[u8]) -> Result<u128, codec::Error> {
+		match &self.ty(ty)?.type_def {
+			TypeDef::Primitive(TypeDefPrimitive::U8) => u8::decode(input).map(Into::into),
+			TypeDef::Primitive(TypeDefPrimitive::U16) => u16::decode(input).map(Into::into),
+			TypeDef::Primitive(TypeDefPrimitive::U32) => u32::decode(input).map(Into::into),
+			TypeDef::Primitive(TypeDefPrimitive::U64) => u64::decode(input).map(Into::into),
+			TypeDef::Primitive(TypeDefPrimitive::U128) => u128::decode(input),
+			TypeDef::Compact(_) => Compact::<u128>::decode(input).map(|compact| compact.0),
+			// A wrapper of a balance.
+			TypeDef::Composite(composite) => match &composite.fields[..] {
+				[field] => self.decode_balance(field.ty.id, input),
+				_ => Err("Unsupported balance type".into()),
+			},
+			_ => Err("Unsupported balance type".into()),
+		}
+	}
+
+	/// The fields of the struct `ty`.
+	fn fields(&self, ty: u32) -> Result<&[Field<PortableForm>], codec::Error> {
+		match &self.ty(ty)?.type_def {
+			TypeDef::Composite(composite) => Ok(&composite.fields),
+			_ => Err("Expected a struct".into()),
+		}
+	}
+
+	fn ty(&self, id: u32) -> Result<&Type<PortableForm>, codec::Error> {
+		self.types.resolve(id).ok_or_else(|| "Unknown type in the metadata".into())
+	}
+}
@@ -29,12 +29,31 @@
 mod tests;
 
 pub mod api;
+pub mod broadcast_store;
+mod dispatch_result;
 pub mod error;
 pub mod event;
+mod extrinsic_decoder;
+mod fee_details;
 pub mod transaction;
 pub mod transaction_broadcast;
-
-pub use api::{TransactionApiServer, TransactionBroadcastApiServer};
-pub use event::{TransactionBlock, TransactionDropped, TransactionError, TransactionEvent};
-pub use transaction::Transaction;
-pub use transaction_broadcast::TransactionBroadcast;
+pub mod transaction_decode;
+pub mod transaction_fee;
+
+pub use api::{
+	TransactionApiServer, TransactionBroadcastApiServer, TransactionDecodeApiServer,
+	TransactionFeeApiServer,
+};
+pub use broadcast_store::{BroadcastStore, PersistedBroadcast};
+pub use event::{
+	InvalidTransactionReason, PoolErrorReason, TransactionBatchEvent, TransactionBlock,
+	TransactionBroadcastOptions, TransactionBroadcastStatus, TransactionDecoded,
+	TransactionDecodedCall, TransactionDispatchError, TransactionDispatchResult,
+	TransactionDropped, TransactionError, TransactionErrorCode, TransactionEvent,
+	TransactionEventKind, TransactionFeeEstimate, TransactionInclusionFee, TransactionModuleError,
+	TransactionMortality, TransactionWatchOptions, UnknownTransactionReason,
+};
+pub use transaction::{Transaction, TransactionConfig};
+pub use transaction_broadcast::{TransactionBroadcast, TransactionBroadcastConfig};
+pub use transaction_decode::TransactionDecode;
+pub use transaction_fee::TransactionFee;
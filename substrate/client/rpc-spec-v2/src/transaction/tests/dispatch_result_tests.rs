@@ -0,0 +1,111 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::transaction::{
+	dispatch_result::EventsDecoder, TransactionDispatchError, TransactionDispatchResult,
+	TransactionModuleError,
+};
+use codec::Encode;
+use frame_support::dispatch::DispatchInfo;
+use frame_system::{Event, EventRecord, Phase};
+use sc_client_api::{CallExecutor, ExecutorProvider};
+use sp_api::CallContext;
+use sp_blockchain::HeaderBackend;
+use sp_core::H256;
+use sp_runtime::{DispatchError, ModuleError};
+use substrate_test_runtime::{Runtime, RuntimeEvent};
+use substrate_test_runtime_client::{AccountKeyring::*, TestClientBuilder, TestClientBuilderExt};
+
+fn events_decoder() -> EventsDecoder {
+	let client = TestClientBuilder::new().build();
+	let genesis = client.info().genesis_hash;
+	let metadata = client
+		.executor()
+		.call(genesis, "Metadata_metadata", &[], CallContext::Offchain)
+		.unwrap();
+	EventsDecoder::new(&metadata).unwrap()
+}
+
+fn record(phase: Phase, event: Event<Runtime>) -> EventRecord<RuntimeEvent, H256> {
+	EventRecord { phase, event: RuntimeEvent::System(event), topics: vec![H256::repeat_byte(1)] }
+}
+
+#[test]
+fn dispatch_results_are_decoded() {
+	let decoder = events_decoder();
+	let dispatch_info = DispatchInfo::default();
+	let events = vec![
+		record(Phase::Initialization, Event::ExtrinsicSuccess { dispatch_info }),
+		record(Phase::ApplyExtrinsic(0), Event::NewAccount { account: Alice.into() }),
+		record(Phase::ApplyExtrinsic(0), Event::ExtrinsicSuccess { dispatch_info }),
+		record(
+			Phase::ApplyExtrinsic(1),
+			Event::ExtrinsicFailed { dispatch_error: DispatchError::BadOrigin, dispatch_info },
+		),
+		record(
+			Phase::ApplyExtrinsic(2),
+			Event::ExtrinsicFailed {
+				dispatch_error: DispatchError::Module(ModuleError {
+					index: 0,
+					error: [0, 0, 0, 0],
+					message: None,
+				}),
+				dispatch_info,
+			},
+		),
+		record(Phase::Finalization, Event::NewAccount { account: Bob.into() }),
+	]
+	.encode();
+
+	assert_eq!(
+		decoder.dispatch_result(&events, 0).unwrap(),
+		Some(TransactionDispatchResult::Success)
+	);
+	assert_eq!(
+		decoder.dispatch_result(&events, 1).unwrap(),
+		Some(TransactionDispatchResult::Failure(TransactionDispatchError {
+			error: "BadOrigin".into(),
+			module: None,
+		}))
+	);
+	assert_eq!(
+		decoder.dispatch_result(&events, 2).unwrap(),
+		Some(TransactionDispatchResult::Failure(TransactionDispatchError {
+			error: "Module".into(),
+			module: Some(TransactionModuleError {
+				index: 0,
+				error: "0x00000000".into(),
+				pallet: Some("System".into()),
+				name: Some("InvalidSpecName".into()),
+			}),
+		}))
+	);
+	assert_eq!(decoder.dispatch_result(&events, 3).unwrap(), None);
+}
+
+#[test]
+fn truncated_events_are_rejected() {
+	let decoder = events_decoder();
+	let events = vec![record(
+		Phase::ApplyExtrinsic(0),
+		Event::ExtrinsicSuccess { dispatch_info: Default::default() },
+	)]
+	.encode();
+
+	assert!(decoder.dispatch_result(&events[..events.len() - 1], 0).is_err());
+}
@@ -184,4 +184,8 @@ impl TransactionPool for MiddlewarePool {
 	fn futures(&self) -> Vec<Self::InPoolTransaction> {
 		self.inner_pool.futures()
 	}
+
+	fn revalidate_ready(&self) -> PoolFuture<usize, Self::Error> {
+		self.inner_pool.revalidate_ready()
+	}
 }
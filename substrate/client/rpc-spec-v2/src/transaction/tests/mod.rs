@@ -21,5 +21,8 @@ mod middleware_pool;
 #[macro_use]
 mod setup;
 
+mod dispatch_result_tests;
 mod transaction_broadcast_tests;
+mod transaction_decode_tests;
+mod transaction_fee_tests;
 mod transaction_tests;
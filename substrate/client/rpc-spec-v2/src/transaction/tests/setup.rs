@@ -21,7 +21,9 @@ use crate::{
 	transaction::{
 		api::{TransactionApiServer, TransactionBroadcastApiServer},
 		tests::executor::{TaskExecutorBroadcast, TaskExecutorState},
-		Transaction as RpcTransaction, TransactionBroadcast as RpcTransactionBroadcast,
+		BroadcastStore, Transaction as RpcTransaction,
+		TransactionBroadcast as RpcTransactionBroadcast, TransactionBroadcastConfig,
+		TransactionConfig,
 	},
 };
 use futures::Future;
@@ -74,6 +76,37 @@ pub fn setup_api(
 	RpcModule<RpcTransactionBroadcast<MiddlewarePool, ChainHeadMockClient<Client<Backend>>>>,
 	TaskExecutorState,
 	MiddlewarePoolRecv,
+) {
+	setup_api_with_store(options, None)
+}
+
+/// Same as [`setup_api`], with the broadcast operations persisted in the given store.
+pub fn setup_api_with_store(
+	options: Options,
+	store: Option<Arc<BroadcastStore>>,
+) -> (
+	Arc<TestApi>,
+	Arc<MiddlewarePool>,
+	Arc<ChainHeadMockClient<Client<Backend>>>,
+	RpcModule<RpcTransactionBroadcast<MiddlewarePool, ChainHeadMockClient<Client<Backend>>>>,
+	TaskExecutorState,
+	MiddlewarePoolRecv,
+) {
+	setup_api_with_config(options, store, Default::default())
+}
+
+/// Same as [`setup_api_with_store`], with the limits of the given configuration.
+pub fn setup_api_with_config(
+	options: Options,
+	store: Option<Arc<BroadcastStore>>,
+	config: TransactionBroadcastConfig,
+) -> (
+	Arc<TestApi>,
+	Arc<MiddlewarePool>,
+	Arc<ChainHeadMockClient<Client<Backend>>>,
+	RpcModule<RpcTransactionBroadcast<MiddlewarePool, ChainHeadMockClient<Client<Backend>>>>,
+	TaskExecutorState,
+	MiddlewarePoolRecv,
 ) {
 	let (pool, api, _) = maintained_pool(options);
 	let (pool, pool_state) = MiddlewarePool::new(Arc::new(pool).clone());
@@ -85,9 +118,17 @@ pub fn setup_api(
 
 	let (task_executor, executor_recv) = TaskExecutorBroadcast::new();
 
-	let tx_api =
-		RpcTransactionBroadcast::new(client_mock.clone(), pool.clone(), Arc::new(task_executor))
-			.into_rpc();
+	let tx_api = RpcTransactionBroadcast::new(
+		client_mock.clone(),
+		pool.clone(),
+		Arc::new(task_executor),
+		config,
+	);
+	let tx_api = match store {
+		Some(store) => tx_api.with_store(store),
+		None => tx_api,
+	}
+	.into_rpc();
 
 	(api, pool, client_mock, tx_api, executor_recv, pool_state)
 }
@@ -96,7 +137,21 @@ pub fn setup_api_tx() -> (
 	Arc<TestApi>,
 	Arc<MiddlewarePool>,
 	Arc<ChainHeadMockClient<Client<Backend>>>,
-	RpcModule<RpcTransaction<MiddlewarePool, ChainHeadMockClient<Client<Backend>>>>,
+	RpcModule<RpcTransaction<Backend, MiddlewarePool, ChainHeadMockClient<Client<Backend>>>>,
+	TaskExecutorState,
+	MiddlewarePoolRecv,
+) {
+	setup_api_tx_with_config(Default::default())
+}
+
+/// Same as [`setup_api_tx`], with the limits of the given configuration.
+pub fn setup_api_tx_with_config(
+	config: TransactionConfig,
+) -> (
+	Arc<TestApi>,
+	Arc<MiddlewarePool>,
+	Arc<ChainHeadMockClient<Client<Backend>>>,
+	RpcModule<RpcTransaction<Backend, MiddlewarePool, ChainHeadMockClient<Client<Backend>>>>,
 	TaskExecutorState,
 	MiddlewarePoolRecv,
 ) {
@@ -110,7 +165,8 @@ pub fn setup_api_tx() -> (
 	let (task_executor, executor_recv) = TaskExecutorBroadcast::new();
 
 	let tx_api =
-		RpcTransaction::new(client_mock.clone(), pool.clone(), Arc::new(task_executor)).into_rpc();
+		RpcTransaction::new(client_mock.clone(), pool.clone(), Arc::new(task_executor), config)
+			.into_rpc();
 
 	(api, pool, client_mock, tx_api, executor_recv, pool_state)
 }
@@ -22,14 +22,19 @@ use codec::Encode;
 use jsonrpsee::{rpc_params, MethodsError as Error};
 use sc_transaction_pool::{Options, PoolLimit};
 use sc_transaction_pool_api::{ChainEvent, MaintainedTransactionPool, TransactionPool};
+use sp_core::H256;
 use std::sync::Arc;
-use substrate_test_runtime_client::AccountKeyring::*;
+use substrate_test_runtime_client::{prelude::*, AccountKeyring::*};
 use substrate_test_runtime_transaction_pool::uxt;
 
 // Test helpers.
-use crate::transaction::tests::{
-	middleware_pool::{MiddlewarePoolEvent, TxStatusTypeTest},
-	setup::{setup_api, ALICE_NONCE},
+use crate::transaction::{
+	tests::{
+		middleware_pool::{MiddlewarePoolEvent, TxStatusTypeTest},
+		setup::{setup_api, setup_api_with_config, setup_api_with_store, ALICE_NONCE},
+	},
+	BroadcastStore, PersistedBroadcast, TransactionBlock, TransactionBroadcastConfig,
+	TransactionBroadcastOptions, TransactionBroadcastStatus, TransactionDropped, TransactionEvent,
 };
 
 #[tokio::test]
@@ -311,6 +316,80 @@ async fn tx_broadcast_stop_after_broadcast_finishes() {
 	);
 }
 
+#[tokio::test]
+async fn tx_broadcast_status() {
+	let (api, pool, client_mock, tx_api, mut exec_middleware, mut pool_middleware) =
+		setup_api(Default::default());
+
+	// Start at block 1.
+	let block_1_header = api.push_block(1, vec![], true);
+
+	let uxt = uxt(Alice, ALICE_NONCE);
+	let xt = hex_string(&uxt.encode());
+
+	let operation_id: String =
+		tx_api.call("transaction_unstable_broadcast", rpc_params![&xt]).await.unwrap();
+
+	// The operation is known before the transaction enters the pool.
+	let status: TransactionBroadcastStatus<H256> = tx_api
+		.call("transaction_unstable_status", rpc_params![&operation_id])
+		.await
+		.unwrap();
+	assert!(!status.ended);
+
+	// Announce block 1 to `transaction_unstable_broadcast`.
+	client_mock.trigger_import_stream(block_1_header).await;
+
+	let event = get_next_event!(&mut pool_middleware);
+	assert_eq!(
+		event,
+		MiddlewarePoolEvent::TransactionStatus {
+			transaction: xt.clone(),
+			status: TxStatusTypeTest::Ready
+		}
+	);
+
+	// Import block 2 with the transaction included.
+	let block_2_header = api.push_block(2, vec![uxt.clone()], true);
+	let block_2 = block_2_header.hash();
+	let event = ChainEvent::Finalized { hash: block_2, tree_route: Arc::from(vec![]) };
+	pool.inner_pool.maintain(event).await;
+
+	let _ = get_next_event!(&mut pool_middleware);
+	let _ = get_next_event!(&mut pool_middleware);
+
+	// Ensure the broadcast future terminated properly.
+	let _ = get_next_event!(&mut exec_middleware.recv);
+	assert_eq!(0, exec_middleware.num_tasks());
+
+	// The events are retained after the operation ended.
+	let status: TransactionBroadcastStatus<H256> = tx_api
+		.call("transaction_unstable_status", rpc_params![&operation_id])
+		.await
+		.unwrap();
+	let block = TransactionBlock { hash: block_2, index: 0, dispatch_result: None };
+	assert_eq!(
+		status,
+		TransactionBroadcastStatus {
+			events: vec![
+				TransactionEvent::Validated,
+				TransactionEvent::BestChainBlockIncluded(Some(block.clone())),
+				TransactionEvent::Finalized(block),
+			],
+			ended: true,
+		}
+	);
+
+	// Unknown operations are reported as invalid.
+	let err = tx_api
+		.call::<_, serde_json::Value>("transaction_unstable_status", rpc_params![&"invalid"])
+		.await
+		.unwrap_err();
+	assert_matches!(err,
+		Error::JsonRpc(err) if err.code() == json_rpc_spec::INVALID_PARAM_ERROR && err.message() == "Invalid operation id"
+	);
+}
+
 #[tokio::test]
 async fn tx_broadcast_resubmits_invalid_tx() {
 	let limits = PoolLimit { count: 8192, total_bytes: 20 * 1024 * 1024 };
@@ -320,6 +399,7 @@ async fn tx_broadcast_resubmits_invalid_tx() {
 		reject_future_transactions: false,
 		// This ensures that a transaction is not banned.
 		ban_time: std::time::Duration::ZERO,
+		revalidation: Default::default(),
 	};
 
 	let (api, pool, client_mock, tx_api, mut exec_middleware, mut pool_middleware) =
@@ -440,6 +520,7 @@ async fn tx_broadcast_resubmits_dropped_tx() {
 		reject_future_transactions: false,
 		// This ensures that a transaction is not banned.
 		ban_time: std::time::Duration::ZERO,
+		revalidation: Default::default(),
 	};
 
 	let (api, pool, client_mock, tx_api, _, mut pool_middleware) = setup_api(options);
@@ -521,3 +602,210 @@ async fn tx_broadcast_resubmits_dropped_tx() {
 	// The dropped transaction was resubmitted.
 	assert_eq!(events.get(&future_xt).unwrap(), &vec![TxStatusTypeTest::Ready]);
 }
+
+#[tokio::test]
+async fn tx_broadcast_persists_operation_until_stopped() {
+	let db = Arc::new(TestClientBuilder::new().build());
+	let store = Arc::new(BroadcastStore::new(db.clone(), 16));
+	let (api, _, client_mock, tx_api, mut exec_middleware, mut pool_middleware) =
+		setup_api_with_store(Default::default(), Some(store.clone()));
+
+	let block_1_header = api.push_block(1, vec![], true);
+
+	let uxt = uxt(Alice, ALICE_NONCE);
+	let xt = hex_string(&uxt.encode());
+
+	let operation_id: String =
+		tx_api.call("transaction_unstable_broadcast", rpc_params![&xt]).await.unwrap();
+
+	client_mock.trigger_import_stream(block_1_header).await;
+	let event = get_next_event!(&mut pool_middleware);
+	assert_eq!(
+		event,
+		MiddlewarePoolEvent::TransactionStatus {
+			transaction: xt.clone(),
+			status: TxStatusTypeTest::Ready
+		}
+	);
+
+	// The pending operation is in the database.
+	let expected = vec![PersistedBroadcast {
+		operation_id: operation_id.clone(),
+		transaction: uxt.encode(),
+		options: Default::default(),
+	}];
+	assert_eq!(store.entries(), expected);
+	assert_eq!(BroadcastStore::new(db.clone(), 16).entries(), expected);
+
+	let _: () = tx_api
+		.call("transaction_unstable_stop", rpc_params![&operation_id])
+		.await
+		.unwrap();
+	let _ = get_next_event!(&mut exec_middleware.recv);
+	assert_eq!(0, exec_middleware.num_tasks());
+
+	// The stopped operation is not resumed after a restart.
+	assert!(store.entries().is_empty());
+	assert!(BroadcastStore::new(db, 16).entries().is_empty());
+}
+
+#[tokio::test]
+async fn tx_broadcast_resumes_persisted_operation() {
+	let db = Arc::new(TestClientBuilder::new().build());
+	let uxt = uxt(Alice, ALICE_NONCE);
+	let xt = hex_string(&uxt.encode());
+
+	// The operation persisted before the restart.
+	assert!(BroadcastStore::new(db.clone(), 16).insert(
+		"persisted",
+		&uxt.encode(),
+		&Default::default()
+	));
+
+	let store = Arc::new(BroadcastStore::new(db.clone(), 16));
+	let (api, pool, client_mock, tx_api, mut exec_middleware, mut pool_middleware) =
+		setup_api_with_store(Default::default(), Some(store.clone()));
+
+	// The operation is resubmitted to the pool.
+	let block_1_header = api.push_block(1, vec![], true);
+	client_mock.trigger_import_stream(block_1_header).await;
+	let event = get_next_event!(&mut pool_middleware);
+	assert_eq!(
+		event,
+		MiddlewarePoolEvent::TransactionStatus {
+			transaction: xt.clone(),
+			status: TxStatusTypeTest::Ready
+		}
+	);
+	assert_eq!(1, pool.inner_pool.status().ready);
+
+	// The pending operations are resumed only once.
+	assert!(store.take_pending().is_empty());
+
+	// The resumed operation keeps its operation ID.
+	let _: () = tx_api
+		.call("transaction_unstable_stop", rpc_params!["persisted"])
+		.await
+		.unwrap();
+	let _ = get_next_event!(&mut exec_middleware.recv);
+	assert_eq!(0, exec_middleware.num_tasks());
+	assert!(BroadcastStore::new(db, 16).entries().is_empty());
+}
+
+#[tokio::test]
+async fn tx_broadcast_persists_at_most_max_entries() {
+	let db = Arc::new(TestClientBuilder::new().build());
+	let store = Arc::new(BroadcastStore::new(db.clone(), 1));
+	let (_, _, _, tx_api, _, _) = setup_api_with_store(Default::default(), Some(store.clone()));
+
+	let first: String = tx_api
+		.call(
+			"transaction_unstable_broadcast",
+			rpc_params![&hex_string(&uxt(Alice, ALICE_NONCE).encode())],
+		)
+		.await
+		.unwrap();
+	let _: String = tx_api
+		.call("transaction_unstable_broadcast", rpc_params![&hex_string(&uxt(Bob, 0).encode())])
+		.await
+		.unwrap();
+
+	// Only the first operation is persisted, both are running.
+	let entries = store.entries();
+	assert_eq!(entries.len(), 1);
+	assert_eq!(entries[0].operation_id, first);
+
+	// The operations over the limit are discarded on startup.
+	let store = BroadcastStore::new(db.clone(), 2);
+	assert!(store.insert("second", &[1], &Default::default()));
+	assert_eq!(BroadcastStore::new(db, 1).entries(), entries);
+}
+
+#[tokio::test]
+async fn tx_broadcast_stops_after_max_duration() {
+	let (_, _, _, tx_api, mut exec_middleware, _) = setup_api(Default::default());
+
+	let xt = hex_string(&uxt(Alice, ALICE_NONCE).encode());
+	let options = TransactionBroadcastOptions { max_duration_secs: Some(1), ..Default::default() };
+
+	// No block is announced, the transaction never enters the pool.
+	let operation_id: String = tx_api
+		.call("transaction_unstable_broadcast", rpc_params![&xt, options])
+		.await
+		.unwrap();
+
+	// The broadcast future exits once the maximum duration is reached.
+	let _ = get_next_event!(&mut exec_middleware.recv);
+	assert_eq!(0, exec_middleware.num_tasks());
+
+	let status: TransactionBroadcastStatus<H256> = tx_api
+		.call("transaction_unstable_status", rpc_params![&operation_id])
+		.await
+		.unwrap();
+	assert_eq!(
+		status,
+		TransactionBroadcastStatus {
+			events: vec![TransactionEvent::Dropped(TransactionDropped {
+				error: "Maximum duration of the broadcast reached".into(),
+			})],
+			ended: true,
+		}
+	);
+}
+
+#[tokio::test]
+async fn tx_broadcast_stops_after_max_attempts() {
+	let (_, _, _, tx_api, mut exec_middleware, _) = setup_api(Default::default());
+
+	let xt = hex_string(&uxt(Alice, ALICE_NONCE).encode());
+	let options = TransactionBroadcastOptions { max_attempts: Some(0), ..Default::default() };
+
+	let operation_id: String = tx_api
+		.call("transaction_unstable_broadcast", rpc_params![&xt, options])
+		.await
+		.unwrap();
+
+	// The transaction is never submitted to the pool.
+	let _ = get_next_event!(&mut exec_middleware.recv);
+	assert_eq!(0, exec_middleware.num_tasks());
+
+	let status: TransactionBroadcastStatus<H256> = tx_api
+		.call("transaction_unstable_status", rpc_params![&operation_id])
+		.await
+		.unwrap();
+	assert_eq!(
+		status,
+		TransactionBroadcastStatus {
+			events: vec![TransactionEvent::Dropped(TransactionDropped {
+				error: "Maximum number of broadcast attempts reached".into(),
+			})],
+			ended: true,
+		}
+	);
+}
+
+#[tokio::test]
+async fn tx_broadcast_max_operations() {
+	let config = TransactionBroadcastConfig { max_operations: 1, ..Default::default() };
+	let (_, _, _, tx_api, _, _) = setup_api_with_config(Default::default(), None, config);
+
+	let first: Option<String> = tx_api
+		.call(
+			"transaction_unstable_broadcast",
+			rpc_params![&hex_string(&uxt(Alice, ALICE_NONCE).encode())],
+		)
+		.await
+		.unwrap();
+	let first = first.unwrap();
+
+	// The server is overloaded while the first operation runs.
+	let xt = hex_string(&uxt(Bob, 0).encode());
+	let second: Option<String> =
+		tx_api.call("transaction_unstable_broadcast", rpc_params![&xt]).await.unwrap();
+	assert!(second.is_none());
+
+	let _: () = tx_api.call("transaction_unstable_stop", rpc_params![&first]).await.unwrap();
+	let second: Option<String> =
+		tx_api.call("transaction_unstable_broadcast", rpc_params![&xt]).await.unwrap();
+	assert!(second.is_some());
+}
@@ -0,0 +1,233 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+	hex_string,
+	transaction::{
+		error::json_rpc_spec, extrinsic_decoder::ExtrinsicDecoder, TransactionDecode,
+		TransactionDecodeApiServer, TransactionDecoded, TransactionDecodedCall,
+		TransactionMortality,
+	},
+};
+use assert_matches::assert_matches;
+use codec::Encode;
+use frame_metadata::{
+	v15::{
+		CustomMetadata, ExtrinsicMetadata, OuterEnums, RuntimeMetadataV15, SignedExtensionMetadata,
+	},
+	RuntimeMetadataPrefixed,
+};
+use jsonrpsee::{rpc_params, MethodsError as Error};
+use scale_info::{meta_type, TypeInfo};
+use serde_json::json;
+use sp_runtime::generic::Era;
+use std::sync::Arc;
+use substrate_test_runtime_client::{
+	runtime::Block, AccountKeyring::*, TestClientBuilder, TestClientBuilderExt,
+};
+use substrate_test_runtime_transaction_pool::uxt;
+
+#[allow(non_camel_case_types)]
+#[derive(Encode, TypeInfo)]
+enum SystemCall {
+	#[codec(index = 0)]
+	remark { remark: Vec<u8> },
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Encode, TypeInfo)]
+enum BalancesCall {
+	#[codec(index = 3)]
+	transfer_keep_alive {
+		dest: MultiAddress,
+		#[codec(compact)]
+		value: u128,
+	},
+}
+
+/// The layout of the outer call enum of a runtime.
+#[derive(Encode, TypeInfo)]
+enum RuntimeCall {
+	#[codec(index = 0)]
+	System(SystemCall),
+	#[codec(index = 5)]
+	Balances(BalancesCall),
+}
+
+#[derive(Encode, TypeInfo)]
+enum MultiAddress {
+	Id([u8; 32]),
+}
+
+#[derive(Encode, TypeInfo)]
+enum MultiSignature {
+	Sr25519([u8; 64]),
+}
+
+#[derive(Encode, TypeInfo)]
+struct CheckNonce(#[codec(compact)] u64);
+
+#[derive(Encode, TypeInfo)]
+struct CheckWeight;
+
+#[derive(Encode, TypeInfo)]
+struct ChargeTransactionPayment(#[codec(compact)] u128);
+
+/// The signed extensions of the runtime, in their encoding order.
+type SignedExtra = (Era, CheckNonce, CheckWeight, ChargeTransactionPayment);
+
+/// The decoder of a runtime with the types above.
+fn decoder() -> ExtrinsicDecoder {
+	fn signed_extension<T: TypeInfo + 'static>(
+		identifier: &'static str,
+	) -> SignedExtensionMetadata {
+		SignedExtensionMetadata {
+			identifier,
+			ty: meta_type::<T>(),
+			additional_signed: meta_type::<()>(),
+		}
+	}
+
+	let metadata = RuntimeMetadataV15::new(
+		vec![],
+		ExtrinsicMetadata {
+			version: 4,
+			address_ty: meta_type::<MultiAddress>(),
+			call_ty: meta_type::<RuntimeCall>(),
+			signature_ty: meta_type::<MultiSignature>(),
+			extra_ty: meta_type::<SignedExtra>(),
+			signed_extensions: vec![
+				signed_extension::<Era>("CheckMortality"),
+				signed_extension::<CheckNonce>("CheckNonce"),
+				signed_extension::<CheckWeight>("CheckWeight"),
+				signed_extension::<ChargeTransactionPayment>("ChargeTransactionPayment"),
+			],
+		},
+		meta_type::<()>(),
+		vec![],
+		OuterEnums {
+			call_enum_ty: meta_type::<RuntimeCall>(),
+			event_enum_ty: meta_type::<()>(),
+			error_enum_ty: meta_type::<()>(),
+		},
+		CustomMetadata { map: Default::default() },
+	);
+	ExtrinsicDecoder::new(&Some(RuntimeMetadataPrefixed::from(metadata).encode()).encode()).unwrap()
+}
+
+/// The encoded signed transfer of `value` to `[2; 32]` by `[1; 32]`.
+fn signed_transfer(value: u128) -> Vec<u8> {
+	let call = RuntimeCall::Balances(BalancesCall::transfer_keep_alive {
+		dest: MultiAddress::Id([2; 32]),
+		value,
+	});
+	let extra = (Era::mortal(64, 100), CheckNonce(7), CheckWeight, ChargeTransactionPayment(10));
+	// The extrinsics are encoded as vectors of bytes.
+	(0b1000_0100u8, MultiAddress::Id([1; 32]), MultiSignature::Sr25519([0; 64]), extra, call)
+		.encode()
+		.encode()
+}
+
+#[test]
+fn signed_extrinsics_are_decoded() {
+	let decoded = decoder().decode(&signed_transfer(u64::MAX as u128 + 1), 100).unwrap();
+	assert_eq!(
+		decoded,
+		TransactionDecoded {
+			version: 4,
+			call: TransactionDecodedCall {
+				pallet: "Balances".into(),
+				pallet_index: 5,
+				call: "transfer_keep_alive".into(),
+				call_index: 3,
+				args: json!({
+					"dest": { "Id": format!("0x{}", "02".repeat(32)) },
+					"value": "18446744073709551616",
+				}),
+			},
+			signer: Some(json!({ "Id": format!("0x{}", "01".repeat(32)) })),
+			nonce: Some(7),
+			tip: Some("10".into()),
+			mortality: Some(TransactionMortality { period: 64, phase: 36, birth: 100, death: 164 }),
+		}
+	);
+}
+
+#[test]
+fn unsigned_extrinsics_are_decoded() {
+	let call = RuntimeCall::System(SystemCall::remark { remark: vec![1, 2, 3] });
+	let extrinsic = (4u8, call).encode().encode();
+
+	assert_eq!(
+		decoder().decode(&extrinsic, 100).unwrap(),
+		TransactionDecoded {
+			version: 4,
+			call: TransactionDecodedCall {
+				pallet: "System".into(),
+				pallet_index: 0,
+				call: "remark".into(),
+				call_index: 0,
+				args: json!({ "remark": "0x010203" }),
+			},
+			signer: None,
+			nonce: None,
+			tip: None,
+			mortality: None,
+		}
+	);
+}
+
+#[test]
+fn malformed_extrinsics_are_rejected() {
+	let decoder = decoder();
+	let call = RuntimeCall::System(SystemCall::remark { remark: vec![] });
+
+	// Unsupported version.
+	assert!(decoder.decode(&(5u8, &call).encode().encode(), 0).is_err());
+	// Unknown pallet.
+	assert!(decoder.decode(&(4u8, 1u8, 0u8, 0u8).encode().encode(), 0).is_err());
+	// Trailing bytes.
+	assert!(decoder.decode(&(4u8, &call, 0u8).encode().encode(), 0).is_err());
+	// Invalid length prefix.
+	let mut extrinsic = signed_transfer(1);
+	extrinsic.pop();
+	assert!(decoder.decode(&extrinsic, 0).is_err());
+}
+
+#[tokio::test]
+async fn decode_rejects_invalid_transactions() {
+	let client = Arc::new(TestClientBuilder::new().build());
+	let api = TransactionDecode::<Block, _>::new(client).into_rpc();
+
+	let err = api
+		.call::<_, TransactionDecoded>("transaction_unstable_decode", rpc_params!["0x"])
+		.await
+		.unwrap_err();
+	assert_matches!(
+		err,
+		Error::JsonRpc(err) if err.code() == json_rpc_spec::INVALID_PARAM_ERROR
+	);
+
+	// The test runtime doesn't provide its metadata.
+	let xt = hex_string(&uxt(Alice, 0).encode());
+	let err = api
+		.call::<_, TransactionDecoded>("transaction_unstable_decode", rpc_params![&xt])
+		.await
+		.unwrap_err();
+	assert_matches!(err, Error::JsonRpc(err) if err.code() == json_rpc_spec::INTERNAL_ERROR);
+}
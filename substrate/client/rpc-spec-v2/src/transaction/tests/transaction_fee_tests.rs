@@ -0,0 +1,180 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+	hex_string,
+	transaction::{
+		error::json_rpc_spec, fee_details::FeeDetailsDecoder, TransactionFee,
+		TransactionFeeApiServer, TransactionFeeEstimate, TransactionInclusionFee,
+	},
+};
+use assert_matches::assert_matches;
+use codec::Encode;
+use frame_metadata::{
+	v15::{
+		CustomMetadata, ExtrinsicMetadata, OuterEnums, RuntimeApiMetadata,
+		RuntimeApiMethodMetadata, RuntimeMetadataV15,
+	},
+	RuntimeMetadataPrefixed,
+};
+use jsonrpsee::{rpc_params, MethodsError as Error};
+use scale_info::{meta_type, TypeInfo};
+use std::sync::Arc;
+use substrate_test_runtime_client::{
+	runtime::Block, AccountKeyring::*, TestClientBuilder, TestClientBuilderExt,
+};
+use substrate_test_runtime_transaction_pool::uxt;
+
+/// The layout of `pallet_transaction_payment::InclusionFee`.
+#[derive(Encode, TypeInfo)]
+struct InclusionFee<Balance> {
+	base_fee: Balance,
+	len_fee: Balance,
+	adjusted_weight_fee: Balance,
+}
+
+/// The layout of `pallet_transaction_payment::FeeDetails`.
+#[derive(Encode, TypeInfo)]
+struct FeeDetails<Balance> {
+	inclusion_fee: Option<InclusionFee<Balance>>,
+	tip: Balance,
+}
+
+/// The encoded `Option<OpaqueMetadata>` of a runtime with the given runtime APIs.
+fn metadata(apis: Vec<RuntimeApiMetadata>) -> Vec<u8> {
+	let metadata = RuntimeMetadataV15::new(
+		vec![],
+		ExtrinsicMetadata {
+			version: 4,
+			address_ty: meta_type::<()>(),
+			call_ty: meta_type::<()>(),
+			signature_ty: meta_type::<()>(),
+			extra_ty: meta_type::<()>(),
+			signed_extensions: vec![],
+		},
+		meta_type::<()>(),
+		apis,
+		OuterEnums {
+			call_enum_ty: meta_type::<()>(),
+			event_enum_ty: meta_type::<()>(),
+			error_enum_ty: meta_type::<()>(),
+		},
+		CustomMetadata { map: Default::default() },
+	);
+	Some(RuntimeMetadataPrefixed::from(metadata).encode()).encode()
+}
+
+/// The decoder of a runtime whose `TransactionPaymentApi` returns `FeeDetails<Balance>`.
+fn decoder<Balance: TypeInfo + 'static>() -> FeeDetailsDecoder {
+	let apis = vec![RuntimeApiMetadata {
+		name: "TransactionPaymentApi",
+		methods: vec![RuntimeApiMethodMetadata {
+			name: "query_fee_details",
+			inputs: vec![],
+			output: meta_type::<FeeDetails<Balance>>(),
+			docs: vec![],
+		}],
+		docs: vec![],
+	}];
+	FeeDetailsDecoder::new(&metadata(apis)).unwrap()
+}
+
+#[test]
+fn fee_details_are_decoded() {
+	let decoder = decoder::<u128>();
+	let fee_details = FeeDetails {
+		inclusion_fee: Some(InclusionFee {
+			base_fee: 1u128,
+			len_fee: 20,
+			adjusted_weight_fee: u64::MAX as u128 + 1,
+		}),
+		tip: 300,
+	};
+
+	assert_eq!(
+		decoder.decode(&fee_details.encode()).unwrap(),
+		TransactionFeeEstimate {
+			version: TransactionFeeEstimate::VERSION,
+			inclusion_fee: Some(TransactionInclusionFee {
+				base_fee: "1".into(),
+				length_fee: "20".into(),
+				weight_fee: "18446744073709551616".into(),
+			}),
+			tip: "300".into(),
+			total: "18446744073709551937".into(),
+		}
+	);
+}
+
+#[test]
+fn fee_details_of_other_balance_types_are_decoded() {
+	// The balance type is read from the metadata.
+	let decoder = decoder::<u64>();
+	let fee_details = FeeDetails {
+		inclusion_fee: Some(InclusionFee { base_fee: 1u64, len_fee: 2, adjusted_weight_fee: 3 }),
+		tip: 0,
+	};
+	assert_eq!(decoder.decode(&fee_details.encode()).unwrap().total, "6");
+	assert!(decoder
+		.decode(&FeeDetails::<u128> { inclusion_fee: None, tip: 0 }.encode())
+		.is_err());
+	assert!(decoder
+		.decode(&FeeDetails::<u32> { inclusion_fee: None, tip: 0 }.encode())
+		.is_err());
+
+	// The unsigned transactions pay no inclusion fee.
+	let fee_details = FeeDetails::<u64> { inclusion_fee: None, tip: 0 };
+	assert_eq!(
+		decoder.decode(&fee_details.encode()).unwrap(),
+		TransactionFeeEstimate {
+			version: TransactionFeeEstimate::VERSION,
+			inclusion_fee: None,
+			tip: "0".into(),
+			total: "0".into(),
+		}
+	);
+}
+
+#[test]
+fn runtimes_without_transaction_payment_are_rejected() {
+	assert!(FeeDetailsDecoder::new(&None::<Vec<u8>>.encode()).is_err());
+	assert!(FeeDetailsDecoder::new(&metadata(vec![])).is_err());
+}
+
+#[tokio::test]
+async fn estimate_fee_rejects_invalid_transactions() {
+	let client = Arc::new(TestClientBuilder::new().build());
+	let api = TransactionFee::<Block, _>::new(client).into_rpc();
+
+	let err = api
+		.call::<_, TransactionFeeEstimate>("transaction_unstable_estimateFee", rpc_params!["0x"])
+		.await
+		.unwrap_err();
+	assert_matches!(
+		err,
+		Error::JsonRpc(err) if err.code() == json_rpc_spec::INVALID_PARAM_ERROR
+	);
+
+	// The test runtime doesn't charge fees.
+	let xt = hex_string(&uxt(Alice, 0).encode());
+	let err = api
+		.call::<_, TransactionFeeEstimate>("transaction_unstable_estimateFee", rpc_params![&xt])
+		.await
+		.unwrap_err();
+	assert_matches!(err, Error::JsonRpc(err) if err.code() == json_rpc_spec::INTERNAL_ERROR);
+}
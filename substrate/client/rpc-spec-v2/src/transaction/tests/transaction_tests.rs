@@ -18,19 +18,24 @@
 
 use crate::{
 	hex_string,
-	transaction::{TransactionBlock, TransactionEvent},
+	transaction::{
+		error::{json_rpc_spec, rpc_spec_v2},
+		TransactionBatchEvent, TransactionBlock, TransactionConfig, TransactionDropped,
+		TransactionError, TransactionErrorCode, TransactionEvent, TransactionEventKind,
+		TransactionWatchOptions,
+	},
 };
 use assert_matches::assert_matches;
 use codec::Encode;
-use jsonrpsee::rpc_params;
-use sc_transaction_pool_api::{ChainEvent, MaintainedTransactionPool};
+use jsonrpsee::{core::server::Subscription, rpc_params, MethodsError as Error};
+use sc_transaction_pool_api::{ChainEvent, MaintainedTransactionPool, TransactionPool};
 use sp_core::H256;
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 use substrate_test_runtime_client::AccountKeyring::*;
 use substrate_test_runtime_transaction_pool::uxt;
 
 // Test helpers.
-use crate::transaction::tests::setup::{setup_api_tx, ALICE_NONCE};
+use crate::transaction::tests::setup::{setup_api_tx, setup_api_tx_with_config, ALICE_NONCE};
 
 #[tokio::test]
 async fn tx_invalid_bytes() {
@@ -43,7 +48,13 @@ async fn tx_invalid_bytes() {
 		.unwrap();
 
 	let event: TransactionEvent<H256> = get_next_event_sub!(&mut sub);
-	assert_matches!(event, TransactionEvent::Invalid(_));
+	assert_matches!(
+		event,
+		TransactionEvent::Invalid(TransactionError {
+			code: Some(TransactionErrorCode::Verification),
+			..
+		})
+	);
 }
 
 #[tokio::test]
@@ -78,11 +89,19 @@ async fn tx_in_finalized() {
 		event,
 		TransactionEvent::BestChainBlockIncluded(Some(TransactionBlock {
 			hash: block_2,
-			index: 0
+			index: 0,
+			dispatch_result: None,
 		}))
 	);
 	let event: TransactionEvent<H256> = get_next_event_sub!(&mut sub);
-	assert_eq!(event, TransactionEvent::Finalized(TransactionBlock { hash: block_2, index: 0 }));
+	assert_eq!(
+		event,
+		TransactionEvent::Finalized(TransactionBlock {
+			hash: block_2,
+			index: 0,
+			dispatch_result: None,
+		})
+	);
 }
 
 #[tokio::test]
@@ -113,7 +132,8 @@ async fn tx_with_pruned_best_block() {
 		event,
 		TransactionEvent::BestChainBlockIncluded(Some(TransactionBlock {
 			hash: block_2,
-			index: 0
+			index: 0,
+			dispatch_result: None,
 		}))
 	);
 
@@ -140,12 +160,207 @@ async fn tx_with_pruned_best_block() {
 		event,
 		TransactionEvent::BestChainBlockIncluded(Some(TransactionBlock {
 			hash: block_2,
-			index: 0
+			index: 0,
+			dispatch_result: None,
 		}))
 	);
 
 	let event = ChainEvent::Finalized { hash: block_2, tree_route: Arc::from(vec![]) };
 	pool.inner_pool.maintain(event).await;
 	let event: TransactionEvent<H256> = get_next_event_sub!(&mut sub);
-	assert_eq!(event, TransactionEvent::Finalized(TransactionBlock { hash: block_2, index: 0 }));
+	assert_eq!(
+		event,
+		TransactionEvent::Finalized(TransactionBlock {
+			hash: block_2,
+			index: 0,
+			dispatch_result: None,
+		})
+	);
+}
+
+/// Collect the next events of a batch subscription, grouped by the index of their transaction.
+async fn get_next_batch_events(
+	sub: &mut Subscription,
+	num: usize,
+) -> HashMap<usize, Vec<TransactionEvent<H256>>> {
+	let mut events = HashMap::new();
+	for _ in 0..num {
+		let event: TransactionBatchEvent<H256> = get_next_event_sub!(sub);
+		events.entry(event.index.unwrap()).or_insert_with(Vec::new).push(event.event);
+	}
+	events
+}
+
+#[tokio::test]
+async fn tx_batch_in_finalized() {
+	let (api, pool, client, tx_api, _exec_middleware, _pool_middleware) = setup_api_tx();
+	let block_1_header = api.push_block(1, vec![], true);
+	client.set_best_block(block_1_header.hash(), 1);
+
+	// The second transaction is only valid once the first one is in the pool.
+	let uxts = vec![uxt(Alice, ALICE_NONCE), uxt(Alice, ALICE_NONCE + 1)];
+	let xts: Vec<_> = uxts.iter().map(|uxt| hex_string(&uxt.encode())).collect();
+
+	let mut sub = tx_api
+		.subscribe_unbounded("transactionWatch_unstable_submitAndWatchBatch", rpc_params![&xts])
+		.await
+		.unwrap();
+
+	let events = get_next_batch_events(&mut sub, 2).await;
+	assert_eq!(events[&0], vec![TransactionEvent::Validated]);
+	assert_eq!(events[&1], vec![TransactionEvent::Validated]);
+	assert_eq!(2, pool.status().ready);
+
+	// Import block 2 with the transactions included.
+	let block_2_header = api.push_block(2, uxts, true);
+	let block_2 = block_2_header.hash();
+
+	let event = ChainEvent::NewBestBlock { hash: block_2, tree_route: None };
+	pool.inner_pool.maintain(event).await;
+	let event = ChainEvent::Finalized { hash: block_2, tree_route: Arc::from(vec![]) };
+	pool.inner_pool.maintain(event).await;
+
+	let events = get_next_batch_events(&mut sub, 4).await;
+	for index in 0..2 {
+		let block = TransactionBlock { hash: block_2, index, dispatch_result: None };
+		assert_eq!(
+			events[&index],
+			vec![
+				TransactionEvent::BestChainBlockIncluded(Some(block.clone())),
+				TransactionEvent::Finalized(block),
+			]
+		);
+	}
+}
+
+#[tokio::test]
+async fn tx_batch_rejected_tx() {
+	let (api, pool, client, tx_api, _exec_middleware, _pool_middleware) = setup_api_tx();
+	let block_1_header = api.push_block(1, vec![], true);
+	client.set_best_block(block_1_header.hash(), 1);
+
+	// The transactions following the invalid one are not submitted.
+	let xts = vec![
+		hex_string(&uxt(Alice, ALICE_NONCE).encode()),
+		"0xdeadbeef".to_string(),
+		hex_string(&uxt(Alice, ALICE_NONCE + 1).encode()),
+	];
+
+	let mut sub = tx_api
+		.subscribe_unbounded("transactionWatch_unstable_submitAndWatchBatch", rpc_params![&xts])
+		.await
+		.unwrap();
+
+	let events = get_next_batch_events(&mut sub, 3).await;
+	assert_eq!(events[&0], vec![TransactionEvent::Validated]);
+	assert_matches!(
+		&events[&1][..],
+		[TransactionEvent::Invalid(TransactionError {
+			code: Some(TransactionErrorCode::Verification),
+			..
+		})]
+	);
+	assert_matches!(&events[&2][..], [TransactionEvent::Dropped(TransactionDropped { .. })]);
+	assert_eq!(1, pool.status().ready);
+}
+
+#[tokio::test]
+async fn tx_batch_invalid_size() {
+	let (_api, _pool, _client_mock, tx_api, _exec_middleware, _pool_middleware) = setup_api_tx();
+
+	let xts: Vec<String> = vec![];
+	let err = tx_api
+		.subscribe_unbounded("transactionWatch_unstable_submitAndWatchBatch", rpc_params![&xts])
+		.await
+		.unwrap_err();
+	assert_matches!(err,
+		Error::JsonRpc(err) if err.code() == json_rpc_spec::INVALID_PARAM_ERROR
+	);
+}
+
+#[tokio::test]
+async fn tx_watch_max_subscriptions() {
+	let config = TransactionConfig { max_subscriptions: 1, ..Default::default() };
+	let (api, _pool, client, tx_api, mut exec_middleware, _pool_middleware) =
+		setup_api_tx_with_config(config);
+	let block_1_header = api.push_block(1, vec![], true);
+	client.set_best_block(block_1_header.hash(), 1);
+
+	let xt = hex_string(&uxt(Alice, ALICE_NONCE).encode());
+	let mut sub = tx_api
+		.subscribe_unbounded("transactionWatch_unstable_submitAndWatch", rpc_params![&xt])
+		.await
+		.unwrap();
+	let event: TransactionEvent<H256> = get_next_event_sub!(&mut sub);
+	assert_eq!(event, TransactionEvent::Validated);
+
+	// The limit is reached while the first subscription runs.
+	let xt = hex_string(&uxt(Bob, 0).encode());
+	let err = tx_api
+		.subscribe_unbounded("transactionWatch_unstable_submitAndWatch", rpc_params![&xt])
+		.await
+		.unwrap_err();
+	assert_matches!(err,
+		Error::JsonRpc(err) if err.code() == rpc_spec_v2::REACHED_LIMITS
+	);
+	let _ = get_next_event!(&mut exec_middleware.recv);
+
+	// The subscription is released once the first one is dropped.
+	drop(sub);
+	let _ = get_next_event!(&mut exec_middleware.recv);
+	let mut sub = tx_api
+		.subscribe_unbounded("transactionWatch_unstable_submitAndWatch", rpc_params![&xt])
+		.await
+		.unwrap();
+	let event: TransactionEvent<H256> = get_next_event_sub!(&mut sub);
+	assert_eq!(event, TransactionEvent::Validated);
+}
+
+#[tokio::test]
+async fn tx_watch_filtered_events() {
+	let (api, pool, client, tx_api, _exec_middleware, _pool_middleware) = setup_api_tx();
+	let block_1_header = api.push_block(1, vec![], true);
+	client.set_best_block(block_1_header.hash(), 1);
+
+	let uxt = uxt(Alice, ALICE_NONCE);
+	let xt = hex_string(&uxt.encode());
+	let options = TransactionWatchOptions {
+		events: Some(vec![TransactionEventKind::BestChainBlockIncluded]),
+		..Default::default()
+	};
+
+	let mut sub = tx_api
+		.subscribe_unbounded("transactionWatch_unstable_submitAndWatch", rpc_params![&xt, options])
+		.await
+		.unwrap();
+
+	// Import block 2 with the transaction included.
+	let block_2_header = api.push_block(2, vec![uxt.clone()], true);
+	let block_2 = block_2_header.hash();
+
+	let event = ChainEvent::NewBestBlock { hash: block_2, tree_route: None };
+	pool.inner_pool.maintain(event).await;
+	let event = ChainEvent::Finalized { hash: block_2, tree_route: Arc::from(vec![]) };
+	pool.inner_pool.maintain(event).await;
+
+	// The `Validated` event is skipped.
+	let event: TransactionEvent<H256> = get_next_event_sub!(&mut sub);
+	assert_eq!(
+		event,
+		TransactionEvent::BestChainBlockIncluded(Some(TransactionBlock {
+			hash: block_2,
+			index: 0,
+			dispatch_result: None,
+		}))
+	);
+	// The final events are always reported.
+	let event: TransactionEvent<H256> = get_next_event_sub!(&mut sub);
+	assert_eq!(
+		event,
+		TransactionEvent::Finalized(TransactionBlock {
+			hash: block_2,
+			index: 0,
+			dispatch_result: None,
+		})
+	);
 }
@@ -19,17 +19,23 @@
 //! API implementation for submitting transactions.
 
 use crate::{
+	session::{self, SessionManager},
 	transaction::{
 		api::TransactionApiServer,
-		error::Error,
-		event::{TransactionBlock, TransactionDropped, TransactionError, TransactionEvent},
+		dispatch_result::DispatchResults,
+		error::{Error, ErrorBatch, ErrorWatch},
+		event::{
+			PoolErrorReason, TransactionBatchEvent, TransactionBlock, TransactionDropped,
+			TransactionError, TransactionErrorCode, TransactionEvent, TransactionWatchOptions,
+		},
 	},
 	SubscriptionTaskExecutor,
 };
 use codec::Decode;
-use futures::{StreamExt, TryFutureExt};
+use futures::{future, stream, stream::BoxStream, StreamExt, TryFutureExt};
 use jsonrpsee::{core::async_trait, PendingSubscriptionSink};
-use sc_rpc::utils::{pipe_from_stream, to_sub_message};
+use sc_client_api::{Backend, ExecutorProvider, StorageProvider};
+use sc_rpc::utils::{pipe_from_stream, pipe_from_stream_with_lagging};
 use sc_transaction_pool_api::{
 	error::IntoPoolError, BlockHash, TransactionFor, TransactionPool, TransactionSource,
 	TransactionStatus,
@@ -37,24 +43,79 @@ use sc_transaction_pool_api::{
 use sp_blockchain::HeaderBackend;
 use sp_core::Bytes;
 use sp_runtime::traits::Block as BlockT;
-use std::sync::Arc;
+use std::sync::{
+	atomic::{AtomicUsize, Ordering},
+	Arc,
+};
 
 pub(crate) const LOG_TARGET: &str = "rpc-spec-v2";
 
+/// The configuration of [`Transaction`].
+#[derive(Debug, Clone)]
+pub struct TransactionConfig {
+	/// The maximum number of `transactionWatch` subscriptions running at a time, across all
+	/// connections.
+	pub max_subscriptions: usize,
+	/// The maximum number of extrinsics submitted by a
+	/// `transactionWatch_unstable_submitAndWatchBatch` subscription.
+	pub max_batch_size: usize,
+}
+
+/// The maximum number of `transactionWatch` subscriptions running at a time.
+const MAX_SUBSCRIPTIONS: usize = 4096;
+
+/// The maximum number of extrinsics submitted by `transactionWatch_unstable_submitAndWatchBatch`.
+const MAX_BATCH_SIZE: usize = 128;
+
+impl Default for TransactionConfig {
+	fn default() -> Self {
+		TransactionConfig { max_subscriptions: MAX_SUBSCRIPTIONS, max_batch_size: MAX_BATCH_SIZE }
+	}
+}
+
 /// An API for transaction RPC calls.
-pub struct Transaction<Pool, Client> {
+pub struct Transaction<BE, Pool, Client> {
 	/// Substrate client.
 	client: Arc<Client>,
 	/// Transactions pool.
 	pool: Arc<Pool>,
 	/// Executor to spawn subscriptions.
 	executor: SubscriptionTaskExecutor,
+	/// Reader of the dispatch results of the included transactions.
+	dispatch_results: Arc<DispatchResults<BE, Client>>,
+	/// The sessions the subscriptions may be part of, if enabled.
+	sessions: Option<SessionManager>,
+	/// The running subscriptions.
+	subscriptions: Subscriptions,
+	/// The maximum number of extrinsics submitted by a batch subscription.
+	max_batch_size: usize,
 }
 
-impl<Pool, Client> Transaction<Pool, Client> {
+impl<BE, Pool, Client> Transaction<BE, Pool, Client> {
 	/// Creates a new [`Transaction`].
-	pub fn new(client: Arc<Client>, pool: Arc<Pool>, executor: SubscriptionTaskExecutor) -> Self {
-		Transaction { client, pool, executor }
+	pub fn new(
+		client: Arc<Client>,
+		pool: Arc<Pool>,
+		executor: SubscriptionTaskExecutor,
+		config: TransactionConfig,
+	) -> Self {
+		let dispatch_results = Arc::new(DispatchResults::new(client.clone()));
+		Transaction {
+			client,
+			pool,
+			executor,
+			dispatch_results,
+			sessions: None,
+			subscriptions: Subscriptions::new(config.max_subscriptions),
+			max_batch_size: config.max_batch_size,
+		}
+	}
+
+	/// Keep the subscriptions of the connections with a session alive after a disconnection,
+	/// such that they can be resumed from another connection.
+	pub fn with_sessions(mut self, sessions: SessionManager) -> Self {
+		self.sessions = Some(sessions);
+		self
 	}
 }
 
@@ -65,64 +126,250 @@ impl<Pool, Client> Transaction<Pool, Client> {
 /// some unique transactions via RPC and have them included in the pool.
 const TX_SOURCE: TransactionSource = TransactionSource::External;
 
+/// The stream of the events of a submitted transaction.
+type EventStream<Hash> = BoxStream<'static, TransactionEvent<Hash>>;
+
+/// The number of running `transactionWatch` subscriptions, bounded by a limit.
+#[derive(Clone)]
+struct Subscriptions {
+	/// The number of running subscriptions.
+	count: Arc<AtomicUsize>,
+	/// The maximum number of running subscriptions.
+	max: usize,
+}
+
+impl Subscriptions {
+	/// Create a new [`Subscriptions`] allowing up to `max` running subscriptions.
+	fn new(max: usize) -> Self {
+		Subscriptions { count: Default::default(), max }
+	}
+
+	/// Reserve a subscription, unless the limit is reached.
+	///
+	/// The subscription is released when the returned guard is dropped.
+	fn reserve(&self) -> Option<ReservedSubscription> {
+		self.count
+			.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |count| {
+				(count < self.max).then_some(count + 1)
+			})
+			.ok()?;
+		Some(ReservedSubscription(self.count.clone()))
+	}
+}
+
+/// A running `transactionWatch` subscription, released on drop.
+struct ReservedSubscription(Arc<AtomicUsize>);
+
+impl Drop for ReservedSubscription {
+	fn drop(&mut self) {
+		self.0.fetch_sub(1, Ordering::SeqCst);
+	}
+}
+
 #[async_trait]
-impl<Pool, Client> TransactionApiServer<BlockHash<Pool>> for Transaction<Pool, Client>
+impl<BE, Pool, Client> TransactionApiServer<BlockHash<Pool>> for Transaction<BE, Pool, Client>
 where
+	BE: Backend<Pool::Block> + 'static,
 	Pool: TransactionPool + Sync + Send + 'static,
 	Pool::Hash: Unpin,
 	<Pool::Block as BlockT>::Hash: Unpin,
-	Client: HeaderBackend<Pool::Block> + Send + Sync + 'static,
+	Client: HeaderBackend<Pool::Block>
+		+ ExecutorProvider<Pool::Block>
+		+ StorageProvider<Pool::Block, BE>
+		+ Send
+		+ Sync
+		+ 'static,
 {
-	fn submit_and_watch(&self, pending: PendingSubscriptionSink, xt: Bytes) {
+	fn submit_and_watch(
+		&self,
+		pending: PendingSubscriptionSink,
+		xt: Bytes,
+		options: Option<TransactionWatchOptions>,
+	) {
 		let client = self.client.clone();
 		let pool = self.pool.clone();
+		let options = options.unwrap_or_default();
+		let dispatch_results = self.dispatch_results.clone();
+		let sessions = self
+			.sessions
+			.clone()
+			.filter(|sessions| sessions.has_session(pending.connection_id()));
+		let subscriptions = self.subscriptions.clone();
 
 		let fut = async move {
-			let decoded_extrinsic = match TransactionFor::<Pool>::decode(&mut &xt[..]) {
-				Ok(decoded_extrinsic) => decoded_extrinsic,
-				Err(e) => {
-					log::debug!(target: LOG_TARGET, "Extrinsic bytes cannot be decoded: {:?}", e);
-
-					let Ok(sink) = pending.accept().await else { return };
-
-					// The transaction is invalid.
-					let msg = to_sub_message(
-						&sink,
-						&TransactionEvent::Invalid::<BlockHash<Pool>>(TransactionError {
-							error: "Extrinsic bytes cannot be decoded".into(),
-						}),
-					);
-					let _ = sink.send(msg).await;
-					return
-				},
+			// Released once the subscription ends.
+			let Some(_reserved) = subscriptions.reserve() else {
+				pending.reject(ErrorWatch::ReachedLimits).await;
+				return
 			};
 
-			let best_block_hash = client.info().best_hash;
-
-			let submit = pool
-				.submit_and_watch(best_block_hash, TX_SOURCE, decoded_extrinsic)
-				.map_err(|e| {
-					e.into_pool_error()
-						.map(Error::from)
-						.unwrap_or_else(|e| Error::Verification(Box::new(e)))
-				});
-
-			match submit.await {
+			match submit_extrinsic(&*client, &*pool, dispatch_results, options, xt).await {
 				Ok(stream) => {
-					let stream = stream.filter_map(move |event| async move { handle_event(event) });
-					pipe_from_stream(pending, stream.boxed()).await;
+					if let Some(sessions) = sessions {
+						// The session keeps the events the client misses.
+						session::pipe_from_stream(pending, stream, &sessions).await;
+						return
+					}
+					// The client can't follow the transaction any longer once it missed events.
+					let lagging = TransactionEvent::Dropped(TransactionDropped {
+						error: "Subscription buffer exceeded, the client is too slow".into(),
+					});
+					pipe_from_stream_with_lagging(pending, stream, lagging).await;
 				},
-				Err(err) => {
-					// We have not created an `Watcher` for the tx. Make sure the
-					// error is still propagated as an event.
-					let event: TransactionEvent<<Pool::Block as BlockT>::Hash> = err.into();
-					pipe_from_stream(pending, futures::stream::once(async { event }).boxed()).await;
+				Err(event) => {
+					let stream = stream::once(future::ready(event)).boxed();
+					match sessions {
+						Some(sessions) =>
+							session::pipe_from_stream(pending, stream, &sessions).await,
+						None => pipe_from_stream(pending, stream).await,
+					}
 				},
 			};
 		};
 
 		sc_rpc::utils::spawn_subscription_task(&self.executor, fut);
 	}
+
+	fn submit_and_watch_batch(
+		&self,
+		pending: PendingSubscriptionSink,
+		bytes: Vec<Bytes>,
+		options: Option<TransactionWatchOptions>,
+	) {
+		let client = self.client.clone();
+		let pool = self.pool.clone();
+		let options = options.unwrap_or_default();
+		let dispatch_results = self.dispatch_results.clone();
+		let sessions = self
+			.sessions
+			.clone()
+			.filter(|sessions| sessions.has_session(pending.connection_id()));
+		let subscriptions = self.subscriptions.clone();
+		let max_batch_size = self.max_batch_size;
+
+		let fut = async move {
+			if bytes.is_empty() || bytes.len() > max_batch_size {
+				pending.reject(ErrorBatch::InvalidBatchSize(max_batch_size)).await;
+				return
+			}
+
+			// Released once the subscription ends.
+			let Some(_reserved) = subscriptions.reserve() else {
+				pending.reject(ErrorWatch::ReachedLimits).await;
+				return
+			};
+
+			let mut streams = Vec::with_capacity(bytes.len());
+			let mut rejected = false;
+			for (index, xt) in bytes.into_iter().enumerate() {
+				let stream = if rejected {
+					// The following transactions likely depend on the rejected one, e.g. through
+					// their nonces, so they would never become valid.
+					let event = TransactionEvent::Dropped(TransactionDropped {
+						error: "A previous transaction of the batch was rejected".into(),
+					});
+					stream::once(future::ready(event)).boxed()
+				} else {
+					match submit_extrinsic(
+						&*client,
+						&*pool,
+						dispatch_results.clone(),
+						options.clone(),
+						xt,
+					)
+					.await
+					{
+						Ok(stream) => stream,
+						Err(event) => {
+							rejected = true;
+							stream::once(future::ready(event)).boxed()
+						},
+					}
+				};
+				streams.push(
+					stream.map(move |event| TransactionBatchEvent { index: Some(index), event }),
+				);
+			}
+			let stream = stream::select_all(streams).boxed();
+
+			if let Some(sessions) = sessions {
+				// The session keeps the events the client misses.
+				session::pipe_from_stream(pending, stream, &sessions).await;
+				return
+			}
+			// The client can't follow the transactions any longer once it missed events.
+			let lagging = TransactionBatchEvent {
+				index: None,
+				event: TransactionEvent::Dropped(TransactionDropped {
+					error: "Subscription buffer exceeded, the client is too slow".into(),
+				}),
+			};
+			pipe_from_stream_with_lagging(pending, stream, lagging).await;
+		};
+
+		sc_rpc::utils::spawn_subscription_task(&self.executor, fut);
+	}
+}
+
+/// Decode and submit an extrinsic to the pool at the best block.
+///
+/// Returns the stream of the events of the transaction reported with the given options, or the
+/// final event of the transaction if it cannot be submitted.
+async fn submit_extrinsic<BE, Pool, Client>(
+	client: &Client,
+	pool: &Pool,
+	dispatch_results: Arc<DispatchResults<BE, Client>>,
+	options: TransactionWatchOptions,
+	xt: Bytes,
+) -> Result<EventStream<BlockHash<Pool>>, TransactionEvent<BlockHash<Pool>>>
+where
+	BE: Backend<Pool::Block> + 'static,
+	Pool: TransactionPool + Sync + Send + 'static,
+	Client: HeaderBackend<Pool::Block>
+		+ ExecutorProvider<Pool::Block>
+		+ StorageProvider<Pool::Block, BE>
+		+ Send
+		+ Sync
+		+ 'static,
+{
+	let decoded_extrinsic = match TransactionFor::<Pool>::decode(&mut &xt[..]) {
+		Ok(decoded_extrinsic) => decoded_extrinsic,
+		Err(e) => {
+			log::debug!(target: LOG_TARGET, "Extrinsic bytes cannot be decoded: {:?}", e);
+
+			// The transaction is invalid.
+			return Err(TransactionEvent::Invalid(TransactionError {
+				error: "Extrinsic bytes cannot be decoded".into(),
+				code: Some(TransactionErrorCode::Verification),
+				retryable: false,
+			}))
+		},
+	};
+
+	let best_block_hash = client.info().best_hash;
+
+	let submit =
+		pool.submit_and_watch(best_block_hash, TX_SOURCE, decoded_extrinsic)
+			.map_err(|e| {
+				e.into_pool_error()
+					.map(Error::from)
+					.unwrap_or_else(|e| Error::Verification(Box::new(e)))
+			});
+
+	match submit.await {
+		Ok(stream) => Ok(stream
+			.filter_map(move |event| {
+				let mut event = handle_event(event).filter(|event| options.reports(event));
+				if let Some(event) = event.as_mut().filter(|_| options.with_dispatch_result) {
+					dispatch_results.add_to_event::<Pool::Block>(event);
+				}
+				future::ready(event)
+			})
+			.boxed()),
+		// We have not created an `Watcher` for the tx. Make sure the
+		// error is still propagated as an event.
+		Err(err) => Err(err.into()),
+	}
 }
 
 /// Handle events generated by the transaction-pool and convert them
@@ -135,22 +382,42 @@ pub fn handle_event<Hash: Clone, BlockHash: Clone>(
 		TransactionStatus::Ready | TransactionStatus::Future =>
 			Some(TransactionEvent::<BlockHash>::Validated),
 		TransactionStatus::InBlock((hash, index)) =>
-			Some(TransactionEvent::BestChainBlockIncluded(Some(TransactionBlock { hash, index }))),
+			Some(TransactionEvent::BestChainBlockIncluded(Some(TransactionBlock {
+				hash,
+				index,
+				dispatch_result: None,
+			}))),
 		TransactionStatus::Retracted(_) => Some(TransactionEvent::BestChainBlockIncluded(None)),
 		TransactionStatus::FinalityTimeout(_) =>
 			Some(TransactionEvent::Dropped(TransactionDropped {
 				error: "Maximum number of finality watchers has been reached".into(),
 			})),
 		TransactionStatus::Finalized((hash, index)) =>
-			Some(TransactionEvent::Finalized(TransactionBlock { hash, index })),
+			Some(TransactionEvent::Finalized(TransactionBlock {
+				hash,
+				index,
+				dispatch_result: None,
+			})),
 		TransactionStatus::Usurped(_) => Some(TransactionEvent::Invalid(TransactionError {
 			error: "Extrinsic was rendered invalid by another extrinsic".into(),
+			code: Some(TransactionErrorCode::Pool {
+				reason: PoolErrorReason::Usurped,
+				ban_expires_in_ms: None,
+				required_priority: None,
+			}),
+			retryable: false,
 		})),
 		TransactionStatus::Dropped => Some(TransactionEvent::Dropped(TransactionDropped {
 			error: "Extrinsic dropped from the pool due to exceeding limits".into(),
 		})),
 		TransactionStatus::Invalid => Some(TransactionEvent::Invalid(TransactionError {
 			error: "Extrinsic marked as invalid".into(),
+			code: Some(TransactionErrorCode::Pool {
+				reason: PoolErrorReason::Invalidated,
+				ban_expires_in_ms: None,
+				required_priority: None,
+			}),
+			retryable: false,
 		})),
 		// These are the events that are not supported by the new API.
 		TransactionStatus::Broadcast(_) => None,
@@ -18,26 +18,75 @@
 
 //! API implementation for broadcasting transactions.
 
-use crate::{transaction::api::TransactionBroadcastApiServer, SubscriptionTaskExecutor};
+use crate::{
+	transaction::{
+		api::TransactionBroadcastApiServer,
+		error::Error,
+		event::{
+			TransactionBlock, TransactionBroadcastOptions, TransactionBroadcastStatus,
+			TransactionDropped, TransactionError, TransactionErrorCode, TransactionEvent,
+		},
+		transaction::handle_event,
+	},
+	SubscriptionTaskExecutor,
+};
 use codec::Decode;
 use futures::{FutureExt, Stream, StreamExt};
 use futures_util::stream::AbortHandle;
 use jsonrpsee::core::{async_trait, RpcResult};
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use rand::{distributions::Alphanumeric, Rng};
 use sc_client_api::BlockchainEvents;
 use sc_transaction_pool_api::{
-	error::IntoPoolError, TransactionFor, TransactionPool, TransactionSource,
+	error::IntoPoolError, BlockHash, TransactionFor, TransactionPool, TransactionSource,
+	TransactionStatus,
 };
 use sp_blockchain::HeaderBackend;
 use sp_core::Bytes;
 use sp_runtime::traits::Block as BlockT;
-use std::{collections::HashMap, sync::Arc};
+use std::{
+	collections::{HashMap, VecDeque},
+	sync::Arc,
+	time::Duration,
+};
+
+use super::{broadcast_store::BroadcastStore, error::ErrorBroadcast, transaction::LOG_TARGET};
+
+/// The configuration of [`TransactionBroadcast`].
+#[derive(Debug, Clone)]
+pub struct TransactionBroadcastConfig {
+	/// The maximum number of `transaction_broadcast` operations running at a time.
+	///
+	/// `transaction_broadcast` returns `null` once the limit is reached, as required by the
+	/// specification for overloaded servers.
+	pub max_operations: usize,
+	/// The maximum number of events retained per `transaction_broadcast` operation.
+	pub max_operation_events: usize,
+	/// The maximum number of ended `transaction_broadcast` operations whose events are retained.
+	pub max_ended_operations: usize,
+}
+
+/// The maximum number of `transaction_broadcast` operations running at a time.
+const MAX_OPERATIONS: usize = 1024;
+
+/// The maximum number of events retained per `transaction_broadcast` operation.
+const MAX_OPERATION_EVENTS: usize = 16;
 
-use super::error::ErrorBroadcast;
+/// The maximum number of ended `transaction_broadcast` operations whose events are retained.
+const MAX_ENDED_OPERATIONS: usize = 512;
+
+impl Default for TransactionBroadcastConfig {
+	fn default() -> Self {
+		TransactionBroadcastConfig {
+			max_operations: MAX_OPERATIONS,
+			max_operation_events: MAX_OPERATION_EVENTS,
+			max_ended_operations: MAX_ENDED_OPERATIONS,
+		}
+	}
+}
 
 /// An API for transaction RPC calls.
-pub struct TransactionBroadcast<Pool, Client> {
+pub struct TransactionBroadcast<Pool: TransactionPool, Client> {
 	/// Substrate client.
 	client: Arc<Client>,
 	/// Transactions pool.
@@ -46,6 +95,12 @@ pub struct TransactionBroadcast<Pool, Client> {
 	executor: SubscriptionTaskExecutor,
 	/// The broadcast operation IDs.
 	broadcast_ids: Arc<RwLock<HashMap<String, BroadcastState>>>,
+	/// The store persisting the broadcast operations across restarts, if enabled.
+	store: Option<Arc<BroadcastStore>>,
+	/// The latest events of the broadcast operations, for `transaction_unstable_status`.
+	events: Arc<Mutex<OperationEvents<BlockHash<Pool>>>>,
+	/// The maximum number of operations running at a time.
+	max_operations: usize,
 }
 
 /// The state of a broadcast operation.
@@ -54,10 +109,93 @@ struct BroadcastState {
 	handle: AbortHandle,
 }
 
-impl<Pool, Client> TransactionBroadcast<Pool, Client> {
+/// The latest events of the broadcast operations.
+///
+/// The events of an operation are retained while it runs, and until `max_ended_operations`
+/// operations have ended after it.
+struct OperationEvents<Hash> {
+	/// The latest events of each operation and whether the operation ended.
+	operations: HashMap<String, (VecDeque<TransactionEvent<Hash>>, bool)>,
+	/// The ended operations, oldest first.
+	ended: VecDeque<String>,
+	/// The maximum number of events retained per operation.
+	max_operation_events: usize,
+	/// The maximum number of ended operations whose events are retained.
+	max_ended_operations: usize,
+}
+
+impl<Hash: Clone> OperationEvents<Hash> {
+	/// Create a new [`OperationEvents`] with the limits of the given configuration.
+	fn new(config: &TransactionBroadcastConfig) -> Self {
+		OperationEvents {
+			operations: Default::default(),
+			ended: Default::default(),
+			max_operation_events: config.max_operation_events,
+			max_ended_operations: config.max_ended_operations,
+		}
+	}
+
+	/// Start retaining the events of the given operation.
+	fn start(&mut self, id: &str) {
+		self.operations.insert(id.into(), Default::default());
+	}
+
+	/// Record an event of the given operation, discarding its oldest event past the limit.
+	fn push(&mut self, id: &str, event: TransactionEvent<Hash>) {
+		let Some((events, _)) = self.operations.get_mut(id) else { return };
+		if events.len() >= self.max_operation_events {
+			events.pop_front();
+		}
+		events.push_back(event);
+	}
+
+	/// Mark the given operation as ended, discarding the events of the oldest ended operation
+	/// past the limit.
+	fn end(&mut self, id: &str) {
+		let Some((_, ended)) = self.operations.get_mut(id) else { return };
+		*ended = true;
+		self.ended.push_back(id.into());
+
+		if self.ended.len() > self.max_ended_operations {
+			if let Some(oldest) = self.ended.pop_front() {
+				self.operations.remove(&oldest);
+			}
+		}
+	}
+
+	/// Discard the events of the given operation.
+	fn remove(&mut self, id: &str) {
+		if self.operations.remove(id).map_or(false, |(_, ended)| ended) {
+			self.ended.retain(|ended| ended != id);
+		}
+	}
+
+	/// The state of the given operation, if its events are retained.
+	fn status(&self, id: &str) -> Option<TransactionBroadcastStatus<Hash>> {
+		self.operations.get(id).map(|(events, ended)| TransactionBroadcastStatus {
+			events: events.iter().cloned().collect(),
+			ended: *ended,
+		})
+	}
+}
+
+impl<Pool: TransactionPool, Client> TransactionBroadcast<Pool, Client> {
 	/// Creates a new [`TransactionBroadcast`].
-	pub fn new(client: Arc<Client>, pool: Arc<Pool>, executor: SubscriptionTaskExecutor) -> Self {
-		TransactionBroadcast { client, pool, executor, broadcast_ids: Default::default() }
+	pub fn new(
+		client: Arc<Client>,
+		pool: Arc<Pool>,
+		executor: SubscriptionTaskExecutor,
+		config: TransactionBroadcastConfig,
+	) -> Self {
+		TransactionBroadcast {
+			client,
+			pool,
+			executor,
+			broadcast_ids: Default::default(),
+			store: None,
+			events: Arc::new(Mutex::new(OperationEvents::new(&config))),
+			max_operations: config.max_operations,
+		}
 	}
 
 	/// Generate an unique operation ID for the `transaction_broadcast` RPC method.
@@ -77,7 +215,7 @@ impl<Pool, Client> TransactionBroadcast<Pool, Client> {
 
 		let broadcast_ids = self.broadcast_ids.read();
 
-		while broadcast_ids.contains_key(&id) {
+		while broadcast_ids.contains_key(&id) || self.events.lock().operations.contains_key(&id) {
 			id = generate_operation_id();
 		}
 
@@ -92,19 +230,41 @@ impl<Pool, Client> TransactionBroadcast<Pool, Client> {
 /// some unique transactions via RPC and have them included in the pool.
 const TX_SOURCE: TransactionSource = TransactionSource::External;
 
-#[async_trait]
-impl<Pool, Client> TransactionBroadcastApiServer for TransactionBroadcast<Pool, Client>
+impl<Pool, Client> TransactionBroadcast<Pool, Client>
 where
 	Pool: TransactionPool + Sync + Send + 'static,
 	Pool::Error: IntoPoolError,
 	<Pool::Block as BlockT>::Hash: Unpin,
 	Client: HeaderBackend<Pool::Block> + BlockchainEvents<Pool::Block> + Send + Sync + 'static,
 {
-	fn broadcast(&self, bytes: Bytes) -> RpcResult<Option<String>> {
-		let pool = self.pool.clone();
+	/// Persist the broadcast operations in the given store, and resume the operations it
+	/// persisted before the last restart.
+	///
+	/// The resumed operations keep their operation ID, such that they can still be stopped.
+	pub fn with_store(mut self, store: Arc<BroadcastStore>) -> Self {
+		let pending = store.take_pending();
+		self.store = Some(store);
+
+		for operation in pending {
+			log::debug!(
+				target: LOG_TARGET,
+				"[broadcast][id={}] Resuming the persisted operation",
+				operation.operation_id
+			);
+			self.spawn_broadcast(operation.operation_id, operation.transaction, operation.options);
+		}
 
-		// The unique ID of this operation.
-		let id = self.generate_unique_id();
+		self
+	}
+
+	/// Spawn the future broadcasting the given transaction until it reaches a final state or the
+	/// limits of the `options` are reached.
+	fn spawn_broadcast(&self, id: String, bytes: Vec<u8>, options: TransactionBroadcastOptions) {
+		let client = self.client.clone();
+		let pool = self.pool.clone();
+		let events = self.events.clone();
+		let operation_id = id.clone();
+		events.lock().start(&id);
 
 		let mut best_block_import_stream =
 			Box::pin(self.client.import_notification_stream().filter_map(
@@ -114,13 +274,33 @@ where
 		let broadcast_transaction_fut = async move {
 			// There is nothing we could do with an extrinsic of invalid format.
 			let Ok(decoded_extrinsic) = TransactionFor::<Pool>::decode(&mut &bytes[..]) else {
+				events.lock().push(
+					&operation_id,
+					TransactionEvent::Invalid(TransactionError {
+						error: "Extrinsic bytes cannot be decoded".into(),
+						code: Some(TransactionErrorCode::Verification),
+						retryable: false,
+					}),
+				);
 				return;
 			};
 
 			// Flag to determine if the we should broadcast the transaction again.
 			let mut is_done = false;
+			// The number of times the transaction was submitted to the pool.
+			let mut attempts = 0;
 
 			while !is_done {
+				if options.max_attempts.map_or(false, |max_attempts| attempts >= max_attempts) {
+					events.lock().push(
+						&operation_id,
+						TransactionEvent::Dropped(TransactionDropped {
+							error: "Maximum number of broadcast attempts reached".into(),
+						}),
+					);
+					return;
+				}
+
 				// Wait for the last block to become available.
 				let Some(best_block_hash) =
 					last_stream_element(&mut best_block_import_stream).await
@@ -128,6 +308,7 @@ where
 					return;
 				};
 
+				attempts += 1;
 				let mut stream = match pool
 					.submit_and_watch(best_block_hash, TX_SOURCE, decoded_extrinsic.clone())
 					.await
@@ -142,12 +323,50 @@ where
 							// recoverable errors.
 							continue
 						} else {
+							events.lock().push(&operation_id, Error::Pool(pool_err).into());
 							return;
 						}
 					},
 				};
 
+				// The block the transaction is included in, with its index in the block.
+				let mut included = None;
+
 				while let Some(event) = stream.next().await {
+					match &event {
+						TransactionStatus::InBlock(block) => included = Some(block.clone()),
+						TransactionStatus::Retracted(_) => included = None,
+						_ => {},
+					}
+
+					// The pool stopped watching the transaction before the block including it
+					// was finalized, wait for the finality of the block instead of broadcasting
+					// the transaction again.
+					if let (true, TransactionStatus::FinalityTimeout(hash)) =
+						(options.stop_on_finalization, &event)
+					{
+						if let Some((_, index)) =
+							included.clone().filter(|(block, _)| block == hash)
+						{
+							if wait_for_finality::<Pool::Block, _>(&*client, hash.clone()).await {
+								events.lock().push(
+									&operation_id,
+									TransactionEvent::Finalized(TransactionBlock {
+										hash: hash.clone(),
+										index,
+										dispatch_result: None,
+									}),
+								);
+								is_done = true;
+								break;
+							}
+						}
+					}
+
+					if let Some(event) = handle_event(event.clone()) {
+						events.lock().push(&operation_id, event);
+					}
+
 					// Check if the transaction could be submitted again
 					// at a later time.
 					if event.is_retriable() {
@@ -164,25 +383,93 @@ where
 			}
 		};
 
+		let broadcast_transaction_fut = match options.max_duration_secs {
+			Some(max_duration_secs) => {
+				let events = self.events.clone();
+				let operation_id = id.clone();
+				async move {
+					let max_duration = Duration::from_secs(max_duration_secs);
+					if tokio::time::timeout(max_duration, broadcast_transaction_fut).await.is_err()
+					{
+						events.lock().push(
+							&operation_id,
+							TransactionEvent::Dropped(TransactionDropped {
+								error: "Maximum duration of the broadcast reached".into(),
+							}),
+						);
+					}
+				}
+				.boxed()
+			},
+			None => broadcast_transaction_fut.boxed(),
+		};
+
 		// Convert the future into an abortable future, for easily terminating it from the
 		// `transaction_stop` method.
 		let (fut, handle) = futures::future::abortable(broadcast_transaction_fut);
 		let broadcast_ids = self.broadcast_ids.clone();
+		let store = self.store.clone();
+		let events = self.events.clone();
 		let drop_id = id.clone();
 		// The future expected by the executor must be `Future<Output = ()>` instead of
 		// `Future<Output = Result<(), Aborted>>`.
 		let fut = fut.map(move |_| {
 			// Remove the entry from the broadcast IDs map.
 			broadcast_ids.write().remove(&drop_id);
+			// The events are kept for a while, unless the operation was stopped.
+			events.lock().end(&drop_id);
+			// The operation ended, it must not be resumed after a restart.
+			if let Some(store) = store {
+				store.remove(&drop_id);
+			}
 		});
 
 		// Keep track of this entry and the abortable handle.
 		{
 			let mut broadcast_ids = self.broadcast_ids.write();
-			broadcast_ids.insert(id.clone(), BroadcastState { handle });
+			broadcast_ids.insert(id, BroadcastState { handle });
 		}
 
 		sc_rpc::utils::spawn_subscription_task(&self.executor, fut);
+	}
+}
+
+#[async_trait]
+impl<Pool, Client> TransactionBroadcastApiServer<BlockHash<Pool>>
+	for TransactionBroadcast<Pool, Client>
+where
+	Pool: TransactionPool + Sync + Send + 'static,
+	Pool::Error: IntoPoolError,
+	<Pool::Block as BlockT>::Hash: Unpin,
+	Client: HeaderBackend<Pool::Block> + BlockchainEvents<Pool::Block> + Send + Sync + 'static,
+{
+	fn broadcast(
+		&self,
+		bytes: Bytes,
+		options: Option<TransactionBroadcastOptions>,
+	) -> RpcResult<Option<String>> {
+		if self.broadcast_ids.read().len() >= self.max_operations {
+			log::debug!(
+				target: LOG_TARGET,
+				"[broadcast] Too many running operations, rejecting the transaction"
+			);
+			return Ok(None)
+		}
+
+		// The unique ID of this operation.
+		let id = self.generate_unique_id();
+		let options = options.unwrap_or_default();
+
+		if let Some(store) = &self.store {
+			if !store.insert(&id, &bytes, &options) {
+				log::debug!(
+					target: LOG_TARGET,
+					"[broadcast][id={id}] Too many persisted operations, not persisting it"
+				);
+			}
+		}
+
+		self.spawn_broadcast(id.clone(), bytes.0, options);
 
 		Ok(Some(id))
 	}
@@ -195,9 +482,44 @@ where
 		};
 
 		broadcast_state.handle.abort();
+		self.events.lock().remove(&operation_id);
 
 		Ok(())
 	}
+
+	fn broadcast_status(
+		&self,
+		operation_id: String,
+	) -> Result<TransactionBroadcastStatus<BlockHash<Pool>>, ErrorBroadcast> {
+		self.events
+			.lock()
+			.status(&operation_id)
+			.ok_or(ErrorBroadcast::InvalidOperationID)
+	}
+}
+
+/// Wait until the given block is finalized.
+///
+/// Returns `false` if the block is not part of the finalized chain, e.g. because a fork of it was
+/// finalized.
+async fn wait_for_finality<Block, Client>(client: &Client, hash: Block::Hash) -> bool
+where
+	Block: BlockT,
+	Client: HeaderBackend<Block> + BlockchainEvents<Block>,
+{
+	// Subscribe before checking the finalized block, such that no finalization is missed.
+	let mut finality_stream = client.finality_notification_stream();
+
+	loop {
+		let Ok(Some(number)) = client.number(hash) else { return false };
+		if client.info().finalized_number >= number {
+			return matches!(client.hash(number), Ok(Some(finalized)) if finalized == hash)
+		}
+
+		if finality_stream.next().await.is_none() {
+			return false
+		}
+	}
 }
 
 /// Returns the last element of the provided stream, or `None` if the stream is closed.
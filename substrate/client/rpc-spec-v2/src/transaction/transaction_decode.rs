@@ -0,0 +1,97 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! API implementation for decoding extrinsics.
+
+use crate::transaction::{
+	api::TransactionDecodeApiServer, error::ErrorDecode, event::TransactionDecoded,
+	extrinsic_decoder::ExtrinsicDecoder,
+};
+use codec::{Decode, Encode};
+use parking_lot::Mutex;
+use sc_client_api::{CallExecutor, ExecutorProvider};
+use sp_api::CallContext;
+use sp_blockchain::{Error as BlockChainError, HeaderBackend};
+use sp_core::Bytes;
+use sp_runtime::{traits::Block as BlockT, SaturatedConversion};
+use std::{marker::PhantomData, sync::Arc};
+
+/// An API for decoding extrinsics.
+pub struct TransactionDecode<Block, Client> {
+	/// Substrate client.
+	client: Arc<Client>,
+	/// The decoder of the extrinsics of the last runtime seen, with its spec version.
+	decoder: Mutex<Option<(u32, Arc<ExtrinsicDecoder>)>>,
+	/// Phantom member to pin the block type.
+	_phantom: PhantomData<Block>,
+}
+
+impl<Block, Client> TransactionDecode<Block, Client> {
+	/// Creates a new [`TransactionDecode`].
+	pub fn new(client: Arc<Client>) -> Self {
+		TransactionDecode { client, decoder: Mutex::new(None), _phantom: PhantomData }
+	}
+}
+
+impl<Block, Client> TransactionDecode<Block, Client>
+where
+	Block: BlockT,
+	Client: ExecutorProvider<Block>,
+{
+	/// The decoder of the extrinsics of the runtime of the block `hash`.
+	fn decoder(&self, hash: Block::Hash) -> Result<Arc<ExtrinsicDecoder>, BlockChainError> {
+		let executor = self.client.executor();
+		let spec_version = executor.runtime_version(hash)?.spec_version;
+		if let Some((version, decoder)) = &*self.decoder.lock() {
+			if *version == spec_version {
+				return Ok(decoder.clone())
+			}
+		}
+
+		let metadata = executor.call(
+			hash,
+			"Metadata_metadata_at_version",
+			&15u32.encode(),
+			CallContext::Offchain,
+		)?;
+		let decoder = Arc::new(
+			ExtrinsicDecoder::new(&metadata)
+				.map_err(|err| BlockChainError::Application(Box::new(err)))?,
+		);
+		*self.decoder.lock() = Some((spec_version, decoder.clone()));
+		Ok(decoder)
+	}
+}
+
+impl<Block, Client> TransactionDecodeApiServer for TransactionDecode<Block, Client>
+where
+	Block: BlockT + 'static,
+	Client: HeaderBackend<Block> + ExecutorProvider<Block> + Send + Sync + 'static,
+{
+	fn decode(&self, xt: Bytes) -> Result<TransactionDecoded, ErrorDecode> {
+		Block::Extrinsic::decode(&mut &xt[..]).map_err(|_| ErrorDecode::InvalidTransaction)?;
+		let info = self.client.info();
+
+		let decoder = self
+			.decoder(info.best_hash)
+			.map_err(|err| ErrorDecode::Unavailable(err.to_string()))?;
+		decoder
+			.decode(&xt, info.best_number.saturated_into())
+			.map_err(|err| ErrorDecode::Unavailable(err.to_string()))
+	}
+}
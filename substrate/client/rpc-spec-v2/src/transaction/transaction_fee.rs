@@ -0,0 +1,109 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! API implementation for estimating the fees of transactions.
+
+use crate::transaction::{
+	api::TransactionFeeApiServer, error::ErrorFee, event::TransactionFeeEstimate,
+	fee_details::FeeDetailsDecoder,
+};
+use codec::{Decode, Encode};
+use parking_lot::Mutex;
+use sc_client_api::{CallExecutor, ExecutorProvider};
+use sp_api::CallContext;
+use sp_blockchain::{Error as BlockChainError, HeaderBackend};
+use sp_core::Bytes;
+use sp_runtime::traits::Block as BlockT;
+use std::{marker::PhantomData, sync::Arc};
+
+/// An API for estimating the fees of transactions.
+pub struct TransactionFee<Block, Client> {
+	/// Substrate client.
+	client: Arc<Client>,
+	/// The decoder of the fee details of the last runtime seen, with its spec version.
+	decoder: Mutex<Option<(u32, Arc<FeeDetailsDecoder>)>>,
+	/// Phantom member to pin the block type.
+	_phantom: PhantomData<Block>,
+}
+
+impl<Block, Client> TransactionFee<Block, Client> {
+	/// Creates a new [`TransactionFee`].
+	pub fn new(client: Arc<Client>) -> Self {
+		TransactionFee { client, decoder: Mutex::new(None), _phantom: PhantomData }
+	}
+}
+
+impl<Block, Client> TransactionFee<Block, Client>
+where
+	Block: BlockT,
+	Client: ExecutorProvider<Block>,
+{
+	/// The decoder of the fee details of the runtime of the block `hash`.
+	fn decoder(&self, hash: Block::Hash) -> Result<Arc<FeeDetailsDecoder>, BlockChainError> {
+		let executor = self.client.executor();
+		let spec_version = executor.runtime_version(hash)?.spec_version;
+		if let Some((version, decoder)) = &*self.decoder.lock() {
+			if *version == spec_version {
+				return Ok(decoder.clone())
+			}
+		}
+
+		let metadata = executor.call(
+			hash,
+			"Metadata_metadata_at_version",
+			&15u32.encode(),
+			CallContext::Offchain,
+		)?;
+		let decoder = Arc::new(
+			FeeDetailsDecoder::new(&metadata)
+				.map_err(|err| BlockChainError::Application(Box::new(err)))?,
+		);
+		*self.decoder.lock() = Some((spec_version, decoder.clone()));
+		Ok(decoder)
+	}
+}
+
+impl<Block, Client> TransactionFeeApiServer for TransactionFee<Block, Client>
+where
+	Block: BlockT + 'static,
+	Client: HeaderBackend<Block> + ExecutorProvider<Block> + Send + Sync + 'static,
+{
+	fn estimate_fee(&self, xt: Bytes) -> Result<TransactionFeeEstimate, ErrorFee> {
+		let extrinsic =
+			Block::Extrinsic::decode(&mut &xt[..]).map_err(|_| ErrorFee::InvalidTransaction)?;
+		let best_block_hash = self.client.info().best_hash;
+
+		let decoder = self
+			.decoder(best_block_hash)
+			.map_err(|err| ErrorFee::Unavailable(err.to_string()))?;
+		let fee_details = self
+			.client
+			.executor()
+			.call(
+				best_block_hash,
+				"TransactionPaymentApi_query_fee_details",
+				&(extrinsic, xt.len() as u32).encode(),
+				CallContext::Offchain,
+			)
+			.map_err(|err| ErrorFee::Unavailable(err.to_string()))?;
+
+		decoder
+			.decode(&fee_details)
+			.map_err(|err| ErrorFee::Unavailable(err.to_string()))
+	}
+}
@@ -177,6 +177,17 @@ where
 			.collect())
 	}
 
+	async fn revalidate_pool(&self) -> Result<usize> {
+		self.deny_unsafe.check_if_safe()?;
+
+		self.pool.revalidate_ready().await.map_err(|e| {
+			e.into_pool_error()
+				.map(|e| Error::Pool(e))
+				.unwrap_or_else(|e| Error::Verification(Box::new(e)))
+				.into()
+		})
+	}
+
 	fn watch_extrinsic(&self, pending: PendingSubscriptionSink, xt: Bytes) {
 		let best_block_hash = self.client.info().best_hash;
 		let dxt = match TransactionFor::<P>::decode(&mut &xt[..]).map_err(|e| Error::from(e)) {
@@ -211,6 +211,21 @@ async fn author_should_remove_extrinsics() {
 	assert_eq!(removed, vec![xt1_hash, xt2_hash, xt3_hash]);
 }
 
+#[tokio::test]
+async fn author_should_revalidate_pool() {
+	let setup = TestSetup::default();
+	let api = setup.author().into_rpc();
+
+	let xt = to_hex(&uxt(AccountKeyring::Alice, 0).encode(), true);
+	api.call::<_, H256>("author_submitExtrinsic", [xt]).await.unwrap();
+	let xt = to_hex(&uxt(AccountKeyring::Bob, 0).encode(), true);
+	api.call::<_, H256>("author_submitExtrinsic", [xt]).await.unwrap();
+
+	let scheduled: usize = api.call("author_revalidatePool", EmptyParams::new()).await.unwrap();
+	assert_eq!(scheduled, 2);
+	assert_eq!(setup.pool.status().ready, 2);
+}
+
 #[tokio::test]
 async fn author_should_insert_key() {
 	let setup = TestSetup::default();
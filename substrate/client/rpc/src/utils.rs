@@ -24,35 +24,215 @@ use futures::{
 	Future, FutureExt, Stream, StreamExt,
 };
 use jsonrpsee::{PendingSubscriptionSink, SubscriptionMessage, SubscriptionSink};
+use parking_lot::{const_rwlock, RwLock};
+use prometheus_endpoint::{register, CounterVec, Opts, PrometheusError, Registry, U64};
 use sp_runtime::Serialize;
-use std::collections::VecDeque;
+use std::{
+	collections::VecDeque,
+	str::FromStr,
+	time::{Duration, Instant},
+};
 
 const DEFAULT_BUF_SIZE: usize = 16;
 
+/// How long the terminal event of a subscription closed for a slow client may take to be sent.
+const LAGGING_EVENT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// What to do with the notifications of a subscription whose client is too slow, once its
+/// buffer is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BackpressurePolicy {
+	/// Drop the oldest buffered notification, suitable when only the latest state matters.
+	DropOldest,
+	/// Drop the new notification.
+	DropNewest,
+	/// Close the subscription.
+	#[default]
+	Close,
+}
+
+impl BackpressurePolicy {
+	fn as_str(&self) -> &'static str {
+		match self {
+			Self::DropOldest => "drop-oldest",
+			Self::DropNewest => "drop-newest",
+			Self::Close => "close",
+		}
+	}
+}
+
+impl FromStr for BackpressurePolicy {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"drop-oldest" => Ok(Self::DropOldest),
+			"drop-newest" => Ok(Self::DropNewest),
+			"close" => Ok(Self::Close),
+			_ => Err(format!(
+				"Invalid backpressure policy `{s}`, expected `drop-oldest`, `drop-newest` or `close`"
+			)),
+		}
+	}
+}
+
+/// The backpressure policy of the subscriptions notified with a method.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubscriptionBackpressure {
+	/// The notification method of the subscriptions, e.g. `chain_newHead`.
+	pub method: String,
+	/// The policy of the subscriptions.
+	pub policy: BackpressurePolicy,
+}
+
+impl FromStr for SubscriptionBackpressure {
+	type Err = String;
+
+	/// Parse a policy formatted as `<METHOD>=<POLICY>`.
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let (method, policy) = s
+			.split_once('=')
+			.ok_or_else(|| format!("Expected `<METHOD>=<POLICY>`, got `{s}`"))?;
+
+		Ok(Self { method: method.trim().to_owned(), policy: policy.trim().parse()? })
+	}
+}
+
+struct Backpressure {
+	policies: Vec<SubscriptionBackpressure>,
+	throttled: Option<CounterVec<U64>>,
+	overflows: Option<CounterVec<U64>>,
+}
+
+static BACKPRESSURE: RwLock<Backpressure> =
+	const_rwlock(Backpressure { policies: Vec::new(), throttled: None, overflows: None });
+
+/// Set the backpressure policies of the subscriptions piped with [`pipe_from_stream`], and
+/// register the counters of their throttled notifications and of the subscriptions closed
+/// because their buffer overflowed.
+///
+/// The subscriptions without a policy are closed once their buffer is full. This applies to the
+/// subscriptions created afterwards, in the whole process.
+pub fn configure_backpressure(
+	policies: Vec<SubscriptionBackpressure>,
+	registry: Option<&Registry>,
+) -> Result<(), PrometheusError> {
+	let throttled = registry
+		.map(|registry| {
+			register(
+				CounterVec::new(
+					Opts::new(
+						"substrate_rpc_subscription_throttled",
+						"Notifications of the subscriptions dropped or closed for a slow client",
+					),
+					&["method", "policy"],
+				)?,
+				registry,
+			)
+		})
+		.transpose()?;
+	let overflows = registry
+		.map(|registry| {
+			register(
+				CounterVec::new(
+					Opts::new(
+						"substrate_rpc_subscription_overflows",
+						"Subscriptions closed because their buffer overflowed",
+					),
+					&["method"],
+				)?,
+				registry,
+			)
+		})
+		.transpose()?;
+
+	*BACKPRESSURE.write() = Backpressure { policies, throttled, overflows };
+	Ok(())
+}
+
+/// The backpressure policy of the subscriptions notified with `method`.
+fn backpressure_policy(method: &str) -> BackpressurePolicy {
+	BACKPRESSURE
+		.read()
+		.policies
+		.iter()
+		.find(|policy| policy.method == method)
+		.map_or_else(Default::default, |policy| policy.policy)
+}
+
+/// Report a notification of a subscription throttled according to `policy`.
+fn on_throttled(method: &str, policy: BackpressurePolicy) {
+	log::debug!(
+		target: "rpc",
+		"Subscription buffer limit={} exceeded for subscription={}; applying policy {}",
+		DEFAULT_BUF_SIZE,
+		method,
+		policy.as_str(),
+	);
+	if let Some(throttled) = &BACKPRESSURE.read().throttled {
+		throttled.with_label_values(&[method, policy.as_str()]).inc();
+	}
+}
+
+/// Report a subscription closed because its buffer overflowed.
+fn on_overflow(method: &str, conn_id: usize, queue_len: usize, age: Duration) {
+	log::warn!(
+		target: "rpc",
+		"Subscription buffer overflow, closing the subscription: method={} conn_id={} queue_len={} subscription_age_ms={}",
+		method,
+		conn_id,
+		queue_len,
+		age.as_millis(),
+	);
+	if let Some(overflows) = &BACKPRESSURE.read().overflows {
+		overflows.with_label_values(&[method]).inc();
+	}
+}
+
 /// A simple bounded VecDeque.
 struct BoundedVecDeque<T> {
 	inner: VecDeque<T>,
 	max_cap: usize,
+	policy: BackpressurePolicy,
 }
 
 impl<T> BoundedVecDeque<T> {
 	/// Create a new bounded VecDeque.
-	fn new() -> Self {
-		Self { inner: VecDeque::with_capacity(DEFAULT_BUF_SIZE), max_cap: DEFAULT_BUF_SIZE }
+	fn new(policy: BackpressurePolicy) -> Self {
+		Self { inner: VecDeque::with_capacity(DEFAULT_BUF_SIZE), max_cap: DEFAULT_BUF_SIZE, policy }
 	}
 
+	fn is_full(&self) -> bool {
+		self.inner.len() >= self.max_cap
+	}
+
+	/// Push an item, applying the backpressure policy if the buffer is full.
+	///
+	/// Fails if the subscription must be closed.
 	fn push_back(&mut self, item: T) -> Result<(), ()> {
-		if self.inner.len() >= self.max_cap {
-			Err(())
-		} else {
+		if !self.is_full() {
 			self.inner.push_back(item);
-			Ok(())
+			return Ok(())
+		}
+
+		match self.policy {
+			BackpressurePolicy::DropOldest => {
+				self.inner.pop_front();
+				self.inner.push_back(item);
+				Ok(())
+			},
+			BackpressurePolicy::DropNewest => Ok(()),
+			BackpressurePolicy::Close => Err(()),
 		}
 	}
 
 	fn pop_front(&mut self) -> Option<T> {
 		self.inner.pop_front()
 	}
+
+	fn len(&self) -> usize {
+		self.inner.len()
+	}
 }
 
 /// Feed items to the subscription from the underlying stream.
@@ -61,13 +241,43 @@ impl<T> BoundedVecDeque<T> {
 /// unbounded and if the subscription can't keep with stream it can
 /// cause the buffer to become very large and consume lots of memory.
 ///
-/// In such cases the subscription is dropped.
-pub async fn pipe_from_stream<S, T>(pending: PendingSubscriptionSink, mut stream: S)
+/// In such cases the subscription is dropped, unless another [`BackpressurePolicy`] was
+/// configured for the subscription with [`configure_backpressure`]. The policy applies once the
+/// subscription has been accepted.
+pub async fn pipe_from_stream<S, T>(pending: PendingSubscriptionSink, stream: S)
 where
 	S: Stream<Item = T> + Unpin + Send + 'static,
 	T: Serialize + Send + 'static,
 {
-	let mut buf = BoundedVecDeque::new();
+	pipe_from_stream_inner(pending, stream, None).await
+}
+
+/// Feed items to the subscription from the underlying stream, like [`pipe_from_stream`], and
+/// notify `lagging` before closing the subscription if the client can't keep up with it.
+///
+/// This is meant for the protocols defining a terminal event for this case.
+pub async fn pipe_from_stream_with_lagging<S, T>(
+	pending: PendingSubscriptionSink,
+	stream: S,
+	lagging: T,
+) where
+	S: Stream<Item = T> + Unpin + Send + 'static,
+	T: Serialize + Send + 'static,
+{
+	pipe_from_stream_inner(pending, stream, Some(lagging)).await
+}
+
+async fn pipe_from_stream_inner<S, T>(
+	pending: PendingSubscriptionSink,
+	mut stream: S,
+	lagging: Option<T>,
+) where
+	S: Stream<Item = T> + Unpin + Send + 'static,
+	T: Serialize + Send + 'static,
+{
+	let started = Instant::now();
+	let conn_id = pending.connection_id();
+	let mut buf = BoundedVecDeque::new(BackpressurePolicy::Close);
 	let accept_fut = pending.accept();
 
 	futures::pin_mut!(accept_fut);
@@ -80,7 +290,8 @@ where
 			Either::Left((Ok(sink), _)) => break sink,
 			Either::Right((Some(msg), f)) => {
 				if buf.push_back(msg).is_err() {
-					log::warn!(target: "rpc", "Subscription::accept failed buffer limit={} exceeded; dropping subscription", buf.max_cap);
+					// The notification method is only known once the subscription is accepted.
+					on_overflow("unknown", conn_id, buf.len(), started.elapsed());
 					return
 				}
 				accept_fut = f;
@@ -90,17 +301,20 @@ where
 		}
 	};
 
-	inner_pipe_from_stream(sink, stream, buf).await
+	inner_pipe_from_stream(sink, stream, buf, lagging, started).await
 }
 
 async fn inner_pipe_from_stream<S, T>(
 	sink: SubscriptionSink,
 	mut stream: S,
 	mut buf: BoundedVecDeque<T>,
+	lagging: Option<T>,
+	started: Instant,
 ) where
 	S: Stream<Item = T> + Unpin + Send + 'static,
 	T: Serialize + Send + 'static,
 {
+	buf.policy = backpressure_policy(sink.method_name());
 	let mut next_fut = Box::pin(Fuse::terminated());
 	let mut next_item = stream.next();
 	let closed = sink.closed();
@@ -124,14 +338,22 @@ async fn inner_pipe_from_stream<S, T>(
 			},
 			// New item from the stream
 			Either::Right((Either::Right((Some(v), n)), c)) => {
+				if buf.is_full() {
+					on_throttled(sink.method_name(), buf.policy);
+				}
 				if buf.push_back(v).is_err() {
-					log::warn!(
-						target: "rpc",
-						"Subscription buffer limit={} exceeded for subscription={} conn_id={}; dropping subscription",
-						buf.max_cap,
+					on_overflow(
 						sink.method_name(),
-						sink.connection_id()
+						sink.connection_id(),
+						buf.len(),
+						started.elapsed(),
 					);
+					// The notification being sent is dropped along with the buffered ones.
+					drop(n);
+					if let Some(lagging) = lagging {
+						let msg = to_sub_message(&sink, &lagging);
+						let _ = sink.send_timeout(msg, LAGGING_EVENT_TIMEOUT).await;
+					}
 					return
 				}
 
@@ -182,7 +404,10 @@ pub fn spawn_subscription_task(
 
 #[cfg(test)]
 mod tests {
-	use super::pipe_from_stream;
+	use super::{
+		configure_backpressure, pipe_from_stream, pipe_from_stream_with_lagging,
+		BackpressurePolicy, SubscriptionBackpressure,
+	};
 	use futures::StreamExt;
 	use jsonrpsee::{core::EmptyServerParams, RpcModule, Subscription};
 
@@ -231,4 +456,52 @@ mod tests {
 		_ = rx.next().await.unwrap();
 		assert!(sub.next::<usize>().await.is_none());
 	}
+
+	#[tokio::test]
+	async fn pipe_from_stream_notifies_lagging_before_closing() {
+		let mut module = RpcModule::new(());
+		module
+			.register_subscription("sub", "my_sub", "unsub", |_, pending, _| async move {
+				let stream = futures::stream::iter(0..32);
+				pipe_from_stream_with_lagging(pending, stream, usize::MAX).await;
+				Ok(())
+			})
+			.unwrap();
+
+		let mut sub = module.subscribe("sub", EmptyServerParams::new(), 1).await.unwrap();
+		let mut received = Vec::new();
+		while let Some(Ok((item, _))) = sub.next::<usize>().await {
+			received.push(item);
+		}
+
+		// The client misses the notifications following the overflow but is told about it.
+		assert!(received.len() < 32);
+		assert_eq!(received.last(), Some(&usize::MAX));
+	}
+
+	#[tokio::test]
+	async fn pipe_from_stream_drops_oldest_items() {
+		let policy = "my_lossy_sub=drop-oldest".parse::<SubscriptionBackpressure>().unwrap();
+		assert_eq!(policy.policy, BackpressurePolicy::DropOldest);
+		configure_backpressure(vec![policy], None).unwrap();
+
+		let mut module = RpcModule::new(());
+		module
+			.register_subscription("sub", "my_lossy_sub", "unsub", |_, pending, _| async move {
+				let stream = futures::stream::iter(0..32);
+				pipe_from_stream(pending, stream).await;
+				Ok(())
+			})
+			.unwrap();
+
+		let mut sub = module.subscribe("sub", EmptyServerParams::new(), 1).await.unwrap();
+		let mut received = Vec::new();
+		while let Some(Ok((item, _))) = sub.next::<usize>().await {
+			received.push(item);
+		}
+
+		// The subscription isn't dropped but the client misses some items.
+		assert!(received.len() < 32);
+		assert_eq!(received.last(), Some(&31));
+	}
 }
@@ -68,7 +68,11 @@ use sc_rpc::{
 use sc_rpc_spec_v2::{
 	archive::ArchiveApiServer,
 	chain_head::ChainHeadApiServer,
-	transaction::{TransactionApiServer, TransactionBroadcastApiServer},
+	runtime_upgrade::RuntimeUpgradeApiServer,
+	transaction::{
+		TransactionApiServer, TransactionBroadcastApiServer, TransactionDecodeApiServer,
+		TransactionFeeApiServer,
+	},
 };
 use sc_telemetry::{telemetry, ConnectionMessage, Telemetry, TelemetryHandle, SUBSTRATE_INFO};
 use sc_transaction_pool_api::{MaintainedTransactionPool, TransactionPool};
@@ -253,6 +257,7 @@ where
 				),
 				wasm_runtime_substitutes,
 				enable_import_proof_recording,
+				execution_witness: config.execution_witness.clone(),
 			},
 		)?;
 
@@ -274,13 +279,17 @@ pub fn new_wasm_executor<H: HostFunctions>(config: &Configuration) -> WasmExecut
 	let strategy = config
 		.default_heap_pages
 		.map_or(DEFAULT_HEAP_ALLOC_STRATEGY, |p| HeapAllocStrategy::Static { extra_pages: p as _ });
-	WasmExecutor::<H>::builder()
+	let builder = WasmExecutor::<H>::builder()
 		.with_execution_method(config.wasm_method)
 		.with_onchain_heap_alloc_strategy(strategy)
 		.with_offchain_heap_alloc_strategy(strategy)
 		.with_max_runtime_instances(config.max_runtime_instances)
-		.with_runtime_cache_size(config.runtime_cache_size)
-		.build()
+		.with_runtime_cache_size(config.runtime_cache_size);
+	match config.prometheus_registry() {
+		Some(registry) => builder.with_prometheus_registry(registry),
+		None => builder,
+	}
+	.build()
 }
 
 /// Create an instance of default DB-backend backend.
@@ -488,6 +497,17 @@ where
 
 	let rpc_id_provider = config.rpc_id_provider.take();
 
+	// Shared by the RPC modules, such that the persisted broadcasts are resumed only once.
+	let broadcast_store = config.rpc_max_persisted_broadcasts.map(|max_entries| {
+		Arc::new(sc_rpc_spec_v2::transaction::BroadcastStore::new(
+			backend.clone(),
+			max_entries as usize,
+		))
+	});
+
+	// Shared by the RPC modules, such that the sessions outlive the connections.
+	let sessions = config.rpc_sessions.clone().map(sc_rpc_spec_v2::session::SessionManager::new);
+
 	// jsonrpsee RPC
 	let gen_rpc_module = |deny_unsafe: DenyUnsafe| {
 		gen_rpc_module(
@@ -499,13 +519,33 @@ where
 			system_rpc_tx.clone(),
 			&config,
 			backend.clone(),
+			broadcast_store.clone(),
+			sessions.clone(),
 			&*rpc_builder,
 		)
 	};
 
-	let rpc = start_rpc_servers(&config, gen_rpc_module, rpc_id_provider)?;
+	let rpc_response_cache =
+		config.rpc_response_cache.clone().map(sc_rpc_server::ResponseCache::new);
+	if let Some(cache) = rpc_response_cache.clone() {
+		spawn_handle.spawn(
+			"rpc-response-cache-invalidation",
+			None,
+			invalidate_rpc_response_cache(client.clone(), cache),
+		);
+	}
+
+	let rpc = start_rpc_servers(&config, gen_rpc_module, rpc_id_provider, rpc_response_cache)?;
 	let rpc_handlers = RpcHandlers(Arc::new(gen_rpc_module(sc_rpc::DenyUnsafe::No)?.into()));
 
+	if config.network.onchain_peers {
+		spawn_handle.spawn(
+			"onchain-peers",
+			Some("networking"),
+			crate::onchain_peers::run(client.clone(), network.clone()),
+		);
+	}
+
 	// Spawn informant task
 	spawn_handle.spawn(
 		"informant",
@@ -523,6 +563,28 @@ where
 	Ok(rpc_handlers)
 }
 
+/// Clears the RPC response cache whenever the best or the finalized block changes.
+async fn invalidate_rpc_response_cache<Block, TCl>(
+	client: Arc<TCl>,
+	cache: sc_rpc_server::ResponseCache,
+) where
+	Block: BlockT,
+	TCl: BlockchainEvents<Block>,
+{
+	let new_best = client
+		.import_notification_stream()
+		.filter(|notification| ready(notification.is_new_best))
+		.map(|_| ());
+	let finalized = client.finality_notification_stream().map(|_| ());
+
+	futures::stream::select(new_best, finalized)
+		.for_each(|()| {
+			cache.clear();
+			ready(())
+		})
+		.await
+}
+
 /// Returns a future that forwards imported transactions to the transaction networking protocol.
 pub async fn propagate_transaction_notifications<Block, ExPool>(
 	transaction_pool: Arc<ExPool>,
@@ -601,6 +663,8 @@ pub fn gen_rpc_module<TBl, TBackend, TCl, TRpc, TExPool>(
 	system_rpc_tx: TracingUnboundedSender<sc_rpc::system::Request<TBl>>,
 	config: &Configuration,
 	backend: Arc<TBackend>,
+	broadcast_store: Option<Arc<sc_rpc_spec_v2::transaction::BroadcastStore>>,
+	sessions: Option<sc_rpc_spec_v2::session::SessionManager>,
 	rpc_builder: &(dyn Fn(DenyUnsafe, SubscriptionTaskExecutor) -> Result<RpcModule<TRpc>, Error>),
 ) -> Result<RpcModule<()>, Error>
 where
@@ -648,14 +712,30 @@ where
 		client.clone(),
 		transaction_pool.clone(),
 		task_executor.clone(),
-	)
+		config.rpc_transaction_broadcast.clone(),
+	);
+	let transaction_broadcast_rpc_v2 = match broadcast_store {
+		Some(store) => transaction_broadcast_rpc_v2.with_store(store),
+		None => transaction_broadcast_rpc_v2,
+	}
 	.into_rpc();
 
-	let transaction_v2 = sc_rpc_spec_v2::transaction::Transaction::new(
+	let transaction_fee_rpc_v2 =
+		sc_rpc_spec_v2::transaction::TransactionFee::<TBl, _>::new(client.clone()).into_rpc();
+
+	let transaction_decode_rpc_v2 =
+		sc_rpc_spec_v2::transaction::TransactionDecode::<TBl, _>::new(client.clone()).into_rpc();
+
+	let transaction_v2 = sc_rpc_spec_v2::transaction::Transaction::<TBackend, _, _>::new(
 		client.clone(),
 		transaction_pool.clone(),
 		task_executor.clone(),
-	)
+		config.rpc_transaction.clone(),
+	);
+	let transaction_v2 = match sessions.clone() {
+		Some(sessions) => transaction_v2.with_sessions(sessions),
+		None => transaction_v2,
+	}
 	.into_rpc();
 
 	let chain_head_v2 = sc_rpc_spec_v2::chain_head::ChainHead::new(
@@ -663,10 +743,23 @@ where
 		backend.clone(),
 		task_executor.clone(),
 		// Defaults to sensible limits for the `ChainHead`.
-		sc_rpc_spec_v2::chain_head::ChainHeadConfig::default(),
-	)
+		config.rpc_chain_head.clone(),
+	);
+	let chain_head_v2 = match config.prometheus_registry() {
+		Some(registry) => chain_head_v2
+			.with_metrics(sc_rpc_spec_v2::chain_head::ChainHeadMetrics::register(registry)?),
+		None => chain_head_v2,
+	};
+	let chain_head_v2 = match sessions.clone() {
+		Some(sessions) => chain_head_v2.with_sessions(sessions),
+		None => chain_head_v2,
+	}
 	.into_rpc();
 
+	let runtime_upgrade_v2 =
+		sc_rpc_spec_v2::runtime_upgrade::RuntimeUpgrade::new(client.clone(), task_executor.clone())
+			.into_rpc();
+
 	// Part of the RPC v2 spec.
 	// An archive node that can respond to the `archive` RPC-v2 queries is a node with:
 	// - state pruning in archive mode: The storage of blocks is kept around
@@ -679,9 +772,9 @@ where
 		let archive_v2 = sc_rpc_spec_v2::archive::Archive::new(
 			client.clone(),
 			backend.clone(),
+			task_executor.clone(),
 			genesis_hash,
-			// Defaults to sensible limits for the `Archive`.
-			sc_rpc_spec_v2::archive::ArchiveConfig::default(),
+			config.rpc_archive.clone(),
 		)
 		.into_rpc();
 		rpc_api.merge(archive_v2).map_err(|e| Error::Application(e.into()))?;
@@ -709,7 +802,19 @@ where
 	rpc_api
 		.merge(transaction_broadcast_rpc_v2)
 		.map_err(|e| Error::Application(e.into()))?;
+	rpc_api
+		.merge(transaction_fee_rpc_v2)
+		.map_err(|e| Error::Application(e.into()))?;
+	rpc_api
+		.merge(transaction_decode_rpc_v2)
+		.map_err(|e| Error::Application(e.into()))?;
 	rpc_api.merge(chain_head_v2).map_err(|e| Error::Application(e.into()))?;
+	rpc_api.merge(runtime_upgrade_v2).map_err(|e| Error::Application(e.into()))?;
+	if let Some(sessions) = sessions {
+		let session_v2 =
+			sc_rpc_spec_v2::session::Session::new(sessions, task_executor.clone()).into_rpc();
+		rpc_api.merge(session_v2).map_err(|e| Error::Application(e.into()))?;
+	}
 
 	// Part of the old RPC spec.
 	rpc_api.merge(chain).map_err(|e| Error::Application(e.into()))?;
@@ -885,6 +990,11 @@ where
 			config.chain_spec.fork_id(),
 			client.clone(),
 		);
+		let handler = match config.network.light_client_server.clone() {
+			Some(server_config) =>
+				handler.with_cache(server_config, config.prometheus_registry())?,
+			None => handler,
+		};
 		spawn_handle.spawn("light-client-request-handler", Some("networking"), handler.run());
 		protocol_config
 	};
@@ -18,8 +18,12 @@
 
 //! Substrate Client
 
-use super::block_rules::{BlockRules, LookupResult as BlockLookupResult};
+use super::{
+	block_rules::{BlockRules, LookupResult as BlockLookupResult},
+	execution_witness::{ExecutionWitness, ExecutionWitnessConfig, ExecutionWitnessMode},
+};
 use crate::client::notification_pinning::NotificationPinningWorker;
+use codec::Decode;
 use log::{debug, info, trace, warn};
 use parking_lot::{Mutex, RwLock};
 use prometheus_endpoint::Registry;
@@ -200,6 +204,9 @@ pub struct ClientConfig<Block: BlockT> {
 	pub wasm_runtime_substitutes: HashMap<NumberFor<Block>, Vec<u8>>,
 	/// Enable recording of storage proofs during block import
 	pub enable_import_proof_recording: bool,
+	/// Capture of the execution witness of the blocks executed during import, `None` if
+	/// disabled.
+	pub execution_witness: Option<ExecutionWitnessConfig>,
 }
 
 impl<Block: BlockT> Default for ClientConfig<Block> {
@@ -211,6 +218,7 @@ impl<Block: BlockT> Default for ClientConfig<Block> {
 			no_genesis: false,
 			wasm_runtime_substitutes: HashMap::new(),
 			enable_import_proof_recording: false,
+			execution_witness: None,
 		}
 	}
 }
@@ -872,23 +880,43 @@ where
 						.proof_recorder()
 						.expect("Proof recording is enabled in the line above; qed.");
 					runtime_api.register_extension(ProofSizeExt::new(recorder));
+				} else if self.config.execution_witness.is_some() {
+					runtime_api.record_proof();
 				}
 
-				runtime_api.execute_block(
-					*parent_hash,
-					Block::new(import_block.header.clone(), body.clone()),
-				)?;
-
 				let state = self.backend.state_at(*parent_hash)?;
-				let gen_storage_changes = runtime_api
-					.into_storage_changes(&state, *parent_hash)
-					.map_err(sp_blockchain::Error::Storage)?;
-
-				if import_block.header.state_root() != &gen_storage_changes.transaction_storage_root
-				{
-					return Err(Error::InvalidStateRoot)
+				let result = runtime_api
+					.execute_block(
+						*parent_hash,
+						Block::new(import_block.header.clone(), body.clone()),
+					)
+					.map_err(Error::from)
+					.and_then(|()| {
+						let gen_storage_changes = runtime_api
+							.into_storage_changes(&state, *parent_hash)
+							.map_err(sp_blockchain::Error::Storage)?;
+
+						if import_block.header.state_root() !=
+							&gen_storage_changes.transaction_storage_root
+						{
+							return Err(Error::InvalidStateRoot)
+						}
+						Ok(gen_storage_changes)
+					});
+
+				if let Some(config) = &self.config.execution_witness {
+					if result.is_err() || config.mode == ExecutionWitnessMode::All {
+						self.write_execution_witness(
+							config,
+							Block::new(import_block.header.clone(), body.clone()),
+							&state,
+							runtime_api.extract_proof().unwrap_or_else(StorageProof::empty),
+							result.as_ref().err(),
+						);
+					}
 				}
-				Some(sc_consensus::StorageChanges::Changes(gen_storage_changes))
+
+				Some(sc_consensus::StorageChanges::Changes(result?))
 			},
 			// No block body, no storage changes
 			(true, None, None) => None,
@@ -899,6 +927,55 @@ where
 		Ok(PrepareStorageChangesResult::Import(storage_changes))
 	}
 
+	/// Write the execution witness of a block executed on the given state of its parent.
+	fn write_execution_witness(
+		&self,
+		config: &ExecutionWitnessConfig,
+		block: Block,
+		parent_state: &B::State,
+		proof: StorageProof,
+		error: Option<&Error>,
+	) {
+		let (number, hash) = (*block.header().number(), block.header().hash());
+		let storage = |key: &[u8]| {
+			parent_state
+				.storage(key)
+				.map_err(|e| format!("Failed to read the parent state: {e}"))
+		};
+		let result = (|| -> Result<PathBuf, String> {
+			let parent_header = self
+				.backend
+				.blockchain()
+				.header(*block.header().parent_hash())
+				.map_err(|e| e.to_string())?
+				.ok_or("Unknown parent block")?;
+			let runtime_code =
+				storage(well_known_keys::CODE)?.ok_or("Runtime code of the parent is missing")?;
+			let heap_pages = storage(well_known_keys::HEAP_PAGES)?
+				.and_then(|pages| u64::decode(&mut &pages[..]).ok());
+
+			ExecutionWitness::new(
+				block,
+				*parent_header.state_root(),
+				runtime_code,
+				heap_pages,
+				proof,
+				error.map(ToString::to_string),
+			)
+			.write(&config.dir)
+			.map_err(|e| e.to_string())
+		})();
+
+		match result {
+			Ok(path) => info!(
+				"💾 Wrote the execution witness of block #{number} ({hash}) to {}",
+				path.display(),
+			),
+			Err(e) =>
+				warn!("Failed to write the execution witness of block #{number} ({hash}): {e}"),
+		}
+	}
+
 	fn apply_finality_with_block_hash(
 		&self,
 		operation: &mut ClientImportOperation<Block, B>,
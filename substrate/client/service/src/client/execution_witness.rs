@@ -0,0 +1,144 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Capture of the execution witness of imported blocks, i.e. everything needed to re-execute a
+//! block without the database of the node which imported it.
+
+use codec::{Decode, Encode};
+use sp_core::traits::{CodeExecutor, RuntimeCode, WrappedRuntimeCode};
+use sp_runtime::traits::{Block as BlockT, Hash as HashT, HashingFor, Header as HeaderT};
+use sp_state_machine::OverlayedChanges;
+use sp_trie::StorageProof;
+use std::{
+	borrow::Cow,
+	fs,
+	path::{Path, PathBuf},
+};
+
+/// Version of the execution witness file format.
+const VERSION: u32 = 1;
+
+/// Blocks to capture the execution witness of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecutionWitnessMode {
+	/// Blocks whose execution failed, or resulted in another state root than the one of the
+	/// block header.
+	Failed,
+	/// All executed blocks.
+	All,
+}
+
+/// Configuration of the execution witness capture.
+#[derive(Debug, Clone)]
+pub struct ExecutionWitnessConfig {
+	/// Directory the witnesses are written to.
+	pub dir: PathBuf,
+	/// Blocks to capture the witness of.
+	pub mode: ExecutionWitnessMode,
+}
+
+/// Execution witness of a block.
+///
+/// Holds the block along with the storage proof of all the state of the parent block accessed
+/// while executing it, such that the block can be re-executed by [`ExecutionWitness::replay`].
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct ExecutionWitness<Block: BlockT> {
+	/// Version of the file format.
+	pub version: u32,
+	/// The executed block.
+	pub block: Block,
+	/// State root of the parent block.
+	pub parent_state_root: Block::Hash,
+	/// Runtime code of the parent block.
+	///
+	/// Wasm overrides and substitutes of the node which captured the witness aren't applied.
+	pub runtime_code: Vec<u8>,
+	/// Heap pages of the parent block.
+	pub heap_pages: Option<u64>,
+	/// Proof of the state accessed while executing the block.
+	pub proof: StorageProof,
+	/// The error the block execution failed with, if it did.
+	pub error: Option<String>,
+}
+
+impl<Block: BlockT> ExecutionWitness<Block> {
+	pub(crate) fn new(
+		block: Block,
+		parent_state_root: Block::Hash,
+		runtime_code: Vec<u8>,
+		heap_pages: Option<u64>,
+		proof: StorageProof,
+		error: Option<String>,
+	) -> Self {
+		Self { version: VERSION, block, parent_state_root, runtime_code, heap_pages, proof, error }
+	}
+
+	/// Write the witness to the given directory, returns the path of the written file.
+	pub fn write(&self, dir: &Path) -> std::io::Result<PathBuf> {
+		fs::create_dir_all(dir)?;
+		let header = self.block.header();
+		let path = dir.join(format!("{}-{:?}.witness", header.number(), header.hash()));
+		fs::write(&path, self.encode())?;
+		Ok(path)
+	}
+
+	/// Read a witness from the given file.
+	pub fn read(path: &Path) -> Result<Self, String> {
+		let content = fs::read(path)
+			.map_err(|e| format!("Failed to read execution witness `{}`: {e}", path.display()))?;
+		let witness = Self::decode(&mut &content[..])
+			.map_err(|e| format!("Invalid execution witness `{}`: {e}", path.display()))?;
+
+		if witness.version != VERSION {
+			return Err(format!(
+				"Unsupported execution witness version {}, expected {VERSION}",
+				witness.version,
+			))
+		}
+
+		Ok(witness)
+	}
+
+	/// Re-execute the block on the state of the witness.
+	///
+	/// Fails with the error of the block execution, e.g. when the runtime panics or the storage
+	/// root of the block doesn't match.
+	pub fn replay<E>(&self, executor: &E) -> Result<(), String>
+	where
+		E: CodeExecutor + Clone + 'static,
+	{
+		let code_fetcher = WrappedRuntimeCode(Cow::Borrowed(&self.runtime_code[..]));
+		let runtime_code = RuntimeCode {
+			code_fetcher: &code_fetcher,
+			heap_pages: self.heap_pages,
+			hash: HashingFor::<Block>::hash(&self.runtime_code).as_ref().to_vec(),
+		};
+
+		sp_state_machine::execution_proof_check::<HashingFor<Block>, _>(
+			self.parent_state_root,
+			self.proof.clone(),
+			&mut OverlayedChanges::default(),
+			executor,
+			"Core_execute_block",
+			&self.block.encode(),
+			&runtime_code,
+		)
+		.map(drop)
+		.map_err(|e| e.to_string())
+	}
+}
@@ -47,6 +47,7 @@
 mod block_rules;
 mod call_executor;
 mod client;
+mod execution_witness;
 mod notification_pinning;
 mod wasm_override;
 mod wasm_substitutes;
@@ -54,6 +55,7 @@ mod wasm_substitutes;
 pub use self::{
 	call_executor::LocalCallExecutor,
 	client::{Client, ClientConfig},
+	execution_witness::{ExecutionWitness, ExecutionWitnessConfig, ExecutionWitnessMode},
 };
 
 #[cfg(feature = "test-helpers")]
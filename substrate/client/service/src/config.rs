@@ -18,10 +18,12 @@
 
 //! Service configuration.
 
+pub use crate::client::{ExecutionWitnessConfig, ExecutionWitnessMode};
 pub use jsonrpsee::server::BatchRequestConfig as RpcBatchRequestConfig;
 use prometheus_endpoint::Registry;
 use sc_chain_spec::ChainSpec;
-pub use sc_client_db::{BlocksPruning, Database, DatabaseSource, PruningMode};
+pub use sc_client_db::{BlockCompression, BlocksPruning, Database, DatabaseSource, PruningMode};
+pub use sc_consensus::SignatureVerifier;
 pub use sc_executor::{WasmExecutionMethod, WasmtimeInstantiationStrategy};
 pub use sc_informant::OutputFormat;
 pub use sc_network::{
@@ -34,14 +36,47 @@ pub use sc_network::{
 	},
 	Multiaddr,
 };
+pub use sc_rpc::utils::{
+	BackpressurePolicy as RpcBackpressurePolicy,
+	SubscriptionBackpressure as RpcSubscriptionBackpressure,
+};
+pub use sc_rpc_server::{
+	ApiKeysConfig as RpcApiKeysConfig, AuditLogConfig as RpcAuditLogConfig,
+	AuthConfig as RpcAuthConfig, AuthKeys as RpcAuthKeys, BoxedRpcService,
+	CallCostConfig as RpcCallCostConfig, CircuitBreakerConfig as RpcCircuitBreakerConfig,
+	Http2Config as RpcHttp2Config, HttpCompressionConfig as RpcHttpCompressionConfig,
+	IpFilter as RpcIpFilter, IpNetwork as RpcIpNetwork, LoadShedConfig as RpcLoadShedConfig,
+	MethodAlias as RpcMethodAlias, MethodCors as RpcMethodCors, MethodCost as RpcMethodCost,
+	MethodFilter as RpcMethodFilter, MethodPattern as RpcMethodPattern,
+	MethodTimeout as RpcMethodTimeout, MetricsConfig as RpcMetricsConfig,
+	MiddlewareContext as RpcMiddlewareContext, QuotaConfig as RpcQuotaConfig,
+	QuotaLimit as RpcQuotaLimit, ResponseCacheConfig as RpcResponseCacheConfig,
+	RpcMiddlewareExtension, SocketConfig as RpcSocketConfig, SseConfig as RpcSseConfig,
+	TimeoutConfig as RpcTimeoutConfig, TlsCertificate as RpcTlsCertificate,
+	TlsConfig as RpcTlsConfig, WsCompressionConfig as RpcWsCompressionConfig,
+	WsPingConfig as RpcWsPingConfig, DEFAULT_CACHED_METHODS as RPC_DEFAULT_CACHED_METHODS,
+};
+pub use sc_rpc_spec_v2::{
+	archive::ArchiveConfig as RpcArchiveConfig,
+	chain_head::ChainHeadConfig as RpcChainHeadConfig,
+	session::SessionConfig as RpcSessionConfig,
+	transaction::{
+		TransactionBroadcastConfig as RpcTransactionBroadcastConfig,
+		TransactionConfig as RpcTransactionConfig,
+	},
+};
 pub use sc_telemetry::TelemetryEndpoints;
-pub use sc_transaction_pool::Options as TransactionPoolOptions;
+pub use sc_transaction_pool::{
+	Options as TransactionPoolOptions, RevalidationPriority as TransactionPoolRevalidationPriority,
+};
 use sp_core::crypto::SecretString;
 use std::{
 	io, iter,
 	net::SocketAddr,
 	num::NonZeroU32,
 	path::{Path, PathBuf},
+	sync::Arc,
+	time::Duration,
 };
 use tempfile::TempDir;
 
@@ -74,6 +109,8 @@ pub struct Configuration {
 	///
 	/// NOTE: only finalized blocks are subject for removal!
 	pub blocks_pruning: BlocksPruning,
+	/// Compression of the block bodies and justifications in the database.
+	pub block_compression: BlockCompression,
 	/// Chain configuration.
 	pub chain_spec: Box<dyn ChainSpec>,
 	/// Wasm execution method.
@@ -82,6 +119,11 @@ pub struct Configuration {
 	/// over on-chain runtimes when the spec version matches. Set to `None` to
 	/// disable overrides (default).
 	pub wasm_runtime_overrides: Option<PathBuf>,
+	/// Capture of the execution witness of the blocks executed during import. `None` if
+	/// disabled.
+	pub execution_witness: Option<ExecutionWitnessConfig>,
+	/// Verifier of the justification signatures of the blocks imported during sync.
+	pub justification_verifier: SignatureVerifier,
 	/// JSON-RPC server binding address.
 	pub rpc_addr: Option<SocketAddr>,
 	/// Maximum number of connections for JSON-RPC server.
@@ -108,6 +150,86 @@ pub struct Configuration {
 	pub rpc_batch_config: RpcBatchRequestConfig,
 	/// RPC rate limit per minute.
 	pub rpc_rate_limit: Option<NonZeroU32>,
+	/// IP addresses of peers exempt from the JSON-RPC rate limit and the per-IP connection
+	/// limit.
+	pub rpc_rate_limit_whitelisted_ips: Vec<RpcIpNetwork>,
+	/// TLS configuration of the JSON-RPC server. `None` if disabled.
+	pub rpc_tls: Option<RpcTlsConfig>,
+	/// IP filter of the JSON-RPC server. `None` if disabled.
+	pub rpc_ip_filter: Option<RpcIpFilter>,
+	/// WebSocket compression of the JSON-RPC server. `None` if disabled.
+	pub rpc_ws_compression: Option<RpcWsCompressionConfig>,
+	/// Authentication of the JSON-RPC server. `None` if disabled.
+	pub rpc_auth: Option<RpcAuthConfig>,
+	/// HTTP/2 support of the JSON-RPC server. `None` if disabled.
+	pub rpc_http2: Option<RpcHttp2Config>,
+	/// Audit log of a sample of the JSON-RPC calls. `None` if disabled.
+	pub rpc_audit_log: Option<RpcAuditLogConfig>,
+	/// Filter on the exposed JSON-RPC methods. `None` if all of them are exposed.
+	pub rpc_method_filter: Option<RpcMethodFilter>,
+	/// WebSocket keepalive of the JSON-RPC server.
+	pub rpc_ws_ping: RpcWsPingConfig,
+	/// Cache of the responses of idempotent JSON-RPC methods. `None` if disabled.
+	pub rpc_response_cache: Option<RpcResponseCacheConfig>,
+	/// CORS of specific JSON-RPC methods, overriding `rpc_cors` for them.
+	pub rpc_method_cors: Vec<RpcMethodCors>,
+	/// Rejection of JSON-RPC calls while the server is under pressure. `None` if disabled.
+	pub rpc_load_shed: Option<RpcLoadShedConfig>,
+	/// Additional JSON-RPC listeners, next to the one on `rpc_addr`.
+	pub rpc_listeners: Vec<RpcListener>,
+	/// Proxies trusted to report the address of the client of RPC requests.
+	pub rpc_trusted_proxies: Vec<RpcIpNetwork>,
+	/// Maximum number of concurrent JSON-RPC connections per peer IP address. `None` if unlimited.
+	pub rpc_max_connections_per_ip: Option<NonZeroU32>,
+	/// API keys granting the JSON-RPC requests carrying them the limits of a tier. `None` if
+	/// disabled.
+	pub rpc_api_keys: Option<RpcApiKeysConfig>,
+	/// Cost of the JSON-RPC calls paid from a budget of each IP address. `None` if disabled.
+	pub rpc_call_cost: Option<RpcCallCostConfig>,
+	/// Backpressure policies of the JSON-RPC subscriptions, the others are closed once their
+	/// buffer is full.
+	pub rpc_subscription_backpressure: Vec<RpcSubscriptionBackpressure>,
+	/// HTTP response compression of the JSON-RPC server. `None` if disabled.
+	pub rpc_http_compression: Option<RpcHttpCompressionConfig>,
+	/// Timeout of the RPC calls. `None` if disabled.
+	pub rpc_timeout: Option<RpcTimeoutConfig>,
+	/// Aliases of the RPC methods.
+	pub rpc_method_aliases: Vec<RpcMethodAlias>,
+	/// Write a structured access log record of every RPC call.
+	pub rpc_access_log: bool,
+	/// Server-Sent Events transport of the RPC subscriptions. `None` if disabled.
+	pub rpc_sse: Option<RpcSseConfig>,
+	/// TCP socket options of the RPC listeners.
+	pub rpc_socket: RpcSocketConfig,
+	/// Quotas on the JSON-RPC calls and bandwidth of each API key or IP address. `None` if
+	/// disabled.
+	pub rpc_quota: Option<RpcQuotaConfig>,
+	/// Log the JSON-RPC calls taking longer than this. `None` if disabled.
+	pub rpc_slow_call_threshold: Option<Duration>,
+	/// Rejection of the calls of the JSON-RPC methods failing or slowing down. `None` if disabled.
+	pub rpc_circuit_breaker: Option<RpcCircuitBreakerConfig>,
+	/// Configuration of the prometheus metrics of the JSON-RPC calls.
+	pub rpc_metrics: RpcMetricsConfig,
+	/// Serve the JSON-RPC calls and subscriptions within tracing spans.
+	pub rpc_call_spans: bool,
+	/// Deprecated JSON-RPC methods, whose calls are counted and warned about.
+	pub rpc_deprecated_methods: Vec<RpcMethodPattern>,
+	/// JSON-RPC middleware of the embedder, wrapping the services of the connections.
+	pub rpc_middleware_extensions: Vec<Arc<dyn RpcMiddlewareExtension>>,
+	/// Maximum number of `transaction_broadcast` operations persisted in the database and resumed
+	/// after a restart. `None` if disabled.
+	pub rpc_max_persisted_broadcasts: Option<u32>,
+	/// Limits of the `chainHead` subscriptions.
+	pub rpc_chain_head: RpcChainHeadConfig,
+	/// Limits of the `transactionWatch` subscriptions.
+	pub rpc_transaction: RpcTransactionConfig,
+	/// Limits of the `transaction_broadcast` operations.
+	pub rpc_transaction_broadcast: RpcTransactionBroadcastConfig,
+	/// Limits of the `archive` methods, served by archive nodes.
+	pub rpc_archive: RpcArchiveConfig,
+	/// Sessions resuming the `chainHead` and `transactionWatch` subscriptions from another
+	/// connection. `None` if disabled.
+	pub rpc_sessions: Option<RpcSessionConfig>,
 	/// Prometheus endpoint configuration. `None` if disabled.
 	pub prometheus_config: Option<PrometheusConfig>,
 	/// Telemetry service URL. `None` if disabled.
@@ -249,6 +371,7 @@ impl Configuration {
 			state_pruning: self.state_pruning.clone(),
 			source: self.database.clone(),
 			blocks_pruning: self.blocks_pruning,
+			block_compression: self.block_compression,
 		}
 	}
 }
@@ -271,6 +394,33 @@ impl Default for RpcMethods {
 	}
 }
 
+/// JSON-RPC listener with its own settings.
+///
+/// The settings of the server which aren't part of the listener, e.g. TLS or authentication,
+/// apply to all listeners.
+#[derive(Debug, Clone)]
+pub struct RpcListener {
+	/// Binding address.
+	pub addr: SocketAddr,
+	/// RPC methods to expose.
+	pub methods: RpcMethods,
+	/// CORS settings. `None` if all origins are allowed.
+	pub cors: Option<Vec<String>>,
+	/// CORS of specific methods, overriding `cors` for them.
+	pub method_cors: Vec<RpcMethodCors>,
+	/// Maximum number of connections.
+	pub max_connections: u32,
+	/// Maximum number of connections per peer IP address. `None` if unlimited.
+	pub max_connections_per_ip: Option<NonZeroU32>,
+	/// Rate limit per minute.
+	pub rate_limit: Option<NonZeroU32>,
+	/// IP addresses, or CIDR ranges, of peers exempt from the rate limit and the per-IP
+	/// connection limit.
+	pub rate_limit_whitelisted_ips: Vec<RpcIpNetwork>,
+	/// Filter on the exposed methods. `None` if all of them are exposed.
+	pub method_filter: Option<RpcMethodFilter>,
+}
+
 #[static_init::dynamic(drop, lazy)]
 static mut BASE_PATH_TEMP: Option<TempDir> = None;
 
@@ -32,6 +32,7 @@ pub mod client;
 #[cfg(not(feature = "test-helpers"))]
 mod client;
 mod metrics;
+mod onchain_peers;
 mod task_manager;
 
 use std::{collections::HashMap, net::SocketAddr};
@@ -70,7 +71,8 @@ pub use sc_chain_spec::{
 };
 
 pub use config::{
-	BasePath, BlocksPruning, Configuration, DatabaseSource, PruningMode, Role, RpcMethods, TaskType,
+	BasePath, BlockCompression, BlocksPruning, Configuration, DatabaseSource, PruningMode, Role,
+	RpcMethods, TaskType,
 };
 pub use sc_chain_spec::{
 	ChainSpec, ChainType, Extension as ChainSpecExtension, GenericChainSpec, NoExtension,
@@ -366,10 +368,14 @@ mod waiting {
 }
 
 /// Starts RPC servers.
+///
+/// The `response_cache` is expected to be cleared whenever the best or the finalized block
+/// changes.
 pub fn start_rpc_servers<R>(
 	config: &Configuration,
 	gen_rpc_module: R,
 	rpc_id_provider: Option<Box<dyn RpcSubscriptionIdProvider>>,
+	response_cache: Option<sc_rpc_server::ResponseCache>,
 ) -> Result<Box<dyn std::any::Any + Send + Sync>, error::Error>
 where
 	R: Fn(sc_rpc::DenyUnsafe) -> Result<RpcModule<()>, Error>,
@@ -382,30 +388,79 @@ where
 		}
 	}
 
-	// if binding the specified port failed then a random port is assigned by the OS.
-	let backup_port = |mut addr: SocketAddr| {
-		addr.set_port(0);
-		addr
+	let main_listener = config::RpcListener {
+		addr: config.rpc_addr.unwrap_or_else(|| ([127, 0, 0, 1], config.rpc_port).into()),
+		methods: config.rpc_methods,
+		cors: config.rpc_cors.clone(),
+		method_cors: config.rpc_method_cors.clone(),
+		max_connections: config.rpc_max_connections,
+		max_connections_per_ip: config.rpc_max_connections_per_ip,
+		rate_limit: config.rpc_rate_limit,
+		rate_limit_whitelisted_ips: config.rpc_rate_limit_whitelisted_ips.clone(),
+		method_filter: config.rpc_method_filter.clone(),
 	};
-
-	let addr = config.rpc_addr.unwrap_or_else(|| ([127, 0, 0, 1], config.rpc_port).into());
-	let backup_addr = backup_port(addr);
-	let metrics = sc_rpc_server::RpcMetrics::new(config.prometheus_registry())?;
+	let listeners = std::iter::once((main_listener, true))
+		.chain(config.rpc_listeners.iter().map(|listener| (listener.clone(), false)))
+		.map(|(listener, is_main)| {
+			let deny_unsafe = deny_unsafe(listener.addr, &listener.methods);
+			Ok(sc_rpc_server::ListenerConfig {
+				addr: listener.addr,
+				// The port of the main listener is picked by the OS if it's in use.
+				retry_random_port: is_main,
+				rpc_api: gen_rpc_module(deny_unsafe)?,
+				// Listing and closing connections is only allowed where unsafe methods are.
+				connections_api: matches!(deny_unsafe, sc_rpc::DenyUnsafe::No),
+				cors: listener.cors,
+				method_cors: listener.method_cors,
+				max_connections: listener.max_connections,
+				max_connections_per_ip: listener.max_connections_per_ip,
+				rate_limit: listener.rate_limit,
+				rate_limit_whitelisted_ips: listener.rate_limit_whitelisted_ips,
+				method_filter: listener.method_filter,
+			})
+		})
+		.collect::<Result<Vec<_>, Error>>()?;
+	let metrics =
+		sc_rpc_server::RpcMetrics::new(config.prometheus_registry(), config.rpc_metrics.clone())?;
+	sc_rpc::utils::configure_backpressure(
+		config.rpc_subscription_backpressure.clone(),
+		config.prometheus_registry(),
+	)?;
 
 	let server_config = sc_rpc_server::Config {
-		addrs: [addr, backup_addr],
+		listeners,
 		batch_config: config.rpc_batch_config,
-		max_connections: config.rpc_max_connections,
 		max_payload_in_mb: config.rpc_max_request_size,
 		max_payload_out_mb: config.rpc_max_response_size,
 		max_subs_per_conn: config.rpc_max_subs_per_conn,
 		message_buffer_capacity: config.rpc_message_buffer_capacity,
-		rpc_api: gen_rpc_module(deny_unsafe(addr, &config.rpc_methods))?,
+		ws_ping: config.rpc_ws_ping,
 		metrics,
 		id_provider: rpc_id_provider,
-		cors: config.rpc_cors.as_ref(),
 		tokio_handle: config.tokio_handle.clone(),
-		rate_limit: config.rpc_rate_limit,
+		tls: config.rpc_tls.clone(),
+		ip_filter: config.rpc_ip_filter.clone(),
+		ws_compression: config.rpc_ws_compression,
+		http_compression: config.rpc_http_compression,
+		auth: config.rpc_auth.clone(),
+		http2: config.rpc_http2,
+		audit_log: config.rpc_audit_log.clone(),
+		response_cache,
+		load_shed: config.rpc_load_shed.clone(),
+		trusted_proxies: config.rpc_trusted_proxies.clone(),
+		api_keys: config.rpc_api_keys.clone(),
+		call_cost: config.rpc_call_cost.clone(),
+		quota: config.rpc_quota.clone(),
+		timeout: config.rpc_timeout.clone(),
+		circuit_breaker: config.rpc_circuit_breaker.clone(),
+		method_aliases: config.rpc_method_aliases.clone(),
+		access_log: config.rpc_access_log,
+		slow_call_threshold: config.rpc_slow_call_threshold,
+		call_spans: config.rpc_call_spans,
+		deprecated_methods: config.rpc_deprecated_methods.clone(),
+		middleware_extensions: config.rpc_middleware_extensions.clone(),
+		sse: config.rpc_sse,
+		socket: config.rpc_socket,
 	};
 
 	// TODO: https://github.com/paritytech/substrate/issues/13773
@@ -20,8 +20,8 @@ use std::time::SystemTime;
 
 use crate::config::Configuration;
 use futures_timer::Delay;
-use prometheus_endpoint::{register, Gauge, GaugeVec, Opts, PrometheusError, Registry, U64};
-use sc_client_api::{ClientInfo, UsageProvider};
+use prometheus_endpoint::{register, Gauge, GaugeVec, Opts, PrometheusError, Registry, F64, U64};
+use sc_client_api::{ClientInfo, CompressionInfo, UsageProvider};
 use sc_network::{config::Role, NetworkStatus, NetworkStatusProvider};
 use sc_network_sync::{SyncStatus, SyncStatusProvider};
 use sc_telemetry::{telemetry, TelemetryHandle, SUBSTRATE_INFO};
@@ -43,6 +43,7 @@ struct PrometheusMetrics {
 	// I/O
 	database_cache: Gauge<U64>,
 	state_cache: Gauge<U64>,
+	database_compression_ratio: GaugeVec<F64>,
 }
 
 impl PrometheusMetrics {
@@ -116,6 +117,17 @@ impl PrometheusMetrics {
 				Gauge::new("substrate_state_cache_bytes", "State cache size in bytes")?,
 				registry,
 			)?,
+			database_compression_ratio: register(
+				GaugeVec::new(
+					Opts::new(
+						"substrate_database_compression_ratio",
+						"Size of the values of a database column written compressed, relative to \
+						their uncompressed size",
+					),
+					&["column"],
+				)?,
+				registry,
+			)?,
 		})
 	}
 }
@@ -252,6 +264,14 @@ impl MetricsService {
 			if let Some(info) = info.usage.as_ref() {
 				metrics.database_cache.set(info.memory.database_cache.as_bytes() as u64);
 				metrics.state_cache.set(info.memory.state_cache.as_bytes() as u64);
+				for (column, compression) in [
+					("bodies", &info.io.body_compression),
+					("justifications", &info.io.justification_compression),
+				] {
+					if let Some(ratio) = compression_ratio(compression) {
+						metrics.database_compression_ratio.with_label_values(&[column]).set(ratio);
+					}
+				}
 			}
 		}
 
@@ -296,3 +316,10 @@ impl MetricsService {
 		}
 	}
 }
+
+/// Compressed size of the values of a database column relative to their uncompressed size, if any
+/// value was written compressed.
+fn compression_ratio(info: &CompressionInfo) -> Option<f64> {
+	(info.uncompressed_bytes > 0)
+		.then(|| info.compressed_bytes as f64 / info.uncompressed_bytes as f64)
+}
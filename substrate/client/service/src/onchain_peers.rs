@@ -0,0 +1,149 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Bootnodes and reserved peers published on-chain.
+//!
+//! With `--onchain-peers`, the node periodically reads the peers from the `BootnodesApi` runtime
+//! api of its best block. The bootnodes are added to the known addresses and the reserved peers
+//! to the reserved set, such that the chain can rotate them without every node being restarted.
+
+use codec::Decode;
+use futures_timer::Delay;
+use log::{debug, info, warn};
+use sc_client_api::{CallExecutor, ExecutorProvider, UsageProvider};
+use sc_network::{config::MultiaddrWithPeerId, service::traits::NetworkService};
+use sc_network_types::PeerId;
+use sp_api::{CallApiAt, RuntimeApiInfo};
+use sp_bootnodes::BootnodesApi;
+use sp_core::traits::CallContext;
+use sp_runtime::traits::Block as BlockT;
+use std::{collections::HashSet, sync::Arc, time::Duration};
+
+const LOG_TARGET: &str = "onchain-peers";
+
+/// Interval between two reads of the peers.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// Keep the bootnodes and the reserved peers of the node in sync with the chain.
+pub(crate) async fn run<Block, Client>(client: Arc<Client>, network: Arc<dyn NetworkService>)
+where
+	Block: BlockT,
+	Client: ExecutorProvider<Block> + CallApiAt<Block> + UsageProvider<Block>,
+{
+	let mut timer = Delay::new(Duration::from_secs(0));
+	// The reserved peers added by this task, removed once they are not published anymore.
+	let mut reserved_peers = HashSet::<PeerId>::new();
+
+	loop {
+		(&mut timer).await;
+		timer.reset(REFRESH_INTERVAL);
+
+		let best_hash = client.usage_info().chain.best_hash;
+		match client.runtime_version_at(best_hash) {
+			Ok(version) if version.has_api_with(&<dyn BootnodesApi<Block>>::ID, |v| v >= 1) => {},
+			Ok(_) => {
+				debug!(target: LOG_TARGET, "The runtime at {best_hash:?} has no `BootnodesApi`");
+				continue
+			},
+			Err(error) => {
+				warn!(target: LOG_TARGET, "Failed to get the runtime version at {best_hash:?}: {error}");
+				continue
+			},
+		}
+
+		let (Some(bootnodes), Some(reserved)) = (
+			fetch_peers(&*client, best_hash, "BootnodesApi_bootnodes"),
+			fetch_peers(&*client, best_hash, "BootnodesApi_reserved_peers"),
+		) else {
+			continue
+		};
+
+		for MultiaddrWithPeerId { multiaddr, peer_id } in &bootnodes {
+			network.add_known_address(*peer_id, multiaddr.clone());
+		}
+
+		let mut added = 0;
+		let published = reserved.iter().map(|peer| peer.peer_id).collect::<HashSet<_>>();
+		for peer in reserved {
+			let peer_id = peer.peer_id;
+			match network.add_reserved_peer(peer) {
+				Ok(()) => {
+					added += usize::from(reserved_peers.insert(peer_id));
+				},
+				Err(error) => {
+					warn!(target: LOG_TARGET, "Failed to add the reserved peer {peer_id}: {error}")
+				},
+			}
+		}
+		let mut removed = 0;
+		reserved_peers.retain(|peer_id| {
+			let keep = published.contains(peer_id);
+			if !keep {
+				network.remove_reserved_peer(*peer_id);
+				removed += 1;
+			}
+			keep
+		});
+
+		info!(
+			target: LOG_TARGET,
+			"🔗 {} on-chain bootnodes, {} on-chain reserved peers ({added} added, {removed} removed)",
+			bootnodes.len(),
+			published.len(),
+		);
+	}
+}
+
+/// Call the given `BootnodesApi` method, skipping the addresses that can't be parsed.
+fn fetch_peers<Block, Client>(
+	client: &Client,
+	hash: Block::Hash,
+	method: &str,
+) -> Option<Vec<MultiaddrWithPeerId>>
+where
+	Block: BlockT,
+	Client: ExecutorProvider<Block>,
+{
+	let result = client
+		.executor()
+		.call(hash, method, &[], CallContext::Offchain)
+		.map_err(|error| error.to_string())
+		.and_then(|output| {
+			<Vec<Vec<u8>>>::decode(&mut &output[..]).map_err(|error| error.to_string())
+		});
+	let addresses = match result {
+		Ok(addresses) => addresses,
+		Err(error) => {
+			warn!(target: LOG_TARGET, "Failed to call `{method}` at {hash:?}: {error}");
+			return None
+		},
+	};
+
+	let peers =
+		addresses
+			.into_iter()
+			.filter_map(|address| {
+				let peer = String::from_utf8(address).map_err(|error| error.to_string()).and_then(
+					|address| address.parse::<MultiaddrWithPeerId>().map_err(|e| e.to_string()),
+				);
+				peer.map_err(|error| debug!(target: LOG_TARGET, "Invalid on-chain peer: {error}"))
+					.ok()
+			})
+			.collect();
+	Some(peers)
+}
@@ -237,9 +237,12 @@ fn node_config<
 		trie_cache_maximum_size: Some(16 * 1024 * 1024),
 		state_pruning: Default::default(),
 		blocks_pruning: BlocksPruning::KeepFinalized,
+		block_compression: Default::default(),
 		chain_spec: Box::new((*spec).clone()),
 		wasm_method: Default::default(),
 		wasm_runtime_overrides: Default::default(),
+		execution_witness: None,
+		justification_verifier: Default::default(),
 		rpc_addr: Default::default(),
 		rpc_max_connections: Default::default(),
 		rpc_cors: None,
@@ -252,6 +255,43 @@ fn node_config<
 		rpc_message_buffer_capacity: Default::default(),
 		rpc_batch_config: RpcBatchRequestConfig::Unlimited,
 		rpc_rate_limit: None,
+		rpc_tls: None,
+		rpc_ip_filter: None,
+		rpc_ws_compression: None,
+		rpc_auth: None,
+		rpc_http2: None,
+		rpc_rate_limit_whitelisted_ips: Default::default(),
+		rpc_audit_log: None,
+		rpc_method_filter: None,
+		rpc_ws_ping: Default::default(),
+		rpc_response_cache: None,
+		rpc_method_cors: Vec::new(),
+		rpc_load_shed: None,
+		rpc_listeners: Vec::new(),
+		rpc_trusted_proxies: Vec::new(),
+		rpc_max_connections_per_ip: None,
+		rpc_api_keys: None,
+		rpc_call_cost: None,
+		rpc_subscription_backpressure: Vec::new(),
+		rpc_http_compression: None,
+		rpc_timeout: None,
+		rpc_method_aliases: Vec::new(),
+		rpc_access_log: false,
+		rpc_sse: None,
+		rpc_socket: Default::default(),
+		rpc_quota: None,
+		rpc_slow_call_threshold: None,
+		rpc_circuit_breaker: None,
+		rpc_metrics: Default::default(),
+		rpc_call_spans: false,
+		rpc_deprecated_methods: Default::default(),
+		rpc_middleware_extensions: Default::default(),
+		rpc_max_persisted_broadcasts: None,
+		rpc_chain_head: Default::default(),
+		rpc_transaction: Default::default(),
+		rpc_transaction_broadcast: Default::default(),
+		rpc_archive: Default::default(),
+		rpc_sessions: None,
 		prometheus_config: None,
 		telemetry_endpoints: None,
 		default_heap_pages: None,
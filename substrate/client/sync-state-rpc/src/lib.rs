@@ -38,6 +38,9 @@
 //!
 //! If the [`LightSyncStateExtension`] is not added as an extension to the chain spec,
 //! the [`SyncState`] will fail at instantiation.
+//!
+//! The [`SyncState`] also implements [`LightSyncStateProvider`], to report the light sync state
+//! through the `chainSpec` RPC methods of `sc-rpc-spec-v2`.
 
 #![deny(unused_crate_dependencies)]
 
@@ -51,6 +54,7 @@ use jsonrpsee::{
 
 use sc_client_api::StorageData;
 use sc_consensus_babe::{BabeWorkerHandle, Error as BabeError};
+use sc_rpc_spec_v2::chain_spec::LightSyncStateProvider;
 use sp_blockchain::HeaderBackend;
 use sp_runtime::traits::{Block as BlockT, NumberFor};
 
@@ -140,6 +144,17 @@ pub struct SyncState<Block: BlockT, Client> {
 	babe_worker_handle: BabeWorkerHandle<Block>,
 }
 
+impl<Block: BlockT, Client> Clone for SyncState<Block, Client> {
+	fn clone(&self) -> Self {
+		Self {
+			chain_spec: self.chain_spec.cloned_box(),
+			client: self.client.clone(),
+			shared_authority_set: self.shared_authority_set.clone(),
+			babe_worker_handle: self.babe_worker_handle.clone(),
+		}
+	}
+}
+
 impl<Block, Client> SyncState<Block, Client>
 where
 	Block: BlockT,
@@ -210,3 +225,15 @@ where
 		serde_json::from_str(&json_str).map_err(|e| Error::<Block>::JsonRpc(e.to_string()))
 	}
 }
+
+#[async_trait]
+impl<Block, Backend> LightSyncStateProvider for SyncState<Block, Backend>
+where
+	Block: BlockT,
+	Backend: HeaderBackend<Block> + sc_client_api::AuxStore + 'static,
+{
+	async fn light_sync_state(&self) -> Result<serde_json::Value, String> {
+		let current_sync_state = self.build_sync_state().await.map_err(|e| e.to_string())?;
+		serde_json::to_value(&current_sync_state).map_err(|e| e.to_string())
+	}
+}
@@ -0,0 +1,69 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use opentelemetry::KeyValue;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::{trace, Resource};
+use std::sync::OnceLock;
+use tracing::Subscriber;
+use tracing_subscriber::{registry::LookupSpan, Layer};
+
+/// Runtime the spans are exported on.
+///
+/// The logger is initialized before the runtime of the node, and outlives it.
+static EXPORT_RUNTIME: OnceLock<tokio::runtime::Runtime> = OnceLock::new();
+
+/// Export of the tracing spans to an OpenTelemetry collector over OTLP/gRPC.
+#[derive(Debug, Clone)]
+pub struct OtlpExport {
+	/// URL of the collector.
+	pub endpoint: String,
+	/// Name of the service the spans are reported for.
+	pub service_name: String,
+}
+
+/// Create a `Layer` exporting the spans in batches to the collector of `export`.
+pub fn otlp_layer<S>(export: &OtlpExport) -> crate::logging::Result<impl Layer<S>>
+where
+	S: Subscriber + for<'a> LookupSpan<'a>,
+{
+	let runtime = match EXPORT_RUNTIME.get() {
+		Some(runtime) => runtime,
+		None => {
+			let runtime = tokio::runtime::Builder::new_multi_thread()
+				.worker_threads(1)
+				.thread_name("otlp-export")
+				.enable_all()
+				.build()?;
+			EXPORT_RUNTIME.get_or_init(|| runtime)
+		},
+	};
+	// The exporter spawns its tasks on the current runtime.
+	let _guard = runtime.enter();
+
+	let tracer = opentelemetry_otlp::new_pipeline()
+		.tracing()
+		.with_exporter(opentelemetry_otlp::new_exporter().tonic().with_endpoint(&export.endpoint))
+		.with_trace_config(trace::config().with_resource(Resource::new([KeyValue::new(
+			"service.name",
+			export.service_name.clone(),
+		)])))
+		.install_batch(opentelemetry_sdk::runtime::Tokio)?;
+
+	Ok(tracing_opentelemetry::layer().with_tracer(tracer))
+}
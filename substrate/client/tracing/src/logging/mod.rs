@@ -66,6 +66,7 @@ pub enum Error {
 	SetGlobalDefaultError(#[from] tracing::subscriber::SetGlobalDefaultError),
 	DirectiveParseError(#[from] tracing_subscriber::filter::ParseError),
 	SetLoggerError(#[from] tracing_log::log_tracer::SetLoggerError),
+	OtlpError(#[from] opentelemetry::trace::TraceError),
 }
 
 macro_rules! enable_log_reloading {
@@ -205,6 +206,7 @@ pub struct LoggerBuilder {
 	log_reloading: bool,
 	force_colors: Option<bool>,
 	detailed_output: bool,
+	otlp_export: Option<OtlpExport>,
 }
 
 impl LoggerBuilder {
@@ -217,6 +219,7 @@ impl LoggerBuilder {
 			log_reloading: false,
 			force_colors: None,
 			detailed_output: false,
+			otlp_export: None,
 		}
 	}
 
@@ -262,6 +265,19 @@ impl LoggerBuilder {
 		self
 	}
 
+	/// Export the spans to the OpenTelemetry collector at `endpoint`, over OTLP/gRPC.
+	///
+	/// The spans exported are the ones enabled by the log filter.
+	pub fn with_otlp_export<E: Into<String>, N: Into<String>>(
+		&mut self,
+		endpoint: E,
+		service_name: N,
+	) -> &mut Self {
+		self.otlp_export =
+			Some(OtlpExport { endpoint: endpoint.into(), service_name: service_name.into() });
+		self
+	}
+
 	/// Initialize the global logger
 	///
 	/// This sets various global logging and tracing instances and thus may only be called once.
@@ -282,7 +298,8 @@ impl LoggerBuilder {
 					.into_iter()
 					.for_each(|profiler| profiling.add_handler(profiler));
 
-				tracing::subscriber::set_global_default(subscriber.with(profiling))?;
+				let otlp = self.otlp_export.as_ref().map(otlp_layer).transpose()?;
+				tracing::subscriber::set_global_default(subscriber.with(profiling).with(otlp))?;
 
 				Ok(())
 			} else {
@@ -300,7 +317,8 @@ impl LoggerBuilder {
 					.into_iter()
 					.for_each(|profiler| profiling.add_handler(profiler));
 
-				tracing::subscriber::set_global_default(subscriber.with(profiling))?;
+				let otlp = self.otlp_export.as_ref().map(otlp_layer).transpose()?;
+				tracing::subscriber::set_global_default(subscriber.with(profiling).with(otlp))?;
 
 				Ok(())
 			}
@@ -313,7 +331,8 @@ impl LoggerBuilder {
 				|builder| enable_log_reloading!(builder),
 			)?;
 
-			tracing::subscriber::set_global_default(subscriber)?;
+			let otlp = self.otlp_export.as_ref().map(otlp_layer).transpose()?;
+			tracing::subscriber::set_global_default(subscriber.with(otlp))?;
 
 			Ok(())
 		} else {
@@ -325,7 +344,8 @@ impl LoggerBuilder {
 				|builder| builder,
 			)?;
 
-			tracing::subscriber::set_global_default(subscriber)?;
+			let otlp = self.otlp_export.as_ref().map(otlp_layer).transpose()?;
+			tracing::subscriber::set_global_default(subscriber.with(otlp))?;
 
 			Ok(())
 		}
@@ -21,6 +21,7 @@
 use sp_runtime::transaction_validity::{
 	InvalidTransaction, TransactionPriority as Priority, UnknownTransaction,
 };
+use std::time::Duration;
 
 /// Transaction pool result.
 pub type Result<T> = std::result::Result<T, Error>;
@@ -43,7 +44,10 @@ pub enum Error {
 	NoTagsProvided,
 
 	#[error("Transaction temporarily Banned")]
-	TemporarilyBanned,
+	TemporarilyBanned {
+		/// The time until the ban of the transaction expires.
+		expires_in: Duration,
+	},
 
 	#[error("[{0:?}] Already imported")]
 	AlreadyImported(Box<dyn std::any::Any + Send + Sync>),
@@ -80,7 +84,7 @@ impl Error {
 		match self {
 			// An invalid transaction is temporarily banned, however it can
 			// become valid at a later time.
-			Error::TemporarilyBanned |
+			Error::TemporarilyBanned { .. } |
 			// The pool is full at the moment.
 			Error::ImmediatelyDropped |
 			// The block id is not known to the pool.
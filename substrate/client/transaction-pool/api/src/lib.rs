@@ -321,6 +321,12 @@ pub trait TransactionPool: Send + Sync {
 
 	/// Return specific ready transaction by hash, if there is one.
 	fn ready_transaction(&self, hash: &TxHash<Self>) -> Option<Arc<Self::InPoolTransaction>>;
+
+	// *** RPC
+	/// Revalidate all the ready transactions against the best block right away.
+	///
+	/// Returns the number of transactions scheduled for revalidation.
+	fn revalidate_ready(&self) -> PoolFuture<usize, Self::Error>;
 }
 
 /// An iterator of ready transactions.
@@ -82,6 +82,11 @@ where
 		EnactmentState { recent_best_block, recent_finalized_block }
 	}
 
+	/// Returns the recent best block.
+	pub fn recent_best_block(&self) -> Block::Hash {
+		self.recent_best_block
+	}
+
 	/// Returns the recently finalized block.
 	pub fn recent_finalized_block(&self) -> Block::Hash {
 		self.recent_finalized_block
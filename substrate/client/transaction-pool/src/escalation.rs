@@ -0,0 +1,383 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Automatic tip escalation of the node's own stuck extrinsics.
+//!
+//! The node submits some extrinsics on its own, e.g. the heartbeats or the election solutions of
+//! its validator. When the fees spike these may stay in the pool for many blocks. The task
+//! spawned with [`run`] monitors the extrinsics selected by a [`TipEscalator`] and, when one is
+//! not included in a best block after [`EscalationConfig::stuck_after`] blocks, asks the
+//! escalator for a replacement with a higher tip and submits it to the pool.
+//!
+//! The tip is part of the signed payload, so only the owner of the signing keys can rebuild the
+//! extrinsic: the escalator is provided by the node. The replacement must use the same nonce,
+//! such that it replaces the stuck extrinsic in the pool thanks to its higher priority.
+
+use crate::LOG_TARGET;
+use futures::{future::ready, prelude::*};
+use prometheus_endpoint::{register, Counter, PrometheusError, Registry, U64};
+use sc_client_api::{BlockBackend, BlockchainEvents};
+use sc_transaction_pool_api::{InPoolTransaction, TransactionPool, TransactionSource, TxHash};
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT, NumberFor, Saturating};
+use std::{
+	collections::{HashMap, HashSet},
+	sync::Arc,
+};
+
+/// Rebuilds the monitored extrinsics with a higher tip.
+pub trait TipEscalator<Block: BlockT>: Send + Sync {
+	/// Whether the given extrinsic, found in the pool, is monitored.
+	///
+	/// Typically the operational extrinsics signed by the keys of the node.
+	fn is_monitored(&self, extrinsic: &Block::Extrinsic) -> bool;
+
+	/// Rebuild the given stuck extrinsic for the escalation `attempt`, starting at 1.
+	///
+	/// The replacement must use the same nonce and a higher tip than the stuck extrinsic.
+	/// Returns `None` to stop escalating it.
+	fn escalate(
+		&self,
+		at: Block::Hash,
+		extrinsic: &Block::Extrinsic,
+		attempt: u32,
+	) -> Option<Block::Extrinsic>;
+}
+
+/// The bounds of the tip escalation.
+#[derive(Debug, Clone)]
+pub struct EscalationConfig {
+	/// The number of best blocks after which a monitored extrinsic is considered stuck.
+	pub stuck_after: u32,
+	/// The maximum number of escalations of a single extrinsic.
+	pub max_attempts: u32,
+	/// The maximum number of extrinsics monitored at once.
+	pub max_tracked: usize,
+}
+
+impl Default for EscalationConfig {
+	fn default() -> Self {
+		Self { stuck_after: 5, max_attempts: 3, max_tracked: 256 }
+	}
+}
+
+/// Tip escalation Prometheus metrics.
+struct Metrics {
+	monitored: Counter<U64>,
+	included: Counter<U64>,
+	escalated: Counter<U64>,
+	abandoned: Counter<U64>,
+}
+
+impl Metrics {
+	fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		Ok(Self {
+			monitored: register(
+				Counter::new(
+					"substrate_sub_txpool_escalation_monitored_transactions",
+					"Total number of own transactions monitored for tip escalation",
+				)?,
+				registry,
+			)?,
+			included: register(
+				Counter::new(
+					"substrate_sub_txpool_escalation_included_transactions",
+					"Total number of monitored transactions included in a best block",
+				)?,
+				registry,
+			)?,
+			escalated: register(
+				Counter::new(
+					"substrate_sub_txpool_escalation_escalated_transactions",
+					"Total number of stuck transactions resubmitted with a higher tip",
+				)?,
+				registry,
+			)?,
+			abandoned: register(
+				Counter::new(
+					"substrate_sub_txpool_escalation_abandoned_transactions",
+					"Total number of stuck transactions no longer escalated",
+				)?,
+				registry,
+			)?,
+		})
+	}
+}
+
+/// A monitored extrinsic.
+#[derive(Debug, Clone, PartialEq)]
+struct Tracked<Extrinsic, Number> {
+	extrinsic: Extrinsic,
+	/// The best block number when the extrinsic was submitted.
+	submitted_at: Number,
+	/// The number of escalations of the extrinsic.
+	attempt: u32,
+}
+
+/// The monitored extrinsics leaving the tracker on a new best block.
+#[derive(Debug, PartialEq)]
+struct BestBlockOutcome<Extrinsic, Number> {
+	/// The number of extrinsics included in the block.
+	included: usize,
+	/// The number of extrinsics no longer in the pool, without being included in a best block.
+	dropped: usize,
+	/// The stuck extrinsics.
+	stuck: Vec<Tracked<Extrinsic, Number>>,
+}
+
+/// The bookkeeping of the monitored extrinsics.
+struct Tracker<Hash, Extrinsic, Number> {
+	config: EscalationConfig,
+	tracked: HashMap<Hash, Tracked<Extrinsic, Number>>,
+}
+
+impl<Hash, Extrinsic, Number> Tracker<Hash, Extrinsic, Number>
+where
+	Hash: Eq + std::hash::Hash + Clone,
+	Number: Copy + PartialOrd + Saturating + From<u32>,
+{
+	fn new(config: EscalationConfig) -> Self {
+		Self { config, tracked: HashMap::new() }
+	}
+
+	fn contains(&self, hash: &Hash) -> bool {
+		self.tracked.contains_key(hash)
+	}
+
+	/// Start monitoring the given extrinsic, unless the maximum is reached.
+	fn track(&mut self, hash: Hash, tracked: Tracked<Extrinsic, Number>) -> bool {
+		if self.tracked.len() >= self.config.max_tracked {
+			return false
+		}
+		self.tracked.insert(hash, tracked);
+		true
+	}
+
+	/// Update the monitored extrinsics on a new best block.
+	fn on_best_block(
+		&mut self,
+		number: Number,
+		included: &HashSet<Hash>,
+		in_pool: impl Fn(&Hash) -> bool,
+	) -> BestBlockOutcome<Extrinsic, Number> {
+		let stuck_after = Number::from(self.config.stuck_after);
+		let mut outcome = BestBlockOutcome { included: 0, dropped: 0, stuck: Vec::new() };
+
+		let hashes = self.tracked.keys().cloned().collect::<Vec<_>>();
+		for hash in hashes {
+			if included.contains(&hash) {
+				self.tracked.remove(&hash);
+				outcome.included += 1;
+			} else if !in_pool(&hash) {
+				self.tracked.remove(&hash);
+				outcome.dropped += 1;
+			} else if self
+				.tracked
+				.get(&hash)
+				.map_or(false, |tracked| number.saturating_sub(tracked.submitted_at) >= stuck_after)
+			{
+				outcome.stuck.extend(self.tracked.remove(&hash));
+			}
+		}
+
+		outcome
+	}
+}
+
+/// Monitor the extrinsics selected by the `escalator` and escalate the tip of the stuck ones.
+///
+/// The future ends when the notification streams of the pool or of the client end.
+pub async fn run<Block, Client, Pool>(
+	client: Arc<Client>,
+	pool: Arc<Pool>,
+	escalator: Arc<dyn TipEscalator<Block>>,
+	config: EscalationConfig,
+	registry: Option<Registry>,
+) where
+	Block: BlockT,
+	Client: BlockchainEvents<Block> + BlockBackend<Block> + HeaderBackend<Block> + 'static,
+	Pool: TransactionPool<Block = Block> + 'static,
+{
+	let metrics = registry.and_then(|registry| {
+		Metrics::register(&registry)
+			.map_err(|err| {
+				log::warn!(target: LOG_TARGET, "Failed to register prometheus metrics: {}", err);
+			})
+			.ok()
+	});
+	let report = |do_this: &dyn Fn(&Metrics)| {
+		if let Some(metrics) = metrics.as_ref() {
+			do_this(metrics);
+		}
+	};
+
+	let max_attempts = config.max_attempts;
+	let mut tracker = Tracker::<TxHash<Pool>, Block::Extrinsic, NumberFor<Block>>::new(config);
+	let mut imported = pool.import_notification_stream().fuse();
+	let mut best_blocks = client
+		.import_notification_stream()
+		.filter(|notification| ready(notification.is_new_best))
+		.fuse();
+
+	loop {
+		futures::select! {
+			hash = imported.next() => {
+				let Some(hash) = hash else { return };
+				if tracker.contains(&hash) {
+					continue
+				}
+				let Some(transaction) = pool.ready_transaction(&hash) else { continue };
+				if !escalator.is_monitored(transaction.data()) {
+					continue
+				}
+
+				let tracked = Tracked {
+					extrinsic: transaction.data().clone(),
+					submitted_at: client.info().best_number,
+					attempt: 0,
+				};
+				if tracker.track(hash, tracked) {
+					report(&|metrics| metrics.monitored.inc());
+				} else {
+					log::debug!(
+						target: LOG_TARGET,
+						"[{:?}] Not monitored for tip escalation: too many monitored transactions",
+						hash,
+					);
+				}
+			},
+			notification = best_blocks.next() => {
+				let Some(notification) = notification else { return };
+				let at = notification.hash;
+
+				let included = match client.block_body(at) {
+					Ok(body) => body
+						.unwrap_or_default()
+						.iter()
+						.map(|extrinsic| pool.hash_of(extrinsic))
+						.collect::<HashSet<_>>(),
+					Err(error) => {
+						log::debug!(target: LOG_TARGET, "Failed to read the body of {at:?}: {error}");
+						HashSet::new()
+					},
+				};
+				let outcome = tracker.on_best_block(
+					*notification.header.number(),
+					&included,
+					|hash| pool.ready_transaction(hash).is_some(),
+				);
+				report(&|metrics| metrics.included.inc_by(outcome.included as u64));
+				if outcome.dropped > 0 {
+					log::debug!(
+						target: LOG_TARGET,
+						"{} monitored transactions left the pool without being included",
+						outcome.dropped,
+					);
+				}
+
+				for stuck in outcome.stuck {
+					let attempt = stuck.attempt + 1;
+					let replacement = (attempt <= max_attempts)
+						.then(|| escalator.escalate(at, &stuck.extrinsic, attempt))
+						.flatten();
+					let Some(replacement) = replacement else {
+						report(&|metrics| metrics.abandoned.inc());
+						continue
+					};
+
+					match pool.submit_one(at, TransactionSource::Local, replacement.clone()).await {
+						Ok(hash) => {
+							log::debug!(
+								target: LOG_TARGET,
+								"[{:?}] Resubmitted a stuck transaction with a higher tip (attempt {})",
+								hash,
+								attempt,
+							);
+							report(&|metrics| metrics.escalated.inc());
+							tracker.track(
+								hash,
+								Tracked {
+									extrinsic: replacement,
+									submitted_at: *notification.header.number(),
+									attempt,
+								},
+							);
+						},
+						Err(error) => {
+							log::warn!(
+								target: LOG_TARGET,
+								"Failed to resubmit a stuck transaction with a higher tip: {error}",
+							);
+							report(&|metrics| metrics.abandoned.inc());
+						},
+					}
+				}
+			},
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	type TestTracker = Tracker<u64, &'static str, u64>;
+
+	fn tracked(extrinsic: &'static str, submitted_at: u64) -> Tracked<&'static str, u64> {
+		Tracked { extrinsic, submitted_at, attempt: 0 }
+	}
+
+	#[test]
+	fn included_and_dropped_transactions_are_forgotten() {
+		let mut tracker = TestTracker::new(Default::default());
+		assert!(tracker.track(1, tracked("included", 10)));
+		assert!(tracker.track(2, tracked("dropped", 10)));
+		assert!(tracker.track(3, tracked("pending", 10)));
+
+		let outcome = tracker.on_best_block(11, &HashSet::from([1]), |hash| *hash != 2);
+		assert_eq!((outcome.included, outcome.dropped), (1, 1));
+		assert!(outcome.stuck.is_empty());
+		assert!(!tracker.contains(&1));
+		assert!(!tracker.contains(&2));
+		assert!(tracker.contains(&3));
+	}
+
+	#[test]
+	fn transactions_are_stuck_after_the_configured_number_of_blocks() {
+		let config = EscalationConfig { stuck_after: 3, ..Default::default() };
+		let mut tracker = TestTracker::new(config);
+		assert!(tracker.track(1, tracked("stuck", 10)));
+
+		let outcome = tracker.on_best_block(12, &HashSet::new(), |_| true);
+		assert!(outcome.stuck.is_empty());
+		assert!(tracker.contains(&1));
+
+		let outcome = tracker.on_best_block(13, &HashSet::new(), |_| true);
+		assert_eq!(outcome.stuck, vec![tracked("stuck", 10)]);
+		assert!(!tracker.contains(&1));
+	}
+
+	#[test]
+	fn the_number_of_monitored_transactions_is_bounded() {
+		let config = EscalationConfig { max_tracked: 1, ..Default::default() };
+		let mut tracker = TestTracker::new(config);
+		assert!(tracker.track(1, tracked("first", 10)));
+		assert!(!tracker.track(2, tracked("second", 10)));
+		assert!(!tracker.contains(&2));
+	}
+}
@@ -18,7 +18,7 @@
 
 use std::{collections::HashMap, sync::Arc, time::Duration};
 
-use crate::LOG_TARGET;
+use crate::{revalidation::RevalidationOptions, LOG_TARGET};
 use futures::{channel::mpsc::Receiver, Future};
 use sc_transaction_pool_api::error;
 use sp_blockchain::TreeRoute;
@@ -119,6 +119,8 @@ pub struct Options {
 	pub reject_future_transactions: bool,
 	/// How long the extrinsic is banned for.
 	pub ban_time: Duration,
+	/// Background revalidation options.
+	pub revalidation: RevalidationOptions,
 }
 
 impl Default for Options {
@@ -128,6 +130,7 @@ impl Default for Options {
 			future: base::Limit { count: 512, total_bytes: 1 * 1024 * 1024 },
 			reject_future_transactions: false,
 			ban_time: Duration::from_secs(60 * 30),
+			revalidation: Default::default(),
 		}
 	}
 }
@@ -509,7 +512,7 @@ mod tests {
 		assert_eq!(pool.validated_pool().status().future, 0);
 
 		// then
-		assert_matches!(res.unwrap_err(), error::Error::TemporarilyBanned);
+		assert_matches!(res.unwrap_err(), error::Error::TemporarilyBanned { .. });
 	}
 
 	#[test]
@@ -61,6 +61,11 @@ impl<Hash: hash::Hash + Eq + Clone> PoolRotator<Hash> {
 		self.banned_until.read().contains_key(hash)
 	}
 
+	/// Returns the instant the ban of the extrinsic expires at, if it is currently banned.
+	pub fn banned_until(&self, hash: &Hash) -> Option<Instant> {
+		self.banned_until.read().get(hash).copied()
+	}
+
 	/// Bans given set of hashes.
 	pub fn ban(&self, now: &Instant, hashes: impl IntoIterator<Item = Hash>) {
 		let mut banned = self.banned_until.write();
@@ -156,10 +161,12 @@ mod tests {
 		assert!(!rotator.is_banned(&hash));
 
 		// when
-		assert!(rotator.ban_if_stale(&Instant::now(), 1, &tx));
+		let now = Instant::now();
+		assert!(rotator.ban_if_stale(&now, 1, &tx));
 
 		// then
 		assert!(rotator.is_banned(&hash));
+		assert_eq!(rotator.banned_until(&hash), Some(now + rotator.ban_time));
 	}
 
 	#[test]
@@ -147,8 +147,10 @@ impl<B: ChainApi> ValidatedPool<B> {
 		tx_hash: &ExtrinsicHash<B>,
 		ignore_banned: bool,
 	) -> Result<(), B::Error> {
-		if !ignore_banned && self.is_banned(tx_hash) {
-			Err(error::Error::TemporarilyBanned.into())
+		let banned_until = if ignore_banned { None } else { self.rotator.banned_until(tx_hash) };
+		if let Some(banned_until) = banned_until {
+			let expires_in = banned_until.saturating_duration_since(Instant::now());
+			Err(error::Error::TemporarilyBanned { expires_in }.into())
 		} else if self.pool.read().is_imported(tx_hash) {
 			Err(error::Error::AlreadyImported(Box::new(*tx_hash)).into())
 		} else {
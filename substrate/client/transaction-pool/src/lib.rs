@@ -25,6 +25,7 @@
 mod api;
 mod enactment_state;
 pub mod error;
+pub mod escalation;
 mod graph;
 mod metrics;
 mod revalidation;
@@ -43,6 +44,7 @@ pub use graph::{
 	base_pool::Limit as PoolLimit, ChainApi, Options, Pool, Transaction, ValidatedTransaction,
 };
 use parking_lot::Mutex;
+pub use revalidation::{RevalidationOptions, RevalidationPriority};
 use std::{
 	collections::{HashMap, HashSet},
 	pin::Pin,
@@ -166,11 +168,13 @@ where
 		finalized_hash: Block::Hash,
 		options: graph::Options,
 	) -> (Self, Pin<Box<dyn Future<Output = ()> + Send>>) {
+		let revalidation_options = options.revalidation.clone();
 		let pool = Arc::new(graph::Pool::new(options, true.into(), pool_api.clone()));
 		let (revalidation_queue, background_task) = revalidation::RevalidationQueue::new_background(
 			pool_api.clone(),
 			pool.clone(),
 			finalized_hash,
+			revalidation_options,
 		);
 		(
 			Self {
@@ -202,6 +206,7 @@ where
 		best_block_hash: Block::Hash,
 		finalized_hash: Block::Hash,
 	) -> Self {
+		let revalidation_options = options.revalidation.clone();
 		let pool = Arc::new(graph::Pool::new(options, is_validator, pool_api.clone()));
 		let (revalidation_queue, background_task) = match revalidation_type {
 			RevalidationType::Light =>
@@ -211,6 +216,7 @@ where
 					pool_api.clone(),
 					pool.clone(),
 					finalized_hash,
+					revalidation_options,
 				);
 				(queue, Some(background))
 			},
@@ -331,6 +337,19 @@ where
 		self.pool.validated_pool().ready_by_hash(hash)
 	}
 
+	fn revalidate_ready(&self) -> PoolFuture<usize, Self::Error> {
+		let at = self.enactment_state.lock().recent_best_block();
+		let hashes = self.pool.validated_pool().ready().map(|tx| tx.hash).collect::<Vec<_>>();
+		let revalidation_queue = self.revalidation_queue.clone();
+
+		async move {
+			let count = hashes.len();
+			revalidation_queue.revalidate_now(at, hashes).await;
+			Ok(count)
+		}
+		.boxed()
+	}
+
 	fn ready_at(&self, at: NumberFor<Self::Block>) -> PolledIterator<PoolApi> {
 		let status = self.status();
 		// If there are no transactions in the pool, it is fine to return early.
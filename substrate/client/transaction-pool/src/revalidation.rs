@@ -19,7 +19,7 @@
 //! Pool periodic revalidation.
 
 use std::{
-	collections::{BTreeMap, HashMap, HashSet},
+	collections::{BTreeMap, HashMap},
 	pin::Pin,
 	sync::Arc,
 };
@@ -40,10 +40,47 @@ const BACKGROUND_REVALIDATION_INTERVAL: Duration = Duration::from_millis(200);
 
 const MIN_BACKGROUND_REVALIDATION_BATCH_SIZE: usize = 20;
 
+/// The order in which queued transactions are revalidated by the background worker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RevalidationPriority {
+	/// Revalidate the transactions that have been in the pool for the longest time first.
+	#[default]
+	Oldest,
+	/// Revalidate the most recently submitted transactions first.
+	///
+	/// Older transactions might not get revalidated while new transactions keep arriving.
+	Newest,
+}
+
+/// Options of the background revalidation.
+#[derive(Debug, Clone)]
+pub struct RevalidationOptions {
+	/// The number of transactions revalidated at every interval.
+	///
+	/// `None` revalidates a quarter of the queued transactions, but at least 20.
+	pub batch_size: Option<usize>,
+	/// How often a batch of transactions is revalidated.
+	pub interval: Duration,
+	/// Which transactions are revalidated first.
+	pub priority: RevalidationPriority,
+}
+
+impl Default for RevalidationOptions {
+	fn default() -> Self {
+		Self {
+			batch_size: None,
+			interval: BACKGROUND_REVALIDATION_INTERVAL,
+			priority: RevalidationPriority::default(),
+		}
+	}
+}
+
 /// Payload from queue to worker.
 struct WorkerPayload<Api: ChainApi> {
 	at: BlockHash<Api>,
 	transactions: Vec<ExtrinsicHash<Api>>,
+	/// Revalidate all the queued transactions right away instead of batch by batch.
+	immediate: bool,
 }
 
 /// Async revalidation worker.
@@ -53,8 +90,15 @@ struct RevalidationWorker<Api: ChainApi> {
 	api: Arc<Api>,
 	pool: Arc<Pool<Api>>,
 	best_block: BlockHash<Api>,
-	block_ordered: BTreeMap<BlockHash<Api>, HashSet<ExtrinsicHash<Api>>>,
-	members: HashMap<ExtrinsicHash<Api>, BlockHash<Api>>,
+	batch_size: Option<usize>,
+	priority: RevalidationPriority,
+	/// Transactions waiting for revalidation, keyed by the order they were first seen in.
+	queue: BTreeMap<u64, ExtrinsicHash<Api>>,
+	/// Transactions waiting for revalidation and their key in `queue`.
+	members: HashMap<ExtrinsicHash<Api>, u64>,
+	/// The order in which the transactions of the pool were first seen.
+	first_seen: HashMap<ExtrinsicHash<Api>, u64>,
+	next_seen: u64,
 }
 
 impl<Api: ChainApi> Unpin for RevalidationWorker<Api> {}
@@ -148,64 +192,71 @@ async fn batch_revalidate<Api: ChainApi>(
 }
 
 impl<Api: ChainApi> RevalidationWorker<Api> {
-	fn new(api: Arc<Api>, pool: Arc<Pool<Api>>, best_block: BlockHash<Api>) -> Self {
+	fn new(
+		api: Arc<Api>,
+		pool: Arc<Pool<Api>>,
+		best_block: BlockHash<Api>,
+		options: &RevalidationOptions,
+	) -> Self {
 		Self {
 			api,
 			pool,
 			best_block,
-			block_ordered: Default::default(),
+			batch_size: options.batch_size,
+			priority: options.priority,
+			queue: Default::default(),
 			members: Default::default(),
+			first_seen: Default::default(),
+			next_seen: 0,
 		}
 	}
 
-	fn prepare_batch(&mut self) -> Vec<ExtrinsicHash<Api>> {
+	/// The number of transactions to revalidate at the next interval.
+	fn next_batch_size(&self) -> usize {
+		self.batch_size.unwrap_or_else(|| {
+			std::cmp::max(MIN_BACKGROUND_REVALIDATION_BATCH_SIZE, self.members.len() / 4)
+		})
+	}
+
+	/// Take up to `count` transactions from the queue, in order of priority.
+	fn take(&mut self, count: usize) -> Vec<ExtrinsicHash<Api>> {
 		let mut queued_exts = Vec::new();
-		let mut left =
-			std::cmp::max(MIN_BACKGROUND_REVALIDATION_BATCH_SIZE, self.members.len() / 4);
-
-		// Take maximum of count transaction by order
-		// which they got into the pool
-		while left > 0 {
-			let first_block = match self.block_ordered.keys().next().cloned() {
-				Some(bn) => bn,
-				None => break,
-			};
-			let mut block_drained = false;
-			if let Some(extrinsics) = self.block_ordered.get_mut(&first_block) {
-				let to_queue = extrinsics.iter().take(left).cloned().collect::<Vec<_>>();
-				if to_queue.len() == extrinsics.len() {
-					block_drained = true;
-				} else {
-					for xt in &to_queue {
-						extrinsics.remove(xt);
-					}
-				}
-				left -= to_queue.len();
-				queued_exts.extend(to_queue);
-			}
 
-			if block_drained {
-				self.block_ordered.remove(&first_block);
-			}
-		}
+		while queued_exts.len() < count {
+			let next = match self.priority {
+				RevalidationPriority::Oldest => self.queue.pop_first(),
+				RevalidationPriority::Newest => self.queue.pop_last(),
+			};
+			let Some((_, hash)) = next else { break };
 
-		for hash in queued_exts.iter() {
-			self.members.remove(hash);
+			self.members.remove(&hash);
+			queued_exts.push(hash);
 		}
 
 		queued_exts
 	}
 
 	fn len(&self) -> usize {
-		self.block_ordered.iter().map(|b| b.1.len()).sum()
+		self.queue.len()
 	}
 
 	fn push(&mut self, worker_payload: WorkerPayload<Api>) {
-		// we don't add something that already scheduled for revalidation
 		let transactions = worker_payload.transactions;
-		let block_number = worker_payload.at;
+
+		// The payload contains all the ready transactions of the pool, so anything that is not
+		// part of it anymore can be forgotten.
+		let mut first_seen = HashMap::with_capacity(transactions.len());
 
 		for ext_hash in transactions {
+			let seen = match self.first_seen.get(&ext_hash) {
+				Some(seen) => *seen,
+				None => {
+					self.next_seen += 1;
+					self.next_seen
+				},
+			};
+			first_seen.insert(ext_hash, seen);
+
 			// we don't add something that already scheduled for revalidation
 			if self.members.contains_key(&ext_hash) {
 				log::trace!(
@@ -217,17 +268,27 @@ impl<Api: ChainApi> RevalidationWorker<Api> {
 				continue
 			}
 
-			self.block_ordered
-				.entry(block_number)
-				.and_modify(|value| {
-					value.insert(ext_hash);
-				})
-				.or_insert_with(|| {
-					let mut bt = HashSet::new();
-					bt.insert(ext_hash);
-					bt
-				});
-			self.members.insert(ext_hash, block_number);
+			self.queue.insert(seen, ext_hash);
+			self.members.insert(ext_hash, seen);
+		}
+
+		self.first_seen = first_seen;
+	}
+
+	/// Revalidate up to `count` queued transactions at the best block.
+	async fn revalidate(&mut self, count: usize) {
+		let next_batch = self.take(count);
+		let batch_len = next_batch.len();
+
+		batch_revalidate(self.pool.clone(), self.api.clone(), self.best_block, next_batch).await;
+
+		if batch_len > 0 || self.len() > 0 {
+			log::debug!(
+				target: LOG_TARGET,
+				"Revalidated {} transactions. Left in the queue for revalidation: {}.",
+				batch_len,
+				self.len(),
+			);
 		}
 	}
 
@@ -250,19 +311,8 @@ impl<Api: ChainApi> RevalidationWorker<Api> {
 			futures::select! {
 				// Using `fuse()` in here is okay, because we reset the interval when it has fired.
 				_ = (&mut interval_fut).fuse() => {
-					let next_batch = this.prepare_batch();
-					let batch_len = next_batch.len();
-
-					batch_revalidate(this.pool.clone(), this.api.clone(), this.best_block, next_batch).await;
-
-					if batch_len > 0 || this.len() > 0 {
-						log::debug!(
-							target: LOG_TARGET,
-							"Revalidated {} transactions. Left in the queue for revalidation: {}.",
-							batch_len,
-							this.len(),
-						);
-					}
+					let batch_size = this.next_batch_size();
+					this.revalidate(batch_size).await;
 
 					interval_fut.reset(interval);
 				},
@@ -270,8 +320,14 @@ impl<Api: ChainApi> RevalidationWorker<Api> {
 					match workload {
 						Some(worker_payload) => {
 							this.best_block = worker_payload.at;
+							let immediate = worker_payload.immediate;
 							this.push(worker_payload);
 
+							if immediate {
+								this.revalidate(usize::MAX).await;
+								interval_fut.reset(interval);
+							}
+
 							if this.members.len() > 0 {
 								log::debug!(
 									target: LOG_TARGET,
@@ -312,28 +368,19 @@ where
 	}
 
 	/// New revalidation queue with background worker.
-	pub fn new_with_interval(
+	pub fn new_background(
 		api: Arc<Api>,
 		pool: Arc<Pool<Api>>,
-		interval: Duration,
 		best_block: BlockHash<Api>,
+		options: RevalidationOptions,
 	) -> (Self, Pin<Box<dyn Future<Output = ()> + Send>>) {
 		let (to_worker, from_queue) = tracing_unbounded("mpsc_revalidation_queue", 100_000);
 
-		let worker = RevalidationWorker::new(api.clone(), pool.clone(), best_block);
+		let worker = RevalidationWorker::new(api.clone(), pool.clone(), best_block, &options);
 
 		let queue = Self { api, pool, background: Some(to_worker) };
 
-		(queue, worker.run(from_queue, interval).boxed())
-	}
-
-	/// New revalidation queue with background worker.
-	pub fn new_background(
-		api: Arc<Api>,
-		pool: Arc<Pool<Api>>,
-		best_block: BlockHash<Api>,
-	) -> (Self, Pin<Box<dyn Future<Output = ()> + Send>>) {
-		Self::new_with_interval(api, pool, BACKGROUND_REVALIDATION_INTERVAL, best_block)
+		(queue, worker.run(from_queue, options.interval).boxed())
 	}
 
 	/// Queue some transaction for later revalidation.
@@ -345,6 +392,24 @@ where
 		&self,
 		at: BlockHash<Api>,
 		transactions: Vec<ExtrinsicHash<Api>>,
+	) {
+		self.send(at, transactions, false).await
+	}
+
+	/// Revalidate the given transactions, together with anything already queued, right away.
+	///
+	/// If queue configured with background worker, this will return immediately.
+	/// If queue configured without background worker, this will resolve after
+	/// revalidation is actually done.
+	pub async fn revalidate_now(&self, at: BlockHash<Api>, transactions: Vec<ExtrinsicHash<Api>>) {
+		self.send(at, transactions, true).await
+	}
+
+	async fn send(
+		&self,
+		at: BlockHash<Api>,
+		transactions: Vec<ExtrinsicHash<Api>>,
+		immediate: bool,
 	) {
 		if transactions.len() > 0 {
 			log::debug!(
@@ -355,7 +420,8 @@ where
 		}
 
 		if let Some(ref to_worker) = self.background {
-			if let Err(e) = to_worker.unbounded_send(WorkerPayload { at, transactions }) {
+			if let Err(e) = to_worker.unbounded_send(WorkerPayload { at, transactions, immediate })
+			{
 				log::warn!(target: LOG_TARGET, "Failed to update background worker: {:?}", e);
 			}
 		} else {
@@ -449,4 +515,39 @@ mod tests {
 		// number of ready shall not change
 		assert_eq!(pool.validated_pool().status().ready, 2);
 	}
+
+	#[test]
+	fn revalidation_worker_respects_priority() {
+		let api = Arc::new(TestApi::default());
+		let pool = Arc::new(Pool::new(Default::default(), true.into(), api.clone()));
+		let at = api.expect_hash_from_number(0);
+		let hashes = (0..4).map(H256::from_low_u64_be).collect::<Vec<_>>();
+		let payload = |transactions: &[H256]| WorkerPayload::<TestApi> {
+			at,
+			transactions: transactions.to_vec(),
+			immediate: false,
+		};
+
+		let options = RevalidationOptions {
+			batch_size: Some(3),
+			priority: RevalidationPriority::Oldest,
+			..Default::default()
+		};
+		let mut worker = RevalidationWorker::new(api.clone(), pool.clone(), at, &options);
+		worker.push(payload(&hashes[..2]));
+		worker.push(payload(&hashes));
+		assert_eq!(worker.next_batch_size(), 3);
+		assert_eq!(worker.take(worker.next_batch_size()), hashes[..3]);
+
+		// Revalidated transactions keep their position when queued again.
+		worker.push(payload(&hashes));
+		assert_eq!(worker.take(usize::MAX), hashes);
+
+		let options = RevalidationOptions { priority: RevalidationPriority::Newest, ..options };
+		let mut worker = RevalidationWorker::new(api.clone(), pool.clone(), at, &options);
+		worker.push(payload(&hashes[..2]));
+		worker.push(payload(&hashes));
+		assert_eq!(worker.take(worker.next_batch_size()), [hashes[3], hashes[2], hashes[1]]);
+		assert_eq!(worker.len(), 1);
+	}
 }
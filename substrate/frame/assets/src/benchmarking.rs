@@ -490,9 +490,21 @@ benchmarks_instance_pallet! {
 		T::Currency::make_free_balance_be(&new_account, DepositBalanceOf::<T, I>::max_value());
 		assert_ne!(asset_owner, new_account);
 		assert!(!Account::<T, I>::contains_key(asset_id.clone().into(), &new_account));
+		// The deposit is sponsored, the worst case.
+		T::Currency::make_free_balance_be(&asset_owner, DepositBalanceOf::<T, I>::max_value());
+		Assets::<T, I>::set_sponsorship(
+			SystemOrigin::Signed(asset_owner.clone()).into(),
+			asset_id.clone(),
+			2,
+		)?;
 	}: _(SystemOrigin::Signed(new_account.clone()), asset_id.clone())
 	verify {
 		assert!(Account::<T, I>::contains_key(asset_id.into(), &new_account));
+		assert_last_event::<T, I>(Event::Touched {
+			asset_id: asset_id.into(),
+			who: new_account,
+			depositor: asset_owner,
+		}.into());
 	}
 
 	touch_other {
@@ -553,5 +565,28 @@ benchmarks_instance_pallet! {
 		assert_last_event::<T, I>(Event::Blocked { asset_id: asset_id.into(), who: caller }.into());
 	}
 
+	set_sponsorship {
+		let (asset_id, caller, _) = create_default_asset::<T, I>(false);
+	}: _(SystemOrigin::Signed(caller.clone()), asset_id.clone(), 100)
+	verify {
+		assert_last_event::<T, I>(Event::SponsorshipSet {
+			asset_id: asset_id.into(),
+			sponsor: caller,
+			max_accounts: 100,
+		}.into());
+	}
+
+	clear_sponsorship {
+		let (asset_id, caller, _) = create_default_asset::<T, I>(false);
+		Assets::<T, I>::set_sponsorship(
+			SystemOrigin::Signed(caller.clone()).into(),
+			asset_id.clone(),
+			100,
+		)?;
+	}: _(SystemOrigin::Signed(caller.clone()), asset_id.clone())
+	verify {
+		assert_last_event::<T, I>(Event::SponsorshipCleared { asset_id: asset_id.into() }.into());
+	}
+
 	impl_benchmark_test_suite!(Assets, crate::mock::new_test_ext(), crate::mock::Test)
 }
@@ -322,6 +322,12 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			!check_depositor || &depositor == &details.admin || &depositor == &details.freezer,
 			Error::<T, I>::NoPermission
 		);
+		// The accounts touched by their holder are sponsored, if the sponsor can still pay.
+		let depositor = if depositor == who {
+			Self::take_sponsor(&id, deposit).unwrap_or(depositor)
+		} else {
+			depositor
+		};
 		let reason = Self::new_account(&who, &mut details, Some((&depositor, deposit)))?;
 		T::Currency::reserve(&depositor, deposit)?;
 		Asset::<T, I>::insert(&id, details);
@@ -339,6 +345,25 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		Ok(())
 	}
 
+	/// Takes the sponsor of the deposit of a new account of asset `id`, if the sponsorship has
+	/// accounts left and the sponsor can reserve the `deposit`.
+	fn take_sponsor(id: &T::AssetId, deposit: DepositBalanceOf<T, I>) -> Option<T::AccountId> {
+		Sponsorship::<T, I>::mutate_exists(id, |maybe_sponsorship| {
+			let sponsorship = maybe_sponsorship.as_mut()?;
+			if sponsorship.remaining == 0 ||
+				!T::Currency::can_reserve(&sponsorship.sponsor, deposit)
+			{
+				return None
+			}
+			let sponsor = sponsorship.sponsor.clone();
+			sponsorship.remaining.saturating_dec();
+			if sponsorship.remaining == 0 {
+				*maybe_sponsorship = None;
+			}
+			Some(sponsor)
+		})
+	}
+
 	/// Returns a deposit or a consumer reference, destroying an asset-account.
 	/// Non-zero balance accounts refunded and destroyed only if `allow_burn` is true.
 	pub(super) fn do_refund(id: T::AssetId, who: T::AccountId, allow_burn: bool) -> DispatchResult {
@@ -843,6 +868,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			ensure!(T::CallbackHandle::destroyed(&id).is_ok(), Error::<T, I>::CallbackFailed);
 
 			let metadata = Metadata::<T, I>::take(&id);
+			Sponsorship::<T, I>::remove(&id);
 			T::Currency::unreserve(
 				&details.owner,
 				details.deposit.saturating_add(metadata.deposit),
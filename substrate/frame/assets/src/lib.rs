@@ -90,7 +90,8 @@
 //! * `approve_transfer`: Create or increase an delegated transfer.
 //! * `cancel_approval`: Rescind a previous approval.
 //! * `transfer_approved`: Transfer third-party's assets to another account.
-//! * `touch`: Create an asset account for non-provider assets. Caller must place a deposit.
+//! * `touch`: Create an asset account for non-provider assets. Caller must place a deposit, unless
+//!   the asset class sponsors it.
 //! * `refund`: Return the deposit (if any) of the caller's asset account or a consumer reference
 //!   (if any) of the caller's account.
 //! * `refund_other`: Return the deposit (if any) of a specified asset account.
@@ -120,6 +121,10 @@
 //!   called by the asset class's Freezer or Admin.
 //! * `block`: Disallows further `transfer`s to and from an account; called by the asset class's
 //!   Freezer.
+//! * `set_sponsorship`: Pays the deposits of the next accounts touched by their holder; called by
+//!   the asset class's Owner or Admin.
+//! * `clear_sponsorship`: Stops paying the deposits of new accounts; called by the sponsor or the
+//!   asset class's Owner.
 //!
 //! Please refer to the [`Call`] enum and its associated variants for documentation on each
 //! function.
@@ -137,6 +142,9 @@
 //! Using `CallbackHandle` associated type, user can configure custom callback functions which are
 //! executed when new asset is created or an existing asset is destroyed.
 //!
+//! Using `FreezeConsumer` associated type, other pallets holding assets can be notified when an
+//! asset or an account's holding of an asset is frozen or thawed, e.g. to pause their markets.
+//!
 //! ## Related Modules
 //!
 //! * [`System`](../frame_system/index.html)
@@ -214,7 +222,7 @@ pub mod pallet {
 	use super::*;
 	use frame_support::{
 		pallet_prelude::*,
-		traits::{AccountTouch, ContainsPair},
+		traits::{fungibles::FreezeConsumer as _, AccountTouch, ContainsPair},
 	};
 	use frame_system::pallet_prelude::*;
 
@@ -261,6 +269,7 @@ pub mod pallet {
 			type StringLimit = ConstU32<50>;
 			type Extra = ();
 			type CallbackHandle = ();
+			type FreezeConsumer = ();
 			type WeightInfo = ();
 			#[cfg(feature = "runtime-benchmarks")]
 			type BenchmarkHelper = ();
@@ -363,6 +372,10 @@ pub mod pallet {
 		/// Callback methods for asset state change (e.g. asset created or destroyed)
 		type CallbackHandle: AssetsCallback<Self::AssetId, Self::AccountId>;
 
+		/// Handler notified when an asset or an account's holding of an asset is frozen or
+		/// thawed.
+		type FreezeConsumer: fungibles::FreezeConsumer<Self::AssetId, Self::AccountId>;
+
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 
@@ -415,6 +428,11 @@ pub mod pallet {
 		ValueQuery,
 	>;
 
+	#[pallet::storage]
+	/// The sponsorship of the deposits of the accounts touched by their holder, per asset.
+	pub(super) type Sponsorship<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AssetId, AssetSponsorship<T::AccountId>>;
+
 	#[pallet::genesis_config]
 	#[derive(frame_support::DefaultNoBound)]
 	pub struct GenesisConfig<T: Config<I>, I: 'static = ()> {
@@ -571,6 +589,10 @@ pub mod pallet {
 		Touched { asset_id: T::AssetId, who: T::AccountId, depositor: T::AccountId },
 		/// Some account `who` was blocked.
 		Blocked { asset_id: T::AssetId, who: T::AccountId },
+		/// The deposits of up to `max_accounts` new accounts of an asset are taken from `sponsor`.
+		SponsorshipSet { asset_id: T::AssetId, sponsor: T::AccountId, max_accounts: u32 },
+		/// The deposits of the new accounts of an asset are no longer sponsored.
+		SponsorshipCleared { asset_id: T::AssetId },
 	}
 
 	#[pallet::error]
@@ -618,6 +640,8 @@ pub mod pallet {
 		NotFrozen,
 		/// Callback action resulted in error
 		CallbackFailed,
+		/// The deposits of the new accounts of the asset are not sponsored.
+		NoSponsorship,
 	}
 
 	#[pallet::call(weight(<T as Config<I>>::WeightInfo))]
@@ -998,6 +1022,7 @@ pub mod pallet {
 				Ok(())
 			})?;
 
+			T::FreezeConsumer::account_frozen(&id, &who);
 			Self::deposit_event(Event::<T, I>::Frozen { asset_id: id, who });
 			Ok(())
 		}
@@ -1035,6 +1060,7 @@ pub mod pallet {
 				Ok(())
 			})?;
 
+			T::FreezeConsumer::account_thawed(&id, &who);
 			Self::deposit_event(Event::<T, I>::Thawed { asset_id: id, who });
 			Ok(())
 		}
@@ -1060,9 +1086,12 @@ pub mod pallet {
 
 				d.status = AssetStatus::Frozen;
 
-				Self::deposit_event(Event::<T, I>::AssetFrozen { asset_id: id });
-				Ok(())
-			})
+				Self::deposit_event(Event::<T, I>::AssetFrozen { asset_id: id.clone() });
+				Ok::<_, DispatchError>(())
+			})?;
+
+			T::FreezeConsumer::asset_frozen(&id);
+			Ok(())
 		}
 
 		/// Allow unprivileged transfers for the asset again.
@@ -1086,9 +1115,12 @@ pub mod pallet {
 
 				d.status = AssetStatus::Live;
 
-				Self::deposit_event(Event::<T, I>::AssetThawed { asset_id: id });
-				Ok(())
-			})
+				Self::deposit_event(Event::<T, I>::AssetThawed { asset_id: id.clone() });
+				Ok::<_, DispatchError>(())
+			})?;
+
+			T::FreezeConsumer::asset_thawed(&id);
+			Ok(())
 		}
 
 		/// Change the Owner of an asset.
@@ -1350,7 +1382,7 @@ pub mod pallet {
 			T::ForceOrigin::ensure_origin(origin)?;
 			let id: T::AssetId = id.into();
 
-			Asset::<T, I>::try_mutate(id.clone(), |maybe_asset| {
+			let was_frozen = Asset::<T, I>::try_mutate(id.clone(), |maybe_asset| {
 				let mut asset = maybe_asset.take().ok_or(Error::<T, I>::Unknown)?;
 				ensure!(asset.status != AssetStatus::Destroying, Error::<T, I>::AssetNotLive);
 				asset.owner = T::Lookup::lookup(owner)?;
@@ -1359,6 +1391,7 @@ pub mod pallet {
 				asset.freezer = T::Lookup::lookup(freezer)?;
 				asset.min_balance = min_balance;
 				asset.is_sufficient = is_sufficient;
+				let was_frozen = asset.status == AssetStatus::Frozen;
 				if is_frozen {
 					asset.status = AssetStatus::Frozen;
 				} else {
@@ -1366,9 +1399,16 @@ pub mod pallet {
 				}
 				*maybe_asset = Some(asset);
 
-				Self::deposit_event(Event::AssetStatusChanged { asset_id: id });
-				Ok(())
-			})
+				Self::deposit_event(Event::AssetStatusChanged { asset_id: id.clone() });
+				Ok::<_, DispatchError>(was_frozen)
+			})?;
+
+			match (was_frozen, is_frozen) {
+				(false, true) => T::FreezeConsumer::asset_frozen(&id),
+				(true, false) => T::FreezeConsumer::asset_thawed(&id),
+				_ => {},
+			}
+			Ok(())
 		}
 
 		/// Approve an amount of asset for transfer by a delegated third-party account.
@@ -1519,7 +1559,8 @@ pub mod pallet {
 
 		/// Create an asset account for non-provider assets.
 		///
-		/// A deposit will be taken from the signer account.
+		/// A deposit will be taken from the sponsor of the asset, if any can still pay it, or else
+		/// from the signer account.
 		///
 		/// - `origin`: Must be Signed; the signer account must have sufficient funds for a deposit
 		///   to be taken.
@@ -1680,9 +1721,78 @@ pub mod pallet {
 				Ok(())
 			})?;
 
+			T::FreezeConsumer::account_frozen(&id, &who);
 			Self::deposit_event(Event::<T, I>::Blocked { asset_id: id, who });
 			Ok(())
 		}
+
+		/// Sponsor the deposits of the accounts of an asset `id` touched by their holder.
+		///
+		/// The deposits of the next `max_accounts` accounts created with `touch` are taken from
+		/// the signer instead of the holders, such that the users without native tokens can hold
+		/// the asset. The sponsor can reclaim a deposit with `refund_other` once the account is
+		/// empty. Replaces the current sponsorship of the asset, if any.
+		///
+		/// Origin must be Signed by the Owner or the Admin of the asset `id`.
+		///
+		/// - `id`: The identifier of the asset.
+		/// - `max_accounts`: The maximum number of accounts whose deposit is sponsored.
+		///
+		/// Emits `SponsorshipSet`.
+		///
+		/// Weight: `O(1)`
+		#[pallet::call_index(32)]
+		pub fn set_sponsorship(
+			origin: OriginFor<T>,
+			id: T::AssetIdParameter,
+			max_accounts: u32,
+		) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			let id: T::AssetId = id.into();
+
+			let d = Asset::<T, I>::get(&id).ok_or(Error::<T, I>::Unknown)?;
+			ensure!(d.status == AssetStatus::Live, Error::<T, I>::AssetNotLive);
+			ensure!(origin == d.owner || origin == d.admin, Error::<T, I>::NoPermission);
+
+			Sponsorship::<T, I>::insert(
+				&id,
+				AssetSponsorship { sponsor: origin.clone(), remaining: max_accounts },
+			);
+			Self::deposit_event(Event::SponsorshipSet {
+				asset_id: id,
+				sponsor: origin,
+				max_accounts,
+			});
+			Ok(())
+		}
+
+		/// Stop sponsoring the deposits of the new accounts of an asset `id`.
+		///
+		/// The deposits already taken from the sponsor stay reserved until refunded.
+		///
+		/// Origin must be Signed by the sponsor or the Owner of the asset `id`.
+		///
+		/// - `id`: The identifier of the asset.
+		///
+		/// Emits `SponsorshipCleared`.
+		///
+		/// Weight: `O(1)`
+		#[pallet::call_index(33)]
+		pub fn clear_sponsorship(origin: OriginFor<T>, id: T::AssetIdParameter) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			let id: T::AssetId = id.into();
+
+			let d = Asset::<T, I>::get(&id).ok_or(Error::<T, I>::Unknown)?;
+			let sponsorship = Sponsorship::<T, I>::get(&id).ok_or(Error::<T, I>::NoSponsorship)?;
+			ensure!(
+				origin == sponsorship.sponsor || origin == d.owner,
+				Error::<T, I>::NoPermission
+			);
+
+			Sponsorship::<T, I>::remove(&id);
+			Self::deposit_event(Event::SponsorshipCleared { asset_id: id });
+			Ok(())
+		}
 	}
 
 	/// Implements [`AccountTouch`] trait.
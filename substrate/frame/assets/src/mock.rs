@@ -115,6 +115,7 @@ impl Config for Test {
 	type ForceOrigin = frame_system::EnsureRoot<u64>;
 	type Freezer = TestFreezer;
 	type CallbackHandle = AssetsCallbackHandle;
+	type FreezeConsumer = TestFreezeConsumer;
 }
 
 use std::collections::HashMap;
@@ -123,9 +124,19 @@ use std::collections::HashMap;
 pub enum Hook {
 	Died(u32, u64),
 }
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum FreezeHook {
+	AssetFrozen(u32),
+	AssetThawed(u32),
+	AccountFrozen(u32, u64),
+	AccountThawed(u32, u64),
+}
+
 parameter_types! {
 	static Frozen: HashMap<(u32, u64), u64> = Default::default();
 	static Hooks: Vec<Hook> = Default::default();
+	static FreezeHooks: Vec<FreezeHook> = Default::default();
 }
 
 pub struct TestFreezer;
@@ -142,6 +153,25 @@ impl FrozenBalance<u32, u64, u64> for TestFreezer {
 	}
 }
 
+pub struct TestFreezeConsumer;
+impl fungibles::FreezeConsumer<u32, u64> for TestFreezeConsumer {
+	fn asset_frozen(asset: &u32) {
+		FreezeHooks::mutate(|v| v.push(FreezeHook::AssetFrozen(*asset)));
+	}
+
+	fn asset_thawed(asset: &u32) {
+		FreezeHooks::mutate(|v| v.push(FreezeHook::AssetThawed(*asset)));
+	}
+
+	fn account_frozen(asset: &u32, who: &u64) {
+		FreezeHooks::mutate(|v| v.push(FreezeHook::AccountFrozen(*asset, *who)));
+	}
+
+	fn account_thawed(asset: &u32, who: &u64) {
+		FreezeHooks::mutate(|v| v.push(FreezeHook::AccountThawed(*asset, *who)));
+	}
+}
+
 pub(crate) fn set_frozen_balance(asset: u32, who: u64, amount: u64) {
 	Frozen::mutate(|v| {
 		v.insert((asset, who), amount);
@@ -162,6 +192,10 @@ pub(crate) fn take_hooks() -> Vec<Hook> {
 	Hooks::take()
 }
 
+pub(crate) fn take_freeze_hooks() -> Vec<FreezeHook> {
+	FreezeHooks::take()
+}
+
 pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
 	let mut storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
 
@@ -185,6 +219,7 @@ pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
 	let mut ext: sp_io::TestExternalities = storage.into();
 	// Clear thread local vars for https://github.com/paritytech/substrate/issues/10479.
 	ext.execute_with(|| take_hooks());
+	ext.execute_with(|| take_freeze_hooks());
 	ext.execute_with(|| System::set_block_number(1));
 	ext
 }
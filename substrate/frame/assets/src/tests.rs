@@ -747,6 +747,45 @@ fn transferring_frozen_asset_should_not_work() {
 	});
 }
 
+#[test]
+fn freeze_consumer_is_notified_of_status_changes() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), 0, 1, true, 1));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(1), 0, 1, 100));
+		assert_ok!(Assets::freeze(RuntimeOrigin::signed(1), 0, 1));
+		assert_ok!(Assets::thaw(RuntimeOrigin::signed(1), 0, 1));
+		assert_ok!(Assets::block(RuntimeOrigin::signed(1), 0, 1));
+		assert_ok!(Assets::freeze_asset(RuntimeOrigin::signed(1), 0));
+		assert_ok!(Assets::thaw_asset(RuntimeOrigin::signed(1), 0));
+		assert_eq!(
+			take_freeze_hooks(),
+			vec![
+				FreezeHook::AccountFrozen(0, 1),
+				FreezeHook::AccountThawed(0, 1),
+				FreezeHook::AccountFrozen(0, 1),
+				FreezeHook::AssetFrozen(0),
+				FreezeHook::AssetThawed(0),
+			]
+		);
+
+		// Only actual changes of the asset status are notified.
+		let force_status = |is_frozen| {
+			Assets::force_asset_status(RuntimeOrigin::root(), 0, 1, 1, 1, 1, 1, true, is_frozen)
+		};
+		assert_ok!(force_status(false));
+		assert!(take_freeze_hooks().is_empty());
+		assert_ok!(force_status(true));
+		assert_eq!(take_freeze_hooks(), vec![FreezeHook::AssetFrozen(0)]);
+
+		// Failed calls aren't notified.
+		assert_noop!(
+			Assets::freeze_asset(RuntimeOrigin::signed(1), 0),
+			Error::<Test>::AssetNotLive
+		);
+		assert!(take_freeze_hooks().is_empty());
+	});
+}
+
 #[test]
 fn approve_transfer_frozen_asset_should_not_work() {
 	new_test_ext().execute_with(|| {
@@ -971,6 +1010,108 @@ fn touch_other_and_freeze_works() {
 	});
 }
 
+#[test]
+fn sponsored_touch_works() {
+	new_test_ext().execute_with(|| {
+		// 1 is the owner and sponsor, 2 and 3 hold no native tokens
+		Balances::make_free_balance_be(&1, 100);
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), 0, 1, false, 1));
+		assert_noop!(
+			Assets::touch(RuntimeOrigin::signed(2), 0),
+			BalancesError::<Test>::InsufficientBalance
+		);
+
+		assert_ok!(Assets::set_sponsorship(RuntimeOrigin::signed(1), 0, 1));
+		System::assert_last_event(RuntimeEvent::Assets(crate::Event::SponsorshipSet {
+			asset_id: 0,
+			sponsor: 1,
+			max_accounts: 1,
+		}));
+
+		// the deposit of `2` is taken from the sponsor
+		assert_ok!(Assets::touch(RuntimeOrigin::signed(2), 0));
+		System::assert_last_event(RuntimeEvent::Assets(crate::Event::Touched {
+			asset_id: 0,
+			who: 2,
+			depositor: 1,
+		}));
+		assert_eq!(Balances::reserved_balance(&1), 10);
+		assert_eq!(Balances::reserved_balance(&2), 0);
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(1), 0, 2, 100));
+		assert_eq!(Assets::balance(0, 2), 100);
+
+		// the sponsorship is used up
+		assert!(Sponsorship::<Test>::get(0).is_none());
+		assert_noop!(
+			Assets::touch(RuntimeOrigin::signed(3), 0),
+			BalancesError::<Test>::InsufficientBalance
+		);
+
+		// the sponsor reclaims the deposit of the emptied account
+		assert_ok!(Assets::burn(RuntimeOrigin::signed(1), 0, 2, 100));
+		assert_noop!(Assets::refund(RuntimeOrigin::signed(2), 0, false), Error::<Test>::NoDeposit);
+		assert_ok!(Assets::refund_other(RuntimeOrigin::signed(1), 0, 2));
+		assert_eq!(Balances::reserved_balance(&1), 0);
+		assert!(!Account::<Test>::contains_key(0, &2));
+	});
+}
+
+#[test]
+fn sponsored_touch_falls_back_to_holder_deposit() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&1, 5);
+		Balances::make_free_balance_be(&2, 100);
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), 0, 1, false, 1));
+		assert_ok!(Assets::set_sponsorship(RuntimeOrigin::signed(1), 0, 10));
+
+		// the sponsor cannot pay the deposit, the holder does
+		assert_ok!(Assets::touch(RuntimeOrigin::signed(2), 0));
+		assert_eq!(Balances::reserved_balance(&2), 10);
+		assert_eq!(Sponsorship::<Test>::get(0).unwrap().remaining, 10);
+
+		// the deposits of the accounts touched by others are not sponsored
+		Balances::make_free_balance_be(&1, 100);
+		assert_ok!(Assets::touch_other(RuntimeOrigin::signed(1), 0, 3));
+		assert_eq!(Balances::reserved_balance(&1), 10);
+		assert_eq!(Sponsorship::<Test>::get(0).unwrap().remaining, 10);
+	});
+}
+
+#[test]
+fn sponsorship_permissions_work() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&1, 100);
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), 0, 1, false, 1));
+		assert_ok!(Assets::set_team(RuntimeOrigin::signed(1), 0, 1, 2, 1));
+		assert_noop!(
+			Assets::set_sponsorship(RuntimeOrigin::signed(3), 0, 1),
+			Error::<Test>::NoPermission
+		);
+		assert_noop!(
+			Assets::set_sponsorship(RuntimeOrigin::signed(1), 1, 1),
+			Error::<Test>::Unknown
+		);
+		assert_noop!(
+			Assets::clear_sponsorship(RuntimeOrigin::signed(1), 0),
+			Error::<Test>::NoSponsorship
+		);
+
+		// the admin sponsors, the owner or the sponsor may clear it
+		assert_ok!(Assets::set_sponsorship(RuntimeOrigin::signed(2), 0, 1));
+		assert_noop!(
+			Assets::clear_sponsorship(RuntimeOrigin::signed(3), 0),
+			Error::<Test>::NoPermission
+		);
+		assert_ok!(Assets::clear_sponsorship(RuntimeOrigin::signed(1), 0));
+		System::assert_last_event(RuntimeEvent::Assets(crate::Event::SponsorshipCleared {
+			asset_id: 0,
+		}));
+		assert_ok!(Assets::set_sponsorship(RuntimeOrigin::signed(2), 0, 1));
+		assert_ok!(Assets::clear_sponsorship(RuntimeOrigin::signed(2), 0));
+		assert!(Sponsorship::<Test>::get(0).is_none());
+	});
+}
+
 #[test]
 fn account_with_deposit_not_destroyed() {
 	new_test_ext().execute_with(|| {
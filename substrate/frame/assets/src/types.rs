@@ -184,6 +184,15 @@ pub struct AssetAccount<Balance, DepositBalance, Extra, AccountId> {
 	pub(super) extra: Extra,
 }
 
+/// The sponsorship of the deposits of the new accounts of an asset.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct AssetSponsorship<AccountId> {
+	/// The account from which the deposits are taken.
+	pub(super) sponsor: AccountId,
+	/// The number of accounts whose deposit may still be taken from the sponsor.
+	pub(super) remaining: u32,
+}
+
 #[derive(Clone, Encode, Decode, Eq, PartialEq, Default, RuntimeDebug, MaxEncodedLen, TypeInfo)]
 pub struct AssetMetadata<DepositBalance, BoundedString> {
 	/// The balance deposited for this metadata.
@@ -36,10 +36,13 @@
 use sp_runtime::traits::{Convert, Member};
 use sp_std::prelude::*;
 
-use codec::Decode;
+use codec::{Codec, Decode, Encode};
 use pallet_mmr::{LeafDataProvider, ParentNumberAndHash};
 use sp_consensus_beefy::{
-	mmr::{BeefyAuthoritySet, BeefyDataProvider, BeefyNextAuthoritySet, MmrLeaf, MmrLeafVersion},
+	mmr::{
+		BeefyAuthoritySet, BeefyDataProvider, BeefyNextAuthoritySet, BeefyValidatorSetCommitment,
+		MmrLeaf, MmrLeafVersion,
+	},
 	ValidatorSet as BeefyValidatorSet,
 };
 
@@ -183,6 +186,40 @@ impl<T: Config> Pallet<T> {
 		BeefyNextAuthorities::<T>::get()
 	}
 
+	/// Return the currently active BEEFY validator set along with its commitments.
+	pub fn validator_set_commitment(
+	) -> Option<BeefyValidatorSetCommitment<<T as pallet_beefy::Config>::BeefyId, MerkleRootOf<T>>>
+	{
+		pallet_beefy::Pallet::<T>::validator_set()
+			.map(|set| Self::compute_validator_set_commitment(&set))
+	}
+
+	/// Return the next/queued BEEFY validator set along with its commitments.
+	pub fn next_validator_set_commitment(
+	) -> Option<BeefyValidatorSetCommitment<<T as pallet_beefy::Config>::BeefyId, MerkleRootOf<T>>>
+	{
+		pallet_beefy::Pallet::<T>::next_validator_set()
+			.map(|set| Self::compute_validator_set_commitment(&set))
+	}
+
+	/// Returns a BEEFY validator set along with the merkle root of its encoded public keys and
+	/// the details committed to in the MMR leaves.
+	fn compute_validator_set_commitment(
+		validator_set: &BeefyValidatorSet<<T as pallet_beefy::Config>::BeefyId>,
+	) -> BeefyValidatorSetCommitment<<T as pallet_beefy::Config>::BeefyId, MerkleRootOf<T>> {
+		let public_keys_root = binary_merkle_tree::merkle_root::<
+			<T as pallet_mmr::Config>::Hashing,
+			_,
+		>(validator_set.validators().iter().map(Encode::encode))
+		.into();
+		BeefyValidatorSetCommitment {
+			id: validator_set.id(),
+			validators: validator_set.validators().to_vec(),
+			public_keys_root,
+			authority_set: Self::compute_authority_set(validator_set),
+		}
+	}
+
 	/// Returns details of a BEEFY authority set.
 	///
 	/// Details contain authority set id, authority set length and a merkle root,
@@ -233,4 +270,18 @@ sp_api::decl_runtime_apis! {
 		/// Return the next/queued BEEFY authority set proof.
 		fn next_authority_set_proof() -> BeefyNextAuthoritySet<H>;
 	}
+
+	/// API returning the BEEFY validator sets along with the commitments BEEFY light clients
+	/// verify them against.
+	pub trait BeefyValidatorSetApi<AuthorityId, H>
+	where
+		AuthorityId: Codec,
+		H: Codec,
+	{
+		/// Return the currently active BEEFY validator set along with its commitments.
+		fn validator_set_commitment() -> Option<BeefyValidatorSetCommitment<AuthorityId, H>>;
+
+		/// Return the next/queued BEEFY validator set along with its commitments.
+		fn next_validator_set_commitment() -> Option<BeefyValidatorSetCommitment<AuthorityId, H>>;
+	}
 }
@@ -207,3 +207,30 @@ fn should_update_authorities() {
 		assert_eq!(want, next_auth_set.keyset_commitment);
 	});
 }
+
+#[test]
+fn should_return_validator_set_commitments() {
+	new_test_ext(vec![1, 2, 3, 4]).execute_with(|| {
+		let commitment = BeefyMmr::validator_set_commitment().unwrap();
+		let next_commitment = BeefyMmr::next_validator_set_commitment().unwrap();
+		let validators = Beefy::validator_set().unwrap().validators().to_vec();
+
+		// check current validator set
+		assert_eq!(0, commitment.id);
+		assert_eq!(validators, commitment.validators);
+		let want = binary_merkle_tree::merkle_root::<Keccak256, _>(
+			validators.iter().map(|validator| validator.encode()),
+		);
+		assert_eq!(want, commitment.public_keys_root);
+		assert_eq!(BeefyMmr::authority_set_proof(), commitment.authority_set);
+
+		// check next validator set
+		assert_eq!(1, next_commitment.id);
+		assert_eq!(BeefyMmr::next_authority_set_proof(), next_commitment.authority_set);
+
+		init_block(1);
+
+		// the announced set is now the current one
+		assert_eq!(Some(next_commitment), BeefyMmr::validator_set_commitment());
+	});
+}
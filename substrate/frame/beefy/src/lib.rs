@@ -364,6 +364,13 @@ impl<T: Config> Pallet<T> {
 		ValidatorSet::<T::BeefyId>::new(validators, id)
 	}
 
+	/// Return the BEEFY validator set queued to be used with the next session.
+	pub fn next_validator_set() -> Option<ValidatorSet<T::BeefyId>> {
+		let validators: BoundedVec<T::BeefyId, T::MaxAuthorities> = NextAuthorities::<T>::get();
+		let id: sp_consensus_beefy::ValidatorSetId = ValidatorSetId::<T>::get() + 1;
+		ValidatorSet::<T::BeefyId>::new(validators, id)
+	}
+
 	/// Submits an extrinsic to report an equivocation. This method will create
 	/// an unsigned extrinsic with a call to `report_equivocation_unsigned` and
 	/// will push the transaction to the pool. Only useful in an offchain context.
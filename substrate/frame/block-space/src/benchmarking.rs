@@ -0,0 +1,101 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmarks for the block space pallet.
+
+use super::*;
+use frame_benchmarking::v2::*;
+use frame_support::traits::fungible::{Inspect, Mutate};
+use frame_system::RawOrigin;
+
+/// A funded account and the weight it can reserve in `n` blocks starting at `start`.
+fn setup<T: Config>(
+	start: BlockNumberFor<T>,
+	n: u32,
+) -> Result<(T::AccountId, Weight), BenchmarkError> {
+	let weight = Weight::from_parts(Pallet::<T>::reservable_weight().ref_time().min(1_000_000), 0);
+	if weight.ref_time().is_zero() {
+		return Err(BenchmarkError::Weightless)
+	}
+
+	let caller: T::AccountId = whitelisted_caller();
+	let deposit = T::WeightPrice::convert(weight).saturating_mul(n.into());
+	T::Currency::set_balance(
+		&caller,
+		T::Currency::minimum_balance().saturating_add(deposit.saturating_mul(2u32.into())),
+	);
+	// The range must be free.
+	let mut block = start;
+	while block < start.saturating_add(n.into()) {
+		ReservedWeight::<T>::remove(block);
+		block.saturating_inc();
+	}
+	Ok((caller, weight))
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn reserve(n: Linear<1, { T::MaxLength::get() }>) -> Result<(), BenchmarkError> {
+		let start = frame_system::Pallet::<T>::block_number().saturating_add(1u32.into());
+		let (caller, weight) = setup::<T>(start, n)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), start, n, weight);
+
+		assert!(Reservations::<T>::contains_key(&caller));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn cancel(n: Linear<1, { T::MaxLength::get() }>) -> Result<(), BenchmarkError> {
+		let start = frame_system::Pallet::<T>::block_number().saturating_add(1u32.into());
+		let (caller, weight) = setup::<T>(start, n)?;
+		Pallet::<T>::reserve(RawOrigin::Signed(caller.clone()).into(), start, n, weight)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()));
+
+		assert!(!Reservations::<T>::contains_key(&caller));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn settle(n: Linear<1, { T::MaxLength::get() }>) -> Result<(), BenchmarkError> {
+		let start = frame_system::Pallet::<T>::block_number().saturating_add(1u32.into());
+		let (caller, weight) = setup::<T>(start, n)?;
+		Pallet::<T>::reserve(RawOrigin::Signed(caller.clone()).into(), start, n, weight)?;
+
+		// Part of the reservation was consumed, such that it is both burned and released.
+		Reservations::<T>::mutate(&caller, |reservation| {
+			if let Some(reservation) = reservation {
+				reservation.consumed = weight;
+			}
+		});
+		frame_system::Pallet::<T>::set_block_number(start.saturating_add(n.into()));
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), caller.clone());
+
+		assert!(!Reservations::<T>::contains_key(&caller));
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
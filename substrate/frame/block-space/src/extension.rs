@@ -0,0 +1,125 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{Config, Pallet};
+use codec::{Decode, Encode};
+use frame_support::{
+	dispatch::{DispatchClass, DispatchInfo},
+	traits::Get,
+	weights::Weight,
+};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{DispatchInfoOf, Dispatchable, SignedExtension},
+	transaction_validity::{
+		InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction,
+	},
+};
+use sp_std::{fmt, marker::PhantomData};
+
+/// Honor the block space reservations in the transaction queue.
+///
+/// The normal transactions fitting in the reservation of their signer get the
+/// [`Config::ReservedPriority`] and consume the reservation when dispatched. The other normal
+/// transactions are invalid in a block if they would consume its unused reserved weight.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct CheckBlockSpace<T: Config + Send + Sync>(PhantomData<T>);
+
+impl<T: Config + Send + Sync> Default for CheckBlockSpace<T> {
+	fn default() -> Self {
+		Self(Default::default())
+	}
+}
+
+impl<T: Config + Send + Sync> fmt::Debug for CheckBlockSpace<T> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "CheckBlockSpace")
+	}
+
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut fmt::Formatter) -> fmt::Result {
+		Ok(())
+	}
+}
+
+impl<T: Config + Send + Sync> CheckBlockSpace<T> {
+	/// Creates new `SignedExtension` to check the block space reservations.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// The weight of the given normal transaction of `len` bytes, if it is one.
+	///
+	/// The encoded transaction is part of the proof of the block, so its length is counted as
+	/// proof size next to the dispatch weight and the base extrinsic weight.
+	fn normal_weight(info: &DispatchInfo, len: usize) -> Option<Weight> {
+		(info.class == DispatchClass::Normal).then(|| {
+			let base = T::BlockWeights::get().get(DispatchClass::Normal).base_extrinsic;
+			info.weight
+				.saturating_add(base)
+				.saturating_add(Weight::from_parts(0, len as u64))
+		})
+	}
+}
+
+impl<T: Config + Send + Sync> SignedExtension for CheckBlockSpace<T>
+where
+	T::RuntimeCall: Dispatchable<Info = DispatchInfo>,
+{
+	const IDENTIFIER: &'static str = "CheckBlockSpace";
+	type AccountId = T::AccountId;
+	type Call = T::RuntimeCall;
+	type AdditionalSigned = ();
+	type Pre = ();
+
+	fn additional_signed(&self) -> Result<Self::AdditionalSigned, TransactionValidityError> {
+		Ok(())
+	}
+
+	fn validate(
+		&self,
+		who: &Self::AccountId,
+		_call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		len: usize,
+	) -> TransactionValidity {
+		let Some(weight) = Self::normal_weight(info, len) else { return Ok(Default::default()) };
+
+		if Pallet::<T>::weight_left(who).map_or(false, |left| weight.all_lte(left)) {
+			return Ok(ValidTransaction {
+				priority: T::ReservedPriority::get(),
+				..Default::default()
+			})
+		}
+		Ok(Default::default())
+	}
+
+	fn pre_dispatch(
+		self,
+		who: &Self::AccountId,
+		_call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		let Some(weight) = Self::normal_weight(info, len) else { return Ok(()) };
+
+		Pallet::<T>::note_transaction(who, weight)
+			.map_err(|()| InvalidTransaction::ExhaustsResources.into())
+	}
+}
@@ -0,0 +1,414 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Block Space Pallet
+//!
+//! A market for reserving block space ahead of time.
+//!
+//! ## Overview
+//!
+//! Accounts which need their transactions to be included in a timely manner, e.g. exchanges or
+//! bridge relayers, reserve some weight of the normal dispatch class in every block of a future
+//! block range with [`Pallet::reserve`]. At most [`Config::MaxReservedShare`] of the normal class
+//! can be reserved in a block.
+//!
+//! The price of the reservation, given by [`Config::WeightPrice`] for each block, is held when
+//! reserving. Once the range is over anyone can [`Pallet::settle`] the reservation: the share of
+//! the price matching the weight consumed by the transactions of the account is burned and the
+//! rest is released. A reservation can be cancelled with a full refund until its range starts.
+//!
+//! ## Transaction queue
+//!
+//! The reservations are enforced by the [`CheckBlockSpace`] signed extension:
+//!
+//! - the transactions of an account fitting in the weight left in its reservation get the
+//!   [`Config::ReservedPriority`], such that the block authors pick them first;
+//! - the other normal transactions are rejected with `ExhaustsResources` when they would consume
+//!   the unused reserved weight, which makes the block authors skip them until the next block.
+//!
+//! The extension must be placed before `frame_system::CheckWeight` in the signed extensions of
+//! the runtime, such that the weight of the block doesn't include the checked transaction yet.
+
+// Ensure we're `no_std` when compiling for Wasm.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+mod extension;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	dispatch::DispatchClass,
+	pallet_prelude::*,
+	traits::{
+		fungible::{self, hold::Mutate as HoldMutate},
+		tokens::{Fortitude, Precision},
+	},
+	weights::Weight,
+};
+use frame_system::pallet_prelude::*;
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{Convert, Saturating, Zero},
+	Perbill, RuntimeDebug, SaturatedConversion,
+};
+use sp_std::prelude::*;
+
+// Re-export pallet items so that they can be accessed from the crate namespace.
+pub use extension::CheckBlockSpace;
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+type BalanceOf<T> =
+	<<T as Config>::Currency as fungible::Inspect<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// A reservation of block space.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Reservation<BlockNumber, Balance> {
+	/// The first block of the reserved range.
+	pub start: BlockNumber,
+	/// The block following the reserved range.
+	pub end: BlockNumber,
+	/// The weight reserved in every block of the range.
+	pub weight: Weight,
+	/// The price held for the reservation.
+	pub deposit: Balance,
+	/// The reserved weight consumed over the whole range.
+	pub consumed: Weight,
+	/// The last block in which reserved weight was consumed.
+	pub consumed_at: BlockNumber,
+	/// The reserved weight consumed in `consumed_at`.
+	pub consumed_in_block: Weight,
+}
+
+impl<BlockNumber: PartialOrd + Copy, Balance> Reservation<BlockNumber, Balance> {
+	/// The reserved weight left in the block `now`, if the reservation is active in it.
+	pub fn weight_left(&self, now: BlockNumber) -> Option<Weight> {
+		if now < self.start || now >= self.end {
+			return None
+		}
+		let consumed =
+			if self.consumed_at == now { self.consumed_in_block } else { Weight::zero() };
+		Some(self.weight.saturating_sub(consumed))
+	}
+}
+
+/// The reservation type of the pallet.
+pub type ReservationOf<T> = Reservation<BlockNumberFor<T>, BalanceOf<T>>;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use sp_runtime::transaction_validity::TransactionPriority;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The currency in which the reservations are paid.
+		type Currency: fungible::Mutate<Self::AccountId>
+			+ HoldMutate<Self::AccountId, Reason = Self::RuntimeHoldReason>;
+
+		/// The overarching hold reason.
+		type RuntimeHoldReason: From<HoldReason>;
+
+		/// The price of reserving the given weight in one block.
+		type WeightPrice: Convert<Weight, BalanceOf<Self>>;
+
+		/// The maximum share of the normal dispatch class which can be reserved in a block.
+		#[pallet::constant]
+		type MaxReservedShare: Get<Perbill>;
+
+		/// The maximum number of blocks of a reservation.
+		#[pallet::constant]
+		type MaxLength: Get<u32>;
+
+		/// The maximum number of blocks between the reservation and the start of its range.
+		#[pallet::constant]
+		type MaxLeadTime: Get<u32>;
+
+		/// The priority of the transactions fitting in the reservation of their signer.
+		#[pallet::constant]
+		type ReservedPriority: Get<TransactionPriority>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// A reason for the pallet placing a hold on funds.
+	#[pallet::composite_enum]
+	pub enum HoldReason {
+		/// The price of a block space reservation.
+		#[codec(index = 0)]
+		Reservation,
+	}
+
+	/// The reservation of each account.
+	#[pallet::storage]
+	pub type Reservations<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, ReservationOf<T>, OptionQuery>;
+
+	/// The total weight reserved in each block.
+	#[pallet::storage]
+	pub type ReservedWeight<T: Config> =
+		StorageMap<_, Twox64Concat, BlockNumberFor<T>, Weight, ValueQuery>;
+
+	/// The reserved weight consumed in the current block, with the number of the block.
+	#[pallet::storage]
+	pub type ConsumedReservedWeight<T: Config> =
+		StorageValue<_, (BlockNumberFor<T>, Weight), ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Block space was reserved.
+		Reserved {
+			who: T::AccountId,
+			start: BlockNumberFor<T>,
+			end: BlockNumberFor<T>,
+			weight: Weight,
+			deposit: BalanceOf<T>,
+		},
+		/// A reservation was cancelled before its start.
+		Cancelled { who: T::AccountId, refund: BalanceOf<T> },
+		/// A reservation was settled after its end.
+		Settled { who: T::AccountId, charged: BalanceOf<T>, refund: BalanceOf<T> },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The account already has a reservation.
+		AlreadyReserved,
+		/// The account has no reservation.
+		NotReserved,
+		/// The reserved range must start after the current block.
+		StartInPast,
+		/// The reserved range starts too far in the future.
+		TooFarAhead,
+		/// The reserved range is empty or too long.
+		InvalidLength,
+		/// The reserved weight has no computation time.
+		ZeroWeight,
+		/// Not enough block space is left in one of the blocks of the range.
+		InsufficientBlockSpace,
+		/// The reserved range already started.
+		AlreadyStarted,
+		/// The reserved range is not over yet.
+		NotEnded,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Reserve `weight` in each of the `length` blocks starting at `start`.
+		///
+		/// The price of the reservation is held until it is cancelled or settled. An account can
+		/// have a single reservation at a time.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::reserve(*length))]
+		pub fn reserve(
+			origin: OriginFor<T>,
+			start: BlockNumberFor<T>,
+			length: u32,
+			weight: Weight,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(!Reservations::<T>::contains_key(&who), Error::<T>::AlreadyReserved);
+
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(start > now, Error::<T>::StartInPast);
+			ensure!(
+				start <= now.saturating_add(T::MaxLeadTime::get().into()),
+				Error::<T>::TooFarAhead
+			);
+			ensure!(length > 0 && length <= T::MaxLength::get(), Error::<T>::InvalidLength);
+			ensure!(!weight.ref_time().is_zero(), Error::<T>::ZeroWeight);
+
+			let capacity = Self::reservable_weight();
+			let end = start.saturating_add(length.into());
+			let mut reserved = Vec::with_capacity(length as usize);
+			let mut block = start;
+			while block < end {
+				let weight = ReservedWeight::<T>::get(block).saturating_add(weight);
+				ensure!(weight.all_lte(capacity), Error::<T>::InsufficientBlockSpace);
+				reserved.push((block, weight));
+				block.saturating_inc();
+			}
+
+			let deposit = T::WeightPrice::convert(weight).saturating_mul(length.into());
+			T::Currency::hold(&HoldReason::Reservation.into(), &who, deposit)?;
+
+			for (block, weight) in reserved {
+				ReservedWeight::<T>::insert(block, weight);
+			}
+
+			Reservations::<T>::insert(
+				&who,
+				Reservation {
+					start,
+					end,
+					weight,
+					deposit,
+					consumed: Weight::zero(),
+					consumed_at: Zero::zero(),
+					consumed_in_block: Weight::zero(),
+				},
+			);
+			Self::deposit_event(Event::Reserved { who, start, end, weight, deposit });
+			Ok(())
+		}
+
+		/// Cancel the reservation of the signer before its range starts, with a full refund.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::cancel(T::MaxLength::get()))]
+		pub fn cancel(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let reservation = Reservations::<T>::get(&who).ok_or(Error::<T>::NotReserved)?;
+
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(now < reservation.start, Error::<T>::AlreadyStarted);
+
+			let refund = Self::release(&who, &reservation, Zero::zero())?;
+			Self::deposit_event(Event::Cancelled { who, refund });
+			Ok(())
+		}
+
+		/// Settle the reservation of `who` once its range is over.
+		///
+		/// The share of the price matching the consumed weight is burned and the rest is released.
+		/// Can be called by anyone.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::settle(T::MaxLength::get()))]
+		pub fn settle(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			ensure_signed(origin)?;
+			let reservation = Reservations::<T>::get(&who).ok_or(Error::<T>::NotReserved)?;
+
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(now >= reservation.end, Error::<T>::NotEnded);
+
+			let length: u64 = reservation.end.saturating_sub(reservation.start).saturated_into();
+			let reserved = reservation.weight.ref_time().saturating_mul(length);
+			let charged = Perbill::from_rational(reservation.consumed.ref_time(), reserved) *
+				reservation.deposit;
+
+			let refund = Self::release(&who, &reservation, charged)?;
+			Self::deposit_event(Event::Settled { who, charged, refund });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The weight which can be reserved in a block.
+	pub fn reservable_weight() -> Weight {
+		let weights = T::BlockWeights::get();
+		let normal = weights.get(DispatchClass::Normal).max_total.unwrap_or(weights.max_block);
+		Weight::from_parts(
+			T::MaxReservedShare::get() * normal.ref_time(),
+			T::MaxReservedShare::get() * normal.proof_size(),
+		)
+	}
+
+	/// The reserved weight left to `who` in the current block.
+	pub fn weight_left(who: &T::AccountId) -> Option<Weight> {
+		let now = frame_system::Pallet::<T>::block_number();
+		Reservations::<T>::get(who).and_then(|reservation| reservation.weight_left(now))
+	}
+
+	/// The weight reserved in the current block and not consumed yet.
+	pub fn unused_reserved_weight() -> Weight {
+		let now = frame_system::Pallet::<T>::block_number();
+		let (consumed_at, consumed) = ConsumedReservedWeight::<T>::get();
+		let consumed = if consumed_at == now { consumed } else { Weight::zero() };
+		ReservedWeight::<T>::get(now).saturating_sub(consumed)
+	}
+
+	/// Account for a normal transaction of `who` consuming `weight` in the current block.
+	///
+	/// The transaction consumes the reservation of `who` if it fits in it. Otherwise it must fit
+	/// in the normal class next to the unused reserved weight, or an error is returned.
+	pub(crate) fn note_transaction(who: &T::AccountId, weight: Weight) -> Result<(), ()> {
+		let now = frame_system::Pallet::<T>::block_number();
+
+		if let Some(mut reservation) = Reservations::<T>::get(who) {
+			if reservation.weight_left(now).map_or(false, |left| weight.all_lte(left)) {
+				if reservation.consumed_at != now {
+					reservation.consumed_at = now;
+					reservation.consumed_in_block = Weight::zero();
+				}
+				reservation.consumed_in_block.saturating_accrue(weight);
+				reservation.consumed.saturating_accrue(weight);
+				Reservations::<T>::insert(who, reservation);
+
+				ConsumedReservedWeight::<T>::mutate(|(consumed_at, consumed)| {
+					if *consumed_at != now {
+						*consumed_at = now;
+						*consumed = Weight::zero();
+					}
+					consumed.saturating_accrue(weight);
+				});
+				return Ok(())
+			}
+		}
+
+		let weights = T::BlockWeights::get();
+		let max_normal = weights.get(DispatchClass::Normal).max_total.unwrap_or(weights.max_block);
+		let consumed = *frame_system::Pallet::<T>::block_weight().get(DispatchClass::Normal);
+		let needed = consumed.saturating_add(weight).saturating_add(Self::unused_reserved_weight());
+		if needed.any_gt(max_normal) {
+			return Err(())
+		}
+		Ok(())
+	}
+
+	/// Remove the reservation of `who`, burning `charged` from its deposit and releasing the
+	/// rest.
+	///
+	/// Returns the released amount.
+	fn release(
+		who: &T::AccountId,
+		reservation: &ReservationOf<T>,
+		charged: BalanceOf<T>,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		let mut block = reservation.start;
+		while block < reservation.end {
+			ReservedWeight::<T>::mutate_exists(block, |reserved| {
+				let left = reserved.unwrap_or_default().saturating_sub(reservation.weight);
+				*reserved = (!left.is_zero()).then_some(left);
+			});
+			block.saturating_inc();
+		}
+		Reservations::<T>::remove(who);
+
+		let reason = HoldReason::Reservation.into();
+		let charged = charged.min(reservation.deposit);
+		if !charged.is_zero() {
+			T::Currency::burn_held(&reason, who, charged, Precision::BestEffort, Fortitude::Force)?;
+		}
+		let refund = reservation.deposit.saturating_sub(charged);
+		let refund = T::Currency::release(&reason, who, refund, Precision::BestEffort)?;
+		Ok(refund)
+	}
+}
@@ -0,0 +1,113 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test environment for block space pallet.
+
+use crate as pallet_block_space;
+use frame_support::{
+	derive_impl,
+	dispatch::DispatchClass,
+	parameter_types,
+	traits::{ConstU32, ConstU64},
+	weights::Weight,
+};
+use frame_system::limits;
+use sp_runtime::{
+	traits::{Convert, IdentityLookup},
+	BuildStorage, Perbill,
+};
+
+pub type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test
+	{
+		System: frame_system,
+		Balances: pallet_balances,
+		BlockSpace: pallet_block_space,
+	}
+);
+
+/// The maximum weight of the normal transactions in a block.
+pub const MAX_NORMAL_WEIGHT: u64 = 750;
+
+parameter_types! {
+	pub RuntimeBlockWeights: limits::BlockWeights = limits::BlockWeights::builder()
+		.base_block(Weight::zero())
+		.for_class(DispatchClass::all(), |weights| {
+			weights.base_extrinsic = Weight::zero();
+		})
+		.for_class(DispatchClass::Normal, |weights| {
+			weights.max_total = Some(Weight::from_parts(MAX_NORMAL_WEIGHT, u64::MAX / 2));
+		})
+		.for_class(DispatchClass::Operational, |weights| {
+			weights.max_total = Some(Weight::from_parts(1000, u64::MAX));
+		})
+		.avg_block_initialization(Perbill::from_percent(0))
+		.build_or_panic();
+	pub const MaxReservedShare: Perbill = Perbill::from_percent(40);
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+	type Block = Block;
+	type BlockWeights = RuntimeBlockWeights;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type AccountId = u64;
+	type BlockHashCount = ConstU64<250>;
+	type Lookup = IdentityLookup<Self::AccountId>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+	type AccountStore = System;
+}
+
+/// A tenth of the computation time of the weight.
+pub struct WeightPrice;
+impl Convert<Weight, u64> for WeightPrice {
+	fn convert(weight: Weight) -> u64 {
+		weight.ref_time() / 10
+	}
+}
+
+impl pallet_block_space::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type WeightPrice = WeightPrice;
+	type MaxReservedShare = MaxReservedShare;
+	type MaxLength = ConstU32<10>;
+	type MaxLeadTime = ConstU32<20>;
+	type ReservedPriority = ConstU64<1_000>;
+	type WeightInfo = ();
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let t = RuntimeGenesisConfig {
+		system: Default::default(),
+		balances: pallet_balances::GenesisConfig::<Test> {
+			balances: vec![(1, 1_000), (2, 1_000), (3, 10)],
+		},
+	}
+	.build_storage()
+	.unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
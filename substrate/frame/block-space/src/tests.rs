@@ -0,0 +1,267 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for block space pallet.
+
+use super::{Error, Event, *};
+use crate::mock::*;
+use frame_support::{
+	assert_noop, assert_ok,
+	dispatch::{DispatchClass, DispatchInfo},
+	traits::fungible::InspectHold,
+};
+use sp_runtime::{
+	traits::SignedExtension,
+	transaction_validity::{InvalidTransaction, TransactionValidityError},
+	TokenError,
+};
+
+fn weight(ref_time: u64) -> Weight {
+	Weight::from_parts(ref_time, 0)
+}
+
+fn held(who: u64) -> u64 {
+	Balances::balance_on_hold(&HoldReason::Reservation.into(), &who)
+}
+
+fn info(ref_time: u64) -> DispatchInfo {
+	DispatchInfo { weight: weight(ref_time), ..Default::default() }
+}
+
+fn call() -> RuntimeCall {
+	RuntimeCall::System(frame_system::Call::remark { remark: vec![] })
+}
+
+fn dispatch(who: u64, ref_time: u64) -> Result<(), TransactionValidityError> {
+	dispatch_with_len(who, ref_time, 0)
+}
+
+fn dispatch_with_len(who: u64, ref_time: u64, len: usize) -> Result<(), TransactionValidityError> {
+	CheckBlockSpace::<Test>::new().pre_dispatch(&who, &call(), &info(ref_time), len)
+}
+
+fn fill_normal_class(ref_time: u64) {
+	frame_system::BlockWeight::<Test>::mutate(|consumed| {
+		consumed.accrue(weight(ref_time), DispatchClass::Normal)
+	});
+}
+
+#[test]
+fn reserve_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(BlockSpace::reserve(RuntimeOrigin::signed(1), 2, 3, weight(100)));
+
+		// A tenth of the weight for each of the three blocks.
+		assert_eq!(held(1), 30);
+		assert_eq!(
+			Reservations::<Test>::get(1),
+			Some(Reservation {
+				start: 2,
+				end: 5,
+				weight: weight(100),
+				deposit: 30,
+				consumed: Weight::zero(),
+				consumed_at: 0,
+				consumed_in_block: Weight::zero(),
+			})
+		);
+		assert_eq!(ReservedWeight::<Test>::get(1), Weight::zero());
+		for block in 2..5 {
+			assert_eq!(ReservedWeight::<Test>::get(block), weight(100));
+		}
+		assert_eq!(ReservedWeight::<Test>::get(5), Weight::zero());
+		System::assert_last_event(
+			Event::Reserved { who: 1, start: 2, end: 5, weight: weight(100), deposit: 30 }.into(),
+		);
+	});
+}
+
+#[test]
+fn reserve_checks_the_range_and_the_block_space() {
+	new_test_ext().execute_with(|| {
+		let origin = RuntimeOrigin::signed(1);
+		assert_noop!(
+			BlockSpace::reserve(origin.clone(), 1, 1, weight(100)),
+			Error::<Test>::StartInPast
+		);
+		assert_noop!(
+			BlockSpace::reserve(origin.clone(), 22, 1, weight(100)),
+			Error::<Test>::TooFarAhead
+		);
+		assert_noop!(
+			BlockSpace::reserve(origin.clone(), 2, 0, weight(100)),
+			Error::<Test>::InvalidLength
+		);
+		assert_noop!(
+			BlockSpace::reserve(origin.clone(), 2, 11, weight(100)),
+			Error::<Test>::InvalidLength
+		);
+		assert_noop!(
+			BlockSpace::reserve(origin.clone(), 2, 1, Weight::from_parts(0, 100)),
+			Error::<Test>::ZeroWeight
+		);
+
+		// 40% of the normal class can be reserved.
+		assert_eq!(BlockSpace::reservable_weight().ref_time(), 300);
+		assert_noop!(
+			BlockSpace::reserve(origin.clone(), 2, 1, weight(301)),
+			Error::<Test>::InsufficientBlockSpace
+		);
+		assert_ok!(BlockSpace::reserve(origin.clone(), 2, 3, weight(200)));
+		assert_noop!(BlockSpace::reserve(origin, 2, 1, weight(10)), Error::<Test>::AlreadyReserved);
+		assert_noop!(
+			BlockSpace::reserve(RuntimeOrigin::signed(2), 4, 2, weight(101)),
+			Error::<Test>::InsufficientBlockSpace
+		);
+		assert_ok!(BlockSpace::reserve(RuntimeOrigin::signed(2), 4, 2, weight(100)));
+		assert_eq!(ReservedWeight::<Test>::get(4), weight(300));
+
+		// The price must be held.
+		assert_noop!(
+			BlockSpace::reserve(RuntimeOrigin::signed(3), 6, 2, weight(100)),
+			TokenError::FundsUnavailable
+		);
+	});
+}
+
+#[test]
+fn cancel_refunds_before_the_start() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(BlockSpace::cancel(RuntimeOrigin::signed(1)), Error::<Test>::NotReserved);
+		assert_ok!(BlockSpace::reserve(RuntimeOrigin::signed(1), 2, 3, weight(100)));
+		assert_ok!(BlockSpace::reserve(RuntimeOrigin::signed(2), 3, 3, weight(100)));
+
+		System::set_block_number(2);
+		assert_noop!(BlockSpace::cancel(RuntimeOrigin::signed(1)), Error::<Test>::AlreadyStarted);
+
+		assert_ok!(BlockSpace::cancel(RuntimeOrigin::signed(2)));
+		assert_eq!(held(2), 0);
+		assert_eq!(Balances::free_balance(2), 1_000);
+		assert_eq!(Reservations::<Test>::get(2), None);
+		assert_eq!(ReservedWeight::<Test>::get(3), weight(100));
+		assert!(!ReservedWeight::<Test>::contains_key(5));
+		System::assert_last_event(Event::Cancelled { who: 2, refund: 30 }.into());
+	});
+}
+
+#[test]
+fn settle_charges_the_consumed_share() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(BlockSpace::reserve(RuntimeOrigin::signed(1), 2, 2, weight(100)));
+		assert_eq!(held(1), 20);
+
+		System::set_block_number(2);
+		assert_ok!(dispatch(1, 30));
+		assert_ok!(dispatch(1, 20));
+		assert_eq!(BlockSpace::weight_left(&1), Some(weight(50)));
+
+		System::set_block_number(3);
+		assert_eq!(BlockSpace::weight_left(&1), Some(weight(100)));
+		assert_noop!(BlockSpace::settle(RuntimeOrigin::signed(2), 1), Error::<Test>::NotEnded);
+
+		System::set_block_number(4);
+		assert_eq!(BlockSpace::weight_left(&1), None);
+		assert_ok!(BlockSpace::settle(RuntimeOrigin::signed(2), 1));
+
+		// 50 of the 200 reserved were consumed.
+		System::assert_last_event(Event::Settled { who: 1, charged: 5, refund: 15 }.into());
+		assert_eq!(held(1), 0);
+		assert_eq!(Balances::free_balance(1), 995);
+		assert_eq!(Reservations::<Test>::get(1), None);
+		assert_eq!(ReservedWeight::<Test>::iter().count(), 0);
+		assert_noop!(BlockSpace::settle(RuntimeOrigin::signed(2), 1), Error::<Test>::NotReserved);
+	});
+}
+
+#[test]
+fn reserved_transactions_are_prioritized() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(BlockSpace::reserve(RuntimeOrigin::signed(1), 2, 1, weight(100)));
+		let extension = CheckBlockSpace::<Test>::new();
+
+		// Not active yet.
+		let validity = extension.validate(&1, &call(), &info(100), 0).unwrap();
+		assert_eq!(validity.priority, 0);
+
+		System::set_block_number(2);
+		let validity = extension.validate(&1, &call(), &info(100), 0).unwrap();
+		assert_eq!(validity.priority, 1_000);
+		let validity = extension.validate(&1, &call(), &info(101), 0).unwrap();
+		assert_eq!(validity.priority, 0);
+		let validity = extension.validate(&2, &call(), &info(100), 0).unwrap();
+		assert_eq!(validity.priority, 0);
+
+		// Operational transactions are not affected.
+		let operational = DispatchInfo { class: DispatchClass::Operational, ..info(10) };
+		let validity = extension.validate(&1, &call(), &operational, 0).unwrap();
+		assert_eq!(validity.priority, 0);
+	});
+}
+
+#[test]
+fn unused_reserved_weight_is_kept_free() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(BlockSpace::reserve(RuntimeOrigin::signed(1), 2, 1, weight(100)));
+
+		System::set_block_number(2);
+		fill_normal_class(600);
+		assert_eq!(BlockSpace::unused_reserved_weight(), weight(100));
+
+		// 600 + 100 + 100 unused exceeds the 750 of the normal class.
+		assert_eq!(dispatch(2, 100), Err(InvalidTransaction::ExhaustsResources.into()));
+		assert_ok!(dispatch(2, 50));
+		fill_normal_class(50);
+
+		// The reservation can always be consumed.
+		assert_ok!(dispatch(1, 100));
+		fill_normal_class(100);
+		assert_eq!(BlockSpace::unused_reserved_weight(), Weight::zero());
+		assert_eq!(BlockSpace::weight_left(&1), Some(Weight::zero()));
+
+		// Transactions over the reservation are regular transactions.
+		assert_eq!(dispatch(1, 1), Err(InvalidTransaction::ExhaustsResources.into()));
+
+		// The reserved weight is free again in the next block.
+		System::set_block_number(3);
+		frame_system::BlockWeight::<Test>::kill();
+		assert_eq!(BlockSpace::unused_reserved_weight(), Weight::zero());
+		assert_ok!(dispatch(2, 1));
+	});
+}
+
+#[test]
+fn length_is_counted_as_proof_size() {
+	new_test_ext().execute_with(|| {
+		let reserved = Weight::from_parts(100, 50);
+		assert_ok!(BlockSpace::reserve(RuntimeOrigin::signed(1), 2, 1, reserved));
+		let extension = CheckBlockSpace::<Test>::new();
+
+		System::set_block_number(2);
+		let validity = extension.validate(&1, &call(), &info(10), 50).unwrap();
+		assert_eq!(validity.priority, 1_000);
+		let validity = extension.validate(&1, &call(), &info(10), 51).unwrap();
+		assert_eq!(validity.priority, 0);
+
+		assert_ok!(dispatch_with_len(1, 10, 40));
+		assert_eq!(BlockSpace::weight_left(&1), Some(Weight::from_parts(90, 10)));
+
+		// Too long for the reservation, dispatched as a regular transaction.
+		assert_ok!(dispatch_with_len(1, 10, 20));
+		assert_eq!(BlockSpace::weight_left(&1), Some(Weight::from_parts(90, 10)));
+		assert_eq!(BlockSpace::unused_reserved_weight(), Weight::from_parts(90, 10));
+	});
+}
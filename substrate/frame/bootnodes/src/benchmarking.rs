@@ -0,0 +1,75 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmarks for the bootnodes pallet.
+
+use super::*;
+use frame_benchmarking::v2::*;
+
+/// The addresses of `n` peers, as long as allowed.
+fn addresses<T: Config>(n: u32) -> PeerAddresses<T> {
+	const SUFFIX: &[u8] =
+		b".example.com/tcp/30333/p2p/12D3KooWEyoppNCUx8Yx66oV9fJnriXwCcXwDDUA2kj6vnc6iDEp";
+	let max_len = T::MaxAddressLength::get() as usize;
+
+	(0..n)
+		.map(|index| {
+			let mut address = b"/dns/".to_vec();
+			address.extend((0..4).map(|digit| b'a' + (index / 26u32.pow(digit) % 26) as u8));
+			address.resize(max_len.saturating_sub(SUFFIX.len()).max(address.len()), b'a');
+			address.extend_from_slice(SUFFIX);
+			address
+				.try_into()
+				.expect("`MaxAddressLength` allows the addresses of the benchmarks")
+		})
+		.collect::<Vec<_>>()
+		.try_into()
+		.expect("`n` is at most `MaxPeers`")
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn set_bootnodes(n: Linear<0, { T::MaxPeers::get() }>) -> Result<(), BenchmarkError> {
+		let origin =
+			T::ManagerOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let addresses = addresses::<T>(n);
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, addresses.clone());
+
+		assert_eq!(Bootnodes::<T>::get(), addresses);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_reserved_peers(n: Linear<0, { T::MaxPeers::get() }>) -> Result<(), BenchmarkError> {
+		let origin =
+			T::ManagerOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let addresses = addresses::<T>(n);
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, addresses.clone());
+
+		assert_eq!(ReservedPeers::<T>::get(), addresses);
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,176 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Bootnodes Pallet
+//!
+//! Publishes bootnodes and reserved peers on-chain.
+//!
+//! ## Overview
+//!
+//! The [`Config::ManagerOrigin`] sets the addresses of the bootnodes and of the reserved peers of
+//! the chain, e.g. the collators of a parachain or the nodes of its operators. The nodes started
+//! with `--onchain-peers` fetch them through the `sp_bootnodes::BootnodesApi` runtime api at
+//! startup and periodically, such that the peers can be rotated without every node operator
+//! updating their `--bootnodes` and `--reserved-nodes`.
+//!
+//! The addresses are UTF-8 multiaddresses ending with the `/p2p/` component of the peer id, e.g.
+//! `/dns/bootnode.example.com/tcp/30333/p2p/12D3KooW...`. Only this shape is checked on-chain,
+//! the nodes ignore the addresses they can't parse.
+
+// Ensure we're `no_std` when compiling for Wasm.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+use frame_support::{pallet_prelude::*, BoundedVec};
+use sp_std::prelude::*;
+
+// Re-export pallet items so that they can be accessed from the crate namespace.
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+/// The address of a peer.
+pub type PeerAddress<T> = BoundedVec<u8, <T as Config>::MaxAddressLength>;
+
+/// The addresses of a list of peers.
+pub type PeerAddresses<T> = BoundedVec<PeerAddress<T>, <T as Config>::MaxPeers>;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The origin allowed to set the bootnodes and the reserved peers.
+		type ManagerOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The maximum number of bootnodes, and of reserved peers.
+		#[pallet::constant]
+		type MaxPeers: Get<u32>;
+
+		/// The maximum length of the address of a peer.
+		#[pallet::constant]
+		type MaxAddressLength: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// The addresses of the bootnodes.
+	#[pallet::storage]
+	pub type Bootnodes<T: Config> = StorageValue<_, PeerAddresses<T>, ValueQuery>;
+
+	/// The addresses of the reserved peers.
+	#[pallet::storage]
+	pub type ReservedPeers<T: Config> = StorageValue<_, PeerAddresses<T>, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The bootnodes were replaced.
+		BootnodesSet { count: u32 },
+		/// The reserved peers were replaced.
+		ReservedPeersSet { count: u32 },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// An address is not a UTF-8 multiaddress ending with the `/p2p/` component of a peer id.
+		InvalidAddress,
+		/// An address is given more than once.
+		DuplicateAddress,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Replace the bootnodes with the given addresses.
+		///
+		/// Must be called by the [`Config::ManagerOrigin`].
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::set_bootnodes(addresses.len() as u32))]
+		pub fn set_bootnodes(origin: OriginFor<T>, addresses: PeerAddresses<T>) -> DispatchResult {
+			T::ManagerOrigin::ensure_origin(origin)?;
+			Self::ensure_valid(&addresses)?;
+
+			let count = addresses.len() as u32;
+			Bootnodes::<T>::put(addresses);
+			Self::deposit_event(Event::BootnodesSet { count });
+			Ok(())
+		}
+
+		/// Replace the reserved peers with the given addresses.
+		///
+		/// The nodes disconnect from the removed peers only if they have no free slot for them.
+		/// Must be called by the [`Config::ManagerOrigin`].
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::set_reserved_peers(addresses.len() as u32))]
+		pub fn set_reserved_peers(
+			origin: OriginFor<T>,
+			addresses: PeerAddresses<T>,
+		) -> DispatchResult {
+			T::ManagerOrigin::ensure_origin(origin)?;
+			Self::ensure_valid(&addresses)?;
+
+			let count = addresses.len() as u32;
+			ReservedPeers::<T>::put(addresses);
+			Self::deposit_event(Event::ReservedPeersSet { count });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The addresses of the bootnodes, for the `BootnodesApi`.
+	pub fn bootnodes() -> Vec<Vec<u8>> {
+		Bootnodes::<T>::get().into_iter().map(Into::into).collect()
+	}
+
+	/// The addresses of the reserved peers, for the `BootnodesApi`.
+	pub fn reserved_peers() -> Vec<Vec<u8>> {
+		ReservedPeers::<T>::get().into_iter().map(Into::into).collect()
+	}
+
+	fn ensure_valid(addresses: &PeerAddresses<T>) -> DispatchResult {
+		for (index, address) in addresses.iter().enumerate() {
+			let address = core::str::from_utf8(address).map_err(|_| Error::<T>::InvalidAddress)?;
+			let peer_id = address.rsplit_once("/p2p/").map(|(_, peer_id)| peer_id);
+			ensure!(
+				address.starts_with('/') &&
+					peer_id.map_or(false, |id| !id.is_empty() && !id.contains('/')),
+				Error::<T>::InvalidAddress
+			);
+			ensure!(
+				!addresses[..index].iter().any(|other| other.as_slice() == address.as_bytes()),
+				Error::<T>::DuplicateAddress
+			);
+		}
+		Ok(())
+	}
+}
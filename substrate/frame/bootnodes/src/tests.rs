@@ -0,0 +1,131 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for bootnodes pallet.
+
+use super::{Bootnodes as BootnodesStorage, Error, Event, PeerAddresses, ReservedPeers};
+use crate::mock::*;
+use frame_support::{assert_noop, assert_ok};
+use frame_system::RawOrigin;
+use sp_runtime::DispatchError;
+
+const PEER: &str =
+	"/dns/boot.example.com/tcp/30333/p2p/12D3KooWEyoppNCUx8Yx66oV9fJnriXwCcXwDDUA2kj6vnc6iDEp";
+const OTHER_PEER: &str =
+	"/ip4/127.0.0.1/tcp/30333/p2p/12D3KooWHdiAxVd8uMQR1hGWXccidmfCwLqcMpGwR6QcTP6QRMuD";
+
+fn addresses(addresses: &[&str]) -> PeerAddresses<Test> {
+	addresses
+		.iter()
+		.map(|address| address.as_bytes().to_vec().try_into().unwrap())
+		.collect::<Vec<_>>()
+		.try_into()
+		.unwrap()
+}
+
+#[test]
+fn sets_bootnodes() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Bootnodes::set_bootnodes(
+			RawOrigin::Root.into(),
+			addresses(&[PEER, OTHER_PEER])
+		));
+
+		assert_eq!(BootnodesStorage::<Test>::get(), addresses(&[PEER, OTHER_PEER]));
+		assert_eq!(
+			Bootnodes::bootnodes(),
+			vec![PEER.as_bytes().to_vec(), OTHER_PEER.as_bytes().to_vec()]
+		);
+		assert!(Bootnodes::reserved_peers().is_empty());
+		System::assert_last_event(Event::BootnodesSet { count: 2 }.into());
+
+		// The addresses are replaced, not appended.
+		assert_ok!(Bootnodes::set_bootnodes(RawOrigin::Root.into(), addresses(&[OTHER_PEER])));
+		assert_eq!(Bootnodes::bootnodes(), vec![OTHER_PEER.as_bytes().to_vec()]);
+		System::assert_last_event(Event::BootnodesSet { count: 1 }.into());
+	});
+}
+
+#[test]
+fn sets_reserved_peers() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Bootnodes::set_reserved_peers(RawOrigin::Root.into(), addresses(&[PEER])));
+
+		assert_eq!(ReservedPeers::<Test>::get(), addresses(&[PEER]));
+		assert_eq!(Bootnodes::reserved_peers(), vec![PEER.as_bytes().to_vec()]);
+		assert!(Bootnodes::bootnodes().is_empty());
+		System::assert_last_event(Event::ReservedPeersSet { count: 1 }.into());
+
+		assert_ok!(Bootnodes::set_reserved_peers(RawOrigin::Root.into(), addresses(&[])));
+		assert!(Bootnodes::reserved_peers().is_empty());
+	});
+}
+
+#[test]
+fn requires_manager_origin() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Bootnodes::set_bootnodes(RawOrigin::Signed(1).into(), addresses(&[PEER])),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Bootnodes::set_reserved_peers(RawOrigin::Signed(1).into(), addresses(&[PEER])),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn rejects_invalid_addresses() {
+	new_test_ext().execute_with(|| {
+		for invalid in [
+			"",
+			"12D3KooWEyoppNCUx8Yx66oV9fJnriXwCcXwDDUA2kj6vnc6iDEp",
+			"/dns/boot.example.com/tcp/30333",
+			"/dns/boot.example.com/tcp/30333/p2p/",
+			"/p2p/12D3KooWEyoppNCUx8Yx66oV9fJnriXwCcXwDDUA2kj6vnc6iDEp/tcp/30333",
+		] {
+			assert_noop!(
+				Bootnodes::set_bootnodes(RawOrigin::Root.into(), addresses(&[PEER, invalid])),
+				Error::<Test>::InvalidAddress
+			);
+		}
+
+		let not_utf8 = vec![vec![b'/', 0xff].try_into().unwrap()].try_into().unwrap();
+		assert_noop!(
+			Bootnodes::set_reserved_peers(RawOrigin::Root.into(), not_utf8),
+			Error::<Test>::InvalidAddress
+		);
+	});
+}
+
+#[test]
+fn rejects_duplicate_addresses() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Bootnodes::set_bootnodes(RawOrigin::Root.into(), addresses(&[PEER, OTHER_PEER, PEER])),
+			Error::<Test>::DuplicateAddress
+		);
+		assert_noop!(
+			Bootnodes::set_reserved_peers(
+				RawOrigin::Root.into(),
+				addresses(&[OTHER_PEER, OTHER_PEER])
+			),
+			Error::<Test>::DuplicateAddress
+		);
+	});
+}
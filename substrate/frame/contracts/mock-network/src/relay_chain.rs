@@ -230,6 +230,7 @@ impl pallet_message_queue::Config for Runtime {
 	type QueueChangeHandler = ();
 	type WeightInfo = ();
 	type QueuePausedQuery = ();
+	type ServiceShares = ();
 }
 
 construct_runtime!(
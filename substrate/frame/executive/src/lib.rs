@@ -167,9 +167,10 @@ use frame_support::{
 use frame_system::pallet_prelude::BlockNumberFor;
 use sp_runtime::{
 	generic::Digest,
+	signature_batch::{self, SignerAccountOf},
 	traits::{
-		self, Applyable, CheckEqual, Checkable, Dispatchable, Header, NumberFor, One,
-		ValidateUnsigned, Zero,
+		self, Applyable, BatchCheckable, CheckEqual, Checkable, Dispatchable, Header, NumberFor,
+		One, ValidateUnsigned, Zero,
 	},
 	transaction_validity::{TransactionSource, TransactionValidity},
 	ApplyExtrinsicResult, ExtrinsicInclusionMode,
@@ -179,7 +180,9 @@ use sp_std::{marker::PhantomData, prelude::*};
 #[cfg(feature = "try-runtime")]
 use ::{
 	frame_support::{
-		traits::{TryDecodeEntireStorage, TryDecodeEntireStorageError, TryState},
+		traits::{
+			PalletsInfoAccess, TryDecodeEntireStorage, TryDecodeEntireStorageError, TryState,
+		},
 		StorageNoopGuard,
 	},
 	frame_try_runtime::{TryStateSelect, UpgradeCheckSelect},
@@ -193,6 +196,7 @@ const LOG_TARGET: &str = "runtime::executive";
 pub type CheckedOf<E, C> = <E as Checkable<C>>::Checked;
 pub type CallOf<E, C> = <CheckedOf<E, C> as Applyable>::Call;
 pub type OriginOf<E, C> = <CallOf<E, C> as Dispatchable>::RuntimeOrigin;
+pub type SignatureOf<E, C> = <E as BatchCheckable<C>>::Signature;
 
 /// Main entry point for certain runtime actions as e.g. `execute_block`.
 ///
@@ -278,7 +282,8 @@ impl<
 			+ OffchainWorker<BlockNumberFor<System>>
 			+ OnPoll<BlockNumberFor<System>>
 			+ TryState<BlockNumberFor<System>>
-			+ TryDecodeEntireStorage,
+			+ TryDecodeEntireStorage
+			+ PalletsInfoAccess,
 		COnRuntimeUpgrade: OnRuntimeUpgrade,
 	> Executive<System, Block, Context, UnsignedValidator, AllPalletsWithSystem, COnRuntimeUpgrade>
 where
@@ -452,6 +457,7 @@ where
 		if checks.any() {
 			let res = AllPalletsWithSystem::try_decode_entire_state();
 			Self::log_decode_result(res)?;
+			Self::log_storage_usage();
 		}
 
 		// Check all storage invariants:
@@ -465,6 +471,23 @@ where
 		Ok(before_all_weight.saturating_add(try_on_runtime_upgrade_weight))
 	}
 
+	/// Logs the storage used by each of the pallets, the largest first.
+	fn log_storage_usage() {
+		let mut usage = frame_support::storage::usage::storage_usage::<AllPalletsWithSystem>();
+		usage.sort_by_key(|usage| sp_std::cmp::Reverse(usage.total_bytes()));
+
+		for usage in usage {
+			log::info!(
+				target: LOG_TARGET,
+				"📦 `{}` uses {} keys, {} bytes of keys and {} bytes of values.",
+				sp_std::str::from_utf8(&usage.pallet_name).unwrap_or("<invalid>"),
+				usage.keys,
+				usage.key_bytes,
+				usage.value_bytes,
+			);
+		}
+	}
+
 	/// Logs the result of trying to decode the entire state.
 	fn log_decode_result(
 		res: Result<usize, Vec<TryDecodeEntireStorageError>>,
@@ -635,6 +658,11 @@ where
 
 	/// Actually execute all transitions for `block`.
 	pub fn execute_block(block: Block) {
+		Self::execute_block_with(block, |extrinsics| Self::apply_extrinsics(extrinsics.into_iter()))
+	}
+
+	/// Execute all transitions for `block`, applying its extrinsics with `apply_extrinsics`.
+	fn execute_block_with(block: Block, apply_extrinsics: impl FnOnce(Vec<Block::Extrinsic>)) {
 		sp_io::init_tracing();
 		sp_tracing::within_span! {
 			sp_tracing::info_span!("execute_block", ?block);
@@ -649,7 +677,7 @@ where
 				panic!("Only inherents are allowed in this block")
 			}
 
-			Self::apply_extrinsics(extrinsics.into_iter());
+			apply_extrinsics(extrinsics);
 
 			// In this case there were no transactions to trigger this state transition:
 			if !<frame_system::Pallet<System>>::inherents_applied() {
@@ -902,3 +930,55 @@ where
 		)
 	}
 }
+
+impl<
+		System: frame_system::Config + EnsureInherentsAreFirst<Block>,
+		Block: traits::Block<
+			Header = frame_system::pallet_prelude::HeaderFor<System>,
+			Hash = System::Hash,
+		>,
+		Context: Default,
+		UnsignedValidator,
+		AllPalletsWithSystem: OnRuntimeUpgrade
+			+ BeforeAllRuntimeMigrations
+			+ OnInitialize<BlockNumberFor<System>>
+			+ OnIdle<BlockNumberFor<System>>
+			+ OnFinalize<BlockNumberFor<System>>
+			+ OffchainWorker<BlockNumberFor<System>>
+			+ OnPoll<BlockNumberFor<System>>,
+		COnRuntimeUpgrade: OnRuntimeUpgrade,
+	> Executive<System, Block, Context, UnsignedValidator, AllPalletsWithSystem, COnRuntimeUpgrade>
+where
+	Block::Extrinsic: BatchCheckable<Context> + Codec,
+	SignatureOf<Block::Extrinsic, Context>: Encode,
+	SignerAccountOf<SignatureOf<Block::Extrinsic, Context>>: Encode,
+	CheckedOf<Block::Extrinsic, Context>: Applyable + GetDispatchInfo,
+	CallOf<Block::Extrinsic, Context>:
+		Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
+	OriginOf<Block::Extrinsic, Context>: From<Option<System::AccountId>>,
+	UnsignedValidator: ValidateUnsigned<Call = CallOf<Block::Extrinsic, Context>>,
+{
+	/// Execute all transitions for `block`, verifying the signatures of its extrinsics in batches.
+	///
+	/// The block is executed exactly as by [`Self::execute_block`], only faster for blocks with
+	/// many signed extrinsics: their signatures, and the ones given by the
+	/// [`SignedExtension::signature_items`](sp_runtime::traits::SignedExtension::signature_items)
+	/// of their signed extensions, are verified together before they are applied. The bad ones are
+	/// verified again, one by one, when applying their extrinsic.
+	///
+	/// Runtimes opt in by calling this function instead of [`Self::execute_block`] in their
+	/// `Core::execute_block` implementation.
+	pub fn execute_block_with_batch_verification(block: Block) {
+		Self::execute_block_with(block, |extrinsics| {
+			let context = Context::default();
+			let items = extrinsics
+				.iter()
+				.flat_map(|uxt| uxt.signature_items(&context))
+				.collect::<Vec<_>>();
+
+			signature_batch::with_verified(&items, || {
+				Self::apply_extrinsics(extrinsics.into_iter())
+			})
+		})
+	}
+}
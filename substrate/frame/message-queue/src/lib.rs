@@ -124,6 +124,12 @@
 //! to advance to the next *ready* queue and service it. This continues until there are no more
 //! queues on which it can make progress or not enough weight to check that.
 //!
+//! The [`Config::ServiceShares`] can reserve a share of the weight limit for classes of queues. A
+//! queue may not use the weight that is still reserved for other classes. Once no queue can make
+//! progress anymore, the unused reservations are released and all queues are serviced again with
+//! the remaining weight. This guarantees each class a minimal share, even when the queue of
+//! another class is flooded.
+//!
 //! # Scenario: Overweight execution
 //!
 //! A permanently over-weight message which was skipped by the message processing will never be
@@ -210,7 +216,7 @@ use frame_support::{
 	traits::{
 		Defensive, DefensiveSaturating, DefensiveTruncateFrom, EnqueueMessage,
 		ExecuteOverweightError, Footprint, ProcessMessage, ProcessMessageError, QueueFootprint,
-		QueuePausedQuery, ServiceQueues,
+		QueuePausedQuery, QueueServiceShares, ServiceQueues,
 	},
 	BoundedSlice, CloneNoBound, DefaultNoBound,
 };
@@ -510,6 +516,11 @@ pub mod pallet {
 		/// it that happen *within* the servicing will not be reflected.
 		type QueuePausedQuery: QueuePausedQuery<<Self::MessageProcessor as ProcessMessage>::Origin>;
 
+		/// The shares of the service weight that are reserved for classes of queues.
+		///
+		/// Use `()` to service all queues from the same weight.
+		type ServiceShares: QueueServiceShares<<Self::MessageProcessor as ProcessMessage>::Origin>;
+
 		/// The size of the page; this implies the maximum message size which can be sent.
 		///
 		/// A good value depends on the expected message sizes, their weights, the weight that is
@@ -1138,6 +1149,81 @@ impl<T: Config> Pallet<T> {
 		(total_processed > 0, next_ready)
 	}
 
+	/// Service the queue of `origin` like [`Self::service_queue`], but without using any of the
+	/// weight which is still `reserved` for the other classes.
+	///
+	/// The weight consumed by the queue is deducted from the reservation of its own class.
+	fn service_queue_within_share(
+		origin: MessageOriginOf<T>,
+		weight: &mut WeightMeter,
+		overweight_limit: Weight,
+		reserved: &mut [(ServiceClassOf<T>, Weight)],
+	) -> (bool, Option<MessageOriginOf<T>>) {
+		if reserved.is_empty() {
+			return Self::service_queue(origin, weight, overweight_limit)
+		}
+
+		let class = T::ServiceShares::class_of(&origin);
+		let reserved_for_others = reserved
+			.iter()
+			.filter(|(c, _)| *c != class)
+			.fold(Weight::zero(), |acc, (_, w)| acc.saturating_add(*w));
+		let mut meter =
+			WeightMeter::with_limit(weight.remaining().saturating_sub(reserved_for_others));
+		let (progressed, next) = Self::service_queue(origin.clone(), &mut meter, overweight_limit);
+
+		weight.consume(meter.consumed());
+		if let Some((_, w)) = reserved.iter_mut().find(|(c, _)| *c == class) {
+			*w = w.saturating_sub(meter.consumed());
+		}
+		// The reservations may leave too little weight to even look at this queue, in which case
+		// the next queue could still be serviced.
+		let base_weight =
+			T::WeightInfo::service_queue_base().saturating_add(T::WeightInfo::ready_ring_unknit());
+		let next = match next {
+			None if weight.can_consume(base_weight) =>
+				BookStateFor::<T>::get(&origin).ready_neighbours.map(|n| n.next),
+			next => next,
+		};
+		(progressed, next)
+	}
+
+	/// Service the ready ring starting at `next` until no queue can make progress anymore.
+	///
+	/// Returns the queue at which it stopped, if any queue is still ready.
+	fn service_ready_ring(
+		mut next: MessageOriginOf<T>,
+		weight: &mut WeightMeter,
+		overweight_limit: Weight,
+		reserved: &mut [(ServiceClassOf<T>, Weight)],
+	) -> Option<MessageOriginOf<T>> {
+		// The last queue that did not make any progress.
+		// The loop aborts as soon as it arrives at this queue again without making any progress
+		// on other queues in between.
+		let mut last_no_progress = None;
+
+		loop {
+			let (progressed, n) =
+				Self::service_queue_within_share(next.clone(), weight, overweight_limit, reserved);
+			next = match n {
+				Some(n) =>
+					if !progressed {
+						if last_no_progress == Some(n.clone()) {
+							return Some(n)
+						}
+						if last_no_progress.is_none() {
+							last_no_progress = Some(next.clone())
+						}
+						n
+					} else {
+						last_no_progress = None;
+						n
+					},
+				None => return None,
+			}
+		}
+	}
+
 	/// Service as many messages of a page as possible.
 	///
 	/// Returns how many messages were processed and the page's status.
@@ -1529,6 +1615,9 @@ pub type HeapSizeU32Of<T> = IntoU32<<T as Config>::HeapSize, <T as Config>::Size
 pub type PageOf<T> = Page<<T as Config>::Size, <T as Config>::HeapSize>;
 /// The [`BookState`] of this pallet.
 pub type BookStateOf<T> = BookState<MessageOriginOf<T>>;
+/// The class of a queue as assigned by the [`Config::ServiceShares`].
+pub type ServiceClassOf<T> =
+	<<T as Config>::ServiceShares as QueueServiceShares<MessageOriginOf<T>>>::Class;
 
 /// Converts a [`sp_core::Get`] with returns a type that can be cast into an `u32` into a `Get`
 /// which returns an `u32`.
@@ -1552,33 +1641,19 @@ impl<T: Config> ServiceQueues for Pallet<T> {
 		});
 
 		match with_service_mutex(|| {
-			let mut next = match Self::bump_service_head(&mut weight) {
+			let next = match Self::bump_service_head(&mut weight) {
 				Some(h) => h,
 				None => return weight.consumed(),
 			};
-			// The last queue that did not make any progress.
-			// The loop aborts as soon as it arrives at this queue again without making any progress
-			// on other queues in between.
-			let mut last_no_progress = None;
-
-			loop {
-				let (progressed, n) = Self::service_queue(next.clone(), &mut weight, max_weight);
-				next = match n {
-					Some(n) =>
-						if !progressed {
-							if last_no_progress == Some(n.clone()) {
-								break
-							}
-							if last_no_progress.is_none() {
-								last_no_progress = Some(next.clone())
-							}
-							n
-						} else {
-							last_no_progress = None;
-							n
-						},
-					None => break,
-				}
+			let mut reserved = T::ServiceShares::shares()
+				.into_iter()
+				.map(|(class, share)| (class, share * weight.remaining()))
+				.collect::<Vec<_>>();
+
+			let next = Self::service_ready_ring(next, &mut weight, max_weight, &mut reserved);
+			// Release the unused reservations and give all queues another chance.
+			if let Some(next) = next.filter(|_| reserved.iter().any(|(_, w)| !w.is_zero())) {
+				Self::service_ready_ring(next, &mut weight, max_weight, &mut []);
 			}
 			weight.consumed()
 		}) {
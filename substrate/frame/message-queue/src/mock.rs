@@ -24,7 +24,7 @@ use super::*;
 
 use crate as pallet_message_queue;
 use frame_support::{derive_impl, parameter_types};
-use sp_runtime::BuildStorage;
+use sp_runtime::{BuildStorage, Perbill};
 use sp_std::collections::btree_map::BTreeMap;
 
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -53,6 +53,7 @@ impl Config for Test {
 	type Size = u32;
 	type QueueChangeHandler = RecordingQueueChangeHandler;
 	type QueuePausedQuery = MockedQueuePauser;
+	type ServiceShares = MockedServiceShares;
 	type HeapSize = HeapSize;
 	type MaxStale = MaxStale;
 	type ServiceWeight = ServiceWeight;
@@ -283,6 +284,24 @@ impl QueuePausedQuery<MessageOrigin> for MockedQueuePauser {
 	}
 }
 
+parameter_types! {
+	pub static QueueShares: Vec<(MessageOrigin, Perbill)> = vec![];
+}
+
+/// Reserves the [`QueueShares`] for single queues.
+pub struct MockedServiceShares;
+impl QueueServiceShares<MessageOrigin> for MockedServiceShares {
+	type Class = MessageOrigin;
+
+	fn class_of(origin: &MessageOrigin) -> Self::Class {
+		origin.clone()
+	}
+
+	fn shares() -> Vec<(Self::Class, Perbill)> {
+		QueueShares::get()
+	}
+}
+
 /// Create new test externalities.
 ///
 /// Is generic since it is used by the unit test, integration tests and benchmarks.
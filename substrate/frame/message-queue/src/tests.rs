@@ -166,6 +166,47 @@ fn service_queues_basic_works() {
 	});
 }
 
+#[test]
+fn service_queues_respects_service_shares() {
+	use MessageOrigin::*;
+	build_and_execute::<Test>(|| {
+		QueueShares::set(vec![(There, Perbill::from_percent(50))]);
+		let flood = (0..10).map(|_| msg("a")).collect::<Vec<_>>();
+		MessageQueue::enqueue_messages(flood.into_iter(), Here);
+		MessageQueue::enqueue_messages(vec![msg("x"), msg("y"), msg("z")].into_iter(), There);
+
+		// `Here` cannot use the half that is reserved for `There`.
+		assert_eq!(MessageQueue::service_queues(4.into_weight()), 4.into_weight());
+		assert_eq!(
+			MessagesProcessed::take(),
+			vec![(vmsg("a"), Here), (vmsg("a"), Here), (vmsg("x"), There), (vmsg("y"), There)]
+		);
+		MessageQueue::do_try_state().unwrap();
+	});
+}
+
+#[test]
+fn service_queues_releases_unused_service_shares() {
+	use MessageOrigin::*;
+	build_and_execute::<Test>(|| {
+		QueueShares::set(vec![
+			(There, Perbill::from_percent(50)),
+			(Everywhere(1), Perbill::from_percent(25)),
+		]);
+		let flood = (0..10).map(|_| msg("a")).collect::<Vec<_>>();
+		MessageQueue::enqueue_messages(flood.into_iter(), Here);
+		MessageQueue::enqueue_message(msg("x"), There);
+
+		// The reservations which are not used by `There` and `Everywhere(1)` go to `Here`.
+		assert_eq!(MessageQueue::service_queues(4.into_weight()), 4.into_weight());
+		assert_eq!(
+			MessagesProcessed::take(),
+			vec![(vmsg("a"), Here), (vmsg("x"), There), (vmsg("a"), Here), (vmsg("a"), Here)]
+		);
+		MessageQueue::do_try_state().unwrap();
+	});
+}
+
 #[test]
 fn service_queues_failing_messages_works() {
 	use MessageOrigin::*;
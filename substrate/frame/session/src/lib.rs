@@ -83,6 +83,8 @@
 //!   for next session rotation.
 //! - `disable_index` - Disable a validator by index.
 //! - `disable` - Disable a validator by Validator ID
+//! - `queued_validator_set` - The validator set of the next session, for the `SessionValidatorsApi`
+//!   runtime API.
 //!
 //! ## Usage
 //!
@@ -131,6 +133,7 @@ use sp_runtime::{
 	traits::{AtLeast32BitUnsigned, Convert, Member, One, OpaqueKeys, Zero},
 	ConsensusEngineId, DispatchError, KeyTypeId, Permill, RuntimeAppPublic,
 };
+use sp_session::QueuedValidatorSet;
 use sp_staking::SessionIndex;
 use sp_std::{
 	marker::PhantomData,
@@ -383,7 +386,7 @@ pub mod pallet {
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		/// The overarching event type.
-		type RuntimeEvent: From<Event> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
 		/// A stable ID for a validator.
 		type ValidatorId: Member
@@ -525,10 +528,13 @@ pub mod pallet {
 
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
-	pub enum Event {
+	pub enum Event<T: Config> {
 		/// New session has happened. Note that the argument is the session index, not the
 		/// block number as the type might suggest.
 		NewSession { session_index: SessionIndex },
+		/// The validator set of the next session was queued and differs from the current one,
+		/// by its validators or their keys. It becomes active at the start of `session_index`.
+		NewQueued { session_index: SessionIndex, validators: Vec<T::ValidatorId> },
 	}
 
 	/// Error for the session pallet.
@@ -686,11 +692,30 @@ impl<T: Config> Pallet<T> {
 
 		// Record that this happened.
 		Self::deposit_event(Event::NewSession { session_index });
+		if next_changed {
+			Self::deposit_event(Event::NewQueued {
+				session_index: session_index + 1,
+				validators: queued_amalgamated.iter().map(|(v, _)| v.clone()).collect(),
+			});
+		}
 
 		// Tell everyone about the new session keys.
 		T::SessionHandler::on_new_session::<T::Keys>(changed, &session_keys, &queued_amalgamated);
 	}
 
+	/// The validator set of the next session, queued at the start of the current one.
+	///
+	/// This is meant to be exposed by the [`sp_session::SessionValidatorsApi`] runtime API.
+	pub fn queued_validator_set() -> QueuedValidatorSet<T::ValidatorId, BlockNumberFor<T>> {
+		let now = frame_system::Pallet::<T>::block_number();
+		QueuedValidatorSet {
+			session_index: CurrentIndex::<T>::get() + 1,
+			validators: QueuedKeys::<T>::get().into_iter().map(|(v, _)| v).collect(),
+			changed: QueuedChanged::<T>::get(),
+			estimated_start: T::NextSessionRotation::estimate_next_session_rotation(now).0,
+		}
+	}
+
 	/// Disable the validator of index `i`, returns `false` if the validator was already disabled.
 	pub fn disable_index(i: u32) -> bool {
 		if i >= Validators::<T>::decode_len().unwrap_or(0) as u32 {
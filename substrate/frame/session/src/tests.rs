@@ -21,7 +21,7 @@ use super::*;
 use crate::mock::{
 	authorities, before_session_end_called, force_new_session, new_test_ext,
 	reset_before_session_end_called, session_changed, set_next_validators, set_session_length,
-	PreUpgradeMockSessionKeys, RuntimeOrigin, Session, SessionChanged, System, Test,
+	PreUpgradeMockSessionKeys, RuntimeEvent, RuntimeOrigin, Session, SessionChanged, System, Test,
 	TestSessionChanged, TestValidatorIdOf,
 };
 
@@ -158,6 +158,41 @@ fn authorities_should_track_validators() {
 	});
 }
 
+#[test]
+fn queued_validator_set_is_announced() {
+	new_test_ext().execute_with(|| {
+		TestValidatorIdOf::set(vec![(1, 1), (2, 2), (3, 3), (4, 4)].into_iter().collect());
+
+		set_next_validators(vec![1, 2]);
+		force_new_session();
+		initialize_block(1);
+		System::assert_has_event(
+			Event::NewQueued { session_index: 2, validators: vec![1, 2] }.into(),
+		);
+		assert_eq!(
+			Session::queued_validator_set(),
+			QueuedValidatorSet {
+				session_index: 2,
+				validators: vec![1, 2],
+				changed: true,
+				estimated_start: None,
+			}
+		);
+
+		// An unchanged set isn't announced.
+		System::reset_events();
+		TestSessionChanged::mutate(|l| *l = true);
+		force_new_session();
+		initialize_block(2);
+		assert!(!System::events()
+			.iter()
+			.any(|r| matches!(r.event, RuntimeEvent::Session(Event::NewQueued { .. }))));
+		let queued = Session::queued_validator_set();
+		assert_eq!((queued.session_index, queued.changed), (3, false));
+		assert_eq!(queued.validators, vec![1, 2]);
+	});
+}
+
 #[test]
 fn should_work_with_early_exit() {
 	new_test_ext().execute_with(|| {
@@ -20,8 +20,10 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use codec::Codec;
+use sp_std::vec::Vec;
 
 sp_api::decl_runtime_apis! {
+	#[api_version(3)]
 	pub trait StakingApi<Balance, AccountId>
 		where
 			Balance: Codec,
@@ -32,5 +34,12 @@ sp_api::decl_runtime_apis! {
 
 		/// Returns the page count of exposures for a validator in a given era.
 		fn eras_stakers_page_count(era: sp_staking::EraIndex, account: AccountId) -> sp_staking::Page;
+
+		/// Returns the slashes deferred to a later era, which can still be cancelled.
+		fn deferred_slashes() -> Vec<sp_staking::DeferredSlash<AccountId, Balance>>;
+
+		/// Returns whether a new era is forced to start at the end of the current session.
+		#[api_version(3)]
+		fn new_era_forced() -> bool;
 	}
 }
@@ -962,6 +962,30 @@ benchmarks! {
 		assert_eq!(Staking::<T>::inspect_bond_state(&stash), Ok(LedgerIntegrityState::Ok));
 	}
 
+	set_auto_payout {
+		let (stash, controller) = create_stash_controller::<T>(USER_SEED, 100, RewardDestination::Staked)?;
+		assert!(!AutoPayout::<T>::contains_key(&stash));
+		whitelist_account!(controller);
+	}: _(RawOrigin::Signed(controller), true)
+	verify {
+		assert!(AutoPayout::<T>::contains_key(&stash));
+	}
+
+	set_payout_splits {
+		let s in 1 .. T::MaxPayoutSplits::get();
+		let (stash, controller) = create_stash_controller::<T>(USER_SEED, 100, RewardDestination::Staked)?;
+		let share = Perbill::from_parts(Perbill::one().deconstruct() / s);
+		let splits: BoundedVec<_, T::MaxPayoutSplits> = (0..s)
+			.map(|i| (account::<T::AccountId>("split", i, SEED), share))
+			.collect::<Vec<_>>()
+			.try_into()
+			.unwrap();
+		whitelist_account!(controller);
+	}: _(RawOrigin::Signed(controller), splits.clone())
+	verify {
+		assert_eq!(PayoutSplits::<T>::get(&stash), splits);
+	}
+
 	impl_benchmark_test_suite!(
 		Staking,
 		crate::mock::ExtBuilder::default().has_stakers(true),
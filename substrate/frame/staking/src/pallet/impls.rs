@@ -26,6 +26,7 @@ use frame_support::{
 	defensive,
 	dispatch::WithPostDispatchInfo,
 	pallet_prelude::*,
+	storage::with_storage_layer,
 	traits::{
 		Currency, Defensive, DefensiveSaturating, EstimateNextNewSession, Get, Imbalance,
 		InspectLockableCurrency, Len, OnUnbalanced, TryCollect, UnixTime,
@@ -41,7 +42,7 @@ use sp_runtime::{
 use sp_staking::{
 	currency_to_vote::CurrencyToVote,
 	offence::{DisableStrategy, OffenceDetails, OnOffenceHandler},
-	EraIndex, OnStakingUpdate, Page, SessionIndex, Stake,
+	DeferredSlash, EraIndex, OnStakingUpdate, Page, SessionIndex, Stake,
 	StakingAccount::{self, Controller, Stash},
 	StakingInterface,
 };
@@ -313,10 +314,13 @@ impl<T: Config> Pallet<T> {
 		});
 
 		let mut total_imbalance = PositiveImbalanceOf::<T>::zero();
-		// We can now make total validator payout:
-		if let Some((imbalance, dest)) =
-			Self::make_payout(&stash, validator_staking_payout + validator_commission_payout)
-		{
+		// We can now make total validator payout, first to the accounts it is split across:
+		let (validator_payout, split_payout_count) = Self::make_split_payouts(
+			&stash,
+			validator_staking_payout + validator_commission_payout,
+			&mut total_imbalance,
+		);
+		if let Some((imbalance, dest)) = Self::make_payout(&stash, validator_payout) {
 			Self::deposit_event(Event::<T>::Rewarded { stash, dest, amount: imbalance.peek() });
 			total_imbalance.subsume(imbalance);
 		}
@@ -350,7 +354,135 @@ impl<T: Config> Pallet<T> {
 		T::Reward::on_unbalanced(total_imbalance);
 		debug_assert!(nominator_payout_count <= T::MaxExposurePageSize::get());
 
-		Ok(Some(T::WeightInfo::payout_stakers_alive_staked(nominator_payout_count)).into())
+		Ok(Some(T::WeightInfo::payout_stakers_alive_staked(
+			nominator_payout_count.saturating_add(split_payout_count),
+		))
+		.into())
+	}
+
+	/// The maximum weight of the payout of a page of stakers, including the payout splits of the
+	/// validator.
+	pub(crate) fn max_payout_weight() -> Weight {
+		T::WeightInfo::payout_stakers_alive_staked(
+			T::MaxExposurePageSize::get().saturating_add(T::MaxPayoutSplits::get()),
+		)
+	}
+
+	/// Pay the shares of `amount` to the accounts the rewards of `stash` are split across.
+	///
+	/// Returns the rest of `amount`, to be paid to the payee of the stash, and the number of
+	/// payouts made.
+	fn make_split_payouts(
+		stash: &T::AccountId,
+		amount: BalanceOf<T>,
+		total_imbalance: &mut PositiveImbalanceOf<T>,
+	) -> (BalanceOf<T>, u32) {
+		if amount.is_zero() {
+			return (amount, 0)
+		}
+
+		let mut rest = amount;
+		let mut count = 0;
+		for (account, share) in PayoutSplits::<T>::get(stash) {
+			let split = (share * amount).min(rest);
+			if split.is_zero() {
+				continue
+			}
+
+			// The splits which can't be credited, e.g. below the existential deposit of a new
+			// account, are left to the payee.
+			let imbalance = T::Currency::deposit_creating(&account, split);
+			if imbalance.peek().is_zero() {
+				continue
+			}
+			rest -= imbalance.peek();
+
+			Self::deposit_event(Event::<T>::Rewarded {
+				stash: stash.clone(),
+				dest: RewardDestination::Account(account),
+				amount: imbalance.peek(),
+			});
+			total_imbalance.subsume(imbalance);
+			count += 1;
+		}
+		(rest, count)
+	}
+
+	/// Pay out the rewards of the last completed era to the [`AutoPayout`] validators, within
+	/// `remaining_weight`.
+	///
+	/// The validators are paid out in the order of the map, resuming from the
+	/// [`AutoPayoutCursor`] across blocks. When an era completes before all of them are paid
+	/// out, the payouts move on to the new era and the pages left are to be claimed manually.
+	///
+	/// Returns the weight consumed.
+	pub(crate) fn do_auto_payouts(remaining_weight: Weight) -> Weight {
+		let db_weight = T::DbWeight::get();
+		// Reading the active era and the cursor.
+		let mut used_weight = db_weight.reads(2);
+		if remaining_weight.any_lt(used_weight) {
+			return Weight::zero()
+		}
+
+		let Some(era) = ActiveEra::<T>::get().and_then(|active| active.index.checked_sub(1)) else {
+			return used_weight
+		};
+		let mut last = match AutoPayoutCursor::<T>::get() {
+			Some((cursor_era, last)) if cursor_era == era => match last {
+				Some(last) => Some(last),
+				// All the validators are paid out for this era.
+				None => return used_weight,
+			},
+			_ => None,
+		};
+
+		let mut validators = match last {
+			Some(ref last) =>
+				AutoPayout::<T>::iter_keys_from(AutoPayout::<T>::hashed_key_for(last)),
+			None => AutoPayout::<T>::iter_keys(),
+		};
+		let payout_weight = Self::max_payout_weight();
+		let mut finished = false;
+		'validators: loop {
+			// Reading the next validator and the cursor update.
+			let next_weight = used_weight.saturating_add(db_weight.reads_writes(1, 1));
+			if remaining_weight.any_lt(next_weight) {
+				break
+			}
+			used_weight.saturating_accrue(db_weight.reads(1));
+			let Some(stash) = validators.next() else {
+				finished = true;
+				break
+			};
+
+			// Pay out the pages of the validator one by one, until all of them are claimed.
+			loop {
+				let next_weight =
+					used_weight.saturating_add(payout_weight).saturating_add(db_weight.writes(1));
+				if remaining_weight.any_lt(next_weight) {
+					break 'validators
+				}
+				let result = with_storage_layer(|| Self::do_payout_stakers(stash.clone(), era));
+				match result {
+					Ok(info) =>
+						used_weight.saturating_accrue(info.actual_weight.unwrap_or(payout_weight)),
+					Err(error) => {
+						used_weight.saturating_accrue(
+							error.post_info.actual_weight.unwrap_or(payout_weight),
+						);
+						break
+					},
+				}
+			}
+			last = Some(stash);
+		}
+
+		// Nothing to persist if no progress was made on a new era.
+		if finished || last.is_some() {
+			AutoPayoutCursor::<T>::put((era, if finished { None } else { last }));
+			used_weight.saturating_accrue(db_weight.writes(1));
+		}
+		used_weight
 	}
 
 	/// Chill a stash account.
@@ -759,6 +891,8 @@ impl<T: Config> Pallet<T> {
 
 		Self::do_remove_validator(&stash);
 		Self::do_remove_nominator(&stash);
+		AutoPayout::<T>::remove(&stash);
+		PayoutSplits::<T>::remove(&stash);
 
 		frame_system::Pallet::<T>::dec_consumers(&stash);
 
@@ -1152,6 +1286,30 @@ impl<T: Config> Pallet<T> {
 	pub fn api_eras_stakers_page_count(era: EraIndex, account: T::AccountId) -> Page {
 		EraInfo::<T>::get_page_count(era, &account)
 	}
+
+	/// The slashes deferred to a later era, in the order they are applied in.
+	pub fn api_deferred_slashes() -> Vec<DeferredSlash<T::AccountId, BalanceOf<T>>> {
+		let mut slashes = UnappliedSlashes::<T>::iter()
+			.flat_map(|(apply_era, slashes)| {
+				slashes.into_iter().enumerate().map(move |(index, slash)| DeferredSlash {
+					apply_era,
+					index: index as u32,
+					validator: slash.validator,
+					own: slash.own,
+					others: slash.others,
+					reporters: slash.reporters,
+					payout: slash.payout,
+				})
+			})
+			.collect::<Vec<_>>();
+		slashes.sort_by_key(|slash| slash.apply_era);
+		slashes
+	}
+
+	/// Whether a new era is forced to start at the end of the current session.
+	pub fn api_new_era_forced() -> bool {
+		matches!(ForceEra::<T>::get(), Forcing::ForceNew | Forcing::ForceAlways)
+	}
 }
 
 impl<T: Config> ElectionDataProvider for Pallet<T> {
@@ -192,6 +192,12 @@ pub mod pallet {
 		/// Supported actions: (1) cancel deferred slash, (2) set minimum commission.
 		type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
 
+		/// The origin which can veto the deferred slashes of a validator, before they are applied.
+		///
+		/// This can be a body more responsive than [`Config::AdminOrigin`], to respond to an
+		/// incident within the slash deferral window.
+		type SlashVetoOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
 		/// Interface for interacting with a session pallet.
 		type SessionInterface: SessionInterface<Self::AccountId>;
 
@@ -217,6 +223,11 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxExposurePageSize: Get<u32>;
 
+		/// The maximum number of accounts the rewards of a validator can be split across, see
+		/// [`Call::set_payout_splits`].
+		#[pallet::constant]
+		type MaxPayoutSplits: Get<u32>;
+
 		/// The fraction of the validator set that is safe to be offending.
 		/// After the threshold is reached a new era will be forced.
 		type OffendingValidatorsThreshold: Get<Perbill>;
@@ -341,6 +352,33 @@ pub mod pallet {
 	pub type Payee<T: Config> =
 		StorageMap<_, Twox64Concat, T::AccountId, RewardDestination<T::AccountId>, OptionQuery>;
 
+	/// The accounts receiving a share of the rewards of a validator stash, the rest being paid to
+	/// its [`Payee`]. Keyed by stash.
+	///
+	/// TWOX-NOTE: SAFE since `AccountId` is a secure hash.
+	#[pallet::storage]
+	pub type PayoutSplits<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		BoundedVec<(T::AccountId, Perbill), T::MaxPayoutSplits>,
+		ValueQuery,
+	>;
+
+	/// The validator stashes whose rewards are paid out automatically, in `on_idle`.
+	///
+	/// TWOX-NOTE: SAFE since `AccountId` is a secure hash.
+	#[pallet::storage]
+	pub type AutoPayout<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, (), OptionQuery>;
+
+	/// The progress of the automatic payouts of an era.
+	///
+	/// Holds the era being paid out and the last [`AutoPayout`] validator fully paid out, or
+	/// `None` once all of them are.
+	#[pallet::storage]
+	pub type AutoPayoutCursor<T: Config> =
+		StorageValue<_, (EraIndex, Option<T::AccountId>), OptionQuery>;
+
 	/// The map from (wannabe) validator stash key to the preferences of that validator.
 	///
 	/// TWOX-NOTE: SAFE since `AccountId` is a secure hash.
@@ -793,6 +831,13 @@ pub mod pallet {
 		ForceEra { mode: Forcing },
 		/// Report of a controller batch deprecation.
 		ControllerBatchDeprecated { failures: u32 },
+		/// The deferred slashes of a validator, which were to be applied at the start of the given
+		/// era, were vetoed.
+		DeferredSlashVetoed { validator: T::AccountId, apply_era: EraIndex, count: u32 },
+		/// The automatic payout of the rewards of a stash was enabled or disabled.
+		AutoPayoutSet { stash: T::AccountId, enabled: bool },
+		/// The accounts the validator rewards of a stash are split across were set.
+		PayoutSplitsSet { stash: T::AccountId, splits: u32 },
 	}
 
 	#[pallet::error]
@@ -858,6 +903,11 @@ pub mod pallet {
 		ControllerDeprecated,
 		/// Cannot reset a ledger.
 		CannotRestoreLedger,
+		/// There is no deferred slash of the validator to be applied in the given era.
+		NoDeferredSlash,
+		/// The shares of the payout splits add up to more than the whole reward, or an account
+		/// is given more than once.
+		InvalidPayoutSplits,
 	}
 
 	#[pallet::hooks]
@@ -867,6 +917,10 @@ pub mod pallet {
 			T::DbWeight::get().reads(1)
 		}
 
+		fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			Self::do_auto_payouts(remaining_weight)
+		}
+
 		fn on_finalize(_n: BlockNumberFor<T>) {
 			// Set the start of the first era.
 			if let Some(mut active_era) = Self::active_era() {
@@ -1568,7 +1622,7 @@ pub mod pallet {
 		///
 		/// If all pages are claimed, it returns an error `InvalidPage`.
 		#[pallet::call_index(18)]
-		#[pallet::weight(T::WeightInfo::payout_stakers_alive_staked(T::MaxExposurePageSize::get()))]
+		#[pallet::weight(Pallet::<T>::max_payout_weight())]
 		pub fn payout_stakers(
 			origin: OriginFor<T>,
 			validator_stash: T::AccountId,
@@ -1896,7 +1950,7 @@ pub mod pallet {
 		/// and so it should not be assumed the highest staker would be on the topmost page and vice
 		/// versa. If rewards are not claimed in [`Config::HistoryDepth`] eras, they are lost.
 		#[pallet::call_index(26)]
-		#[pallet::weight(T::WeightInfo::payout_stakers_alive_staked(T::MaxExposurePageSize::get()))]
+		#[pallet::weight(Pallet::<T>::max_payout_weight())]
 		pub fn payout_stakers_by_page(
 			origin: OriginFor<T>,
 			validator_stash: T::AccountId,
@@ -2085,6 +2139,108 @@ pub mod pallet {
 			);
 			Ok(())
 		}
+
+		/// Veto the deferred slashes of `validator` which are to be applied at the start of
+		/// `apply_era`.
+		///
+		/// Can be called by the `T::SlashVetoOrigin`. Unlike [`Call::cancel_deferred_slash`], the
+		/// slashes are identified by their validator rather than by their index, which shifts as
+		/// other slashes are cancelled. The deferred slashes and the eras they are applied in are
+		/// listed by the `StakingApi::deferred_slashes` runtime API.
+		#[pallet::call_index(30)]
+		#[pallet::weight(T::WeightInfo::cancel_deferred_slash(1))]
+		pub fn veto_deferred_slash(
+			origin: OriginFor<T>,
+			apply_era: EraIndex,
+			validator: T::AccountId,
+		) -> DispatchResult {
+			T::SlashVetoOrigin::ensure_origin(origin)?;
+
+			let mut unapplied = UnappliedSlashes::<T>::get(&apply_era);
+			let len = unapplied.len();
+			unapplied.retain(|slash| slash.validator != validator);
+			let count = len - unapplied.len();
+			ensure!(count > 0, Error::<T>::NoDeferredSlash);
+
+			if unapplied.is_empty() {
+				UnappliedSlashes::<T>::remove(&apply_era);
+			} else {
+				UnappliedSlashes::<T>::insert(&apply_era, &unapplied);
+			}
+			Self::deposit_event(Event::<T>::DeferredSlashVetoed {
+				validator,
+				apply_era,
+				count: count as u32,
+			});
+			Ok(())
+		}
+
+		/// Enable or disable the automatic payout of the rewards of the validator.
+		///
+		/// The rewards of the last completed era of the validators opted in are paid out in
+		/// `on_idle`, within the weight left in the blocks, such that no external account needs
+		/// to call [`Call::payout_stakers`]. The pages which are still unclaimed once the next
+		/// era completes are left to be claimed manually.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the controller.
+		///
+		/// Emits `AutoPayoutSet`.
+		#[pallet::call_index(31)]
+		#[pallet::weight(T::WeightInfo::set_auto_payout())]
+		pub fn set_auto_payout(origin: OriginFor<T>, enabled: bool) -> DispatchResult {
+			let controller = ensure_signed(origin)?;
+			let ledger = Self::ledger(Controller(controller))?;
+
+			if enabled {
+				AutoPayout::<T>::insert(&ledger.stash, ());
+			} else {
+				AutoPayout::<T>::remove(&ledger.stash);
+			}
+			Self::deposit_event(Event::<T>::AutoPayoutSet { stash: ledger.stash, enabled });
+			Ok(())
+		}
+
+		/// Split the rewards of the validator across the given accounts.
+		///
+		/// Each account receives its share of every validator reward of the stash in its free
+		/// balance, and the rest is paid to the [`RewardDestination`] of the stash. The shares
+		/// must not add up to more than the whole reward. An empty list removes the splits. The
+		/// rewards received as a nominator are not split.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the controller.
+		///
+		/// Emits `PayoutSplitsSet`.
+		#[pallet::call_index(32)]
+		#[pallet::weight(T::WeightInfo::set_payout_splits(splits.len() as u32))]
+		pub fn set_payout_splits(
+			origin: OriginFor<T>,
+			splits: BoundedVec<(T::AccountId, Perbill), T::MaxPayoutSplits>,
+		) -> DispatchResult {
+			let controller = ensure_signed(origin)?;
+			let ledger = Self::ledger(Controller(controller))?;
+
+			let mut total = 0u64;
+			for (index, (account, share)) in splits.iter().enumerate() {
+				total = total.saturating_add(u64::from(share.deconstruct()));
+				ensure!(
+					!splits[..index].iter().any(|(other, _)| other == account),
+					Error::<T>::InvalidPayoutSplits
+				);
+			}
+			ensure!(
+				total <= u64::from(Perbill::one().deconstruct()),
+				Error::<T>::InvalidPayoutSplits
+			);
+
+			let count = splits.len() as u32;
+			if splits.is_empty() {
+				PayoutSplits::<T>::remove(&ledger.stash);
+			} else {
+				PayoutSplits::<T>::insert(&ledger.stash, splits);
+			}
+			Self::deposit_event(Event::<T>::PayoutSplitsSet { stash: ledger.stash, splits: count });
+			Ok(())
+		}
 	}
 }
 
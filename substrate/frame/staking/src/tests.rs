@@ -1157,6 +1157,163 @@ fn reward_destination_works() {
 	});
 }
 
+#[test]
+fn payout_splits_work() {
+	ExtBuilder::default().nominate(false).build_and_execute(|| {
+		// The shares cannot exceed the whole reward, nor repeat an account.
+		assert_noop!(
+			Staking::set_payout_splits(
+				RuntimeOrigin::signed(11),
+				bounded_vec![(2000, Perbill::from_percent(60)), (2001, Perbill::from_percent(50))]
+			),
+			Error::<Test>::InvalidPayoutSplits
+		);
+		assert_noop!(
+			Staking::set_payout_splits(
+				RuntimeOrigin::signed(11),
+				bounded_vec![(2000, Perbill::from_percent(20)), (2000, Perbill::from_percent(30))]
+			),
+			Error::<Test>::InvalidPayoutSplits
+		);
+
+		assert_ok!(Staking::set_payout_splits(
+			RuntimeOrigin::signed(11),
+			bounded_vec![(2000, Perbill::from_percent(20)), (2001, Perbill::from_percent(30))]
+		));
+		assert_eq!(PayoutSplits::<Test>::get(11).len(), 2);
+
+		let total_payout_0 = current_total_payout_for_duration(reward_time_per_era());
+		Pallet::<Test>::reward_by_ids(vec![(11, 1)]);
+		mock::start_active_era(1);
+		mock::make_all_reward_payment(0);
+
+		// The split accounts received their share, the rest was staked by the validator.
+		let share_2000 = Perbill::from_percent(20) * total_payout_0;
+		let share_2001 = Perbill::from_percent(30) * total_payout_0;
+		assert_eq!(Balances::free_balance(2000), share_2000);
+		assert_eq!(Balances::free_balance(2001), share_2001);
+		assert_eq!(Balances::free_balance(11), 1000 + total_payout_0 - share_2000 - share_2001);
+		assert_eq!(
+			Staking::ledger(11.into()).unwrap().active,
+			1000 + total_payout_0 - share_2000 - share_2001
+		);
+		assert!(staking_events().contains(&Event::Rewarded {
+			stash: 11,
+			dest: RewardDestination::Account(2001),
+			amount: share_2001,
+		}));
+
+		// An empty list removes the splits.
+		assert_ok!(Staking::set_payout_splits(RuntimeOrigin::signed(11), bounded_vec![]));
+		assert!(!PayoutSplits::<Test>::contains_key(11));
+	});
+}
+
+#[test]
+fn payout_splits_below_existential_deposit_are_left_to_the_payee() {
+	ExtBuilder::default().nominate(false).build_and_execute(|| {
+		assert_ok!(Staking::set_payout_splits(
+			RuntimeOrigin::signed(11),
+			bounded_vec![(2000, Perbill::from_percent(20)), (2001, Perbill::from_percent(30))]
+		));
+
+		let total_payout_0 = current_total_payout_for_duration(reward_time_per_era());
+		Pallet::<Test>::reward_by_ids(vec![(11, 1)]);
+		mock::start_active_era(1);
+
+		// The share of 2000 cannot create its account.
+		let share_2000 = Perbill::from_percent(20) * total_payout_0;
+		let share_2001 = Perbill::from_percent(30) * total_payout_0;
+		ExistentialDeposit::set(share_2000 + 1);
+		mock::make_all_reward_payment(0);
+
+		assert_eq!(Balances::free_balance(2000), 0);
+		assert_eq!(Balances::free_balance(2001), share_2001);
+		assert_eq!(Balances::free_balance(11), 1000 + total_payout_0 - share_2001);
+		assert!(!staking_events().iter().any(|event| matches!(
+			event,
+			Event::Rewarded { dest: RewardDestination::Account(2000), .. }
+		)));
+	});
+}
+
+#[test]
+fn auto_payout_pays_out_the_last_era_on_idle() {
+	ExtBuilder::default().nominate(false).build_and_execute(|| {
+		assert_ok!(Staking::set_auto_payout(RuntimeOrigin::signed(11), true));
+		assert_ok!(Staking::set_auto_payout(RuntimeOrigin::signed(21), true));
+		assert_eq!(
+			*staking_events().last().unwrap(),
+			Event::AutoPayoutSet { stash: 21, enabled: true }
+		);
+
+		let total_payout_0 = current_total_payout_for_duration(reward_time_per_era());
+		Pallet::<Test>::reward_by_ids(vec![(11, 1), (21, 1)]);
+		mock::start_active_era(1);
+
+		<Staking as Hooks<u64>>::on_idle(System::block_number(), Weight::MAX);
+
+		assert_eq!(Staking::claimed_rewards(0, &11), vec![0]);
+		assert_eq!(Staking::claimed_rewards(0, &21), vec![0]);
+		assert_eq_error_rate!(Balances::free_balance(11), 1000 + total_payout_0 / 2, 1);
+		assert_eq!(AutoPayoutCursor::<Test>::get(), Some((0, None)));
+
+		// All the validators are paid out for the era.
+		assert_eq!(
+			<Staking as Hooks<u64>>::on_idle(System::block_number(), Weight::MAX),
+			<Test as frame_system::Config>::DbWeight::get().reads(2)
+		);
+
+		// The validators opted out are not paid out.
+		assert_ok!(Staking::set_auto_payout(RuntimeOrigin::signed(21), false));
+		Pallet::<Test>::reward_by_ids(vec![(11, 1), (21, 1)]);
+		mock::start_active_era(2);
+
+		<Staking as Hooks<u64>>::on_idle(System::block_number(), Weight::MAX);
+
+		assert_eq!(Staking::claimed_rewards(1, &11), vec![0]);
+		assert!(Staking::claimed_rewards(1, &21).is_empty());
+	});
+}
+
+#[test]
+fn auto_payout_resumes_across_blocks() {
+	ExtBuilder::default().nominate(false).build_and_execute(|| {
+		assert_ok!(Staking::set_auto_payout(RuntimeOrigin::signed(11), true));
+		assert_ok!(Staking::set_auto_payout(RuntimeOrigin::signed(21), true));
+
+		Pallet::<Test>::reward_by_ids(vec![(11, 1), (21, 1)]);
+		mock::start_active_era(1);
+
+		let claimed = || {
+			[11, 21]
+				.into_iter()
+				.filter(|v| !Staking::claimed_rewards(0, v).is_empty())
+				.count()
+		};
+
+		// Not enough weight for a single payout.
+		<Staking as Hooks<u64>>::on_idle(
+			System::block_number(),
+			Pallet::<Test>::max_payout_weight() / 2,
+		);
+		assert_eq!(claimed(), 0);
+		assert_eq!(AutoPayoutCursor::<Test>::get(), None);
+
+		// Enough weight for a single payout per block.
+		let budget = Pallet::<Test>::max_payout_weight() +
+			<Test as frame_system::Config>::DbWeight::get().reads_writes(3, 1);
+		<Staking as Hooks<u64>>::on_idle(System::block_number(), budget);
+		assert_eq!(claimed(), 1);
+
+		for _ in 0..3 {
+			<Staking as Hooks<u64>>::on_idle(System::block_number(), budget);
+		}
+		assert_eq!(claimed(), 2);
+		assert_eq!(AutoPayoutCursor::<Test>::get(), Some((0, None)));
+	});
+}
+
 #[test]
 fn validator_payment_prefs_work() {
 	// Test that validator preferences are correctly honored
@@ -3310,6 +3467,79 @@ fn remove_multi_deferred() {
 	})
 }
 
+#[test]
+fn veto_deferred_slash_works() {
+	ExtBuilder::default().slash_defer_duration(2).build_and_execute(|| {
+		mock::start_active_era(1);
+
+		let exposure = Staking::eras_stakers(active_era(), &11);
+		on_offence_now(
+			&[OffenceDetails { offender: (11, exposure.clone()), reporters: vec![] }],
+			&[Perbill::from_percent(10)],
+		);
+		on_offence_now(
+			&[OffenceDetails {
+				offender: (21, Staking::eras_stakers(active_era(), &21)),
+				reporters: vec![],
+			}],
+			&[Perbill::from_percent(10)],
+		);
+		on_offence_now(
+			&[OffenceDetails { offender: (11, exposure.clone()), reporters: vec![] }],
+			&[Perbill::from_percent(25)],
+		);
+
+		// all of them are deferred to the start of era 4.
+		let deferred = Staking::api_deferred_slashes();
+		assert_eq!(
+			deferred.iter().map(|s| (s.apply_era, s.index, s.validator)).collect::<Vec<_>>(),
+			vec![(4, 0, 11), (4, 1, 21), (4, 2, 11)]
+		);
+
+		assert_noop!(Staking::veto_deferred_slash(RuntimeOrigin::signed(2), 4, 11), BadOrigin);
+		// the slashes are applied at the start of era 4, not 3.
+		assert_noop!(
+			Staking::veto_deferred_slash(RuntimeOrigin::root(), 3, 11),
+			Error::<Test>::NoDeferredSlash
+		);
+
+		// both slashes of 11 are vetoed.
+		assert_ok!(Staking::veto_deferred_slash(RuntimeOrigin::root(), 4, 11));
+		assert_eq!(
+			*staking_events_since_last_call().last().unwrap(),
+			Event::DeferredSlashVetoed { validator: 11, apply_era: 4, count: 2 }
+		);
+		let slashes = UnappliedSlashes::<Test>::get(&4);
+		assert_eq!(slashes.len(), 1);
+		assert_eq!(slashes[0].validator, 21);
+
+		assert_ok!(Staking::veto_deferred_slash(RuntimeOrigin::root(), 4, 21));
+		assert!(!UnappliedSlashes::<Test>::contains_key(&4));
+		assert!(Staking::api_deferred_slashes().is_empty());
+
+		mock::start_active_era(4);
+		assert_eq!(Balances::free_balance(11), 1000);
+	})
+}
+
+#[test]
+fn forced_new_era_is_reported() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert!(!Staking::api_new_era_forced());
+
+		assert_ok!(Staking::force_new_era(RuntimeOrigin::root()));
+		assert!(Staking::api_new_era_forced());
+
+		// The forcing is consumed by the new era.
+		advance_session();
+		assert!(!Staking::api_new_era_forced());
+
+		assert_ok!(Staking::force_new_era_always(RuntimeOrigin::root()));
+		advance_session();
+		assert!(Staking::api_new_era_forced());
+	})
+}
+
 #[test]
 fn slash_kicks_validators_not_nominators_and_disables_nominator_for_kicked_validator() {
 	ExtBuilder::default().build_and_execute(|| {
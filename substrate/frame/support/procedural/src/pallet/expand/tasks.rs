@@ -194,10 +194,14 @@ impl ToTokens for TasksDef {
 					}
 				}
 
-				fn run(&self) -> Result<(), #scrate::pallet_prelude::DispatchError> {
+				fn run(&self) -> #scrate::traits::tasks::__private::DispatchResultWithPostInfo {
 					match self.clone() {
+						// Task functions may return either a `DispatchResult` or a
+						// `DispatchResultWithPostInfo` reporting the weight actually consumed.
 						#(#enum_ident::#task_fn_idents { #(#task_arg_names),* } => {
 							<#enum_use>::#task_fn_names(#( #task_arg_names, )* )
+								.map(Into::into)
+								.map_err(Into::into)
 						},)*
 						Task::__Ignore(_, _) => unreachable!(),
 					}
@@ -72,6 +72,8 @@ pub mod inherent;
 pub mod instances;
 pub mod migrations;
 pub mod storage;
+#[cfg(feature = "std")]
+pub mod test_externalities;
 #[cfg(test)]
 mod tests;
 pub mod traits;
@@ -871,7 +873,9 @@ pub mod testing_prelude {
 		assert_err, assert_err_ignore_postinfo, assert_err_with_weight, assert_error_encoded_size,
 		assert_noop, assert_ok, assert_storage_noop, parameter_types, traits::Get,
 	};
+	pub use crate::test_externalities::cached_test_ext;
 	pub use sp_arithmetic::assert_eq_error_rate;
+	pub use sp_io::TestExternalitiesSnapshot;
 	pub use sp_runtime::{bounded_btree_map, bounded_vec};
 }
 
@@ -2375,6 +2379,10 @@ pub mod pallet_macros {
 	/// item.
 	///
 	/// It takes a closure as input, which should return a `Weight` value.
+	///
+	/// This weight is the budget of the task. A task function returning a
+	/// `DispatchResultWithPostInfo` can report the weight it actually consumed, `do_task`
+	/// refunds the unused budget and emits a `TaskWeightOverrun` event if it was exceeded.
 	pub use frame_support_procedural::task_weight;
 
 	/// Allows you to define some service work that can be recognized by a script or an
@@ -2394,6 +2402,8 @@ pub mod pallet_macros {
 	/// * [`pallet::task_weight`](`task_weight`)
 	/// * [`pallet::task_index`](`task_index`)
 	///
+	/// and return either a `DispatchResult` or a `DispatchResultWithPostInfo`.
+	///
 	/// All of such Tasks are then aggregated into a `RuntimeTask` by
 	/// [`construct_runtime`](frame_support::construct_runtime).
 	///
@@ -52,6 +52,7 @@ mod stream_iter;
 pub mod transactional;
 pub mod types;
 pub mod unhashed;
+pub mod usage;
 pub mod weak_bounded_vec;
 
 /// Utility type for converting a storage map into a `Get<u32>` impl which returns the maximum
@@ -0,0 +1,148 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Storage usage of the pallets, e.g. to quantify the state growth of each of them.
+//!
+//! The usage is reported through the [`runtime_api::StorageUsageApi`] and can be logged after a
+//! runtime upgrade with try-runtime.
+
+use crate::{traits::PalletsInfoAccess, StorageHasher, Twox128};
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+
+/// Storage used by a pallet.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug, TypeInfo)]
+pub struct PalletStorageUsage {
+	/// Name of the pallet as configured in the runtime.
+	pub pallet_name: Vec<u8>,
+	/// Number of keys under the prefix of the pallet.
+	pub keys: u64,
+	/// Total size of the keys, in bytes.
+	pub key_bytes: u64,
+	/// Total size of the values, in bytes.
+	pub value_bytes: u64,
+}
+
+impl PalletStorageUsage {
+	/// Total size of the keys and values, in bytes.
+	pub fn total_bytes(&self) -> u64 {
+		self.key_bytes.saturating_add(self.value_bytes)
+	}
+}
+
+/// The storage used by the pallet with the given name, walking all the keys under its prefix.
+pub fn pallet_storage_usage(pallet_name: &str) -> PalletStorageUsage {
+	let prefix = Twox128::hash(pallet_name.as_bytes());
+	let mut usage =
+		PalletStorageUsage { pallet_name: pallet_name.as_bytes().to_vec(), ..Default::default() };
+
+	let mut next_key = Some(prefix.to_vec());
+	while let Some(key) = next_key.filter(|key| key.starts_with(&prefix)) {
+		// Only the length of the value is read.
+		if let Some(len) = sp_io::storage::read(&key, &mut [], 0) {
+			usage.keys += 1;
+			usage.key_bytes += key.len() as u64;
+			usage.value_bytes += len as u64;
+		}
+		next_key = sp_io::storage::next_key(&key);
+	}
+
+	usage
+}
+
+/// The storage used by each of the pallets `P`, e.g. `AllPalletsWithSystem`.
+///
+/// This walks the entire storage of the pallets, so it is only meant for offchain use. The keys
+/// outside of the prefix of any pallet, e.g. the runtime code, are not accounted for.
+pub fn storage_usage<P: PalletsInfoAccess>() -> Vec<PalletStorageUsage> {
+	P::infos().into_iter().map(|info| pallet_storage_usage(info.name)).collect()
+}
+
+/// Runtime API of the storage usage.
+pub mod runtime_api {
+	use super::*;
+
+	sp_api::decl_runtime_apis! {
+		/// Runtime API reporting the storage used by each of the pallets.
+		pub trait StorageUsageApi {
+			/// The storage used by each of the pallets of the runtime, see [`storage_usage`].
+			fn storage_usage() -> Vec<PalletStorageUsage>;
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::pallet_storage_usage;
+	use crate::{
+		pallet_prelude::{StorageMap, StorageValue, Twox64Concat},
+		traits::StorageInstance,
+	};
+	use sp_io::TestExternalities;
+
+	struct ValuePrefix;
+	impl StorageInstance for ValuePrefix {
+		const STORAGE_PREFIX: &'static str = "value";
+		fn pallet_prefix() -> &'static str {
+			"pallet"
+		}
+	}
+	type Value = StorageValue<ValuePrefix, u32>;
+
+	struct MapPrefix;
+	impl StorageInstance for MapPrefix {
+		const STORAGE_PREFIX: &'static str = "map";
+		fn pallet_prefix() -> &'static str {
+			"pallet"
+		}
+	}
+	type Map = StorageMap<MapPrefix, Twox64Concat, u16, Vec<u8>>;
+
+	struct OtherPrefix;
+	impl StorageInstance for OtherPrefix {
+		const STORAGE_PREFIX: &'static str = "value";
+		fn pallet_prefix() -> &'static str {
+			"other_pallet"
+		}
+	}
+	type Other = StorageValue<OtherPrefix, u64>;
+
+	#[test]
+	fn pallet_storage_usage_counts_the_keys_of_the_pallet() {
+		TestExternalities::new_empty().execute_with(|| {
+			assert_eq!(pallet_storage_usage("pallet").keys, 0);
+
+			Value::put(1);
+			Map::insert(1, vec![0; 10]);
+			Map::insert(2, vec![0; 20]);
+			Other::put(1);
+
+			let usage = pallet_storage_usage("pallet");
+			assert_eq!(usage.pallet_name, b"pallet".to_vec());
+			assert_eq!(usage.keys, 3);
+			// 32 bytes of prefix, plus 8 bytes of hash and 2 bytes of key for the map.
+			assert_eq!(usage.key_bytes, 32 + 2 * (32 + 8 + 2));
+			// 1 byte of length prefix for the vectors.
+			assert_eq!(usage.value_bytes, 4 + 11 + 21);
+			assert_eq!(usage.total_bytes(), usage.key_bytes + usage.value_bytes);
+
+			assert_eq!(pallet_storage_usage("other_pallet").keys, 1);
+		});
+	}
+}
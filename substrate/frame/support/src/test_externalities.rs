@@ -0,0 +1,80 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Helpers to share the genesis storage of [`TestExternalities`] between test cases.
+
+use sp_io::{TestExternalities, TestExternalitiesSnapshot};
+use std::{
+	collections::HashMap,
+	sync::{Mutex, OnceLock},
+};
+
+/// The snapshots taken by [`cached_test_ext`], by key.
+static SNAPSHOTS: OnceLock<Mutex<HashMap<&'static str, TestExternalitiesSnapshot>>> =
+	OnceLock::new();
+
+/// Returns a fresh [`TestExternalities`] with the storage built by `build`.
+///
+/// `build` runs once per `key` for the whole process; every later call with the same `key`
+/// restores a snapshot of the storage it produced instead. Test suites (and fuzzing loops) whose
+/// genesis is expensive to build can then use this in their `new_test_ext` function.
+///
+/// Only the storage is cached: the extensions and the offchain database registered by `build`
+/// are not, so they must be registered on the returned externalities.
+///
+/// # Example
+///
+/// ```
+/// use frame_support::test_externalities::cached_test_ext;
+///
+/// fn new_test_ext() -> sp_io::TestExternalities {
+/// 	cached_test_ext("my-pallet", || {
+/// 		let mut ext = sp_io::TestExternalities::default();
+/// 		ext.execute_with(|| frame_support::storage::unhashed::put(b"key", &1u32));
+/// 		ext
+/// 	})
+/// }
+///
+/// new_test_ext().execute_with(|| {
+/// 	assert_eq!(frame_support::storage::unhashed::get::<u32>(b"key"), Some(1));
+/// 	frame_support::storage::unhashed::put(b"key", &2u32);
+/// });
+/// // The changes of a test case are not seen by the next one.
+/// new_test_ext().execute_with(|| {
+/// 	assert_eq!(frame_support::storage::unhashed::get::<u32>(b"key"), Some(1));
+/// });
+/// ```
+pub fn cached_test_ext(
+	key: &'static str,
+	build: impl FnOnce() -> TestExternalities,
+) -> TestExternalities {
+	let snapshots = SNAPSHOTS.get_or_init(Default::default);
+
+	if let Some(snapshot) = snapshots.lock().unwrap_or_else(|e| e.into_inner()).get(key) {
+		return TestExternalities::from_snapshot(snapshot)
+	}
+
+	// Built without holding the lock, so `build` may itself use cached externalities.
+	let snapshot = build().snapshot();
+	let ext = TestExternalities::from_snapshot(&snapshot);
+	snapshots
+		.lock()
+		.unwrap_or_else(|e| e.into_inner())
+		.entry(key)
+		.or_insert(snapshot);
+	ext
+}
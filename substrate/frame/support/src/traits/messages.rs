@@ -21,7 +21,7 @@ use super::storage::Footprint;
 use codec::{Decode, Encode, FullCodec, MaxEncodedLen};
 use scale_info::TypeInfo;
 use sp_core::{ConstU32, Get, TypedGet};
-use sp_runtime::{traits::Convert, BoundedSlice, RuntimeDebug};
+use sp_runtime::{traits::Convert, BoundedSlice, Perbill, RuntimeDebug};
 use sp_std::{fmt::Debug, marker::PhantomData, prelude::*};
 use sp_weights::{Weight, WeightMeter};
 
@@ -255,3 +255,28 @@ impl<Origin> QueuePausedQuery<Origin> for Tuple {
 		false
 	}
 }
+
+/// Assigns message queues to classes which are each guaranteed a share of the service weight.
+///
+/// The weight reserved for a class can only be used by the queues of that class until none of
+/// them can make progress anymore. Unreserved weight is available to all queues.
+pub trait QueueServiceShares<Origin> {
+	/// The class of a queue.
+	type Class: PartialEq;
+
+	/// The class of the queue of `origin`.
+	fn class_of(origin: &Origin) -> Self::Class;
+
+	/// The share of the service weight that is reserved for each class.
+	fn shares() -> Vec<(Self::Class, Perbill)>;
+}
+
+impl<Origin> QueueServiceShares<Origin> for () {
+	type Class = ();
+
+	fn class_of(_: &Origin) -> Self::Class {}
+
+	fn shares() -> Vec<(Self::Class, Perbill)> {
+		Vec::new()
+	}
+}
@@ -18,9 +18,9 @@
 //! Contains the [`Task`] trait, which defines a general-purpose way for defining and executing
 //! service work, and supporting types.
 
+use crate::dispatch::DispatchResultWithPostInfo;
 use codec::FullCodec;
 use scale_info::TypeInfo;
-use sp_runtime::DispatchError;
 use sp_std::{fmt::Debug, iter::Iterator, vec, vec::IntoIter};
 use sp_weights::Weight;
 
@@ -28,6 +28,7 @@ use sp_weights::Weight;
 /// expansion of `RuntimeTask`.
 #[doc(hidden)]
 pub mod __private {
+	pub use crate::dispatch::DispatchResultWithPostInfo;
 	pub use codec::FullCodec;
 	pub use scale_info::TypeInfo;
 	pub use sp_runtime::DispatchError;
@@ -49,9 +50,15 @@ pub trait Task: Sized + FullCodec + TypeInfo + Clone + Debug + PartialEq + Eq {
 	fn is_valid(&self) -> bool;
 
 	/// Performs the work for this particular `Task` variant.
-	fn run(&self) -> Result<(), DispatchError>;
+	///
+	/// The weight actually consumed can be reported through the returned post dispatch info,
+	/// it's metered against [`Self::weight`] by the executor.
+	fn run(&self) -> DispatchResultWithPostInfo;
 
 	/// Returns the weight of executing this `Task`.
+	///
+	/// This is the budget of the task: it's charged up front when the task is executed and the
+	/// weight reported by [`Self::run`] is expected not to exceed it.
 	fn weight(&self) -> Weight;
 
 	/// A unique value representing this `Task` within the current pallet. Analogous to
@@ -73,8 +80,8 @@ impl Task for () {
 		true
 	}
 
-	fn run(&self) -> Result<(), DispatchError> {
-		Ok(())
+	fn run(&self) -> DispatchResultWithPostInfo {
+		Ok(().into())
 	}
 
 	fn weight(&self) -> Weight {
@@ -0,0 +1,59 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Trait for notifying other pallets about assets and accounts getting frozen or thawed.
+//!
+//! See the [`crate::traits::fungibles`] doc for more information about fungibles traits.
+
+/// Handler notified when an asset or an account's holding of an asset is frozen or thawed.
+///
+/// This allows pallets holding assets, e.g. DEXes or lending protocols, to pause the markets of
+/// a frozen asset instead of having transfers fail unpredictably in the middle of an operation.
+///
+/// Handlers are called after the status change took effect.
+pub trait FreezeConsumer<AssetId, AccountId> {
+	/// Transfers of the asset `asset` were disallowed.
+	fn asset_frozen(_asset: &AssetId) {}
+
+	/// Transfers of the asset `asset` were allowed again.
+	fn asset_thawed(_asset: &AssetId) {}
+
+	/// Transfers of the asset `asset` from the account `who` were disallowed.
+	fn account_frozen(_asset: &AssetId, _who: &AccountId) {}
+
+	/// Transfers of the asset `asset` from the account `who` were allowed again.
+	fn account_thawed(_asset: &AssetId, _who: &AccountId) {}
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(8)]
+impl<AssetId, AccountId> FreezeConsumer<AssetId, AccountId> for Tuple {
+	fn asset_frozen(asset: &AssetId) {
+		for_tuples!( #( Tuple::asset_frozen(asset); )* );
+	}
+
+	fn asset_thawed(asset: &AssetId) {
+		for_tuples!( #( Tuple::asset_thawed(asset); )* );
+	}
+
+	fn account_frozen(asset: &AssetId, who: &AccountId) {
+		for_tuples!( #( Tuple::account_frozen(asset, who); )* );
+	}
+
+	fn account_thawed(asset: &AssetId, who: &AccountId) {
+		for_tuples!( #( Tuple::account_thawed(asset, who); )* );
+	}
+}
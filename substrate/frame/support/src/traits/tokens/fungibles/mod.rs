@@ -29,6 +29,7 @@
 pub mod approvals;
 mod enumerable;
 pub mod freeze;
+mod freeze_consumer;
 pub mod hold;
 pub(crate) mod imbalance;
 mod lifetime;
@@ -39,6 +40,7 @@ mod union_of;
 
 pub use enumerable::Inspect as InspectEnumerable;
 pub use freeze::{Inspect as InspectFreeze, Mutate as MutateFreeze};
+pub use freeze_consumer::FreezeConsumer;
 pub use hold::{
 	Balanced as BalancedHold, Inspect as InspectHold, Mutate as MutateHold,
 	Unbalanced as UnbalancedHold,
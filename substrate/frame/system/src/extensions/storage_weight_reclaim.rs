@@ -0,0 +1,262 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Reclaim of the over-estimated proof size weight of the extrinsics.
+
+use crate::{Config, LOG_TARGET};
+use codec::{Decode, Encode};
+use frame_support::dispatch::{DispatchInfo, PostDispatchInfo};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{DispatchInfoOf, Dispatchable, PostDispatchInfoOf, SignedExtension},
+	transaction_validity::TransactionValidityError,
+	DispatchResult,
+};
+use sp_weights::Weight;
+
+/// Source of the size of the storage proof recorded so far while building or importing the
+/// block.
+pub trait StorageProofSize {
+	/// The size of the storage proof in bytes, `None` if the proof isn't recorded.
+	fn storage_proof_size() -> Option<u64>;
+}
+
+/// The proof isn't recorded, e.g. on solo chains.
+impl StorageProofSize for () {
+	fn storage_proof_size() -> Option<u64> {
+		None
+	}
+}
+
+/// Storage weight reclaim mechanism.
+///
+/// This extension checks the size of the storage proof, as reported by `P`, before and after
+/// executing a given extrinsic. The proof size weight that was benchmarked but not consumed is
+/// removed from the block weight, increasing the capacity of the block.
+///
+/// The unspent weight reported by the `PostDispatchInfo` of the extrinsic is refunded by
+/// [`CheckWeight`](crate::CheckWeight) and is accounted for, so both extensions can be used
+/// together in any order.
+///
+/// # Transaction Validity
+///
+/// This extension does not influence any fields of `TransactionValidity`.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, Default, TypeInfo)]
+#[scale_info(skip_type_params(T, P))]
+pub struct StorageWeightReclaim<T: Config + Send + Sync, P>(sp_std::marker::PhantomData<(T, P)>);
+
+impl<T: Config + Send + Sync, P> StorageWeightReclaim<T, P> {
+	/// Create a new `StorageWeightReclaim` instance.
+	pub fn new() -> Self {
+		Self(Default::default())
+	}
+}
+
+impl<T: Config + Send + Sync, P> SignedExtension for StorageWeightReclaim<T, P>
+where
+	T::RuntimeCall: Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
+	P: StorageProofSize + Clone + Eq + Send + Sync + 'static,
+{
+	const IDENTIFIER: &'static str = "StorageWeightReclaim";
+
+	type AccountId = T::AccountId;
+	type Call = T::RuntimeCall;
+	type AdditionalSigned = ();
+	type Pre = Option<u64>;
+
+	fn additional_signed(&self) -> Result<Self::AdditionalSigned, TransactionValidityError> {
+		Ok(())
+	}
+
+	fn pre_dispatch(
+		self,
+		_who: &Self::AccountId,
+		_call: &Self::Call,
+		_info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		Ok(P::storage_proof_size())
+	}
+
+	fn post_dispatch(
+		pre: Option<Self::Pre>,
+		info: &DispatchInfoOf<Self::Call>,
+		post_info: &PostDispatchInfoOf<Self::Call>,
+		_len: usize,
+		_result: &DispatchResult,
+	) -> Result<(), TransactionValidityError> {
+		let Some(Some(pre_dispatch_proof_size)) = pre else { return Ok(()) };
+
+		let Some(post_dispatch_proof_size) = P::storage_proof_size() else {
+			log::debug!(
+				target: LOG_TARGET,
+				"Proof recording enabled during pre-dispatch, now disabled. This should not happen."
+			);
+			return Ok(())
+		};
+		let benchmarked_weight = info.weight.proof_size();
+		let consumed_weight = post_dispatch_proof_size.saturating_sub(pre_dispatch_proof_size);
+
+		// Unspent weight according to the `actual_weight` from `PostDispatchInfo`.
+		// This unspent weight will be refunded by the `CheckWeight` extension, so we need to
+		// account for that.
+		let unspent = post_info.calc_unspent(info).proof_size();
+		let storage_size_diff =
+			benchmarked_weight.saturating_sub(unspent).abs_diff(consumed_weight);
+
+		crate::BlockWeight::<T>::mutate(|current| {
+			if consumed_weight > benchmarked_weight {
+				log::error!(
+					target: LOG_TARGET,
+					"Benchmarked storage weight smaller than consumed storage weight. \
+					benchmarked: {benchmarked_weight} consumed: {consumed_weight} unspent: {unspent}"
+				);
+				current.accrue(Weight::from_parts(0, storage_size_diff), info.class)
+			} else {
+				log::trace!(
+					target: LOG_TARGET,
+					"Reclaiming storage weight. benchmarked: {benchmarked_weight}, \
+					consumed: {consumed_weight} unspent: {unspent}"
+				);
+				current.reduce(Weight::from_parts(0, storage_size_diff), info.class)
+			}
+		});
+		Ok(())
+	}
+}
+
+impl<T: Config + Send + Sync, P> sp_std::fmt::Debug for StorageWeightReclaim<T, P> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		write!(f, "StorageWeightReclaim")
+	}
+
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		mock::{new_test_ext, Test, CALL},
+		BlockWeight, CheckWeight, DispatchClass,
+	};
+	use frame_support::{assert_ok, weights::Weight};
+	use std::cell::Cell;
+
+	thread_local! {
+		static PROOF_SIZE: Cell<Option<u64>> = Cell::new(None);
+	}
+
+	#[derive(Clone, Eq, PartialEq, Default)]
+	struct TestProofSize;
+
+	impl StorageProofSize for TestProofSize {
+		fn storage_proof_size() -> Option<u64> {
+			PROOF_SIZE.with(|p| p.get())
+		}
+	}
+
+	type Reclaim = StorageWeightReclaim<Test, TestProofSize>;
+
+	fn set_proof_size(size: Option<u64>) {
+		PROOF_SIZE.with(|p| p.set(size));
+	}
+
+	fn dispatch(info: &DispatchInfo, post_info: &PostDispatchInfo, proof_sizes: (u64, u64)) {
+		set_proof_size(Some(proof_sizes.0));
+		let pre = Reclaim::new().pre_dispatch(&1, CALL, info, 0).unwrap();
+		assert_eq!(pre, Some(proof_sizes.0));
+
+		set_proof_size(Some(proof_sizes.1));
+		assert_ok!(Reclaim::post_dispatch(Some(pre), info, post_info, 0, &Ok(())));
+	}
+
+	#[test]
+	fn reclaims_unused_proof_size() {
+		new_test_ext().execute_with(|| {
+			BlockWeight::<Test>::mutate(|current| {
+				current.set(Weight::from_parts(0, 1000), DispatchClass::Normal)
+			});
+			let info = DispatchInfo { weight: Weight::from_parts(0, 500), ..Default::default() };
+
+			dispatch(&info, &PostDispatchInfo::default(), (0, 200));
+
+			assert_eq!(BlockWeight::<Test>::get().total().proof_size(), 700);
+		})
+	}
+
+	#[test]
+	fn accrues_underestimated_proof_size() {
+		new_test_ext().execute_with(|| {
+			BlockWeight::<Test>::mutate(|current| {
+				current.set(Weight::from_parts(0, 1000), DispatchClass::Normal)
+			});
+			let info = DispatchInfo { weight: Weight::from_parts(0, 500), ..Default::default() };
+
+			dispatch(&info, &PostDispatchInfo::default(), (100, 800));
+
+			assert_eq!(BlockWeight::<Test>::get().total().proof_size(), 1200);
+		})
+	}
+
+	#[test]
+	fn accounts_for_the_check_weight_refund() {
+		new_test_ext().execute_with(|| {
+			BlockWeight::<Test>::mutate(|current| {
+				current.set(Weight::from_parts(0, 1000), DispatchClass::Normal)
+			});
+			let info = DispatchInfo { weight: Weight::from_parts(0, 500), ..Default::default() };
+			// The extrinsic reports 400 of the 500 benchmarked.
+			let post_info = PostDispatchInfo {
+				actual_weight: Some(Weight::from_parts(0, 400)),
+				pays_fee: Default::default(),
+			};
+
+			dispatch(&info, &post_info, (0, 200));
+			assert_ok!(CheckWeight::<Test>::post_dispatch(None, &info, &post_info, 0, &Ok(())));
+
+			// Only the 200 actually consumed remain.
+			assert_eq!(BlockWeight::<Test>::get().total().proof_size(), 700);
+		})
+	}
+
+	#[test]
+	fn does_nothing_without_proof_recording() {
+		new_test_ext().execute_with(|| {
+			BlockWeight::<Test>::mutate(|current| {
+				current.set(Weight::from_parts(0, 1000), DispatchClass::Normal)
+			});
+			let info = DispatchInfo { weight: Weight::from_parts(0, 500), ..Default::default() };
+
+			set_proof_size(None);
+			let pre = Reclaim::new().pre_dispatch(&1, CALL, &info, 0).unwrap();
+			assert_ok!(Reclaim::post_dispatch(
+				Some(pre),
+				&info,
+				&PostDispatchInfo::default(),
+				0,
+				&Ok(())
+			));
+
+			assert_eq!(BlockWeight::<Test>::get().total().proof_size(), 1000);
+		})
+	}
+}
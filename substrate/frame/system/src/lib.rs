@@ -163,10 +163,14 @@ pub mod weights;
 pub mod migrations;
 
 pub use extensions::{
-	check_genesis::CheckGenesis, check_mortality::CheckMortality,
-	check_non_zero_sender::CheckNonZeroSender, check_nonce::CheckNonce,
-	check_spec_version::CheckSpecVersion, check_tx_version::CheckTxVersion,
+	check_genesis::CheckGenesis,
+	check_mortality::CheckMortality,
+	check_non_zero_sender::CheckNonZeroSender,
+	check_nonce::CheckNonce,
+	check_spec_version::CheckSpecVersion,
+	check_tx_version::CheckTxVersion,
 	check_weight::CheckWeight,
+	storage_weight_reclaim::{StorageProofSize, StorageWeightReclaim},
 };
 // Backward compatible re-export.
 pub use extensions::check_mortality::CheckMortality as CheckEra;
@@ -749,16 +753,33 @@ pub mod pallet {
 			}
 
 			Self::deposit_event(Event::TaskStarted { task: task.clone() });
-			if let Err(err) = task.run() {
-				Self::deposit_event(Event::TaskFailed { task, err });
-				return Err(Error::<T>::FailedTask.into())
+
+			// The declared weight is the budget of the task, meter what it reports to consume.
+			let budget = task.weight();
+			let result = task.run();
+			let post_info = match &result {
+				Ok(post_info) => *post_info,
+				Err(err) => err.post_info,
+			};
+			if let Some(consumed) = post_info.actual_weight.filter(|w| w.any_gt(budget)) {
+				Self::deposit_event(Event::TaskWeightOverrun {
+					task: task.clone(),
+					budget,
+					consumed,
+				});
+			}
+
+			if let Err(mut err) = result {
+				Self::deposit_event(Event::TaskFailed { task, err: err.error });
+				err.error = Error::<T>::FailedTask.into();
+				return Err(err)
 			}
 
 			// Emit a success event, if your design includes events for this pallet.
 			Self::deposit_event(Event::TaskCompleted { task });
 
-			// Return success.
-			Ok(().into())
+			// Return success, refunding the unused budget.
+			Ok(post_info)
 		}
 
 		/// Authorize an upgrade to a given `code_hash` for the runtime. The runtime can be supplied
@@ -836,6 +857,9 @@ pub mod pallet {
 		#[cfg(feature = "experimental")]
 		/// A [`Task`] failed during execution.
 		TaskFailed { task: T::RuntimeTask, err: DispatchError },
+		#[cfg(feature = "experimental")]
+		/// A [`Task`] consumed more weight than its budget.
+		TaskWeightOverrun { task: T::RuntimeTask, budget: Weight, consumed: Weight },
 		/// An upgrade was authorized.
 		UpgradeAuthorized { code_hash: T::Hash, check_version: bool },
 	}
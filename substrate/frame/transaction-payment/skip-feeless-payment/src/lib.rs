@@ -42,6 +42,7 @@ use frame_support::{
 };
 use scale_info::{StaticTypeInfo, TypeInfo};
 use sp_runtime::{
+	signature_batch::EncodedSignatureItem,
 	traits::{DispatchInfoOf, PostDispatchInfoOf, SignedExtension},
 	transaction_validity::{TransactionValidity, TransactionValidityError, ValidTransaction},
 };
@@ -122,6 +123,10 @@ where
 		self.0.additional_signed()
 	}
 
+	fn signature_items(&self) -> sp_std::vec::Vec<EncodedSignatureItem<Self::AccountId>> {
+		self.0.signature_items()
+	}
+
 	fn validate(
 		&self,
 		who: &Self::AccountId,
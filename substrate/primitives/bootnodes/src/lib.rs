@@ -0,0 +1,42 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The runtime api of the bootnodes and reserved peers published on-chain.
+//!
+//! The nodes started with `--onchain-peers` query it at startup and periodically, such that the
+//! peers of a chain can be rotated by its governance without every operator updating the
+//! `--bootnodes` and `--reserved-nodes` of their nodes.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	/// The bootnodes and reserved peers published on-chain.
+	///
+	/// The peers are given by their UTF-8 multiaddress ending with the `/p2p/` component of their
+	/// peer id, e.g. `/dns/bootnode.example.com/tcp/30333/p2p/12D3KooW...`.
+	pub trait BootnodesApi {
+		/// The addresses of the bootnodes, added to the addresses known by the nodes.
+		fn bootnodes() -> Vec<Vec<u8>>;
+
+		/// The addresses of the reserved peers, added to the reserved peers of the nodes.
+		fn reserved_peers() -> Vec<Vec<u8>>;
+	}
+}
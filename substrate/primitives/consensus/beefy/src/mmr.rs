@@ -134,6 +134,22 @@ pub struct BeefyAuthoritySet<AuthoritySetCommitment> {
 /// Details of the next BEEFY authority set.
 pub type BeefyNextAuthoritySet<MerkleRoot> = BeefyAuthoritySet<MerkleRoot>;
 
+/// A BEEFY validator set along with the commitments to it, as BEEFY light clients need them.
+#[derive(Debug, PartialEq, Eq, Clone, Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BeefyValidatorSetCommitment<AuthorityId, MerkleRoot> {
+	/// Id of the set.
+	pub id: crate::ValidatorSetId,
+	/// Public keys of the validators, in the order they sign commitments in.
+	pub validators: Vec<AuthorityId>,
+	/// Merkle root of the encoded public keys of the validators.
+	///
+	/// For ECDSA these are the compressed (33 bytes) public keys.
+	pub public_keys_root: MerkleRoot,
+	/// The set as committed to in the MMR leaves.
+	pub authority_set: BeefyAuthoritySet<MerkleRoot>,
+}
+
 /// Extract the MMR root hash from a digest in the given header, if it exists.
 pub fn find_mmr_root_digest<B: Block>(header: &B::Header) -> Option<MmrRootHash> {
 	let id = OpaqueDigestItemId::Consensus(&BEEFY_ENGINE_ID);
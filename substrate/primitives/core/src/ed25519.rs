@@ -22,7 +22,7 @@ use crate::crypto::{
 	PublicBytes, SecretStringError, SignatureBytes,
 };
 
-use ed25519_zebra::{SigningKey, VerificationKey};
+use ed25519_zebra::{SigningKey, VerificationKey, VerificationKeyBytes};
 
 use sp_std::vec::Vec;
 
@@ -137,6 +137,25 @@ impl Pair {
 			Self::from_seed(&padded_seed)
 		})
 	}
+
+	/// Verify a batch of signatures on messages.
+	///
+	/// Returns true if all the signatures are good, as if each of them was verified by
+	/// [`Pair::verify`]. Returns false if any of them is bad, without telling which one.
+	#[cfg(feature = "std")]
+	pub fn verify_batch<M: AsRef<[u8]>>(items: &[(Signature, M, Public)]) -> bool {
+		let mut verifier = ed25519_zebra::batch::Verifier::new();
+		for (sig, message, public) in items {
+			let Ok(public) = VerificationKeyBytes::try_from(public.as_slice()) else {
+				return false
+			};
+			let Ok(signature) = ed25519_zebra::Signature::try_from(sig.as_ref()) else {
+				return false
+			};
+			verifier.queue((public, signature, message.as_ref()));
+		}
+		verifier.verify(rand::thread_rng()).is_ok()
+	}
 }
 
 impl CryptoType for Public {
@@ -169,6 +188,24 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn verify_batch_works() {
+		let (alice, bob) = (Pair::from_seed(&[1; 32]), Pair::from_seed(&[2; 32]));
+		let items = vec![
+			(alice.sign(b"first"), b"first".to_vec(), alice.public()),
+			(bob.sign(b"second"), b"second".to_vec(), bob.public()),
+		];
+		assert!(Pair::verify_batch(&items));
+		assert!(Pair::verify_batch::<Vec<u8>>(&[]));
+
+		let mut bad = items.clone();
+		bad[1].1 = b"other".to_vec();
+		assert!(!Pair::verify_batch(&bad));
+
+		bad[1] = (bob.sign(b"second"), b"second".to_vec(), alice.public());
+		assert!(!Pair::verify_batch(&bad));
+	}
+
 	#[test]
 	fn seed_and_derive_should_work() {
 		let seed = array_bytes::hex2array_unchecked(
@@ -284,6 +284,27 @@ impl Pair {
 			Err(_) => false,
 		}
 	}
+
+	/// Verify a batch of signatures on messages.
+	///
+	/// Returns true if all the signatures are good, as if each of them was verified by
+	/// [`Pair::verify`]. Returns false if any of them is bad, without telling which one.
+	pub fn verify_batch<M: AsRef<[u8]>>(items: &[(Signature, M, Public)]) -> bool {
+		let mut signatures = Vec::with_capacity(items.len());
+		let mut public_keys = Vec::with_capacity(items.len());
+		for (sig, _, public) in items {
+			let Ok(signature) = schnorrkel::Signature::from_bytes(sig.as_ref()) else {
+				return false
+			};
+			let Ok(public) = PublicKey::from_bytes(public.as_ref()) else { return false };
+			signatures.push(signature);
+			public_keys.push(public);
+		}
+
+		let context = signing_context(SIGNING_CTX);
+		let transcripts = items.iter().map(|(_, message, _)| context.bytes(message.as_ref()));
+		schnorrkel::verify_batch(transcripts, &signatures, &public_keys, false).is_ok()
+	}
 }
 
 impl CryptoType for Public {
@@ -749,6 +770,24 @@ mod tests {
 		assert!(Pair::verify(&signature, &message[..], &public));
 	}
 
+	#[test]
+	fn verify_batch_works() {
+		let (alice, bob) = (Pair::from_seed(&[1; 32]), Pair::from_seed(&[2; 32]));
+		let items = vec![
+			(alice.sign(b"first"), b"first".to_vec(), alice.public()),
+			(bob.sign(b"second"), b"second".to_vec(), bob.public()),
+		];
+		assert!(Pair::verify_batch(&items));
+		assert!(Pair::verify_batch::<Vec<u8>>(&[]));
+
+		let mut bad = items.clone();
+		bad[1].1 = b"other".to_vec();
+		assert!(!Pair::verify_batch(&bad));
+
+		bad[1] = (bob.sign(b"second"), b"second".to_vec(), alice.public());
+		assert!(!Pair::verify_batch(&bad));
+	}
+
 	#[test]
 	fn messed_signature_should_not_work() {
 		let (pair, _) = Pair::generate();
@@ -17,6 +17,7 @@
 
 use crate::{Error, InherentData, InherentIdentifier};
 use sp_runtime::traits::Block as BlockT;
+use std::fmt;
 
 /// Something that can create inherent data providers.
 ///
@@ -77,6 +78,32 @@ impl<Block: BlockT, ExtraArgs: Send, IDPS: InherentDataProvider>
 	}
 }
 
+/// Severity of an issue with the data of an inherent data provider.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HealthSeverity {
+	/// The data is stale or inaccurate, but a block can still be authored with it.
+	Stale,
+	/// The data is unfit to be included in a block, which should not be authored.
+	NotReady,
+}
+
+/// Issue with the data of an inherent data provider, reported before authoring a block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HealthIssue {
+	/// Identifier of the inherent the issue is about.
+	pub identifier: InherentIdentifier,
+	/// Severity of the issue.
+	pub severity: HealthSeverity,
+	/// Description of the issue.
+	pub description: String,
+}
+
+impl fmt::Display for HealthIssue {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}: {}", String::from_utf8_lossy(&self.identifier), self.description)
+	}
+}
+
 /// Something that provides inherent data.
 #[async_trait::async_trait]
 pub trait InherentDataProvider: Send + Sync {
@@ -102,6 +129,16 @@ pub trait InherentDataProvider: Send + Sync {
 		identifier: &InherentIdentifier,
 		error: &[u8],
 	) -> Option<Result<(), Error>>;
+
+	/// Check the health of the provided data, before authoring a block with it.
+	///
+	/// The block authors report the returned issues, and skip authoring the block if any of them
+	/// is [`HealthSeverity::NotReady`].
+	///
+	/// By default no issue is reported.
+	async fn check_health(&self) -> Vec<HealthIssue> {
+		Vec::new()
+	}
 }
 
 #[impl_trait_for_tuples::impl_for_tuples(30)]
@@ -124,4 +161,10 @@ impl InherentDataProvider for Tuple {
 
 		None
 	}
+
+	async fn check_health(&self) -> Vec<HealthIssue> {
+		let mut issues = Vec::new();
+		for_tuples!( #( issues.extend(Tuple.check_health().await); )* );
+		issues
+	}
 }
@@ -467,6 +467,42 @@ mod tests {
 		assert_eq!(inherent_data.get_data::<u32>(&TEST_INHERENT_0).unwrap().unwrap(), 42u32);
 	}
 
+	struct StaleInherentDataProvider;
+
+	#[async_trait::async_trait]
+	impl InherentDataProvider for StaleInherentDataProvider {
+		async fn provide_inherent_data(&self, data: &mut InherentData) -> Result<(), Error> {
+			data.put_data(TEST_INHERENT_1, &7)
+		}
+
+		async fn try_handle_error(
+			&self,
+			_: &InherentIdentifier,
+			_: &[u8],
+		) -> Option<Result<(), Error>> {
+			None
+		}
+
+		async fn check_health(&self) -> Vec<HealthIssue> {
+			vec![HealthIssue {
+				identifier: TEST_INHERENT_1,
+				severity: HealthSeverity::Stale,
+				description: "Data is 42 blocks old".into(),
+			}]
+		}
+	}
+
+	#[test]
+	fn check_health_reports_issues_of_all_providers() {
+		let providers = (TestInherentDataProvider, StaleInherentDataProvider);
+
+		let issues = futures::executor::block_on(providers.check_health());
+
+		assert_eq!(issues.len(), 1);
+		assert_eq!(issues[0].severity, HealthSeverity::Stale);
+		assert_eq!(issues[0].to_string(), "testinh1: Data is 42 blocks old");
+	}
+
 	#[test]
 	fn check_inherents_result_encodes_and_decodes() {
 		let mut result = CheckInherentsResult::new();
@@ -19,9 +19,10 @@
 
 use crate::{
 	generic::CheckedExtrinsic,
+	signature_batch::SignatureItem,
 	traits::{
-		self, Checkable, Extrinsic, ExtrinsicMetadata, IdentifyAccount, MaybeDisplay, Member,
-		SignaturePayload, SignedExtension,
+		self, BatchCheckable, BatchVerify, Checkable, Extrinsic, ExtrinsicMetadata,
+		IdentifyAccount, MaybeDisplay, Member, SignaturePayload, SignedExtension,
 	},
 	transaction_validity::{InvalidTransaction, TransactionValidityError},
 	OpaqueExtrinsic,
@@ -201,6 +202,39 @@ where
 	}
 }
 
+impl<LookupSource, AccountId, Call, Signature, Extra, Lookup> BatchCheckable<Lookup>
+	for UncheckedExtrinsic<LookupSource, Call, Signature, Extra>
+where
+	LookupSource: Member + MaybeDisplay,
+	Call: Encode + Member,
+	Signature: Member + BatchVerify + Decode,
+	<Signature as traits::Verify>::Signer: IdentifyAccount<AccountId = AccountId>,
+	Extra: SignedExtension<AccountId = AccountId>,
+	AccountId: Member + MaybeDisplay,
+	Lookup: traits::Lookup<Source = LookupSource, Target = AccountId>,
+{
+	type Signature = Signature;
+
+	fn signature_items(&self, lookup: &Lookup) -> Vec<SignatureItem<Signature>> {
+		let Some((signed, signature, extra)) = self.signature.as_ref() else { return Vec::new() };
+		let mut items = extra
+			.signature_items()
+			.into_iter()
+			.filter_map(|(signature, msg, signer)| {
+				Some((Signature::decode(&mut &signature[..]).ok()?, msg, signer))
+			})
+			.collect::<Vec<_>>();
+
+		let signed = lookup.lookup(signed.clone()).ok();
+		let raw_payload = SignedPayload::new(self.function.clone(), extra.clone()).ok();
+		if let (Some(signed), Some(raw_payload)) = (signed, raw_payload) {
+			let msg = raw_payload.using_encoded(|payload| payload.to_vec());
+			items.push((signature.clone(), msg, signed));
+		}
+		items
+	}
+}
+
 impl<Address, Call, Signature, Extra> ExtrinsicMetadata
 	for UncheckedExtrinsic<Address, Call, Signature, Extra>
 where
@@ -416,6 +450,7 @@ mod tests {
 	use super::*;
 	use crate::{
 		codec::{Decode, Encode},
+		signature_batch::EncodedSignatureItem,
 		testing::TestSignature as TestSig,
 		traits::{DispatchInfoOf, IdentityLookup, SignedExtension},
 	};
@@ -452,6 +487,35 @@ mod tests {
 		}
 	}
 
+	/// Carries the signature of a co-signer, which would be verified in `pre_dispatch`.
+	#[derive(Debug, Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+	struct CoSigned(TestSig);
+	impl SignedExtension for CoSigned {
+		const IDENTIFIER: &'static str = "CoSigned";
+		type AccountId = u64;
+		type Call = ();
+		type AdditionalSigned = ();
+		type Pre = ();
+
+		fn additional_signed(&self) -> sp_std::result::Result<(), TransactionValidityError> {
+			Ok(())
+		}
+
+		fn pre_dispatch(
+			self,
+			_who: &Self::AccountId,
+			_call: &Self::Call,
+			_info: &DispatchInfoOf<Self::Call>,
+			_len: usize,
+		) -> Result<Self::Pre, TransactionValidityError> {
+			Ok(())
+		}
+
+		fn signature_items(&self) -> Vec<EncodedSignatureItem<u64>> {
+			vec![(self.0.encode(), self.0 .1.clone(), self.0 .0)]
+		}
+	}
+
 	type Ex = UncheckedExtrinsic<TestAccountId, TestCall, TestSig, TestExtra>;
 	type CEx = CheckedExtrinsic<TestAccountId, TestCall, TestExtra>;
 
@@ -500,6 +564,25 @@ mod tests {
 		assert_eq!(Ex::decode(&mut &encoded[..]), Ok(ux));
 	}
 
+	#[test]
+	fn signature_items_include_the_signatures_of_the_extensions() {
+		type CoSignedEx =
+			UncheckedExtrinsic<TestAccountId, TestCall, TestSig, (TestExtra, CoSigned)>;
+
+		let co_signature = TestSig(1, b"co-signed".to_vec());
+		let extra = (TestExtra, CoSigned(co_signature.clone()));
+		let payload = (vec![0u8; 0], extra.clone()).encode();
+		let signature = TestSig(TEST_ACCOUNT, payload.clone());
+		let ux = CoSignedEx::new_signed(vec![0u8; 0], TEST_ACCOUNT, signature.clone(), extra);
+		assert_eq!(
+			ux.signature_items(&TestContext::default()),
+			vec![(co_signature, b"co-signed".to_vec(), 1), (signature, payload, TEST_ACCOUNT)],
+		);
+
+		let ux = CoSignedEx::new_unsigned(vec![0u8; 0]);
+		assert_eq!(ux.signature_items(&TestContext::default()), vec![]);
+	}
+
 	#[test]
 	fn unsigned_check_should_work() {
 		let ux = Ex::new_unsigned(vec![0u8; 0]);
@@ -87,6 +87,7 @@ mod multiaddress;
 pub mod offchain;
 pub mod runtime_logger;
 mod runtime_string;
+pub mod signature_batch;
 #[cfg(feature = "std")]
 pub mod testing;
 pub mod traits;
@@ -203,7 +204,7 @@ impl From<Justification> for Justifications {
 	}
 }
 
-use traits::{Lazy, Verify};
+use traits::{BatchVerify, Lazy, Verify};
 
 use crate::traits::IdentifyAccount;
 #[cfg(feature = "serde")]
@@ -443,6 +444,10 @@ impl std::fmt::Display for MultiSigner {
 impl Verify for MultiSignature {
 	type Signer = MultiSigner;
 	fn verify<L: Lazy<[u8]>>(&self, mut msg: L, signer: &AccountId32) -> bool {
+		if signature_batch::is_verified(self, msg.get(), signer) {
+			return true
+		}
+
 		match (self, signer) {
 			(Self::Ed25519(ref sig), who) => match ed25519::Public::from_slice(who.as_ref()) {
 				Ok(signer) => sig.verify(msg, &signer),
@@ -465,6 +470,39 @@ impl Verify for MultiSignature {
 	}
 }
 
+impl BatchVerify for MultiSignature {
+	fn verify_batch(items: &[(Self, Vec<u8>, AccountId32)]) -> Vec<bool> {
+		let mut valid = vec![false; items.len()];
+		let (mut ed25519_items, mut sr25519_items) = (Vec::new(), Vec::new());
+
+		for (index, (signature, msg, who)) in items.iter().enumerate() {
+			match signature {
+				Self::Ed25519(sig) =>
+					if let Ok(signer) = ed25519::Public::from_slice(who.as_ref()) {
+						ed25519_items.push((index, (sig.clone(), msg.clone(), signer)));
+					},
+				Self::Sr25519(sig) =>
+					if let Ok(signer) = sr25519::Public::from_slice(who.as_ref()) {
+						sr25519_items.push((index, (sig.clone(), msg.clone(), signer)));
+					},
+				Self::Ecdsa(_) => valid[index] = signature.verify(&msg[..], who),
+			}
+		}
+
+		let (indices, ed25519_items): (Vec<_>, Vec<_>) = ed25519_items.into_iter().unzip();
+		for (index, ok) in indices.into_iter().zip(ed25519::Signature::verify_batch(&ed25519_items))
+		{
+			valid[index] = ok;
+		}
+		let (indices, sr25519_items): (Vec<_>, Vec<_>) = sr25519_items.into_iter().unzip();
+		for (index, ok) in indices.into_iter().zip(sr25519::Signature::verify_batch(&sr25519_items))
+		{
+			valid[index] = ok;
+		}
+		valid
+	}
+}
+
 /// Signature verify that can work with any known signature types..
 #[derive(Eq, PartialEq, Clone, Default, Encode, Decode, RuntimeDebug, TypeInfo)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
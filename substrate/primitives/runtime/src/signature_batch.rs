@@ -0,0 +1,108 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Verification of the signatures of many extrinsics together.
+//!
+//! [`with_verified`] verifies the given signatures in batches with [`BatchVerify`] and runs a
+//! closure, e.g. the execution of the extrinsics of a block, in which the good signatures aren't
+//! verified again by the [`Verify`] implementations of [`MultiSignature`](crate::MultiSignature)
+//! and of the `ed25519` and `sr25519` signatures. The bad signatures are verified again one by one
+//! when they are checked, such that the result of the closure is the same as without the batch.
+
+use crate::traits::{BatchVerify, IdentifyAccount, Verify};
+use codec::Encode;
+use sp_std::{collections::btree_set::BTreeSet, vec::Vec};
+
+environmental::environmental!(VERIFIED: BTreeSet<[u8; 32]>);
+
+/// The account of the signer of the signature `S`.
+pub type SignerAccountOf<S> = <<S as Verify>::Signer as IdentifyAccount>::AccountId;
+
+/// A signature, the signed message and the signer.
+pub type SignatureItem<S> = (S, Vec<u8>, SignerAccountOf<S>);
+
+/// An encoded signature, the signed message and the signer, as given by
+/// [`SignedExtension::signature_items`](crate::traits::SignedExtension::signature_items).
+pub type EncodedSignatureItem<AccountId> = (Vec<u8>, Vec<u8>, AccountId);
+
+fn item_key(signature: &impl Encode, msg: &[u8], signer: &impl Encode) -> [u8; 32] {
+	(signature, msg, signer).using_encoded(sp_io::hashing::blake2_256)
+}
+
+/// Whether the given signature was verified by the enclosing [`with_verified`].
+pub(crate) fn is_verified(signature: &impl Encode, msg: &[u8], signer: &impl Encode) -> bool {
+	VERIFIED::with(|verified| verified.contains(&item_key(signature, msg, signer)))
+		.unwrap_or_default()
+}
+
+/// Verify the given signatures together and run `f`, in which the good ones are known to be
+/// verified.
+pub fn with_verified<S, R>(items: &[SignatureItem<S>], f: impl FnOnce() -> R) -> R
+where
+	S: BatchVerify + Encode,
+	SignerAccountOf<S>: Encode,
+{
+	let mut verified = items
+		.iter()
+		.zip(S::verify_batch(items))
+		.filter(|(_, valid)| *valid)
+		.map(|((signature, msg, signer), _)| item_key(signature, msg, signer))
+		.collect::<BTreeSet<_>>();
+
+	VERIFIED::using(&mut verified, f)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{AccountId32, MultiSignature, MultiSigner};
+	use sp_core::{ed25519, sr25519, Pair};
+
+	fn item<P: Pair>(pair: &P, msg: &[u8]) -> SignatureItem<MultiSignature>
+	where
+		MultiSignature: From<P::Signature>,
+		MultiSigner: From<P::Public>,
+	{
+		let signer = MultiSigner::from(pair.public()).into_account();
+		(pair.sign(msg).into(), msg.to_vec(), signer)
+	}
+
+	#[test]
+	fn with_verified_remembers_the_good_signatures() {
+		let alice = sr25519::Pair::from_seed(&[1; 32]);
+		let bob = ed25519::Pair::from_seed(&[2; 32]);
+		let mut items = vec![item(&alice, b"first"), item(&bob, b"second"), item(&bob, b"third")];
+		items[2].1 = b"other".to_vec();
+
+		sp_io::TestExternalities::default().execute_with(|| {
+			assert_eq!(MultiSignature::verify_batch(&items), vec![true, true, false]);
+
+			with_verified(&items, || {
+				for (signature, msg, signer) in &items {
+					assert_eq!(
+						is_verified(signature, msg, signer),
+						signature.verify(&msg[..], signer)
+					);
+				}
+				let unknown: AccountId32 = [0; 32].into();
+				assert!(!is_verified(&items[0].0, &items[0].1, &unknown));
+			});
+
+			assert!(!is_verified(&items[0].0, &items[0].1, &items[0].2));
+		});
+	}
+}
@@ -176,6 +176,15 @@ impl traits::Verify for TestSignature {
 	}
 }
 
+impl traits::BatchVerify for TestSignature {
+	fn verify_batch(items: &[(Self, Vec<u8>, u64)]) -> Vec<bool> {
+		items
+			.iter()
+			.map(|(signature, msg, signer)| traits::Verify::verify(signature, &msg[..], signer))
+			.collect()
+	}
+}
+
 /// Digest item
 pub type DigestItem = generic::DigestItem;
 
@@ -16,7 +16,24 @@
 // limitations under the License.
 
 use alloc::vec::Vec;
+use codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
 pub use sp_core::crypto::KeyTypeId;
+use sp_core::RuntimeDebug;
+use sp_staking::SessionIndex;
+
+/// The validator set of the next session, queued ahead of the session boundary.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct QueuedValidatorSet<ValidatorId, BlockNumber> {
+	/// The index of the session the validators are queued for.
+	pub session_index: SessionIndex,
+	/// The validators of the session.
+	pub validators: Vec<ValidatorId>,
+	/// Whether the validators or their session keys differ from the ones of the current session.
+	pub changed: bool,
+	/// The estimated block the session starts at, `None` if it can't be estimated.
+	pub estimated_start: Option<BlockNumber>,
+}
 
 sp_api::decl_runtime_apis! {
 	/// Session keys runtime api.
@@ -35,4 +52,14 @@ sp_api::decl_runtime_apis! {
 		/// Returns the list of public raw public keys + key type.
 		fn decode_session_keys(encoded: Vec<u8>) -> Option<Vec<(Vec<u8>, sp_core::crypto::KeyTypeId)>>;
 	}
+
+	/// API to query the validator set of the next session before it becomes active.
+	pub trait SessionValidatorsApi<ValidatorId, BlockNumber>
+	where
+		ValidatorId: Codec,
+		BlockNumber: Codec,
+	{
+		/// The validator set queued for the next session.
+		fn queued_validator_set() -> QueuedValidatorSet<ValidatorId, BlockNumber>;
+	}
 }
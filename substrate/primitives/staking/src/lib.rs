@@ -304,6 +304,25 @@ pub trait StakingInterface {
 	fn set_current_era(era: EraIndex);
 }
 
+/// A slash deferred to a later era, until which it can still be cancelled.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct DeferredSlash<AccountId, Balance> {
+	/// The era at the start of which the slash is applied.
+	pub apply_era: EraIndex,
+	/// The index of the slash among the ones applied in `apply_era`.
+	pub index: u32,
+	/// The stash of the slashed validator.
+	pub validator: AccountId,
+	/// The slash of the validator's own stake.
+	pub own: Balance,
+	/// The slash of the stake of each of the validator's nominators.
+	pub others: Vec<(AccountId, Balance)>,
+	/// The reporters of the offence.
+	pub reporters: Vec<AccountId>,
+	/// The payout to the reporters.
+	pub payout: Balance,
+}
+
 /// The amount of exposure for an era that an individual nominator has (susceptible to slashing).
 #[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
 pub struct IndividualExposure<AccountId, Balance: HasCompact> {
@@ -146,7 +146,7 @@ mod std_reexport {
 		basic::BasicExternalities,
 		in_memory_backend::new_in_mem,
 		read_only::{InspectState, ReadOnlyExternalities},
-		testing::TestExternalities,
+		testing::{TestExternalities, TestExternalitiesSnapshot},
 		trie_backend::create_proof_check_backend,
 	};
 	pub use sp_trie::{
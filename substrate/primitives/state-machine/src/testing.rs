@@ -205,6 +205,42 @@ where
 		(raw_key_values, *self.backend.root())
 	}
 
+	/// Take a snapshot of the storage, to roll back to with [`restore`](Self::restore).
+	///
+	/// The backend is cloned as is, without recomputing its trie, along with the changes that are
+	/// overlaid on it. The offchain storage and the extensions are not part of the snapshot.
+	pub fn snapshot(&self) -> TestExternalitiesSnapshot<H> {
+		TestExternalitiesSnapshot {
+			overlay: self.overlay.clone(),
+			backend: self.backend.clone(),
+			state_version: self.state_version,
+		}
+	}
+
+	/// Roll the storage back to `snapshot`.
+	///
+	/// Only the changes overlaid on the backend are copied back, unless the backend itself was
+	/// changed since the snapshot, e.g. by [`commit_all`](Self::commit_all). This makes it cheap
+	/// to run many cases from the same snapshot.
+	pub fn restore(&mut self, snapshot: &TestExternalitiesSnapshot<H>) {
+		self.overlay = snapshot.overlay.clone();
+		if self.backend.root() != snapshot.backend.root() {
+			self.backend = snapshot.backend.clone();
+		}
+		self.state_version = snapshot.state_version;
+	}
+
+	/// Create a new instance of `TestExternalities` with the storage of `snapshot`.
+	pub fn from_snapshot(snapshot: &TestExternalitiesSnapshot<H>) -> Self {
+		Self {
+			overlay: snapshot.overlay.clone(),
+			backend: snapshot.backend.clone(),
+			offchain_db: Default::default(),
+			extensions: Default::default(),
+			state_version: snapshot.state_version,
+		}
+	}
+
 	/// Return a new backend with all pending changes.
 	///
 	/// In contrast to [`commit_all`](Self::commit_all) this will not panic if there are open
@@ -279,6 +315,31 @@ where
 	}
 }
 
+/// A snapshot of the storage of [`TestExternalities`].
+///
+/// Created by [`TestExternalities::snapshot`].
+pub struct TestExternalitiesSnapshot<H: Hasher>
+where
+	H::Out: codec::Codec + Ord,
+{
+	overlay: OverlayedChanges<H>,
+	backend: InMemoryBackend<H>,
+	state_version: StateVersion,
+}
+
+impl<H: Hasher> Clone for TestExternalitiesSnapshot<H>
+where
+	H::Out: codec::Codec + Ord,
+{
+	fn clone(&self) -> Self {
+		Self {
+			overlay: self.overlay.clone(),
+			backend: self.backend.clone(),
+			state_version: self.state_version,
+		}
+	}
+}
+
 impl<H: Hasher> std::fmt::Debug for TestExternalities<H>
 where
 	H::Out: Ord + codec::Codec,
@@ -453,6 +514,30 @@ mod tests {
 		assert!(recovered_ext.backend.backend_storage().keys().values().all(|r| *r == 2));
 	}
 
+	#[test]
+	fn snapshot_and_restore() {
+		let mut ext = TestExternalities::<BlakeTwo256>::default();
+		ext.insert(b"doe".to_vec(), b"reindeer".to_vec());
+		ext.ext().set_storage(b"dog".to_vec(), b"puppy".to_vec());
+		let snapshot = ext.snapshot();
+
+		ext.ext().set_storage(b"dog".to_vec(), b"cat".to_vec());
+		ext.ext().clear_storage(b"doe");
+		ext.restore(&snapshot);
+		assert_eq!(ext.ext().storage(b"doe"), Some(b"reindeer".to_vec()));
+		assert_eq!(ext.ext().storage(b"dog"), Some(b"puppy".to_vec()));
+
+		// The backend itself is rolled back as well.
+		ext.commit_all().unwrap();
+		ext.insert(b"doe".to_vec(), b"deer".to_vec());
+		ext.restore(&snapshot);
+		assert_eq!(ext.backend.storage(b"doe").unwrap(), Some(b"reindeer".to_vec()));
+		assert_eq!(ext.backend.storage(b"dog").unwrap(), None);
+
+		let copy = TestExternalities::from_snapshot(&snapshot);
+		assert_eq!(copy.as_backend().root(), ext.as_backend().root());
+	}
+
 	#[test]
 	fn set_and_retrieve_code() {
 		let mut ext = TestExternalities::<BlakeTwo256>::default();
@@ -468,6 +553,7 @@ mod tests {
 	fn check_send() {
 		fn assert_send<T: Send>() {}
 		assert_send::<TestExternalities<BlakeTwo256>>();
+		assert_send::<TestExternalitiesSnapshot<BlakeTwo256>>();
 	}
 
 	#[test]
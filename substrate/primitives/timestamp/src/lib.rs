@@ -246,4 +246,33 @@ impl sp_inherents::InherentDataProvider for InherentDataProvider {
 			identifier, error,
 		)?))))
 	}
+
+	async fn check_health(&self) -> Vec<sp_inherents::HealthIssue> {
+		use sp_inherents::{HealthIssue, HealthSeverity};
+
+		let now = Timestamp::current();
+		let issue = |severity, description| {
+			vec![HealthIssue { identifier: INHERENT_IDENTIFIER, severity, description }]
+		};
+
+		// Blocks too far in the future are rejected by the other nodes, whereas blocks in the past
+		// are accepted but hint at a clock or a data source lagging behind.
+		match (self.timestamp.checked_sub(now), now.checked_sub(self.timestamp)) {
+			(Some(ahead), _) if ahead > self.max_drift => issue(
+				HealthSeverity::NotReady,
+				format!(
+					"Timestamp is {}ms ahead of the system time, more than the maximum drift of {}ms",
+					*ahead, *self.max_drift,
+				),
+			),
+			(_, Some(behind)) if behind > self.max_drift => issue(
+				HealthSeverity::Stale,
+				format!(
+					"Timestamp is {}ms behind the system time, more than the maximum drift of {}ms",
+					*behind, *self.max_drift,
+				),
+			),
+			_ => Vec::new(),
+		}
+	}
 }
@@ -763,6 +763,18 @@ impl PalletCmd {
 			self.print_summary(&batches, &storage_info, pov_modes.clone())
 		}
 
+		if let Some(path) = &self.json_analysis_file {
+			writer::write_json_analysis(
+				&batches,
+				&storage_info,
+				&component_ranges,
+				pov_modes.clone(),
+				self.default_pov_mode,
+				path,
+				self,
+			)?;
+		}
+
 		// Create the weights.rs file.
 		if let Some(output_path) = &self.output {
 			writer::write_results(
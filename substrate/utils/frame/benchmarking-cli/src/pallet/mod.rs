@@ -89,6 +89,14 @@ pub struct PalletCmd {
 	#[arg(long, conflicts_with = "json_output")]
 	pub json_file: Option<PathBuf>,
 
+	/// Write the analyzed results in JSON format into the given file.
+	///
+	/// In contrast to the raw results, this contains the component ranges, weight formulas and
+	/// per storage item reads, writes and proof sizes of each benchmark. The output follows a
+	/// versioned schema meant to be consumed by tooling, e.g. to detect weight regressions.
+	#[arg(long)]
+	pub json_analysis_file: Option<PathBuf>,
+
 	/// Don't print the median-slopes linear regression analysis.
 	#[arg(long)]
 	pub no_median_slopes: bool,
@@ -18,7 +18,7 @@
 // Outputs benchmark results to Rust files that can be ingested by the runtime.
 
 use std::{
-	collections::{HashMap, HashSet},
+	collections::{BTreeMap, HashMap, HashSet},
 	fs,
 	path::PathBuf,
 };
@@ -44,6 +44,8 @@ use sp_runtime::traits::Zero;
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 const TEMPLATE: &str = include_str!("./template.hbs");
+// Version of the `--json-analysis-file` schema, to be bumped on any breaking change.
+const JSON_ANALYSIS_VERSION: u32 = 1;
 
 // This is the final structure we will pass to the Handlebars template.
 #[derive(Serialize, Default, Debug, Clone)]
@@ -84,6 +86,9 @@ struct BenchmarkData {
 	comments: Vec<String>,
 	#[serde(serialize_with = "string_serialize")]
 	min_execution_time: u128,
+	// Only used for the JSON analysis.
+	#[serde(skip)]
+	storage: Vec<StorageAccess>,
 }
 
 // This forwards some specific metadata from the `PalletCmd`
@@ -118,6 +123,135 @@ struct ComponentSlope {
 	error: u128,
 }
 
+// The accesses of a benchmark to a storage item, i.e. to all keys sharing a prefix.
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct StorageAccess {
+	prefix: Vec<u8>,
+	pallet: Option<String>,
+	storage: Option<String>,
+	reads: u32,
+	writes: u32,
+	proof_size: u128,
+	component_proof_size: Vec<ComponentSlope>,
+}
+
+// The root of the `--json-analysis-file` output.
+#[derive(Serialize, Debug)]
+struct JsonAnalysis {
+	version: u32,
+	benchmarks: Vec<JsonBenchmark>,
+}
+
+// The analysis of a single benchmark.
+#[derive(Serialize, Debug)]
+struct JsonBenchmark {
+	pallet: String,
+	instance: String,
+	benchmark: String,
+	components: Vec<JsonComponent>,
+	weight: JsonFormula,
+	reads: JsonFormula,
+	writes: JsonFormula,
+	proof_size: JsonFormula,
+	recorded_proof_size: JsonFormula,
+	min_execution_time: u128,
+	storage: Vec<JsonStorageAccess>,
+}
+
+// A component along with the range it was benchmarked over.
+#[derive(Serialize, Debug)]
+struct JsonComponent {
+	name: String,
+	min: Option<u32>,
+	max: Option<u32>,
+	is_used: bool,
+}
+
+// A linear formula of the form `base + sum(slope * component)`.
+#[derive(Serialize, Debug)]
+struct JsonFormula {
+	base: u128,
+	slopes: Vec<JsonSlope>,
+}
+
+#[derive(Serialize, Debug)]
+struct JsonSlope {
+	component: String,
+	slope: u128,
+	error: u128,
+}
+
+#[derive(Serialize, Debug)]
+struct JsonStorageAccess {
+	// Hex encoded, `0x` prefixed.
+	prefix: String,
+	pallet: Option<String>,
+	storage: Option<String>,
+	reads: u32,
+	writes: u32,
+	proof_size: JsonFormula,
+}
+
+impl JsonFormula {
+	fn new(base: u128, slopes: &[ComponentSlope]) -> Self {
+		let slopes = slopes
+			.iter()
+			.map(|s| JsonSlope { component: s.name.clone(), slope: s.slope, error: s.error })
+			.collect();
+		Self { base, slopes }
+	}
+}
+
+impl JsonBenchmark {
+	fn new(pallet: String, instance: String, data: BenchmarkData) -> Self {
+		let components = data
+			.components
+			.iter()
+			.map(|component| {
+				let range = data.component_ranges.iter().find(|r| r.name == component.name);
+				JsonComponent {
+					name: component.name.clone(),
+					min: range.map(|r| r.min),
+					max: range.map(|r| r.max),
+					is_used: component.is_used,
+				}
+			})
+			.collect();
+		let storage = data
+			.storage
+			.iter()
+			.map(|access| JsonStorageAccess {
+				prefix: format!("0x{}", HexDisplay::from(&access.prefix)),
+				pallet: access.pallet.clone(),
+				storage: access.storage.clone(),
+				reads: access.reads,
+				writes: access.writes,
+				proof_size: JsonFormula::new(access.proof_size, &access.component_proof_size),
+			})
+			.collect();
+
+		Self {
+			pallet,
+			instance,
+			benchmark: data.name,
+			components,
+			weight: JsonFormula::new(data.base_weight, &data.component_weight),
+			reads: JsonFormula::new(data.base_reads, &data.component_reads),
+			writes: JsonFormula::new(data.base_writes, &data.component_writes),
+			proof_size: JsonFormula::new(
+				data.base_calculated_proof_size,
+				&data.component_calculated_proof_size,
+			),
+			recorded_proof_size: JsonFormula::new(
+				data.base_recorded_proof_size,
+				&data.component_recorded_proof_size,
+			),
+			min_execution_time: data.min_execution_time,
+			storage,
+		}
+	}
+}
+
 // Small helper to create an `io::Error` from a string.
 fn io_error(s: &str) -> std::io::Error {
 	use std::io::{Error, ErrorKind};
@@ -341,6 +475,8 @@ fn get_benchmark_data(
 	}
 	used_calculated_proof_size.sort_by(|a, b| a.name.cmp(&b.name));
 
+	let storage = storage_accesses(&batch.db_results, storage_info, &proof_size_per_components);
+
 	// This puts a marker on any component which is entirely unused in the weight formula.
 	let components = batch.time_results[0]
 		.components
@@ -373,7 +509,63 @@ fn get_benchmark_data(
 		component_ranges,
 		comments,
 		min_execution_time: extrinsic_time.minimum,
+		storage,
+	}
+}
+
+// Sum up the reads and writes of the non-whitelisted keys per storage item, taking the maximum
+// over all runs of the benchmark.
+fn storage_accesses(
+	results: &[BenchmarkResult],
+	storage_info: &[StorageInfo],
+	proof_size_per_prefix: &[(Vec<u8>, Vec<ComponentSlope>, u128)],
+) -> Vec<StorageAccess> {
+	let mut accesses = BTreeMap::<Vec<u8>, (u32, u32)>::new();
+	for result in results {
+		let mut per_prefix = HashMap::<&[u8], (u32, u32)>::new();
+		for (key, reads, writes, whitelisted) in &result.keys {
+			if *whitelisted {
+				continue
+			}
+			let (total_reads, total_writes) =
+				per_prefix.entry(&key[..key.len().min(32)]).or_default();
+			*total_reads += reads;
+			*total_writes += writes;
+		}
+
+		for (prefix, (reads, writes)) in per_prefix {
+			let (max_reads, max_writes) = accesses.entry(prefix.to_vec()).or_default();
+			*max_reads = (*max_reads).max(reads);
+			*max_writes = (*max_writes).max(writes);
+		}
 	}
+
+	accesses
+		.into_iter()
+		.map(|(prefix, (reads, writes))| {
+			let info = storage_info.iter().find(|info| info.prefix == prefix);
+			let (proof_size, component_proof_size) = proof_size_per_prefix
+				.iter()
+				.find(|(p, _, _)| *p == prefix)
+				.map(|(_, slopes, base)| {
+					(
+						*base,
+						slopes.iter().filter(|s| !s.slope.is_zero()).cloned().collect::<Vec<_>>(),
+					)
+				})
+				.unwrap_or_default();
+
+			StorageAccess {
+				pallet: info.map(|i| String::from_utf8_lossy(&i.pallet_name).into_owned()),
+				storage: info.map(|i| String::from_utf8_lossy(&i.storage_name).into_owned()),
+				prefix,
+				reads,
+				writes,
+				proof_size,
+				component_proof_size,
+			}
+		})
+		.collect()
 }
 
 /// Create weight file from benchmark data and Handlebars template.
@@ -511,6 +703,54 @@ pub(crate) fn write_results(
 	Ok(())
 }
 
+/// Write the analyzed results as JSON, following a versioned schema meant for tooling.
+pub(crate) fn write_json_analysis(
+	batches: &[BenchmarkBatchSplitResults],
+	storage_info: &[StorageInfo],
+	component_ranges: &ComponentRangeMap,
+	pov_modes: PovModesMap,
+	default_pov_mode: PovEstimationMode,
+	path: &PathBuf,
+	cmd: &PalletCmd,
+) -> Result<(), sc_cli::Error> {
+	let analysis_choice: AnalysisChoice =
+		cmd.output_analysis.clone().try_into().map_err(io_error)?;
+	let pov_analysis_choice: AnalysisChoice =
+		cmd.output_pov_analysis.clone().try_into().map_err(io_error)?;
+
+	let all_results = map_results(
+		batches,
+		storage_info,
+		component_ranges,
+		pov_modes,
+		default_pov_mode,
+		&analysis_choice,
+		&pov_analysis_choice,
+		cmd.worst_case_map_values,
+		cmd.additional_trie_layers,
+	)?;
+
+	let mut benchmarks = all_results
+		.into_iter()
+		.flat_map(|((pallet, instance), results)| {
+			results
+				.into_iter()
+				.map(move |data| JsonBenchmark::new(pallet.clone(), instance.clone(), data))
+		})
+		.collect::<Vec<_>>();
+	benchmarks.sort_by(|a, b| {
+		(&a.pallet, &a.instance, &a.benchmark).cmp(&(&b.pallet, &b.instance, &b.benchmark))
+	});
+
+	let json =
+		serde_json::to_string_pretty(&JsonAnalysis { version: JSON_ANALYSIS_VERSION, benchmarks })
+			.map_err(|e| format!("Serializing into JSON: {:?}", e))?;
+	fs::write(path, json)?;
+	println!("Created file: {:?}", path);
+
+	Ok(())
+}
+
 /// This function looks at the keys touched during the benchmark, and the storage info we collected
 /// from the pallets, and creates comments with information about the storage keys touched during
 /// each benchmark.
@@ -1246,6 +1486,49 @@ mod test {
 		);
 	}
 
+	#[test]
+	fn storage_accesses_are_reported() {
+		let mapped_results = map_results(
+			&[test_data(b"bounded", b"bounded", BenchmarkParameter::d, 4, 6)],
+			&test_storage_info(),
+			&Default::default(),
+			Default::default(),
+			PovEstimationMode::MaxEncodedLen,
+			&AnalysisChoice::default(),
+			&AnalysisChoice::MedianSlopes,
+			1_000_000,
+			0,
+		)
+		.unwrap();
+		let result = mapped_results
+			.get(&("bounded_pallet".to_string(), "instance".to_string()))
+			.unwrap()[0]
+			.clone();
+
+		// The largest run reads and writes the key `4 + 6 * 4` times.
+		assert_eq!(
+			result.storage,
+			vec![StorageAccess {
+				prefix: b"bounded".to_vec(),
+				pallet: Some("bounded".into()),
+				storage: Some("bounded".into()),
+				reads: 28,
+				writes: 28,
+				proof_size: 10028,
+				component_proof_size: vec![ComponentSlope {
+					name: "d".into(),
+					slope: 15042,
+					error: 0
+				}],
+			}]
+		);
+
+		let json = JsonBenchmark::new("bounded_pallet".into(), "instance".into(), result);
+		assert_eq!(json.storage[0].prefix, "0x626f756e646564");
+		assert_eq!(json.components[0].name, "d");
+		assert!(json.components[0].is_used);
+	}
+
 	#[test]
 	fn additional_trie_layers_work() {
 		let mapped_results = map_results(
@@ -17,7 +17,12 @@
 
 //! System FRAME specific RPC methods.
 
-use std::{fmt::Display, sync::Arc};
+use std::{
+	collections::HashMap,
+	fmt::Display,
+	sync::{Arc, Mutex},
+	time::{Duration, Instant},
+};
 
 use codec::{self, Codec, Decode, Encode};
 use jsonrpsee::{
@@ -32,10 +37,20 @@ use sp_api::ApiExt;
 use sp_block_builder::BlockBuilder;
 use sp_blockchain::HeaderBackend;
 use sp_core::{hexdisplay::HexDisplay, Bytes};
-use sp_runtime::{legacy, traits};
+use sp_runtime::{
+	legacy,
+	traits::{self, UniqueSaturatedFrom, UniqueSaturatedInto},
+	ApplyExtrinsicResult,
+};
 
 pub use frame_system_rpc_runtime_api::AccountNonceApi;
 
+/// Maximum number of nonces that can be reserved with a single call.
+pub const MAX_RESERVED_NONCES: u32 = 1024;
+
+/// How long nonce reservations are kept by default.
+pub const DEFAULT_NONCE_RESERVATION_TTL: Duration = Duration::from_secs(60);
+
 /// System RPC methods.
 #[rpc(client, server)]
 pub trait SystemApi<BlockHash, AccountId, Nonce> {
@@ -47,9 +62,26 @@ pub trait SystemApi<BlockHash, AccountId, Nonce> {
 	#[method(name = "system_accountNextIndex", aliases = ["account_nextIndex"])]
 	async fn nonce(&self, account: AccountId) -> RpcResult<Nonce>;
 
+	/// Reserves the next `count` indices (aka nonces) for given account.
+	///
+	/// The reserved nonces follow both the next valid index (see `system_accountNextIndex`)
+	/// and the nonces previously reserved for the account, so concurrent callers never get
+	/// the same nonce. Reservations of an account expire once it didn't reserve any nonces
+	/// for a while, after which the unused nonces are handed out again.
+	#[method(name = "system_reserveNonces")]
+	async fn reserve_nonces(&self, account: AccountId, count: u32) -> RpcResult<Vec<Nonce>>;
+
 	/// Dry run an extrinsic at a given block. Return SCALE encoded ApplyExtrinsicResult.
 	#[method(name = "system_dryRun", aliases = ["system_dryRunAt"])]
 	async fn dry_run(&self, extrinsic: Bytes, at: Option<BlockHash>) -> RpcResult<Bytes>;
+
+	/// Dry run an extrinsic of the given account after its pending transactions.
+	///
+	/// The ready transactions of the account in the pool, following its nonce at the best
+	/// block, are applied first, so the result tells whether the extrinsic still succeeds once
+	/// they are included. Return SCALE encoded ApplyExtrinsicResult.
+	#[method(name = "system_dryRunPending")]
+	async fn dry_run_pending(&self, extrinsic: Bytes, account: AccountId) -> RpcResult<Bytes>;
 }
 
 /// Error type of this RPC api.
@@ -58,6 +90,8 @@ pub enum Error {
 	DecodeError,
 	/// The call to runtime failed.
 	RuntimeError,
+	/// The requested number of nonces can't be reserved.
+	InvalidNonceCount,
 }
 
 impl From<Error> for i32 {
@@ -65,22 +99,49 @@ impl From<Error> for i32 {
 		match e {
 			Error::RuntimeError => 1,
 			Error::DecodeError => 2,
+			Error::InvalidNonceCount => 3,
 		}
 	}
 }
 
+/// Nonces reserved for an account.
+struct NonceReservation {
+	/// The nonce following the last reserved one.
+	next: u128,
+	/// When the reservation expires.
+	expires_at: Instant,
+}
+
 /// An implementation of System-specific RPC methods on full client.
 pub struct System<P: TransactionPool, C, B> {
 	client: Arc<C>,
 	pool: Arc<P>,
 	deny_unsafe: DenyUnsafe,
+	/// Nonce reservations keyed by the encoded account id.
+	reservations: Mutex<HashMap<Vec<u8>, NonceReservation>>,
+	reservation_ttl: Duration,
 	_marker: std::marker::PhantomData<B>,
 }
 
 impl<P: TransactionPool, C, B> System<P, C, B> {
 	/// Create new `FullSystem` given client and transaction pool.
 	pub fn new(client: Arc<C>, pool: Arc<P>, deny_unsafe: DenyUnsafe) -> Self {
-		Self { client, pool, deny_unsafe, _marker: Default::default() }
+		Self {
+			client,
+			pool,
+			deny_unsafe,
+			reservations: Default::default(),
+			reservation_ttl: DEFAULT_NONCE_RESERVATION_TTL,
+			_marker: Default::default(),
+		}
+	}
+
+	/// Set how long nonces reserved with `system_reserveNonces` are kept.
+	///
+	/// Each reservation extends the expiry of all nonces reserved for the account.
+	pub fn with_nonce_reservation_ttl(mut self, ttl: Duration) -> Self {
+		self.reservation_ttl = ttl;
+		self
 	}
 }
 
@@ -93,7 +154,7 @@ where
 	C: Send + Sync + 'static,
 	C::Api: AccountNonceApi<Block, AccountId, Nonce>,
 	C::Api: BlockBuilder<Block>,
-	P: TransactionPool + 'static,
+	P: TransactionPool<Block = Block> + 'static,
 	Block: traits::Block,
 	AccountId: Clone + Display + Codec + Send + 'static,
 	Nonce: Clone + Display + Codec + Send + traits::AtLeast32Bit + 'static,
@@ -112,6 +173,39 @@ where
 		Ok(adjust_nonce(&*self.pool, account, nonce))
 	}
 
+	async fn reserve_nonces(&self, account: AccountId, count: u32) -> RpcResult<Vec<Nonce>> {
+		self.deny_unsafe.check_if_safe()?;
+
+		if count == 0 || count > MAX_RESERVED_NONCES {
+			return Err(ErrorObject::owned(
+				Error::InvalidNonceCount.into(),
+				"Unable to reserve nonces.",
+				Some(format!("Expected between 1 and {MAX_RESERVED_NONCES} nonces, got {count}")),
+			))
+		}
+
+		let next_nonce: u128 =
+			SystemApiServer::<<Block as traits::Block>::Hash, AccountId, Nonce>::nonce(
+				self,
+				account.clone(),
+			)
+			.await?
+			.unique_saturated_into();
+
+		let now = Instant::now();
+		let mut reservations = self.reservations.lock().expect("Lock poisoned; qed");
+		reservations.retain(|_, reservation| reservation.expires_at > now);
+
+		let key = account.encode();
+		let start = reservations.get(&key).map_or(next_nonce, |r| r.next.max(next_nonce));
+		let end = start.saturating_add(count.into());
+		reservations
+			.insert(key, NonceReservation { next: end, expires_at: now + self.reservation_ttl });
+
+		log::debug!(target: "rpc", "Reserved nonces {}..{} for {}", start, end, account);
+		Ok((start..end).map(Nonce::unique_saturated_from).collect())
+	}
+
 	async fn dry_run(
 		&self,
 		extrinsic: Bytes,
@@ -123,55 +217,77 @@ where
 			// If the block hash is not supplied assume the best block.
 			self.client.info().best_hash);
 
-		let uxt: <Block as traits::Block>::Extrinsic =
-			Decode::decode(&mut &*extrinsic).map_err(|e| {
-				ErrorObject::owned(
-					Error::DecodeError.into(),
-					"Unable to dry run extrinsic",
-					Some(e.to_string()),
-				)
-			})?;
-
-		let api_version = api
-			.api_version::<dyn BlockBuilder<Block>>(best_hash)
-			.map_err(|e| {
-				ErrorObject::owned(
-					Error::RuntimeError.into(),
-					"Unable to dry run extrinsic.",
-					Some(e.to_string()),
-				)
-			})?
-			.ok_or_else(|| {
-				ErrorObject::owned(
-					Error::RuntimeError.into(),
-					"Unable to dry run extrinsic.",
-					Some(format!("Could not find `BlockBuilder` api for block `{:?}`.", best_hash)),
-				)
-			})?;
-
-		let result = if api_version < 6 {
-			#[allow(deprecated)]
-			api.apply_extrinsic_before_version_6(best_hash, uxt)
-				.map(legacy::byte_sized_error::convert_to_latest)
-				.map_err(|e| {
-					ErrorObject::owned(
-						Error::RuntimeError.into(),
-						"Unable to dry run extrinsic.",
-						Some(e.to_string()),
-					)
-				})?
-		} else {
-			api.apply_extrinsic(best_hash, uxt).map_err(|e| {
-				ErrorObject::owned(
-					Error::RuntimeError.into(),
-					"Unable to dry run extrinsic.",
-					Some(e.to_string()),
-				)
-			})?
-		};
+		let uxt = decode_extrinsic::<Block>(&extrinsic)?;
+		let result = apply_extrinsic::<Block, _>(&*api, best_hash, uxt)?;
 
 		Ok(Encode::encode(&result).into())
 	}
+
+	async fn dry_run_pending(&self, extrinsic: Bytes, account: AccountId) -> RpcResult<Bytes> {
+		self.deny_unsafe.check_if_safe()?;
+		let uxt = decode_extrinsic::<Block>(&extrinsic)?;
+		let api = self.client.runtime_api();
+		let best_hash = self.client.info().best_hash;
+
+		let nonce = api.account_nonce(best_hash, account.clone()).map_err(|e| {
+			ErrorObject::owned(
+				Error::RuntimeError.into(),
+				"Unable to query nonce.",
+				Some(e.to_string()),
+			)
+		})?;
+
+		// The changes of the pending transactions are kept by `api` for the calls that follow.
+		let (_, pending) = pending_transactions(&*self.pool, account, nonce);
+		for tx in pending {
+			let result = apply_extrinsic::<Block, _>(&*api, best_hash, tx.data().clone())?;
+			log::debug!(target: "rpc", "Dry run of pending transaction {:?}: {:?}", tx.hash(), result);
+		}
+		let result = apply_extrinsic::<Block, _>(&*api, best_hash, uxt)?;
+
+		Ok(Encode::encode(&result).into())
+	}
+}
+
+fn decode_extrinsic<Block: traits::Block>(extrinsic: &[u8]) -> RpcResult<Block::Extrinsic> {
+	Decode::decode(&mut &*extrinsic).map_err(|e| {
+		ErrorObject::owned(
+			Error::DecodeError.into(),
+			"Unable to dry run extrinsic",
+			Some(e.to_string()),
+		)
+	})
+}
+
+/// Apply an extrinsic on top of the changes made by the previous calls to `api`.
+fn apply_extrinsic<Block, Api>(
+	api: &Api,
+	at: Block::Hash,
+	uxt: Block::Extrinsic,
+) -> RpcResult<ApplyExtrinsicResult>
+where
+	Block: traits::Block,
+	Api: BlockBuilder<Block> + ApiExt<Block>,
+{
+	let runtime_error = |e: String| {
+		ErrorObject::owned(Error::RuntimeError.into(), "Unable to dry run extrinsic.", Some(e))
+	};
+
+	let api_version = api
+		.api_version::<dyn BlockBuilder<Block>>(at)
+		.map_err(|e| runtime_error(e.to_string()))?
+		.ok_or_else(|| {
+			runtime_error(format!("Could not find `BlockBuilder` api for block `{:?}`.", at))
+		})?;
+
+	if api_version < 6 {
+		#[allow(deprecated)]
+		api.apply_extrinsic_before_version_6(at, uxt)
+			.map(legacy::byte_sized_error::convert_to_latest)
+			.map_err(|e| runtime_error(e.to_string()))
+	} else {
+		api.apply_extrinsic(at, uxt).map_err(|e| runtime_error(e.to_string()))
+	}
 }
 
 /// Adjust account nonce from state, so that tx with the nonce will be
@@ -183,6 +299,21 @@ where
 	Nonce: Clone + std::fmt::Display + Encode + traits::AtLeast32Bit + 'static,
 {
 	log::debug!(target: "rpc", "State nonce for {}: {}", account, nonce);
+	pending_transactions(pool, account, nonce).0
+}
+
+/// Ready txpool transactions of an account following the nonce from state, along with the
+/// nonce following them.
+fn pending_transactions<P, AccountId, Nonce>(
+	pool: &P,
+	account: AccountId,
+	nonce: Nonce,
+) -> (Nonce, Vec<Arc<P::InPoolTransaction>>)
+where
+	P: TransactionPool,
+	AccountId: Clone + std::fmt::Display + Encode,
+	Nonce: Clone + std::fmt::Display + Encode + traits::AtLeast32Bit + 'static,
+{
 	// Now we need to query the transaction pool
 	// and find transactions originating from the same sender.
 	//
@@ -191,6 +322,7 @@ where
 	// that matches the current one.
 	let mut current_nonce = nonce.clone();
 	let mut current_tag = (account.clone(), nonce).encode();
+	let mut pending = Vec::new();
 	for tx in pool.ready() {
 		log::debug!(
 			target: "rpc",
@@ -204,10 +336,11 @@ where
 		if tx.provides().get(0) == Some(&current_tag) {
 			current_nonce += traits::One::one();
 			current_tag = (account.clone(), current_nonce.clone()).encode();
+			pending.push(tx);
 		}
 	}
 
-	current_nonce
+	(current_nonce, pending)
 }
 
 #[cfg(test)]
@@ -259,6 +392,85 @@ mod tests {
 		assert_eq!(nonce.unwrap(), 2);
 	}
 
+	#[tokio::test]
+	async fn should_reserve_nonces_after_pool_and_previous_reservations() {
+		sp_tracing::try_init_simple();
+
+		// given
+		let client = Arc::new(substrate_test_runtime_client::new());
+		let spawner = sp_core::testing::TaskExecutor::new();
+		let pool =
+			BasicPool::new_full(Default::default(), true.into(), None, spawner, client.clone());
+
+		let source = sp_runtime::transaction_validity::TransactionSource::External;
+		let ext0 = Transfer {
+			from: AccountKeyring::Alice.into(),
+			to: AccountKeyring::Bob.into(),
+			amount: 5,
+			nonce: 0,
+		}
+		.into_unchecked_extrinsic();
+		block_on(pool.submit_one(client.info().genesis_hash, source, ext0)).unwrap();
+
+		let accounts = System::new(client, pool, DenyUnsafe::No);
+
+		// when
+		let first = accounts.reserve_nonces(AccountKeyring::Alice.into(), 3).await;
+		let second = accounts.reserve_nonces(AccountKeyring::Alice.into(), 2).await;
+		let other = accounts.reserve_nonces(AccountKeyring::Bob.into(), 1).await;
+
+		// then
+		assert_eq!(first.unwrap(), vec![1, 2, 3]);
+		assert_eq!(second.unwrap(), vec![4, 5]);
+		assert_eq!(other.unwrap(), vec![0]);
+		assert!(accounts.reserve_nonces(AccountKeyring::Alice.into(), 0).await.is_err());
+		assert!(accounts
+			.reserve_nonces(AccountKeyring::Alice.into(), MAX_RESERVED_NONCES + 1)
+			.await
+			.is_err());
+	}
+
+	#[tokio::test]
+	async fn expired_nonce_reservations_are_released() {
+		sp_tracing::try_init_simple();
+
+		// given
+		let client = Arc::new(substrate_test_runtime_client::new());
+		let spawner = sp_core::testing::TaskExecutor::new();
+		let pool =
+			BasicPool::new_full(Default::default(), true.into(), None, spawner, client.clone());
+
+		let accounts =
+			System::new(client, pool, DenyUnsafe::No).with_nonce_reservation_ttl(Duration::ZERO);
+
+		// when
+		let first = accounts.reserve_nonces(AccountKeyring::Alice.into(), 2).await;
+		let second = accounts.reserve_nonces(AccountKeyring::Alice.into(), 2).await;
+
+		// then
+		assert_eq!(first.unwrap(), vec![0, 1]);
+		assert_eq!(second.unwrap(), vec![0, 1]);
+	}
+
+	#[tokio::test]
+	async fn reserve_nonces_should_deny_unsafe() {
+		sp_tracing::try_init_simple();
+
+		// given
+		let client = Arc::new(substrate_test_runtime_client::new());
+		let spawner = sp_core::testing::TaskExecutor::new();
+		let pool =
+			BasicPool::new_full(Default::default(), true.into(), None, spawner, client.clone());
+
+		let accounts = System::new(client, pool, DenyUnsafe::Yes);
+
+		// when
+		let res = accounts.reserve_nonces(AccountKeyring::Alice.into(), 1).await;
+		assert_matches!(res, Err(e) => {
+			assert!(e.message().contains("RPC call is unsafe to be called externally"));
+		});
+	}
+
 	#[tokio::test]
 	async fn dry_run_should_deny_unsafe() {
 		sp_tracing::try_init_simple();
@@ -333,4 +545,47 @@ mod tests {
 		let apply_res: ApplyExtrinsicResult = Decode::decode(&mut bytes.as_ref()).unwrap();
 		assert_eq!(apply_res, Err(TransactionValidityError::Invalid(InvalidTransaction::Future)));
 	}
+	#[tokio::test]
+	async fn dry_run_pending_should_apply_pending_transactions_first() {
+		sp_tracing::try_init_simple();
+
+		// given
+		let client = Arc::new(substrate_test_runtime_client::new());
+		let spawner = sp_core::testing::TaskExecutor::new();
+		let pool =
+			BasicPool::new_full(Default::default(), true.into(), None, spawner, client.clone());
+
+		let source = sp_runtime::transaction_validity::TransactionSource::External;
+		let new_transaction = |nonce: u64| {
+			Transfer {
+				from: AccountKeyring::Alice.into(),
+				to: AccountKeyring::Bob.into(),
+				amount: 5,
+				nonce,
+			}
+			.into_unchecked_extrinsic()
+		};
+		block_on(pool.submit_one(client.info().genesis_hash, source, new_transaction(0))).unwrap();
+
+		let accounts = System::new(client, pool, DenyUnsafe::No);
+		let dry_run_pending = |nonce: u64| {
+			accounts.dry_run_pending(
+				new_transaction(nonce).encode().into(),
+				AccountKeyring::Alice.into(),
+			)
+		};
+
+		// when
+		let after_pending = dry_run_pending(1).await.expect("Call is successful");
+		let conflicting = dry_run_pending(0).await.expect("Call is successful");
+		let at_best = accounts.dry_run(new_transaction(1).encode().into(), None).await.unwrap();
+
+		// then
+		let apply_res: ApplyExtrinsicResult = Decode::decode(&mut after_pending.as_ref()).unwrap();
+		assert_eq!(apply_res, Ok(Ok(())));
+		let apply_res: ApplyExtrinsicResult = Decode::decode(&mut conflicting.as_ref()).unwrap();
+		assert_eq!(apply_res, Err(TransactionValidityError::Invalid(InvalidTransaction::Stale)));
+		let apply_res: ApplyExtrinsicResult = Decode::decode(&mut at_best.as_ref()).unwrap();
+		assert_eq!(apply_res, Err(TransactionValidityError::Invalid(InvalidTransaction::Future)));
+	}
 }
@@ -0,0 +1,264 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Build attestations, describing how a runtime blob was built such that the build can be
+//! reproduced and the result compared.
+
+use crate::{wasm_project::BuildConfiguration, CargoCommandVersioned};
+use blake2::{digest::consts::U32, Blake2b, Digest};
+use serde::{Deserialize, Serialize};
+use std::{
+	collections::BTreeMap,
+	env, fs,
+	path::{Path, PathBuf},
+	process::Command,
+};
+use walkdir::WalkDir;
+
+/// Version of the attestation format.
+const VERSION: u32 = 1;
+
+/// Environment variables influencing the build which are recorded in the attestation.
+const ATTESTED_ENV: &[&str] = &[
+	crate::WASM_BUILD_TYPE_ENV,
+	crate::WASM_BUILD_RUSTFLAGS_ENV,
+	crate::WASM_BUILD_TOOLCHAIN,
+	crate::WASM_BUILD_STD,
+	crate::RUNTIME_TARGET,
+	"RUSTC_BOOTSTRAP",
+];
+
+type Blake2b256 = Blake2b<U32>;
+
+/// Toolchain a runtime blob was built with.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Toolchain {
+	/// Output of `rustc --version`.
+	pub rustc: String,
+	/// Output of `cargo --version`.
+	pub cargo: String,
+	/// Version of the wasm builder.
+	pub wasm_builder: String,
+}
+
+impl Toolchain {
+	pub(crate) fn new(cargo_cmd: &CargoCommandVersioned) -> Self {
+		let cargo = cargo_cmd
+			.command()
+			.arg("--version")
+			.output()
+			.ok()
+			.and_then(|o| String::from_utf8(o.stdout).ok())
+			.unwrap_or_else(|| "unknown cargo version".into());
+
+		Self {
+			rustc: cargo_cmd.rustc_version().trim().into(),
+			cargo: cargo.trim().into(),
+			wasm_builder: env!("CARGO_PKG_VERSION").into(),
+		}
+	}
+}
+
+/// Attestation of a runtime blob build.
+///
+/// Written next to the blob as `<name>.attestation.json` and copied to `WASM_TARGET_DIRECTORY`
+/// along with the blob.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuildAttestation {
+	/// Version of the attestation format.
+	pub version: u32,
+	/// Name of the package the blob was built from.
+	pub package: String,
+	/// Path of the package manifest, relative to the workspace root.
+	pub manifest_path: PathBuf,
+	/// Profile of the outer build, as passed to `cargo --profile`.
+	pub outer_profile: String,
+	/// Profile the blob was built with.
+	pub blob_profile: String,
+	/// Features enabled for the outer build.
+	pub features: Vec<String>,
+	/// Environment variables influencing the build that were set.
+	pub env: BTreeMap<String, String>,
+	/// Toolchain the blob was built with.
+	pub toolchain: Toolchain,
+	/// Hash of the `Cargo.lock` and the sources of all local packages the blob was built from.
+	pub source_hash: String,
+	/// BLAKE2b-256 hash of the blob, i.e. the code hash once it's set on chain.
+	pub code_hash: String,
+}
+
+impl BuildAttestation {
+	/// Create the attestation of a build.
+	pub(crate) fn new(
+		cargo_manifest: &Path,
+		workspace_root: &Path,
+		features: Vec<String>,
+		build_config: &BuildConfiguration,
+		toolchain: Toolchain,
+		sources: &[PathBuf],
+		blob: &Path,
+	) -> Self {
+		let env = ATTESTED_ENV
+			.iter()
+			.filter_map(|name| env::var(name).ok().map(|value| (name.to_string(), value)))
+			.collect();
+
+		Self {
+			version: VERSION,
+			package: crate::wasm_project::get_crate_name(cargo_manifest),
+			manifest_path: relative_path(cargo_manifest, workspace_root),
+			outer_profile: build_config.outer_build_profile.name().into(),
+			blob_profile: build_config.blob_build_profile.name().into(),
+			features,
+			env,
+			toolchain,
+			source_hash: source_hash(sources, workspace_root),
+			code_hash: to_hex(&Blake2b256::digest(
+				fs::read(blob).expect("The blob was just written; qed"),
+			)),
+		}
+	}
+
+	/// Write the attestation next to the given blob, returns the path of the written file.
+	pub(crate) fn write(&self, blob: &crate::wasm_project::WasmBinaryBloaty) -> PathBuf {
+		let path = blob.bloaty_path().with_extension("attestation.json");
+		crate::write_file_if_changed(
+			&path,
+			serde_json::to_string_pretty(self).expect("Attestation is serializable; qed"),
+		);
+		path
+	}
+
+	/// Read an attestation from the given file.
+	pub fn read(path: &Path) -> Result<Self, String> {
+		let content = fs::read(path)
+			.map_err(|e| format!("Failed to read attestation `{}`: {e}", path.display()))?;
+		let attestation: Self = serde_json::from_slice(&content)
+			.map_err(|e| format!("Invalid attestation `{}`: {e}", path.display()))?;
+
+		if attestation.version != VERSION {
+			return Err(format!(
+				"Unsupported attestation version {}, expected {VERSION}",
+				attestation.version,
+			))
+		}
+
+		Ok(attestation)
+	}
+}
+
+/// Reproduce the build described by the attestation and compare the result.
+///
+/// The build is done from the workspace of the given `Cargo.toml`, in a fresh target directory.
+/// Returns the attestation of the reproduced build if it resulted in the same code.
+pub fn verify_attestation(
+	attestation: &Path,
+	manifest_path: &Path,
+) -> Result<BuildAttestation, String> {
+	let expected = BuildAttestation::read(attestation)?;
+	let target_dir = tempfile::tempdir()
+		.map_err(|e| format!("Failed to create a temporary target directory: {e}"))?;
+
+	let mut build_cmd = Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".into()));
+	build_cmd
+		.arg("build")
+		.arg("--locked")
+		.arg(format!("--manifest-path={}", manifest_path.display()))
+		.arg(format!("--package={}", expected.package))
+		.arg(format!("--profile={}", expected.outer_profile))
+		.env("CARGO_TARGET_DIR", target_dir.path())
+		.env_remove(crate::SKIP_BUILD_ENV)
+		.env_remove(crate::WASM_TARGET_DIRECTORY);
+	if !expected.features.is_empty() {
+		build_cmd.arg(format!("--features={}", expected.features.join(",")));
+	}
+	// Only the settings recorded in the attestation may influence the build.
+	for name in ATTESTED_ENV {
+		build_cmd.env_remove(name);
+	}
+	build_cmd.envs(&expected.env);
+
+	if !build_cmd.status().map_or(false, |s| s.success()) {
+		return Err(format!("Failed to build `{}`", expected.package))
+	}
+
+	let reproduced = WalkDir::new(target_dir.path())
+		.into_iter()
+		.filter_map(Result::ok)
+		.filter(|e| e.file_name().to_string_lossy().ends_with(".attestation.json"))
+		.filter_map(|e| BuildAttestation::read(e.path()).ok())
+		.find(|a| a.package == expected.package)
+		.ok_or_else(|| format!("The build of `{}` produced no attestation", expected.package))?;
+
+	if reproduced.source_hash != expected.source_hash {
+		return Err(format!(
+			"The sources differ from the attested ones: expected source hash {}, got {}",
+			expected.source_hash, reproduced.source_hash,
+		))
+	}
+
+	if reproduced.code_hash != expected.code_hash {
+		let mut error = format!(
+			"The reproduced code differs: expected code hash {}, got {}",
+			expected.code_hash, reproduced.code_hash,
+		);
+		if reproduced.toolchain != expected.toolchain {
+			error.push_str(&format!(
+				"\nThe toolchain differs: expected {:?}, got {:?}",
+				expected.toolchain, reproduced.toolchain,
+			));
+		}
+		return Err(error)
+	}
+
+	Ok(reproduced)
+}
+
+/// Hash the given files along with their paths relative to the workspace root.
+fn source_hash(sources: &[PathBuf], workspace_root: &Path) -> String {
+	let mut sources = sources
+		.iter()
+		.map(|path| (relative_path(path, workspace_root), path))
+		.collect::<Vec<_>>();
+	sources.sort();
+	sources.dedup();
+
+	let mut hasher = Blake2b256::new();
+	for (relative, path) in sources {
+		let content = fs::read(path).unwrap_or_default();
+		hasher.update(relative.to_string_lossy().as_bytes());
+		hasher.update([0u8]);
+		hasher.update((content.len() as u64).to_le_bytes());
+		hasher.update(content);
+	}
+
+	to_hex(&hasher.finalize())
+}
+
+/// The path relative to the workspace root, or the path itself if it's outside of it.
+fn relative_path(path: &Path, workspace_root: &Path) -> PathBuf {
+	path.strip_prefix(workspace_root).unwrap_or(path).to_path_buf()
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+	let mut hex = String::with_capacity(2 + bytes.len() * 2);
+	hex.push_str("0x");
+	for byte in bytes {
+		hex.push_str(&format!("{byte:02x}"));
+	}
+	hex
+}
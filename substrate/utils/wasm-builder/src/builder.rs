@@ -21,7 +21,7 @@ use std::{
 	process,
 };
 
-use crate::RuntimeTarget;
+use crate::{wasm_project::PostProcessing, RuntimeTarget};
 
 /// Returns the manifest dir from the `CARGO_MANIFEST_DIR` env.
 fn get_manifest_dir() -> PathBuf {
@@ -53,6 +53,8 @@ impl WasmBuilderSelectProject {
 			disable_runtime_version_section_check: false,
 			export_heap_base: false,
 			import_memory: false,
+			wasm_opt_passes: Vec::new(),
+			max_compressed_size: None,
 		}
 	}
 
@@ -71,6 +73,8 @@ impl WasmBuilderSelectProject {
 				disable_runtime_version_section_check: false,
 				export_heap_base: false,
 				import_memory: false,
+				wasm_opt_passes: Vec::new(),
+				max_compressed_size: None,
 			})
 		} else {
 			Err("Project path must point to the `Cargo.toml` of the project")
@@ -108,6 +112,10 @@ pub struct WasmBuilder {
 	export_heap_base: bool,
 	/// Whether `--import-memory` should be added to the link args (WASM-only).
 	import_memory: bool,
+	/// Additional `wasm-opt` passes run when compacting the WASM binary.
+	wasm_opt_passes: Vec<wasm_opt::Pass>,
+	/// Maximum size in bytes of the compressed WASM binary.
+	max_compressed_size: Option<usize>,
 }
 
 impl WasmBuilder {
@@ -169,6 +177,24 @@ impl WasmBuilder {
 		self
 	}
 
+	/// Run the given `wasm-opt` pass when compacting the WASM binary.
+	///
+	/// The passes are run in the order they are added, after stripping the DWARF debug info.
+	/// They are only run when the binary is compacted, i.e. not for `dev` builds.
+	pub fn add_wasm_opt_pass(mut self, pass: wasm_opt::Pass) -> Self {
+		self.wasm_opt_passes.push(pass);
+		self
+	}
+
+	/// Fail the build if the compressed WASM binary is larger than `max_size` bytes.
+	///
+	/// A report of the code size per crate, e.g. per pallet, is printed when the budget is
+	/// exceeded. The size is only checked when the binary is compacted, i.e. not for `dev` builds.
+	pub fn max_compressed_size(mut self, max_size: usize) -> Self {
+		self.max_compressed_size = Some(max_size);
+		self
+	}
+
 	/// Build the WASM binary.
 	pub fn build(mut self) {
 		let target = crate::runtime_target();
@@ -204,6 +230,10 @@ impl WasmBuilder {
 			self.features_to_enable,
 			self.file_name,
 			!self.disable_runtime_version_section_check,
+			PostProcessing {
+				wasm_opt_passes: self.wasm_opt_passes,
+				max_compressed_size: self.max_compressed_size,
+			},
 		);
 
 		// As last step we need to generate our `rerun-if-changed` stuff. If a build fails, we don't
@@ -270,6 +300,8 @@ fn generate_rerun_if_changed_instructions() {
 ///
 /// `check_for_runtime_version_section` - Should the wasm binary be checked for the
 /// `runtime_version` section?
+///
+/// `post_processing` - How the compact wasm binary should be optimized and checked.
 fn build_project(
 	target: RuntimeTarget,
 	file_name: PathBuf,
@@ -278,6 +310,7 @@ fn build_project(
 	features_to_enable: Vec<String>,
 	wasm_binary_name: Option<String>,
 	check_for_runtime_version_section: bool,
+	post_processing: PostProcessing,
 ) {
 	let cargo_cmd = match crate::prerequisites::check(target) {
 		Ok(cmd) => cmd,
@@ -295,6 +328,7 @@ fn build_project(
 		features_to_enable,
 		wasm_binary_name,
 		check_for_runtime_version_section,
+		post_processing,
 	);
 
 	let (wasm_binary, wasm_binary_bloaty) = if let Some(wasm_binary) = wasm_binary {
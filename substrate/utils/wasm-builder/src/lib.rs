@@ -97,6 +97,39 @@
 //! `SKIP_PROJECT_NAME_WASM_BUILD`. Where `PROJECT_NAME` needs to be replaced by the name of the
 //! cargo project, e.g. `kitchensink-runtime` will be `NODE_RUNTIME`.
 //!
+//! ## Post-processing and size budget
+//!
+//! Additional `wasm-opt` passes can be run when compacting the Wasm binary with
+//! [`WasmBuilder::add_wasm_opt_pass`], and [`WasmBuilder::max_compressed_size`] fails the build
+//! when the compressed Wasm binary exceeds the given size, printing the code size per crate:
+//!
+//! ```no_run
+//! use substrate_wasm_builder::{WasmBuilder, WasmOptPass};
+//!
+//! fn main() {
+//!     WasmBuilder::new()
+//!         .with_current_project()
+//!         .export_heap_base()
+//!         .import_memory()
+//!         .add_wasm_opt_pass(WasmOptPass::Vacuum)
+//!         // Stay well below the code size limit of the relay chain.
+//!         .max_compressed_size(2 * 1024 * 1024)
+//!         .build()
+//! }
+//! ```
+//!
+//! ## Build attestations
+//!
+//! Next to every built blob, a `<name>.attestation.json` file is written (and copied to
+//! `WASM_TARGET_DIRECTORY` if set) which records the toolchain, the build settings, a hash of the
+//! sources and the code hash of the resulting blob. Anyone with the same sources can reproduce the
+//! build and compare the result, e.g. to check that a proposed runtime upgrade matches the
+//! published source code:
+//!
+//! ```sh
+//! substrate-wasm-builder verify path/to/runtime.attestation.json --manifest-path Cargo.toml
+//! ```
+//!
 //! ## Prerequisites:
 //!
 //! Wasm builder requires the following prerequisites for building the Wasm binary:
@@ -121,12 +154,16 @@ use std::{
 };
 use version::Version;
 
+mod attestation;
 mod builder;
 mod prerequisites;
+mod size_report;
 mod version;
 mod wasm_project;
 
+pub use attestation::{verify_attestation, BuildAttestation, Toolchain};
 pub use builder::{WasmBuilder, WasmBuilderSelectProject};
+pub use wasm_opt::Pass as WasmOptPass;
 
 /// Environment variable that tells us to skip building the wasm binary.
 const SKIP_BUILD_ENV: &str = "SKIP_WASM_BUILD";
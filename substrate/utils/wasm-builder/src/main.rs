@@ -0,0 +1,63 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Command line tool to verify runtime build attestations.
+
+use std::{path::PathBuf, process};
+
+const USAGE: &str =
+	"Usage: substrate-wasm-builder verify <ATTESTATION> [--manifest-path <CARGO_TOML>]";
+
+fn main() {
+	let mut args = std::env::args().skip(1);
+	if args.next().as_deref() != Some("verify") {
+		eprintln!("{USAGE}");
+		process::exit(2);
+	}
+
+	let mut attestation = None;
+	let mut manifest_path = PathBuf::from("Cargo.toml");
+	while let Some(arg) = args.next() {
+		match arg.as_str() {
+			"--manifest-path" => match args.next() {
+				Some(path) => manifest_path = path.into(),
+				None => {
+					eprintln!("{USAGE}");
+					process::exit(2);
+				},
+			},
+			_ if attestation.is_none() => attestation = Some(PathBuf::from(arg)),
+			_ => {
+				eprintln!("{USAGE}");
+				process::exit(2);
+			},
+		}
+	}
+	let Some(attestation) = attestation else {
+		eprintln!("{USAGE}");
+		process::exit(2);
+	};
+
+	match substrate_wasm_builder::verify_attestation(&attestation, &manifest_path) {
+		Ok(reproduced) =>
+			println!("Reproduced `{}` with code hash {}", reproduced.package, reproduced.code_hash),
+		Err(e) => {
+			eprintln!("{e}");
+			process::exit(1);
+		},
+	}
+}
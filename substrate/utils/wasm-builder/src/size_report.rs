@@ -0,0 +1,172 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Size budget of the runtime blob and the report of the crates its code comes from.
+
+use parity_wasm::elements::{deserialize_buffer, ImportCountType, Module, Serialize};
+use std::{collections::BTreeMap, fmt, fs, path::Path, process};
+
+/// Number of crates listed in the report, the code of the others is summed up.
+const REPORTED_CRATES: usize = 25;
+
+/// The code of functions without a name or not belonging to a crate is reported under this name.
+const OTHER: &str = "<other>";
+
+/// Size of the code of a blob per crate.
+pub(crate) struct SizeReport {
+	/// Size of the function bodies per crate, largest first.
+	crates: Vec<(String, usize)>,
+	/// Size of all the function bodies.
+	total: usize,
+}
+
+impl SizeReport {
+	/// Create the report of the given blob.
+	///
+	/// The functions are attributed to crates using the `name` section, which is kept in the
+	/// compact blob unless stripped by one of the configured `wasm-opt` passes.
+	pub(crate) fn new(blob: &[u8]) -> Result<Self, String> {
+		let module = deserialize_buffer::<Module>(blob).map_err(|e| e.to_string())?;
+		let module = module.parse_names().unwrap_or_else(|(_, module)| module);
+		let imported_functions = module.import_count(ImportCountType::Function);
+		let names = module.names_section().and_then(|names| names.functions());
+		let bodies = module.code_section().map_or(&[][..], |code| code.bodies());
+
+		let mut sizes = BTreeMap::<&str, usize>::new();
+		for (index, body) in bodies.iter().enumerate() {
+			let mut code = Vec::new();
+			body.clone().serialize(&mut code).map_err(|e| e.to_string())?;
+
+			let crate_name = names
+				.and_then(|names| names.names().get((imported_functions + index) as u32))
+				.and_then(|name| crate_name(name))
+				.unwrap_or(OTHER);
+			*sizes.entry(crate_name).or_default() += code.len();
+		}
+
+		let total = sizes.values().sum();
+		let mut crates = sizes
+			.into_iter()
+			.map(|(name, size)| (name.to_string(), size))
+			.collect::<Vec<_>>();
+		crates.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+		Ok(Self { crates, total })
+	}
+}
+
+impl fmt::Display for SizeReport {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let line = |f: &mut fmt::Formatter, size: usize, name: &str| {
+			let share = if self.total == 0 { 0.0 } else { size as f64 * 100.0 / self.total as f64 };
+			writeln!(f, "{:>12.2} KiB {share:>6.2}%  {name}", size as f64 / 1024.0)
+		};
+
+		writeln!(f, "Uncompressed code size per crate:")?;
+		for (name, size) in self.crates.iter().take(REPORTED_CRATES) {
+			line(f, *size, name)?;
+		}
+		if self.crates.len() > REPORTED_CRATES {
+			let others = &self.crates[REPORTED_CRATES..];
+			let size = others.iter().map(|(_, size)| size).sum();
+			line(f, size, &format!("{} other crates", others.len()))?;
+		}
+		line(f, self.total, "total")
+	}
+}
+
+/// The crate the function with the given name belongs to.
+///
+/// Both demangled names and names using the legacy Rust mangling are supported. For trait
+/// implementations, the crate of the implementing type is returned.
+fn crate_name(name: &str) -> Option<&str> {
+	let (mut path, mangled) = match name.strip_prefix("_ZN") {
+		Some(mangled) => {
+			// The path starts with the length of its first segment.
+			let digits = mangled.bytes().take_while(u8::is_ascii_digit).count();
+			let len = mangled[..digits].parse::<usize>().ok()?;
+			(mangled.get(digits..digits + len)?, true)
+		},
+		None => (name, false),
+	};
+
+	while let Some(rest) = ["_$LT$", "$LT$", "<", "$RF$", "&", "*const ", "*mut ", "dyn "]
+		.iter()
+		.find_map(|prefix| path.strip_prefix(prefix))
+	{
+		path = rest;
+	}
+
+	// The first segment of a mangled path is the crate itself, unless it is a trait implementation.
+	let end = ["::", "..", "$", "<", " "]
+		.iter()
+		.filter_map(|sep| path.find(sep))
+		.min()
+		.or(mangled.then_some(path.len()))?;
+	let crate_name = &path[..end];
+	(!crate_name.is_empty() && crate_name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'))
+		.then_some(crate_name)
+}
+
+/// Fail the build if `blob` is larger than `max_size` bytes.
+///
+/// The size report of `compact_blob`, i.e. the blob before compression, is printed to help
+/// finding what is taking up the space.
+pub(crate) fn check_size_budget(blob: &Path, compact_blob: &Path, max_size: usize) {
+	let size = fs::metadata(blob).expect("The blob was just written; qed").len() as usize;
+	if size <= max_size {
+		return
+	}
+
+	// We use println! + exit instead of a panic in order to have a cleaner output.
+	println!(
+		"The runtime blob `{}` is {size} bytes, exceeding its size budget of {max_size} bytes.",
+		blob.display(),
+	);
+	match SizeReport::new(&fs::read(compact_blob).expect("The blob was just written; qed")) {
+		Ok(report) => println!("{report}"),
+		Err(e) => println!("Failed to create the size report of the blob: {e}"),
+	}
+	process::exit(1);
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn crate_name_works() {
+		assert_eq!(
+			crate_name("pallet_balances::pallet::Pallet<T,I>::transfer::h0123456789abcdef"),
+			Some("pallet_balances"),
+		);
+		assert_eq!(
+			crate_name("<pallet_xcm::pallet::Pallet<T> as frame_support::traits::Hooks>::on_idle"),
+			Some("pallet_xcm"),
+		);
+		assert_eq!(
+			crate_name("_ZN15pallet_balances6pallet6Pallet8transfer17h0123456789abcdefE"),
+			Some("pallet_balances"),
+		);
+		assert_eq!(
+			crate_name("_ZN62_$LT$sp_runtime..generic..Header$LT$N$GT$$u20$as$u20$Clone$GT$5clone17h0123456789abcdefE"),
+			Some("sp_runtime"),
+		);
+		assert_eq!(crate_name("memcpy"), None);
+		assert_eq!(crate_name("_ZN99tooshort"), None);
+	}
+}
@@ -15,7 +15,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{write_file_if_changed, CargoCommandVersioned, RuntimeTarget, OFFLINE};
+use crate::{
+	attestation, size_report, write_file_if_changed, CargoCommandVersioned, RuntimeTarget, OFFLINE,
+};
 
 use build_helper::rerun_if_changed;
 use cargo_metadata::{DependencyKind, Metadata, MetadataCommand};
@@ -106,6 +108,15 @@ fn crate_metadata(cargo_manifest: &Path) -> Metadata {
 	crate_metadata
 }
 
+/// Post-processing of the compact WASM binary.
+#[derive(Debug, Default)]
+pub(crate) struct PostProcessing {
+	/// Additional `wasm-opt` passes run when compacting the binary.
+	pub wasm_opt_passes: Vec<wasm_opt::Pass>,
+	/// Maximum size in bytes of the compressed binary.
+	pub max_compressed_size: Option<usize>,
+}
+
 /// Creates the WASM project, compiles the WASM binary and compacts the WASM binary.
 ///
 /// # Returns
@@ -119,6 +130,7 @@ pub(crate) fn create_and_compile(
 	features_to_enable: Vec<String>,
 	bloaty_blob_out_name_override: Option<String>,
 	check_for_runtime_version_section: bool,
+	post_processing: PostProcessing,
 ) -> (Option<WasmBinary>, WasmBinaryBloaty) {
 	let runtime_workspace_root = get_wasm_workspace_root();
 	let runtime_workspace = runtime_workspace_root.join(target.build_subdirectory());
@@ -135,6 +147,7 @@ pub(crate) fn create_and_compile(
 	);
 
 	let build_config = BuildConfiguration::detect(target, &project);
+	let toolchain = attestation::Toolchain::new(&cargo_cmd);
 
 	// Build the bloaty runtime blob
 	let raw_blob_path = build_bloaty_blob(
@@ -152,6 +165,7 @@ pub(crate) fn create_and_compile(
 			bloaty_blob_out_name_override,
 			check_for_runtime_version_section,
 			&build_config,
+			&post_processing,
 		),
 		RuntimeTarget::Riscv => {
 			let out_name = bloaty_blob_out_name_override
@@ -162,14 +176,41 @@ pub(crate) fn create_and_compile(
 		},
 	};
 
+	let metadata = create_metadata_command(project.join("Cargo.toml"))
+		.exec()
+		.expect("`cargo metadata` can not fail!");
+	let packages = dependency_packages(&metadata, project_cargo_toml, &runtime_workspace);
+
 	generate_rerun_if_changed_instructions(
 		project_cargo_toml,
-		&project,
-		&runtime_workspace,
+		&packages,
 		final_blob_binary.as_ref(),
 		&bloaty_blob_binary,
 	);
 
+	let attestation = attestation::BuildAttestation::new(
+		project_cargo_toml,
+		crate_metadata.workspace_root.as_ref(),
+		project_enabled_features(
+			&get_crate_name(project_cargo_toml),
+			project_cargo_toml,
+			&crate_metadata,
+		),
+		&build_config,
+		toolchain,
+		&attested_sources(project_cargo_toml, &packages),
+		final_blob_binary
+			.as_ref()
+			.map_or(bloaty_blob_binary.bloaty_path(), |b| b.wasm_binary_path()),
+	);
+	let attestation_path = attestation.write(&bloaty_blob_binary);
+	if target == RuntimeTarget::Wasm {
+		copy_file_to_target_directory(
+			&attestation_path,
+			format!("{}.attestation.json", get_blob_name(target, project_cargo_toml)),
+		);
+	}
+
 	if let Err(err) = adjust_mtime(&bloaty_blob_binary, final_blob_binary.as_ref()) {
 		build_helper::warning!("Error while adjusting the mtime of the blob binaries: {}", err)
 	}
@@ -183,6 +224,7 @@ fn compile_wasm(
 	bloaty_blob_out_name_override: Option<String>,
 	check_for_runtime_version_section: bool,
 	build_config: &BuildConfiguration,
+	post_processing: &PostProcessing,
 ) -> (Option<WasmBinary>, WasmBinaryBloaty) {
 	// Get the name of the bloaty runtime blob.
 	let bloaty_blob_default_name = get_blob_name(RuntimeTarget::Wasm, project_cargo_toml);
@@ -208,6 +250,7 @@ fn compile_wasm(
 				&build_config.blob_build_profile,
 				project_cargo_toml,
 				&bloaty_blob_out_name,
+				&post_processing.wasm_opt_passes,
 			);
 			let compact_compressed_blob_path = compact_blob_path
 				.as_ref()
@@ -221,6 +264,17 @@ fn compile_wasm(
 		ensure_runtime_version_wasm_section_exists(bloaty_blob_binary.bloaty_path());
 	}
 
+	if let (Some(max_size), Some(compact_blob)) =
+		(post_processing.max_compressed_size, compact_blob_path.as_ref())
+	{
+		let blob = compact_compressed_blob_path.as_ref().unwrap_or(compact_blob);
+		size_report::check_size_budget(
+			blob.wasm_binary_path(),
+			compact_blob.wasm_binary_path(),
+			max_size,
+		);
+	}
+
 	compact_blob_path
 		.as_ref()
 		.map(|wasm_binary| copy_blob_to_target_directory(project_cargo_toml, wasm_binary));
@@ -329,7 +383,7 @@ fn find_cargo_lock(cargo_manifest: &Path) -> Option<PathBuf> {
 }
 
 /// Extract the crate name from the given `Cargo.toml`.
-fn get_crate_name(cargo_manifest: &Path) -> String {
+pub(crate) fn get_crate_name(cargo_manifest: &Path) -> String {
 	let cargo_toml: Table = toml::from_str(
 		&fs::read_to_string(cargo_manifest).expect("File exists as checked before; qed"),
 	)
@@ -639,7 +693,7 @@ fn create_project(
 
 /// A rustc profile.
 #[derive(Clone, Debug, EnumIter)]
-enum Profile {
+pub(crate) enum Profile {
 	/// The `--profile dev` profile.
 	Debug,
 	/// The `--profile release` profile.
@@ -650,7 +704,7 @@ enum Profile {
 
 impl Profile {
 	/// The name of the profile as supplied to the cargo `--profile` cli option.
-	fn name(&self) -> &'static str {
+	pub(crate) fn name(&self) -> &'static str {
 		match self {
 			Self::Debug => "dev",
 			Self::Release => "release",
@@ -679,7 +733,7 @@ impl Profile {
 
 /// The build configuration for this build.
 #[derive(Debug)]
-struct BuildConfiguration {
+pub(crate) struct BuildConfiguration {
 	/// The profile that is used to build the outer project.
 	pub outer_build_profile: Profile,
 	/// The profile to use to build the runtime blob.
@@ -911,6 +965,7 @@ fn compact_wasm(
 	inner_profile: &Profile,
 	cargo_manifest: &Path,
 	out_name: &str,
+	wasm_opt_passes: &[wasm_opt::Pass],
 ) -> Option<WasmBinary> {
 	let default_out_name = get_blob_name(RuntimeTarget::Wasm, cargo_manifest);
 	let in_path = project
@@ -920,10 +975,15 @@ fn compact_wasm(
 
 	let wasm_compact_path = project.join(format!("{}.compact.wasm", out_name));
 	let start = std::time::Instant::now();
-	wasm_opt::OptimizationOptions::new_opt_level_0()
+	let mut options = wasm_opt::OptimizationOptions::new_opt_level_0();
+	options
 		.mvp_features_only()
 		.debug_info(true)
-		.add_pass(wasm_opt::Pass::StripDwarf)
+		.add_pass(wasm_opt::Pass::StripDwarf);
+	for pass in wasm_opt_passes {
+		options.add_pass(pass.clone());
+	}
+	options
 		.run(&in_path, &wasm_compact_path)
 		.expect("Failed to compact generated WASM binary.");
 	println!(
@@ -1028,24 +1088,12 @@ fn create_metadata_command(path: impl Into<PathBuf>) -> MetadataCommand {
 	metadata_command
 }
 
-/// Generate the `rerun-if-changed` instructions for cargo to make sure that the WASM binary is
-/// rebuilt when needed.
-fn generate_rerun_if_changed_instructions(
+/// Collect the crate we want to compile for wasm and all the packages it depends on.
+fn dependency_packages<'a>(
+	metadata: &'a Metadata,
 	cargo_manifest: &Path,
-	project_folder: &Path,
 	wasm_workspace: &Path,
-	compressed_or_compact_wasm: Option<&WasmBinary>,
-	bloaty_wasm: &WasmBinaryBloaty,
-) {
-	// Rerun `build.rs` if the `Cargo.lock` changes
-	if let Some(cargo_lock) = find_cargo_lock(cargo_manifest) {
-		rerun_if_changed(cargo_lock);
-	}
-
-	let metadata = create_metadata_command(project_folder.join("Cargo.toml"))
-		.exec()
-		.expect("`cargo metadata` can not fail!");
-
+) -> HashSet<DeduplicatePackage<'a>> {
 	let package = metadata
 		.packages
 		.iter()
@@ -1086,8 +1134,24 @@ fn generate_rerun_if_changed_instructions(
 		}
 	}
 
+	packages
+}
+
+/// Generate the `rerun-if-changed` instructions for cargo to make sure that the WASM binary is
+/// rebuilt when needed.
+fn generate_rerun_if_changed_instructions(
+	cargo_manifest: &Path,
+	packages: &HashSet<DeduplicatePackage>,
+	compressed_or_compact_wasm: Option<&WasmBinary>,
+	bloaty_wasm: &WasmBinaryBloaty,
+) {
+	// Rerun `build.rs` if the `Cargo.lock` changes
+	if let Some(cargo_lock) = find_cargo_lock(cargo_manifest) {
+		rerun_if_changed(cargo_lock);
+	}
+
 	// Make sure that if any file/folder of a dependency change, we need to rerun the `build.rs`
-	packages.iter().for_each(package_rerun_if_changed);
+	packages.iter().flat_map(package_files).for_each(rerun_if_changed);
 
 	compressed_or_compact_wasm.map(|w| rerun_if_changed(w.wasm_binary_path()));
 	rerun_if_changed(bloaty_wasm.bloaty_path());
@@ -1102,8 +1166,8 @@ fn generate_rerun_if_changed_instructions(
 	println!("cargo:rerun-if-env-changed={}", crate::RUNTIME_TARGET);
 }
 
-/// Track files and paths related to the given package to rerun `build.rs` on any relevant change.
-fn package_rerun_if_changed(package: &DeduplicatePackage) {
+/// The files of the given package that are relevant for the build.
+fn package_files(package: &DeduplicatePackage) -> Vec<PathBuf> {
 	let mut manifest_path = package.manifest_path.clone();
 	if manifest_path.ends_with("Cargo.toml") {
 		manifest_path.pop();
@@ -1120,12 +1184,35 @@ fn package_rerun_if_changed(package: &DeduplicatePackage) {
 		})
 		.filter_map(|p| p.ok().map(|p| p.into_path()))
 		.filter(|p| p.extension().map(|e| e == "rs" || e == "toml").unwrap_or_default())
-		.for_each(rerun_if_changed);
+		.collect()
+}
+
+/// The files the sources of the build are attested with.
+///
+/// Packages from a registry or a git repository are pinned by the `Cargo.lock`, so only the
+/// files of local packages are included.
+fn attested_sources(cargo_manifest: &Path, packages: &HashSet<DeduplicatePackage>) -> Vec<PathBuf> {
+	let mut sources = packages
+		.iter()
+		.filter(|package| package.source.is_none())
+		.flat_map(package_files)
+		.collect::<Vec<_>>();
+	sources.extend(find_cargo_lock(cargo_manifest));
+	sources
 }
 
 /// Copy the blob binary to the target directory set in `WASM_TARGET_DIRECTORY` environment
 /// variable. If the variable is not set, this is a no-op.
 fn copy_blob_to_target_directory(cargo_manifest: &Path, blob_binary: &WasmBinary) {
+	copy_file_to_target_directory(
+		blob_binary.wasm_binary_path(),
+		format!("{}.wasm", get_blob_name(RuntimeTarget::Wasm, cargo_manifest)),
+	);
+}
+
+/// Copy the file to the target directory set in `WASM_TARGET_DIRECTORY` environment variable,
+/// under the given name. If the variable is not set, this is a no-op.
+fn copy_file_to_target_directory(file: &Path, name: String) {
 	let target_dir = match env::var(crate::WASM_TARGET_DIRECTORY) {
 		Ok(path) => PathBuf::from(path),
 		Err(_) => return,
@@ -1143,9 +1230,5 @@ fn copy_blob_to_target_directory(cargo_manifest: &Path, blob_binary: &WasmBinary
 
 	fs::create_dir_all(&target_dir).expect("Creates `WASM_TARGET_DIRECTORY`.");
 
-	fs::copy(
-		blob_binary.wasm_binary_path(),
-		target_dir.join(format!("{}.wasm", get_blob_name(RuntimeTarget::Wasm, cargo_manifest))),
-	)
-	.expect("Copies blob binary to `WASM_TARGET_DIRECTORY`.");
+	fs::copy(file, target_dir.join(name)).expect("Copies file to `WASM_TARGET_DIRECTORY`.");
 }